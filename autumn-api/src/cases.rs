@@ -0,0 +1,107 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use autumn_database::impls::cases::{
+    CaseFilters, get_case_by_label, get_case_events, list_recent_cases,
+};
+use autumn_database::model::cases::{CaseEvent, CaseSummary, ModerationCase};
+use autumn_utils::formatting::{format_case_label, parse_case_label};
+
+use crate::ApiState;
+use crate::error::{ApiError, ApiResult};
+
+const DEFAULT_CASE_LIMIT: u32 = 50;
+
+#[derive(Deserialize)]
+pub(crate) struct CaseQuery {
+    user_id: Option<u64>,
+    moderator_id: Option<u64>,
+    action: Option<String>,
+    limit: Option<u32>,
+}
+
+/// `GET /guilds/{guild_id}/cases` — newest first, at most 200.
+pub(crate) async fn list_cases(
+    State(state): State<ApiState>,
+    Path(guild_id): Path<u64>,
+    Query(query): Query<CaseQuery>,
+) -> ApiResult<Json<Value>> {
+    let cases = list_recent_cases(
+        &state.db,
+        guild_id,
+        CaseFilters {
+            target_user_id: query.user_id,
+            moderator_user_id: query.moderator_id,
+            action: query.action.as_deref(),
+            limit: query.limit.unwrap_or(DEFAULT_CASE_LIMIT),
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "cases": cases.iter().map(case_summary_json).collect::<Vec<_>>(),
+    })))
+}
+
+/// `GET /guilds/{guild_id}/cases/{label}` — a case with its event history.
+pub(crate) async fn get_case(
+    State(state): State<ApiState>,
+    Path((guild_id, label)): Path<(u64, String)>,
+) -> ApiResult<Json<Value>> {
+    let Some((case_code, number)) = parse_case_label(&label) else {
+        return Err(ApiError::BadRequest(format!(
+            "invalid case label `{label}`"
+        )));
+    };
+
+    let Some(case) = get_case_by_label(&state.db, guild_id, &case_code, number).await? else {
+        return Err(ApiError::NotFound);
+    };
+    let events = get_case_events(&state.db, guild_id, &case_code, number).await?;
+
+    Ok(Json(json!({
+        "case": moderation_case_json(&case),
+        "events": events.iter().map(case_event_json).collect::<Vec<_>>(),
+    })))
+}
+
+fn case_summary_json(case: &CaseSummary) -> Value {
+    json!({
+        "label": format_case_label(&case.case_code, case.action_case_number),
+        "case_number": case.case_number,
+        "action": case.action,
+        "target_user_id": case.target_user_id.map(|id| id.to_string()),
+        "moderator_user_id": case.moderator_user_id.to_string(),
+        "reason": case.reason,
+        "duration_seconds": case.duration_seconds,
+        "created_at": case.created_at,
+    })
+}
+
+fn moderation_case_json(case: &ModerationCase) -> Value {
+    json!({
+        "label": format_case_label(&case.case_code, case.action_case_number),
+        "case_number": case.case_number,
+        "action": case.action,
+        "status": case.status,
+        "target_user_id": case.target_user_id.map(|id| id.to_string()),
+        "moderator_user_id": case.moderator_user_id.to_string(),
+        "reason": case.reason,
+        "duration_seconds": case.duration_seconds,
+        "created_at": case.created_at,
+        "updated_at": case.updated_at,
+    })
+}
+
+fn case_event_json(event: &CaseEvent) -> Value {
+    json!({
+        "event_type": event.event_type,
+        "actor_user_id": event.actor_user_id.to_string(),
+        "old_reason": event.old_reason,
+        "new_reason": event.new_reason,
+        "note": event.note,
+        "created_at": event.created_at,
+    })
+}
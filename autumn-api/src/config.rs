@@ -0,0 +1,126 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use serde::{Deserialize, Deserializer};
+use serde_json::{Value, json};
+
+use autumn_database::impls::ai_config::{get_llm_enabled, set_llm_enabled};
+use autumn_database::impls::modlog_config::{
+    clear_modlog_channel_id, get_modlog_channel_id, get_purge_transcripts_enabled,
+    set_modlog_channel_id, set_purge_transcripts_enabled,
+};
+use autumn_database::impls::serverlog_config::{
+    clear_serverlog_channel_id, get_serverlog_channel_id, set_serverlog_channel_id,
+};
+use autumn_database::impls::userlog_config::{
+    clear_userlog_channel_id, get_userlog_channel_id, set_userlog_channel_id,
+};
+
+use crate::ApiState;
+use crate::error::{ApiError, ApiResult};
+
+/// Fields accepted by `PATCH /guilds/{guild_id}/config`. Omitted fields are
+/// left alone; a `null` channel clears it.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ConfigPatch {
+    #[serde(default, deserialize_with = "present")]
+    modlog_channel_id: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    userlog_channel_id: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    serverlog_channel_id: Option<Option<String>>,
+    ai_enabled: Option<bool>,
+    purge_transcripts: Option<bool>,
+}
+
+/// `GET /guilds/{guild_id}/config`
+pub(crate) async fn get_config(
+    State(state): State<ApiState>,
+    Path(guild_id): Path<u64>,
+) -> ApiResult<Json<Value>> {
+    Ok(Json(config_json(&state, guild_id).await?))
+}
+
+/// `PATCH /guilds/{guild_id}/config` — returns the updated config.
+pub(crate) async fn update_config(
+    State(state): State<ApiState>,
+    Path(guild_id): Path<u64>,
+    Json(patch): Json<ConfigPatch>,
+) -> ApiResult<Json<Value>> {
+    let db = &state.db;
+
+    // Validate every channel before writing anything.
+    let modlog = parse_channel_field("modlog_channel_id", patch.modlog_channel_id)?;
+    let userlog = parse_channel_field("userlog_channel_id", patch.userlog_channel_id)?;
+    let serverlog = parse_channel_field("serverlog_channel_id", patch.serverlog_channel_id)?;
+
+    match modlog {
+        Some(Some(channel_id)) => set_modlog_channel_id(db, guild_id, channel_id).await?,
+        Some(None) => clear_modlog_channel_id(db, guild_id).await?,
+        None => {}
+    }
+    match userlog {
+        Some(Some(channel_id)) => set_userlog_channel_id(db, guild_id, channel_id).await?,
+        Some(None) => clear_userlog_channel_id(db, guild_id).await?,
+        None => {}
+    }
+    match serverlog {
+        Some(Some(channel_id)) => set_serverlog_channel_id(db, guild_id, channel_id).await?,
+        Some(None) => clear_serverlog_channel_id(db, guild_id).await?,
+        None => {}
+    }
+
+    if let Some(enabled) = patch.ai_enabled {
+        set_llm_enabled(db, guild_id, enabled).await?;
+    }
+
+    if let Some(enabled) = patch.purge_transcripts
+        && !set_purge_transcripts_enabled(db, guild_id, enabled).await?
+    {
+        return Err(ApiError::BadRequest(
+            "purge_transcripts requires a modlog channel".to_owned(),
+        ));
+    }
+
+    Ok(Json(config_json(&state, guild_id).await?))
+}
+
+async fn config_json(state: &ApiState, guild_id: u64) -> anyhow::Result<Value> {
+    let db = &state.db;
+
+    Ok(json!({
+        "modlog_channel_id": get_modlog_channel_id(db, guild_id).await?.map(|id| id.to_string()),
+        "userlog_channel_id": get_userlog_channel_id(db, guild_id).await?.map(|id| id.to_string()),
+        "serverlog_channel_id": get_serverlog_channel_id(db, guild_id)
+            .await?
+            .map(|id| id.to_string()),
+        "ai_enabled": get_llm_enabled(db, guild_id).await?,
+        "purge_transcripts": get_purge_transcripts_enabled(db, guild_id).await?,
+    }))
+}
+
+fn parse_channel_field(
+    name: &str,
+    value: Option<Option<String>>,
+) -> ApiResult<Option<Option<u64>>> {
+    match value {
+        None => Ok(None),
+        Some(None) => Ok(Some(None)),
+        Some(Some(raw)) => raw
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|id| *id != 0)
+            .map(|id| Some(Some(id)))
+            .ok_or_else(|| ApiError::BadRequest(format!("`{name}` must be a channel ID string"))),
+    }
+}
+
+/// Distinguish an explicit `null` (`Some(None)`) from an omitted field (`None`).
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,103 @@
+//! Optional authenticated HTTP API exposing moderation data and guild config
+//! to external dashboards.
+//!
+//! Every route except `/health` requires `Authorization: Bearer <API_TOKEN>`.
+//! Discord IDs are returned as strings, matching Discord's own API.
+
+mod auth;
+mod cases;
+mod config;
+mod error;
+mod users;
+
+use std::env;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::middleware;
+use axum::routing::get;
+use tracing::info;
+
+use autumn_database::Database;
+
+/// Where the API listens and the token clients must present.
+#[derive(Clone, Debug)]
+pub struct ApiConfig {
+    pub bind_addr: SocketAddr,
+    pub token: String,
+}
+
+impl ApiConfig {
+    /// Read `API_BIND_ADDR` and `API_TOKEN`. Returns `None` when the API is not
+    /// configured; a bind address without a token is an error so the API is
+    /// never served unauthenticated.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let Some(bind_addr) = env::var("API_BIND_ADDR")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let bind_addr = bind_addr
+            .parse::<SocketAddr>()
+            .map_err(|e| anyhow::anyhow!("invalid API_BIND_ADDR `{bind_addr}`: {e}"))?;
+
+        let token = env::var("API_TOKEN")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("API_BIND_ADDR is set but API_TOKEN is missing"))?;
+
+        Ok(Some(Self { bind_addr, token }))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ApiState {
+    pub(crate) db: Database,
+    pub(crate) token: Arc<str>,
+}
+
+/// Build the API router.
+pub fn router(db: Database, token: &str) -> Router {
+    let state = ApiState {
+        db,
+        token: Arc::from(token),
+    };
+
+    let guild_routes = Router::new()
+        .route("/guilds/{guild_id}/cases", get(cases::list_cases))
+        .route("/guilds/{guild_id}/cases/{label}", get(cases::get_case))
+        .route(
+            "/guilds/{guild_id}/users/{user_id}/warnings",
+            get(users::list_warnings),
+        )
+        .route(
+            "/guilds/{guild_id}/users/{user_id}/notes",
+            get(users::list_notes),
+        )
+        .route(
+            "/guilds/{guild_id}/config",
+            get(config::get_config).patch(config::update_config),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ));
+
+    Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .merge(guild_routes)
+        .with_state(state)
+}
+
+/// Serve the API until the process exits.
+pub async fn serve(config: ApiConfig, db: Database) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(config.bind_addr).await?;
+    info!(bind_addr = %config.bind_addr, "HTTP API listening.");
+    axum::serve(listener, router(db, &config.token)).await?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use serde_json::{Value, json};
+
+use autumn_database::impls::notes::list_user_notes;
+use autumn_database::impls::warnings::warnings_since;
+
+use crate::ApiState;
+use crate::error::ApiResult;
+
+/// `GET /guilds/{guild_id}/users/{user_id}/warnings` — oldest first.
+pub(crate) async fn list_warnings(
+    State(state): State<ApiState>,
+    Path((guild_id, user_id)): Path<(u64, u64)>,
+) -> ApiResult<Json<Value>> {
+    let warnings = warnings_since(&state.db, guild_id, user_id, 0).await?;
+
+    Ok(Json(json!({
+        "warnings": warnings
+            .iter()
+            .map(|warning| json!({
+                "moderator_user_id": warning.moderator_id.to_string(),
+                "reason": warning.reason,
+                "warned_at": warning.warned_at,
+            }))
+            .collect::<Vec<_>>(),
+    })))
+}
+
+/// `GET /guilds/{guild_id}/users/{user_id}/notes` — newest first.
+pub(crate) async fn list_notes(
+    State(state): State<ApiState>,
+    Path((guild_id, user_id)): Path<(u64, u64)>,
+) -> ApiResult<Json<Value>> {
+    let notes = list_user_notes(&state.db, guild_id, user_id).await?;
+
+    Ok(Json(json!({
+        "notes": notes
+            .iter()
+            .map(|note| json!({
+                "id": note.id,
+                "author_user_id": note.author_user_id.to_string(),
+                "content": note.content,
+                "created_at": note.created_at,
+                "updated_at": note.updated_at,
+            }))
+            .collect::<Vec<_>>(),
+    })))
+}
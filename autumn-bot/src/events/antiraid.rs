@@ -0,0 +1,254 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_commands::branding::guild_accent_color;
+use autumn_core::Data;
+use autumn_database::impls::antiraid::{
+    get_antiraid_if_enabled, record_member_join, set_previous_verification_level, start_raid,
+};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_database::impls::raidmode::get_raidmode_snapshot;
+use autumn_database::model::antiraid::AntiraidConfig;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::format_compact_duration;
+
+const RAID_KICK_REASON: &str = "Anti-raid: joined during an active raid";
+const RAIDMODE_KICK_REASON: &str = "Raid mode is on: the server is not accepting new members";
+
+/// Track the guild's join rate and trigger the configured raid response when
+/// joins go over the threshold. While a raid is active, new joins are kicked
+/// if the response includes kicking. While raid mode is on, every join is
+/// kicked regardless of the anti-raid config. Returns `true` if the member
+/// was kicked.
+pub async fn handle_member_addition_antiraid(
+    ctx: &serenity::Context,
+    data: &Data,
+    member: &serenity::Member,
+) -> bool {
+    if member.user.bot {
+        return false;
+    }
+
+    let guild_id = member.guild_id;
+
+    match get_raidmode_snapshot(&data.db, guild_id.get()).await {
+        Ok(Some(_)) => return kick_member(ctx, member, RAIDMODE_KICK_REASON).await,
+        Ok(None) => {}
+        Err(source) => error!(?source, "failed to read raid mode state"),
+    }
+
+    let config = match get_antiraid_if_enabled(&data.db, guild_id.get()).await {
+        Ok(Some(cfg)) => cfg,
+        Ok(None) => return false,
+        Err(source) => {
+            error!(?source, "failed to read anti-raid config");
+            return false;
+        }
+    };
+
+    let now = unix_now();
+
+    if config.raid_active(now) {
+        return config.kick_new_joins && kick_member(ctx, member, RAID_KICK_REASON).await;
+    }
+
+    let join_window = config.join_window();
+    let join_count = match record_member_join(&data.db, guild_id.get(), join_window).await {
+        Ok(count) => count,
+        Err(source) => {
+            error!(?source, "failed to record anti-raid join");
+            return false;
+        }
+    };
+
+    if !config.exceeds_threshold(join_count) {
+        return false;
+    }
+
+    let until = config.raid_ends_at(now);
+    match start_raid(&data.db, guild_id.get(), now, until).await {
+        Ok(true) => {}
+        // Another join already started the raid response.
+        Ok(false) => {
+            return config.kick_new_joins && kick_member(ctx, member, RAID_KICK_REASON).await;
+        }
+        Err(source) => {
+            error!(?source, "failed to start anti-raid state");
+            return false;
+        }
+    }
+
+    let verification_raised = if config.raise_verification {
+        raise_verification_level(ctx, data, guild_id).await
+    } else {
+        false
+    };
+
+    let kicked = config.kick_new_joins && kick_member(ctx, member, RAID_KICK_REASON).await;
+
+    let description =
+        raid_alert_description(&config, join_count, join_window, until, verification_raised);
+    if let Err(source) = publish_raid_alert(ctx, data, guild_id, description).await {
+        error!(
+            ?source,
+            "failed to publish anti-raid alert to modlog channel"
+        );
+    }
+
+    kicked
+}
+
+/// Raise the guild's verification level to High, remembering the previous
+/// level so `!antiraid end` can restore it. Returns `true` if it was changed.
+async fn raise_verification_level(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+) -> bool {
+    let current = match guild_id.to_partial_guild(&ctx.http).await {
+        Ok(guild) => guild.verification_level,
+        Err(source) => {
+            error!(?source, "failed to read guild verification level");
+            return false;
+        }
+    };
+
+    if u8::from(current) >= u8::from(serenity::VerificationLevel::High) {
+        return false;
+    }
+
+    if let Err(source) =
+        set_previous_verification_level(&data.db, guild_id.get(), i16::from(u8::from(current)))
+            .await
+    {
+        error!(?source, "failed to store previous verification level");
+        return false;
+    }
+
+    match guild_id
+        .edit(
+            &ctx.http,
+            serenity::EditGuild::new()
+                .verification_level(serenity::VerificationLevel::High)
+                .audit_log_reason("Anti-raid: join threshold exceeded"),
+        )
+        .await
+    {
+        Ok(_) => true,
+        Err(source) => {
+            warn!(?source, "failed to raise verification level during raid");
+            false
+        }
+    }
+}
+
+async fn kick_member(ctx: &serenity::Context, member: &serenity::Member, reason: &str) -> bool {
+    match member.kick_with_reason(&ctx.http, reason).await {
+        Ok(()) => true,
+        Err(source) => {
+            warn!(?source, "failed to kick member during raid");
+            false
+        }
+    }
+}
+
+fn raid_alert_description(
+    config: &AntiraidConfig,
+    join_count: u64,
+    join_window: u64,
+    until: i64,
+    verification_raised: bool,
+) -> String {
+    let mut response = Vec::new();
+    if verification_raised {
+        response.push("Raised verification level to High");
+    }
+    if config.kick_new_joins {
+        response.push("Kicking new joins");
+    }
+    if response.is_empty() {
+        response.push("Alert only");
+    }
+
+    format!(
+        "**Joins :** {} in {}\n\
+         **Response :** {}\n\
+         **Raid Ends :** <t:{}:R>\n\n\
+         Use `!antiraid end` to end the raid early and restore the verification level.",
+        join_count,
+        format_compact_duration(join_window),
+        response.join(", "),
+        until
+    )
+}
+
+async fn publish_raid_alert(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    description: String,
+) -> Result<(), serenity::Error> {
+    let channel_id = match get_modlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Ok(()),
+        Err(source) => {
+            error!(?source, "failed to read modlog channel for anti-raid");
+            return Ok(());
+        }
+    };
+
+    let embed = guild_embed(guild_accent_color(&data.db, guild_id.get()).await)
+        .title("Raid Detected")
+        .description(description);
+
+    serenity::ChannelId::new(channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(raise_verification: bool, kick_new_joins: bool) -> AntiraidConfig {
+        AntiraidConfig {
+            guild_id: 1,
+            enabled: true,
+            join_threshold: 10,
+            join_window_seconds: 30,
+            raid_duration_seconds: 600,
+            raise_verification,
+            kick_new_joins,
+            raid_active_until: None,
+            previous_verification_level: None,
+        }
+    }
+
+    #[test]
+    fn alert_lists_the_response_taken() {
+        let description = raid_alert_description(&config(true, true), 12, 30, 1_600, true);
+
+        assert!(description.contains("**Joins :** 12 in 30s"));
+        assert!(description.contains("Raised verification level to High, Kicking new joins"));
+        assert!(description.contains("<t:1600:R>"));
+    }
+
+    #[test]
+    fn alert_without_a_response_says_so() {
+        // The level was already High, so nothing was raised.
+        let description = raid_alert_description(&config(true, false), 12, 30, 1_600, false);
+
+        assert!(description.contains("**Response :** Alert only"));
+    }
+}
@@ -0,0 +1,119 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::Data;
+use autumn_database::impls::antispam::{record_duplicate_message, record_message_rate};
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::normalize::content_fingerprint;
+
+use super::automod::{AutomodHit, delete_automod_message, execute_automod_action};
+
+/// Track per-user message rate and repeated content (across channels, keyed
+/// on a content fingerprint), and execute the matching configured action the
+/// first time a user crosses a threshold within the window. Repeated content
+/// creates `spam_repeat` cases with its own action. Further spam in the same
+/// window is only deleted (when the action deletes) so a burst produces a
+/// single case. Returns `true` if the message was acted on.
+pub async fn handle_message_antispam(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) -> bool {
+    // Ignore bots and webhooks.
+    if message.author.bot || message.webhook_id.is_some() {
+        return false;
+    }
+
+    let Some(guild_id) = message.guild_id else {
+        return false;
+    };
+
+    let Some(antispam) = config.antispam_if_enabled() else {
+        return false;
+    };
+
+    let user_id = message.author.id.get();
+    let message_threshold = u64::try_from(antispam.message_threshold.max(1)).unwrap_or(1);
+    let message_window = u64::try_from(antispam.message_window_seconds.max(1)).unwrap_or(1);
+    let duplicate_threshold = u64::try_from(antispam.duplicate_threshold.max(1)).unwrap_or(1);
+    let duplicate_window = u64::try_from(antispam.duplicate_window_seconds.max(1)).unwrap_or(1);
+
+    let rate_count =
+        match record_message_rate(&data.db, guild_id.get(), user_id, message_window).await {
+            Ok(count) => count,
+            Err(source) => {
+                error!(?source, "failed to record anti-spam message rate");
+                return false;
+            }
+        };
+
+    let duplicate_count = if message.content.trim().is_empty() {
+        0
+    } else {
+        let fingerprint = content_fingerprint(&message.content);
+        match record_duplicate_message(
+            &data.db,
+            guild_id.get(),
+            user_id,
+            &fingerprint,
+            duplicate_window,
+        )
+        .await
+        {
+            Ok(count) => count,
+            Err(source) => {
+                error!(?source, "failed to record anti-spam duplicate");
+                0
+            }
+        }
+    };
+
+    let violation = if rate_count > message_threshold {
+        Some((
+            rate_count == message_threshold + 1,
+            antispam.action.as_str(),
+            AutomodHit {
+                case_prefix: "spam_filter",
+                filter_name: "Anti-Spam",
+                violation: format!(
+                    "Sent {} messages in {}",
+                    rate_count,
+                    format_compact_duration(message_window)
+                ),
+            },
+        ))
+    } else if duplicate_count > duplicate_threshold {
+        Some((
+            duplicate_count == duplicate_threshold + 1,
+            antispam.duplicate_action.as_str(),
+            AutomodHit {
+                case_prefix: "spam_repeat",
+                filter_name: "Repeated Message",
+                violation: format!(
+                    "Repeated the same message {} times in {}",
+                    duplicate_count,
+                    format_compact_duration(duplicate_window)
+                ),
+            },
+        ))
+    } else {
+        None
+    };
+
+    let Some((first_violation, action, hit)) = violation else {
+        return false;
+    };
+
+    if !first_violation {
+        if action != "log_only" {
+            delete_automod_message(ctx, data, message).await;
+        }
+        return true;
+    }
+
+    execute_automod_action(ctx, data, message, config, action, hit).await;
+
+    true
+}
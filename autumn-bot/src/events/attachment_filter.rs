@@ -0,0 +1,68 @@
+use poise::serenity_prelude as serenity;
+
+use autumn_core::Data;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::files::file_extension;
+
+use super::automod::{AutomodHit, execute_automod_action};
+
+/// Check an incoming message's attachments against the blocked extensions and
+/// the channels where attachments are not allowed, and execute the configured
+/// action. Returns `true` if the message was acted on.
+pub async fn handle_message_attachment_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) -> bool {
+    // Ignore bots and webhooks.
+    if message.author.bot || message.webhook_id.is_some() {
+        return false;
+    }
+
+    if message.attachments.is_empty() {
+        return false;
+    }
+
+    let Some(attachment_filter) = config.attachment_filter_if_enabled() else {
+        return false;
+    };
+
+    let violation = if attachment_filter
+        .blocked_channel_ids
+        .contains(&message.channel_id.get())
+    {
+        Some(format!(
+            "Attachment in a channel where attachments are blocked ({})",
+            message.attachments[0].filename
+        ))
+    } else {
+        message.attachments.iter().find_map(|attachment| {
+            let extension = file_extension(&attachment.filename)?;
+            attachment_filter
+                .blocked_extensions
+                .contains(&extension)
+                .then(|| format!("Blocked file type .{} ({})", extension, attachment.filename))
+        })
+    };
+
+    let Some(violation) = violation else {
+        return false;
+    };
+
+    execute_automod_action(
+        ctx,
+        data,
+        message,
+        config,
+        &attachment_filter.action,
+        AutomodHit {
+            case_prefix: "attachment_filter",
+            filter_name: "Attachment Filter",
+            violation,
+        },
+    )
+    .await;
+
+    true
+}
@@ -0,0 +1,27 @@
+use poise::serenity_prelude as serenity;
+
+/// How far apart (in seconds) an audit log entry and the gateway event it
+/// explains may be.
+const AUDIT_MATCH_WINDOW_SECONDS: i64 = 20;
+
+/// Find the most recent audit log entry for `action` against `target_id`
+/// created around `event_unix` (unix seconds). Returns `None` if the bot
+/// can't read the audit log or no entry matches.
+pub async fn find_recent_audit_entry(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    action: serenity::audit_log::Action,
+    target_id: u64,
+    event_unix: i64,
+) -> Option<serenity::AuditLogEntry> {
+    let audit_logs = guild_id
+        .audit_logs(&ctx.http, Some(action), None, None, Some(10))
+        .await
+        .ok()?;
+
+    audit_logs.entries.into_iter().find(|entry| {
+        let same_target = entry.target_id.is_some_and(|id| id.get() == target_id);
+        let audit_ts = entry.id.created_at().unix_timestamp();
+        same_target && (audit_ts - event_unix).abs() <= AUDIT_MATCH_WINDOW_SECONDS
+    })
+}
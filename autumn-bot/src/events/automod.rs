@@ -0,0 +1,327 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_commands::branding::guild_accent_color;
+use autumn_commands::moderation::escalation_check::check_and_escalate;
+use autumn_commands::moderation::send_moderation_target_dm_for_guild;
+use autumn_core::Data;
+use autumn_database::impls::cases::{NewCase, create_case};
+use autumn_database::impls::modlog_template::get_modlog_layout;
+use autumn_database::model::cases::CaseSummary;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::formatting::{format_case_label, format_compact_duration};
+use autumn_utils::modlog_template::{ModlogField, build_modlog_embed};
+
+/// Run every message filter in order, stopping after the first one that acts
+/// so a single message never produces more than one automod case.
+pub async fn handle_message_automod(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) {
+    if super::antispam::handle_message_antispam(ctx, data, message, config).await {
+        return;
+    }
+
+    if super::word_filter::handle_message_word_filter(ctx, data, message, config).await {
+        return;
+    }
+
+    if super::invite_filter::handle_message_invite_filter(ctx, data, message, config).await {
+        return;
+    }
+
+    if super::attachment_filter::handle_message_attachment_filter(ctx, data, message, config).await
+    {
+        return;
+    }
+
+    if super::image_filter::handle_message_image_filter(ctx, data, message, config).await {
+        return;
+    }
+
+    if super::link_filter::handle_message_link_filter(ctx, data, message, config).await {
+        return;
+    }
+
+    super::caps_filter::handle_message_caps_filter(ctx, data, message, config).await;
+}
+
+/// Timeout applied by the `timeout_delete_and_log` automod action.
+const AUTOMOD_TIMEOUT_SECONDS: u64 = 300;
+
+/// A message that tripped an automod filter.
+pub struct AutomodHit<'a> {
+    /// Case action prefix, e.g. `word_filter` produces `word_filter_delete`.
+    pub case_prefix: &'a str,
+    /// Human-readable filter name used in DMs and modlog titles.
+    pub filter_name: &'a str,
+    /// What matched; stored as the case reason.
+    pub violation: String,
+}
+
+/// Execute a configured automod action (`log_only`, `delete_and_log`,
+/// `warn_and_log`, `timeout_delete_and_log`) against `message`, then record a
+/// case and publish it to the modlog channel.
+pub async fn execute_automod_action(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+    action: &str,
+    hit: AutomodHit<'_>,
+) {
+    let Some(guild_id) = message.guild_id else {
+        return;
+    };
+
+    let bot_user_id = ctx.cache.current_user().id.get();
+    let dm_reason = format!("{}: {}", hit.filter_name, hit.violation);
+
+    // Suppress this message from user-log recording if it will be deleted.
+    if matches!(
+        action,
+        "delete_and_log" | "warn_and_log" | "timeout_delete_and_log"
+    ) {
+        let mut suppressed = data.suppressed_deletes.write().await;
+        suppressed.insert(message.id.get());
+    }
+
+    // Execute the configured action.
+    match action {
+        "delete_and_log" => {
+            delete_violating_message(ctx, message).await;
+        }
+        "warn_and_log" => {
+            delete_violating_message(ctx, message).await;
+
+            // Issue a warning for the user.
+            if let Err(source) = data
+                .warnings
+                .record_warning(
+                    guild_id.get(),
+                    message.author.id.get(),
+                    bot_user_id,
+                    &dm_reason,
+                )
+                .await
+            {
+                error!(?source, "failed to record warning for automod violation");
+            }
+
+            // DM the user about the warning.
+            let _ = send_moderation_target_dm_for_guild(
+                &ctx.http,
+                &message.author,
+                guild_id,
+                "warned",
+                Some(&dm_reason),
+                None,
+                guild_accent_color(&data.db, guild_id.get()).await,
+            )
+            .await;
+
+            // Check for automatic escalation (warn threshold → auto-timeout).
+            check_and_escalate(
+                &ctx.http,
+                data,
+                guild_id,
+                &message.author,
+                Some(message.channel_id),
+                bot_user_id,
+            )
+            .await;
+        }
+        "timeout_delete_and_log" => {
+            delete_violating_message(ctx, message).await;
+
+            let timeout_duration = Duration::from_secs(AUTOMOD_TIMEOUT_SECONDS);
+            let until_system_time = SystemTime::now()
+                .checked_add(timeout_duration)
+                .unwrap_or(SystemTime::now());
+            let until_unix = until_system_time
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()) as i64;
+
+            if let Ok(until) = serenity::Timestamp::from_unix_timestamp(until_unix) {
+                let edit = serenity::EditMember::new().disable_communication_until_datetime(until);
+                if let Err(source) = guild_id
+                    .edit_member(&ctx.http, message.author.id, edit)
+                    .await
+                {
+                    if !is_missing_permissions(&source) {
+                        error!(?source, "failed to timeout user for automod violation");
+                    } else {
+                        warn!(
+                            user_id = %message.author.id,
+                            "missing permissions to timeout user for automod violation \
+                             (check role hierarchy)"
+                        );
+                    }
+                }
+            }
+
+            // DM the user about the timeout.
+            let _ = send_moderation_target_dm_for_guild(
+                &ctx.http,
+                &message.author,
+                guild_id,
+                "timed out",
+                Some(&dm_reason),
+                Some(&format_compact_duration(AUTOMOD_TIMEOUT_SECONDS)),
+                guild_accent_color(&data.db, guild_id.get()).await,
+            )
+            .await;
+        }
+        // "log_only" or anything else — no message action needed.
+        _ => {}
+    }
+
+    // Create a moderation case for the violation.
+    let case_suffix = match action {
+        "timeout_delete_and_log" => "timeout",
+        "delete_and_log" => "delete",
+        "warn_and_log" => "warn",
+        _ => "log",
+    };
+    let case_action = format!("{}_{}", hit.case_prefix, case_suffix);
+
+    let new_case = NewCase {
+        guild_id: guild_id.get(),
+        target_user_id: Some(message.author.id.get()),
+        moderator_user_id: bot_user_id,
+        action: &case_action,
+        reason: &hit.violation,
+        status: "completed",
+        duration_seconds: if action == "timeout_delete_and_log" {
+            Some(AUTOMOD_TIMEOUT_SECONDS)
+        } else {
+            None
+        },
+    };
+
+    let case = match create_case(&data.db, new_case).await {
+        Ok(case) => case,
+        Err(source) => {
+            error!(?source, "failed to create automod case");
+            return;
+        }
+    };
+
+    // Publish to modlog channel.
+    if let Err(source) = publish_automod_to_modlog(ctx, data, config, &case, &hit, action).await {
+        error!(?source, "failed to publish automod case to modlog channel");
+    }
+}
+
+/// Delete a message as part of automod without creating a case, keeping it out
+/// of the user log.
+pub async fn delete_automod_message(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+) {
+    data.suppressed_deletes
+        .write()
+        .await
+        .insert(message.id.get());
+    delete_violating_message(ctx, message).await;
+}
+
+async fn delete_violating_message(ctx: &serenity::Context, message: &serenity::Message) {
+    if let Err(source) = message.delete(&ctx.http).await {
+        if !is_missing_permissions(&source) {
+            error!(?source, "failed to delete filtered message");
+        } else {
+            warn!("missing permissions to delete filtered message");
+        }
+    }
+}
+
+async fn publish_automod_to_modlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    config: &GuildConfig,
+    case: &CaseSummary,
+    hit: &AutomodHit<'_>,
+    action: &str,
+) -> Result<(), serenity::Error> {
+    let Some(channel_id) = config.modlog_channel_id else {
+        return Ok(());
+    };
+
+    let case_label = format_case_label(&case.case_code, case.action_case_number);
+
+    let action_label = match action {
+        "timeout_delete_and_log" => "Timeout, Delete & Log",
+        "delete_and_log" => "Delete & Log",
+        "warn_and_log" => "Warn, Delete & Log",
+        _ => "Log Only",
+    };
+
+    let layout = match get_modlog_layout(&data.db, config.guild_id).await {
+        Ok(layout) => layout,
+        Err(source) => {
+            error!(?source, "failed to read modlog template");
+            None
+        }
+    };
+
+    let mut fields = vec![
+        ModlogField::new(
+            "target",
+            format!("**User :** <@{}>", case.target_user_id.unwrap_or(0)),
+        ),
+        ModlogField::new(
+            "reason",
+            format!(
+                "**Violation :** {}",
+                hit.violation.replace('@', "@\u{200B}")
+            ),
+        ),
+        ModlogField::new("action", format!("**Action Taken :** {}", action_label)),
+    ];
+
+    if let Some(duration_seconds) = case.duration_seconds {
+        fields.push(ModlogField::new(
+            "duration",
+            format!(
+                "**Timeout Duration :** {}",
+                format_compact_duration(duration_seconds)
+            ),
+        ));
+    }
+
+    // Blank line separator before metadata.
+    fields.push(ModlogField::separator());
+
+    fields.push(ModlogField::new(
+        "when",
+        format!("**When :** <t:{}:R>", case.created_at),
+    ));
+
+    let title = format!("{} Violation - #{}", hit.filter_name, case_label);
+    let embed = build_modlog_embed(
+        title,
+        &fields,
+        layout.as_ref(),
+        &[("{case}", &case_label), ("{action}", hit.filter_name)],
+    );
+
+    serenity::ChannelId::new(channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+fn is_missing_permissions(source: &serenity::Error) -> bool {
+    matches!(
+        source,
+        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
+            if response.status_code.as_u16() == 403 || response.error.code == 50013
+    )
+}
@@ -0,0 +1,58 @@
+use poise::serenity_prelude as serenity;
+
+use autumn_core::Data;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::normalize::letter_case_counts;
+
+use super::automod::{AutomodHit, execute_automod_action};
+
+/// Flag messages whose letters are mostly uppercase and execute the configured
+/// action. Messages shorter than the configured minimum are ignored. Returns
+/// `true` if the message was acted on.
+pub async fn handle_message_caps_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) -> bool {
+    // Ignore bots and webhooks.
+    if message.author.bot || message.webhook_id.is_some() {
+        return false;
+    }
+
+    let (letters, uppercase) = letter_case_counts(&message.content);
+    if letters == 0 {
+        return false;
+    }
+
+    let Some(caps_filter) = config.caps_filter_if_enabled() else {
+        return false;
+    };
+
+    let min_length = usize::try_from(caps_filter.min_length.max(1)).unwrap_or(1);
+    if letters < min_length {
+        return false;
+    }
+
+    let percent = uppercase * 100 / letters;
+    let max_percent = usize::try_from(caps_filter.max_caps_percent.clamp(1, 100)).unwrap_or(100);
+    if percent <= max_percent {
+        return false;
+    }
+
+    execute_automod_action(
+        ctx,
+        data,
+        message,
+        config,
+        &caps_filter.action,
+        AutomodHit {
+            case_prefix: "caps_filter",
+            filter_name: "Caps Filter",
+            violation: format!("{}% uppercase ({} letters)", percent, letters),
+        },
+    )
+    .await;
+
+    true
+}
@@ -0,0 +1,71 @@
+use std::any::Any;
+
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::{Data, Error};
+use autumn_database::impls::command_aliases::{list_command_aliases, resolve_command_alias};
+
+/// Handles prefix messages poise didn't recognize as a command. If the first
+/// word is one of the guild's aliases, the message is dispatched again with
+/// the alias swapped for its command. Returns `false` when it isn't an alias.
+pub async fn dispatch_alias(
+    ctx: &serenity::Context,
+    framework: poise::FrameworkContext<'_, Data, Error>,
+    msg: &serenity::Message,
+    prefix: &str,
+    msg_content: &str,
+    trigger: poise::MessageDispatchTrigger,
+    invocation_data: &tokio::sync::Mutex<Box<dyn Any + Send + Sync>>,
+) -> bool {
+    let Some(guild_id) = msg.guild_id else {
+        return false;
+    };
+
+    let (word, rest) = msg_content
+        .split_once(char::is_whitespace)
+        .unwrap_or((msg_content, ""));
+    if word.is_empty() {
+        return false;
+    }
+
+    let aliases = match list_command_aliases(&framework.user_data.db, guild_id.get()).await {
+        Ok(aliases) => aliases,
+        Err(source) => {
+            error!(?source, "failed to load command aliases");
+            return false;
+        }
+    };
+    let Some(command_name) = resolve_command_alias(&aliases, word) else {
+        return false;
+    };
+
+    // An alias whose command is gone would come straight back here.
+    if !framework
+        .options
+        .commands
+        .iter()
+        .any(|command| command.name == command_name)
+    {
+        return false;
+    }
+
+    let mut aliased = msg.clone();
+    aliased.content = format!("{}{} {}", prefix, command_name, rest.trim_start());
+
+    let mut parent_commands = Vec::new();
+    if let Err(error) = poise::dispatch_message(
+        framework,
+        ctx,
+        &aliased,
+        trigger,
+        invocation_data,
+        &mut parent_commands,
+    )
+    .await
+    {
+        (framework.options.on_error)(error).await;
+    }
+
+    true
+}
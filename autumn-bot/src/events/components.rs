@@ -0,0 +1,37 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_commands::moderation::modlogs::{MODLOGS_PAGE_KIND, handle_modlogs_page};
+use autumn_core::Data;
+use autumn_utils::pagination::parse_stateless_page_press;
+
+/// Routes presses on stateless paginators to the command that owns them.
+/// Their ids carry the whole query, so they work after the original
+/// collector has timed out or the bot has restarted. Everything else is left
+/// to the collectors that created it.
+pub async fn handle_component_interaction(
+    ctx: &serenity::Context,
+    data: &Data,
+    interaction: &serenity::ComponentInteraction,
+) {
+    let selected = match &interaction.data.kind {
+        serenity::ComponentInteractionDataKind::StringSelect { values } => {
+            values.first().map(String::as_str)
+        }
+        _ => None,
+    };
+    let Some(press) = parse_stateless_page_press(&interaction.data.custom_id, selected) else {
+        return;
+    };
+
+    let result = match press.kind {
+        MODLOGS_PAGE_KIND => {
+            handle_modlogs_page(ctx, data, interaction, press.state, press.page).await
+        }
+        _ => return,
+    };
+
+    if let Err(source) = result {
+        error!(?source, kind = press.kind, "failed to turn stateless page");
+    }
+}
@@ -0,0 +1,242 @@
+use poise::serenity_prelude as serenity;
+use serenity::audit_log::{Action, Change, MemberAction};
+use tracing::error;
+
+use autumn_core::Data;
+use autumn_database::impls::cases::{NewCase, create_case};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_database::impls::modlog_template::get_modlog_layout;
+use autumn_database::model::cases::CaseSummary;
+use autumn_utils::formatting::{
+    action_display_name, action_past_tense, format_case_label, format_compact_duration,
+};
+use autumn_utils::modlog_template::{ModlogField, build_modlog_embed};
+use autumn_utils::time::now_unix_secs;
+
+use super::audit::find_recent_audit_entry;
+
+/// Record a ban made through Discord (or another bot) as a case.
+pub async fn handle_ban_addition_external(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    user: &serenity::User,
+) {
+    let Some(entry) =
+        find_external_entry(ctx, guild_id, Action::Member(MemberAction::BanAdd), user.id).await
+    else {
+        return;
+    };
+
+    record_external_case(ctx, data, guild_id, user.id, &entry, "ban", None).await;
+}
+
+/// Record a kick made through Discord (or another bot) as a case. Members
+/// leaving on their own have no matching audit entry and are ignored.
+pub async fn handle_member_removal_external(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    user: &serenity::User,
+) {
+    let Some(entry) =
+        find_external_entry(ctx, guild_id, Action::Member(MemberAction::Kick), user.id).await
+    else {
+        return;
+    };
+
+    record_external_case(ctx, data, guild_id, user.id, &entry, "kick", None).await;
+}
+
+/// Record a timeout applied through Discord (or another bot) as a case.
+pub async fn handle_member_update_external(
+    ctx: &serenity::Context,
+    data: &Data,
+    old: Option<&serenity::Member>,
+    event: &serenity::GuildMemberUpdateEvent,
+) {
+    let Some(until) = event.communication_disabled_until else {
+        return;
+    };
+
+    let now = now_unix_secs();
+    let Ok(until_unix) = u64::try_from(until.unix_timestamp()) else {
+        return;
+    };
+    if until_unix <= now {
+        return;
+    }
+
+    // Member updates fire for nicknames, roles, etc.; only a changed timeout counts.
+    if old.is_some_and(|member| member.communication_disabled_until == Some(until)) {
+        return;
+    }
+
+    let Some(entry) = find_external_entry(
+        ctx,
+        event.guild_id,
+        Action::Member(MemberAction::Update),
+        event.user.id,
+    )
+    .await
+    else {
+        return;
+    };
+
+    let sets_timeout = entry.changes.iter().flatten().any(|change| {
+        matches!(
+            change,
+            Change::CommunicationDisabledUntil { new: Some(_), .. }
+        )
+    });
+    if !sets_timeout {
+        return;
+    }
+
+    record_external_case(
+        ctx,
+        data,
+        event.guild_id,
+        event.user.id,
+        &entry,
+        "timeout",
+        Some(until_unix - now),
+    )
+    .await;
+}
+
+/// Find the audit entry for an action the bot didn't perform itself. The
+/// bot's own commands and filters already create their cases.
+async fn find_external_entry(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    action: Action,
+    target_user_id: serenity::UserId,
+) -> Option<serenity::AuditLogEntry> {
+    let event_unix = i64::try_from(now_unix_secs()).ok()?;
+    let entry =
+        find_recent_audit_entry(ctx, guild_id, action, target_user_id.get(), event_unix).await?;
+
+    let bot_user_id = ctx.cache.current_user().id;
+    (entry.user_id != bot_user_id).then_some(entry)
+}
+
+async fn record_external_case(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    target_user_id: serenity::UserId,
+    entry: &serenity::AuditLogEntry,
+    action: &str,
+    duration_seconds: Option<u64>,
+) {
+    let reason = entry
+        .reason
+        .as_deref()
+        .filter(|reason| !reason.trim().is_empty())
+        .unwrap_or("No reason provided");
+
+    let case = match create_case(
+        &data.db,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(target_user_id.get()),
+            moderator_user_id: entry.user_id.get(),
+            action,
+            reason,
+            status: "active",
+            duration_seconds,
+        },
+    )
+    .await
+    {
+        Ok(case) => case,
+        Err(source) => {
+            error!(?source, "failed to create case for external action");
+            return;
+        }
+    };
+
+    if let Err(source) = publish_external_case_to_modlog(ctx, data, guild_id, &case).await {
+        error!(
+            ?source,
+            "failed to publish external action case to modlog channel"
+        );
+    }
+}
+
+async fn publish_external_case_to_modlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    case: &CaseSummary,
+) -> Result<(), serenity::Error> {
+    let channel_id = match get_modlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Ok(()),
+        Err(source) => {
+            error!(?source, "failed to read modlog channel for external action");
+            return Ok(());
+        }
+    };
+
+    let case_label = format_case_label(&case.case_code, case.action_case_number);
+
+    let layout = match get_modlog_layout(&data.db, guild_id.get()).await {
+        Ok(layout) => layout,
+        Err(source) => {
+            error!(?source, "failed to read modlog template");
+            None
+        }
+    };
+
+    let action_name = action_display_name(&case.action);
+    let mut fields = vec![
+        ModlogField::new(
+            "target",
+            format!("**Target :** <@{}>", case.target_user_id.unwrap_or(0)),
+        ),
+        ModlogField::new(
+            "reason",
+            format!("**Reason :** {}", case.reason.replace('@', "@\u{200B}")),
+        ),
+    ];
+    if let Some(duration_seconds) = case.duration_seconds {
+        fields.push(ModlogField::new(
+            "duration",
+            format!(
+                "**Duration :** {}",
+                format_compact_duration(duration_seconds)
+            ),
+        ));
+    }
+    fields.push(ModlogField::separator());
+    fields.push(ModlogField::new(
+        "moderator",
+        format!("**Moderator :** <@{}>", case.moderator_user_id),
+    ));
+    fields.push(ModlogField::new(
+        "when",
+        format!("**When :** <t:{}:R>", case.created_at),
+    ));
+
+    let mut embed = build_modlog_embed(
+        format!("#{}", case_label),
+        &fields,
+        layout.as_ref(),
+        &[("{case}", &case_label), ("{action}", &action_name)],
+    );
+    // A template footer replaces the default note.
+    if layout.as_ref().is_none_or(|layout| layout.footer.is_none()) {
+        embed = embed.footer(serenity::CreateEmbedFooter::new(format!(
+            "User {} outside of Autumn",
+            action_past_tense(&case.action)
+        )));
+    }
+
+    serenity::ChannelId::new(channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_core::Data;
+use autumn_database::impls::cases::{NewCase, create_case};
+use autumn_database::impls::global_blacklist::{
+    BLACKLIST_ACTION_OFF, BLACKLIST_ACTION_QUARANTINE, GlobalBlacklistEntry,
+    get_global_blacklist_entry,
+};
+use autumn_database::impls::guild_config::get_guild_config;
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_database::impls::modlog_template::get_modlog_layout;
+use autumn_database::impls::warnings::now_unix_secs;
+use autumn_database::model::cases::CaseSummary;
+use autumn_utils::formatting::{format_case_label, format_compact_duration};
+use autumn_utils::modlog_template::{ModlogField, build_modlog_embed};
+
+/// Discord caps timeouts at 28 days; stay a minute under so clock skew
+/// doesn't push the request over.
+const QUARANTINE_SECONDS: u64 = 28 * 24 * 60 * 60 - 60;
+
+/// Ban or quarantine a newly joined member on the global blacklist, per the
+/// guild's `!blacklistmode`. Returns `true` when the member was banned, so
+/// the other join handlers can skip them.
+pub async fn handle_member_addition_global_blacklist(
+    ctx: &serenity::Context,
+    data: &Data,
+    member: &serenity::Member,
+) -> bool {
+    if member.user.bot {
+        return false;
+    }
+
+    let entry = match get_global_blacklist_entry(&data.db, member.user.id.get()).await {
+        Ok(Some(entry)) => entry,
+        Ok(None) => return false,
+        Err(source) => {
+            error!(?source, "failed to read global blacklist");
+            return false;
+        }
+    };
+
+    let guild_id = member.guild_id;
+    let response = match get_guild_config(&data.db, guild_id.get()).await {
+        Ok(config) => BlacklistResponse::from_mode(config.global_blacklist_action.as_deref()),
+        Err(source) => {
+            error!(?source, "failed to read guild config");
+            BlacklistResponse::Ban
+        }
+    };
+
+    let audit_reason = format!("Global blacklist: {}", entry.reason);
+    let (case_action, duration_seconds) = match response {
+        BlacklistResponse::Ignore => return false,
+        BlacklistResponse::Quarantine => {
+            let Some(until) = quarantine_until(now_unix_secs()) else {
+                return false;
+            };
+
+            let edit = serenity::EditMember::new()
+                .disable_communication_until_datetime(until)
+                .audit_log_reason(&audit_reason);
+            if let Err(source) = guild_id.edit_member(&ctx.http, member.user.id, edit).await {
+                warn!(?source, user_id = %member.user.id, "failed to quarantine blacklisted member");
+                return false;
+            }
+            ("global_blacklist_quarantine", Some(QUARANTINE_SECONDS))
+        }
+        BlacklistResponse::Ban => {
+            if let Err(source) = guild_id
+                .ban_with_reason(&ctx.http, member.user.id, 0, &audit_reason)
+                .await
+            {
+                warn!(?source, user_id = %member.user.id, "failed to ban blacklisted member");
+                return false;
+            }
+            ("global_blacklist_ban", None)
+        }
+    };
+
+    let bot_user_id = ctx.cache.current_user().id.get();
+    let case = match create_case(
+        &data.db,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(member.user.id.get()),
+            moderator_user_id: bot_user_id,
+            action: case_action,
+            reason: &entry.reason,
+            status: "active",
+            duration_seconds,
+        },
+    )
+    .await
+    {
+        Ok(case) => Some(case),
+        Err(source) => {
+            error!(?source, "failed to create global blacklist case");
+            None
+        }
+    };
+
+    if let Err(source) =
+        publish_global_blacklist_to_modlog(ctx, data, guild_id, &entry, case_action, case.as_ref())
+            .await
+    {
+        error!(
+            ?source,
+            "failed to publish global blacklist case to modlog channel"
+        );
+    }
+
+    case_action == "global_blacklist_ban"
+}
+
+/// What a guild does with blacklisted members who join.
+#[derive(Debug, PartialEq, Eq)]
+enum BlacklistResponse {
+    Ignore,
+    Quarantine,
+    Ban,
+}
+
+impl BlacklistResponse {
+    /// Guilds that never ran `!blacklistmode`, or hold an unknown mode, ban.
+    fn from_mode(mode: Option<&str>) -> Self {
+        match mode {
+            Some(BLACKLIST_ACTION_OFF) => Self::Ignore,
+            Some(BLACKLIST_ACTION_QUARANTINE) => Self::Quarantine,
+            _ => Self::Ban,
+        }
+    }
+}
+
+fn quarantine_until(now: u64) -> Option<serenity::Timestamp> {
+    i64::try_from(now + QUARANTINE_SECONDS)
+        .ok()
+        .and_then(|secs| serenity::Timestamp::from_unix_timestamp(secs).ok())
+}
+
+fn action_label(case_action: &str) -> String {
+    if case_action == "global_blacklist_quarantine" {
+        format!(
+            "Quarantined ({} timeout)",
+            format_compact_duration(QUARANTINE_SECONDS)
+        )
+    } else {
+        "Banned".to_owned()
+    }
+}
+
+async fn publish_global_blacklist_to_modlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    entry: &GlobalBlacklistEntry,
+    action: &str,
+    case: Option<&CaseSummary>,
+) -> Result<(), serenity::Error> {
+    let channel_id = match get_modlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Ok(()),
+        Err(source) => {
+            error!(
+                ?source,
+                "failed to read modlog channel for global blacklist"
+            );
+            return Ok(());
+        }
+    };
+
+    let layout = match get_modlog_layout(&data.db, guild_id.get()).await {
+        Ok(layout) => layout,
+        Err(source) => {
+            error!(?source, "failed to read modlog template");
+            None
+        }
+    };
+
+    let case_label = case.map_or_else(
+        || "?".to_owned(),
+        |case| format_case_label(&case.case_code, case.action_case_number),
+    );
+    let action_label = action_label(action);
+    let when = case.map_or_else(now_unix_secs, |case| case.created_at);
+
+    let fields = [
+        ModlogField::new("target", format!("**User :** <@{}>", entry.user_id)),
+        ModlogField::new(
+            "reason",
+            format!(
+                "**Blacklist Reason :** {}",
+                entry.reason.replace('@', "@\u{200B}")
+            ),
+        ),
+        ModlogField::new("action", format!("**Action Taken :** {}", action_label)),
+        ModlogField::new(
+            "blacklisted",
+            format!("**Blacklisted :** <t:{}:R>", entry.created_at),
+        ),
+        ModlogField::separator(),
+        ModlogField::new("when", format!("**When :** <t:{}:R>", when)),
+    ];
+
+    let embed = build_modlog_embed(
+        format!("Global Blacklist - #{}", case_label),
+        &fields,
+        layout.as_ref(),
+        &[("{case}", &case_label), ("{action}", "Global Blacklist")],
+    );
+
+    serenity::ChannelId::new(channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autumn_database::impls::global_blacklist::BLACKLIST_ACTION_BAN;
+
+    #[test]
+    fn guilds_ban_unless_they_chose_otherwise() {
+        assert_eq!(BlacklistResponse::from_mode(None), BlacklistResponse::Ban);
+        assert_eq!(
+            BlacklistResponse::from_mode(Some(BLACKLIST_ACTION_BAN)),
+            BlacklistResponse::Ban
+        );
+        assert_eq!(
+            BlacklistResponse::from_mode(Some("unknown")),
+            BlacklistResponse::Ban
+        );
+        assert_eq!(
+            BlacklistResponse::from_mode(Some(BLACKLIST_ACTION_OFF)),
+            BlacklistResponse::Ignore
+        );
+        assert_eq!(
+            BlacklistResponse::from_mode(Some(BLACKLIST_ACTION_QUARANTINE)),
+            BlacklistResponse::Quarantine
+        );
+    }
+
+    #[test]
+    fn quarantine_stays_under_the_timeout_cap() {
+        let now = 1_700_000_000;
+        let until = quarantine_until(now).expect("timestamp in range");
+
+        let length = until.unix_timestamp() - i64::try_from(now).unwrap();
+        assert!(length < 28 * 24 * 60 * 60);
+        assert!(length > 27 * 24 * 60 * 60);
+    }
+
+    #[test]
+    fn modlog_names_the_action_taken() {
+        assert_eq!(action_label("global_blacklist_ban"), "Banned");
+        assert!(action_label("global_blacklist_quarantine").starts_with("Quarantined ("));
+    }
+}
@@ -0,0 +1,24 @@
+use poise::serenity_prelude as serenity;
+use tracing::info;
+
+/// Per-guild config rows are created on first write and every read falls back
+/// to defaults, so joining a guild needs no setup beyond noting it.
+pub fn handle_guild_create(guild: &serenity::Guild, is_new: Option<bool>) {
+    if is_new == Some(true) {
+        info!(
+            guild_id = guild.id.get(),
+            guild_name = %guild.name,
+            member_count = guild.member_count,
+            "joined guild"
+        );
+    }
+}
+
+/// Configuration and case history are kept so a re-invite picks up where the
+/// guild left off.
+pub fn handle_guild_delete(incomplete: &serenity::UnavailableGuild) {
+    // `unavailable` means an outage, not a removal.
+    if !incomplete.unavailable {
+        info!(guild_id = incomplete.id.get(), "removed from guild");
+    }
+}
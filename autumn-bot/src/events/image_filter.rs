@@ -0,0 +1,106 @@
+use poise::serenity_prelude as serenity;
+use tracing::warn;
+
+use autumn_core::Data;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_llm::ChatImage;
+
+use super::automod::{AutomodHit, execute_automod_action};
+
+/// Image types the vision backends accept.
+const SUPPORTED_IMAGE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Images larger than this are skipped rather than downloaded.
+const MAX_IMAGE_BYTES: u32 = 5 * 1024 * 1024;
+
+/// Most images from one message sent to the model.
+const MAX_IMAGES_PER_MESSAGE: usize = 4;
+
+/// Send a message's image attachments to the LLM and execute the configured
+/// action when it is confident they are NSFW or gore. Returns `true` if the
+/// message was acted on.
+pub async fn handle_message_image_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) -> bool {
+    // Ignore bots and webhooks.
+    if message.author.bot || message.webhook_id.is_some() {
+        return false;
+    }
+
+    let attachments: Vec<(&serenity::Attachment, &str)> = message
+        .attachments
+        .iter()
+        .filter(|attachment| attachment.size <= MAX_IMAGE_BYTES)
+        .filter_map(|attachment| {
+            let media_type = attachment.content_type.as_deref()?;
+            let media_type = media_type.split(';').next().unwrap_or(media_type).trim();
+            SUPPORTED_IMAGE_TYPES
+                .contains(&media_type)
+                .then_some((attachment, media_type))
+        })
+        .take(MAX_IMAGES_PER_MESSAGE)
+        .collect();
+    if attachments.is_empty() {
+        return false;
+    }
+
+    let Some(image_filter) = config.image_filter_if_enabled() else {
+        return false;
+    };
+
+    let Some(llm) = data.llm.as_ref() else {
+        return false;
+    };
+
+    let mut images = Vec::with_capacity(attachments.len());
+    for (attachment, media_type) in &attachments {
+        match attachment.download().await {
+            Ok(data) => images.push(ChatImage {
+                media_type: (*media_type).to_owned(),
+                data,
+            }),
+            Err(source) => {
+                warn!(?source, filename = %attachment.filename, "failed to download image for the image filter");
+            }
+        }
+    }
+    if images.is_empty() {
+        return false;
+    }
+
+    let verdict = match llm.classify_images(images).await {
+        Ok(verdict) => verdict,
+        Err(source) => {
+            warn!(?source, "failed to classify images for the image filter");
+            return false;
+        }
+    };
+
+    if verdict.category == "none" || verdict.score < image_filter.threshold {
+        return false;
+    }
+
+    let mut violation = format!("{} ({}% confidence)", verdict.category, verdict.score);
+    if !verdict.reason.is_empty() {
+        violation.push_str(&format!(": {}", verdict.reason));
+    }
+
+    execute_automod_action(
+        ctx,
+        data,
+        message,
+        config,
+        &image_filter.action,
+        AutomodHit {
+            case_prefix: "image_filter",
+            filter_name: "Image Filter",
+            violation,
+        },
+    )
+    .await;
+
+    true
+}
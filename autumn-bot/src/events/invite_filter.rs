@@ -0,0 +1,95 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::Data;
+use autumn_database::impls::invite_filter::resolve_invite_guild_cached;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::links::extract_invite_codes;
+
+use super::automod::{AutomodHit, execute_automod_action};
+
+/// Check an incoming message for Discord invites to servers that are not
+/// whitelisted and execute the configured action. Invites to the current
+/// server are always allowed; invites that no longer resolve are treated as
+/// violations. Returns `true` if the message was acted on.
+pub async fn handle_message_invite_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) -> bool {
+    // Ignore bots and webhooks.
+    if message.author.bot || message.webhook_id.is_some() {
+        return false;
+    }
+
+    let Some(guild_id) = message.guild_id else {
+        return false;
+    };
+
+    let codes = extract_invite_codes(&message.content);
+    if codes.is_empty() {
+        return false;
+    }
+
+    let Some(invite_filter) = config.invite_filter_if_enabled() else {
+        return false;
+    };
+
+    for code in codes {
+        let resolved = resolve_invite_guild_cached(&data.db, &code, || async {
+            match ctx.http.get_invite(&code, false, false, None).await {
+                Ok(invite) => Ok(invite.guild.map(|guild| guild.id.get())),
+                Err(source) if is_unknown_invite(&source) => Ok(None),
+                Err(source) => Err(source.into()),
+            }
+        })
+        .await;
+
+        let target_guild = match resolved {
+            Ok(target) => target,
+            Err(source) => {
+                error!(?source, invite = %code, "failed to resolve invite");
+                continue;
+            }
+        };
+
+        let allowed = target_guild.is_some_and(|target| {
+            target == guild_id.get() || invite_filter.allowed_guild_ids.contains(&target)
+        });
+        if allowed {
+            continue;
+        }
+
+        let violation = match target_guild {
+            Some(target) => format!("discord.gg/{} (server {})", code, target),
+            None => format!("discord.gg/{} (invalid invite)", code),
+        };
+
+        execute_automod_action(
+            ctx,
+            data,
+            message,
+            config,
+            &invite_filter.action,
+            AutomodHit {
+                case_prefix: "invite_filter",
+                filter_name: "Invite Filter",
+                violation,
+            },
+        )
+        .await;
+
+        return true;
+    }
+
+    false
+}
+
+fn is_unknown_invite(source: &serenity::Error) -> bool {
+    matches!(
+        source,
+        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
+            if response.status_code.as_u16() == 404 || response.error.code == 10006
+    )
+}
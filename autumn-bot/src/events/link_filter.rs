@@ -0,0 +1,64 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::Data;
+use autumn_database::impls::link_filter::{find_blocked_link, list_link_filter_domains};
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::links::extract_link_domains;
+
+use super::automod::{AutomodHit, execute_automod_action};
+
+/// Check an incoming message for links blocked by the guild's link filter and
+/// execute the configured action. Returns `true` if the message was acted on.
+pub async fn handle_message_link_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) -> bool {
+    // Ignore bots and webhooks.
+    if message.author.bot || message.webhook_id.is_some() {
+        return false;
+    }
+
+    let Some(guild_id) = message.guild_id else {
+        return false;
+    };
+
+    let hosts = extract_link_domains(&message.content);
+    if hosts.is_empty() {
+        return false;
+    }
+
+    let Some(link_filter) = config.link_filter_if_enabled() else {
+        return false;
+    };
+
+    let domains = match list_link_filter_domains(&data.db, guild_id.get()).await {
+        Ok(domains) => domains,
+        Err(source) => {
+            error!(?source, "failed to load link filter domains");
+            return false;
+        }
+    };
+
+    let Some(blocked) = find_blocked_link(&link_filter.mode, &domains, &hosts) else {
+        return false;
+    };
+
+    execute_automod_action(
+        ctx,
+        data,
+        message,
+        config,
+        &link_filter.action,
+        AutomodHit {
+            case_prefix: "link_filter",
+            filter_name: "Link Filter",
+            violation: blocked.clone(),
+        },
+    )
+    .await;
+
+    true
+}
@@ -1,15 +1,46 @@
+use std::time::{Duration, Instant};
+
 use poise::serenity_prelude as serenity;
-use tracing::error;
+use tracing::{error, warn};
 
 use autumn_core::{Data, Error};
-use autumn_database::impls::ai_config::get_llm_enabled;
 use autumn_database::impls::llm_chat::insert_llm_chat_message;
 use autumn_database::impls::rate_limit::llm_mention_within_limit;
+use autumn_database::impls::word_filter::{find_matching_pattern, get_all_filter_words_for_guild};
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_database::model::word_filter::WordFilterPattern;
+use autumn_llm::LlmService;
+use autumn_utils::COMMAND_PREFIX;
+
+const EMPTY_REPLY: &str = "I couldn't generate a useful response for that. Try rephrasing?";
+const LLM_ERROR_REPLY: &str = "I ran into an LLM error. Try again in a moment.";
+/// Posted instead of a reply that contains a word on the guild's filter list.
+const FILTERED_REPLY: &str = "I can't say that here. Try asking something else?";
+const STREAM_PLACEHOLDER: &str = "*thinking…*";
+/// Minimum gap between edits of a streamed reply.
+const STREAM_EDIT_INTERVAL: Duration = Duration::from_millis(1500);
+const MAX_MESSAGE_CHARS: usize = 2000;
+/// Longest name given to a conversation thread.
+const THREAD_NAME_MAX_CHARS: usize = 60;
+
+/// The mention being answered.
+struct MentionRequest<'a> {
+    guild_id: serenity::GuildId,
+    message: &'a serenity::Message,
+    /// Where the reply goes and whose history is used: the message's own
+    /// channel, or the thread opened for it.
+    channel_id: serenity::ChannelId,
+    prompt: &'a str,
+    author_display_name: &'a str,
+    /// The guild's word filter list; replies may never contain these.
+    filtered_words: &'a [WordFilterPattern],
+}
 
 pub async fn handle_message_mention_llm(
     ctx: &serenity::Context,
     data: &Data,
     new_message: &serenity::Message,
+    config: &GuildConfig,
 ) -> Result<(), Error> {
     if new_message.author.bot || new_message.webhook_id.is_some() {
         return Ok(());
@@ -23,15 +54,7 @@ pub async fn handle_message_mention_llm(
         return Ok(());
     };
 
-    let llm_enabled = match get_llm_enabled(&data.db, guild_id.get()).await {
-        Ok(enabled) => enabled,
-        Err(source) => {
-            error!(?source, "failed to read guild AI config");
-            return Ok(());
-        }
-    };
-
-    if !llm_enabled {
+    if !config.llm_enabled {
         return Ok(());
     }
 
@@ -43,11 +66,21 @@ pub async fn handle_message_mention_llm(
         }
     };
 
-    if !mentions_bot {
+    let bot_user_id = ctx.cache.current_user().id;
+    let channel_kind = if config.llm_threads {
+        channel_kind(ctx, guild_id, new_message.channel_id, bot_user_id).await
+    } else {
+        ChannelKind::Channel
+    };
+
+    // In a thread Autumn opened, every message is part of the conversation.
+    let in_conversation_thread = matches!(channel_kind, ChannelKind::Thread { owned_by_bot: true })
+        && !new_message.content.starts_with(COMMAND_PREFIX);
+
+    if !mentions_bot && !in_conversation_thread {
         return Ok(());
     }
 
-    let bot_user_id = ctx.cache.current_user().id;
     let author_display_name = message_display_name(new_message);
     let bot_display_name = ctx.cache.current_user().name.clone();
     let prompt = strip_bot_mention(&new_message.content, bot_user_id)
@@ -55,7 +88,9 @@ pub async fn handle_message_mention_llm(
         .to_owned();
 
     if prompt.is_empty() {
-        new_message.reply(&ctx.http, "a?").await?;
+        if mentions_bot {
+            new_message.reply(&ctx.http, "a?").await?;
+        }
         return Ok(());
     }
 
@@ -84,33 +119,47 @@ pub async fn handle_message_mention_llm(
         return Ok(());
     }
 
-    let _ = new_message.channel_id.broadcast_typing(&ctx.http).await;
+    let channel_id = if matches!(channel_kind, ChannelKind::Channel) && config.llm_threads {
+        open_conversation_thread(ctx, new_message, &prompt)
+            .await
+            .unwrap_or(new_message.channel_id)
+    } else {
+        new_message.channel_id
+    };
 
-    let llm_reply = match llm
-        .generate_channel_reply(
-            &data.db,
-            guild_id.get(),
-            new_message.channel_id.get(),
-            &prompt,
-            &author_display_name,
-        )
-        .await
-    {
-        Ok(content) if !content.trim().is_empty() => content,
-        Ok(_) => "I couldn't generate a useful response for that. Try rephrasing?".to_owned(),
+    let _ = channel_id.broadcast_typing(&ctx.http).await;
+
+    // Replies are checked against the guild's word list even where the filter
+    // itself is switched off, so the bot never says a filtered word.
+    let filtered_words = match get_all_filter_words_for_guild(&data.db, guild_id.get()).await {
+        Ok(words) => words,
         Err(source) => {
-            error!(?source, "llm reply generation failed");
-            new_message
-                .reply(&ctx.http, "I ran into an LLM error. Try again in a moment.")
-                .await?;
-            return Ok(());
+            error!(?source, "failed to load word filter list for llm reply");
+            Vec::new()
         }
     };
 
+    let request = MentionRequest {
+        guild_id,
+        message: new_message,
+        channel_id,
+        prompt: &prompt,
+        author_display_name: &author_display_name,
+        filtered_words: &filtered_words,
+    };
+    let reply = if llm.streams_replies() {
+        stream_reply(ctx, llm, data, &request).await?
+    } else {
+        send_reply(ctx, llm, data, &request).await?
+    };
+    let Some(llm_reply) = reply else {
+        return Ok(());
+    };
+
     if let Err(source) = insert_llm_chat_message(
         &data.db,
         guild_id.get(),
-        new_message.channel_id.get(),
+        channel_id.get(),
         new_message.author.id.get(),
         Some(author_display_name.as_str()),
         "user",
@@ -121,12 +170,10 @@ pub async fn handle_message_mention_llm(
         error!(?source, "failed to persist user llm chat message");
     }
 
-    new_message.reply(&ctx.http, &llm_reply).await?;
-
     if let Err(source) = insert_llm_chat_message(
         &data.db,
         guild_id.get(),
-        new_message.channel_id.get(),
+        channel_id.get(),
         bot_user_id.get(),
         Some(bot_display_name.as_str()),
         "assistant",
@@ -140,6 +187,212 @@ pub async fn handle_message_mention_llm(
     Ok(())
 }
 
+/// Generate the whole reply, then send it. Returns `None` if generation
+/// failed and the user was told so.
+async fn send_reply(
+    ctx: &serenity::Context,
+    llm: &LlmService,
+    data: &Data,
+    request: &MentionRequest<'_>,
+) -> Result<Option<String>, Error> {
+    let llm_reply = match llm
+        .generate_channel_reply(
+            &data.db,
+            request.guild_id.get(),
+            request.channel_id.get(),
+            request.prompt,
+            request.author_display_name,
+        )
+        .await
+    {
+        Ok(content) if !content.trim().is_empty() => {
+            sanitize_reply(&content, request.filtered_words)
+        }
+        Ok(_) => EMPTY_REPLY.to_owned(),
+        Err(source) => {
+            error!(?source, "llm reply generation failed");
+            post_reply(ctx, request, LLM_ERROR_REPLY).await?;
+            return Ok(None);
+        }
+    };
+
+    post_reply(ctx, request, &llm_reply).await?;
+    Ok(Some(llm_reply))
+}
+
+/// Reply with a placeholder and edit it as tokens arrive, at most once per
+/// [`STREAM_EDIT_INTERVAL`] to stay clear of Discord's edit rate limit.
+/// Returns `None` if generation failed and the placeholder says so.
+async fn stream_reply(
+    ctx: &serenity::Context,
+    llm: &LlmService,
+    data: &Data,
+    request: &MentionRequest<'_>,
+) -> Result<Option<String>, Error> {
+    let mut placeholder = post_reply(ctx, request, STREAM_PLACEHOLDER).await?;
+
+    let result = async {
+        let mut stream = llm
+            .stream_channel_reply(
+                &data.db,
+                request.guild_id.get(),
+                request.channel_id.get(),
+                request.prompt,
+                request.author_display_name,
+            )
+            .await?;
+
+        let mut last_edit = Instant::now();
+        while let Some(text) = stream.next_text().await? {
+            if last_edit.elapsed() < STREAM_EDIT_INTERVAL || text.trim().is_empty() {
+                continue;
+            }
+            let edit = serenity::EditMessage::new()
+                .content(sanitize_reply(text, request.filtered_words))
+                .allowed_mentions(serenity::CreateAllowedMentions::new());
+            if let Err(source) = placeholder.edit(&ctx.http, edit).await {
+                warn!(?source, "failed to update streamed llm reply");
+            }
+            last_edit = Instant::now();
+        }
+
+        anyhow::Ok(stream.into_text())
+    }
+    .await;
+
+    let (content, llm_reply) = match result {
+        Ok(content) if !content.is_empty() => {
+            let content = sanitize_reply(&content, request.filtered_words);
+            (content.clone(), Some(content))
+        }
+        Ok(_) => (EMPTY_REPLY.to_owned(), Some(EMPTY_REPLY.to_owned())),
+        Err(source) => {
+            error!(?source, "llm reply streaming failed");
+            (LLM_ERROR_REPLY.to_owned(), None)
+        }
+    };
+
+    placeholder
+        .edit(
+            &ctx.http,
+            serenity::EditMessage::new()
+                .content(content)
+                .allowed_mentions(serenity::CreateAllowedMentions::new()),
+        )
+        .await?;
+    Ok(llm_reply)
+}
+
+/// Reply to the mention, or post in the thread opened for it. Mentions are
+/// never allowed to ping, whatever slips past [`sanitize_reply`].
+async fn post_reply(
+    ctx: &serenity::Context,
+    request: &MentionRequest<'_>,
+    content: &str,
+) -> serenity::Result<serenity::Message> {
+    let mut message = serenity::CreateMessage::new()
+        .content(content)
+        .allowed_mentions(serenity::CreateAllowedMentions::new());
+    if request.channel_id == request.message.channel_id {
+        message = message.reference_message(request.message);
+    }
+
+    request.channel_id.send_message(&ctx.http, message).await
+}
+
+enum ChannelKind {
+    Thread { owned_by_bot: bool },
+    Channel,
+}
+
+/// Whether `channel_id` is a thread, and if so whether Autumn opened it.
+/// Reads the cache first and only asks Discord about unknown channels.
+async fn channel_kind(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    bot_user_id: serenity::UserId,
+) -> ChannelKind {
+    let cached = ctx.cache.guild(guild_id).and_then(|guild| {
+        if let Some(thread) = guild.threads.iter().find(|thread| thread.id == channel_id) {
+            return Some(ChannelKind::Thread {
+                owned_by_bot: thread.owner_id == Some(bot_user_id),
+            });
+        }
+        guild
+            .channels
+            .contains_key(&channel_id)
+            .then_some(ChannelKind::Channel)
+    });
+    if let Some(kind) = cached {
+        return kind;
+    }
+
+    match channel_id.to_channel(&ctx.http).await {
+        Ok(serenity::Channel::Guild(channel)) if channel.thread_metadata.is_some() => {
+            ChannelKind::Thread {
+                owned_by_bot: channel.owner_id == Some(bot_user_id),
+            }
+        }
+        Ok(_) => ChannelKind::Channel,
+        Err(source) => {
+            warn!(?source, "failed to look up channel for llm thread check");
+            ChannelKind::Channel
+        }
+    }
+}
+
+/// Open a thread on the mention for the conversation to continue in.
+/// Returns `None` (reply in the channel instead) if Discord refuses.
+async fn open_conversation_thread(
+    ctx: &serenity::Context,
+    message: &serenity::Message,
+    prompt: &str,
+) -> Option<serenity::ChannelId> {
+    let name: String = prompt
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(THREAD_NAME_MAX_CHARS)
+        .collect();
+
+    let builder = serenity::CreateThread::new(name)
+        .auto_archive_duration(serenity::AutoArchiveDuration::OneDay);
+    match message
+        .channel_id
+        .create_thread_from_message(&ctx.http, message.id, builder)
+        .await
+    {
+        Ok(thread) => Some(thread.id),
+        Err(source) => {
+            warn!(?source, "failed to open llm conversation thread");
+            None
+        }
+    }
+}
+
+/// Make generated text safe to post: a reply containing a filtered word is
+/// replaced outright, mentions are defused so the model can't ping anyone,
+/// and the result fits in one message.
+fn sanitize_reply(content: &str, filtered_words: &[WordFilterPattern]) -> String {
+    if find_matching_pattern(content, filtered_words).is_some() {
+        return FILTERED_REPLY.to_owned();
+    }
+
+    truncate_for_message(&content.replace('@', "@\u{200B}"))
+}
+
+/// Cut a reply down to Discord's message length limit.
+fn truncate_for_message(content: &str) -> String {
+    if content.chars().count() <= MAX_MESSAGE_CHARS {
+        return content.to_owned();
+    }
+    let mut truncated: String = content.chars().take(MAX_MESSAGE_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
 fn strip_bot_mention(content: &str, bot_user_id: serenity::UserId) -> String {
     content
         .replace(&format!("<@{}>", bot_user_id.get()), "")
@@ -164,3 +417,54 @@ fn message_display_name(message: &serenity::Message) -> String {
 
     message.author.name.clone()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(word: &str, match_mode: &str) -> WordFilterPattern {
+        WordFilterPattern {
+            word: word.to_owned(),
+            match_mode: match_mode.to_owned(),
+        }
+    }
+
+    #[test]
+    fn filtered_word_replaces_the_whole_reply() {
+        let filtered = [pattern("badword", "whole")];
+
+        assert_eq!(
+            sanitize_reply("sure, here is a badword for you", &filtered),
+            FILTERED_REPLY
+        );
+        assert_eq!(sanitize_reply("all clean", &filtered), "all clean");
+    }
+
+    #[test]
+    fn mentions_are_defused() {
+        let reply = sanitize_reply("hey @everyone and @here, ask <@123> or <@&456>", &[]);
+
+        assert!(!reply.contains("@everyone"));
+        assert!(!reply.contains("@here"));
+        assert!(!reply.contains("<@123>"));
+        assert!(!reply.contains("<@&456>"));
+        assert!(reply.contains("@\u{200B}everyone"));
+        assert!(reply.contains("<@\u{200B}123>"));
+    }
+
+    #[test]
+    fn long_replies_are_truncated_on_a_char_boundary() {
+        let reply = sanitize_reply(&"é".repeat(MAX_MESSAGE_CHARS + 500), &[]);
+
+        assert_eq!(reply.chars().count(), MAX_MESSAGE_CHARS);
+        assert!(reply.ends_with('…'));
+        assert!(reply.starts_with('é'));
+    }
+
+    #[test]
+    fn short_replies_are_left_whole() {
+        let reply = "é".repeat(MAX_MESSAGE_CHARS);
+
+        assert_eq!(sanitize_reply(&reply, &[]), reply);
+    }
+}
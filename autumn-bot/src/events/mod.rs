@@ -1,3 +1,24 @@
+pub mod antiraid;
+pub mod antispam;
+pub mod attachment_filter;
+pub mod audit;
+pub mod automod;
+pub mod caps_filter;
+pub mod command_aliases;
+pub mod components;
+pub mod external_actions;
+pub mod global_blacklist;
+pub mod guilds;
+pub mod image_filter;
+pub mod invite_filter;
+pub mod link_filter;
 pub mod llm_events;
+pub mod modmail;
+pub mod name_filter;
+pub mod permission_alerts;
+pub mod quarantine;
+pub mod serverlog;
+pub mod shadow_mute;
 pub mod userlog;
+pub mod watchlist;
 pub mod word_filter;
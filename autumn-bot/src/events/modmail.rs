@@ -0,0 +1,560 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_commands::branding::guild_accent_color;
+use autumn_core::Data;
+use autumn_database::impls::modmail::{
+    ModmailConversation, NewModmailMessage, delete_modmail_conversation,
+    get_open_conversation_for_thread, get_open_conversation_for_user, list_modmail_channels,
+    open_modmail_conversation, record_modmail_message, set_modmail_thread,
+};
+use autumn_utils::COMMAND_PREFIX;
+use autumn_utils::embed::guild_embed;
+
+/// How long a member sharing several modmail servers has to pick one.
+const GUILD_PICK_TIMEOUT_SECS: u64 = 60;
+/// Discord's limit on select menu options.
+const MAX_GUILD_CHOICES: usize = 25;
+const THREAD_NAME_MAX_CHARS: usize = 90;
+const DELIVERED_EMOJI: char = '✅';
+const UNDELIVERED_EMOJI: char = '❌';
+
+/// Relay DMs to the member's staff thread, opening one if needed, and staff
+/// messages in those threads back to the member.
+pub async fn handle_message_modmail(
+    ctx: &serenity::Context,
+    data: &Data,
+    new_message: &serenity::Message,
+) {
+    if new_message.author.bot || new_message.webhook_id.is_some() {
+        return;
+    }
+
+    let result = match new_message.guild_id {
+        None => relay_from_member(ctx, data, new_message).await,
+        Some(guild_id) => relay_from_staff(ctx, data, guild_id, new_message).await,
+    };
+    if let Err(source) = result {
+        error!(?source, "modmail relay failed");
+    }
+}
+
+async fn relay_from_member(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+) -> anyhow::Result<()> {
+    let conversation =
+        match get_open_conversation_for_user(&data.db, message.author.id.get()).await? {
+            Some(conversation) => conversation,
+            None => match open_conversation(ctx, data, message).await? {
+                Some(conversation) => conversation,
+                None => return Ok(()),
+            },
+        };
+
+    let Some(thread_id) = conversation.thread_id else {
+        message
+            .reply(
+                &ctx.http,
+                "Your conversation is still being set up. Send that again in a moment.",
+            )
+            .await?;
+        return Ok(());
+    };
+
+    let guild_id = serenity::GuildId::new(conversation.guild_id);
+    let accent = guild_accent_color(&data.db, guild_id.get()).await;
+    let mut embed = guild_embed(accent)
+        .author(
+            serenity::CreateEmbedAuthor::new(message.author.tag()).icon_url(message.author.face()),
+        )
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "User ID: {}",
+            message.author.id
+        )));
+    if !message.content.is_empty() {
+        embed = embed.description(&message.content);
+    }
+    if let Some(attachments) = attachment_lines(message) {
+        embed = embed.field("Attachments", attachments, false);
+    }
+
+    let posted = serenity::ChannelId::new(thread_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await;
+    if let Err(source) = posted {
+        warn!(?source, "failed to post modmail message to staff thread");
+        let _ = message
+            .react(
+                &ctx.http,
+                serenity::ReactionType::Unicode(UNDELIVERED_EMOJI.to_string()),
+            )
+            .await;
+        message
+            .reply(
+                &ctx.http,
+                "I couldn't pass that on to the staff. Try again later.",
+            )
+            .await?;
+        return Ok(());
+    }
+
+    record(data, &conversation, message, false).await;
+    let _ = message
+        .react(
+            &ctx.http,
+            serenity::ReactionType::Unicode(DELIVERED_EMOJI.to_string()),
+        )
+        .await;
+
+    Ok(())
+}
+
+async fn relay_from_staff(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    message: &serenity::Message,
+) -> anyhow::Result<()> {
+    let bot_id = ctx.cache.current_user().id;
+    let channel = cached_channel(ctx, guild_id, message.channel_id);
+    if !is_staff_reply(&message.content, channel, bot_id) {
+        return Ok(());
+    }
+
+    let Some(conversation) =
+        get_open_conversation_for_thread(&data.db, guild_id.get(), message.channel_id.get())
+            .await?
+    else {
+        return Ok(());
+    };
+
+    let accent = guild_accent_color(&data.db, guild_id.get()).await;
+    let guild_name = guild_id
+        .name(&ctx.cache)
+        .unwrap_or_else(|| "Server".to_owned());
+    let mut author = serenity::CreateEmbedAuthor::new(format!("{} staff", guild_name));
+    if let Some(icon_url) = ctx.cache.guild(guild_id).and_then(|guild| guild.icon_url()) {
+        author = author.icon_url(icon_url);
+    }
+    let mut embed = guild_embed(accent).author(author);
+    if !message.content.is_empty() {
+        embed = embed.description(&message.content);
+    }
+    if let Some(attachments) = attachment_lines(message) {
+        embed = embed.field("Attachments", attachments, false);
+    }
+
+    let delivered = serenity::UserId::new(conversation.user_id)
+        .direct_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await;
+    if delivered.is_err() {
+        let _ = message
+            .react(
+                &ctx.http,
+                serenity::ReactionType::Unicode(UNDELIVERED_EMOJI.to_string()),
+            )
+            .await;
+        message
+            .reply(
+                &ctx.http,
+                "Not delivered: the member has DMs closed or left every server we share.",
+            )
+            .await?;
+        return Ok(());
+    }
+
+    record(data, &conversation, message, true).await;
+    let _ = message
+        .react(
+            &ctx.http,
+            serenity::ReactionType::Unicode(DELIVERED_EMOJI.to_string()),
+        )
+        .await;
+
+    Ok(())
+}
+
+/// Open a conversation with the staff of a server the member shares with
+/// the bot, asking which one when there are several. Returns `None` when
+/// nothing was opened; the member has already been told why.
+async fn open_conversation(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+) -> anyhow::Result<Option<ModmailConversation>> {
+    let user = &message.author;
+    let mut choices = Vec::new();
+    for (guild_id, channel_id) in list_modmail_channels(&data.db).await? {
+        let guild_id = serenity::GuildId::new(guild_id);
+        let Some(name) = guild_id.name(&ctx.cache) else {
+            continue;
+        };
+        if guild_id.member(&ctx.http, user.id).await.is_ok() {
+            choices.push((guild_id, serenity::ChannelId::new(channel_id), name));
+        }
+    }
+
+    let (guild_id, channel_id, guild_name) = match choose_guild(choices) {
+        GuildChoice::Nowhere => {
+            message
+                .reply(
+                    &ctx.http,
+                    "None of the servers we share take modmail, so there's no one to pass this on to.",
+                )
+                .await?;
+            return Ok(None);
+        }
+        GuildChoice::Only(choice) => choice,
+        GuildChoice::Ask(choices) => match pick_guild(ctx, message, choices).await? {
+            Some(choice) => choice,
+            None => return Ok(None),
+        },
+    };
+
+    let Some(id) = open_modmail_conversation(&data.db, guild_id.get(), user.id.get()).await? else {
+        // Another message opened one in the meantime.
+        return get_open_conversation_for_user(&data.db, user.id.get()).await;
+    };
+
+    let accent = guild_accent_color(&data.db, guild_id.get()).await;
+    let opener = guild_embed(accent)
+        .title("New Modmail")
+        .author(serenity::CreateEmbedAuthor::new(user.tag()).icon_url(user.face()))
+        .description(format!(
+            "**User :** <@{}>\n**Account Created :** <t:{}:R>\n\n\
+             Reply in the thread to answer. Close with `{}modmail close [note]`.",
+            user.id,
+            user.created_at().unix_timestamp(),
+            COMMAND_PREFIX
+        ))
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "User ID: {}",
+            user.id
+        )));
+
+    let thread = async {
+        let starter = channel_id
+            .send_message(&ctx.http, serenity::CreateMessage::new().embed(opener))
+            .await?;
+        channel_id
+            .create_thread_from_message(
+                &ctx.http,
+                starter.id,
+                serenity::CreateThread::new(thread_name(&user.name))
+                    .auto_archive_duration(serenity::AutoArchiveDuration::OneWeek),
+            )
+            .await
+    }
+    .await;
+
+    let thread = match thread {
+        Ok(thread) => thread,
+        Err(source) => {
+            warn!(?source, "failed to open modmail thread");
+            delete_modmail_conversation(&data.db, id).await?;
+            message
+                .reply(
+                    &ctx.http,
+                    format!(
+                        "I couldn't reach the staff of **{}** right now. Try again later.",
+                        guild_name
+                    ),
+                )
+                .await?;
+            return Ok(None);
+        }
+    };
+    set_modmail_thread(&data.db, id, thread.id.get()).await?;
+
+    let welcome = guild_embed(accent)
+        .title("Message Sent")
+        .description(format!(
+            "Your message went to the staff of **{}**. Their replies will arrive here, \
+             and anything else you send here goes to them too.",
+            guild_name
+        ));
+    message
+        .channel_id
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(welcome))
+        .await?;
+
+    get_open_conversation_for_user(&data.db, user.id.get()).await
+}
+
+/// Ask which server a message is for with a select menu in the DM.
+async fn pick_guild(
+    ctx: &serenity::Context,
+    message: &serenity::Message,
+    choices: Vec<(serenity::GuildId, serenity::ChannelId, String)>,
+) -> anyhow::Result<Option<(serenity::GuildId, serenity::ChannelId, String)>> {
+    let custom_id = format!("modmail_pick_{}", message.id);
+    let options = choices
+        .iter()
+        .map(|(guild_id, _, name)| {
+            serenity::CreateSelectMenuOption::new(name.as_str(), guild_id.to_string())
+        })
+        .collect();
+    let menu = serenity::CreateSelectMenu::new(
+        &custom_id,
+        serenity::CreateSelectMenuKind::String { options },
+    )
+    .placeholder("Choose a server");
+
+    let prompt = message
+        .channel_id
+        .send_message(
+            &ctx.http,
+            serenity::CreateMessage::new()
+                .content("Which server's staff is this for?")
+                .components(vec![serenity::CreateActionRow::SelectMenu(menu)]),
+        )
+        .await?;
+
+    let Some(interaction) = prompt
+        .await_component_interaction(&ctx.shard)
+        .author_id(message.author.id)
+        .custom_ids(vec![custom_id])
+        .timeout(Duration::from_secs(GUILD_PICK_TIMEOUT_SECS))
+        .await
+    else {
+        let _ = prompt.delete(&ctx.http).await;
+        message
+            .reply(&ctx.http, "No server picked, so nothing was sent.")
+            .await?;
+        return Ok(None);
+    };
+
+    let picked = match &interaction.data.kind {
+        serenity::ComponentInteractionDataKind::StringSelect { values } => values.first(),
+        _ => None,
+    }
+    .and_then(|value| find_picked_guild(choices, value));
+
+    let content = match &picked {
+        Some((_, _, name)) => format!("Sending to **{}**.", name),
+        None => "That server isn't available any more.".to_owned(),
+    };
+    interaction
+        .create_response(
+            &ctx.http,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    Ok(picked)
+}
+
+/// Which servers a member could be talking to.
+enum GuildChoice<T> {
+    Nowhere,
+    Only(T),
+    Ask(Vec<T>),
+}
+
+/// Take the only server directly and ask about several, offering as many
+/// as a select menu holds.
+fn choose_guild<T>(mut choices: Vec<T>) -> GuildChoice<T> {
+    choices.truncate(MAX_GUILD_CHOICES);
+    match choices.len() {
+        0 => GuildChoice::Nowhere,
+        1 => GuildChoice::Only(choices.remove(0)),
+        _ => GuildChoice::Ask(choices),
+    }
+}
+
+fn find_picked_guild<T>(
+    choices: Vec<(serenity::GuildId, serenity::ChannelId, T)>,
+    value: &str,
+) -> Option<(serenity::GuildId, serenity::ChannelId, T)> {
+    choices
+        .into_iter()
+        .find(|(guild_id, _, _)| guild_id.to_string() == value)
+}
+
+fn thread_name(user_name: &str) -> String {
+    format!("modmail-{}", user_name)
+        .chars()
+        .take(THREAD_NAME_MAX_CHARS)
+        .collect()
+}
+
+/// What the cache knows about the channel a guild message was sent in.
+enum CachedChannel {
+    Thread { owner_id: Option<serenity::UserId> },
+    Channel,
+    Unknown,
+}
+
+fn cached_channel(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) -> CachedChannel {
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return CachedChannel::Unknown;
+    };
+    if let Some(thread) = guild.threads.iter().find(|thread| thread.id == channel_id) {
+        return CachedChannel::Thread {
+            owner_id: thread.owner_id,
+        };
+    }
+    if guild.channels.contains_key(&channel_id) {
+        CachedChannel::Channel
+    } else {
+        CachedChannel::Unknown
+    }
+}
+
+/// Whether a guild message may be a staff reply worth looking up. Modmail
+/// threads are opened by the bot, so the cache rules out ordinary channels
+/// and other threads; anything it doesn't know is checked against the
+/// database. Commands, like `!modmail close`, stay in the thread.
+fn is_staff_reply(content: &str, channel: CachedChannel, bot_id: serenity::UserId) -> bool {
+    if content.starts_with(COMMAND_PREFIX) {
+        return false;
+    }
+    match channel {
+        CachedChannel::Thread { owner_id } => owner_id == Some(bot_id),
+        CachedChannel::Channel => false,
+        CachedChannel::Unknown => true,
+    }
+}
+
+async fn record(
+    data: &Data,
+    conversation: &ModmailConversation,
+    message: &serenity::Message,
+    from_staff: bool,
+) {
+    let attachments: Vec<String> = message
+        .attachments
+        .iter()
+        .map(|attachment| attachment.url.clone())
+        .collect();
+    let recorded = record_modmail_message(
+        &data.db,
+        NewModmailMessage {
+            conversation_id: conversation.id,
+            author_user_id: message.author.id.get(),
+            author_name: &message.author.tag(),
+            from_staff,
+            content: &message.content,
+            attachments: &attachments,
+        },
+    )
+    .await;
+    if let Err(source) = recorded {
+        // The message was relayed; only the transcript misses it.
+        error!(?source, "failed to record modmail message");
+    }
+}
+
+fn attachment_lines(message: &serenity::Message) -> Option<String> {
+    (!message.attachments.is_empty()).then(|| {
+        message
+            .attachments
+            .iter()
+            .map(|attachment| format!("[{}]({})", attachment.filename, attachment.url))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOT_ID: serenity::UserId = serenity::UserId::new(1);
+
+    fn choice(guild_id: u64, name: &str) -> (serenity::GuildId, serenity::ChannelId, String) {
+        (
+            serenity::GuildId::new(guild_id),
+            serenity::ChannelId::new(guild_id * 10),
+            name.to_owned(),
+        )
+    }
+
+    #[test]
+    fn replies_in_bot_threads_are_relayed() {
+        let thread = CachedChannel::Thread {
+            owner_id: Some(BOT_ID),
+        };
+
+        assert!(is_staff_reply("we're looking into it", thread, BOT_ID));
+    }
+
+    #[test]
+    fn other_channels_and_threads_are_ignored() {
+        let foreign_thread = CachedChannel::Thread {
+            owner_id: Some(serenity::UserId::new(2)),
+        };
+        let ownerless_thread = CachedChannel::Thread { owner_id: None };
+
+        assert!(!is_staff_reply("hello", foreign_thread, BOT_ID));
+        assert!(!is_staff_reply("hello", ownerless_thread, BOT_ID));
+        assert!(!is_staff_reply("hello", CachedChannel::Channel, BOT_ID));
+    }
+
+    #[test]
+    fn uncached_channels_are_looked_up() {
+        assert!(is_staff_reply("hello", CachedChannel::Unknown, BOT_ID));
+    }
+
+    #[test]
+    fn commands_stay_in_the_thread() {
+        let thread = CachedChannel::Thread {
+            owner_id: Some(BOT_ID),
+        };
+        let command = format!("{}modmail close resolved", COMMAND_PREFIX);
+
+        assert!(!is_staff_reply(&command, thread, BOT_ID));
+        assert!(!is_staff_reply(&command, CachedChannel::Unknown, BOT_ID));
+    }
+
+    #[test]
+    fn single_server_is_taken_without_asking() {
+        assert!(matches!(
+            choose_guild(Vec::<u8>::new()),
+            GuildChoice::Nowhere
+        ));
+        assert!(matches!(choose_guild(vec![7]), GuildChoice::Only(7)));
+    }
+
+    #[test]
+    fn several_servers_are_offered_up_to_the_menu_limit() {
+        let GuildChoice::Ask(choices) = choose_guild((0..40).collect::<Vec<_>>()) else {
+            panic!("expected a choice");
+        };
+
+        assert_eq!(choices.len(), MAX_GUILD_CHOICES);
+        assert_eq!(choices[0], 0);
+
+        assert!(matches!(choose_guild(vec![1, 2]), GuildChoice::Ask(choices) if choices == [1, 2]));
+    }
+
+    #[test]
+    fn picked_guild_is_matched_by_id() {
+        let choices = vec![choice(100, "Alpha"), choice(200, "Beta")];
+
+        let picked = find_picked_guild(choices.clone(), "200").map(|(_, _, name)| name);
+        assert_eq!(picked.as_deref(), Some("Beta"));
+        assert!(find_picked_guild(choices, "300").is_none());
+    }
+
+    #[test]
+    fn thread_names_are_capped() {
+        assert_eq!(thread_name("river"), "modmail-river");
+
+        let long = thread_name(&"ä".repeat(200));
+        assert_eq!(long.chars().count(), THREAD_NAME_MAX_CHARS);
+        assert!(long.starts_with("modmail-"));
+    }
+}
@@ -0,0 +1,236 @@
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_core::Data;
+use autumn_database::impls::cases::{NewCase, create_case};
+use autumn_database::impls::exemptions::{is_exempt, list_exemptions};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_database::impls::modlog_template::get_modlog_layout;
+use autumn_database::impls::name_filter::get_name_filter_if_enabled;
+use autumn_database::impls::word_filter::{find_matching_pattern, get_all_filter_words_for_guild};
+use autumn_database::model::cases::CaseSummary;
+use autumn_utils::formatting::format_case_label;
+use autumn_utils::modlog_template::{ModlogField, build_modlog_embed};
+
+/// Check a newly joined member's name against the word filter list.
+pub async fn handle_member_addition_name_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    member: &serenity::Member,
+) {
+    let role_ids: Vec<u64> = member.roles.iter().map(|role_id| role_id.get()).collect();
+    check_member_name(
+        ctx,
+        data,
+        member.guild_id,
+        &member.user,
+        member.nick.as_deref(),
+        &role_ids,
+    )
+    .await;
+}
+
+/// Check a member's name again after a nickname or profile change.
+pub async fn handle_member_update_name_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    event: &serenity::GuildMemberUpdateEvent,
+) {
+    let role_ids: Vec<u64> = event.roles.iter().map(|role_id| role_id.get()).collect();
+    check_member_name(
+        ctx,
+        data,
+        event.guild_id,
+        &event.user,
+        event.nick.as_deref(),
+        &role_ids,
+    )
+    .await;
+}
+
+/// Run the member's visible name (nickname, else display name and username)
+/// through the word filter list and apply the name filter action. Only the
+/// visible name is checked so a clean placeholder nickname ends the loop of
+/// renames triggering updates.
+async fn check_member_name(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    user: &serenity::User,
+    nick: Option<&str>,
+    role_ids: &[u64],
+) {
+    if user.bot {
+        return;
+    }
+
+    let config = match get_name_filter_if_enabled(&data.db, guild_id.get()).await {
+        Ok(Some(cfg)) => cfg,
+        Ok(None) => return,
+        Err(source) => {
+            error!(?source, "failed to read name filter config");
+            return;
+        }
+    };
+
+    let names: Vec<&str> = match nick {
+        Some(nick) => vec![nick],
+        None => user
+            .global_name
+            .as_deref()
+            .into_iter()
+            .chain(std::iter::once(user.name.as_str()))
+            .collect(),
+    };
+
+    // Word filter user and role exemptions also cover names.
+    let exemptions = match list_exemptions(&data.db, guild_id.get(), "word_filter").await {
+        Ok(list) => list,
+        Err(source) => {
+            error!(?source, "failed to load word filter exemptions");
+            Vec::new()
+        }
+    };
+
+    if is_exempt(&exemptions, user.id.get(), None, role_ids) {
+        return;
+    }
+
+    let words = match get_all_filter_words_for_guild(&data.db, guild_id.get()).await {
+        Ok(w) => w,
+        Err(source) => {
+            error!(?source, "failed to load word filter list");
+            return;
+        }
+    };
+
+    if words.is_empty() {
+        return;
+    }
+
+    let Some((name, matched)) = names.iter().find_map(|name| {
+        find_matching_pattern(name, &words).map(|pattern| (*name, pattern.word.clone()))
+    }) else {
+        return;
+    };
+
+    let reason = format!("Name filter: \"{}\" matched a filtered word", name);
+
+    let action = match config.action.as_str() {
+        "rename" => {
+            let placeholder = config.placeholder.as_str();
+            if nick == Some(placeholder) {
+                return;
+            }
+
+            let edit = serenity::EditMember::new()
+                .nickname(placeholder)
+                .audit_log_reason(&reason);
+            if let Err(source) = guild_id.edit_member(&ctx.http, user.id, edit).await {
+                warn!(?source, user_id = %user.id, "failed to rename member for name filter");
+                return;
+            }
+            "rename"
+        }
+        "kick" => {
+            if let Err(source) = guild_id.kick_with_reason(&ctx.http, user.id, &reason).await {
+                warn!(?source, user_id = %user.id, "failed to kick member for name filter");
+                return;
+            }
+            "kick"
+        }
+        _ => "log",
+    };
+
+    let bot_user_id = ctx.cache.current_user().id.get();
+    let case_action = format!("name_filter_{}", action);
+    let violation = format!("Name \"{}\" matched `{}`", name, matched);
+
+    let case = match create_case(
+        &data.db,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(user.id.get()),
+            moderator_user_id: bot_user_id,
+            action: &case_action,
+            reason: &violation,
+            status: "completed",
+            duration_seconds: None,
+        },
+    )
+    .await
+    {
+        Ok(case) => case,
+        Err(source) => {
+            error!(?source, "failed to create name filter case");
+            return;
+        }
+    };
+
+    if let Err(source) = publish_name_filter_to_modlog(ctx, data, guild_id, &case, action).await {
+        error!(
+            ?source,
+            "failed to publish name filter case to modlog channel"
+        );
+    }
+}
+
+async fn publish_name_filter_to_modlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    case: &CaseSummary,
+    action: &str,
+) -> Result<(), serenity::Error> {
+    let channel_id = match get_modlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Ok(()),
+        Err(source) => {
+            error!(?source, "failed to read modlog channel for name filter");
+            return Ok(());
+        }
+    };
+
+    let case_label = format_case_label(&case.case_code, case.action_case_number);
+
+    let action_label = match action {
+        "rename" => "Renamed",
+        "kick" => "Kicked",
+        _ => "Log Only",
+    };
+
+    let layout = match get_modlog_layout(&data.db, guild_id.get()).await {
+        Ok(layout) => layout,
+        Err(source) => {
+            error!(?source, "failed to read modlog template");
+            None
+        }
+    };
+
+    let fields = [
+        ModlogField::new(
+            "target",
+            format!("**User :** <@{}>", case.target_user_id.unwrap_or(0)),
+        ),
+        ModlogField::new(
+            "reason",
+            format!("**Violation :** {}", case.reason.replace('@', "@\u{200B}")),
+        ),
+        ModlogField::new("action", format!("**Action Taken :** {}", action_label)),
+        ModlogField::separator(),
+        ModlogField::new("when", format!("**When :** <t:{}:R>", case.created_at)),
+    ];
+
+    let embed = build_modlog_embed(
+        format!("Name Filter Violation - #{}", case_label),
+        &fields,
+        layout.as_ref(),
+        &[("{case}", &case_label), ("{action}", "Name Filter")],
+    );
+
+    serenity::ChannelId::new(channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
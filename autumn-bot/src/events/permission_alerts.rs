@@ -0,0 +1,342 @@
+use poise::serenity_prelude as serenity;
+use serenity::audit_log::{Action, ChannelOverwriteAction, RoleAction};
+use tracing::error;
+
+use autumn_commands::branding::guild_accent_color;
+use autumn_core::Data;
+use autumn_database::impls::permission_alerts::get_permission_alert_channel_id;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::time::now_unix_secs;
+
+use super::audit::find_recent_audit_entry;
+
+/// Alert on a new role that already carries Administrator.
+pub async fn handle_role_create_permission_alerts(
+    ctx: &serenity::Context,
+    data: &Data,
+    role: &serenity::Role,
+) {
+    let granted = granted_role_permissions(
+        role.guild_id,
+        role.id,
+        serenity::Permissions::empty(),
+        role.permissions,
+    );
+    if granted.is_empty() {
+        return;
+    }
+
+    publish_permission_alert(
+        ctx,
+        data,
+        PermissionAlert {
+            guild_id: role.guild_id,
+            action: Action::Role(RoleAction::Create),
+            target_id: role.id.get(),
+            lines: vec![
+                format!(
+                    "**Role Created :** {}",
+                    role_display(role.guild_id, role.id)
+                ),
+                format!(
+                    "**Granted :** {}",
+                    granted.get_permission_names().join(", ")
+                ),
+            ],
+        },
+    )
+    .await;
+}
+
+/// Alert when a role gains Administrator, or @everyone gains Mention
+/// Everyone.
+pub async fn handle_role_update_permission_alerts(
+    ctx: &serenity::Context,
+    data: &Data,
+    old: Option<&serenity::Role>,
+    new: &serenity::Role,
+) {
+    let Some(old) = old else {
+        return;
+    };
+
+    let granted = granted_role_permissions(new.guild_id, new.id, old.permissions, new.permissions);
+    if granted.is_empty() {
+        return;
+    }
+
+    publish_permission_alert(
+        ctx,
+        data,
+        PermissionAlert {
+            guild_id: new.guild_id,
+            action: Action::Role(RoleAction::Update),
+            target_id: new.id.get(),
+            lines: vec![
+                format!("**Role :** {}", role_display(new.guild_id, new.id)),
+                format!(
+                    "**Granted :** {}",
+                    granted.get_permission_names().join(", ")
+                ),
+            ],
+        },
+    )
+    .await;
+}
+
+/// Alert when a channel overwrite starts allowing Manage Webhooks. One alert
+/// per overwrite, since each has its own audit log entry.
+pub async fn handle_channel_update_permission_alerts(
+    ctx: &serenity::Context,
+    data: &Data,
+    old: Option<&serenity::GuildChannel>,
+    new: &serenity::GuildChannel,
+) {
+    let Some(old) = old else {
+        return;
+    };
+
+    for grant in granted_overwrites(&old.permission_overwrites, &new.permission_overwrites) {
+        let action = if grant.existed {
+            ChannelOverwriteAction::Update
+        } else {
+            ChannelOverwriteAction::Create
+        };
+
+        publish_permission_alert(
+            ctx,
+            data,
+            PermissionAlert {
+                guild_id: new.guild_id,
+                action: Action::ChannelOverwrite(action),
+                target_id: new.id.get(),
+                lines: vec![
+                    format!("**Channel :** <#{}>", new.id.get()),
+                    format!(
+                        "**Overwrite For :** {}",
+                        overwrite_target_display(new.guild_id, grant.kind)
+                    ),
+                    format!(
+                        "**Granted :** {}",
+                        grant.granted.get_permission_names().join(", ")
+                    ),
+                ],
+            },
+        )
+        .await;
+    }
+}
+
+/// The permissions worth an alert that a role gained going from `before` to
+/// `after`.
+fn granted_role_permissions(
+    guild_id: serenity::GuildId,
+    role_id: serenity::RoleId,
+    before: serenity::Permissions,
+    after: serenity::Permissions,
+) -> serenity::Permissions {
+    let dangerous = if role_id.get() == guild_id.get() {
+        serenity::Permissions::ADMINISTRATOR | serenity::Permissions::MENTION_EVERYONE
+    } else {
+        serenity::Permissions::ADMINISTRATOR
+    };
+    dangerous & (after - before)
+}
+
+/// An overwrite that started allowing something worth an alert.
+struct OverwriteGrant {
+    kind: serenity::PermissionOverwriteType,
+    granted: serenity::Permissions,
+    /// Whether the overwrite was there before, as opposed to newly added.
+    existed: bool,
+}
+
+fn granted_overwrites(
+    old: &[serenity::PermissionOverwrite],
+    new: &[serenity::PermissionOverwrite],
+) -> Vec<OverwriteGrant> {
+    new.iter()
+        .filter_map(|overwrite| {
+            let previous = old.iter().find(|previous| previous.kind == overwrite.kind);
+            let granted = serenity::Permissions::MANAGE_WEBHOOKS
+                & (overwrite.allow - previous.map_or(serenity::Permissions::empty(), |p| p.allow));
+            (!granted.is_empty()).then_some(OverwriteGrant {
+                kind: overwrite.kind,
+                granted,
+                existed: previous.is_some(),
+            })
+        })
+        .collect()
+}
+
+fn role_display(guild_id: serenity::GuildId, role_id: serenity::RoleId) -> String {
+    if role_id.get() == guild_id.get() {
+        "@everyone".to_owned()
+    } else {
+        format!("<@&{}>", role_id.get())
+    }
+}
+
+fn overwrite_target_display(
+    guild_id: serenity::GuildId,
+    kind: serenity::PermissionOverwriteType,
+) -> String {
+    match kind {
+        serenity::PermissionOverwriteType::Role(role_id) => role_display(guild_id, role_id),
+        serenity::PermissionOverwriteType::Member(user_id) => format!("<@{}>", user_id.get()),
+        _ => "Unknown".to_owned(),
+    }
+}
+
+struct PermissionAlert {
+    guild_id: serenity::GuildId,
+    action: Action,
+    target_id: u64,
+    lines: Vec<String>,
+}
+
+/// Attribute the change via the audit log and send the alert to the
+/// configured staff channel.
+async fn publish_permission_alert(ctx: &serenity::Context, data: &Data, alert: PermissionAlert) {
+    let alert_channel_id =
+        match get_permission_alert_channel_id(&data.db, alert.guild_id.get()).await {
+            Ok(channel_id) => channel_id,
+            Err(source) => {
+                error!(?source, "failed to read permission alert channel config");
+                None
+            }
+        };
+
+    let Some(target_channel_id) = alert_channel_id else {
+        return;
+    };
+
+    let now = now_unix_secs();
+    let event_unix = i64::try_from(now).unwrap_or(i64::MAX);
+    let audit_entry = find_recent_audit_entry(
+        ctx,
+        alert.guild_id,
+        alert.action,
+        alert.target_id,
+        event_unix,
+    )
+    .await;
+
+    let mut lines = alert.lines;
+    match &audit_entry {
+        Some(audit_entry) => {
+            lines.push(format!(
+                "**By :** <@{}> ({})",
+                audit_entry.user_id.get(),
+                audit_entry.user_id.get()
+            ));
+            if let Some(reason) = audit_entry.reason.as_deref() {
+                lines.push(format!("**Reason :** {}", reason));
+            }
+        }
+        None => lines.push("**By :** Unknown".to_owned()),
+    }
+    lines.push(format!("**When :** <t:{}:R>", now));
+
+    let embed = guild_embed(guild_accent_color(&data.db, alert.guild_id.get()).await)
+        .title("Dangerous Permission Granted")
+        .description(lines.join("\n"));
+
+    if let Err(source) = serenity::ChannelId::new(target_channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await
+    {
+        error!(?source, "failed to publish permission alert");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: serenity::GuildId = serenity::GuildId::new(1);
+    const EVERYONE: serenity::RoleId = serenity::RoleId::new(1);
+    const MODS: serenity::RoleId = serenity::RoleId::new(2);
+
+    fn overwrite(role_id: u64, allow: serenity::Permissions) -> serenity::PermissionOverwrite {
+        serenity::PermissionOverwrite {
+            allow,
+            deny: serenity::Permissions::empty(),
+            kind: serenity::PermissionOverwriteType::Role(serenity::RoleId::new(role_id)),
+        }
+    }
+
+    #[test]
+    fn gaining_administrator_alerts() {
+        let granted = granted_role_permissions(
+            GUILD,
+            MODS,
+            serenity::Permissions::KICK_MEMBERS,
+            serenity::Permissions::KICK_MEMBERS | serenity::Permissions::ADMINISTRATOR,
+        );
+
+        assert_eq!(granted, serenity::Permissions::ADMINISTRATOR);
+    }
+
+    #[test]
+    fn permissions_already_held_do_not_alert() {
+        let admin = serenity::Permissions::ADMINISTRATOR;
+
+        assert!(granted_role_permissions(GUILD, MODS, admin, admin).is_empty());
+        assert!(
+            granted_role_permissions(
+                GUILD,
+                MODS,
+                admin,
+                admin | serenity::Permissions::BAN_MEMBERS
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn mention_everyone_only_alerts_for_everyone() {
+        let before = serenity::Permissions::empty();
+        let after = serenity::Permissions::MENTION_EVERYONE;
+
+        assert!(granted_role_permissions(GUILD, MODS, before, after).is_empty());
+        assert_eq!(
+            granted_role_permissions(GUILD, EVERYONE, before, after),
+            serenity::Permissions::MENTION_EVERYONE
+        );
+    }
+
+    #[test]
+    fn new_webhook_overwrites_alert_as_created() {
+        let new = [overwrite(
+            2,
+            serenity::Permissions::MANAGE_WEBHOOKS | serenity::Permissions::SEND_MESSAGES,
+        )];
+
+        let grants = granted_overwrites(&[], &new);
+
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].granted, serenity::Permissions::MANAGE_WEBHOOKS);
+        assert_eq!(grants[0].kind, new[0].kind);
+        assert!(!grants[0].existed);
+    }
+
+    #[test]
+    fn changed_overwrites_alert_only_on_new_webhook_access() {
+        let old = [
+            overwrite(2, serenity::Permissions::SEND_MESSAGES),
+            overwrite(3, serenity::Permissions::MANAGE_WEBHOOKS),
+        ];
+        let new = [
+            overwrite(2, serenity::Permissions::MANAGE_WEBHOOKS),
+            overwrite(3, serenity::Permissions::MANAGE_WEBHOOKS),
+            overwrite(4, serenity::Permissions::SEND_MESSAGES),
+        ];
+
+        let grants = granted_overwrites(&old, &new);
+
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].kind, old[0].kind);
+        assert!(grants[0].existed);
+    }
+}
@@ -0,0 +1,77 @@
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_commands::moderation::quarantine::quarantine_overwrite;
+use autumn_core::Data;
+use autumn_database::impls::quarantine::{get_quarantine, get_quarantine_role_id};
+
+/// Deny the quarantine role in a new channel, like the channels that
+/// existed when the role was set up.
+pub async fn handle_channel_create_quarantine(
+    ctx: &serenity::Context,
+    data: &Data,
+    channel: &serenity::GuildChannel,
+) {
+    let role_id = match get_quarantine_role_id(&data.db, channel.guild_id.get()).await {
+        Ok(Some(role_id)) => serenity::RoleId::new(role_id),
+        Ok(None) => return,
+        Err(source) => {
+            error!(?source, "failed to read quarantine role");
+            return;
+        }
+    };
+
+    let overwrite = quarantine_overwrite(role_id);
+    // Channels created in a category can inherit the overwrite already.
+    if channel
+        .permission_overwrites
+        .iter()
+        .any(|existing| existing.kind == overwrite.kind && existing.deny.contains(overwrite.deny))
+    {
+        return;
+    }
+
+    if let Err(source) = channel.create_permission(&ctx.http, overwrite).await {
+        warn!(?source, channel_id = %channel.id, "failed to deny quarantine role in new channel");
+    }
+}
+
+/// Put the quarantine role back on a quarantined member who left and
+/// rejoined, so leaving doesn't lift the quarantine.
+pub async fn handle_member_addition_quarantine(
+    ctx: &serenity::Context,
+    data: &Data,
+    member: &serenity::Member,
+) {
+    let guild_id = member.guild_id.get();
+    match get_quarantine(&data.db, guild_id, member.user.id.get()).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return,
+        Err(source) => {
+            error!(?source, "failed to read quarantine");
+            return;
+        }
+    }
+
+    let role_id = match get_quarantine_role_id(&data.db, guild_id).await {
+        Ok(Some(role_id)) => serenity::RoleId::new(role_id),
+        Ok(None) => return,
+        Err(source) => {
+            error!(?source, "failed to read quarantine role");
+            return;
+        }
+    };
+
+    if let Err(source) = ctx
+        .http
+        .add_member_role(
+            member.guild_id,
+            member.user.id,
+            role_id,
+            Some("Rejoined while quarantined"),
+        )
+        .await
+    {
+        warn!(?source, user_id = %member.user.id, "failed to reapply quarantine role");
+    }
+}
@@ -0,0 +1,344 @@
+use poise::serenity_prelude as serenity;
+use serenity::audit_log::{Action, ChannelAction, RoleAction};
+use tracing::error;
+
+use autumn_commands::branding::guild_accent_color;
+use autumn_core::Data;
+use autumn_database::impls::serverlog_config::get_serverlog_channel_id;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::time::now_unix_secs;
+
+use super::audit::find_recent_audit_entry;
+
+pub async fn handle_channel_create_serverlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    channel: &serenity::GuildChannel,
+) {
+    let mut lines = vec![
+        format!("**Channel :** <#{}> (`{}`)", channel.id.get(), channel.name),
+        format!("**Type :** {}", channel_kind_display(channel.kind)),
+    ];
+    if let Some(parent_id) = channel.parent_id {
+        lines.push(format!("**Category :** <#{}>", parent_id.get()));
+    }
+
+    publish_serverlog(
+        ctx,
+        data,
+        ServerLogEntry {
+            guild_id: channel.guild_id,
+            title: "Channel Created",
+            action: Action::Channel(ChannelAction::Create),
+            target_id: channel.id.get(),
+            lines,
+        },
+    )
+    .await;
+}
+
+pub async fn handle_channel_delete_serverlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    channel: &serenity::GuildChannel,
+) {
+    let lines = vec![
+        format!("**Channel :** `#{}` ({})", channel.name, channel.id.get()),
+        format!("**Type :** {}", channel_kind_display(channel.kind)),
+    ];
+
+    publish_serverlog(
+        ctx,
+        data,
+        ServerLogEntry {
+            guild_id: channel.guild_id,
+            title: "Channel Deleted",
+            action: Action::Channel(ChannelAction::Delete),
+            target_id: channel.id.get(),
+            lines,
+        },
+    )
+    .await;
+}
+
+pub async fn handle_channel_update_serverlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    old: Option<&serenity::GuildChannel>,
+    new: &serenity::GuildChannel,
+) {
+    // Without the previous state there is nothing to diff against.
+    let Some(old) = old else {
+        return;
+    };
+
+    let mut changes = Vec::new();
+    if old.name != new.name {
+        changes.push(format!("**Name :** `{}` → `{}`", old.name, new.name));
+    }
+    if old.topic != new.topic {
+        changes.push(format!(
+            "**Topic :** {} → {}",
+            optional_text_display(old.topic.as_deref()),
+            optional_text_display(new.topic.as_deref())
+        ));
+    }
+    if old.nsfw != new.nsfw {
+        changes.push(format!(
+            "**NSFW :** {} → {}",
+            yes_no(old.nsfw),
+            yes_no(new.nsfw)
+        ));
+    }
+    if old.rate_limit_per_user != new.rate_limit_per_user {
+        changes.push(format!(
+            "**Slowmode :** {}s → {}s",
+            old.rate_limit_per_user.unwrap_or(0),
+            new.rate_limit_per_user.unwrap_or(0)
+        ));
+    }
+    if old.parent_id != new.parent_id {
+        changes.push(format!(
+            "**Category :** {} → {}",
+            channel_mention_display(old.parent_id),
+            channel_mention_display(new.parent_id)
+        ));
+    }
+    if old.permission_overwrites != new.permission_overwrites {
+        changes.push("**Permissions :** overwrites changed".to_owned());
+    }
+
+    // Position-only moves and other untracked fields are not worth a log entry.
+    if changes.is_empty() {
+        return;
+    }
+
+    let mut lines = vec![format!("**Channel :** <#{}>", new.id.get())];
+    lines.extend(changes);
+
+    publish_serverlog(
+        ctx,
+        data,
+        ServerLogEntry {
+            guild_id: new.guild_id,
+            title: "Channel Updated",
+            action: Action::Channel(ChannelAction::Update),
+            target_id: new.id.get(),
+            lines,
+        },
+    )
+    .await;
+}
+
+pub async fn handle_role_create_serverlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    role: &serenity::Role,
+) {
+    let lines = vec![
+        format!("**Role :** <@&{}> (`{}`)", role.id.get(), role.name),
+        format!("**Color :** {}", color_display(role.colour)),
+    ];
+
+    publish_serverlog(
+        ctx,
+        data,
+        ServerLogEntry {
+            guild_id: role.guild_id,
+            title: "Role Created",
+            action: Action::Role(RoleAction::Create),
+            target_id: role.id.get(),
+            lines,
+        },
+    )
+    .await;
+}
+
+pub async fn handle_role_delete_serverlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    role_id: serenity::RoleId,
+    role: Option<&serenity::Role>,
+) {
+    let role_display = match role {
+        Some(role) => format!("`{}` ({})", role.name, role_id.get()),
+        None => format!("`{}`", role_id.get()),
+    };
+
+    publish_serverlog(
+        ctx,
+        data,
+        ServerLogEntry {
+            guild_id,
+            title: "Role Deleted",
+            action: Action::Role(RoleAction::Delete),
+            target_id: role_id.get(),
+            lines: vec![format!("**Role :** {}", role_display)],
+        },
+    )
+    .await;
+}
+
+pub async fn handle_role_update_serverlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    old: Option<&serenity::Role>,
+    new: &serenity::Role,
+) {
+    let Some(old) = old else {
+        return;
+    };
+
+    let mut changes = Vec::new();
+    if old.name != new.name {
+        changes.push(format!("**Name :** `{}` → `{}`", old.name, new.name));
+    }
+    if old.colour != new.colour {
+        changes.push(format!(
+            "**Color :** {} → {}",
+            color_display(old.colour),
+            color_display(new.colour)
+        ));
+    }
+    if old.hoist != new.hoist {
+        changes.push(format!(
+            "**Hoisted :** {} → {}",
+            yes_no(old.hoist),
+            yes_no(new.hoist)
+        ));
+    }
+    if old.mentionable != new.mentionable {
+        changes.push(format!(
+            "**Mentionable :** {} → {}",
+            yes_no(old.mentionable),
+            yes_no(new.mentionable)
+        ));
+    }
+
+    let granted = new.permissions - old.permissions;
+    if !granted.is_empty() {
+        changes.push(format!(
+            "**Permissions Granted :** {}",
+            granted.get_permission_names().join(", ")
+        ));
+    }
+    let revoked = old.permissions - new.permissions;
+    if !revoked.is_empty() {
+        changes.push(format!(
+            "**Permissions Revoked :** {}",
+            revoked.get_permission_names().join(", ")
+        ));
+    }
+
+    // Position changes fire an update for every role that shifted; skip them.
+    if changes.is_empty() {
+        return;
+    }
+
+    let mut lines = vec![format!("**Role :** <@&{}>", new.id.get())];
+    lines.extend(changes);
+
+    publish_serverlog(
+        ctx,
+        data,
+        ServerLogEntry {
+            guild_id: new.guild_id,
+            title: "Role Updated",
+            action: Action::Role(RoleAction::Update),
+            target_id: new.id.get(),
+            lines,
+        },
+    )
+    .await;
+}
+
+struct ServerLogEntry {
+    guild_id: serenity::GuildId,
+    title: &'static str,
+    action: Action,
+    target_id: u64,
+    lines: Vec<String>,
+}
+
+/// Attribute the change via the audit log and send the embed to the
+/// configured server log channel.
+async fn publish_serverlog(ctx: &serenity::Context, data: &Data, entry: ServerLogEntry) {
+    let serverlog_channel_id = match get_serverlog_channel_id(&data.db, entry.guild_id.get()).await
+    {
+        Ok(channel_id) => channel_id,
+        Err(source) => {
+            error!(?source, "failed to read server log channel config");
+            None
+        }
+    };
+
+    let Some(target_channel_id) = serverlog_channel_id else {
+        return;
+    };
+
+    let now = now_unix_secs();
+    let event_unix = i64::try_from(now).unwrap_or(i64::MAX);
+    let audit_entry = find_recent_audit_entry(
+        ctx,
+        entry.guild_id,
+        entry.action,
+        entry.target_id,
+        event_unix,
+    )
+    .await;
+
+    let mut lines = entry.lines;
+    match &audit_entry {
+        Some(audit_entry) => {
+            lines.push(format!("**By :** <@{}>", audit_entry.user_id.get()));
+            if let Some(reason) = audit_entry.reason.as_deref() {
+                lines.push(format!("**Reason :** {}", reason));
+            }
+        }
+        None => lines.push("**By :** Unknown".to_owned()),
+    }
+    lines.push(format!("**When :** <t:{}:R>", now));
+
+    let embed = guild_embed(guild_accent_color(&data.db, entry.guild_id.get()).await)
+        .title(entry.title)
+        .description(lines.join("\n"));
+
+    if let Err(source) = serenity::ChannelId::new(target_channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await
+    {
+        error!(?source, "failed to publish server log embed");
+    }
+}
+
+fn channel_kind_display(kind: serenity::ChannelType) -> &'static str {
+    match kind {
+        serenity::ChannelType::Text => "Text",
+        serenity::ChannelType::Voice => "Voice",
+        serenity::ChannelType::Category => "Category",
+        serenity::ChannelType::News => "Announcement",
+        serenity::ChannelType::Stage => "Stage",
+        serenity::ChannelType::Forum => "Forum",
+        _ => "Other",
+    }
+}
+
+fn channel_mention_display(channel_id: Option<serenity::ChannelId>) -> String {
+    channel_id.map_or_else(|| "None".to_owned(), |id| format!("<#{}>", id.get()))
+}
+
+fn optional_text_display(value: Option<&str>) -> String {
+    match value.filter(|value| !value.is_empty()) {
+        Some(value) => format!("`{}`", value.replace('`', "'")),
+        None => "None".to_owned(),
+    }
+}
+
+fn color_display(colour: serenity::Colour) -> String {
+    format!("`#{:06X}`", colour.0)
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "Yes" } else { "No" }
+}
@@ -0,0 +1,25 @@
+use poise::serenity_prelude as serenity;
+
+use autumn_core::Data;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::time::now_unix_secs;
+
+use crate::events::automod::delete_automod_message;
+
+/// Delete a message from a shadow-muted member, quietly: no case, no DM and
+/// no user log entry. Returns `true` when the message was theirs, so the
+/// other message handlers can skip it.
+pub async fn handle_message_shadow_mute(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) -> bool {
+    if !config.is_shadow_muted(message.author.id.get(), now_unix_secs()) {
+        return false;
+    }
+
+    delete_automod_message(ctx, data, message).await;
+
+    true
+}
@@ -0,0 +1,110 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::Data;
+use autumn_core::storage::message_attachment_prefix;
+use autumn_database::impls::userlog_config::get_userlog_channel_id;
+
+use super::media::{download_media_bytes, sanitize_attachment_filename};
+
+/// Copies a new message's attachments into the attachment store so they can be
+/// re-uploaded if the message is deleted after the CDN links expire. Only runs
+/// for guilds with a user log channel. The files go when the message is
+/// deleted or its snapshot ages out in the retention sweep.
+pub async fn archive_message_attachments(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    message: &serenity::Message,
+) {
+    let Some(store) = data.attachment_store.as_deref() else {
+        return;
+    };
+
+    if message.attachments.is_empty() {
+        return;
+    }
+
+    match get_userlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return,
+        Err(source) => {
+            error!(
+                ?source,
+                "failed to read user log channel config for archival"
+            );
+            return;
+        }
+    }
+
+    let prefix = archive_prefix(guild_id, message.channel_id, message.id);
+    for (index, attachment) in message.attachments.iter().enumerate() {
+        let Some(bytes) = download_media_bytes(&attachment.url).await else {
+            error!(url = %attachment.url, "failed to download attachment for archival");
+            continue;
+        };
+
+        let key = archive_key(&prefix, index, &attachment.filename);
+        if let Err(source) = store.put(&key, bytes).await {
+            error!(?source, "failed to archive attachment");
+        }
+    }
+}
+
+/// Loads an archived attachment by its position in the message's attachment summary.
+pub async fn load_archived_attachment(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    message_id: serenity::MessageId,
+    index: usize,
+    filename: &str,
+) -> Option<Vec<u8>> {
+    let store = data.attachment_store.as_deref()?;
+    let key = archive_key(
+        &archive_prefix(guild_id, channel_id, message_id),
+        index,
+        filename,
+    );
+
+    match store.get(&key).await {
+        Ok(bytes) => bytes,
+        Err(source) => {
+            error!(?source, "failed to load archived attachment");
+            None
+        }
+    }
+}
+
+/// Drops every archived attachment of a message once it is no longer needed.
+pub async fn discard_archived_attachments(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    message_id: serenity::MessageId,
+) {
+    let Some(store) = data.attachment_store.as_deref() else {
+        return;
+    };
+
+    let prefix = archive_prefix(guild_id, channel_id, message_id);
+    if let Err(source) = store.delete_prefix(&prefix).await {
+        error!(?source, "failed to discard archived attachments");
+    }
+}
+
+fn archive_prefix(
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    message_id: serenity::MessageId,
+) -> String {
+    message_attachment_prefix(guild_id.get(), channel_id.get(), message_id.get())
+}
+
+fn archive_key(prefix: &str, index: usize, filename: &str) -> String {
+    format!(
+        "{}/{}-{}",
+        prefix,
+        index,
+        sanitize_attachment_filename(filename)
+    )
+}
@@ -1,14 +1,18 @@
 use poise::serenity_prelude as serenity;
 use tracing::error;
 
+use autumn_commands::branding::guild_accent_color;
 use autumn_core::Data;
 use autumn_database::impls::userlog_config::get_userlog_channel_id;
+use autumn_utils::embed::guild_embed;
 
+use super::archive::load_archived_attachment;
 use super::media::{
     download_media_bytes, extract_first_media_url, extract_first_unfurl_link, infer_media_filename,
     is_direct_image_url, parse_attachment_summary, sanitize_attachment_filename,
 };
 use super::util::{sanitize_mentions, truncate_for_embed};
+use autumn_utils::diff::render_word_diff;
 
 /// Data needed to build and publish a user log embed.
 pub struct PublishUserLogEntry<'a> {
@@ -83,6 +87,15 @@ pub async fn publish_userlog_embed(
         if !attachment_filenames.is_empty() {
             description_lines.push(attachment_filenames.join("\n"));
         }
+    } else if entry.event_type == "message_edit"
+        && let (Some(before_content), Some(after_content)) =
+            (entry.before_content, entry.after_content)
+    {
+        description_lines.push(sanitize_mentions(&render_word_diff(
+            before_content,
+            after_content,
+            1200,
+        )));
     } else {
         if let Some(before_content) = entry.before_content.filter(|value| !value.is_empty()) {
             description_lines.push(truncate_for_embed(&sanitize_mentions(before_content), 600));
@@ -155,8 +168,7 @@ pub async fn publish_userlog_embed(
         description_lines.extend(metadata_lines);
     }
 
-    let mut embed = serenity::CreateEmbed::new()
-        .color(autumn_utils::embed::DEFAULT_EMBED_COLOR)
+    let mut embed = guild_embed(guild_accent_color(&data.db, entry.guild_id.get()).await)
         .title(event_label)
         .description(if description_lines.is_empty() {
             "\u{200B}".to_owned()
@@ -169,12 +181,31 @@ pub async fn publish_userlog_embed(
     }
 
     let mut files = Vec::new();
-    for attachment in &attachment_items {
+    for (index, attachment) in attachment_items.iter().enumerate() {
+        let safe_filename = sanitize_attachment_filename(&attachment.filename);
+
+        // Prefer the archived copy for deleted messages; the CDN link may
+        // already be dead.
+        if entry.event_type == "message_delete"
+            && let Some(message_id) = entry.message_id
+            && let Some(bytes) = load_archived_attachment(
+                data,
+                entry.guild_id,
+                entry.channel_id,
+                message_id,
+                index,
+                &attachment.filename,
+            )
+            .await
+        {
+            files.push(serenity::CreateAttachment::bytes(bytes, safe_filename));
+            continue;
+        }
+
         if !attachment.is_media {
             continue;
         }
 
-        let safe_filename = sanitize_attachment_filename(&attachment.filename);
         match download_media_bytes(&attachment.url).await {
             Some(bytes) => {
                 files.push(serenity::CreateAttachment::bytes(bytes, safe_filename));
@@ -2,11 +2,14 @@ use poise::serenity_prelude as serenity;
 use tracing::error;
 
 use autumn_core::Data;
+use autumn_database::impls::snipe::{record_deleted_message, record_edited_message};
 use autumn_database::impls::user_logs::{
     NewMessageSnapshot, NewUserLog, delete_message_snapshot, get_message_snapshot, insert_user_log,
     upsert_message_snapshot,
 };
+use autumn_database::model::snipe::SnipedMessage;
 
+use super::archive::{archive_message_attachments, discard_archived_attachments};
 use super::embed::{PublishUserLogEntry, publish_userlog_embed};
 use super::util::{attachment_summary_from_message, now_unix_secs, resolve_deleted_by_user_id};
 
@@ -22,27 +25,36 @@ pub async fn handle_message_create_userlog(data: &Data, message: &serenity::Mess
     let attachment_summary = attachment_summary_from_message(message);
     let now = now_unix_secs();
 
-    if let Err(source) = upsert_message_snapshot(
-        &data.db,
-        NewMessageSnapshot {
-            guild_id: guild_id.get(),
-            channel_id: message.channel_id.get(),
-            message_id: message.id.get(),
-            author_user_id: message.author.id.get(),
-            content: &message.content,
-            attachment_summary: attachment_summary.as_deref(),
-            updated_at: now,
-        },
-    )
-    .await
+    let snapshot = NewMessageSnapshot {
+        guild_id: guild_id.get(),
+        channel_id: message.channel_id.get(),
+        message_id: message.id.get(),
+        author_user_id: message.author.id.get(),
+        content: &message.content,
+        attachment_summary: attachment_summary.as_deref(),
+        updated_at: now,
+    };
+
+    if let Err(source) = data
+        .db
+        .with_retry("message_snapshot", || {
+            upsert_message_snapshot(&data.db, snapshot)
+        })
+        .await
     {
         error!(?source, "failed to upsert message snapshot on create");
     }
+
+    archive_message_attachments(data, guild_id, message).await;
 }
 
+/// Logs an edit with a before/after diff. The previous version comes from the
+/// gateway cache when available, otherwise from the `message_snapshots`
+/// archive recorded at create/update time.
 pub async fn handle_message_update_userlog(
     ctx: &serenity::Context,
     data: &Data,
+    old_message: Option<&serenity::Message>,
     update_event: &serenity::MessageUpdateEvent,
 ) {
     let Some(guild_id) = update_event.guild_id else {
@@ -65,24 +77,55 @@ pub async fn handle_message_update_userlog(
     let attachment_summary = attachment_summary_from_message(&current_message);
     let now = now_unix_secs();
 
-    let snapshot = match get_message_snapshot(
-        &data.db,
-        guild_id.get(),
-        update_event.channel_id.get(),
-        update_event.id.get(),
-    )
-    .await
-    {
-        Ok(snapshot) => snapshot,
-        Err(source) => {
-            error!(?source, "failed to get message snapshot on update");
-            None
-        }
+    let previous = match old_message {
+        Some(message) => Some((
+            message.content.clone(),
+            attachment_summary_from_message(message),
+        )),
+        None => match data
+            .db
+            .with_retry("message_snapshot", || {
+                get_message_snapshot(
+                    &data.db,
+                    guild_id.get(),
+                    update_event.channel_id.get(),
+                    update_event.id.get(),
+                )
+            })
+            .await
+        {
+            Ok(snapshot) => {
+                snapshot.map(|snapshot| (snapshot.content, snapshot.attachment_summary))
+            }
+            Err(source) => {
+                error!(?source, "failed to get message snapshot on update");
+                None
+            }
+        },
     };
 
-    if let Some(previous) = snapshot {
-        let content_changed = previous.content != current_message.content;
-        let attachments_changed = previous.attachment_summary != attachment_summary;
+    if let Some((previous_content, previous_attachment_summary)) = previous {
+        let content_changed = previous_content != current_message.content;
+        let attachments_changed = previous_attachment_summary != attachment_summary;
+
+        if content_changed
+            && let Err(source) = record_edited_message(
+                &data.db,
+                guild_id.get(),
+                current_message.channel_id.get(),
+                &SnipedMessage {
+                    message_id: current_message.id.get(),
+                    author_user_id: current_message.author.id.get(),
+                    content: previous_content.clone(),
+                    edited_content: Some(current_message.content.clone()),
+                    attachment_summary: attachment_summary.clone(),
+                    sniped_at: now,
+                },
+            )
+            .await
+        {
+            error!(?source, "failed to record edited message for editsnipe");
+        }
 
         if content_changed || attachments_changed {
             let event_type = if !content_changed && attachments_changed {
@@ -97,13 +140,17 @@ pub async fn handle_message_update_userlog(
                 message_id: Some(current_message.id.get()),
                 author_user_id: Some(current_message.author.id.get()),
                 event_type,
-                before_content: Some(previous.content.as_str()),
+                before_content: Some(previous_content.as_str()),
                 after_content: Some(current_message.content.as_str()),
                 attachment_summary: attachment_summary.as_deref(),
                 created_at: now,
             };
 
-            if let Err(source) = insert_user_log(&data.db, log_entry).await {
+            if let Err(source) = data
+                .db
+                .with_retry("user_log", || insert_user_log(&data.db, log_entry))
+                .await
+            {
                 error!(?source, "failed to insert user log on message update");
             } else {
                 publish_userlog_embed(
@@ -116,7 +163,7 @@ pub async fn handle_message_update_userlog(
                         message_id: Some(current_message.id),
                         author_user_id: Some(current_message.author.id),
                         deleted_by_user_id: None,
-                        before_content: Some(previous.content.as_str()),
+                        before_content: Some(previous_content.as_str()),
                         after_content: Some(current_message.content.as_str()),
                         attachment_summary: attachment_summary.as_deref(),
                         created_at: now,
@@ -127,19 +174,22 @@ pub async fn handle_message_update_userlog(
         }
     }
 
-    if let Err(source) = upsert_message_snapshot(
-        &data.db,
-        NewMessageSnapshot {
-            guild_id: guild_id.get(),
-            channel_id: current_message.channel_id.get(),
-            message_id: current_message.id.get(),
-            author_user_id: current_message.author.id.get(),
-            content: &current_message.content,
-            attachment_summary: attachment_summary.as_deref(),
-            updated_at: now,
-        },
-    )
-    .await
+    let snapshot = NewMessageSnapshot {
+        guild_id: guild_id.get(),
+        channel_id: current_message.channel_id.get(),
+        message_id: current_message.id.get(),
+        author_user_id: current_message.author.id.get(),
+        content: &current_message.content,
+        attachment_summary: attachment_summary.as_deref(),
+        updated_at: now,
+    };
+
+    if let Err(source) = data
+        .db
+        .with_retry("message_snapshot", || {
+            upsert_message_snapshot(&data.db, snapshot)
+        })
+        .await
     {
         error!(?source, "failed to upsert message snapshot on update");
     }
@@ -164,24 +214,46 @@ pub async fn handle_message_delete_userlog(
                 message_id.get(),
             )
             .await;
+            discard_archived_attachments(data, guild_id, channel_id, message_id).await;
             return;
         }
     }
 
-    let snapshot =
-        match get_message_snapshot(&data.db, guild_id.get(), channel_id.get(), message_id.get())
-            .await
-        {
-            Ok(snapshot) => snapshot,
-            Err(source) => {
-                error!(?source, "failed to get message snapshot on delete");
-                None
-            }
-        };
+    let snapshot = match data
+        .db
+        .with_retry("message_snapshot", || {
+            get_message_snapshot(&data.db, guild_id.get(), channel_id.get(), message_id.get())
+        })
+        .await
+    {
+        Ok(snapshot) => snapshot,
+        Err(source) => {
+            error!(?source, "failed to get message snapshot on delete");
+            None
+        }
+    };
 
     let now = now_unix_secs();
 
     if let Some(previous) = snapshot {
+        if let Err(source) = record_deleted_message(
+            &data.db,
+            guild_id.get(),
+            channel_id.get(),
+            &SnipedMessage {
+                message_id: previous.message_id,
+                author_user_id: previous.author_user_id,
+                content: previous.content.clone(),
+                edited_content: None,
+                attachment_summary: previous.attachment_summary.clone(),
+                sniped_at: now,
+            },
+        )
+        .await
+        {
+            error!(?source, "failed to record deleted message for snipe");
+        }
+
         let log_entry = NewUserLog {
             guild_id: guild_id.get(),
             channel_id: previous.channel_id,
@@ -194,7 +266,11 @@ pub async fn handle_message_delete_userlog(
             created_at: now,
         };
 
-        if let Err(source) = insert_user_log(&data.db, log_entry).await {
+        if let Err(source) = data
+            .db
+            .with_retry("user_log", || insert_user_log(&data.db, log_entry))
+            .await
+        {
             error!(?source, "failed to insert user log on message delete");
         } else {
             let deleted_by_user_id = resolve_deleted_by_user_id(
@@ -228,9 +304,15 @@ pub async fn handle_message_delete_userlog(
         }
     }
 
-    if let Err(source) =
-        delete_message_snapshot(&data.db, guild_id.get(), channel_id.get(), message_id.get()).await
+    if let Err(source) = data
+        .db
+        .with_retry("message_snapshot", || {
+            delete_message_snapshot(&data.db, guild_id.get(), channel_id.get(), message_id.get())
+        })
+        .await
     {
         error!(?source, "failed to delete message snapshot");
     }
+
+    discard_archived_attachments(data, guild_id, channel_id, message_id).await;
 }
@@ -1,3 +1,4 @@
+mod archive;
 mod embed;
 mod handlers;
 mod media;
@@ -0,0 +1,254 @@
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_core::Data;
+use autumn_database::impls::guild_config::get_guild_config;
+use autumn_database::impls::watchlist::get_watch;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::embed::guild_embed;
+
+/// How much of a watched member's message an alert quotes.
+const QUOTED_MESSAGE_CHARS: usize = 1000;
+
+pub async fn handle_message_watchlist(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    config: &GuildConfig,
+) {
+    if config
+        .watchlist_alert_channel(message.author.id.get())
+        .is_none()
+    {
+        return;
+    }
+
+    let description = message_alert_description(
+        message.channel_id,
+        &message.link(),
+        &message.content,
+        message.attachments.len(),
+    );
+
+    post_alert(ctx, data, config, &message.author, "Message", description).await;
+}
+
+pub async fn handle_member_addition_watchlist(
+    ctx: &serenity::Context,
+    data: &Data,
+    member: &serenity::Member,
+) {
+    let Some(config) = watching_config(data, member.guild_id, &member.user).await else {
+        return;
+    };
+
+    post_alert(
+        ctx,
+        data,
+        &config,
+        &member.user,
+        "Joined",
+        format!(
+            "**Account Created :** <t:{}:R>",
+            member.user.created_at().unix_timestamp()
+        ),
+    )
+    .await;
+}
+
+/// Alert on a username, display name or nickname change. Role and avatar
+/// updates are ignored, as are changes to members missing from the cache.
+pub async fn handle_member_update_watchlist(
+    ctx: &serenity::Context,
+    data: &Data,
+    old: Option<&serenity::Member>,
+    event: &serenity::GuildMemberUpdateEvent,
+) {
+    let Some(old) = old else {
+        return;
+    };
+
+    let changes = name_changes([
+        ("Username", Some(&old.user.name), Some(&event.user.name)),
+        (
+            "Display name",
+            old.user.global_name.as_ref(),
+            event.user.global_name.as_ref(),
+        ),
+        ("Nickname", old.nick.as_ref(), event.nick.as_ref()),
+    ]);
+    if changes.is_empty() {
+        return;
+    }
+
+    let Some(config) = watching_config(data, event.guild_id, &event.user).await else {
+        return;
+    };
+
+    post_alert(
+        ctx,
+        data,
+        &config,
+        &event.user,
+        "Name Changed",
+        changes.join("\n"),
+    )
+    .await;
+}
+
+fn message_alert_description(
+    channel_id: serenity::ChannelId,
+    link: &str,
+    content: &str,
+    attachment_count: usize,
+) -> String {
+    let mut description = format!("**Channel :** <#{}> ([jump]({}))", channel_id, link);
+    if !content.is_empty() {
+        let quoted = content
+            .chars()
+            .take(QUOTED_MESSAGE_CHARS)
+            .collect::<String>();
+        description.push_str(&format!(
+            "\n\n{}{}",
+            quoted,
+            if content.chars().count() > QUOTED_MESSAGE_CHARS {
+                "…"
+            } else {
+                ""
+            }
+        ));
+    }
+    if attachment_count > 0 {
+        description.push_str(&format!("\n\n**Attachments :** {}", attachment_count));
+    }
+    description
+}
+
+/// One line per name that changed, as `before → after`.
+fn name_changes<const N: usize>(
+    names: [(&str, Option<&String>, Option<&String>); N],
+) -> Vec<String> {
+    names
+        .into_iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(label, before, after)| {
+            format!(
+                "**{} :** {} → {}",
+                label,
+                before.map_or("(none)", String::as_str),
+                after.map_or("(none)", String::as_str)
+            )
+        })
+        .collect()
+}
+
+/// The guild's config, when `user` is watched there and alerts are on.
+async fn watching_config(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    user: &serenity::User,
+) -> Option<GuildConfig> {
+    if user.bot {
+        return None;
+    }
+
+    let config = get_guild_config(&data.db, guild_id.get())
+        .await
+        .inspect_err(|source| error!(?source, "failed to read guild config"))
+        .ok()?;
+    config.watchlist_alert_channel(user.id.get())?;
+
+    Some(config)
+}
+
+async fn post_alert(
+    ctx: &serenity::Context,
+    data: &Data,
+    config: &GuildConfig,
+    user: &serenity::User,
+    event: &str,
+    description: String,
+) {
+    let Some(channel_id) = config.watchlist_alert_channel(user.id.get()) else {
+        return;
+    };
+
+    let note = match get_watch(&data.db, config.guild_id, user.id.get()).await {
+        Ok(watch) => watch.and_then(|watch| watch.note),
+        Err(source) => {
+            error!(?source, "failed to read watchlist entry");
+            None
+        }
+    };
+
+    let mut embed = guild_embed(config.embed_color)
+        .author(serenity::CreateEmbedAuthor::new(user.tag()).icon_url(user.face()))
+        .title(format!("Watchlist: {}", event))
+        .description(format!("**User :** <@{}>\n{}", user.id, description))
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "User ID: {}",
+            user.id
+        )));
+    if let Some(note) = note {
+        embed = embed.field("Watch Note", note, false);
+    }
+
+    if let Err(source) = serenity::ChannelId::new(channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await
+    {
+        warn!(?source, "failed to post watchlist alert");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINK: &str = "https://discord.com/channels/1/2/3";
+
+    #[test]
+    fn only_changed_names_are_listed() {
+        let name = "river".to_owned();
+        let renamed = "brook".to_owned();
+        let nick = "Riv".to_owned();
+
+        let changes = name_changes([
+            ("Username", Some(&name), Some(&renamed)),
+            ("Display name", None, None),
+            ("Nickname", Some(&nick), None),
+        ]);
+
+        assert_eq!(
+            changes,
+            [
+                "**Username :** river → brook",
+                "**Nickname :** Riv → (none)"
+            ]
+        );
+        assert!(name_changes([("Username", Some(&name), Some(&name))]).is_empty());
+    }
+
+    #[test]
+    fn message_alert_quotes_the_message() {
+        let description = message_alert_description(serenity::ChannelId::new(2), LINK, "hi", 2);
+
+        assert_eq!(
+            description,
+            format!(
+                "**Channel :** <#2> ([jump]({}))\n\nhi\n\n**Attachments :** 2",
+                LINK
+            )
+        );
+    }
+
+    #[test]
+    fn long_messages_are_cut_short() {
+        let content = "é".repeat(QUOTED_MESSAGE_CHARS + 1);
+        let description = message_alert_description(serenity::ChannelId::new(2), LINK, &content, 0);
+
+        let quoted = description.split("\n\n").nth(1).expect("message is quoted");
+        assert_eq!(quoted.chars().count(), QUOTED_MESSAGE_CHARS + 1);
+        assert!(quoted.ends_with('…'));
+    }
+}
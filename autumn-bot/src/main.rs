@@ -1,6 +1,7 @@
 mod events;
 
 use std::env;
+use std::sync::Arc;
 use std::time::Duration;
 
 use poise::serenity_prelude as serenity;
@@ -13,15 +14,34 @@ use tracing_subscriber::util::SubscriberInitExt;
 use rustls::crypto::ring::default_provider;
 use sqlx::postgres::PgPoolOptions;
 
-use autumn_core::{Data, Error};
+use autumn_api::ApiConfig;
+use autumn_commands::branding::accent_color;
+use autumn_commands::moderation::modlog_publisher::run_modlog_outbox_worker;
+use autumn_commands::moderation::retention::run_retention_worker;
+use autumn_commands::moderation::scheduler::run_scheduled_job_worker;
+use autumn_commands::moderation::webhook_dispatcher::run_webhook_delivery_worker;
+use autumn_commands::{analytics, checks};
+use autumn_core::storage::{AttachmentStore, LocalAttachmentStore};
+use autumn_core::{Data, Error, SuppressedDeletes};
 use autumn_database::{
-    CacheService, Database, MIGRATOR, cache::DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
-    cache::DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
+    CacheService, Database, MIGRATOR, cache::CONFIG_CACHE_TTL, cache::CacheTtls,
+    cache::DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS, cache::DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
+    cache::DEFAULT_MEMORY_CACHE_CAPACITY, cache::DEFAULT_MEMORY_CACHE_MAX_TTL,
+    cache::DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_MAX_HITS,
+    cache::DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_WINDOW, cache::RateLimit, cache::RateLimiter,
+    cache::RedisTopology, cache::WORD_LIST_CACHE_TTL, impls::guild_config::get_guild_config,
+    impls::retention::DEFAULT_CASE_ANONYMIZE_AFTER_DAYS,
+    impls::retention::DEFAULT_COMMAND_USAGE_RETENTION_DAYS,
+    impls::retention::DEFAULT_LLM_CHAT_RETENTION_DAYS,
+    impls::retention::DEFAULT_USERLOG_RETENTION_DAYS, impls::retention::RetentionPolicy,
+    impls::retention::retention_days,
 };
 use autumn_llm::LlmService;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let started_at = autumn_utils::time::now_unix_secs();
+
     let fmt_layer = tracing_subscriber::fmt::layer().with_filter(filter_fn(|metadata| {
         let target = metadata.target();
 
@@ -59,9 +79,20 @@ async fn main() -> anyhow::Result<()> {
 
     let mut cache = if redis_enabled {
         match env::var("REDIS_URL") {
-            Ok(redis_url) => match CacheService::redis(&redis_url, redis_key_prefix.clone()) {
+            Ok(redis_url) => match RedisTopology::from_parts(
+                env::var("REDIS_MODE").ok().as_deref(),
+                &redis_url,
+                env::var("REDIS_SENTINEL_MASTER").ok().as_deref(),
+            )
+            .and_then(|topology| {
+                CacheService::redis_with_topology(&topology, redis_key_prefix.clone())
+            }) {
                 Ok(cache) => {
-                    info!(key_prefix = %redis_key_prefix, "Redis cache enabled.");
+                    info!(
+                        key_prefix = %redis_key_prefix,
+                        mode = cache.redis_mode().unwrap_or("single"),
+                        "Redis cache enabled."
+                    );
                     cache
                 }
                 Err(err) => {
@@ -79,6 +110,50 @@ async fn main() -> anyhow::Result<()> {
         CacheService::disabled(redis_key_prefix.clone())
     };
 
+    let memory_cache_capacity = env_u64(
+        "CACHE_MEMORY_CAPACITY",
+        DEFAULT_MEMORY_CACHE_CAPACITY as u64,
+    );
+    let memory_cache_ttl_seconds = env_u64(
+        "CACHE_MEMORY_TTL_SECONDS",
+        DEFAULT_MEMORY_CACHE_MAX_TTL.as_secs(),
+    );
+    cache.configure_memory_cache(
+        usize::try_from(memory_cache_capacity).unwrap_or(usize::MAX),
+        Duration::from_secs(memory_cache_ttl_seconds),
+    );
+    if cache.is_memory_cache_enabled() {
+        info!(
+            memory_cache_capacity,
+            memory_cache_ttl_seconds, "In-process cache tier enabled."
+        );
+    } else {
+        info!("In-process cache tier disabled (CACHE_MEMORY_CAPACITY=0).");
+    }
+
+    let mut cache_ttls = CacheTtls {
+        config: Duration::from_secs(
+            env_u64("CACHE_CONFIG_TTL_SECONDS", CONFIG_CACHE_TTL.as_secs()).max(1),
+        ),
+        word_list: Duration::from_secs(
+            env_u64("CACHE_WORD_LIST_TTL_SECONDS", WORD_LIST_CACHE_TTL.as_secs()).max(1),
+        ),
+        ..CacheTtls::default()
+    };
+    if let Ok(raw) = env::var("CACHE_TTL_OVERRIDES") {
+        match CacheTtls::parse_overrides(&raw) {
+            Ok(overrides) => cache_ttls.overrides = overrides,
+            Err(err) => warn!(?err, "Ignoring invalid CACHE_TTL_OVERRIDES."),
+        }
+    }
+    info!(
+        config_ttl_seconds = cache_ttls.config.as_secs(),
+        word_list_ttl_seconds = cache_ttls.word_list.as_secs(),
+        overrides = cache_ttls.overrides.len(),
+        "Cache TTLs configured."
+    );
+    cache.configure_ttls(cache_ttls);
+
     let llm_ratelimit_window_seconds = env_u64(
         "LLM_RATELIMIT_WINDOW_SECONDS",
         DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW.as_secs(),
@@ -87,16 +162,42 @@ async fn main() -> anyhow::Result<()> {
         "LLM_RATELIMIT_MAX_HITS",
         DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
     );
-    cache.configure_llm_rate_limit(
-        Duration::from_secs(llm_ratelimit_window_seconds),
-        llm_ratelimit_max_hits,
+    cache.configure_rate_limit(
+        RateLimiter::LlmMention,
+        RateLimit::new(
+            Duration::from_secs(llm_ratelimit_window_seconds),
+            llm_ratelimit_max_hits,
+        ),
     );
+    let llm_rate_limit = cache.rate_limit(RateLimiter::LlmMention);
     info!(
-        llm_ratelimit_window_seconds = cache.llm_rate_limit_window().as_secs(),
-        llm_ratelimit_max_hits = cache.llm_rate_limit_max_hits(),
+        llm_ratelimit_window_seconds = llm_rate_limit.window.as_secs(),
+        llm_ratelimit_max_hits = llm_rate_limit.max_hits,
         "LLM rate limit configured."
     );
 
+    let mod_ratelimit_window_seconds = env_u64(
+        "MOD_COMMAND_RATELIMIT_WINDOW_SECONDS",
+        DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_WINDOW.as_secs(),
+    );
+    let mod_ratelimit_max_hits = env_u64(
+        "MOD_COMMAND_RATELIMIT_MAX_HITS",
+        DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_MAX_HITS,
+    );
+    cache.configure_rate_limit(
+        RateLimiter::ModeratorCommand,
+        RateLimit::new(
+            Duration::from_secs(mod_ratelimit_window_seconds),
+            mod_ratelimit_max_hits,
+        ),
+    );
+    let mod_rate_limit = cache.rate_limit(RateLimiter::ModeratorCommand);
+    info!(
+        mod_ratelimit_window_seconds = mod_rate_limit.window.as_secs(),
+        mod_ratelimit_max_hits = mod_rate_limit.max_hits,
+        "Moderator command rate limit configured."
+    );
+
     if cache.is_redis_enabled() {
         if let Err(err) = cache.ping().await {
             warn!(
@@ -114,9 +215,31 @@ async fn main() -> anyhow::Result<()> {
     if llm.is_some() {
         info!("LLM integration enabled.");
     } else {
-        info!("LLM integration disabled (missing/empty OLLAMA_* vars or OLLAMA_ENABLED=false).");
+        info!(
+            "LLM integration disabled (no OLLAMA_* vars or ANTHROPIC_API_KEY for LLM_PROVIDER, or OLLAMA_ENABLED=false)."
+        );
     }
 
+    let attachment_store: Option<Arc<dyn AttachmentStore>> =
+        match env::var("ATTACHMENT_ARCHIVE_DIR") {
+            Ok(dir) if !dir.trim().is_empty() => {
+                info!(dir = %dir.trim(), "Attachment archival enabled.");
+                Some(Arc::new(LocalAttachmentStore::new(dir.trim())))
+            }
+            _ => {
+                info!("Attachment archival disabled (set ATTACHMENT_ARCHIVE_DIR to enable).");
+                None
+            }
+        };
+
+    // Commands register globally (every guild the bot is in) unless a guild is
+    // given, which registers there only and applies instantly during development.
+    let command_guild_id = env::var("DISCORD_GUILD_ID")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|guild_id| *guild_id != 0)
+        .map(serenity::GuildId::new);
+
     let auto_run_migrations = env_bool("AUTO_RUN_MIGRATIONS", true);
     if auto_run_migrations {
         MIGRATOR.run(db.pool()).await?;
@@ -125,8 +248,47 @@ async fn main() -> anyhow::Result<()> {
         info!("Auto migrations disabled (set AUTO_RUN_MIGRATIONS=true to run at startup).");
     }
 
+    let retention_policy = RetentionPolicy {
+        userlog_max_age: retention_days(env_u64(
+            "USERLOG_RETENTION_DAYS",
+            DEFAULT_USERLOG_RETENTION_DAYS,
+        )),
+        llm_chat_max_age: retention_days(env_u64(
+            "LLM_CHAT_RETENTION_DAYS",
+            DEFAULT_LLM_CHAT_RETENTION_DAYS,
+        )),
+        case_anonymize_after: retention_days(env_u64(
+            "CASE_ANONYMIZE_AFTER_DAYS",
+            DEFAULT_CASE_ANONYMIZE_AFTER_DAYS,
+        )),
+        command_usage_max_age: retention_days(env_u64(
+            "COMMAND_USAGE_RETENTION_DAYS",
+            DEFAULT_COMMAND_USAGE_RETENTION_DAYS,
+        )),
+    };
+    if retention_policy.is_enabled() {
+        info!(?retention_policy, "Data retention policy configured.");
+    } else {
+        info!("Data retention disabled; stored data is kept indefinitely.");
+    }
+
+    match ApiConfig::from_env()? {
+        Some(api_config) => {
+            let db = db.clone();
+            tokio::spawn(async move {
+                if let Err(source) = autumn_api::serve(api_config, db).await {
+                    error!(?source, "HTTP API stopped");
+                }
+            });
+        }
+        None => info!("HTTP API disabled (set API_BIND_ADDR and API_TOKEN to enable)."),
+    }
+
     let intents = serenity::GatewayIntents::GUILDS
+        | serenity::GatewayIntents::GUILD_MEMBERS
+        | serenity::GatewayIntents::GUILD_MODERATION
         | serenity::GatewayIntents::GUILD_MESSAGES
+        | serenity::GatewayIntents::DIRECT_MESSAGES
         | serenity::GatewayIntents::MESSAGE_CONTENT;
 
     let framework = poise::Framework::builder()
@@ -136,6 +298,9 @@ async fn main() -> anyhow::Result<()> {
                 Box::pin(handle_event(ctx, event, framework, data))
             },
             on_error: |error| Box::pin(on_error(error)),
+            pre_command: |ctx| Box::pin(analytics::start_invocation(ctx)),
+            command_check: Some(|ctx| Box::pin(checks::command_check(ctx))),
+            post_command: |ctx| Box::pin(analytics::record_invocation(ctx, None)),
             prefix_options: poise::PrefixFrameworkOptions {
                 prefix: Some(autumn_utils::COMMAND_PREFIX.to_string()),
                 mention_as_prefix: false,
@@ -143,18 +308,59 @@ async fn main() -> anyhow::Result<()> {
             },
             ..Default::default()
         })
-        .setup(move |ctx, _ready, framework| {
+        .setup(move |ctx, ready, framework| {
             let db = db.clone();
             let llm = llm.clone();
+            let attachment_store = attachment_store.clone();
             Box::pin(async move {
                 info!("Autumn has awoken!");
 
-                poise::builtins::register_globally(ctx, &framework.options().commands).await?;
+                match command_guild_id {
+                    Some(guild_id) => {
+                        poise::builtins::register_in_guild(
+                            ctx,
+                            &framework.options().commands,
+                            guild_id,
+                        )
+                        .await?;
+                        info!(
+                            guild_id = guild_id.get(),
+                            "Commands registered in a single guild (DISCORD_GUILD_ID)."
+                        );
+                    }
+                    None => {
+                        poise::builtins::register_globally(ctx, &framework.options().commands)
+                            .await?;
+                        info!("Commands registered globally.");
+                    }
+                }
+
+                tokio::spawn(run_modlog_outbox_worker(ctx.http.clone(), db.clone()));
+                tokio::spawn(run_webhook_delivery_worker(db.clone()));
+                let suppressed_deletes = SuppressedDeletes::default();
+                tokio::spawn(run_scheduled_job_worker(
+                    ctx.http.clone(),
+                    db.clone(),
+                    ready.user.id,
+                    suppressed_deletes.clone(),
+                ));
+                if retention_policy.is_enabled() {
+                    tokio::spawn(run_retention_worker(
+                        db.clone(),
+                        retention_policy,
+                        attachment_store.clone(),
+                    ));
+                }
 
                 Ok(Data {
+                    cases: Arc::new(db.clone()),
+                    warnings: Arc::new(db.clone()),
+                    escalation: Arc::new(db.clone()),
                     db,
                     llm,
-                    suppressed_deletes: Default::default(),
+                    suppressed_deletes,
+                    attachment_store,
+                    started_at,
                 })
             })
         })
@@ -191,17 +397,18 @@ async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
     match error {
         poise::FrameworkError::Command { error, ctx, .. } => {
             error!(?error, "command error");
+            analytics::record_invocation(ctx, Some(&error.to_string())).await;
 
-            let embed = serenity::CreateEmbed::new()
+            let embed = autumn_utils::embed::guild_embed(accent_color(ctx).await)
                 .title("Command Error")
-                .description("Something went wrong while running this command.")
-                .color(autumn_utils::embed::DEFAULT_EMBED_COLOR);
+                .description("Something went wrong while running this command.");
 
             let _ = ctx
                 .send(poise::CreateReply::default().ephemeral(true).embed(embed))
                 .await;
         }
         poise::FrameworkError::ArgumentParse { ctx, input, .. } => {
+            analytics::record_invocation(ctx, Some("invalid arguments")).await;
             let usage = format!("Usage: `!{}`", ctx.command().qualified_name);
             let description = if let Some(input) = input {
                 format!("Invalid argument: `{}`\n{}", input, usage)
@@ -211,8 +418,43 @@ async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
 
             let _ = ctx.say(description).await;
         }
-        poise::FrameworkError::UnknownCommand { .. } => {
-            debug!("unknown command invocation");
+        poise::FrameworkError::UnknownCommand {
+            ctx,
+            msg,
+            prefix,
+            msg_content,
+            framework,
+            invocation_data,
+            trigger,
+            ..
+        } => {
+            if !events::command_aliases::dispatch_alias(
+                ctx,
+                framework,
+                msg,
+                prefix,
+                msg_content,
+                trigger,
+                invocation_data,
+            )
+            .await
+            {
+                debug!("unknown command invocation");
+            }
+        }
+        poise::FrameworkError::CommandCheckFailed {
+            error: None, ctx, ..
+        } => {
+            let reason = if checks::was_permission_denied(ctx).await {
+                "permission denied"
+            } else {
+                // Rate limited, or disabled with `!command disable`.
+                "blocked"
+            };
+            analytics::record_invocation(ctx, Some(reason)).await;
+        }
+        poise::FrameworkError::NotAnOwner { .. } => {
+            debug!("owner-only command used by a non-owner");
         }
         other => {
             error!(?other, "framework error");
@@ -228,12 +470,107 @@ async fn handle_event(
 ) -> Result<(), Error> {
     match event {
         serenity::FullEvent::Message { new_message } => {
-            events::word_filter::handle_message_word_filter(ctx, data, new_message).await;
-            events::userlog::handle_message_create_userlog(data, new_message).await;
-            events::llm_events::handle_message_mention_llm(ctx, data, new_message).await?;
+            // One config load serves every handler below; bots and webhooks
+            // are ignored by all of them.
+            let config = match new_message.guild_id {
+                Some(guild_id) if !new_message.author.bot && new_message.webhook_id.is_none() => {
+                    data.db
+                        .with_retry("guild_config", || {
+                            get_guild_config(&data.db, guild_id.get())
+                        })
+                        .await
+                        .inspect_err(|source| error!(?source, "failed to read guild config"))
+                        .ok()
+                }
+                _ => None,
+            };
+
+            let shadow_muted = match &config {
+                Some(config) => {
+                    events::shadow_mute::handle_message_shadow_mute(ctx, data, new_message, config)
+                        .await
+                }
+                None => false,
+            };
+            if !shadow_muted {
+                if let Some(config) = &config {
+                    events::watchlist::handle_message_watchlist(ctx, data, new_message, config)
+                        .await;
+                    events::automod::handle_message_automod(ctx, data, new_message, config).await;
+                }
+                events::userlog::handle_message_create_userlog(data, new_message).await;
+                events::modmail::handle_message_modmail(ctx, data, new_message).await;
+                if let Some(config) = &config {
+                    events::llm_events::handle_message_mention_llm(ctx, data, new_message, config)
+                        .await?;
+                }
+            }
+        }
+        serenity::FullEvent::GuildMemberAddition { new_member } => {
+            let banned = events::global_blacklist::handle_member_addition_global_blacklist(
+                ctx, data, new_member,
+            )
+            .await;
+            if !banned {
+                events::quarantine::handle_member_addition_quarantine(ctx, data, new_member).await;
+                events::watchlist::handle_member_addition_watchlist(ctx, data, new_member).await;
+                let kicked =
+                    events::antiraid::handle_member_addition_antiraid(ctx, data, new_member).await;
+                if !kicked {
+                    events::name_filter::handle_member_addition_name_filter(ctx, data, new_member)
+                        .await;
+                }
+            }
+        }
+        serenity::FullEvent::GuildMemberUpdate {
+            old_if_available,
+            event,
+            ..
+        } => {
+            events::watchlist::handle_member_update_watchlist(
+                ctx,
+                data,
+                old_if_available.as_ref(),
+                event,
+            )
+            .await;
+            events::name_filter::handle_member_update_name_filter(ctx, data, event).await;
+            events::external_actions::handle_member_update_external(
+                ctx,
+                data,
+                old_if_available.as_ref(),
+                event,
+            )
+            .await;
         }
-        serenity::FullEvent::MessageUpdate { event, .. } => {
-            events::userlog::handle_message_update_userlog(ctx, data, event).await;
+        serenity::FullEvent::GuildMemberRemoval { guild_id, user, .. } => {
+            events::external_actions::handle_member_removal_external(ctx, data, *guild_id, user)
+                .await;
+        }
+        serenity::FullEvent::GuildBanAddition {
+            guild_id,
+            banned_user,
+        } => {
+            events::external_actions::handle_ban_addition_external(
+                ctx,
+                data,
+                *guild_id,
+                banned_user,
+            )
+            .await;
+        }
+        serenity::FullEvent::MessageUpdate {
+            old_if_available,
+            event,
+            ..
+        } => {
+            events::userlog::handle_message_update_userlog(
+                ctx,
+                data,
+                old_if_available.as_ref(),
+                event,
+            )
+            .await;
         }
         serenity::FullEvent::MessageDelete {
             channel_id,
@@ -267,6 +604,71 @@ async fn handle_event(
                 .await;
             }
         }
+        serenity::FullEvent::InteractionCreate {
+            interaction: serenity::Interaction::Component(interaction),
+        } => {
+            events::components::handle_component_interaction(ctx, data, interaction).await;
+        }
+        serenity::FullEvent::GuildCreate { guild, is_new } => {
+            events::guilds::handle_guild_create(guild, *is_new);
+        }
+        serenity::FullEvent::GuildDelete { incomplete, .. } => {
+            events::guilds::handle_guild_delete(incomplete);
+        }
+        serenity::FullEvent::ChannelCreate { channel } => {
+            events::serverlog::handle_channel_create_serverlog(ctx, data, channel).await;
+            events::quarantine::handle_channel_create_quarantine(ctx, data, channel).await;
+        }
+        serenity::FullEvent::ChannelDelete { channel, .. } => {
+            events::serverlog::handle_channel_delete_serverlog(ctx, data, channel).await;
+        }
+        serenity::FullEvent::ChannelUpdate { old, new } => {
+            events::serverlog::handle_channel_update_serverlog(ctx, data, old.as_ref(), new).await;
+            events::permission_alerts::handle_channel_update_permission_alerts(
+                ctx,
+                data,
+                old.as_ref(),
+                new,
+            )
+            .await;
+        }
+        serenity::FullEvent::GuildRoleCreate { new } => {
+            events::serverlog::handle_role_create_serverlog(ctx, data, new).await;
+            events::permission_alerts::handle_role_create_permission_alerts(ctx, data, new).await;
+        }
+        serenity::FullEvent::GuildRoleDelete {
+            guild_id,
+            removed_role_id,
+            removed_role_data_if_available,
+        } => {
+            events::serverlog::handle_role_delete_serverlog(
+                ctx,
+                data,
+                *guild_id,
+                *removed_role_id,
+                removed_role_data_if_available.as_ref(),
+            )
+            .await;
+        }
+        serenity::FullEvent::GuildRoleUpdate {
+            old_data_if_available,
+            new,
+        } => {
+            events::serverlog::handle_role_update_serverlog(
+                ctx,
+                data,
+                old_data_if_available.as_ref(),
+                new,
+            )
+            .await;
+            events::permission_alerts::handle_role_update_permission_alerts(
+                ctx,
+                data,
+                old_data_if_available.as_ref(),
+                new,
+            )
+            .await;
+        }
         _ => {}
     }
 
@@ -0,0 +1,67 @@
+use std::time::Instant;
+
+use sha2::{Digest, Sha256};
+use tracing::error;
+
+use autumn_core::Context;
+use autumn_database::impls::command_usage::{NewCommandInvocation, record_command_invocation};
+
+/// When the command started, stored as invocation data by [`start_invocation`].
+struct InvocationStart(Instant);
+
+/// `pre_command` hook: remember when the command started.
+pub async fn start_invocation(ctx: Context<'_>) {
+    ctx.set_invocation_data(InvocationStart(Instant::now()))
+        .await;
+}
+
+/// Record a finished invocation. Called from `post_command` on success and
+/// from the error handler with the error's description.
+pub async fn record_invocation(ctx: Context<'_>, error: Option<&str>) {
+    let latency_ms = match ctx.invocation_data::<InvocationStart>().await {
+        Some(start) => u64::try_from(start.0.elapsed().as_millis()).unwrap_or(u64::MAX),
+        None => 0,
+    };
+
+    let args = invocation_args(ctx);
+    let args_hash = (!args.is_empty()).then(|| hash_args(&args));
+
+    if let Err(source) = record_command_invocation(
+        &ctx.data().db,
+        NewCommandInvocation {
+            guild_id: ctx.guild_id().map(|guild_id| guild_id.get()),
+            user_id: ctx.author().id.get(),
+            command: &ctx.command().qualified_name,
+            args_hash: args_hash.as_deref(),
+            error,
+            latency_ms,
+        },
+    )
+    .await
+    {
+        error!(?source, "failed to record command invocation");
+    }
+}
+
+/// The arguments as typed, without the command name.
+fn invocation_args(ctx: Context<'_>) -> String {
+    match ctx {
+        poise::Context::Prefix(prefix) => prefix.args.trim().to_owned(),
+        poise::Context::Application(_) => {
+            let invocation = ctx.invocation_string();
+            let name = format!("/{}", ctx.command().qualified_name);
+            invocation
+                .strip_prefix(&name)
+                .unwrap_or(&invocation)
+                .trim()
+                .to_owned()
+        }
+    }
+}
+
+/// Arguments are stored hashed so repeated invocations can be grouped
+/// without keeping reasons or other free text.
+fn hash_args(args: &str) -> String {
+    let digest = Sha256::digest(args.as_bytes());
+    hex::encode(&digest[..8])
+}
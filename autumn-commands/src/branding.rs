@@ -0,0 +1,30 @@
+use tracing::error;
+
+use autumn_core::Context;
+use autumn_database::Database;
+use autumn_database::impls::moderation_settings::get_embed_color;
+use autumn_utils::pagination::PaginationOptions;
+
+/// The guild's embed accent color, if it set one. A failed read keeps the
+/// default color rather than failing whatever is being sent.
+pub async fn guild_accent_color(db: &Database, guild_id: u64) -> Option<u32> {
+    get_embed_color(db, guild_id)
+        .await
+        .inspect_err(|source| error!(?source, "failed to read embed color"))
+        .ok()
+        .flatten()
+}
+
+/// [`guild_accent_color`] for the guild a command was run in; `None` in DMs.
+pub async fn accent_color(ctx: Context<'_>) -> Option<u32> {
+    let guild_id = ctx.guild_id()?;
+    guild_accent_color(&ctx.data().db, guild_id.get()).await
+}
+
+/// Paginator options carrying the guild's accent color.
+pub async fn pagination_options(ctx: Context<'_>) -> PaginationOptions<'static> {
+    PaginationOptions {
+        accent_color: accent_color(ctx).await,
+        ..Default::default()
+    }
+}
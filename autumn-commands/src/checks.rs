@@ -0,0 +1,332 @@
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use crate::branding::accent_color;
+use autumn_core::{Context, Error};
+use autumn_database::Database;
+use autumn_database::impls::disabled_commands::{is_command_disabled, list_disabled_commands};
+use autumn_database::impls::guild_config::get_guild_config;
+use autumn_database::impls::permission_levels::{highest_permission_level, list_permission_levels};
+use autumn_database::impls::rate_limit::check_moderator_command_limit;
+use autumn_database::model::permission_levels::PermissionLevel;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::permissions::{compare_role_hierarchy, resolve_user_permissions};
+
+/// The Discord permissions a bot permission level stands in for.
+pub fn level_permissions(level: PermissionLevel) -> serenity::Permissions {
+    let helper = serenity::Permissions::MANAGE_MESSAGES;
+    let moderator = helper
+        | serenity::Permissions::MODERATE_MEMBERS
+        | serenity::Permissions::KICK_MEMBERS
+        | serenity::Permissions::BAN_MEMBERS;
+
+    match level {
+        PermissionLevel::Helper => helper,
+        PermissionLevel::Mod => moderator,
+        PermissionLevel::Admin => {
+            moderator
+                | serenity::Permissions::MANAGE_GUILD
+                | serenity::Permissions::MANAGE_CHANNELS
+                | serenity::Permissions::MANAGE_ROLES
+        }
+    }
+}
+
+/// The author's Discord permissions plus whatever their highest bot
+/// permission level grants.
+pub async fn effective_permissions(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+) -> anyhow::Result<serenity::Permissions> {
+    let perms = resolve_user_permissions(ctx.http(), guild_id, ctx.author().id).await?;
+    if perms.contains(serenity::Permissions::ADMINISTRATOR) {
+        return Ok(perms);
+    }
+
+    let levels = match list_permission_levels(&ctx.data().db, guild_id.get()).await {
+        Ok(levels) => levels,
+        Err(source) => {
+            // Discord permissions still apply while levels can't be read.
+            error!(?source, "failed to load permission levels");
+            return Ok(perms);
+        }
+    };
+    if levels.is_empty() {
+        return Ok(perms);
+    }
+
+    let Some(member) = ctx.author_member().await else {
+        return Ok(perms);
+    };
+    let role_ids: Vec<u64> = member.roles.iter().map(|role_id| role_id.get()).collect();
+
+    Ok(match highest_permission_level(&levels, &role_ids) {
+        Some(level) => perms | level_permissions(level),
+        None => perms,
+    })
+}
+
+/// Permission check shared by commands: passes when the author has
+/// `required` in Discord, or holds a role whose bot permission level
+/// (`!permlevels`) covers it.
+pub async fn has_command_permission(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    required: serenity::Permissions,
+) -> anyhow::Result<bool> {
+    let perms = effective_permissions(ctx, guild_id).await?;
+
+    Ok(perms.contains(serenity::Permissions::ADMINISTRATOR) || perms.contains(required))
+}
+
+/// [`has_command_permission`] for a member pressing a component outside any
+/// command invocation. Interactions carry the member's resolved permissions,
+/// so only permission levels need a lookup.
+pub async fn member_has_permission(
+    db: &Database,
+    guild_id: serenity::GuildId,
+    member: &serenity::Member,
+    required: serenity::Permissions,
+) -> anyhow::Result<bool> {
+    let perms = member.permissions.unwrap_or_default();
+    if perms.contains(serenity::Permissions::ADMINISTRATOR) || perms.contains(required) {
+        return Ok(true);
+    }
+
+    let levels = list_permission_levels(db, guild_id.get()).await?;
+    let role_ids: Vec<u64> = member.roles.iter().map(|role_id| role_id.get()).collect();
+
+    Ok(highest_permission_level(&levels, &role_ids)
+        .is_some_and(|level| (perms | level_permissions(level)).contains(required)))
+}
+
+/// Invocation data left by [`require_permission`] when it turns someone away,
+/// so the error handler can record the denial.
+struct PermissionDenied;
+
+/// Require `required` (directly or through a permission level) to continue.
+/// A denial is answered with an embed and logged for auditing. Outside guilds
+/// this passes and the command answers with its own guild-only message.
+pub async fn require_permission(
+    ctx: Context<'_>,
+    required: serenity::Permissions,
+) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+    if has_command_permission(ctx, guild_id, required).await? {
+        return Ok(true);
+    }
+
+    let names = required.get_permission_names();
+    warn!(
+        guild_id = guild_id.get(),
+        user_id = ctx.author().id.get(),
+        command = %ctx.command().qualified_name,
+        required = ?names,
+        "permission denied"
+    );
+    ctx.set_invocation_data(PermissionDenied).await;
+
+    let command = match &ctx.command().context_menu_name {
+        Some(name) => format!("**{}**", name),
+        None => format!("`{}{}`", ctx.prefix(), ctx.command().qualified_name),
+    };
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Missing Permissions")
+        .description(format!(
+            "You need **{}** to use {}.",
+            names.join(", "),
+            command
+        ));
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(false)
+}
+
+/// Whether this invocation was stopped by [`require_permission`].
+pub async fn was_permission_denied(ctx: Context<'_>) -> bool {
+    ctx.invocation_data::<PermissionDenied>().await.is_some()
+}
+
+/// Poise `check` functions for `#[poise::command(check = "...")]`, one per
+/// permission commands require. Subcommands inherit their parent's checks.
+macro_rules! permission_checks {
+    ($($name:ident => $permission:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("Command check requiring `", stringify!($permission), "`.")]
+            pub async fn $name(ctx: Context<'_>) -> Result<bool, Error> {
+                require_permission(ctx, serenity::Permissions::$permission).await
+            }
+        )*
+    };
+}
+
+permission_checks! {
+    require_ban_members => BAN_MEMBERS,
+    require_kick_members => KICK_MEMBERS,
+    require_manage_channels => MANAGE_CHANNELS,
+    require_manage_guild => MANAGE_GUILD,
+    require_manage_messages => MANAGE_MESSAGES,
+    require_manage_roles => MANAGE_ROLES,
+    require_moderate_members => MODERATE_MEMBERS,
+}
+
+/// Refuse up front when the bot's highest role doesn't outrank `target`, with
+/// a reply saying so. If the lookup itself fails the action goes ahead and
+/// Discord has the final say.
+pub async fn bot_outranks_target(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    target: serenity::UserId,
+    action: &str,
+) -> Result<bool, Error> {
+    let bot_id = ctx.framework().bot_id;
+    let hierarchy = match compare_role_hierarchy(ctx.http(), guild_id, bot_id, target).await {
+        Ok(hierarchy) => hierarchy,
+        Err(source) => {
+            error!(?source, "failed to check role hierarchy");
+            return Ok(true);
+        }
+    };
+
+    match hierarchy.bot_blocked_message(action) {
+        Some(message) => {
+            ctx.say(message).await?;
+            Ok(false)
+        }
+        None => Ok(true),
+    }
+}
+
+/// With `!modhierarchy on`, refuse when the author's highest role doesn't
+/// outrank `target`, so moderators can't action each other by accident.
+/// Passes when the setting is off or the lookup fails.
+pub async fn moderator_outranks_target(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    target: serenity::UserId,
+    action: &str,
+) -> Result<bool, Error> {
+    let enforced = match get_guild_config(&ctx.data().db, guild_id.get()).await {
+        Ok(config) => config.enforce_mod_hierarchy,
+        Err(source) => {
+            error!(?source, "failed to load moderator hierarchy setting");
+            return Ok(true);
+        }
+    };
+    if !enforced {
+        return Ok(true);
+    }
+
+    let hierarchy =
+        match compare_role_hierarchy(ctx.http(), guild_id, ctx.author().id, target).await {
+            Ok(hierarchy) => hierarchy,
+            Err(source) => {
+                error!(?source, "failed to check moderator role hierarchy");
+                return Ok(true);
+            }
+        };
+
+    match hierarchy.moderator_blocked_message(action) {
+        Some(message) => {
+            ctx.say(message).await?;
+            Ok(false)
+        }
+        None => Ok(true),
+    }
+}
+
+/// Global `command_check`: disabled commands are refused before they count
+/// toward the moderator rate limit.
+pub async fn command_check(ctx: Context<'_>) -> Result<bool, Error> {
+    Ok(command_enabled(ctx).await? && moderator_rate_limit(ctx).await?)
+}
+
+/// Refuse commands switched off with `!command disable` in this guild or
+/// channel. `!command` itself always runs so it can be switched back on.
+pub async fn command_enabled(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+    // Subcommands are disabled along with their parent.
+    let name = ctx
+        .parent_commands()
+        .first()
+        .map_or(ctx.command().name.as_str(), |root| root.name.as_str());
+    if name == crate::moderation::command::META.name {
+        return Ok(true);
+    }
+
+    let disabled = match list_disabled_commands(&ctx.data().db, guild_id.get()).await {
+        Ok(disabled) => disabled,
+        Err(source) => {
+            error!(?source, "failed to load disabled commands");
+            return Ok(true);
+        }
+    };
+    if !is_command_disabled(&disabled, name, ctx.channel_id().get()) {
+        return Ok(true);
+    }
+
+    // Prefix invocations are ignored quietly so a disabled command stays
+    // silent in e.g. announcement channels; interactions must be answered.
+    if let poise::Context::Application(_) = ctx {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(format!("`/{name}` is disabled here."))
+                .ephemeral(true),
+        )
+        .await?;
+    }
+
+    Ok(false)
+}
+
+/// Invocation data set once [`moderator_rate_limit`] has counted an
+/// invocation. Poise runs the global check for every parent command as well
+/// as the subcommand itself, each time seeing the subcommand.
+struct RateLimitCounted;
+
+/// Part of [`command_check`]: cap how many moderation commands one moderator
+/// can run in a guild per window. Other commands and DMs always pass.
+pub async fn moderator_rate_limit(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+    if ctx.command().category.as_deref() != Some("Moderation") {
+        return Ok(true);
+    }
+    if ctx.invocation_data::<RateLimitCounted>().await.is_some() {
+        return Ok(true);
+    }
+
+    let decision =
+        match check_moderator_command_limit(&ctx.data().db, guild_id.get(), ctx.author().id.get())
+            .await
+        {
+            Ok(decision) => decision,
+            Err(source) => {
+                // Never lock moderators out because the cache is unavailable.
+                error!(?source, "failed to check moderator command rate limit");
+                return Ok(true);
+            }
+        };
+
+    if decision.allowed {
+        ctx.set_invocation_data(RateLimitCounted).await;
+    } else {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(format!(
+                    "You're running moderation commands too quickly. Try again in {} seconds.",
+                    decision.retry_after.as_secs()
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+    }
+
+    Ok(decision.allowed)
+}
@@ -1,40 +1,101 @@
+pub mod analytics;
+pub mod branding;
+pub mod checks;
 pub mod moderation;
 pub mod utility;
 
 use autumn_core::{Data, Error};
+use poise::serenity_prelude as serenity;
 
 pub struct CommandMeta {
     pub name: &'static str,
     pub desc: &'static str,
     pub category: &'static str,
     pub usage: &'static str,
+    /// Permissions a member needs to run the command; `None` if anyone can.
+    pub required_permissions: Option<serenity::Permissions>,
+    /// Sample invocations shown by `!help <command>`.
+    pub examples: &'static [&'static str],
+    /// Names of commands worth pointing to from this one's help page.
+    pub related: &'static [&'static str],
 }
 
 pub const COMMANDS: &[CommandMeta] = &[
     utility::ping::META,
+    utility::status::META,
     utility::universe::META,
     utility::help::META,
     utility::usage::META,
+    utility::translate::META,
+    utility::poll::META,
+    utility::report::META,
     utility::pagetest::META,
     moderation::aitoggle::META,
+    moderation::ai::META,
     moderation::ban::META,
     moderation::unban::META,
     moderation::kick::META,
     moderation::timeout::META,
     moderation::untimeout::META,
+    moderation::shadowmute::META,
+    moderation::unshadowmute::META,
+    moderation::temprole::META,
+    moderation::quarantine::META,
+    moderation::release::META,
     moderation::warn::META,
     moderation::warnings::META,
     moderation::unwarn::META,
     moderation::purge::META,
     moderation::permissions::META,
+    moderation::permlevels::META,
+    moderation::command::META,
+    moderation::alias::META,
+    moderation::modhierarchy::META,
+    moderation::dualapproval::META,
+    moderation::permcheck::META,
+    moderation::config::META,
     moderation::terminate::META,
+    moderation::archive::META,
     moderation::modlogs::META,
     moderation::modlogchannel::META,
+    moderation::modlogtemplate::META,
+    moderation::embedcolor::META,
+    moderation::purgetranscripts::META,
+    moderation::casereview::META,
+    moderation::warndecay::META,
+    moderation::privacy::META,
+    moderation::backup::META,
+    moderation::import::META,
+    moderation::tasks::META,
+    moderation::webhooks::META,
     moderation::userlogs::META,
+    moderation::snipe::META,
+    moderation::editsnipe::META,
+    moderation::search::META,
     moderation::userlogchannel::META,
+    moderation::serverlogchannel::META,
+    moderation::permalertchannel::META,
     moderation::case::META,
     moderation::notes::META,
+    moderation::summarize::META,
+    moderation::remind::META,
+    moderation::reports::META,
+    moderation::watch::META,
+    moderation::unwatch::META,
+    moderation::watchlist::META,
     moderation::wordfilter::META,
+    moderation::linkfilter::META,
+    moderation::invitefilter::META,
+    moderation::antiraid::META,
+    moderation::antispam::META,
+    moderation::attachmentfilter::META,
+    moderation::capsfilter::META,
+    moderation::imagefilter::META,
+    moderation::namefilter::META,
+    moderation::raidmode::META,
+    moderation::blacklistmode::META,
+    moderation::bansync::META,
+    moderation::modmail::META,
     moderation::escalation::META,
     moderation::setup::META,
 ];
@@ -42,29 +103,88 @@ pub const COMMANDS: &[CommandMeta] = &[
 pub fn commands() -> Vec<poise::Command<Data, Error>> {
     vec![
         utility::ping::ping(),
+        utility::status::status(),
+        utility::cache::cache(),
+        utility::blacklist::blacklist(),
         utility::universe::universe(),
         utility::help::help(),
         utility::usage::usage(),
+        utility::translate::translate(),
+        utility::translate::translate_message(),
+        utility::poll::poll(),
+        utility::report::report(),
+        utility::report::report_message(),
         utility::pagetest::pagetest(),
         moderation::aitoggle::aitoggle(),
+        moderation::ai::ai(),
         moderation::ban::ban(),
         moderation::unban::unban(),
         moderation::kick::kick(),
         moderation::timeout::timeout(),
+        moderation::timeout::timeout_user(),
         moderation::untimeout::untimeout(),
+        moderation::shadowmute::shadowmute(),
+        moderation::unshadowmute::unshadowmute(),
+        moderation::temprole::temprole(),
+        moderation::quarantine::quarantine(),
+        moderation::release::release(),
         moderation::warn::warn(),
+        moderation::warn::warn_user(),
+        moderation::warn::delete_and_warn(),
         moderation::warnings::warnings(),
         moderation::unwarn::unwarn(),
         moderation::purge::purge(),
         moderation::permissions::permissions(),
+        moderation::permlevels::permlevels(),
+        moderation::command::command(),
+        moderation::alias::alias(),
+        moderation::modhierarchy::modhierarchy(),
+        moderation::dualapproval::dualapproval(),
+        moderation::permcheck::permcheck(),
+        moderation::config::config(),
         moderation::terminate::terminate(),
+        moderation::archive::archive(),
         moderation::modlogs::modlogs(),
+        moderation::modlogs::user_history(),
         moderation::modlogchannel::modlogchannel(),
+        moderation::modlogtemplate::modlogtemplate(),
+        moderation::embedcolor::embedcolor(),
+        moderation::purgetranscripts::purgetranscripts(),
+        moderation::casereview::casereview(),
+        moderation::warndecay::warndecay(),
+        moderation::privacy::privacy(),
+        moderation::backup::backup(),
+        moderation::import::import(),
+        moderation::tasks::tasks(),
+        moderation::webhooks::webhooks(),
         moderation::userlogs::userlogs(),
+        moderation::snipe::snipe(),
+        moderation::editsnipe::editsnipe(),
+        moderation::search::search(),
         moderation::userlogchannel::userlogchannel(),
+        moderation::serverlogchannel::serverlogchannel(),
+        moderation::permalertchannel::permalertchannel(),
         moderation::case::case(),
         moderation::notes::notes(),
+        moderation::summarize::summarize(),
+        moderation::remind::remind(),
+        moderation::reports::reports(),
+        moderation::watch::watch(),
+        moderation::unwatch::unwatch(),
+        moderation::watchlist::watchlist(),
         moderation::wordfilter::wordfilter(),
+        moderation::linkfilter::linkfilter(),
+        moderation::invitefilter::invitefilter(),
+        moderation::antiraid::antiraid(),
+        moderation::antispam::antispam(),
+        moderation::attachmentfilter::attachmentfilter(),
+        moderation::capsfilter::capsfilter(),
+        moderation::imagefilter::imagefilter(),
+        moderation::namefilter::namefilter(),
+        moderation::raidmode::raidmode(),
+        moderation::blacklistmode::blacklistmode(),
+        moderation::bansync::bansync(),
+        moderation::modmail::modmail(),
         moderation::escalation::escalation(),
         moderation::setup::setup(),
     ]
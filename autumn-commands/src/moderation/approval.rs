@@ -0,0 +1,341 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use crate::branding::accent_color;
+use crate::checks::member_has_permission;
+use crate::moderation::logging::create_case_and_publish_with_attachment;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::{
+    CaseStatusChange, NewCase, get_case_by_label, update_case_status,
+};
+use autumn_database::impls::modlog_outbox::ModlogAttachment;
+use autumn_database::impls::scheduled_jobs::{
+    JOB_APPROVAL_EXPIRY, NewScheduledJob, cancel_jobs_with_payload, schedule_job,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::{format_compact_duration, parse_case_label};
+use autumn_utils::time::now_unix_secs;
+
+/// How long a second moderator has to respond. Kept well under the 15
+/// minutes a slash command can still edit its replies, since the action
+/// itself reports back afterwards.
+const APPROVAL_TIMEOUT_SECS: u64 = 5 * 60;
+/// Extra wait before the scheduled backstop cancels a case the request
+/// itself should already have settled.
+const APPROVAL_EXPIRY_GRACE_SECS: u64 = 60;
+
+/// A pending case another moderator approved. The caller carries out the
+/// action, then settles the case with [`finish_approval`].
+pub(crate) struct Approval {
+    pub case_label: String,
+    pub approver: serenity::UserId,
+}
+
+pub(crate) struct ApprovalRequest<'a> {
+    pub user: &'a serenity::User,
+    pub action: &'a str,
+    pub reason: &'a str,
+    pub duration_seconds: Option<u64>,
+    /// What a moderator needs to approve or reject.
+    pub required: serenity::Permissions,
+    pub attachment: Option<ModlogAttachment>,
+}
+
+/// Record the action as a pending case and post Approve/Reject buttons that
+/// only other moderators with `required` can use. The invoker can reject to
+/// withdraw. Returns `None` once the request is rejected, times out or the
+/// case is cancelled, having said so.
+pub(crate) async fn request_approval(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    request: ApprovalRequest<'_>,
+) -> Result<Option<Approval>, Error> {
+    let Some(case_label) = create_case_and_publish_with_attachment(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(request.user.id.get()),
+            moderator_user_id: ctx.author().id.get(),
+            action: request.action,
+            reason: request.reason,
+            status: "pending",
+            duration_seconds: request.duration_seconds,
+        },
+        request.attachment,
+    )
+    .await
+    else {
+        ctx.say("Failed to create the pending case. Nothing was done.")
+            .await?;
+        return Ok(None);
+    };
+
+    // The buttons only live as long as this command, so a restart would leave
+    // the case pending for good without a job to cancel it.
+    let db = &ctx.data().db;
+    if let Err(source) = schedule_job(
+        db,
+        NewScheduledJob {
+            guild_id: guild_id.get(),
+            kind: JOB_APPROVAL_EXPIRY,
+            target_user_id: Some(request.user.id.get()),
+            channel_id: None,
+            payload: &case_label,
+            run_at: now_unix_secs() + APPROVAL_TIMEOUT_SECS + APPROVAL_EXPIRY_GRACE_SECS,
+            created_by: ctx.author().id.get(),
+        },
+    )
+    .await
+    {
+        error!(?source, "failed to schedule approval expiry");
+    }
+
+    let approve_id = format!("{}_approval_approve", ctx.id());
+    let reject_id = format!("{}_approval_reject", ctx.id());
+    let buttons = vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&approve_id)
+            .label("Approve")
+            .style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(&reject_id)
+            .label("Reject")
+            .style(serenity::ButtonStyle::Danger),
+    ])];
+
+    let mut description = format!(
+        "<@{}> wants to **{}** <@{}>.\n**Reason :** {}",
+        ctx.author().id,
+        request.action,
+        request.user.id,
+        request.reason.replace('@', "@\u{200B}")
+    );
+    if let Some(duration_seconds) = request.duration_seconds {
+        description.push_str(&format!(
+            "\n**Duration :** {}",
+            format_compact_duration(duration_seconds)
+        ));
+    }
+    description.push_str(&format!(
+        "\n**Case :** #{}\n\nAnother moderator with **{}** must approve within {} minutes.",
+        case_label,
+        request.required.get_permission_names().join(", "),
+        APPROVAL_TIMEOUT_SECS / 60
+    ));
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Approval Needed")
+        .description(description);
+
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed.clone())
+                .components(buttons),
+        )
+        .await?;
+    let message_id = reply.message().await?.id;
+
+    let author_id = ctx.author().id;
+    while let Some(press) = serenity::ComponentInteractionCollector::new(ctx)
+        .filter({
+            let prefix = format!("{}_approval_", ctx.id());
+            move |interaction| {
+                interaction.data.custom_id.starts_with(&prefix)
+                    && interaction.message.id == message_id
+            }
+        })
+        .timeout(Duration::from_secs(APPROVAL_TIMEOUT_SECS))
+        .await
+    {
+        let approving = press.data.custom_id == approve_id;
+        let problem = if approving && press.user.id == author_id {
+            Some("You can't approve your own request.".to_owned())
+        } else if press.user.id != author_id
+            && !match &press.member {
+                Some(member) => member_has_permission(db, guild_id, member, request.required)
+                    .await
+                    .unwrap_or_else(|source| {
+                        error!(?source, "failed to check approver permissions");
+                        false
+                    }),
+                None => false,
+            }
+        {
+            Some(format!(
+                "Only moderators with **{}** can respond to this.",
+                request.required.get_permission_names().join(", ")
+            ))
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            press
+                .create_response(
+                    ctx.http(),
+                    serenity::CreateInteractionResponse::Message(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content(problem)
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        let (content, approval) = if !is_still_pending(ctx, guild_id, &case_label).await? {
+            (
+                format!(
+                    "Case #{} is no longer pending. Nothing was done.",
+                    case_label
+                ),
+                None,
+            )
+        } else if approving {
+            (
+                format!("Approved by <@{}>.", press.user.id),
+                Some(Approval {
+                    case_label: case_label.clone(),
+                    approver: press.user.id,
+                }),
+            )
+        } else {
+            let note = if press.user.id == author_id {
+                "Withdrawn by the requesting moderator".to_owned()
+            } else {
+                format!("Rejected by <@{}>", press.user.id)
+            };
+            cancel_pending(ctx, guild_id, &case_label, press.user.id, &note).await;
+            (format!("{}. Nothing was done.", note), None)
+        };
+
+        press
+            .create_response(
+                ctx.http(),
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .embed(embed)
+                        .components(Vec::new())
+                        .allowed_mentions(serenity::CreateAllowedMentions::new()),
+                ),
+            )
+            .await?;
+        cancel_expiry_job(ctx, guild_id, &case_label).await;
+        return Ok(approval);
+    }
+
+    cancel_pending(ctx, guild_id, &case_label, author_id, "Approval timed out").await;
+    cancel_expiry_job(ctx, guild_id, &case_label).await;
+    let _ = reply
+        .edit(
+            ctx,
+            poise::CreateReply::default()
+                .content("No one approved in time. Nothing was done.")
+                .embed(embed)
+                .components(Vec::new()),
+        )
+        .await;
+
+    Ok(None)
+}
+
+/// Settle an approved case once its action has run: `active` when it was
+/// carried out, `failed` when it wasn't.
+pub(crate) async fn finish_approval(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    approval: &Approval,
+    carried_out: bool,
+) {
+    let note = if carried_out {
+        format!("Approved by <@{}>", approval.approver)
+    } else {
+        format!(
+            "Approved by <@{}>, but the action failed",
+            approval.approver
+        )
+    };
+    let change = CaseStatusChange {
+        from: "pending",
+        to: if carried_out { "active" } else { "failed" },
+        note: &note,
+    };
+    set_status(
+        ctx,
+        guild_id,
+        &approval.case_label,
+        approval.approver,
+        change,
+    )
+    .await;
+}
+
+/// Drop the backstop job once the request has settled the case itself, so it
+/// can't cancel an approved case whose action is still running.
+async fn cancel_expiry_job(ctx: Context<'_>, guild_id: serenity::GuildId, case_label: &str) {
+    if let Err(source) = cancel_jobs_with_payload(
+        &ctx.data().db,
+        guild_id.get(),
+        JOB_APPROVAL_EXPIRY,
+        case_label,
+    )
+    .await
+    {
+        error!(?source, "failed to cancel approval expiry job");
+    }
+}
+
+async fn is_still_pending(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    case_label: &str,
+) -> Result<bool, Error> {
+    let Some((case_code, number)) = parse_case_label(case_label) else {
+        return Ok(false);
+    };
+    let case = get_case_by_label(&ctx.data().db, guild_id.get(), &case_code, number).await?;
+
+    Ok(case.is_some_and(|case| case.status == "pending"))
+}
+
+async fn cancel_pending(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    case_label: &str,
+    actor: serenity::UserId,
+    note: &str,
+) {
+    let change = CaseStatusChange {
+        from: "pending",
+        to: "cancelled",
+        note,
+    };
+    set_status(ctx, guild_id, case_label, actor, change).await;
+}
+
+async fn set_status(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    case_label: &str,
+    actor: serenity::UserId,
+    change: CaseStatusChange<'_>,
+) {
+    let Some((case_code, number)) = parse_case_label(case_label) else {
+        return;
+    };
+    match update_case_status(
+        &ctx.data().db,
+        guild_id.get(),
+        &case_code,
+        number,
+        actor.get(),
+        change,
+    )
+    .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => warn!(case_label, "approval case changed status underneath us"),
+        Err(source) => error!(?source, "failed to update approval case status"),
+    }
+}
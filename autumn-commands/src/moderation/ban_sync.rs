@@ -0,0 +1,186 @@
+use tracing::{error, warn};
+
+use poise::serenity_prelude as serenity;
+
+use crate::moderation::logging::publish_case;
+use autumn_database::Database;
+use autumn_database::impls::ban_sync::{
+    get_ban_sync_group_for_guild, list_active_ban_sync_mirrors, list_ban_sync_members,
+    mark_ban_sync_mirror_undone, reason_has_sync_tag, record_ban_sync_mirror,
+};
+use autumn_database::impls::cases::NewCase;
+
+/// Discord rejects audit log reasons over 512 characters; leave room for the
+/// "Ban sync from ..." prefix.
+const MIRRORED_REASON_MAX_CHARS: usize = 400;
+
+/// Mirror a ban whose reason carries the group's sync tag to every partner
+/// guild, with a local case in each. Partners that already ban the user are
+/// skipped, so undoing the sync never lifts a ban they issued themselves.
+/// Returns how many partners applied the ban.
+pub(crate) async fn mirror_ban(
+    http: &serenity::Http,
+    db: &Database,
+    source_guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+    reason: &str,
+    bot_user_id: u64,
+) -> usize {
+    let group = match get_ban_sync_group_for_guild(db, source_guild_id.get()).await {
+        Ok(Some(group)) => group,
+        Ok(None) => return 0,
+        Err(source) => {
+            error!(?source, "failed to read ban sync group");
+            return 0;
+        }
+    };
+
+    if !reason_has_sync_tag(reason, &group.tag) {
+        return 0;
+    }
+
+    let partners = match list_ban_sync_members(db, group.id).await {
+        Ok(members) => members,
+        Err(source) => {
+            error!(?source, "failed to list ban sync members");
+            return 0;
+        }
+    };
+
+    let source_name = guild_name(http, source_guild_id).await;
+    let mirrored_reason = format!(
+        "Ban sync from {}: {}",
+        source_name,
+        reason
+            .chars()
+            .take(MIRRORED_REASON_MAX_CHARS)
+            .collect::<String>()
+    );
+
+    let mut applied = 0;
+    for target in partners
+        .into_iter()
+        .filter(|guild_id| *guild_id != source_guild_id.get())
+        .map(serenity::GuildId::new)
+    {
+        match http.get_ban(target, user_id).await {
+            Ok(Some(_)) => continue,
+            Ok(None) => {}
+            Err(source) => {
+                warn!(?source, guild_id = %target, "failed to check existing ban for ban sync");
+                continue;
+            }
+        }
+
+        if let Err(source) = target
+            .ban_with_reason(http, user_id, 0, &mirrored_reason)
+            .await
+        {
+            warn!(?source, guild_id = %target, "failed to mirror ban to partner guild");
+            continue;
+        }
+
+        if let Err(source) =
+            record_ban_sync_mirror(db, source_guild_id.get(), target.get(), user_id.get()).await
+        {
+            error!(?source, "failed to record mirrored ban");
+        }
+
+        publish_case(
+            http,
+            db,
+            target,
+            NewCase {
+                guild_id: target.get(),
+                target_user_id: Some(user_id.get()),
+                moderator_user_id: bot_user_id,
+                action: "bansync_ban",
+                reason: &mirrored_reason,
+                status: "active",
+                duration_seconds: None,
+            },
+            None,
+        )
+        .await;
+
+        applied += 1;
+    }
+
+    applied
+}
+
+/// Lift mirrored bans of `user_id` that `guild_id` sent to its partners or
+/// received from one, logging an unban case wherever a ban is lifted. Returns
+/// how many bans were lifted and how many couldn't be.
+pub(crate) async fn undo_mirrored_bans(
+    http: &serenity::Http,
+    db: &Database,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+    moderator_user_id: u64,
+    bot_user_id: u64,
+) -> anyhow::Result<(usize, usize)> {
+    let mirrors = list_active_ban_sync_mirrors(db, guild_id.get(), user_id.get()).await?;
+    let undoer_name = guild_name(http, guild_id).await;
+
+    let mut lifted = 0;
+    let mut failed = 0;
+    for mirror in mirrors {
+        let target = serenity::GuildId::new(mirror.target_guild_id);
+        let (moderator, reason) = if target == guild_id {
+            (moderator_user_id, "Synced ban undone".to_owned())
+        } else {
+            (bot_user_id, format!("Ban sync undone by {}", undoer_name))
+        };
+
+        match http.get_ban(target, user_id).await {
+            // Already lifted by hand; nothing left to undo.
+            Ok(None) => {
+                mark_ban_sync_mirror_undone(db, mirror.id).await?;
+                continue;
+            }
+            Ok(Some(_)) => {}
+            Err(source) => {
+                warn!(?source, guild_id = %target, "failed to look up mirrored ban");
+                failed += 1;
+                continue;
+            }
+        }
+
+        if let Err(source) = target.unban(http, user_id).await {
+            warn!(?source, guild_id = %target, "failed to lift mirrored ban");
+            failed += 1;
+            continue;
+        }
+
+        mark_ban_sync_mirror_undone(db, mirror.id).await?;
+
+        publish_case(
+            http,
+            db,
+            target,
+            NewCase {
+                guild_id: target.get(),
+                target_user_id: Some(user_id.get()),
+                moderator_user_id: moderator,
+                action: "unban",
+                reason: &reason,
+                status: "active",
+                duration_seconds: None,
+            },
+            None,
+        )
+        .await;
+
+        lifted += 1;
+    }
+
+    Ok((lifted, failed))
+}
+
+pub(crate) async fn guild_name(http: &serenity::Http, guild_id: serenity::GuildId) -> String {
+    match guild_id.to_partial_guild(http).await {
+        Ok(guild) => guild.name,
+        Err(_) => format!("Server {}", guild_id.get()),
+    }
+}
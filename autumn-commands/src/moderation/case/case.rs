@@ -2,29 +2,51 @@ use poise::serenity_prelude as serenity;
 use tracing::error;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_messages;
 use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::moderation::remind::schedule_reminder;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::{
-    add_case_note, get_case_by_label, get_case_events, update_case_reason,
+    CaseStatusChange, add_case_note, get_case_by_label, get_case_events, update_case_reason,
+    update_case_status,
 };
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_database::impls::scheduled_jobs::{
+    JOB_CASE_REVIEW, JOB_DEFERRED_ACTION, cancel_jobs_with_payload,
+};
+use autumn_utils::embed::guild_embed;
 use autumn_utils::formatting::{
-    action_display_name, event_display_name, format_compact_duration, parse_case_label,
+    action_display_name, event_display_name, format_case_label, format_compact_duration,
+    parse_case_label,
 };
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::parse::parse_duration_seconds;
 
 pub const META: CommandMeta = CommandMeta {
     name: "case",
     desc: "View or edit a moderation case.",
     category: "moderation",
-    usage: "!case <case_id> [reason|note] [text]",
+    usage: "!case <case_id> [reason|note|remindme|cancel|resolve] [text]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &[
+        "!case 12",
+        "!case 12 reason Spamming invite links",
+        "!case 12 remindme 1d",
+    ],
+    related: &["modlogs", "notes", "casereview"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn case(
     ctx: Context<'_>,
     #[description = "Case id (e.g. W1, B3)"] case_id: Option<String>,
-    #[description = "Optional action: reason or note"] action: Option<String>,
+    #[description = "Optional action: reason, note, remindme, cancel or resolve"] action: Option<
+        String,
+    >,
     #[description = "Text for the selected action"]
     #[rest]
     value: Option<String>,
@@ -34,17 +56,6 @@ pub async fn case(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(case_id) = case_id else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -151,7 +162,156 @@ pub async fn case(
             return Ok(());
         }
 
-        ctx.say("Supported actions: `reason`, `note`").await?;
+        if action.eq_ignore_ascii_case("remindme") {
+            let Some(seconds) = value.as_deref().and_then(parse_duration_seconds) else {
+                ctx.say("Usage: `!case <case_id> remindme <duration>`")
+                    .await?;
+                return Ok(());
+            };
+
+            let case = get_case_by_label(
+                &ctx.data().db,
+                guild_id.get(),
+                &case_code,
+                action_case_number,
+            )
+            .await;
+
+            match case {
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    ctx.say(format!(
+                        "Case {}{} was not found.",
+                        case_code, action_case_number
+                    ))
+                    .await?;
+                    return Ok(());
+                }
+                Err(source) => {
+                    error!(?source, "case load failed");
+                    ctx.say("Failed to load case.").await?;
+                    return Ok(());
+                }
+            }
+
+            let text = format!("Check back on case #{}{}.", case_code, action_case_number);
+            return schedule_reminder(ctx, guild_id, seconds, &text).await;
+        }
+
+        if action.eq_ignore_ascii_case("cancel") {
+            let cancelled = update_case_status(
+                &ctx.data().db,
+                guild_id.get(),
+                &case_code,
+                action_case_number,
+                ctx.author().id.get(),
+                CaseStatusChange {
+                    from: "pending",
+                    to: "cancelled",
+                    note: "Scheduled action cancelled",
+                },
+            )
+            .await;
+
+            let cancelled = match cancelled {
+                Ok(cancelled) => cancelled,
+                Err(source) => {
+                    error!(?source, "case cancel failed");
+                    ctx.say("Failed to cancel case.").await?;
+                    return Ok(());
+                }
+            };
+
+            if cancelled.is_none() {
+                ctx.say(format!(
+                    "Case {}{} was not found or is not a pending scheduled action.",
+                    case_code, action_case_number
+                ))
+                .await?;
+                return Ok(());
+            }
+
+            if let Err(source) = cancel_jobs_with_payload(
+                &ctx.data().db,
+                guild_id.get(),
+                JOB_DEFERRED_ACTION,
+                &format_case_label(&case_code, action_case_number),
+            )
+            .await
+            {
+                // The worker skips cases that are no longer pending.
+                error!(?source, "failed to remove cancelled deferred job");
+            }
+
+            ctx.say(format!(
+                "Cancelled scheduled action #{}{}.",
+                case_code, action_case_number
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        if action.eq_ignore_ascii_case("resolve") {
+            let note = value
+                .as_deref()
+                .map(str::trim)
+                .filter(|note| !note.is_empty())
+                .unwrap_or("Case resolved");
+
+            let resolved = update_case_status(
+                &ctx.data().db,
+                guild_id.get(),
+                &case_code,
+                action_case_number,
+                ctx.author().id.get(),
+                CaseStatusChange {
+                    from: "open",
+                    to: "resolved",
+                    note,
+                },
+            )
+            .await;
+
+            let resolved = match resolved {
+                Ok(resolved) => resolved,
+                Err(source) => {
+                    error!(?source, "case resolve failed");
+                    ctx.say("Failed to resolve case.").await?;
+                    return Ok(());
+                }
+            };
+
+            if resolved.is_none() {
+                ctx.say(format!(
+                    "Case {}{} was not found or is not open.",
+                    case_code, action_case_number
+                ))
+                .await?;
+                return Ok(());
+            }
+
+            if let Err(source) = cancel_jobs_with_payload(
+                &ctx.data().db,
+                guild_id.get(),
+                JOB_CASE_REVIEW,
+                &format_case_label(&case_code, action_case_number),
+            )
+            .await
+            {
+                // The worker skips cases that are no longer open.
+                error!(?source, "failed to remove case review reminder");
+            }
+
+            ctx.say(format!(
+                "Resolved case #{}{}.",
+                case_code, action_case_number
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        ctx.say("Supported actions: `reason`, `note`, `remindme`, `cancel`, `resolve`")
+            .await?;
         return Ok(());
     }
 
@@ -213,6 +373,10 @@ pub async fn case(
         ));
     }
 
+    if case.status != "active" {
+        fields.push(format!("Status : {}", capitalize_status(&case.status)));
+    }
+
     fields.push(format!("Created : <t:{}:f>", case.created_at));
 
     let mut description = fields.join("\n");
@@ -240,11 +404,17 @@ pub async fn case(
         }
     }
 
-    let embed = serenity::CreateEmbed::new()
-        .color(DEFAULT_EMBED_COLOR)
+    let embed = guild_embed(accent_color(ctx).await)
         .title(format!("#{}{}", case.case_code, case.action_case_number))
         .description(description.trim_end().to_owned());
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
+
+fn capitalize_status(status: &str) -> String {
+    let mut chars = status.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
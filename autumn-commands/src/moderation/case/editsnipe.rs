@@ -0,0 +1,77 @@
+use poise::serenity_prelude as serenity;
+
+use super::snipe::content_display;
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::snipe::get_edited_message;
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "editsnipe",
+    desc: "Show the most recently edited message in this channel.",
+    category: "moderation",
+    usage: "!editsnipe",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!editsnipe"],
+    related: &["snipe", "userlogs"],
+};
+
+const CONTENT_MAX_LEN: usize = 900;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn editsnipe(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(message) =
+        get_edited_message(&ctx.data().db, guild_id.get(), ctx.channel_id().get()).await?
+    else {
+        ctx.say("Nothing to snipe in this channel.").await?;
+        return Ok(());
+    };
+
+    let lines = [
+        format!("**Author :** <@{}>", message.author_user_id),
+        format!(
+            "**Before :** {}",
+            content_display(&message.content, CONTENT_MAX_LEN)
+        ),
+        format!(
+            "**After :** {}",
+            content_display(
+                message.edited_content.as_deref().unwrap_or_default(),
+                CONTENT_MAX_LEN
+            )
+        ),
+        format!(
+            "**Message :** https://discord.com/channels/{}/{}/{}",
+            guild_id.get(),
+            ctx.channel_id().get(),
+            message.message_id
+        ),
+        format!("**Edited :** <t:{}:R>", message.sniped_at),
+    ];
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Sniped Edit")
+        .description(lines.join("\n"));
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,5 +1,14 @@
 pub mod case;
+pub mod editsnipe;
 pub mod modlogs;
 pub mod notes;
+pub mod remind;
+pub mod reports;
+pub mod search;
+pub mod snipe;
+pub mod summarize;
+pub mod unwatch;
 pub mod userlogs;
 pub mod warnings;
+pub mod watch;
+pub mod watchlist;
@@ -1,45 +1,77 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::{accent_color, guild_accent_color, pagination_options};
+use crate::checks::{member_has_permission, require_manage_messages};
 use crate::moderation::embeds::{guild_only_message, usage_message};
-use autumn_core::{Context, Error};
+use autumn_core::{Context, Data, Error};
+use autumn_database::Database;
 use autumn_database::impls::cases::{CaseFilters, list_recent_cases};
-use autumn_utils::formatting::{action_display_name, format_case_label, format_compact_duration};
-use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::formatting::{
+    action_display_name, format_case_label, format_compact_duration, is_filter_action,
+};
+use autumn_utils::page_builder::{PageBuilder, PageEntry};
+use autumn_utils::pagination::{
+    PageSection, PaginationOptions, fits_stateless_page_id, labeled_page_sections,
+    paginate_embed_pages_with_options, stateless_page_message,
+};
 
 pub const META: CommandMeta = CommandMeta {
     name: "modlogs",
     desc: "View recent moderation actions.",
     category: "moderation",
     usage: "!modlogs [target_user] [moderator] [action]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!modlogs @user", "!modlogs @user @moderator ban"],
+    related: &["case", "warnings", "modlogchannel"],
 };
 
 const CASES_PER_PAGE: usize = 5;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+const MODLOGS_TITLE: &str = "Moderation Logs";
+
+/// Custom-id prefix routing page buttons to [`handle_modlogs_page`].
+pub const MODLOGS_PAGE_KIND: &str = "modlogs";
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn modlogs(
     ctx: Context<'_>,
     #[description = "Filter by target user"] target_user: Option<serenity::User>,
     #[description = "Filter by moderator"] moderator: Option<serenity::User>,
     #[description = "Filter by action (ban, warn, etc.)"] action: Option<String>,
+) -> Result<(), Error> {
+    show_cases(ctx, target_user, moderator, action).await
+}
+
+/// Show a user's moderation history from the Apps menu on their profile.
+#[poise::command(
+    context_menu_command = "View History",
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn user_history(
+    ctx: Context<'_>,
+    #[description = "The user whose cases to show"] user: serenity::User,
+) -> Result<(), Error> {
+    show_cases(ctx, Some(user), None, None).await
+}
+
+async fn show_cases(
+    ctx: Context<'_>,
+    target_user: Option<serenity::User>,
+    moderator: Option<serenity::User>,
+    action: Option<String>,
 ) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
         ctx.say(guild_only_message()).await?;
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     if action
         .as_deref()
         .is_some_and(|value| value.trim().is_empty())
@@ -48,79 +80,216 @@ pub async fn modlogs(
         return Ok(());
     }
 
+    let query = ModlogQuery {
+        target_user_id: target_user.as_ref().map(|user| user.id.get()),
+        moderator_user_id: moderator.as_ref().map(|user| user.id.get()),
+        action: action.as_deref().map(str::trim).map(str::to_owned),
+    };
+    let Some((pages, sections)) = modlog_pages(&ctx.data().db, guild_id.get(), &query).await?
+    else {
+        ctx.say("No matching moderation cases found.").await?;
+        return Ok(());
+    };
+
+    let state = query.encode();
+    if fits_stateless_page_id(MODLOGS_PAGE_KIND, &state) {
+        let (embed, components) = stateless_page_message(
+            MODLOGS_TITLE,
+            &pages,
+            1,
+            MODLOGS_PAGE_KIND,
+            &state,
+            &sections,
+            accent_color(ctx).await,
+        );
+        ctx.send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(components),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Other moderators in a staff channel can page through the same view.
+    let options = PaginationOptions {
+        sections: &sections,
+        shared_with: META.required_permissions,
+        ..pagination_options(ctx).await
+    };
+    paginate_embed_pages_with_options(ctx, MODLOGS_TITLE, &pages, 1, options).await?;
+    Ok(())
+}
+
+/// Answer a press on a modlogs page button, even one sent before a restart.
+/// Anyone allowed to run `!modlogs` may flip the pages.
+pub async fn handle_modlogs_page(
+    ctx: &serenity::Context,
+    data: &Data,
+    interaction: &serenity::ComponentInteraction,
+    state: &str,
+    page: usize,
+) -> Result<(), Error> {
+    let (Some(guild_id), Some(member)) = (interaction.guild_id, interaction.member.as_ref()) else {
+        return Ok(());
+    };
+
+    let required = META
+        .required_permissions
+        .unwrap_or(serenity::Permissions::MANAGE_MESSAGES);
+    if !member_has_permission(&data.db, guild_id, member, required).await? {
+        interaction
+            .create_response(
+                &ctx.http,
+                serenity::CreateInteractionResponse::Message(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content(format!(
+                            "You need {} to page through these logs.",
+                            required.get_permission_names().join(", ")
+                        ))
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let Some(query) = ModlogQuery::decode(state) else {
+        return Ok(());
+    };
+
+    let response = match modlog_pages(&data.db, guild_id.get(), &query).await? {
+        Some((pages, sections)) => {
+            let (embed, components) = stateless_page_message(
+                MODLOGS_TITLE,
+                &pages,
+                page,
+                MODLOGS_PAGE_KIND,
+                state,
+                &sections,
+                guild_accent_color(&data.db, guild_id.get()).await,
+            );
+            serenity::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .components(components)
+        }
+        None => serenity::CreateInteractionResponseMessage::new()
+            .content("No matching moderation cases found.")
+            .embeds(Vec::new())
+            .components(Vec::new()),
+    };
+
+    interaction
+        .create_response(
+            &ctx.http,
+            serenity::CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The filters behind a modlogs view, carried in its button ids.
+struct ModlogQuery {
+    target_user_id: Option<u64>,
+    moderator_user_id: Option<u64>,
+    action: Option<String>,
+}
+
+impl ModlogQuery {
+    /// `target:moderator:action`, with `-` for an unset user. The action goes
+    /// last so it can't be confused with the separators.
+    fn encode(&self) -> String {
+        let user = |id: Option<u64>| id.map_or_else(|| "-".to_owned(), |id| id.to_string());
+        format!(
+            "{}:{}:{}",
+            user(self.target_user_id),
+            user(self.moderator_user_id),
+            self.action.as_deref().unwrap_or_default()
+        )
+    }
+
+    fn decode(state: &str) -> Option<Self> {
+        let mut parts = state.splitn(3, ':');
+        let user = |raw: &str| match raw {
+            "-" => Some(None),
+            raw => raw.parse::<u64>().ok().map(Some),
+        };
+
+        Some(Self {
+            target_user_id: user(parts.next()?)?,
+            moderator_user_id: user(parts.next()?)?,
+            action: Some(parts.next()?.to_owned()).filter(|action| !action.is_empty()),
+        })
+    }
+}
+
+/// Every page of cases matching `query`, plus a section per action type, or
+/// `None` when nothing matches.
+async fn modlog_pages(
+    db: &Database,
+    guild_id: u64,
+    query: &ModlogQuery,
+) -> anyhow::Result<Option<(Vec<String>, Vec<PageSection>)>> {
     let rows = list_recent_cases(
-        &ctx.data().db,
-        guild_id.get(),
+        db,
+        guild_id,
         CaseFilters {
-            target_user_id: target_user.as_ref().map(|user| user.id.get()),
-            moderator_user_id: moderator.as_ref().map(|user| user.id.get()),
-            action: action
-                .as_deref()
-                .map(str::trim)
-                .filter(|value| !value.is_empty()),
+            target_user_id: query.target_user_id,
+            moderator_user_id: query.moderator_user_id,
+            action: query.action.as_deref(),
             limit: 200,
         },
     )
     .await?;
 
     if rows.is_empty() {
-        ctx.say("No matching moderation cases found.").await?;
-        return Ok(());
+        return Ok(None);
     }
 
-    let total = rows.len();
-    let total_pages = total.div_ceil(CASES_PER_PAGE);
-    let mut pages = Vec::with_capacity(total_pages);
-
-    for page in 0..total_pages {
-        let start = page * CASES_PER_PAGE;
-        let end = (start + CASES_PER_PAGE).min(total);
-
-        let mut body = String::new();
-        body.push_str(&format!("Total cases: **{}**\n\n", total));
-        for case in &rows[start..end] {
-            let action_name = action_display_name(&case.action);
-            let mut fields = Vec::new();
-            fields.push(format!("**Action :** {}", action_name));
-
-            if let Some(target_user_id) = case.target_user_id {
-                fields.push(format!("**Target :** <@{}>", target_user_id));
-            }
-
-            // Use "Violation" label for word filter cases, skip for purge.
-            if case.action.starts_with("word_filter_") {
-                fields.push(format!(
-                    "**Violation :** {}",
-                    case.reason.replace('@', "@\u{200B}")
-                ));
-            } else if case.action != "purge" {
-                fields.push(format!(
-                    "**Reason :** {}",
-                    case.reason.replace('@', "@\u{200B}")
-                ));
-            }
-
-            if let Some(duration_seconds) = case.duration_seconds {
-                fields.push(format!(
-                    "**Duration :** {}",
-                    format_compact_duration(duration_seconds)
-                ));
-            }
-
-            fields.push(format!("**Moderator :** <@{}>", case.moderator_user_id));
-
-            fields.push(format!("**When :** <t:{}:R>", case.created_at));
-
-            body.push_str(&format!(
-                "#{}\n{}\n\n",
-                format_case_label(&case.case_code, case.action_case_number),
-                fields.join("\n"),
-            ));
+    let mut builder =
+        PageBuilder::new(CASES_PER_PAGE).header(format!("Total cases: **{}**", rows.len()));
+    for case in &rows {
+        let mut entry = PageEntry::new(format!(
+            "#{}",
+            format_case_label(&case.case_code, case.action_case_number)
+        ))
+        .field("Action", action_display_name(&case.action));
+
+        if let Some(target_user_id) = case.target_user_id {
+            entry = entry.field("Target", format!("<@{}>", target_user_id));
+        }
+
+        // Use "Violation" label for automod filter cases, skip for purge.
+        if is_filter_action(&case.action) {
+            entry = entry.field("Violation", case.reason.replace('@', "@\u{200B}"));
+        } else if case.action != "purge" {
+            entry = entry.field("Reason", case.reason.replace('@', "@\u{200B}"));
         }
 
-        pages.push(body.trim_end().to_owned());
+        if let Some(duration_seconds) = case.duration_seconds {
+            entry = entry.field("Duration", format_compact_duration(duration_seconds));
+        }
+
+        builder.push(
+            entry
+                .field("Moderator", format!("<@{}>", case.moderator_user_id))
+                .field("When", format!("<t:{}:R>", case.created_at)),
+        );
     }
+    let (pages, case_pages) = builder.build_with_entry_pages();
 
-    paginate_embed_pages(ctx, "Moderation Logs", &pages, 1).await?;
-    Ok(())
+    // Cases are newest first, so each action's section opens on its latest case.
+    let action_names: Vec<String> = rows
+        .iter()
+        .map(|case| action_display_name(&case.action))
+        .collect();
+    let sections = labeled_page_sections(
+        action_names
+            .iter()
+            .map(String::as_str)
+            .zip(case_pages.iter().copied()),
+    );
+
+    Ok(Some((pages, sections)))
 }
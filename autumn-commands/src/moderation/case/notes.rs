@@ -2,24 +2,38 @@ use poise::serenity_prelude as serenity;
 use std::time::Duration;
 
 use crate::CommandMeta;
+use crate::branding::pagination_options;
+use crate::checks::require_manage_messages;
 use crate::moderation::embeds::guild_only_message;
 use autumn_core::{Context, Error};
 use autumn_database::impls::notes::{add_user_note, clear_user_notes, list_user_notes};
 use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
-use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::page_builder::{PageBuilder, PageEntry};
+use autumn_utils::pagination::paginate_embed_pages_with_options;
 
 pub const META: CommandMeta = CommandMeta {
     name: "notes",
     desc: "Add or view a moderator note for a user.",
     category: "moderation",
     usage: "!notes <user> [note|clear]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &[
+        "!notes @user",
+        "!notes @user Alt of a banned account",
+        "!notes @user clear",
+    ],
+    related: &["case", "summarize"],
 };
 
 const NOTES_PER_PAGE: usize = 5;
 const NOTES_CLEAR_CONFIRM_TIMEOUT_SECS: u64 = 30;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn notes(
     ctx: Context<'_>,
     #[description = "Target user"] user: Option<serenity::User>,
@@ -32,17 +46,6 @@ pub async fn notes(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(format!("Usage: `{}`", META.usage)).await?;
         return Ok(());
@@ -126,28 +129,25 @@ pub async fn notes(
         return Ok(());
     }
 
-    let total = notes.len();
-    let total_pages = total.div_ceil(NOTES_PER_PAGE);
-    let mut pages = Vec::with_capacity(total_pages);
-
-    for page in 0..total_pages {
-        let start = page * NOTES_PER_PAGE;
-        let end = (start + NOTES_PER_PAGE).min(total);
-
-        let mut body = String::new();
-        for note in &notes[start..end] {
-            body.push_str(&format!(
-                "#{} • by <@{}> • <t:{}:R>\n{}\n\n",
-                note.id,
-                note.author_user_id,
-                note.created_at,
-                note.content.replace('@', "@\u{200B}"),
-            ));
-        }
-
-        pages.push(body.trim_end().to_owned());
+    let mut builder = PageBuilder::new(NOTES_PER_PAGE);
+    for note in &notes {
+        builder.push(
+            PageEntry::new(format!(
+                "#{} • by <@{}> • <t:{}:R>",
+                note.id, note.author_user_id, note.created_at
+            ))
+            .text(note.content.replace('@', "@\u{200B}")),
+        );
     }
-
-    paginate_embed_pages(ctx, &format!("Notes for {}", user.name), &pages, 1).await?;
+    let pages = builder.build();
+
+    paginate_embed_pages_with_options(
+        ctx,
+        &format!("Notes for {}", user.name),
+        &pages,
+        1,
+        pagination_options(ctx).await,
+    )
+    .await?;
     Ok(())
 }
@@ -0,0 +1,105 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::scheduled_jobs::{JOB_REMINDER, NewScheduledJob, schedule_job};
+use autumn_database::impls::warnings::now_unix_secs;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::split_leading_duration;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "remind",
+    desc: "Get pinged in this channel after a delay.",
+    category: "moderation",
+    usage: "!remind <duration> <text>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!remind 2h check the appeal"],
+    related: &["case"],
+};
+
+/// Longest delay a reminder can be set for.
+const MAX_REMINDER_SECS: u64 = 365 * 24 * 60 * 60;
+const MAX_REMINDER_CHARS: usize = 1000;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn remind(
+    ctx: Context<'_>,
+    #[description = "Delay (e.g. 3d, 2h 30m) followed by the reminder text"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let (Some(seconds), Some(text)) = input
+        .as_deref()
+        .map_or((None, None), split_leading_duration)
+    else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if text.chars().count() > MAX_REMINDER_CHARS {
+        ctx.say(format!(
+            "Reminder text is too long (max {MAX_REMINDER_CHARS} characters)."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    schedule_reminder(ctx, guild_id, seconds, &text).await
+}
+
+/// Schedule a reminder for the invoking moderator in the current channel and
+/// confirm it. Shared with `!case <id> remindme`.
+pub(crate) async fn schedule_reminder(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    seconds: u64,
+    text: &str,
+) -> Result<(), Error> {
+    if seconds > MAX_REMINDER_SECS {
+        ctx.say("Reminders can be set at most a year ahead.")
+            .await?;
+        return Ok(());
+    }
+
+    let run_at = now_unix_secs().saturating_add(seconds);
+    let scheduled = schedule_job(
+        &ctx.data().db,
+        NewScheduledJob {
+            guild_id: guild_id.get(),
+            kind: JOB_REMINDER,
+            target_user_id: None,
+            channel_id: Some(ctx.channel_id().get()),
+            payload: text,
+            run_at,
+            created_by: ctx.author().id.get(),
+        },
+    )
+    .await;
+
+    if let Err(source) = scheduled {
+        error!(?source, "failed to schedule reminder");
+        ctx.say("Failed to set reminder.").await?;
+        return Ok(());
+    }
+
+    ctx.say(format!(
+        "I'll remind you in {} (<t:{}:f>).",
+        format_compact_duration(seconds),
+        run_at
+    ))
+    .await?;
+    Ok(())
+}
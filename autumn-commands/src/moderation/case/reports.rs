@@ -0,0 +1,423 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::guild_only_message;
+use crate::moderation::logging::create_case_and_publish;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::{CaseStatusChange, NewCase, update_case_status};
+use autumn_database::impls::scheduled_jobs::{JOB_CASE_REVIEW, cancel_jobs_with_payload};
+use autumn_database::impls::user_reports::{
+    REPORT_OPEN, UserReport, claim_user_report, get_user_report, list_active_reports,
+    resolve_user_report, set_user_report_case,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::parse_case_label;
+use autumn_utils::pagination::PAGINATION_TIMEOUT_SECS;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "reports",
+    desc: "Work through member reports: claim one to open a case, resolve it when handled.",
+    category: "moderation",
+    usage: "!reports",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!reports"],
+    related: &["report", "case"],
+};
+
+/// Discord's limit on embed field values.
+const FIELD_VALUE_LIMIT: usize = 1024;
+
+/// Page through open and claimed reports, oldest first.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn reports(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let mut queue = list_active_reports(db, guild_id.get()).await?;
+    if queue.is_empty() {
+        ctx.say("No reports are waiting.").await?;
+        return Ok(());
+    }
+
+    let accent = accent_color(ctx).await;
+    let ctx_id = ctx.id();
+    let prev_id = format!("{}_reports_prev", ctx_id);
+    let claim_id = format!("{}_reports_claim", ctx_id);
+    let resolve_id = format!("{}_reports_resolve", ctx_id);
+    let next_id = format!("{}_reports_next", ctx_id);
+    let ids = QueueButtons {
+        prev: &prev_id,
+        claim: &claim_id,
+        resolve: &resolve_id,
+        next: &next_id,
+    };
+    let mut page = 0;
+
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(report_embed(&queue, page, accent))
+                .components(queue_components(&ids, &queue, page)),
+        )
+        .await?;
+    let message_id = reply.message().await?.id;
+
+    while let Some(press) = serenity::ComponentInteractionCollector::new(ctx)
+        .filter({
+            let prefix = format!("{}_reports_", ctx_id);
+            let author_id = ctx.author().id;
+            move |interaction| {
+                interaction.data.custom_id.starts_with(&prefix)
+                    && interaction.user.id == author_id
+                    && interaction.message.id == message_id
+            }
+        })
+        .timeout(Duration::from_secs(PAGINATION_TIMEOUT_SECS))
+        .await
+    {
+        let custom_id = press.data.custom_id.as_str();
+        if custom_id == prev_id {
+            page = page.saturating_sub(1);
+        } else if custom_id == next_id {
+            page += 1;
+        } else if custom_id == claim_id || custom_id == resolve_id {
+            let report = &queue[page];
+            let outcome = if custom_id == claim_id {
+                claim(ctx, guild_id, report.id).await?
+            } else {
+                resolve(ctx, guild_id, report.id).await?
+            };
+            if let Some(problem) = outcome {
+                press
+                    .create_response(
+                        ctx.http(),
+                        serenity::CreateInteractionResponse::Message(
+                            serenity::CreateInteractionResponseMessage::new()
+                                .content(problem)
+                                .ephemeral(true),
+                        ),
+                    )
+                    .await?;
+                continue;
+            }
+            queue = list_active_reports(db, guild_id.get()).await?;
+        }
+
+        if queue.is_empty() {
+            press
+                .create_response(
+                    ctx.http(),
+                    serenity::CreateInteractionResponse::UpdateMessage(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content("No reports are waiting.")
+                            .embeds(Vec::new())
+                            .components(Vec::new()),
+                    ),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        page = page.min(queue.len() - 1);
+        press
+            .create_response(
+                ctx.http(),
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(report_embed(&queue, page, accent))
+                        .components(queue_components(&ids, &queue, page)),
+                ),
+            )
+            .await?;
+    }
+
+    let _ = reply
+        .edit(
+            ctx,
+            poise::CreateReply::default()
+                .embed(report_embed(&queue, page, accent))
+                .components(Vec::new()),
+        )
+        .await;
+
+    Ok(())
+}
+
+/// Take a report and open a case against the reported member. Returns why
+/// it couldn't be claimed, if it couldn't.
+async fn claim(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    report_id: i64,
+) -> Result<Option<String>, Error> {
+    let db = &ctx.data().db;
+    let Some(report) = get_user_report(db, guild_id.get(), report_id).await? else {
+        return Ok(Some("That report no longer exists.".to_owned()));
+    };
+
+    if !claim_user_report(db, guild_id.get(), report_id, ctx.author().id.get()).await? {
+        return Ok(Some(
+            "Another moderator already claimed that report.".to_owned(),
+        ));
+    }
+
+    let reason = case_reason(guild_id, &report);
+    let case_label = create_case_and_publish(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(report.target_user_id),
+            moderator_user_id: ctx.author().id.get(),
+            action: "report",
+            reason: &reason,
+            status: "open",
+            duration_seconds: None,
+        },
+    )
+    .await;
+
+    match case_label {
+        Some(case_label) => {
+            set_user_report_case(db, guild_id.get(), report_id, &case_label).await?;
+            Ok(None)
+        }
+        None => Ok(Some(
+            "Claimed, but I couldn't open a case for it.".to_owned(),
+        )),
+    }
+}
+
+/// Close a report and its case, claiming it first if nobody has.
+async fn resolve(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    report_id: i64,
+) -> Result<Option<String>, Error> {
+    let db = &ctx.data().db;
+    let Some(report) = get_user_report(db, guild_id.get(), report_id).await? else {
+        return Ok(Some("That report no longer exists.".to_owned()));
+    };
+
+    let report = if report.status == REPORT_OPEN {
+        if let Some(problem) = claim(ctx, guild_id, report_id).await? {
+            return Ok(Some(problem));
+        }
+        get_user_report(db, guild_id.get(), report_id)
+            .await?
+            .unwrap_or(report)
+    } else {
+        report
+    };
+
+    if let Some((case_code, action_case_number)) =
+        report.case_label.as_deref().and_then(parse_case_label)
+    {
+        let resolved = update_case_status(
+            db,
+            guild_id.get(),
+            &case_code,
+            action_case_number,
+            ctx.author().id.get(),
+            CaseStatusChange {
+                from: "open",
+                to: "resolved",
+                note: "Report resolved",
+            },
+        )
+        .await;
+        if let Err(source) = resolved {
+            error!(?source, "failed to resolve report case");
+        }
+
+        if let Err(source) = cancel_jobs_with_payload(
+            db,
+            guild_id.get(),
+            JOB_CASE_REVIEW,
+            report.case_label.as_deref().unwrap_or_default(),
+        )
+        .await
+        {
+            // The worker skips cases that are no longer open.
+            error!(?source, "failed to remove case review reminder");
+        }
+    }
+
+    if !resolve_user_report(db, guild_id.get(), report_id, ctx.author().id.get()).await? {
+        return Ok(Some("That report was already resolved.".to_owned()));
+    }
+
+    Ok(None)
+}
+
+fn case_reason(guild_id: serenity::GuildId, report: &UserReport) -> String {
+    let mut reason = format!(
+        "Reported by <@{}>: {}",
+        report.reporter_user_id, report.reason
+    );
+    if let Some(link) = message_link(guild_id, report) {
+        reason.push_str(&format!(" ({})", link));
+    }
+    reason
+}
+
+fn message_link(guild_id: serenity::GuildId, report: &UserReport) -> Option<String> {
+    let (channel_id, message_id) = report.channel_id.zip(report.message_id)?;
+    Some(
+        serenity::MessageId::new(message_id)
+            .link(serenity::ChannelId::new(channel_id), Some(guild_id)),
+    )
+}
+
+fn report_embed(queue: &[UserReport], page: usize, accent: Option<u32>) -> serenity::CreateEmbed {
+    let report = &queue[page];
+    let guild_id = serenity::GuildId::new(report.guild_id);
+
+    let mut embed = guild_embed(accent)
+        .title(format!("Report #{}", report.id))
+        .description(report.reason.replace('@', "@\u{200B}"))
+        .field("Reported", format!("<@{}>", report.target_user_id), true)
+        .field("By", format!("<@{}>", report.reporter_user_id), true)
+        .field("Filed", format!("<t:{}:R>", report.created_at), true)
+        .field("Status", report_status(report), false)
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Report {} of {}",
+            page + 1,
+            queue.len()
+        )));
+
+    if let Some(link) = message_link(guild_id, report) {
+        let content = report.message_content.as_deref().unwrap_or_default();
+        embed = embed.field("Message", message_field(&link, content), false);
+    }
+
+    embed
+}
+
+fn report_status(report: &UserReport) -> String {
+    match (report.handled_by, report.case_label.as_deref()) {
+        (Some(moderator), Some(case_label)) => {
+            format!("Claimed by <@{}> (case #{})", moderator, case_label)
+        }
+        (Some(moderator), None) => format!("Claimed by <@{}>", moderator),
+        _ => "Open".to_owned(),
+    }
+}
+
+/// The link to the reported message followed by as much of its content as
+/// fits in one embed field.
+fn message_field(link: &str, content: &str) -> String {
+    let mut value = format!("{}\n", link);
+    let room = FIELD_VALUE_LIMIT.saturating_sub(value.chars().count() + 1);
+    value.extend(content.chars().take(room));
+    if content.chars().count() > room {
+        value.push('…');
+    }
+    value
+}
+
+struct QueueButtons<'a> {
+    prev: &'a str,
+    claim: &'a str,
+    resolve: &'a str,
+    next: &'a str,
+}
+
+fn queue_components(
+    ids: &QueueButtons<'_>,
+    queue: &[UserReport],
+    page: usize,
+) -> Vec<serenity::CreateActionRow> {
+    let claimed = queue[page].status != REPORT_OPEN;
+    vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(ids.prev)
+            .label("Prev")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page == 0),
+        serenity::CreateButton::new(ids.claim)
+            .label("Claim")
+            .style(serenity::ButtonStyle::Primary)
+            .disabled(claimed),
+        serenity::CreateButton::new(ids.resolve)
+            .label("Resolve")
+            .style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(ids.next)
+            .label("Next")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page + 1 >= queue.len()),
+    ])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> UserReport {
+        UserReport {
+            id: 7,
+            guild_id: 1,
+            reporter_user_id: 10,
+            target_user_id: 20,
+            reason: "scam links".to_owned(),
+            channel_id: None,
+            message_id: None,
+            message_content: None,
+            status: REPORT_OPEN.to_owned(),
+            handled_by: None,
+            case_label: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn status_shows_who_claimed_it() {
+        let mut report = report();
+        assert_eq!(report_status(&report), "Open");
+
+        report.handled_by = Some(30);
+        assert_eq!(report_status(&report), "Claimed by <@30>");
+
+        report.case_label = Some("RP12".to_owned());
+        assert_eq!(report_status(&report), "Claimed by <@30> (case #RP12)");
+    }
+
+    #[test]
+    fn case_reason_links_the_reported_message() {
+        let mut report = report();
+        let guild_id = serenity::GuildId::new(1);
+        assert_eq!(
+            case_reason(guild_id, &report),
+            "Reported by <@10>: scam links"
+        );
+
+        report.channel_id = Some(2);
+        report.message_id = Some(3);
+        assert_eq!(
+            case_reason(guild_id, &report),
+            "Reported by <@10>: scam links (https://discord.com/channels/1/2/3)"
+        );
+    }
+
+    #[test]
+    fn message_field_fits_the_embed_limit() {
+        let link = "https://discord.com/channels/1/2/3";
+        assert_eq!(message_field(link, "hi"), format!("{}\nhi", link));
+
+        let field = message_field(link, &"é".repeat(FIELD_VALUE_LIMIT));
+        assert_eq!(field.chars().count(), FIELD_VALUE_LIMIT);
+        assert!(field.ends_with('…'));
+    }
+}
@@ -0,0 +1,185 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::pagination_options;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_utils::pagination::paginate_embed_pages_with_options;
+use autumn_utils::parse::parse_user_mention;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "search",
+    desc: "Search recent channel history by author or keyword.",
+    category: "moderation",
+    usage: "!search <user|keyword> [#channel] [limit]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!search @user", "!search free nitro #general 500"],
+    related: &["userlogs", "snipe"],
+};
+
+const DEFAULT_SCAN_LIMIT: u16 = 200;
+const MAX_SCAN_LIMIT: u16 = 1000;
+const RESULTS_PER_PAGE: usize = 5;
+const CONTENT_PREVIEW_LEN: usize = 200;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "User mention/ID or keyword to look for"] query: Option<String>,
+    #[description = "Channel to search (defaults to this one)"] channel: Option<
+        serenity::GuildChannel,
+    >,
+    #[description = "How many recent messages to scan (max 1000)"] limit: Option<u16>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(query) = query
+        .as_deref()
+        .map(str::trim)
+        .filter(|query| !query.is_empty())
+    else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if channel
+        .as_ref()
+        .is_some_and(|channel| channel.guild_id != guild_id)
+    {
+        ctx.say("That channel is not in this server.").await?;
+        return Ok(());
+    }
+
+    let channel_id = channel.map_or_else(|| ctx.channel_id(), |channel| channel.id);
+    let scan_limit = limit.unwrap_or(DEFAULT_SCAN_LIMIT).clamp(1, MAX_SCAN_LIMIT);
+
+    let author_filter = parse_user_mention(query).map(serenity::UserId::new);
+    let keyword = query.to_lowercase();
+
+    ctx.defer().await?;
+
+    let messages = match fetch_recent_messages(ctx, channel_id, scan_limit).await {
+        Ok(messages) => messages,
+        Err(_) => {
+            ctx.say("I couldn't read that channel's history.").await?;
+            return Ok(());
+        }
+    };
+    let scanned = messages.len();
+
+    let matches: Vec<&serenity::Message> = messages
+        .iter()
+        .filter(|message| match author_filter {
+            Some(author_id) => message.author.id == author_id,
+            None => message.content.to_lowercase().contains(&keyword),
+        })
+        .collect();
+
+    if matches.is_empty() {
+        ctx.say(format!(
+            "No matches in the last {} message(s) of <#{}>.",
+            scanned,
+            channel_id.get()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let total = matches.len();
+    let total_pages = total.div_ceil(RESULTS_PER_PAGE);
+    let mut pages = Vec::with_capacity(total_pages);
+
+    for page in 0..total_pages {
+        let start = page * RESULTS_PER_PAGE;
+        let end = (start + RESULTS_PER_PAGE).min(total);
+
+        let mut body = format!(
+            "**{}** match(es) in the last **{}** message(s) of <#{}>\n\n",
+            total,
+            scanned,
+            channel_id.get()
+        );
+
+        for message in &matches[start..end] {
+            body.push_str(&format!(
+                "**Author :** <@{}>\n**Content :** {}\n**When :** <t:{}:R> · [Jump]({})\n\n",
+                message.author.id.get(),
+                content_preview(message),
+                message.timestamp.unix_timestamp(),
+                message.link()
+            ));
+        }
+
+        pages.push(body.trim_end().to_owned());
+    }
+
+    paginate_embed_pages_with_options(
+        ctx,
+        "Message Search",
+        &pages,
+        1,
+        pagination_options(ctx).await,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Page backwards through channel history, newest first.
+async fn fetch_recent_messages(
+    ctx: Context<'_>,
+    channel_id: serenity::ChannelId,
+    limit: u16,
+) -> Result<Vec<serenity::Message>, serenity::Error> {
+    let mut messages: Vec<serenity::Message> = Vec::with_capacity(usize::from(limit));
+    let mut before: Option<serenity::MessageId> = None;
+
+    while messages.len() < usize::from(limit) {
+        let remaining = usize::from(limit) - messages.len();
+        let batch_size = remaining.min(100) as u8;
+
+        let mut request = serenity::GetMessages::new().limit(batch_size);
+        if let Some(before) = before {
+            request = request.before(before);
+        }
+
+        let batch = channel_id.messages(ctx.http(), request).await?;
+        let batch_len = batch.len();
+        before = batch.last().map(|message| message.id);
+        messages.extend(batch);
+
+        if batch_len < usize::from(batch_size) {
+            break;
+        }
+    }
+
+    Ok(messages)
+}
+
+fn content_preview(message: &serenity::Message) -> String {
+    let content = message.content.trim();
+    if content.is_empty() {
+        return if message.attachments.is_empty() {
+            "*No text content*".to_owned()
+        } else {
+            format!("*{} attachment(s)*", message.attachments.len())
+        };
+    }
+
+    let content = content.replace('@', "@\u{200B}").replace('\n', " ");
+    if content.chars().count() <= CONTENT_PREVIEW_LEN {
+        return content;
+    }
+
+    let mut output: String = content.chars().take(CONTENT_PREVIEW_LEN).collect();
+    output.push('…');
+    output
+}
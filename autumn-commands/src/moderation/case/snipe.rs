@@ -0,0 +1,81 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::snipe::get_deleted_message;
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "snipe",
+    desc: "Show the most recently deleted message in this channel.",
+    category: "moderation",
+    usage: "!snipe",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!snipe"],
+    related: &["editsnipe", "userlogs"],
+};
+
+const CONTENT_MAX_LEN: usize = 1800;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn snipe(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(message) =
+        get_deleted_message(&ctx.data().db, guild_id.get(), ctx.channel_id().get()).await?
+    else {
+        ctx.say("Nothing to snipe in this channel.").await?;
+        return Ok(());
+    };
+
+    let mut lines = vec![
+        format!("**Author :** <@{}>", message.author_user_id),
+        format!(
+            "**Content :** {}",
+            content_display(&message.content, CONTENT_MAX_LEN)
+        ),
+    ];
+    if let Some(attachments) = message.attachment_summary.as_deref() {
+        lines.push(format!("**Attachments :**\n{}", attachments));
+    }
+    lines.push(format!("**Deleted :** <t:{}:R>", message.sniped_at));
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Sniped Message")
+        .description(lines.join("\n"));
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub(crate) fn content_display(content: &str, max_len: usize) -> String {
+    let content = content.trim();
+    if content.is_empty() {
+        return "*No text content*".to_owned();
+    }
+
+    if content.chars().count() <= max_len {
+        return content.to_owned();
+    }
+
+    let mut output: String = content.chars().take(max_len).collect();
+    output.push('…');
+    output
+}
@@ -0,0 +1,102 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::{guild_only_message, target_profile_from_user, usage_message};
+use autumn_core::{Context, Error};
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "summarize",
+    desc: "Summarize a user's moderation history with the LLM.",
+    category: "moderation",
+    usage: "!summarize <user>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!summarize @user"],
+    related: &["modlogs", "notes"],
+};
+
+/// Discord's limit on embed descriptions.
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn summarize(
+    ctx: Context<'_>,
+    #[description = "The user to summarize"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let Some(llm) = ctx.data().llm.as_ref() else {
+        ctx.say("No LLM backend is configured, so summaries are unavailable.")
+            .await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+
+    let profile = target_profile_from_user(&user);
+    let subject = format!("{} ({})", profile.display_name, user.id.get());
+    let summary = match llm
+        .summarize_moderation_history(&ctx.data().db, guild_id.get(), user.id.get(), &subject)
+        .await
+    {
+        Ok(Some(summary)) => summary,
+        Ok(None) => {
+            ctx.say(format!(
+                "<@{}> has no cases, warnings or notes to summarize.",
+                user.id.get()
+            ))
+            .await?;
+            return Ok(());
+        }
+        Err(source) => {
+            error!(?source, "failed to summarize moderation history");
+            ctx.say("The LLM couldn't produce a summary right now. Try again later.")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let mut description: String = summary
+        .text
+        .replace('@', "@\u{200B}")
+        .chars()
+        .take(EMBED_DESCRIPTION_LIMIT)
+        .collect();
+    if description.trim().is_empty() {
+        description = "The LLM returned an empty summary.".to_owned();
+    }
+
+    let mut author =
+        serenity::CreateEmbedAuthor::new(format!("Moderation Summary — {}", profile.display_name));
+    if let Some(avatar_url) = profile.avatar_url {
+        author = author.icon_url(avatar_url);
+    }
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .author(author)
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Generated from {} cases, {} active warnings and {} notes. Check the records before acting.",
+            summary.cases, summary.warnings, summary.notes
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::watchlist::remove_watch;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "unwatch",
+    desc: "Take a member off the watchlist.",
+    category: "moderation",
+    usage: "!unwatch <user>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!unwatch @user"],
+    related: &["watch", "watchlist"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn unwatch(
+    ctx: Context<'_>,
+    #[description = "The member to stop watching"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if remove_watch(&ctx.data().db, guild_id.get(), user.id.get()).await? {
+        ctx.say(format!("Stopped watching <@{}>.", user.id.get()))
+            .await?;
+    } else {
+        ctx.say(format!("<@{}> isn't on the watchlist.", user.id.get()))
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,22 +1,31 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::pagination_options;
+use crate::checks::require_manage_messages;
 use crate::moderation::embeds::{guild_only_message, usage_message};
 use autumn_core::{Context, Error};
 use autumn_database::impls::user_logs::{UserLogFilters, list_recent_user_logs};
-use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::pagination::paginate_embed_pages_with_options;
 
 pub const META: CommandMeta = CommandMeta {
     name: "userlogs",
     desc: "View recent user message edit/delete activity.",
     category: "moderation",
     usage: "!userlogs [target_user] [event]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!userlogs @user", "!userlogs @user message_delete"],
+    related: &["userlogchannel", "snipe", "search"],
 };
 
 const LOGS_PER_PAGE: usize = 5;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn userlogs(
     ctx: Context<'_>,
     #[description = "Filter by target user"] target_user: Option<serenity::User>,
@@ -28,17 +37,6 @@ pub async fn userlogs(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     if event
         .as_deref()
         .is_some_and(|value| value.trim().is_empty())
@@ -153,7 +151,8 @@ pub async fn userlogs(
         pages.push(body.trim_end().to_owned());
     }
 
-    paginate_embed_pages(ctx, "User Logs", &pages, 1).await?;
+    paginate_embed_pages_with_options(ctx, "User Logs", &pages, 1, pagination_options(ctx).await)
+        .await?;
     Ok(())
 }
 
@@ -1,19 +1,28 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::pagination_options;
+use crate::checks::require_manage_messages;
 use crate::moderation::embeds::{
     fetch_target_profile, guild_only_message, usage_message, warnings_window_label_days,
 };
 use autumn_core::{Context, Error};
 use autumn_database::impls::warnings::{now_unix_secs, warnings_since};
-use autumn_utils::pagination::paginate_embed_pages_with_icon;
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::page_builder::{PageBuilder, PageEntry};
+use autumn_utils::pagination::{PaginationOptions, paginate_embed_pages_with_options};
 
 pub const META: CommandMeta = CommandMeta {
     name: "warnings",
     desc: "Show warning history for a user in a time window.",
     category: "moderation",
     usage: "!warnings <user> [days|all]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &[
+        "!warnings @user",
+        "!warnings @user 30",
+        "!warnings @user all",
+    ],
+    related: &["warn", "unwarn", "warndecay"],
 };
 
 const DEFAULT_DAYS: u64 = 30;
@@ -24,7 +33,12 @@ enum WarningWindow {
     All,
 }
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn warnings(
     ctx: Context<'_>,
     #[description = "The user to check"] user: Option<serenity::User>,
@@ -35,17 +49,6 @@ pub async fn warnings(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -68,52 +71,46 @@ pub async fn warnings(
             "Total warnings in {}: **0**\n\nNo warnings in this period.",
             window_label
         );
-        paginate_embed_pages_with_icon(
+        let options = PaginationOptions {
+            author_icon_url: target_profile.avatar_url.as_deref(),
+            ..pagination_options(ctx).await
+        };
+        paginate_embed_pages_with_options(
             ctx,
             &format!("Warnings for {}", target_profile.display_name),
             &[page],
             1,
-            target_profile.avatar_url.as_deref(),
+            options,
         )
         .await?;
         return Ok(());
     }
 
     let total = entries.len();
-    let total_pages = total_pages(total, WARNINGS_PER_PAGE);
-    let pages = (1..=total_pages)
-        .map(|current_page| {
-            let (start, end) = page_window(total, WARNINGS_PER_PAGE, current_page);
-            let mut lines = String::new();
-
-            lines.push_str(&format!(
-                "Total warnings in {}: **{}**\n\n",
-                window_label, total
-            ));
-
-            for display_index in start..end {
-                let reverse_index = total - 1 - display_index;
-                let entry = &entries[reverse_index];
-
-                lines.push_str(&format!(
-                    "#{idx} • by <@{mod_id}>\n**Reason :** {reason}\n**When :** <t:{ts}:R> • <t:{ts}:f>\n\n",
-                    idx = reverse_index + 1,
-                    mod_id = entry.moderator_id,
-                    reason = entry.reason.replace('@', "@\u{200B}"),
-                    ts = entry.warned_at,
-                ));
-            }
-
-            lines.trim_end().to_owned()
-        })
-        .collect::<Vec<_>>();
-
-    paginate_embed_pages_with_icon(
+    let mut builder = PageBuilder::new(WARNINGS_PER_PAGE)
+        .header(format!("Total warnings in {}: **{}**", window_label, total));
+    for (index, entry) in entries.iter().enumerate().rev() {
+        builder.push(
+            PageEntry::new(format!("#{} • by <@{}>", index + 1, entry.moderator_id))
+                .field("Reason", entry.reason.replace('@', "@\u{200B}"))
+                .field(
+                    "When",
+                    format!("<t:{ts}:R> • <t:{ts}:f>", ts = entry.warned_at),
+                ),
+        );
+    }
+    let pages = builder.build();
+
+    let options = PaginationOptions {
+        author_icon_url: target_profile.avatar_url.as_deref(),
+        ..pagination_options(ctx).await
+    };
+    paginate_embed_pages_with_options(
         ctx,
         &format!("Warnings for {}", target_profile.display_name),
         &pages,
         1,
-        target_profile.avatar_url.as_deref(),
+        options,
     )
     .await?;
 
@@ -135,17 +132,3 @@ fn parse_window(value: Option<&str>) -> WarningWindow {
 
     WarningWindow::Days(days)
 }
-
-fn total_pages(total_items: usize, per_page: usize) -> usize {
-    let per_page = per_page.max(1);
-    let pages = total_items.div_ceil(per_page);
-    pages.max(1)
-}
-
-fn page_window(total_items: usize, per_page: usize, page: usize) -> (usize, usize) {
-    let per_page = per_page.max(1);
-    let page = page.max(1);
-    let start = (page - 1).saturating_mul(per_page).min(total_items);
-    let end = (start + per_page).min(total_items);
-    (start, end)
-}
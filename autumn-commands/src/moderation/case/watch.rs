@@ -0,0 +1,101 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_messages;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::guild_config::get_guild_config;
+use autumn_database::impls::watchlist::{
+    MAX_WATCHED_USERS, WATCH_NOTE_MAX_CHARS, add_watch, count_watches,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "watch",
+    desc: "Post a member's messages, joins and name changes to the watchlist channel.",
+    category: "moderation",
+    usage: "!watch <user> [note]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &[
+        "!watch @user",
+        "!watch @user Suspected alt of a banned account",
+    ],
+    related: &["unwatch", "watchlist", "notes"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn watch(
+    ctx: Context<'_>,
+    #[description = "The member to watch"] user: Option<serenity::User>,
+    #[description = "Why they're being watched"]
+    #[rest]
+    note: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if user.bot {
+        ctx.say("Bots can't be watched.").await?;
+        return Ok(());
+    }
+
+    let note = note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+    if note.is_some_and(|note| note.chars().count() > WATCH_NOTE_MAX_CHARS) {
+        ctx.say(format!(
+            "Notes can be at most {} characters.",
+            WATCH_NOTE_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let db = &ctx.data().db;
+    let config = get_guild_config(db, guild_id.get()).await?;
+    if !config.watched_user_ids.contains(&user.id.get())
+        && count_watches(db, guild_id.get()).await? >= MAX_WATCHED_USERS
+    {
+        ctx.say(format!(
+            "The watchlist is full ({} members). Remove someone with `!unwatch` first.",
+            MAX_WATCHED_USERS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let added = add_watch(
+        db,
+        guild_id.get(),
+        user.id.get(),
+        note,
+        ctx.author().id.get(),
+    )
+    .await?;
+
+    let mut reply = if added {
+        format!("Now watching <@{}>.", user.id.get())
+    } else {
+        format!("Updated the watchlist note for <@{}>.", user.id.get())
+    };
+    if config.watchlist_channel_id.is_none() {
+        reply.push_str(
+            " No alerts will be posted until a channel is set with `!watchlist channel`.",
+        );
+    }
+    ctx.say(reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::pagination_options;
+use crate::checks::{require_manage_guild, require_manage_messages};
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::guild_config::get_guild_config;
+use autumn_database::impls::watchlist::{
+    clear_watchlist_channel_id, list_watches, set_watchlist_channel_id,
+};
+use autumn_utils::page_builder::{PageBuilder, PageEntry};
+use autumn_utils::pagination::paginate_embed_pages_with_options;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "watchlist",
+    desc: "List watched members, or set the channel their activity is posted to.",
+    category: "moderation",
+    usage: "!watchlist [channel <#channel|clear>]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &[
+        "!watchlist",
+        "!watchlist channel #watchlist",
+        "!watchlist channel clear",
+    ],
+    related: &["watch", "unwatch"],
+};
+
+const WATCHES_PER_PAGE: usize = 8;
+
+/// List watched members and where their activity is posted.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("watchlist_channel"),
+    check = "require_manage_messages"
+)]
+pub async fn watchlist(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let channel = match get_guild_config(db, guild_id.get())
+        .await?
+        .watchlist_channel_id
+    {
+        Some(channel_id) => format!("Alerts go to <#{}>.", channel_id),
+        None => "No alert channel set. Set one with `!watchlist channel #channel`.".to_owned(),
+    };
+
+    let watches = list_watches(db, guild_id.get()).await?;
+    if watches.is_empty() {
+        ctx.say(format!("Nobody is on the watchlist. {}", channel))
+            .await?;
+        return Ok(());
+    }
+
+    let mut builder = PageBuilder::new(WATCHES_PER_PAGE).header(channel);
+    for watch in &watches {
+        let mut entry = PageEntry::new(format!("<@{}> ({})", watch.user_id, watch.user_id)).field(
+            "Added",
+            format!("<t:{}:R> by <@{}>", watch.created_at, watch.added_by),
+        );
+        if let Some(note) = &watch.note {
+            entry = entry.field("Note", note.replace('@', "@\u{200B}"));
+        }
+        builder.push(entry);
+    }
+    let pages = builder.build();
+
+    paginate_embed_pages_with_options(ctx, "Watchlist", &pages, 1, pagination_options(ctx).await)
+        .await?;
+
+    Ok(())
+}
+
+/// Set or clear the channel watchlist alerts are posted to.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "channel",
+    check = "require_manage_guild"
+)]
+pub async fn watchlist_channel(
+    ctx: Context<'_>,
+    #[description = "Channel mention/id, or 'clear'"] input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(input) = input
+        .as_deref()
+        .map(str::trim)
+        .filter(|input| !input.is_empty())
+    else {
+        ctx.say("Provide a valid channel mention/id, or `clear`.")
+            .await?;
+        return Ok(());
+    };
+
+    if input.eq_ignore_ascii_case("clear") {
+        clear_watchlist_channel_id(&ctx.data().db, guild_id.get()).await?;
+        ctx.say("Watchlist channel cleared. Alerts are off.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(channel_id) = parse_channel_id(input) else {
+        ctx.say("Provide a valid channel mention/id, or `clear`.")
+            .await?;
+        return Ok(());
+    };
+
+    set_watchlist_channel_id(&ctx.data().db, guild_id.get(), channel_id).await?;
+    ctx.say(format!("Watchlist alerts now go to <#{}>.", channel_id))
+        .await?;
+
+    Ok(())
+}
+
+fn parse_channel_id(raw: &str) -> Option<u64> {
+    if let Ok(id) = raw.parse::<u64>() {
+        return Some(id);
+    }
+
+    if raw.starts_with("<#") && raw.ends_with('>') {
+        return raw
+            .trim_start_matches("<#")
+            .trim_end_matches('>')
+            .parse::<u64>()
+            .ok();
+    }
+
+    None
+}
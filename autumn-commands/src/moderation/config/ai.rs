@@ -0,0 +1,319 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{require_manage_guild, require_permission};
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::ai_config::{
+    LLM_SYSTEM_PROMPT_MAX_CHARS, TRANSLATE_LANGUAGE_MAX_CHARS, get_llm_suggest_reasons,
+    get_llm_system_prompt, get_llm_threads, get_translate_language, is_valid_translate_language,
+    set_llm_suggest_reasons, set_llm_system_prompt, set_llm_threads, set_translate_language,
+};
+use autumn_database::impls::llm_chat::clear_llm_chat_history;
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "ai",
+    desc: "Configure how Autumn talks when mentioned.",
+    category: "moderation",
+    usage: "!ai <reset|reasons [on|off]|threads [on|off]|language [name|reset]|prompt [set <text>|reset]>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!ai threads on",
+        "!ai language french",
+        "!ai prompt set Be strict about scam links.",
+    ],
+    related: &["aitoggle"],
+};
+
+/// Configure how Autumn talks when mentioned.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("prompt", "reset", "reasons", "threads", "language")
+)]
+pub async fn ai(ctx: Context<'_>) -> Result<(), Error> {
+    show_prompt(ctx).await
+}
+
+/// Make Autumn forget the conversation in this channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn reset(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let cleared =
+        clear_llm_chat_history(&ctx.data().db, guild_id.get(), ctx.channel_id().get()).await?;
+
+    let content = if cleared == 0 {
+        "There's no conversation to forget in this channel."
+    } else {
+        "Conversation reset. The next mention starts fresh."
+    };
+    ctx.say(content).await?;
+
+    Ok(())
+}
+
+/// Suggest a reason when `!warn` replies to a message without one.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn reasons(
+    ctx: Context<'_>,
+    #[description = "Desired state: on, off, or status"] state: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    match state
+        .as_deref()
+        .map(str::trim)
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        None | Some("status") => {
+            let enabled = get_llm_suggest_reasons(db, guild_id.get()).await?;
+            ctx.say(format!(
+                "Reason suggestions are **{}**.",
+                if enabled { "on" } else { "off" }
+            ))
+            .await?;
+        }
+        Some("on") => {
+            set_llm_suggest_reasons(db, guild_id.get(), true).await?;
+            let mut reply = "Reason suggestions are **on**. Reply to a message with `!warn` and \
+                             no reason to get one."
+                .to_owned();
+            if ctx.data().llm.is_none() {
+                reply.push_str(" No LLM backend is configured yet, so none will be offered.");
+            }
+            ctx.say(reply).await?;
+        }
+        Some("off") => {
+            set_llm_suggest_reasons(db, guild_id.get(), false).await?;
+            ctx.say("Reason suggestions are **off**.").await?;
+        }
+        Some(_) => {
+            ctx.say("Usage: `!ai reasons [on|off]`").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Answer mentions in a new thread where the conversation continues without
+/// mentioning Autumn.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn threads(
+    ctx: Context<'_>,
+    #[description = "Desired state: on, off, or status"] state: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    match state
+        .as_deref()
+        .map(str::trim)
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        None | Some("status") => {
+            let enabled = get_llm_threads(db, guild_id.get()).await?;
+            ctx.say(format!(
+                "Threaded conversations are **{}**.",
+                if enabled { "on" } else { "off" }
+            ))
+            .await?;
+        }
+        Some("on") => {
+            set_llm_threads(db, guild_id.get(), true).await?;
+            ctx.say(
+                "Threaded conversations are **on**. Mentioning Autumn in a channel now opens a \
+                 thread, and messages in it get replies without a mention. Autumn needs the \
+                 Create Public Threads permission.",
+            )
+            .await?;
+        }
+        Some("off") => {
+            set_llm_threads(db, guild_id.get(), false).await?;
+            ctx.say("Threaded conversations are **off**.").await?;
+        }
+        Some(_) => {
+            ctx.say("Usage: `!ai threads [on|off]`").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Set the language `!translate` translates into.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "Language name, or reset"]
+    #[rest]
+    language: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let Some(language) = language
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+    else {
+        let current = get_translate_language(db, guild_id.get()).await?;
+        ctx.say(format!("`!translate` translates into **{}**.", current))
+            .await?;
+        return Ok(());
+    };
+
+    if language.eq_ignore_ascii_case("reset") {
+        set_translate_language(db, guild_id.get(), None).await?;
+        let current = get_translate_language(db, guild_id.get()).await?;
+        ctx.say(format!("Translation language reset to **{}**.", current))
+            .await?;
+        return Ok(());
+    }
+
+    if !is_valid_translate_language(language) {
+        ctx.say(format!(
+            "Languages are names like `Spanish` or `Brazilian Portuguese`, up to {} characters.",
+            TRANSLATE_LANGUAGE_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    set_translate_language(db, guild_id.get(), Some(language)).await?;
+    ctx.say(format!(
+        "`!translate` now translates into **{}**.",
+        language
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Show this server's system prompt.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("prompt_set", "prompt_reset"),
+    check = "require_manage_guild"
+)]
+pub async fn prompt(ctx: Context<'_>) -> Result<(), Error> {
+    show_prompt(ctx).await
+}
+
+/// Replace Autumn's persona in this server with your own system prompt.
+#[poise::command(prefix_command, slash_command, category = "Moderation", rename = "set")]
+pub async fn prompt_set(
+    ctx: Context<'_>,
+    #[description = "The system prompt to use"]
+    #[rest]
+    text: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(text) = text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+    else {
+        ctx.say("Usage: `!ai prompt set <text>`").await?;
+        return Ok(());
+    };
+
+    if text.chars().count() > LLM_SYSTEM_PROMPT_MAX_CHARS {
+        ctx.say(format!(
+            "System prompts can be at most {} characters.",
+            LLM_SYSTEM_PROMPT_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    set_llm_system_prompt(&ctx.data().db, guild_id.get(), Some(text)).await?;
+    ctx.say("Custom system prompt saved. It applies from the next mention.")
+        .await?;
+
+    Ok(())
+}
+
+/// Go back to Autumn's default persona.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "reset"
+)]
+pub async fn prompt_reset(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_llm_system_prompt(&ctx.data().db, guild_id.get(), None).await?;
+    ctx.say("System prompt reset to the default.").await?;
+
+    Ok(())
+}
+
+async fn show_prompt(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    if !require_permission(ctx, serenity::Permissions::MANAGE_GUILD).await? {
+        return Ok(());
+    }
+
+    let description = match get_llm_system_prompt(&ctx.data().db, guild_id.get()).await? {
+        Some(prompt) => prompt.replace('@', "@\u{200B}"),
+        None => "Using the default persona.".to_owned(),
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("System Prompt")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: reset, reasons, threads, language, prompt set <text>, prompt reset",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
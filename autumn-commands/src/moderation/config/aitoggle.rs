@@ -1,19 +1,27 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::checks::require_manage_guild;
 use crate::moderation::embeds::{guild_only_message, usage_message};
 use autumn_core::{Context, Error};
 use autumn_database::impls::ai_config::{get_llm_enabled, set_llm_enabled};
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "aitoggle",
     desc: "Enable or disable AI mention replies for this server.",
     category: "moderation",
     usage: "!aitoggle <on|off|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!aitoggle on", "!aitoggle status"],
+    related: &["ai"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
 pub async fn aitoggle(
     ctx: Context<'_>,
     #[description = "Desired state: on, off, or status"] state: Option<String>,
@@ -23,17 +31,6 @@ pub async fn aitoggle(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw_state) = state.as_deref().map(str::trim) else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
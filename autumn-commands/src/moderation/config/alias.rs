@@ -0,0 +1,156 @@
+use poise::serenity_prelude as serenity;
+
+use super::command::resolve_command_name;
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::command_aliases::{
+    COMMAND_ALIAS_MAX_CHARS, MAX_COMMAND_ALIASES_PER_GUILD, is_valid_command_alias,
+    list_command_aliases, remove_command_alias, set_command_alias,
+};
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "alias",
+    desc: "Add server-specific names for commands, like !mute for !timeout.",
+    category: "moderation",
+    usage: "!alias [add <alias> <command>|remove <alias>]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!alias", "!alias add mute timeout", "!alias remove mute"],
+    related: &["command"],
+};
+
+/// List this server's command aliases.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("add", "remove"),
+    check = "require_manage_guild"
+)]
+pub async fn alias(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let aliases = list_command_aliases(&ctx.data().db, guild_id.get()).await?;
+    let description = if aliases.is_empty() {
+        "No aliases set.".to_owned()
+    } else {
+        aliases
+            .iter()
+            .map(|entry| format!("`!{}` → `!{}`", entry.alias, entry.command_name))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Command Aliases")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "{}/{} aliases · Subcommands: add <alias> <command>, remove <alias>",
+            aliases.len(),
+            MAX_COMMAND_ALIASES_PER_GUILD
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Add an alias, or point an existing one at a different command.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "New name, e.g. mute"] alias: Option<String>,
+    #[description = "Command it runs, e.g. timeout"] command: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let (Some(alias), Some(raw_command)) = (alias, command) else {
+        ctx.say("Usage: `!alias add <alias> <command>`").await?;
+        return Ok(());
+    };
+
+    let alias = alias.trim().trim_start_matches('!').to_ascii_lowercase();
+    if !is_valid_command_alias(&alias) {
+        ctx.say(format!(
+            "Aliases are one word of up to {} letters, digits, `-` or `_`.",
+            COMMAND_ALIAS_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    if resolve_command_name(ctx, &alias).is_some() {
+        ctx.say(format!("`!{}` is already a command.", alias))
+            .await?;
+        return Ok(());
+    }
+
+    let Some(command_name) = resolve_command_name(ctx, &raw_command) else {
+        ctx.say(format!("Unknown command: `{}`", raw_command.trim()))
+            .await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let existing = list_command_aliases(db, guild_id.get()).await?;
+    if existing.len() >= MAX_COMMAND_ALIASES_PER_GUILD
+        && !existing.iter().any(|entry| entry.alias == alias)
+    {
+        ctx.say(format!(
+            "This server already has {} aliases. Remove one first.",
+            MAX_COMMAND_ALIASES_PER_GUILD
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    set_command_alias(
+        db,
+        guild_id.get(),
+        &alias,
+        &command_name,
+        ctx.author().id.get(),
+    )
+    .await?;
+
+    ctx.say(format!("`!{}` now runs `!{}`.", alias, command_name))
+        .await?;
+
+    Ok(())
+}
+
+/// Remove an alias.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Alias to remove"] alias: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(alias) = alias else {
+        ctx.say("Usage: `!alias remove <alias>`").await?;
+        return Ok(());
+    };
+
+    let alias = alias.trim().trim_start_matches('!').to_ascii_lowercase();
+    let content = if remove_command_alias(&ctx.data().db, guild_id.get(), &alias).await? {
+        format!("Removed the `!{}` alias.", alias)
+    } else {
+        format!("There's no `!{}` alias.", alias)
+    };
+    ctx.say(content).await?;
+
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::antiraid::{
+    end_raid, get_antiraid_config, set_antiraid_duration, set_antiraid_enabled,
+    set_antiraid_response, set_antiraid_threshold,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::parse_duration_seconds;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "antiraid",
+    desc: "Manage join-rate raid detection.",
+    category: "moderation",
+    usage: "!antiraid <enable|disable|threshold|response|duration|end>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!antiraid enable",
+        "!antiraid threshold 10 30s",
+        "!antiraid response lockdown",
+    ],
+    related: &["raidmode", "antispam"],
+};
+
+/// Manage join-rate raid detection.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("enable", "disable", "threshold", "response", "duration", "end"),
+    check = "require_manage_guild"
+)]
+pub async fn antiraid(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_antiraid_config(&ctx.data().db, guild_id.get()).await?;
+    let now = unix_now();
+
+    let (enabled, joins, window, duration, raise_verification, kick_new_joins, active_until) =
+        match &config {
+            Some(cfg) => (
+                cfg.enabled,
+                cfg.join_threshold,
+                cfg.join_window_seconds,
+                cfg.raid_duration_seconds,
+                cfg.raise_verification,
+                cfg.kick_new_joins,
+                cfg.raid_active_until.filter(|_| cfg.raid_active(now)),
+            ),
+            None => (false, 10, 10, 600, true, false, None),
+        };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+    let raid_state = match active_until {
+        Some(until) => format!("Active (ends <t:{}:R>)", until),
+        None => "None".to_owned(),
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Anti-Raid Config")
+        .description(format!(
+            "**Status :** {}\n\
+             **Join Limit :** {} join(s) per {}\n\
+             **Raid Duration :** {}\n\
+             **Response :** {}\n\
+             **Current Raid :** {}\n\n\
+             Going over the join limit starts a raid and alerts the modlog channel. \
+             A raised verification level stays until `!antiraid end`.",
+            status,
+            joins,
+            format_compact_duration(window as u64),
+            format_compact_duration(duration as u64),
+            response_display(raise_verification, kick_new_joins),
+            raid_state,
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, threshold, response, duration, end",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the anti-raid.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_antiraid_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Anti-raid has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the anti-raid.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_antiraid_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Anti-raid has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set how many joins within a window count as a raid.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn threshold(
+    ctx: Context<'_>,
+    #[description = "Join count and window (e.g. 10 10s)"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!antiraid threshold <count> <duration>` (e.g. `10 10s`)";
+
+    let Some((count, seconds)) = input.as_deref().and_then(parse_join_limit) else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    set_antiraid_threshold(&ctx.data().db, guild_id.get(), count, seconds as i64).await?;
+    ctx.say(format!(
+        "Raid join limit set to **{}** within **{}**.",
+        count,
+        format_compact_duration(seconds)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set what happens when a raid is detected.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn response(
+    ctx: Context<'_>,
+    #[description = "Response: alert, verification, kick, or lockdown"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!antiraid response <alert|verification|kick|lockdown>`\n\
+                 • `alert` — Only alert the modlog channel\n\
+                 • `verification` — Raise the verification level to High\n\
+                 • `kick` — Kick everyone who joins during the raid\n\
+                 • `lockdown` — Raise the verification level and kick new joins";
+
+    let parsed = match input
+        .as_deref()
+        .map(|raw| raw.trim().to_lowercase())
+        .as_deref()
+    {
+        Some("alert") => Some((false, false)),
+        Some("verification") => Some((true, false)),
+        Some("kick") => Some((false, true)),
+        Some("lockdown") => Some((true, true)),
+        _ => None,
+    };
+
+    let Some((raise_verification, kick_new_joins)) = parsed else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    set_antiraid_response(
+        &ctx.data().db,
+        guild_id.get(),
+        raise_verification,
+        kick_new_joins,
+    )
+    .await?;
+
+    ctx.say(format!(
+        "Raid response set to **{}**.",
+        response_display(raise_verification, kick_new_joins)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set how long a detected raid lasts.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn duration(
+    ctx: Context<'_>,
+    #[description = "Raid duration (e.g. 10m)"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(seconds) = input
+        .as_deref()
+        .and_then(|raw| parse_duration_seconds(raw.trim()))
+        .filter(|seconds| (60..=86_400).contains(seconds))
+    else {
+        ctx.say("Usage: `!antiraid duration <duration>` (between `1m` and `1d`)")
+            .await?;
+        return Ok(());
+    };
+
+    set_antiraid_duration(&ctx.data().db, guild_id.get(), seconds as i64).await?;
+    ctx.say(format!(
+        "Raid duration set to **{}**.",
+        format_compact_duration(seconds)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// End the current raid and restore the previous verification level.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn end(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let previous_level = end_raid(&ctx.data().db, guild_id.get()).await?;
+
+    let Some(level) = previous_level.and_then(|level| u8::try_from(level).ok()) else {
+        ctx.say("Raid state cleared.").await?;
+        return Ok(());
+    };
+
+    let restored = guild_id
+        .edit(
+            ctx.http(),
+            serenity::EditGuild::new()
+                .verification_level(serenity::VerificationLevel::from(level))
+                .audit_log_reason("Anti-raid: raid ended"),
+        )
+        .await;
+
+    match restored {
+        Ok(_) => {
+            ctx.say("Raid state cleared and the verification level has been restored.")
+                .await?;
+        }
+        Err(_) => {
+            ctx.say(
+                "Raid state cleared, but I couldn't restore the verification level. \
+                 Please check my **Manage Server** permission.",
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `<count> <duration>` with a count between 2 and 500 and a window of
+/// at most an hour.
+fn parse_join_limit(raw: &str) -> Option<(i32, u64)> {
+    let mut parts = raw.split_whitespace();
+    let count = parts.next()?.parse::<i32>().ok()?;
+    let seconds = parse_duration_seconds(parts.next()?)?;
+    if parts.next().is_some() || !(2..=500).contains(&count) || !(1..=3_600).contains(&seconds) {
+        return None;
+    }
+
+    Some((count, seconds))
+}
+
+fn response_display(raise_verification: bool, kick_new_joins: bool) -> &'static str {
+    match (raise_verification, kick_new_joins) {
+        (true, true) => "Lockdown (raise verification, kick new joins)",
+        (true, false) => "Raise Verification Level",
+        (false, true) => "Kick New Joins",
+        (false, false) => "Alert Only",
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
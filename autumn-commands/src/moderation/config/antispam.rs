@@ -0,0 +1,276 @@
+use poise::serenity_prelude as serenity;
+
+use super::automod::{FILTER_ACTION_HELP, filter_action_display, parse_filter_action};
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::antispam::{
+    get_antispam_config, set_antispam_action, set_antispam_duplicate_action,
+    set_antispam_duplicates, set_antispam_enabled, set_antispam_rate,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::parse_duration_seconds;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "antispam",
+    desc: "Manage message-rate and duplicate spam detection.",
+    category: "moderation",
+    usage: "!antispam <enable|disable|action|duplicate-action|rate|duplicates>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!antispam enable",
+        "!antispam rate 5 10s",
+        "!antispam action timeout",
+    ],
+    related: &["antiraid", "capsfilter"],
+};
+
+/// Manage message-rate and duplicate spam detection.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands(
+        "enable",
+        "disable",
+        "action",
+        "duplicate_action",
+        "rate",
+        "duplicates"
+    ),
+    check = "require_manage_guild"
+)]
+pub async fn antispam(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_antispam_config(&ctx.data().db, guild_id.get()).await?;
+
+    let (enabled, rate, rate_window, dups, dup_window, action, dup_action) = match &config {
+        Some(cfg) => (
+            cfg.enabled,
+            cfg.message_threshold,
+            cfg.message_window_seconds,
+            cfg.duplicate_threshold,
+            cfg.duplicate_window_seconds,
+            cfg.action.as_str(),
+            cfg.duplicate_action.as_str(),
+        ),
+        None => (
+            false,
+            6,
+            5,
+            3,
+            30,
+            "timeout_delete_and_log",
+            "delete_and_log",
+        ),
+    };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Anti-Spam Config")
+        .description(format!(
+            "**Status :** {}\n\
+             **Message Rate :** {} message(s) per {}\n\
+             **Duplicates :** {} identical message(s) per {}\n\
+             **Rate Action :** {}\n\
+             **Duplicate Action :** {}\n\n\
+             Going over either limit triggers its action once per window. \
+             Duplicates are counted across channels. \
+             `warn` also counts towards automatic escalation.",
+            status,
+            rate,
+            format_compact_duration(rate_window as u64),
+            dups,
+            format_compact_duration(dup_window as u64),
+            filter_action_display(action),
+            filter_action_display(dup_action),
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, action, duplicate-action, rate, duplicates",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the anti-spam.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_antispam_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Anti-spam has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the anti-spam.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_antispam_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Anti-spam has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a user sends messages too quickly.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(action) = input.as_deref().and_then(parse_filter_action) else {
+        ctx.say(format!(
+            "Usage: `!antispam action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_antispam_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Message rate action set to **{}**.",
+        filter_action_display(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a user repeats the same message.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "duplicate-action"
+)]
+pub async fn duplicate_action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(action) = input.as_deref().and_then(parse_filter_action) else {
+        ctx.say(format!(
+            "Usage: `!antispam duplicate-action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_antispam_duplicate_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Repeated message action set to **{}**.",
+        filter_action_display(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set how many messages a user may send within a window.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn rate(
+    ctx: Context<'_>,
+    #[description = "Message count and window (e.g. 6 5s)"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!antispam rate <count> <duration>` (e.g. `6 5s`)";
+
+    let Some((count, seconds)) = input.as_deref().and_then(parse_limit) else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    set_antispam_rate(&ctx.data().db, guild_id.get(), count, seconds as i64).await?;
+    ctx.say(format!(
+        "Message rate limit set to **{}** within **{}**.",
+        count,
+        format_compact_duration(seconds)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set how many identical messages a user may send within a window.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn duplicates(
+    ctx: Context<'_>,
+    #[description = "Duplicate count and window (e.g. 3 30s)"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!antispam duplicates <count> <duration>` (e.g. `3 30s`)";
+
+    let Some((count, seconds)) = input.as_deref().and_then(parse_limit) else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    set_antispam_duplicates(&ctx.data().db, guild_id.get(), count, seconds as i64).await?;
+    ctx.say(format!(
+        "Duplicate message limit set to **{}** within **{}**.",
+        count,
+        format_compact_duration(seconds)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Parse `<count> <duration>` with a count between 1 and 100.
+fn parse_limit(raw: &str) -> Option<(i32, u64)> {
+    let mut parts = raw.split_whitespace();
+    let count = parts.next()?.parse::<i32>().ok()?;
+    let seconds = parse_duration_seconds(parts.next()?)?;
+    if parts.next().is_some() || !(1..=100).contains(&count) || seconds > 86_400 {
+        return None;
+    }
+
+    Some((count, seconds))
+}
@@ -0,0 +1,270 @@
+use poise::serenity_prelude as serenity;
+
+use super::automod::{FILTER_ACTION_HELP, filter_action_display, parse_filter_action};
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::attachment_filter::{
+    add_blocked_extension, get_attachment_filter_config, remove_blocked_extension,
+    set_attachment_channel_blocked, set_attachment_filter_action, set_attachment_filter_enabled,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::files::normalize_extension_input;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "attachmentfilter",
+    desc: "Block file types or attachments in specific channels.",
+    category: "moderation",
+    usage: "!attachmentfilter <enable|disable|action|block|unblock|channel>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!attachmentfilter enable",
+        "!attachmentfilter block exe",
+        "!attachmentfilter channel #media off",
+    ],
+    related: &["imagefilter"],
+};
+
+/// Block file types or attachments in specific channels.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("enable", "disable", "action", "block", "unblock", "channel"),
+    check = "require_manage_guild"
+)]
+pub async fn attachmentfilter(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_attachment_filter_config(&ctx.data().db, guild_id.get()).await?;
+
+    let (enabled, action, extensions, channels) = match &config {
+        Some(cfg) => (
+            cfg.enabled,
+            cfg.action.as_str(),
+            cfg.blocked_extensions.as_slice(),
+            cfg.blocked_channel_ids.as_slice(),
+        ),
+        None => (false, "delete_and_log", [].as_slice(), [].as_slice()),
+    };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+    let extensions_label = if extensions.is_empty() {
+        "None".to_owned()
+    } else {
+        extensions
+            .iter()
+            .map(|ext| format!("`.{}`", ext))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let channels_label = if channels.is_empty() {
+        "None".to_owned()
+    } else {
+        channels
+            .iter()
+            .map(|id| format!("<#{}>", id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Attachment Filter Status")
+        .description(format!(
+            "**Status :** {}\n**Action :** {}\n**Blocked File Types :** {}\n\
+             **No-Attachment Channels :** {}",
+            status,
+            filter_action_display(action),
+            extensions_label,
+            channels_label
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, action, block, unblock, channel",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the attachment filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_attachment_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Attachment filter has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the attachment filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_attachment_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Attachment filter has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a blocked attachment is posted.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(action) = input.as_deref().and_then(parse_filter_action) else {
+        ctx.say(format!(
+            "Usage: `!attachmentfilter action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_attachment_filter_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Attachment filter action set to **{}**.",
+        filter_action_display(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Block a file extension in every channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn block(
+    ctx: Context<'_>,
+    #[description = "File extension to block (e.g. exe)"]
+    #[rest]
+    extension: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(extension) = extension.as_deref().and_then(normalize_extension_input) else {
+        ctx.say("Usage: `!attachmentfilter block <extension>` (e.g. `exe`)")
+            .await?;
+        return Ok(());
+    };
+
+    let added = add_blocked_extension(&ctx.data().db, guild_id.get(), &extension).await?;
+
+    if added {
+        ctx.say(format!("`.{}` files are now blocked.", extension))
+            .await?;
+    } else {
+        ctx.say(format!("`.{}` files are already blocked.", extension))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Unblock a file extension.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn unblock(
+    ctx: Context<'_>,
+    #[description = "File extension to unblock"]
+    #[rest]
+    extension: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(extension) = extension.as_deref().and_then(normalize_extension_input) else {
+        ctx.say("Usage: `!attachmentfilter unblock <extension>`")
+            .await?;
+        return Ok(());
+    };
+
+    let removed = remove_blocked_extension(&ctx.data().db, guild_id.get(), &extension).await?;
+
+    if removed {
+        ctx.say(format!("`.{}` files are no longer blocked.", extension))
+            .await?;
+    } else {
+        ctx.say(format!("`.{}` files are not blocked.", extension))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Block or allow all attachments in a channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn channel(
+    ctx: Context<'_>,
+    #[description = "Channel and on/off (e.g. #general on)"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!attachmentfilter channel <#channel> <on|off>`\n\
+                 • `on` — Block every attachment in the channel\n\
+                 • `off` — Only block the listed file types";
+
+    let mut parts = input.as_deref().unwrap_or_default().split_whitespace();
+    let channel_id = parts.next().and_then(parse_channel_id);
+    let blocked = match parts.next().map(str::to_lowercase).as_deref() {
+        Some("on") => Some(true),
+        Some("off") => Some(false),
+        _ => None,
+    };
+
+    let (Some(channel_id), Some(blocked), None) = (channel_id, blocked, parts.next()) else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    let changed =
+        set_attachment_channel_blocked(&ctx.data().db, guild_id.get(), channel_id, blocked).await?;
+
+    let message = match (blocked, changed) {
+        (true, true) => format!("All attachments are now blocked in <#{}>.", channel_id),
+        (true, false) => format!("Attachments are already blocked in <#{}>.", channel_id),
+        (false, true) => format!("Attachments are allowed again in <#{}>.", channel_id),
+        (false, false) => format!("Attachments are not blocked in <#{}>.", channel_id),
+    };
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+fn parse_channel_id(raw: &str) -> Option<u64> {
+    raw.strip_prefix("<#")
+        .and_then(|value| value.strip_suffix('>'))
+        .unwrap_or(raw)
+        .parse::<u64>()
+        .ok()
+}
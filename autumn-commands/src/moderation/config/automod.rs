@@ -0,0 +1,28 @@
+//! Action parsing and display shared by the automod filter command groups.
+
+/// Help text listing the actions accepted by `parse_filter_action`.
+pub(crate) const FILTER_ACTION_HELP: &str = "• `log` — Only log the violation\n\
+     • `delete` — Delete message and log\n\
+     • `warn` — Warn user, delete message, and log\n\
+     • `timeout` — Timeout user, delete message, and log";
+
+/// Map user input (`log`, `delete`, `warn`, `timeout`) to a stored action.
+pub(crate) fn parse_filter_action(raw: &str) -> Option<&'static str> {
+    match raw.trim().to_lowercase().as_str() {
+        "log" => Some("log_only"),
+        "delete" => Some("delete_and_log"),
+        "warn" => Some("warn_and_log"),
+        "timeout" => Some("timeout_delete_and_log"),
+        _ => None,
+    }
+}
+
+pub(crate) fn filter_action_display(action: &str) -> &str {
+    match action {
+        "log_only" => "Only Log",
+        "delete_and_log" => "Delete and Log",
+        "warn_and_log" => "Warn, Delete and Log",
+        "timeout_delete_and_log" => "Timeout, Delete and Log",
+        _ => "Unknown",
+    }
+}
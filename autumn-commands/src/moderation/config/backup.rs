@@ -0,0 +1,199 @@
+use poise::serenity_prelude as serenity;
+use std::time::Duration;
+use tracing::info;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::backup::{
+    export_guild_backup, guild_has_moderation_history, restore_guild_backup,
+};
+use autumn_database::model::backup::{GUILD_BACKUP_VERSION, GuildBackup};
+use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "backup",
+    desc: "Export this server's configuration and history, or restore a backup.",
+    category: "moderation",
+    usage: "!backup <create|restore>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!backup create", "!backup restore (attach the backup file)"],
+    related: &["import", "config"],
+};
+
+/// Largest backup file accepted by restore, in bytes.
+const MAX_BACKUP_BYTES: u32 = 8 * 1024 * 1024;
+const RESTORE_CONFIRM_TIMEOUT_SECS: u64 = 30;
+
+/// Export or restore this server's data.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("create", "restore")
+)]
+pub async fn backup(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.say(usage_message(META.usage)).await?;
+    Ok(())
+}
+
+/// Export configuration, word lists, cases, warnings and notes as a JSON file.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn create(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let backup = export_guild_backup(&ctx.data().db, guild_id.get()).await?;
+    let bytes = serde_json::to_vec_pretty(&backup)?;
+    let filename = format!(
+        "autumn-backup-{}-{}.json",
+        guild_id.get(),
+        backup.created_at
+    );
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "Backup created with **{}** case(s), **{}** warning(s), **{}** note(s) and \
+                 **{}** filtered word(s). It contains moderation history, so store it somewhere \
+                 private.",
+                backup.cases.len(),
+                backup.warnings.len(),
+                backup.notes.len(),
+                backup.words.len(),
+            ))
+            .attachment(serenity::CreateAttachment::bytes(bytes, filename))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Import a backup file into this server. Only works on servers with no cases,
+/// warnings or notes yet.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn restore(
+    ctx: Context<'_>,
+    #[description = "Backup file from !backup create"] file: Option<serenity::Attachment>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(file) = file else {
+        ctx.say("Usage: `!backup restore` with a backup `.json` file attached.")
+            .await?;
+        return Ok(());
+    };
+
+    if file.size > MAX_BACKUP_BYTES {
+        ctx.say("That file is too large to restore (max 8 MB).")
+            .await?;
+        return Ok(());
+    }
+
+    let bytes = file.download().await?;
+    let Ok(backup) = serde_json::from_slice::<GuildBackup>(&bytes) else {
+        ctx.say("That file is not a valid backup.").await?;
+        return Ok(());
+    };
+
+    if backup.version != GUILD_BACKUP_VERSION {
+        ctx.say(format!(
+            "That backup uses format version {}, but this bot only restores version {}.",
+            backup.version, GUILD_BACKUP_VERSION
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let db = &ctx.data().db;
+    if guild_has_moderation_history(db, guild_id.get()).await? {
+        ctx.say(
+            "This server already has cases, warnings or notes. \
+             Backups can only be restored into a fresh server.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let confirm_embed = serenity::CreateEmbed::new().description(format!(
+        "This will overwrite this server's moderation settings and import \
+         **{}** case(s), **{}** warning(s) and **{}** note(s) from server `{}`.\n\
+         Channel and role settings keep their original IDs; review them afterwards.",
+        backup.cases.len(),
+        backup.warnings.len(),
+        backup.notes.len(),
+        backup.source_guild_id,
+    ));
+
+    let confirmation = prompt_confirm_decline(
+        ctx,
+        "Confirm backup restore",
+        confirm_embed,
+        Duration::from_secs(RESTORE_CONFIRM_TIMEOUT_SECS),
+    )
+    .await?;
+
+    let Some(interaction) = resolve_confirmation_result(
+        ctx,
+        confirmation,
+        "Timed out",
+        "Restore cancelled.",
+        "Restoring backup...",
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let report = restore_guild_backup(db, guild_id.get(), &backup).await?;
+
+    info!(
+        guild_id = guild_id.get(),
+        source_guild_id = backup.source_guild_id,
+        moderator_id = ctx.author().id.get(),
+        cases = report.cases,
+        "Restored guild backup."
+    );
+
+    interaction
+        .edit_response(
+            ctx.http(),
+            serenity::EditInteractionResponse::new()
+                .content(format!(
+                    "Backup restored.\n\
+                     **Cases :** {}\n\
+                     **Warnings :** {}\n\
+                     **Notes :** {}\n\
+                     **Filtered words :** {}\n\
+                     **Link domains :** {}\n\
+                     **Exemptions :** {}",
+                    report.cases,
+                    report.warnings,
+                    report.notes,
+                    report.words,
+                    report.link_domains,
+                    report.exemptions,
+                ))
+                .embeds(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,312 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{require_ban_members, require_manage_guild};
+use crate::moderation::ban_sync::{guild_name, undo_mirrored_bans};
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::ban_sync::{
+    BAN_SYNC_NAME_MAX_CHARS, BAN_SYNC_TAG_MAX_CHARS, MAX_BAN_SYNC_MEMBERS, create_ban_sync_group,
+    get_ban_sync_group_by_code, get_ban_sync_group_for_guild, join_ban_sync_group,
+    leave_ban_sync_group, list_ban_sync_members,
+};
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "bansync",
+    desc: "Mirror bans tagged with a shared tag between partnered servers.",
+    category: "moderation",
+    usage: "!bansync [create <tag> <name>|join <code>|leave|undo <user>]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!bansync create #shared Gaming Network",
+        "!bansync join 3f9a1c22be07",
+        "!bansync undo 123456789012345678",
+    ],
+    related: &["ban", "unban"],
+};
+
+/// Show this server's ban-sync group and its partners.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("bansync_create", "bansync_join", "bansync_leave", "bansync_undo"),
+    check = "require_manage_guild"
+)]
+pub async fn bansync(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let status = match get_ban_sync_group_for_guild(db, guild_id.get()).await? {
+        Some(group) => {
+            let mut partners = Vec::new();
+            for member in list_ban_sync_members(db, group.id).await? {
+                let member = serenity::GuildId::new(member);
+                if member != guild_id {
+                    partners.push(format!("- {}", guild_name(ctx.http(), member).await));
+                }
+            }
+            format!(
+                "**Group :** {}\n**Tag :** `{}`\n**Invite Code :** `{}`\n**Partners :**\n{}",
+                group.name,
+                group.tag,
+                group.invite_code,
+                if partners.is_empty() {
+                    "None yet. Share the invite code with a partner server; it works once."
+                        .to_owned()
+                } else {
+                    partners.join("\n")
+                }
+            )
+        }
+        None => "This server isn't in a ban-sync group.".to_owned(),
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Ban Sync")
+        .description(format!(
+            "{}\n\n\
+             Permanent bans whose reason contains the group's tag are mirrored \
+             to every partner, with a case logged there. Partners that already \
+             ban the user are skipped.",
+            status
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: create, join, leave, undo",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Start a ban-sync group and get an invite code for partners.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "create"
+)]
+pub async fn bansync_create(
+    ctx: Context<'_>,
+    #[description = "Tag that marks a ban for syncing, e.g. #shared"] tag: Option<String>,
+    #[description = "Name for the group"]
+    #[rest]
+    name: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let tag = tag.as_deref().map(str::trim).filter(|tag| !tag.is_empty());
+    let name = name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty());
+    let (Some(tag), Some(name)) = (tag, name) else {
+        ctx.say(usage_message("!bansync create <tag> <name>"))
+            .await?;
+        return Ok(());
+    };
+
+    if tag.chars().count() > BAN_SYNC_TAG_MAX_CHARS || tag.contains(char::is_whitespace) {
+        ctx.say(format!(
+            "The tag must be one word of at most {} characters.",
+            BAN_SYNC_TAG_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+    if name.chars().count() > BAN_SYNC_NAME_MAX_CHARS {
+        ctx.say(format!(
+            "Keep the name under {} characters.",
+            BAN_SYNC_NAME_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let invite_code = new_invite_code();
+    let Some(group) = create_ban_sync_group(
+        &ctx.data().db,
+        guild_id.get(),
+        name,
+        tag,
+        &invite_code,
+        ctx.author().id.get(),
+    )
+    .await?
+    else {
+        ctx.say(
+            "This server is already in a ban-sync group. Leave it first with `!bansync leave`.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    ctx.say(format!(
+        "Created **{}**. A partner server joins with `!bansync join {}`; bans whose reason \
+         contains `{}` will be mirrored to them. Each code admits one server, and `!bansync` \
+         shows the next one.",
+        group.name, group.invite_code, group.tag
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Join a partner's ban-sync group with its invite code.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "join"
+)]
+pub async fn bansync_join(
+    ctx: Context<'_>,
+    #[description = "Invite code from the partner server"] code: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(code) = code
+        .as_deref()
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+    else {
+        ctx.say(usage_message("!bansync join <code>")).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    if get_ban_sync_group_for_guild(db, guild_id.get())
+        .await?
+        .is_some()
+    {
+        ctx.say(
+            "This server is already in a ban-sync group. Leave it first with `!bansync leave`.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let code = code.to_ascii_lowercase();
+    let Some(group) = get_ban_sync_group_by_code(db, &code).await? else {
+        ctx.say("No ban-sync group has that invite code. Codes work once; ask the partner for a fresh one.")
+            .await?;
+        return Ok(());
+    };
+
+    let members = list_ban_sync_members(db, group.id).await?;
+    if members.len() as u64 >= MAX_BAN_SYNC_MEMBERS {
+        ctx.say(format!(
+            "That group is full ({} servers).",
+            MAX_BAN_SYNC_MEMBERS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let next_invite_code = new_invite_code();
+    if !join_ban_sync_group(db, group.id, &code, &next_invite_code, guild_id.get()).await? {
+        ctx.say("That invite code was just used. Ask the partner for a fresh one.")
+            .await?;
+        return Ok(());
+    }
+
+    ctx.say(format!(
+        "Joined **{}**. Permanent bans whose reason contains `{}` are now shared with {} partner server(s).",
+        group.name,
+        group.tag,
+        members.len()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Leave this server's ban-sync group. Mirrored bans stay until undone.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "leave"
+)]
+pub async fn bansync_leave(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    if leave_ban_sync_group(&ctx.data().db, guild_id.get()).await? {
+        ctx.say(
+            "Left the ban-sync group. Bans already mirrored stay in place; `!bansync undo <user>` still lifts them.",
+        )
+        .await?;
+    } else {
+        ctx.say("This server isn't in a ban-sync group.").await?;
+    }
+
+    Ok(())
+}
+
+/// Lift mirrored bans of a user: the ones this server sent to partners, or
+/// the one it received.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "undo",
+    check = "require_ban_members"
+)]
+pub async fn bansync_undo(
+    ctx: Context<'_>,
+    #[description = "The user whose synced bans to lift"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message("!bansync undo <user>")).await?;
+        return Ok(());
+    };
+
+    let (lifted, failed) = undo_mirrored_bans(
+        ctx.http(),
+        &ctx.data().db,
+        guild_id,
+        user.id,
+        ctx.author().id.get(),
+        ctx.framework().bot_id.get(),
+    )
+    .await?;
+
+    let reply = match (lifted, failed) {
+        (0, 0) => format!("**{}** has no synced bans to undo.", user.tag()),
+        (lifted, 0) => format!("Lifted {} synced ban(s) of **{}**.", lifted, user.tag()),
+        (lifted, failed) => format!(
+            "Lifted {} synced ban(s) of **{}**; {} couldn't be lifted. Check my permissions there.",
+            lifted,
+            user.tag(),
+            failed
+        ),
+    };
+    ctx.say(reply).await?;
+
+    Ok(())
+}
+
+/// Invite codes are single-use, so a fresh one replaces each code as it's used.
+fn new_invite_code() -> String {
+    hex::encode(rand::random::<[u8; 6]>())
+}
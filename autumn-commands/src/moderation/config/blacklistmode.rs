@@ -0,0 +1,79 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::global_blacklist::{
+    BLACKLIST_ACTION_OFF, BLACKLIST_ACTION_QUARANTINE, BLACKLIST_ACTIONS,
+};
+use autumn_database::impls::moderation_settings::{
+    get_global_blacklist_action, set_global_blacklist_action,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "blacklistmode",
+    desc: "Choose what happens when a user on the bot's global blacklist joins.",
+    category: "moderation",
+    usage: "!blacklistmode <ban|quarantine|off|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!blacklistmode quarantine", "!blacklistmode status"],
+    related: &["antiraid", "modlogchannel"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn blacklistmode(
+    ctx: Context<'_>,
+    #[description = "ban, quarantine, off, or status"] mode: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_mode) = mode.as_deref().map(str::trim) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if raw_mode.eq_ignore_ascii_case("status") {
+        let action = get_global_blacklist_action(&ctx.data().db, guild_id.get()).await?;
+        ctx.say(format!(
+            "Globally blacklisted users who join are currently {}.",
+            describe(&action)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let Some(action) = BLACKLIST_ACTIONS
+        .iter()
+        .find(|action| raw_mode.eq_ignore_ascii_case(action))
+    else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    set_global_blacklist_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Globally blacklisted users who join will now be {}.",
+        describe(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+fn describe(action: &str) -> &'static str {
+    match action {
+        BLACKLIST_ACTION_QUARANTINE => "**quarantined** (timed out for 28 days)",
+        BLACKLIST_ACTION_OFF => "**left alone**",
+        _ => "**banned**",
+    }
+}
@@ -0,0 +1,202 @@
+use poise::serenity_prelude as serenity;
+
+use super::automod::{FILTER_ACTION_HELP, filter_action_display, parse_filter_action};
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::caps_filter::{
+    get_caps_filter_config, set_caps_filter_action, set_caps_filter_enabled,
+    set_caps_filter_min_length, set_caps_filter_percent,
+};
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "capsfilter",
+    desc: "Manage the excessive caps filter.",
+    category: "moderation",
+    usage: "!capsfilter <enable|disable|action|percent|length>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!capsfilter enable",
+        "!capsfilter percent 80",
+        "!capsfilter length 20",
+    ],
+    related: &["antispam"],
+};
+
+/// Manage the excessive caps filter.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("enable", "disable", "action", "percent", "length"),
+    check = "require_manage_guild"
+)]
+pub async fn capsfilter(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_caps_filter_config(&ctx.data().db, guild_id.get()).await?;
+
+    let (enabled, percent, min_length, action) = match &config {
+        Some(cfg) => (
+            cfg.enabled,
+            cfg.max_caps_percent,
+            cfg.min_length,
+            cfg.action.as_str(),
+        ),
+        None => (false, 70, 10, "delete_and_log"),
+    };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Caps Filter Status")
+        .description(format!(
+            "**Status :** {}\n\
+             **Max Uppercase :** {}%\n\
+             **Min Length :** {} letter(s)\n\
+             **Action :** {}",
+            status,
+            percent,
+            min_length,
+            filter_action_display(action),
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, action, percent, length",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the caps filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_caps_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Caps filter has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the caps filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_caps_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Caps filter has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a message has too many capitals.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(action) = input.as_deref().and_then(parse_filter_action) else {
+        ctx.say(format!(
+            "Usage: `!capsfilter action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_caps_filter_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Caps filter action set to **{}**.",
+        filter_action_display(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the highest allowed percentage of uppercase letters.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn percent(
+    ctx: Context<'_>,
+    #[description = "Percentage between 50 and 100"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(percent) = input
+        .as_deref()
+        .map(|raw| raw.trim().trim_end_matches('%'))
+        .and_then(|raw| raw.parse::<i32>().ok())
+        .filter(|value| (50..=100).contains(value))
+    else {
+        ctx.say("Usage: `!capsfilter percent <50-100>`").await?;
+        return Ok(());
+    };
+
+    set_caps_filter_percent(&ctx.data().db, guild_id.get(), percent).await?;
+    ctx.say(format!(
+        "Messages over **{}%** uppercase will be flagged.",
+        percent
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the minimum number of letters before a message is checked.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn length(
+    ctx: Context<'_>,
+    #[description = "Minimum letters between 1 and 500"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(min_length) = input
+        .as_deref()
+        .and_then(|raw| raw.trim().parse::<i32>().ok())
+        .filter(|value| (1..=500).contains(value))
+    else {
+        ctx.say("Usage: `!capsfilter length <1-500>`").await?;
+        return Ok(());
+    };
+
+    set_caps_filter_min_length(&ctx.data().db, guild_id.get(), min_length).await?;
+    ctx.say(format!(
+        "Only messages with at least **{}** letter(s) will be checked.",
+        min_length
+    ))
+    .await?;
+
+    Ok(())
+}
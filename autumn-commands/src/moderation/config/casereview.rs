@@ -0,0 +1,94 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::modlog_config::{
+    get_case_review_sla_seconds, set_case_review_sla_seconds,
+};
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::parse_duration_seconds;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "casereview",
+    desc: "Remind the modlog about open cases nobody resolved in time.",
+    category: "moderation",
+    usage: "!casereview <duration|off|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!casereview 3d", "!casereview off"],
+    related: &["case"],
+};
+
+const MIN_CASE_REVIEW_SECS: u64 = 5 * 60;
+const MAX_CASE_REVIEW_SECS: u64 = 30 * 24 * 60 * 60;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn casereview(
+    ctx: Context<'_>,
+    #[description = "Review window (e.g. 12h, 2d), off, or status"] window: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_window) = window.as_deref().map(str::trim) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if raw_window.eq_ignore_ascii_case("status") {
+        let sla_seconds = get_case_review_sla_seconds(&ctx.data().db, guild_id.get()).await?;
+        let content = match sla_seconds {
+            Some(sla_seconds) => format!(
+                "Open cases get a review reminder after **{}**.",
+                format_compact_duration(sla_seconds)
+            ),
+            None => "Case review reminders are **disabled** for this server.".to_owned(),
+        };
+        ctx.say(content).await?;
+        return Ok(());
+    }
+
+    let sla_seconds = if raw_window.eq_ignore_ascii_case("off") {
+        None
+    } else {
+        match parse_duration_seconds(raw_window) {
+            Some(seconds) if (MIN_CASE_REVIEW_SECS..=MAX_CASE_REVIEW_SECS).contains(&seconds) => {
+                Some(seconds)
+            }
+            Some(_) => {
+                ctx.say("The review window must be between 5 minutes and 30 days.")
+                    .await?;
+                return Ok(());
+            }
+            None => {
+                ctx.say(usage_message(META.usage)).await?;
+                return Ok(());
+            }
+        }
+    };
+
+    if !set_case_review_sla_seconds(&ctx.data().db, guild_id.get(), sla_seconds).await? {
+        ctx.say("Set a modlog channel first with `!modlogchannel`; reminders are posted there.")
+            .await?;
+        return Ok(());
+    }
+
+    let content = match sla_seconds {
+        Some(sla_seconds) => format!(
+            "Open cases created from now on get a review reminder after **{}**.",
+            format_compact_duration(sla_seconds)
+        ),
+        None => "Case review reminders are now **disabled** for this server.".to_owned(),
+    };
+    ctx.say(content).await?;
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::disabled_commands::{
+    disable_command, enable_command, list_disabled_commands,
+};
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "command",
+    desc: "Disable or re-enable commands in this server or a single channel.",
+    category: "moderation",
+    usage: "!command [disable|enable] <name> [#channel]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!command",
+        "!command disable translate",
+        "!command enable ping #general",
+    ],
+    related: &["alias"],
+};
+
+/// List the commands disabled in this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("disable", "enable"),
+    check = "require_manage_guild"
+)]
+pub async fn command(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let disabled = list_disabled_commands(&ctx.data().db, guild_id.get()).await?;
+    let description = if disabled.is_empty() {
+        "No commands are disabled.".to_owned()
+    } else {
+        disabled
+            .iter()
+            .map(|entry| match entry.channel_id {
+                Some(channel_id) => format!("`!{}` — <#{}>", entry.command_name, channel_id),
+                None => format!("`!{}` — everywhere", entry.command_name),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Disabled Commands")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: disable <name> [#channel], enable <name> [#channel]",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Disable a command everywhere, or only in one channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(
+    ctx: Context<'_>,
+    #[description = "Command to disable"] name: Option<String>,
+    #[description = "Only disable it in this channel"] channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_name) = name else {
+        ctx.say("Usage: `!command disable <name> [#channel]`")
+            .await?;
+        return Ok(());
+    };
+    let Some(command_name) = resolve_command_name(ctx, &raw_name) else {
+        ctx.say(format!("Unknown command: `{}`", raw_name.trim()))
+            .await?;
+        return Ok(());
+    };
+
+    if command_name == META.name {
+        ctx.say("`!command` can't be disabled.").await?;
+        return Ok(());
+    }
+
+    if channel
+        .as_ref()
+        .is_some_and(|channel| channel.guild_id != guild_id)
+    {
+        ctx.say("That channel isn't in this server.").await?;
+        return Ok(());
+    }
+
+    let channel_id = channel.map(|channel| channel.id.get());
+    let disabled = disable_command(
+        &ctx.data().db,
+        guild_id.get(),
+        &command_name,
+        channel_id,
+        ctx.author().id.get(),
+    )
+    .await?;
+
+    let scope = scope_label(channel_id);
+    ctx.say(if disabled {
+        format!("`!{}` is now disabled {}.", command_name, scope)
+    } else {
+        format!("`!{}` is already disabled {}.", command_name, scope)
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Re-enable a command everywhere, or in the channel it was disabled in.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(
+    ctx: Context<'_>,
+    #[description = "Command to re-enable"] name: Option<String>,
+    #[description = "Channel it was disabled in"] channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_name) = name else {
+        ctx.say("Usage: `!command enable <name> [#channel]`")
+            .await?;
+        return Ok(());
+    };
+    let Some(command_name) = resolve_command_name(ctx, &raw_name) else {
+        ctx.say(format!("Unknown command: `{}`", raw_name.trim()))
+            .await?;
+        return Ok(());
+    };
+
+    let channel_id = channel.map(|channel| channel.id.get());
+    let enabled = enable_command(&ctx.data().db, guild_id.get(), &command_name, channel_id).await?;
+
+    let scope = scope_label(channel_id);
+    ctx.say(if enabled {
+        format!("`!{}` is enabled again {}.", command_name, scope)
+    } else {
+        format!("`!{}` wasn't disabled {}.", command_name, scope)
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// The registered top-level command `raw` names, by name or alias.
+pub(crate) fn resolve_command_name(ctx: Context<'_>, raw: &str) -> Option<String> {
+    let lookup = raw
+        .trim()
+        .trim_start_matches(['!', '/'])
+        .to_ascii_lowercase();
+
+    ctx.framework()
+        .options()
+        .commands
+        .iter()
+        .find(|command| command.name == lookup || command.aliases.contains(&lookup))
+        .map(|command| command.name.clone())
+}
+
+fn scope_label(channel_id: Option<u64>) -> String {
+    match channel_id {
+        Some(channel_id) => format!("in <#{}>", channel_id),
+        None => "in this server".to_owned(),
+    }
+}
@@ -0,0 +1,460 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+
+use super::automod::filter_action_display;
+use super::exemptions::exemption_lines;
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::command_aliases::list_command_aliases;
+use autumn_database::impls::disabled_commands::list_disabled_commands;
+use autumn_database::impls::exemptions::list_exemptions;
+use autumn_database::impls::guild_config::get_guild_config;
+use autumn_database::impls::modlog_config::get_case_review_sla_seconds;
+use autumn_database::impls::permission_alerts::get_permission_alert_channel_id;
+use autumn_database::impls::serverlog_config::get_serverlog_channel_id;
+use autumn_database::impls::warning_decay::get_warning_decay_config;
+use autumn_database::model::exemptions::ModerationExemption;
+use autumn_database::model::guild_config::GuildConfig;
+use autumn_utils::COMMAND_PREFIX;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::pagination::PAGINATION_TIMEOUT_SECS;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "config",
+    desc: "View every server setting in one place, with how to change each section.",
+    category: "moderation",
+    usage: "!config",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!config"],
+    related: &["setup", "permcheck", "backup"],
+};
+
+#[derive(Clone, Copy)]
+enum Section {
+    Logging,
+    Moderation,
+    Filters,
+    Ai,
+    Commands,
+    Exemptions,
+}
+
+impl Section {
+    const ALL: [Self; 6] = [
+        Self::Logging,
+        Self::Moderation,
+        Self::Filters,
+        Self::Ai,
+        Self::Commands,
+        Self::Exemptions,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Logging => "Logging",
+            Self::Moderation => "Moderation",
+            Self::Filters => "Filters",
+            Self::Ai => "AI",
+            Self::Commands => "Commands",
+            Self::Exemptions => "Exemptions",
+        }
+    }
+
+    /// The commands that change this section, shown by the Edit button.
+    fn edit_help(self) -> &'static str {
+        match self {
+            Self::Logging => {
+                "`!modlogchannel <#channel|clear>` — moderation case log\n\
+                 `!userlogchannel <#channel|clear>` — member and message log\n\
+                 `!serverlogchannel <#channel|clear>` — server change log\n\
+                 `!permalertchannel <#channel|clear>` — dangerous permission alerts\n\
+                 `!purgetranscripts <on|off>` — attach purged messages to the modlog\n\
+                 `!casereview <duration|off>` — remind about open cases\n\
+                 `!warndecay <age|off>` — expire old warnings"
+            }
+            Self::Moderation => {
+                "`!escalation` — automatic timeouts after repeated warnings\n\
+                 `!antispam` — message rate and duplicate limits\n\
+                 `!modhierarchy <on|off>` — stop moderators actioning higher roles\n\
+                 `!embedcolor <#rrggbb|default>` — accent color of the bot's embeds"
+            }
+            Self::Filters => {
+                "`!wordfilter` — blocked words and their action\n\
+                 `!invitefilter`, `!linkfilter`, `!capsfilter` — message filters\n\
+                 `!attachmentfilter`, `!imagefilter` — attachment filters"
+            }
+            Self::Ai => {
+                "`!aitoggle <on|off>` — answer mentions with the LLM\n\
+                 `!ai reasons <on|off>` — suggest warning reasons\n\
+                 `!ai threads <on|off>` — continue conversations in threads\n\
+                 `!ai language <name>` — `!translate` target\n\
+                 `!ai prompt set <text>` — custom persona"
+            }
+            Self::Commands => {
+                "`!command <disable|enable> <name> [#channel]` — switch commands off\n\
+                 `!alias <add|remove>` — extra names for commands\n\
+                 `!permlevels` — roles that can run moderation commands"
+            }
+            Self::Exemptions => {
+                "`!wordfilter exempt <add|remove> <@role|#channel|@user>`\n\
+                 `!escalation exempt <add|remove> <@role|#channel|@user>`"
+            }
+        }
+    }
+}
+
+/// Everything the pages show, read once when the command runs.
+struct Snapshot {
+    config: GuildConfig,
+    serverlog_channel_id: Option<u64>,
+    permission_alert_channel_id: Option<u64>,
+    case_review_sla_seconds: Option<u64>,
+    warning_decay_seconds: Option<u64>,
+    disabled_commands: usize,
+    command_aliases: usize,
+    word_filter_exemptions: Vec<ModerationExemption>,
+    escalation_exemptions: Vec<ModerationExemption>,
+    accent_color: Option<u32>,
+}
+
+/// Show all server settings, one section per page.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn config(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let guild = guild_id.get();
+    let snapshot = Snapshot {
+        config: get_guild_config(db, guild).await?,
+        serverlog_channel_id: get_serverlog_channel_id(db, guild).await?,
+        permission_alert_channel_id: get_permission_alert_channel_id(db, guild).await?,
+        case_review_sla_seconds: get_case_review_sla_seconds(db, guild).await?,
+        warning_decay_seconds: get_warning_decay_config(db, guild)
+            .await?
+            .map(|decay| decay.max_age_seconds),
+        disabled_commands: list_disabled_commands(db, guild).await?.len(),
+        command_aliases: list_command_aliases(db, guild).await?.len(),
+        word_filter_exemptions: list_exemptions(db, guild, "word_filter").await?,
+        escalation_exemptions: list_exemptions(db, guild, "escalation").await?,
+        accent_color: accent_color(ctx).await,
+    };
+
+    let ctx_id = ctx.id();
+    let prev_id = format!("{}_config_prev", ctx_id);
+    let next_id = format!("{}_config_next", ctx_id);
+    let edit_id = format!("{}_config_edit", ctx_id);
+    let mut page = 0;
+
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(section_embed(&snapshot, page))
+                .components(section_components(&prev_id, &next_id, &edit_id, page)),
+        )
+        .await?;
+    let message = reply.message().await?;
+    let message_id = message.id;
+
+    while let Some(press) = serenity::ComponentInteractionCollector::new(ctx)
+        .filter({
+            let prefix = format!("{}_config_", ctx_id);
+            let author_id = ctx.author().id;
+            move |interaction| {
+                interaction.data.custom_id.starts_with(&prefix)
+                    && interaction.user.id == author_id
+                    && interaction.message.id == message_id
+            }
+        })
+        .timeout(Duration::from_secs(PAGINATION_TIMEOUT_SECS))
+        .await
+    {
+        if press.data.custom_id == edit_id {
+            let section = Section::ALL[page];
+            let embed = guild_embed(snapshot.accent_color)
+                .title(format!("Edit {}", section.title()))
+                .description(section.edit_help());
+            press
+                .create_response(
+                    ctx.http(),
+                    serenity::CreateInteractionResponse::Message(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .embed(embed)
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        if press.data.custom_id == prev_id {
+            page = page.saturating_sub(1);
+        } else if press.data.custom_id == next_id {
+            page = (page + 1).min(Section::ALL.len() - 1);
+        }
+
+        press
+            .create_response(
+                ctx.http(),
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(section_embed(&snapshot, page))
+                        .components(section_components(&prev_id, &next_id, &edit_id, page)),
+                ),
+            )
+            .await?;
+    }
+
+    let _ = reply
+        .edit(
+            ctx,
+            poise::CreateReply::default()
+                .embed(section_embed(&snapshot, page))
+                .components(Vec::new()),
+        )
+        .await;
+
+    Ok(())
+}
+
+fn section_embed(snapshot: &Snapshot, page: usize) -> serenity::CreateEmbed {
+    let section = Section::ALL[page];
+    let body = match section {
+        Section::Logging => logging_lines(snapshot),
+        Section::Moderation => moderation_lines(&snapshot.config),
+        Section::Filters => filter_lines(&snapshot.config),
+        Section::Ai => ai_lines(&snapshot.config),
+        Section::Commands => command_lines(snapshot),
+        Section::Exemptions => exemption_section_lines(snapshot),
+    };
+
+    guild_embed(snapshot.accent_color)
+        .title(format!("Server Config — {}", section.title()))
+        .description(body.join("\n"))
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Page {}/{} · Edit shows the commands for this section",
+            page + 1,
+            Section::ALL.len()
+        )))
+}
+
+fn section_components(
+    prev_id: &str,
+    next_id: &str,
+    edit_id: &str,
+    page: usize,
+) -> Vec<serenity::CreateActionRow> {
+    vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(prev_id)
+            .label("Prev")
+            .disabled(page == 0)
+            .style(serenity::ButtonStyle::Secondary),
+        serenity::CreateButton::new(edit_id)
+            .label("Edit")
+            .style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new(next_id)
+            .label("Next")
+            .disabled(page + 1 >= Section::ALL.len())
+            .style(serenity::ButtonStyle::Secondary),
+    ])]
+}
+
+fn logging_lines(snapshot: &Snapshot) -> Vec<String> {
+    let config = &snapshot.config;
+    vec![
+        format!("**Modlog :** {}", channel_display(config.modlog_channel_id)),
+        format!(
+            "**User log :** {}",
+            channel_display(config.userlog_channel_id)
+        ),
+        format!(
+            "**Server log :** {}",
+            channel_display(snapshot.serverlog_channel_id)
+        ),
+        format!(
+            "**Permission alerts :** {}",
+            channel_display(snapshot.permission_alert_channel_id)
+        ),
+        format!(
+            "**Purge transcripts :** {}",
+            on_off(config.purge_transcripts)
+        ),
+        format!(
+            "**Case review :** {}",
+            duration_display(snapshot.case_review_sla_seconds)
+        ),
+        format!(
+            "**Warning decay :** {}",
+            duration_display(snapshot.warning_decay_seconds)
+        ),
+    ]
+}
+
+fn moderation_lines(config: &GuildConfig) -> Vec<String> {
+    let escalation = match config.escalation_if_enabled() {
+        Some(escalation) => format!(
+            "timeout after {} warnings in {}",
+            escalation.warn_threshold,
+            format_compact_duration(u64::try_from(escalation.warn_window_seconds).unwrap_or(0))
+        ),
+        None => "Off".to_owned(),
+    };
+    let antispam = match config.antispam_if_enabled() {
+        Some(antispam) => format!(
+            "{} messages in {}, {} duplicates in {}",
+            antispam.message_threshold,
+            format_compact_duration(u64::try_from(antispam.message_window_seconds).unwrap_or(0)),
+            antispam.duplicate_threshold,
+            format_compact_duration(u64::try_from(antispam.duplicate_window_seconds).unwrap_or(0)),
+        ),
+        None => "Off".to_owned(),
+    };
+
+    vec![
+        format!("**Escalation :** {}", escalation),
+        format!("**Antispam :** {}", antispam),
+        format!(
+            "**Moderator hierarchy :** {}",
+            on_off(config.enforce_mod_hierarchy)
+        ),
+        format!(
+            "**Embed color :** {}",
+            config
+                .embed_color
+                .map_or_else(|| "Default".to_owned(), |color| format!("`#{:06X}`", color))
+        ),
+    ]
+}
+
+fn filter_lines(config: &GuildConfig) -> Vec<String> {
+    let filter = |name: &str, enabled_action: Option<&str>| match enabled_action {
+        Some(action) => format!("**{} :** {}", name, filter_action_display(action)),
+        None => format!("**{} :** Off", name),
+    };
+
+    let mut lines = vec![
+        filter(
+            "Word filter",
+            config
+                .word_filter
+                .as_ref()
+                .filter(|word_filter| word_filter.enabled)
+                .map(|word_filter| word_filter.action.as_str()),
+        ),
+        filter(
+            "Invite filter",
+            config
+                .invite_filter_if_enabled()
+                .map(|filter| filter.action.as_str()),
+        ),
+        filter(
+            "Link filter",
+            config
+                .link_filter_if_enabled()
+                .map(|filter| filter.action.as_str()),
+        ),
+        filter(
+            "Caps filter",
+            config
+                .caps_filter_if_enabled()
+                .map(|filter| filter.action.as_str()),
+        ),
+        filter(
+            "Attachment filter",
+            config
+                .attachment_filter_if_enabled()
+                .map(|filter| filter.action.as_str()),
+        ),
+        filter(
+            "Image filter",
+            config
+                .image_filter_if_enabled()
+                .map(|filter| filter.action.as_str()),
+        ),
+    ];
+
+    if !config.word_filter_channels.is_empty() {
+        let overrides = config
+            .word_filter_channels
+            .iter()
+            .map(|entry| format!("<#{}> {}", entry.channel_id, on_off(entry.enabled)))
+            .collect::<Vec<_>>();
+        lines.push(format!(
+            "**Word filter channels :** {}",
+            overrides.join(", ")
+        ));
+    }
+
+    lines
+}
+
+fn ai_lines(config: &GuildConfig) -> Vec<String> {
+    vec![
+        format!("**Mention replies :** {}", on_off(config.llm_enabled)),
+        format!(
+            "**Reason suggestions :** {}",
+            on_off(config.llm_suggest_reasons)
+        ),
+        format!("**Threads :** {}", on_off(config.llm_threads)),
+        format!(
+            "**Translate language :** {}",
+            config.translate_language.as_deref().unwrap_or("Default")
+        ),
+        format!(
+            "**Persona :** {}",
+            if config.llm_system_prompt.is_some() {
+                "Custom"
+            } else {
+                "Default"
+            }
+        ),
+    ]
+}
+
+fn command_lines(snapshot: &Snapshot) -> Vec<String> {
+    vec![
+        format!("**Prefix :** `{}`", COMMAND_PREFIX),
+        format!("**Disabled commands :** {}", snapshot.disabled_commands),
+        format!("**Aliases :** {}", snapshot.command_aliases),
+    ]
+}
+
+fn exemption_section_lines(snapshot: &Snapshot) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (label, exemptions) in [
+        ("Word filter", &snapshot.word_filter_exemptions),
+        ("Escalation", &snapshot.escalation_exemptions),
+    ] {
+        lines.push(format!("__{}__", label));
+        if exemptions.is_empty() {
+            lines.push("None".to_owned());
+        } else {
+            lines.extend(exemption_lines(exemptions));
+        }
+    }
+    lines
+}
+
+fn channel_display(channel_id: Option<u64>) -> String {
+    channel_id.map_or_else(|| "Not set".to_owned(), |id| format!("<#{}>", id))
+}
+
+fn duration_display(seconds: Option<u64>) -> String {
+    seconds.map_or_else(|| "Off".to_owned(), format_compact_duration)
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "On" } else { "Off" }
+}
@@ -0,0 +1,68 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::moderation_settings::{get_dual_approval, set_dual_approval};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "dualapproval",
+    desc: "Make bans and terminations wait for a second moderator's approval.",
+    category: "moderation",
+    usage: "!dualapproval <on|off|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!dualapproval on", "!dualapproval status"],
+    related: &["ban", "terminate", "modhierarchy"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn dualapproval(
+    ctx: Context<'_>,
+    #[description = "Desired state: on, off, or status"] state: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_state) = state.as_deref().map(str::trim) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if raw_state.eq_ignore_ascii_case("status") {
+        let enabled = get_dual_approval(&ctx.data().db, guild_id.get()).await?;
+        ctx.say(format!(
+            "Dual approval for bans and terminations is currently **{}** for this server.",
+            if enabled { "enabled" } else { "disabled" }
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let new_state = if raw_state.eq_ignore_ascii_case("on") {
+        true
+    } else if raw_state.eq_ignore_ascii_case("off") {
+        false
+    } else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    set_dual_approval(&ctx.data().db, guild_id.get(), new_state).await?;
+
+    ctx.say(if new_state {
+        "Dual approval is now **enabled**. Bans and terminations wait for another moderator to press Approve."
+    } else {
+        "Dual approval is now **disabled**. Bans and terminations take effect right away."
+    })
+    .await?;
+
+    Ok(())
+}
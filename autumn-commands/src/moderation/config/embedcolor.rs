@@ -0,0 +1,78 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::moderation_settings::{get_embed_color, set_embed_color};
+use autumn_utils::embed::{DEFAULT_EMBED_COLOR, guild_embed};
+use autumn_utils::modlog_template::parse_hex_color;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "embedcolor",
+    desc: "Set the accent color of the bot's embeds in this server.",
+    category: "moderation",
+    usage: "!embedcolor <#rrggbb|default|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!embedcolor #ff8800",
+        "!embedcolor default",
+        "!embedcolor status",
+    ],
+    related: &["modlogtemplate", "config"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn embedcolor(
+    ctx: Context<'_>,
+    #[description = "Hex color like #ff8800, 'default', or 'status'"] input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(input) = input.as_deref().map(str::trim) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+
+    if input.eq_ignore_ascii_case("status") {
+        let color = get_embed_color(db, guild_id.get()).await?;
+        let label = match color {
+            Some(color) => format!("`#{:06X}`", color),
+            None => format!("the default (`#{:06X}`)", DEFAULT_EMBED_COLOR),
+        };
+        let embed = guild_embed(color)
+            .title("Embed Color")
+            .description(format!("Embeds in this server use {}.", label));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    if input.eq_ignore_ascii_case("default") {
+        set_embed_color(db, guild_id.get(), None).await?;
+        ctx.say("Embed color reset to the default.").await?;
+        return Ok(());
+    }
+
+    let Some(color) = parse_hex_color(input) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    set_embed_color(db, guild_id.get(), Some(color)).await?;
+    let embed = guild_embed(Some(color))
+        .title("Embed Color")
+        .description(format!("Embeds in this server now use `#{:06X}`.", color));
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,22 +1,31 @@
 use poise::serenity_prelude as serenity;
 
+use super::exemptions::{exempt_add_for_scope, exempt_list_for_scope, exempt_remove_for_scope};
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
 use crate::moderation::embeds::guild_only_message;
 use autumn_core::{Context, Error};
 use autumn_database::impls::escalation::{
     get_escalation_config, set_escalation_enabled, set_timeout_window, set_warn_threshold,
     set_warn_window,
 };
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::embed::guild_embed;
 use autumn_utils::formatting::format_compact_duration;
 use autumn_utils::parse::parse_duration_seconds;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "escalation",
     desc: "Configure automatic warn → timeout escalation.",
     category: "moderation",
-    usage: "!escalation <enable|disable|set>",
+    usage: "!escalation <enable|disable|set|exempt>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!escalation enable",
+        "!escalation set warns 3",
+        "!escalation set warnwindow 7d",
+    ],
+    related: &["warn", "warndecay"],
 };
 
 /// Configure automatic warn → timeout escalation.
@@ -24,7 +33,8 @@ pub const META: CommandMeta = CommandMeta {
     prefix_command,
     slash_command,
     category = "Moderation",
-    subcommands("enable", "disable", "set")
+    subcommands("enable", "disable", "set", "exempt"),
+    check = "require_manage_guild"
 )]
 pub async fn escalation(ctx: Context<'_>) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
@@ -32,17 +42,6 @@ pub async fn escalation(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let config = get_escalation_config(&ctx.data().db, guild_id.get()).await?;
 
     let (enabled, threshold, warn_window, timeout_window) = match &config {
@@ -57,7 +56,7 @@ pub async fn escalation(ctx: Context<'_>) -> Result<(), Error> {
 
     let status = if enabled { "Enabled" } else { "Disabled" };
 
-    let embed = serenity::CreateEmbed::new()
+    let embed = guild_embed(accent_color(ctx).await)
         .title("Escalation Config")
         .description(format!(
             "**Status :** {}\n\
@@ -75,9 +74,8 @@ pub async fn escalation(ctx: Context<'_>) -> Result<(), Error> {
             format_compact_duration(warn_window as u64),
             format_compact_duration(timeout_window as u64),
         ))
-        .color(DEFAULT_EMBED_COLOR)
         .footer(serenity::CreateEmbedFooter::new(
-            "Subcommands: enable, disable, set warns/warnwindow/timeoutwindow",
+            "Subcommands: enable, disable, set warns/warnwindow/timeoutwindow, exempt",
         ));
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
@@ -85,6 +83,63 @@ pub async fn escalation(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Manage escalation exemptions for roles, channels, and users.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("exempt_add", "exempt_remove", "exempt_list")
+)]
+pub async fn exempt(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.say(
+        "Usage:\n\
+         `!escalation exempt add <@role|#channel|@user>` — exempt a target\n\
+         `!escalation exempt remove <@role|#channel|@user>` — remove an exemption\n\
+         `!escalation exempt list` — list exemptions",
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Exempt a role, channel, or user.
+#[poise::command(prefix_command, slash_command, category = "Moderation", rename = "add")]
+pub async fn exempt_add(
+    ctx: Context<'_>,
+    #[description = "Role, channel, or user to exempt"]
+    #[rest]
+    target: Option<String>,
+) -> Result<(), Error> {
+    exempt_add_for_scope(ctx, "escalation", "escalation", target).await
+}
+
+/// Remove an exemption.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "remove"
+)]
+pub async fn exempt_remove(
+    ctx: Context<'_>,
+    #[description = "Role, channel, or user to un-exempt"]
+    #[rest]
+    target: Option<String>,
+) -> Result<(), Error> {
+    exempt_remove_for_scope(ctx, "escalation", "escalation", target).await
+}
+
+/// List exemptions.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "list"
+)]
+pub async fn exempt_list(ctx: Context<'_>) -> Result<(), Error> {
+    exempt_list_for_scope(ctx, "escalation", "Escalation Exemptions").await
+}
+
 /// Enable automatic escalation.
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
 pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
@@ -93,17 +148,6 @@ pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_escalation_enabled(&ctx.data().db, guild_id.get(), true).await?;
     ctx.say("Automatic escalation has been **enabled**.")
         .await?;
@@ -119,17 +163,6 @@ pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_escalation_enabled(&ctx.data().db, guild_id.get(), false).await?;
     ctx.say("Automatic escalation has been **disabled**.")
         .await?;
@@ -169,17 +202,6 @@ pub async fn warns(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
         ctx.say("Usage: `!escalation set warns <count>` (e.g. `3`)")
             .await?;
@@ -220,17 +242,6 @@ pub async fn warnwindow(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
         ctx.say("Usage: `!escalation set warnwindow <duration>` (e.g. `24h`, `7d`)")
             .await?;
@@ -267,17 +278,6 @@ pub async fn timeoutwindow(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
         ctx.say("Usage: `!escalation set timeoutwindow <duration>` (e.g. `7d`, `30d`)")
             .await?;
@@ -0,0 +1,177 @@
+//! Shared `exempt add/remove/list` handlers used by the word filter and
+//! escalation command groups.
+
+use crate::branding::accent_color;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::exemptions::{add_exemption, list_exemptions, remove_exemption};
+use autumn_database::model::exemptions::ModerationExemption;
+use autumn_utils::embed::guild_embed;
+
+pub(crate) async fn exempt_add_for_scope(
+    ctx: Context<'_>,
+    scope: &str,
+    command_name: &str,
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some((target_kind, target_id)) = input.as_deref().and_then(parse_exemption_target) else {
+        ctx.say(exempt_usage(command_name, "add")).await?;
+        return Ok(());
+    };
+
+    let inserted = add_exemption(
+        &ctx.data().db,
+        guild_id.get(),
+        scope,
+        target_kind,
+        target_id,
+        ctx.author().id.get(),
+    )
+    .await?;
+
+    let target = format_exemption_target(target_kind, target_id);
+    if inserted {
+        ctx.say(format!("{} is now exempt.", target)).await?;
+    } else {
+        ctx.say(format!("{} is already exempt.", target)).await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn exempt_remove_for_scope(
+    ctx: Context<'_>,
+    scope: &str,
+    command_name: &str,
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some((target_kind, target_id)) = input.as_deref().and_then(parse_exemption_target) else {
+        ctx.say(exempt_usage(command_name, "remove")).await?;
+        return Ok(());
+    };
+
+    let removed = remove_exemption(
+        &ctx.data().db,
+        guild_id.get(),
+        scope,
+        target_kind,
+        target_id,
+    )
+    .await?;
+
+    let target = format_exemption_target(target_kind, target_id);
+    if removed {
+        ctx.say(format!("{} is no longer exempt.", target)).await?;
+    } else {
+        ctx.say(format!("{} was not exempt.", target)).await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn exempt_list_for_scope(
+    ctx: Context<'_>,
+    scope: &str,
+    title: &str,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let exemptions = list_exemptions(&ctx.data().db, guild_id.get(), scope).await?;
+
+    if exemptions.is_empty() {
+        ctx.say("No exemptions configured.").await?;
+        return Ok(());
+    }
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title(title)
+        .description(exemption_lines(&exemptions).join("\n"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// One `**Roles :** ...` style line per kind of exemption present.
+pub(crate) fn exemption_lines(exemptions: &[ModerationExemption]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (kind, label) in [
+        ("role", "Roles"),
+        ("channel", "Channels"),
+        ("user", "Users"),
+    ] {
+        let entries = exemptions
+            .iter()
+            .filter(|exemption| exemption.target_kind == kind)
+            .map(|exemption| format_exemption_target(kind, exemption.target_id))
+            .collect::<Vec<_>>();
+
+        if !entries.is_empty() {
+            lines.push(format!("**{} :** {}", label, entries.join(", ")));
+        }
+    }
+    lines
+}
+
+/// Parse a role/channel/user mention, or an explicit `<role|channel|user> <id>` pair.
+fn parse_exemption_target(raw: &str) -> Option<(&'static str, u64)> {
+    let mut parts = raw.split_whitespace();
+    let first = parts.next()?;
+
+    if let Some(id) = parts.next() {
+        let kind = match first.to_ascii_lowercase().as_str() {
+            "role" => "role",
+            "channel" => "channel",
+            "user" => "user",
+            _ => return None,
+        };
+        let id = id.parse::<u64>().ok()?;
+        return Some((kind, id));
+    }
+
+    if let Some(inner) = first.strip_prefix("<@&").and_then(|v| v.strip_suffix('>')) {
+        return inner.parse::<u64>().ok().map(|id| ("role", id));
+    }
+
+    if let Some(inner) = first.strip_prefix("<#").and_then(|v| v.strip_suffix('>')) {
+        return inner.parse::<u64>().ok().map(|id| ("channel", id));
+    }
+
+    if let Some(inner) = first.strip_prefix("<@").and_then(|v| v.strip_suffix('>')) {
+        return inner
+            .trim_start_matches('!')
+            .parse::<u64>()
+            .ok()
+            .map(|id| ("user", id));
+    }
+
+    None
+}
+
+fn format_exemption_target(target_kind: &str, target_id: u64) -> String {
+    match target_kind {
+        "role" => format!("<@&{}>", target_id),
+        "channel" => format!("<#{}>", target_id),
+        _ => format!("<@{}>", target_id),
+    }
+}
+
+fn exempt_usage(command_name: &str, operation: &str) -> String {
+    format!(
+        "Usage: `!{command_name} exempt {operation} <@role|#channel|@user>`\n\
+         Or with a raw id: `!{command_name} exempt {operation} <role|channel|user> <id>`"
+    )
+}
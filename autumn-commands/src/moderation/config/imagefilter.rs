@@ -0,0 +1,170 @@
+use poise::serenity_prelude as serenity;
+
+use super::automod::{FILTER_ACTION_HELP, filter_action_display, parse_filter_action};
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::image_filter::{
+    get_image_filter_config, set_image_filter_action, set_image_filter_enabled,
+    set_image_filter_threshold,
+};
+use autumn_database::model::image_filter::DEFAULT_IMAGE_FILTER_THRESHOLD;
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "imagefilter",
+    desc: "Manage the LLM image filter.",
+    category: "moderation",
+    usage: "!imagefilter <enable|disable|action|threshold>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!imagefilter enable", "!imagefilter threshold 80"],
+    related: &["attachmentfilter"],
+};
+
+/// Manage the LLM image filter.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("enable", "disable", "action", "threshold"),
+    check = "require_manage_guild"
+)]
+pub async fn imagefilter(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_image_filter_config(&ctx.data().db, guild_id.get()).await?;
+
+    let (enabled, threshold, action) = match &config {
+        Some(cfg) => (cfg.enabled, cfg.threshold, cfg.action.as_str()),
+        None => (false, DEFAULT_IMAGE_FILTER_THRESHOLD, "delete_and_log"),
+    };
+
+    let mut description = format!(
+        "**Status :** {}\n\
+         **Threshold :** {}% confidence\n\
+         **Action :** {}",
+        if enabled { "Enabled" } else { "Disabled" },
+        threshold,
+        filter_action_display(action),
+    );
+    if ctx.data().llm.is_none() {
+        description.push_str("\n\nNo LLM backend is configured, so images will not be checked.");
+    }
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Image Filter Status")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, action, threshold",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the image filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_image_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
+
+    let mut reply =
+        "Image filter has been **enabled**. Image attachments are now classified by the LLM."
+            .to_owned();
+    if ctx.data().llm.is_none() {
+        reply.push_str(" No LLM backend is configured yet, so nothing will be checked.");
+    }
+    ctx.say(reply).await?;
+
+    Ok(())
+}
+
+/// Disable the image filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_image_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Image filter has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set the action taken when an image is flagged.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(action) = input.as_deref().and_then(parse_filter_action) else {
+        ctx.say(format!(
+            "Usage: `!imagefilter action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_image_filter_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Image filter action set to **{}**.",
+        filter_action_display(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the LLM confidence needed before an image is acted on.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn threshold(
+    ctx: Context<'_>,
+    #[description = "Confidence between 1 and 100"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(threshold) = input
+        .as_deref()
+        .map(|raw| raw.trim().trim_end_matches('%'))
+        .and_then(|raw| raw.parse::<u8>().ok())
+        .filter(|value| (1..=100).contains(value))
+    else {
+        ctx.say("Usage: `!imagefilter threshold <1-100>`").await?;
+        return Ok(());
+    };
+
+    set_image_filter_threshold(&ctx.data().db, guild_id.get(), threshold).await?;
+    ctx.say(format!(
+        "Images are now acted on at **{}%** confidence or higher.",
+        threshold
+    ))
+    .await?;
+
+    Ok(())
+}
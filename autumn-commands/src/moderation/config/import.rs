@@ -0,0 +1,216 @@
+use poise::serenity_prelude as serenity;
+use std::time::Duration;
+use tracing::info;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::escalation::{
+    set_escalation_enabled, set_warn_threshold, set_warn_window,
+};
+use autumn_database::impls::warnings::{import_warnings, now_unix_secs};
+use autumn_database::impls::word_filter::import_filter_words;
+use autumn_database::model::warnings::WarningEntry;
+use autumn_database::model::word_filter::WordFilterPattern;
+use autumn_utils::bot_import::{BotImport, SourceBot, parse_bot_export};
+use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
+use autumn_utils::formatting::format_compact_duration;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "import",
+    desc: "Import word lists, warnings and auto-mute settings from another bot.",
+    category: "moderation",
+    usage: "!import <dyno|carlbot|wick> (attach export file)",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!import dyno (attach the export file)"],
+    related: &["backup"],
+};
+
+/// Largest export file accepted, in bytes.
+const MAX_IMPORT_BYTES: u32 = 4 * 1024 * 1024;
+const IMPORT_CONFIRM_TIMEOUT_SECS: u64 = 60;
+
+/// Preview and apply an export from Dyno, Carl-bot or Wick.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn import(
+    ctx: Context<'_>,
+    #[description = "Bot the file was exported from: dyno, carlbot or wick"] bot: Option<String>,
+    #[description = "Export file from that bot"] file: Option<serenity::Attachment>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let (Some(source), Some(file)) = (bot.as_deref().and_then(SourceBot::parse), file) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if file.size > MAX_IMPORT_BYTES {
+        ctx.say("That file is too large to import (max 4 MB).")
+            .await?;
+        return Ok(());
+    }
+
+    let bytes = file.download().await?;
+    let Ok(content) = String::from_utf8(bytes) else {
+        ctx.say("That file is not valid UTF-8 text.").await?;
+        return Ok(());
+    };
+
+    let Ok(parsed) = parse_bot_export(source, &content) else {
+        ctx.say(format!(
+            "Could not read that file as a {} export.",
+            source.display_name()
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    if parsed.is_empty() {
+        ctx.say(format!(
+            "Found nothing to import in that {} export.",
+            source.display_name()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let preview = serenity::CreateEmbed::new()
+        .title(format!("{} import preview", source.display_name()))
+        .description(preview_lines(&parsed));
+
+    let confirmation = prompt_confirm_decline(
+        ctx,
+        "Dry run: nothing has been imported yet. Apply this import?",
+        preview,
+        Duration::from_secs(IMPORT_CONFIRM_TIMEOUT_SECS),
+    )
+    .await?;
+
+    let Some(interaction) = resolve_confirmation_result(
+        ctx,
+        confirmation,
+        "Timed out",
+        "Import cancelled. Nothing was changed.",
+        "Importing...",
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let guild_id = guild_id.get();
+
+    let patterns: Vec<WordFilterPattern> = parsed
+        .words
+        .iter()
+        .map(|word| WordFilterPattern {
+            word: word.clone(),
+            match_mode: "whole".to_owned(),
+        })
+        .collect();
+    let words_added = import_filter_words(db, guild_id, &patterns).await?;
+
+    let now = now_unix_secs();
+    let warnings: Vec<(u64, WarningEntry)> = parsed
+        .warnings
+        .iter()
+        .map(|warning| {
+            (
+                warning.user_id,
+                WarningEntry {
+                    warned_at: warning.warned_at.unwrap_or(now),
+                    moderator_id: warning.moderator_id.unwrap_or(ctx.author().id.get()),
+                    reason: warning.reason.clone(),
+                },
+            )
+        })
+        .collect();
+    let warnings_added = import_warnings(db, guild_id, &warnings).await?;
+
+    if let Some(mute) = parsed.mute {
+        if let Some(threshold) = mute.warn_threshold {
+            set_warn_threshold(db, guild_id, i32::try_from(threshold).unwrap_or(i32::MAX)).await?;
+        }
+        if let Some(window) = mute.warn_window_seconds {
+            set_warn_window(db, guild_id, i64::try_from(window).unwrap_or(i64::MAX)).await?;
+        }
+        set_escalation_enabled(db, guild_id, true).await?;
+    }
+
+    info!(
+        guild_id,
+        moderator_id = ctx.author().id.get(),
+        source = source.display_name(),
+        words_added,
+        warnings_added,
+        "Imported settings from another bot."
+    );
+
+    interaction
+        .edit_response(
+            ctx.http(),
+            serenity::EditInteractionResponse::new()
+                .content(format!(
+                    "Import from {} complete.\n\
+                     **Filtered words added :** {} ({} already present)\n\
+                     **Warnings added :** {}\n\
+                     **Escalation :** {}",
+                    source.display_name(),
+                    words_added,
+                    patterns.len() as u64 - words_added,
+                    warnings_added,
+                    if parsed.mute.is_some() {
+                        "enabled"
+                    } else {
+                        "unchanged"
+                    },
+                ))
+                .embeds(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn preview_lines(parsed: &BotImport) -> String {
+    let mut lines = vec![
+        format!("**Filtered words :** {}", parsed.words.len()),
+        format!("**Warnings :** {}", parsed.warnings.len()),
+    ];
+
+    if parsed.skipped_warnings > 0 {
+        lines.push(format!(
+            "**Skipped warnings :** {} (no valid user id)",
+            parsed.skipped_warnings
+        ));
+    }
+
+    if let Some(mute) = parsed.mute {
+        let threshold = mute
+            .warn_threshold
+            .map_or_else(|| "unchanged".to_owned(), |n| n.to_string());
+        let window = mute
+            .warn_window_seconds
+            .map_or_else(|| "unchanged".to_owned(), format_compact_duration);
+        lines.push(format!(
+            "**Auto-mute :** enables escalation with warn threshold {threshold}, window {window}"
+        ));
+    }
+
+    if !parsed.words.is_empty() {
+        let sample: Vec<&str> = parsed.words.iter().take(10).map(String::as_str).collect();
+        lines.push(format!("**Sample words :** `{}`", sample.join("`, `")));
+    }
+
+    lines.join("\n")
+}
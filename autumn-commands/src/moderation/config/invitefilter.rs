@@ -0,0 +1,237 @@
+use poise::serenity_prelude as serenity;
+
+use super::automod::{FILTER_ACTION_HELP, filter_action_display, parse_filter_action};
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::invite_filter::{
+    add_invite_allowed_guild, get_invite_filter_config, remove_invite_allowed_guild,
+    set_invite_filter_action, set_invite_filter_enabled,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::links::extract_invite_codes;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "invitefilter",
+    desc: "Manage the Discord invite filter for this server.",
+    category: "moderation",
+    usage: "!invitefilter <enable|disable|action|allow|remove>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!invitefilter enable",
+        "!invitefilter allow discord.gg/rust-lang",
+    ],
+    related: &["linkfilter"],
+};
+
+/// Manage the Discord invite filter for this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("enable", "disable", "action", "allow", "remove"),
+    check = "require_manage_guild"
+)]
+pub async fn invitefilter(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_invite_filter_config(&ctx.data().db, guild_id.get()).await?;
+
+    let (enabled, action, allowed) = match &config {
+        Some(cfg) => (
+            cfg.enabled,
+            cfg.action.as_str(),
+            cfg.allowed_guild_ids.as_slice(),
+        ),
+        None => (false, "delete_and_log", [].as_slice()),
+    };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+    let allowed_label = if allowed.is_empty() {
+        "None".to_owned()
+    } else {
+        allowed
+            .iter()
+            .map(|id| format!("`{}`", id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Invite Filter Status")
+        .description(format!(
+            "**Status :** {}\n**Action :** {}\n**Whitelisted Servers :** {}",
+            status,
+            filter_action_display(action),
+            allowed_label
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Invites to this server are always allowed. \
+             Subcommands: enable, disable, action, allow, remove",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the invite filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_invite_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Invite filter has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the invite filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_invite_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Invite filter has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a non-whitelisted invite is posted.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(action) = input.as_deref().and_then(parse_filter_action) else {
+        ctx.say(format!(
+            "Usage: `!invitefilter action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_invite_filter_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Invite filter action set to **{}**.",
+        filter_action_display(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Whitelist a server by id or invite link.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn allow(
+    ctx: Context<'_>,
+    #[description = "Server id or invite link"]
+    #[rest]
+    target: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(allowed_guild_id) = resolve_target_guild(ctx, target.as_deref()).await else {
+        ctx.say("Usage: `!invitefilter allow <server id|invite link>`")
+            .await?;
+        return Ok(());
+    };
+
+    let inserted =
+        add_invite_allowed_guild(&ctx.data().db, guild_id.get(), allowed_guild_id).await?;
+
+    if inserted {
+        ctx.say(format!(
+            "Invites to server `{}` are now allowed.",
+            allowed_guild_id
+        ))
+        .await?;
+    } else {
+        ctx.say(format!(
+            "Server `{}` is already whitelisted.",
+            allowed_guild_id
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Remove a server from the invite whitelist.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Server id or invite link"]
+    #[rest]
+    target: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(allowed_guild_id) = resolve_target_guild(ctx, target.as_deref()).await else {
+        ctx.say("Usage: `!invitefilter remove <server id|invite link>`")
+            .await?;
+        return Ok(());
+    };
+
+    let removed =
+        remove_invite_allowed_guild(&ctx.data().db, guild_id.get(), allowed_guild_id).await?;
+
+    if removed {
+        ctx.say(format!(
+            "Server `{}` removed from the invite whitelist.",
+            allowed_guild_id
+        ))
+        .await?;
+    } else {
+        ctx.say(format!(
+            "Server `{}` was not whitelisted.",
+            allowed_guild_id
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Parse a raw server id, or resolve an invite link to its server id.
+async fn resolve_target_guild(ctx: Context<'_>, raw: Option<&str>) -> Option<u64> {
+    let raw = raw.map(str::trim).filter(|s| !s.is_empty())?;
+
+    if let Ok(id) = raw.parse::<u64>() {
+        return Some(id);
+    }
+
+    let code = extract_invite_codes(raw).into_iter().next()?;
+    let invite = ctx
+        .http()
+        .get_invite(&code, false, false, None)
+        .await
+        .ok()?;
+    invite.guild.map(|guild| guild.id.get())
+}
@@ -0,0 +1,311 @@
+use poise::serenity_prelude as serenity;
+
+use super::automod::{FILTER_ACTION_HELP, filter_action_display, parse_filter_action};
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::branding::pagination_options;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::link_filter::{
+    LINK_FILTER_MODES, get_link_filter_config, list_link_filter_domains, remove_link_filter_domain,
+    set_link_filter_action, set_link_filter_domain, set_link_filter_enabled, set_link_filter_mode,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::links::normalize_domain_input;
+use autumn_utils::pagination::paginate_embed_pages_with_options;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "linkfilter",
+    desc: "Manage the link filter for this server.",
+    category: "moderation",
+    usage: "!linkfilter <enable|disable|mode|action|allow|deny|remove|list>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!linkfilter enable",
+        "!linkfilter mode allowlist",
+        "!linkfilter allow youtube.com",
+    ],
+    related: &["invitefilter", "wordfilter"],
+};
+
+/// Manage the link filter for this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands(
+        "enable", "disable", "mode", "action", "allow", "deny", "remove", "list"
+    ),
+    check = "require_manage_guild"
+)]
+pub async fn linkfilter(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_link_filter_config(&ctx.data().db, guild_id.get()).await?;
+
+    let (enabled, mode, action) = match &config {
+        Some(cfg) => (cfg.enabled, cfg.mode.as_str(), cfg.action.as_str()),
+        None => (false, "denylist", "delete_and_log"),
+    };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Link Filter Status")
+        .description(format!(
+            "**Status :** {}\n**Mode :** {}\n**Action :** {}",
+            status,
+            mode_display(mode),
+            filter_action_display(action)
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, mode, action, allow, deny, remove, list",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the link filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_link_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Link filter has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the link filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_link_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Link filter has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set which links are blocked.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn mode(
+    ctx: Context<'_>,
+    #[description = "Mode: denylist, allowlist, or block_all"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!linkfilter mode <denylist|allowlist|block_all>`\n\
+                 • `denylist` — Block only denied domains\n\
+                 • `allowlist` — Block every domain that is not allowed\n\
+                 • `block_all` — Block all links";
+
+    let Some(mode) = input
+        .as_deref()
+        .map(|raw| raw.trim().to_lowercase())
+        .filter(|raw| LINK_FILTER_MODES.contains(&raw.as_str()))
+    else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    set_link_filter_mode(&ctx.data().db, guild_id.get(), &mode).await?;
+
+    ctx.say(format!(
+        "Link filter mode set to **{}**.",
+        mode_display(&mode)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a blocked link is posted.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(action) = input.as_deref().and_then(parse_filter_action) else {
+        ctx.say(format!(
+            "Usage: `!linkfilter action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_link_filter_action(&ctx.data().db, guild_id.get(), action).await?;
+
+    ctx.say(format!(
+        "Link filter action set to **{}**.",
+        filter_action_display(action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Add a domain to the allow list.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn allow(
+    ctx: Context<'_>,
+    #[description = "Domain to allow (subdomains included)"]
+    #[rest]
+    domain: Option<String>,
+) -> Result<(), Error> {
+    set_domain_list(ctx, domain, "allow").await
+}
+
+/// Add a domain to the deny list.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn deny(
+    ctx: Context<'_>,
+    #[description = "Domain to deny (subdomains included)"]
+    #[rest]
+    domain: Option<String>,
+) -> Result<(), Error> {
+    set_domain_list(ctx, domain, "deny").await
+}
+
+/// Remove a domain from the allow or deny list.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Domain to remove"]
+    #[rest]
+    domain: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(domain) = domain.as_deref().and_then(normalize_domain_input) else {
+        ctx.say("Usage: `!linkfilter remove <domain>`").await?;
+        return Ok(());
+    };
+
+    let removed = remove_link_filter_domain(&ctx.data().db, guild_id.get(), &domain).await?;
+
+    if removed {
+        ctx.say(format!("Removed `{}` from the link filter lists.", domain))
+            .await?;
+    } else {
+        ctx.say(format!("`{}` is not on either link filter list.", domain))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// List allowed and denied domains.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let domains = list_link_filter_domains(&ctx.data().db, guild_id.get()).await?;
+
+    if domains.is_empty() {
+        ctx.say("The link filter lists are empty.").await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = domains
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let tag = if entry.list_kind == "allow" {
+                "allowed"
+            } else {
+                "denied"
+            };
+            format!("{}. `{}` ({})", i + 1, entry.domain, tag)
+        })
+        .collect();
+
+    let items_per_page = 20;
+    let pages: Vec<String> = lines
+        .chunks(items_per_page)
+        .map(|chunk| chunk.join("\n"))
+        .collect();
+
+    paginate_embed_pages_with_options(
+        ctx,
+        "Link Filter Domains",
+        &pages,
+        1,
+        pagination_options(ctx).await,
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn set_domain_list(
+    ctx: Context<'_>,
+    domain: Option<String>,
+    list_kind: &str,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(domain) = domain.as_deref().and_then(normalize_domain_input) else {
+        ctx.say(format!("Usage: `!linkfilter {} <domain>`", list_kind))
+            .await?;
+        return Ok(());
+    };
+
+    let changed =
+        set_link_filter_domain(&ctx.data().db, guild_id.get(), &domain, list_kind).await?;
+
+    if changed {
+        ctx.say(format!("Added `{}` to the {} list.", domain, list_kind))
+            .await?;
+    } else {
+        ctx.say(format!(
+            "`{}` is already on the {} list.",
+            domain, list_kind
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn mode_display(mode: &str) -> &str {
+    match mode {
+        "allowlist" => "Allowlist (block unlisted domains)",
+        "block_all" => "Block All Links",
+        _ => "Denylist (block denied domains)",
+    }
+}
@@ -1,7 +1,41 @@
+pub mod ai;
 pub mod aitoggle;
+pub mod alias;
+pub mod antiraid;
+pub mod antispam;
+pub mod attachmentfilter;
+pub(crate) mod automod;
+pub mod backup;
+pub mod bansync;
+pub mod blacklistmode;
+pub mod capsfilter;
+pub mod casereview;
+pub mod command;
+pub mod config;
+pub mod dualapproval;
+pub mod embedcolor;
 pub mod escalation;
+pub(crate) mod exemptions;
+pub mod imagefilter;
+pub mod import;
+pub mod invitefilter;
+pub mod linkfilter;
+pub mod modhierarchy;
 pub mod modlogchannel;
+pub mod modlogtemplate;
+pub mod modmail;
+pub mod namefilter;
+pub mod permalertchannel;
+pub mod permcheck;
 pub mod permissions;
+pub mod permlevels;
+pub mod privacy;
+pub mod purgetranscripts;
+pub mod raidmode;
+pub mod serverlogchannel;
 pub mod setup;
+pub mod tasks;
 pub mod userlogchannel;
+pub mod warndecay;
+pub mod webhooks;
 pub mod wordfilter;
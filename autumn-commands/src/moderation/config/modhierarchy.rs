@@ -0,0 +1,69 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::moderation_settings::{
+    get_enforce_mod_hierarchy, set_enforce_mod_hierarchy,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "modhierarchy",
+    desc: "Stop moderators from actioning members whose top role is equal to or above theirs.",
+    category: "moderation",
+    usage: "!modhierarchy <on|off|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!modhierarchy on", "!modhierarchy status"],
+    related: &["permlevels"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn modhierarchy(
+    ctx: Context<'_>,
+    #[description = "Desired state: on, off, or status"] state: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_state) = state.as_deref().map(str::trim) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if raw_state.eq_ignore_ascii_case("status") {
+        let enabled = get_enforce_mod_hierarchy(&ctx.data().db, guild_id.get()).await?;
+        ctx.say(format!(
+            "Moderator hierarchy checks are currently **{}** for this server.",
+            if enabled { "enabled" } else { "disabled" }
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let new_state = if raw_state.eq_ignore_ascii_case("on") {
+        true
+    } else if raw_state.eq_ignore_ascii_case("off") {
+        false
+    } else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    set_enforce_mod_hierarchy(&ctx.data().db, guild_id.get(), new_state).await?;
+
+    ctx.say(format!(
+        "Moderator hierarchy checks are now **{}** for this server.",
+        if new_state { "enabled" } else { "disabled" }
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -1,21 +1,29 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::checks::require_manage_guild;
 use crate::moderation::embeds::guild_only_message;
 use autumn_core::{Context, Error};
 use autumn_database::impls::modlog_config::{
     clear_modlog_channel_id, get_modlog_channel_id, set_modlog_channel_id,
 };
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "modlogchannel",
     desc: "Set or view the moderation log channel.",
     category: "moderation",
     usage: "!modlogchannel [#channel|channel_id|clear]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!modlogchannel #mod-log", "!modlogchannel clear"],
+    related: &["modlogtemplate", "userlogchannel", "serverlogchannel"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
 pub async fn modlogchannel(
     ctx: Context<'_>,
     #[description = "Channel mention/id, or 'clear'"]
@@ -27,17 +35,6 @@ pub async fn modlogchannel(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     if let Some(input) = input
         .as_deref()
         .map(str::trim)
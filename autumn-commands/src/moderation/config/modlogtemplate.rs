@@ -0,0 +1,245 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::modlog_template::{
+    get_modlog_template, reset_modlog_template, set_modlog_template_color,
+    set_modlog_template_fields, set_modlog_template_footer,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::modlog_template::{
+    MODLOG_FOOTER_PLACEHOLDERS, MODLOG_TEMPLATE_FIELDS, MODLOG_TEMPLATE_SEPARATOR, parse_hex_color,
+    parse_template_fields,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "modlogtemplate",
+    desc: "Customize the layout of modlog embeds.",
+    category: "moderation",
+    usage: "!modlogtemplate <fields|color|footer|reset>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!modlogtemplate color #ff8800",
+        "!modlogtemplate footer Appeals: example.com",
+        "!modlogtemplate reset",
+    ],
+    related: &["modlogchannel"],
+};
+
+/// Customize the layout of modlog embeds.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("fields", "color", "footer", "reset"),
+    check = "require_manage_guild"
+)]
+pub async fn modlogtemplate(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let template = get_modlog_template(&ctx.data().db, guild_id.get()).await?;
+
+    let field_order = template
+        .as_ref()
+        .and_then(|template| template.field_order.as_deref())
+        .map(|order| format!("`{}`", order.replace(',', ", ")))
+        .unwrap_or_else(|| "Default".to_owned());
+    let color = template
+        .as_ref()
+        .and_then(|template| template.layout().color)
+        .map(|color| format!("`#{:06X}`", color))
+        .unwrap_or_else(|| "Default".to_owned());
+    let footer = template
+        .as_ref()
+        .and_then(|template| template.footer.as_deref())
+        .map(|footer| format!("`{}`", footer))
+        .unwrap_or_else(|| "None".to_owned());
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Modlog Template")
+        .description(format!(
+            "**Fields :** {}\n**Color :** {}\n**Footer :** {}\n\n\
+             **Available fields :** {} (`{}` adds a blank line)\n\
+             **Footer placeholders :** {}",
+            field_order,
+            color,
+            footer,
+            available_fields(),
+            MODLOG_TEMPLATE_SEPARATOR,
+            MODLOG_FOOTER_PLACEHOLDERS
+                .iter()
+                .map(|placeholder| format!("`{}`", placeholder))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: fields, color, footer, reset",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Choose which fields modlog embeds show, in order.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn fields(
+    ctx: Context<'_>,
+    #[description = "Fields in order, e.g. target, reason, -, moderator, when (or 'default')"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = format!(
+        "Usage: `!modlogtemplate fields <field, field, ...|default>`\nAvailable fields: {} (`{}` adds a blank line)",
+        available_fields(),
+        MODLOG_TEMPLATE_SEPARATOR
+    );
+
+    let Some(input) = input
+        .as_deref()
+        .map(str::trim)
+        .filter(|input| !input.is_empty())
+    else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    if input.eq_ignore_ascii_case("default") {
+        set_modlog_template_fields(&ctx.data().db, guild_id.get(), None).await?;
+        ctx.say("Modlog fields reset to the default layout.")
+            .await?;
+        return Ok(());
+    }
+
+    let fields = match parse_template_fields(input) {
+        Ok(fields) => fields,
+        Err(unknown) if unknown.is_empty() => {
+            ctx.say(usage).await?;
+            return Ok(());
+        }
+        Err(unknown) => {
+            ctx.say(format!("Unknown field `{}`.\n{}", unknown, usage))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    set_modlog_template_fields(&ctx.data().db, guild_id.get(), Some(&fields)).await?;
+
+    ctx.say(format!("Modlog fields set to `{}`.", fields.join(", ")))
+        .await?;
+
+    Ok(())
+}
+
+/// Set the modlog embed color.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn color(
+    ctx: Context<'_>,
+    #[description = "Hex color like #ff8800, or 'default'"] input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(input) = input.as_deref().map(str::trim) else {
+        ctx.say("Usage: `!modlogtemplate color <#rrggbb|default>`")
+            .await?;
+        return Ok(());
+    };
+
+    if input.eq_ignore_ascii_case("default") {
+        set_modlog_template_color(&ctx.data().db, guild_id.get(), None).await?;
+        ctx.say("Modlog color reset to the default.").await?;
+        return Ok(());
+    }
+
+    let Some(color) = parse_hex_color(input) else {
+        ctx.say("Usage: `!modlogtemplate color <#rrggbb|default>`")
+            .await?;
+        return Ok(());
+    };
+
+    set_modlog_template_color(&ctx.data().db, guild_id.get(), Some(color)).await?;
+    ctx.say(format!("Modlog color set to `#{:06X}`.", color))
+        .await?;
+
+    Ok(())
+}
+
+/// Set the modlog embed footer text.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn footer(
+    ctx: Context<'_>,
+    #[description = "Footer text ({case} and {action} are replaced), or 'clear'"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(input) = input
+        .as_deref()
+        .map(str::trim)
+        .filter(|input| !input.is_empty())
+    else {
+        ctx.say("Usage: `!modlogtemplate footer <text|clear>`")
+            .await?;
+        return Ok(());
+    };
+
+    if input.eq_ignore_ascii_case("clear") {
+        set_modlog_template_footer(&ctx.data().db, guild_id.get(), None).await?;
+        ctx.say("Modlog footer cleared.").await?;
+        return Ok(());
+    }
+
+    // Discord caps embed footers at 2048 characters.
+    if input.chars().count() > 2048 {
+        ctx.say("Footer text must be 2048 characters or fewer.")
+            .await?;
+        return Ok(());
+    }
+
+    set_modlog_template_footer(&ctx.data().db, guild_id.get(), Some(input)).await?;
+    ctx.say("Modlog footer updated.").await?;
+
+    Ok(())
+}
+
+/// Restore the default modlog layout.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn reset(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    reset_modlog_template(&ctx.data().db, guild_id.get()).await?;
+    ctx.say("Modlog template reset to the default layout.")
+        .await?;
+
+    Ok(())
+}
+
+fn available_fields() -> String {
+    MODLOG_TEMPLATE_FIELDS
+        .iter()
+        .map(|field| format!("`{}`", field))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,291 @@
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{require_manage_guild, require_manage_messages};
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::modmail::{
+    clear_modmail_channel_id, close_modmail_conversation, count_open_conversations,
+    get_modmail_channel_id, get_open_conversation_for_thread, list_modmail_messages,
+    set_modmail_channel_id,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::transcript::{TranscriptMessage, render_transcript};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "modmail",
+    desc: "Relay members' DMs to staff threads; close a conversation for its transcript.",
+    category: "moderation",
+    usage: "!modmail [channel <#channel>|off|close [note]]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &[
+        "!modmail channel #modmail",
+        "!modmail close Sorted, thanks for reaching out",
+        "!modmail off",
+    ],
+    related: &["report", "config"],
+};
+
+/// Show whether modmail is on and how many conversations are open.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("modmail_channel", "modmail_off", "modmail_close"),
+    check = "require_manage_messages"
+)]
+pub async fn modmail(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let status = match get_modmail_channel_id(db, guild_id.get()).await? {
+        Some(channel_id) => format!(
+            "**Status :** On\n**Channel :** <#{}>\n**Open conversations :** {}",
+            channel_id,
+            count_open_conversations(db, guild_id.get()).await?
+        ),
+        None => "**Status :** Off".to_owned(),
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Modmail")
+        .description(format!(
+            "{}\n\n\
+             Members DM the bot to reach staff. Each conversation gets a thread \
+             in the modmail channel, and anything staff write there is sent back \
+             to the member, except commands. Close it with `!modmail close` to \
+             post a transcript.",
+            status
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: channel, off, close",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Take modmail in a staff channel.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "channel",
+    check = "require_manage_guild"
+)]
+pub async fn modmail_channel(
+    ctx: Context<'_>,
+    #[description = "Staff channel that conversations open threads in"] channel: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(channel_id) = channel.as_deref().map(str::trim).and_then(parse_channel_id) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let channels = guild_id.channels(ctx.http()).await?;
+    let Some(channel) = channels.get(&serenity::ChannelId::new(channel_id)) else {
+        ctx.say("That channel isn't in this server.").await?;
+        return Ok(());
+    };
+    if channel.kind != serenity::ChannelType::Text {
+        ctx.say("Modmail needs a text channel to open threads in.")
+            .await?;
+        return Ok(());
+    }
+
+    set_modmail_channel_id(&ctx.data().db, guild_id.get(), channel_id).await?;
+    ctx.say(format!(
+        "Modmail is on. Conversations open threads in <#{}>.",
+        channel_id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop taking modmail. Open conversations stay open until closed.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "off",
+    check = "require_manage_guild"
+)]
+pub async fn modmail_off(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    clear_modmail_channel_id(&ctx.data().db, guild_id.get()).await?;
+    ctx.say("Modmail is off. Members' DMs no longer open conversations.")
+        .await?;
+
+    Ok(())
+}
+
+/// Close the modmail conversation in this thread and post its transcript.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "close"
+)]
+pub async fn modmail_close(
+    ctx: Context<'_>,
+    #[description = "Note sent to the member"]
+    #[rest]
+    note: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let Some(conversation) =
+        get_open_conversation_for_thread(db, guild_id.get(), ctx.channel_id().get()).await?
+    else {
+        ctx.say("Run this in the thread of an open modmail conversation.")
+            .await?;
+        return Ok(());
+    };
+
+    if !close_modmail_conversation(db, conversation.id, ctx.author().id.get()).await? {
+        ctx.say("This conversation is already closed.").await?;
+        return Ok(());
+    }
+
+    let accent = accent_color(ctx).await;
+    let note = note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+    let guild_name = guild_id
+        .name(ctx)
+        .unwrap_or_else(|| "the server".to_owned());
+
+    let mut farewell = guild_embed(accent)
+        .title("Conversation Closed")
+        .description(format!(
+            "Staff of **{}** closed this conversation. Message again any time to start a new one.",
+            guild_name
+        ));
+    if let Some(note) = note {
+        farewell = farewell.field("Note", note, false);
+    }
+    let delivered = serenity::UserId::new(conversation.user_id)
+        .direct_message(ctx.http(), serenity::CreateMessage::new().embed(farewell))
+        .await
+        .is_ok();
+
+    let messages = list_modmail_messages(db, conversation.id).await?;
+    let transcript = render_transcript(
+        &format!(
+            "Modmail with user {} in guild {}, opened {}, closed by {} ({})",
+            conversation.user_id,
+            guild_id.get(),
+            format_timestamp(conversation.opened_at),
+            ctx.author().tag(),
+            ctx.author().id.get()
+        ),
+        &messages
+            .iter()
+            .map(|message| TranscriptMessage {
+                author_id: message.author_user_id,
+                author_name: if message.from_staff {
+                    format!("{} [staff]", message.author_name)
+                } else {
+                    message.author_name.clone()
+                },
+                channel_id: None,
+                timestamp: format_timestamp(message.created_at),
+                content: message.content.clone(),
+                attachments: message.attachments.clone(),
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let mut summary = format!(
+        "**Member :** <@{}>\n**Thread :** <#{}>\n**Opened :** <t:{}:R>\n**Closed By :** <@{}>\n**Messages :** {}",
+        conversation.user_id,
+        ctx.channel_id(),
+        conversation.opened_at,
+        ctx.author().id,
+        messages.len()
+    );
+    if let Some(note) = note {
+        summary.push_str(&format!("\n**Note :** {}", note));
+    }
+    if !delivered {
+        summary.push_str("\n\nThe member couldn't be told: their DMs are closed.");
+    }
+
+    let log = serenity::CreateMessage::new()
+        .embed(
+            guild_embed(accent)
+                .title("Modmail Closed")
+                .description(summary),
+        )
+        .add_file(serenity::CreateAttachment::bytes(
+            transcript.into_bytes(),
+            format!("modmail-{}.txt", conversation.id),
+        ));
+    let log_channel = get_modmail_channel_id(db, guild_id.get())
+        .await?
+        .map_or(ctx.channel_id(), serenity::ChannelId::new);
+    if let Err(source) = log_channel.send_message(ctx.http(), log).await {
+        error!(?source, "failed to post modmail transcript");
+        ctx.say("Closed, but I couldn't post the transcript.")
+            .await?;
+    } else {
+        ctx.say("Conversation closed.").await?;
+    }
+
+    if let Err(source) = ctx
+        .channel_id()
+        .edit_thread(
+            ctx.http(),
+            serenity::EditThread::new().archived(true).locked(true),
+        )
+        .await
+    {
+        warn!(?source, "failed to archive modmail thread");
+    }
+
+    Ok(())
+}
+
+fn format_timestamp(unix_secs: u64) -> String {
+    i64::try_from(unix_secs)
+        .ok()
+        .and_then(|secs| serenity::Timestamp::from_unix_timestamp(secs).ok())
+        .map_or_else(|| unix_secs.to_string(), |timestamp| timestamp.to_string())
+}
+
+fn parse_channel_id(raw: &str) -> Option<u64> {
+    if let Ok(id) = raw.parse::<u64>() {
+        return Some(id);
+    }
+
+    if raw.starts_with("<#") && raw.ends_with('>') {
+        return raw
+            .trim_start_matches("<#")
+            .trim_end_matches('>')
+            .parse::<u64>()
+            .ok();
+    }
+
+    None
+}
@@ -0,0 +1,182 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::name_filter::{
+    NAME_FILTER_ACTIONS, get_name_filter_config, set_name_filter_action, set_name_filter_enabled,
+    set_name_filter_placeholder,
+};
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "namefilter",
+    desc: "Check usernames and nicknames against the word filter list.",
+    category: "moderation",
+    usage: "!namefilter <enable|disable|action|placeholder>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!namefilter enable",
+        "!namefilter action rename",
+        "!namefilter placeholder Moderated Nickname",
+    ],
+    related: &["wordfilter"],
+};
+
+/// Discord's nickname length limit.
+const MAX_NICKNAME_LENGTH: usize = 32;
+
+/// Check usernames and nicknames against the word filter list.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("enable", "disable", "action", "placeholder"),
+    check = "require_manage_guild"
+)]
+pub async fn namefilter(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let config = get_name_filter_config(&ctx.data().db, guild_id.get()).await?;
+
+    let (enabled, action, placeholder) = match &config {
+        Some(cfg) => (cfg.enabled, cfg.action.as_str(), cfg.placeholder.as_str()),
+        None => (false, "rename", "Moderated Nickname"),
+    };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Name Filter Status")
+        .description(format!(
+            "**Status :** {}\n**Action :** {}\n**Placeholder :** {}\n\n\
+             Names are checked on join and whenever a member's name changes, \
+             using the word filter list and its user and role exemptions.",
+            status,
+            action_display(action),
+            placeholder
+        ))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, action, placeholder",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the name filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_name_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Name filter has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the name filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_name_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Name filter has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a name matches the word filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: rename, kick, or log"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!namefilter action <rename|kick|log>`\n\
+                 • `rename` — Set the member's nickname to the placeholder\n\
+                 • `kick` — Kick the member\n\
+                 • `log` — Only log to the modlog channel";
+
+    let Some(action) = input
+        .as_deref()
+        .map(|raw| raw.trim().to_lowercase())
+        .filter(|raw| NAME_FILTER_ACTIONS.contains(&raw.as_str()))
+    else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    set_name_filter_action(&ctx.data().db, guild_id.get(), &action).await?;
+
+    ctx.say(format!(
+        "Name filter action set to **{}**.",
+        action_display(&action)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the nickname given to members with a filtered name.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn placeholder(
+    ctx: Context<'_>,
+    #[description = "Placeholder nickname"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(placeholder) = input
+        .as_deref()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty() && raw.chars().count() <= MAX_NICKNAME_LENGTH)
+    else {
+        ctx.say(format!(
+            "Usage: `!namefilter placeholder <nickname>` (up to {} characters)",
+            MAX_NICKNAME_LENGTH
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    set_name_filter_placeholder(&ctx.data().db, guild_id.get(), placeholder).await?;
+
+    ctx.say(format!(
+        "Name filter placeholder set to **{}**.",
+        placeholder
+    ))
+    .await?;
+
+    Ok(())
+}
+
+fn action_display(action: &str) -> &str {
+    match action {
+        "kick" => "Kick",
+        "log" => "Log Only",
+        _ => "Rename to Placeholder",
+    }
+}
@@ -0,0 +1,185 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::guild_config::get_guild_config;
+use autumn_database::impls::permission_alerts::get_permission_alert_channel_id;
+use autumn_database::impls::serverlog_config::get_serverlog_channel_id;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::permissions::{BOT_FEATURES, missing_permissions, permission_names};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "permcheck",
+    desc: "Check the bot's permissions and list the features they break.",
+    category: "moderation",
+    usage: "!permcheck",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!permcheck"],
+    related: &["permissions", "config"],
+};
+
+/// Channels named individually before the rest are summarized as a count.
+const MAX_LISTED_CHANNELS: usize = 10;
+
+/// Audit the bot's permissions server-wide, in each log channel and in every
+/// channel its filters watch.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn permcheck(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+
+    let http = ctx.http();
+    let guild = guild_id.to_partial_guild(http).await?;
+    let bot = guild_id.member(http, ctx.framework().bot_id).await?;
+    let channels = guild_id.channels(http).await?;
+
+    let db = &ctx.data().db;
+    let config = get_guild_config(db, guild_id.get()).await?;
+    let serverlog_channel_id = get_serverlog_channel_id(db, guild_id.get()).await?;
+    let permission_alert_channel_id = get_permission_alert_channel_id(db, guild_id.get()).await?;
+
+    let guild_permissions = guild.member_permissions(&bot);
+    let mut problems = Vec::new();
+
+    for feature in BOT_FEATURES {
+        let missing = missing_permissions(guild_permissions, feature.required);
+        if !missing.is_empty() {
+            problems.push(format!(
+                "**{}**: missing {}",
+                feature.name,
+                permission_list(missing)
+            ));
+        }
+    }
+
+    if config.llm_enabled && config.llm_threads {
+        let missing = missing_permissions(
+            guild_permissions,
+            serenity::Permissions::CREATE_PUBLIC_THREADS
+                | serenity::Permissions::SEND_MESSAGES_IN_THREADS,
+        );
+        if !missing.is_empty() {
+            problems.push(format!(
+                "**AI threads**: missing {}",
+                permission_list(missing)
+            ));
+        }
+    }
+
+    let log_permissions = serenity::Permissions::VIEW_CHANNEL
+        | serenity::Permissions::SEND_MESSAGES
+        | serenity::Permissions::EMBED_LINKS;
+    let modlog_permissions = if config.purge_transcripts {
+        log_permissions | serenity::Permissions::ATTACH_FILES
+    } else {
+        log_permissions
+    };
+    let log_channels = [
+        ("Modlog", config.modlog_channel_id, modlog_permissions),
+        ("User log", config.userlog_channel_id, log_permissions),
+        ("Server log", serverlog_channel_id, log_permissions),
+        (
+            "Permission alerts",
+            permission_alert_channel_id,
+            log_permissions,
+        ),
+    ];
+
+    for (label, channel_id, required) in log_channels {
+        let Some(channel_id) = channel_id else {
+            continue;
+        };
+        match channels.get(&serenity::ChannelId::new(channel_id)) {
+            Some(channel) => {
+                let missing =
+                    missing_permissions(guild.user_permissions_in(channel, &bot), required);
+                if !missing.is_empty() {
+                    problems.push(format!(
+                        "**{} channel** <#{}>: missing {}",
+                        label,
+                        channel_id,
+                        permission_list(missing)
+                    ));
+                }
+            }
+            None => problems.push(format!(
+                "**{} channel**: <#{}> no longer exists",
+                label, channel_id
+            )),
+        }
+    }
+
+    // Filters see these channels but can't remove anything posted in them.
+    let mut undeletable: Vec<&serenity::GuildChannel> = channels
+        .values()
+        .filter(|channel| {
+            matches!(
+                channel.kind,
+                serenity::ChannelType::Text | serenity::ChannelType::News
+            )
+        })
+        .filter(|channel| {
+            let permissions = guild.user_permissions_in(channel, &bot);
+            permissions.contains(serenity::Permissions::VIEW_CHANNEL)
+                && !missing_permissions(permissions, serenity::Permissions::MANAGE_MESSAGES)
+                    .is_empty()
+        })
+        .collect();
+    undeletable.sort_by_key(|channel| channel.position);
+
+    if !undeletable.is_empty() {
+        let mut listed: Vec<String> = undeletable
+            .iter()
+            .take(MAX_LISTED_CHANNELS)
+            .map(|channel| format!("<#{}>", channel.id))
+            .collect();
+        if undeletable.len() > MAX_LISTED_CHANNELS {
+            listed.push(format!(
+                "and {} more",
+                undeletable.len() - MAX_LISTED_CHANNELS
+            ));
+        }
+        problems.push(format!(
+            "**Can't delete messages in**: {}",
+            listed.join(", ")
+        ));
+    }
+
+    let description = if problems.is_empty() {
+        "Every feature has the permissions it needs.".to_owned()
+    } else {
+        problems
+            .iter()
+            .map(|problem| format!("• {}", problem))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Permission Check")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Server-wide: {}",
+            permission_names(guild_permissions).join(", ")
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn permission_list(permissions: serenity::Permissions) -> String {
+    permissions.get_permission_names().join(", ")
+}
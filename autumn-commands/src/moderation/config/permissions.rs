@@ -1,18 +1,31 @@
+use poise::serenity_prelude as serenity;
+
 use crate::CommandMeta;
+use crate::branding::pagination_options;
+use crate::checks::require_manage_messages;
 use autumn_core::{Context, Error};
-use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::{has_user_permission, permission_names, resolve_user_permissions};
+use autumn_utils::page_builder::{PageBuilder, PageEntry};
+use autumn_utils::pagination::paginate_embed_pages_with_options;
+use autumn_utils::permissions::{permission_names, resolve_user_permissions};
 
 pub const META: CommandMeta = CommandMeta {
     name: "permissions",
     desc: "Display your server permissions.",
     category: "moderation",
     usage: "!permissions [page]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!permissions", "!permissions 2"],
+    related: &["permlevels", "permcheck"],
 };
 
 const PERMISSIONS_PER_PAGE: usize = 10;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn permissions(
     ctx: Context<'_>,
     #[description = "Starting page"] page: Option<usize>,
@@ -22,17 +35,6 @@ pub async fn permissions(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        poise::serenity_prelude::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let perms = resolve_user_permissions(ctx.http(), guild_id, ctx.author().id).await?;
     let names = permission_names(perms);
 
@@ -41,43 +43,30 @@ pub async fn permissions(
         return Ok(());
     }
 
-    let total = total_pages(names.len(), PERMISSIONS_PER_PAGE);
+    let mut builder = PageBuilder::new(PERMISSIONS_PER_PAGE).compact();
+    for (index, name) in names.iter().enumerate() {
+        builder.push(PageEntry::new(format!("{}. {}", index + 1, name)));
+    }
+    let pages = builder.build();
     let requested_page = page.unwrap_or(1);
 
-    if requested_page == 0 || requested_page > total {
+    if requested_page == 0 || requested_page > pages.len() {
         ctx.say(format!(
             "Page {} does not exist. Available pages: 1-{}.",
-            requested_page, total
+            requested_page,
+            pages.len()
         ))
         .await?;
         return Ok(());
     }
 
-    let pages = (1..=total)
-        .map(|current_page| {
-            let (start, end) = page_window(names.len(), PERMISSIONS_PER_PAGE, current_page);
-            names[start..end]
-                .iter()
-                .enumerate()
-                .map(|(index, item)| format!("{}. {}", start + index + 1, item))
-                .collect::<Vec<_>>()
-                .join("\n")
-        })
-        .collect::<Vec<_>>();
-    paginate_embed_pages(ctx, "Your Permissions", &pages, requested_page).await?;
+    paginate_embed_pages_with_options(
+        ctx,
+        "Your Permissions",
+        &pages,
+        requested_page,
+        pagination_options(ctx).await,
+    )
+    .await?;
     Ok(())
 }
-
-fn total_pages(total_items: usize, per_page: usize) -> usize {
-    let per_page = per_page.max(1);
-    let pages = total_items.div_ceil(per_page);
-    pages.max(1)
-}
-
-fn page_window(total_items: usize, per_page: usize, page: usize) -> (usize, usize) {
-    let per_page = per_page.max(1);
-    let page = page.max(1);
-    let start = (page - 1).saturating_mul(per_page).min(total_items);
-    let end = (start + per_page).min(total_items);
-    (start, end)
-}
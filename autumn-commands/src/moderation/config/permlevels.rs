@@ -0,0 +1,177 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{effective_permissions, level_permissions, require_manage_guild};
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::permission_levels::{
+    list_permission_levels, remove_role_permission_level, set_role_permission_level,
+};
+use autumn_database::model::permission_levels::PermissionLevel;
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "permlevels",
+    desc: "Grant roles bot permission levels (helper, mod, admin).",
+    category: "moderation",
+    usage: "!permlevels [set <role> <helper|mod|admin>|remove <role>]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!permlevels",
+        "!permlevels set @Mods mod",
+        "!permlevels remove @Mods",
+    ],
+    related: &["permissions", "modhierarchy"],
+};
+
+/// Show which roles hold bot permission levels.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("set", "remove"),
+    check = "require_manage_guild"
+)]
+pub async fn permlevels(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let levels = list_permission_levels(&ctx.data().db, guild_id.get()).await?;
+
+    let lines: Vec<String> = PermissionLevel::ALL
+        .iter()
+        .rev()
+        .map(|level| {
+            let roles: Vec<String> = levels
+                .iter()
+                .filter(|entry| entry.level == *level)
+                .map(|entry| format!("<@&{}>", entry.role_id))
+                .collect();
+            let roles = if roles.is_empty() {
+                "None".to_owned()
+            } else {
+                roles.join(", ")
+            };
+            format!(
+                "**{} :** {}\n-# Acts as {}",
+                level.label(),
+                roles,
+                level_permissions(*level).get_permission_names().join(", ")
+            )
+        })
+        .collect();
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Permission Levels")
+        .description(lines.join("\n\n"))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: set <role> <level>, remove <role>",
+        ));
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Grant a role a bot permission level.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Role to grant the level to"] role: Option<serenity::Role>,
+    #[description = "Level: helper, mod, or admin"] level: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let (Some(role), Some(level)) = (role, level.as_deref().and_then(PermissionLevel::parse))
+    else {
+        ctx.say("Usage: `!permlevels set <role> <helper|mod|admin>`")
+            .await?;
+        return Ok(());
+    };
+
+    if role.id.get() == guild_id.get() {
+        ctx.say("Permission levels can't be given to @everyone.")
+            .await?;
+        return Ok(());
+    }
+
+    // Nobody can hand out more than they could do themselves.
+    let granted = level_permissions(level);
+    let perms = effective_permissions(ctx, guild_id).await?;
+    if !perms.contains(serenity::Permissions::ADMINISTRATOR) && !perms.contains(granted) {
+        ctx.say(format!(
+            "You can only grant levels you already have the permissions of. **{}** needs {}.",
+            level.label(),
+            granted.get_permission_names().join(", ")
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    set_role_permission_level(
+        &ctx.data().db,
+        guild_id.get(),
+        role.id.get(),
+        level,
+        ctx.author().id.get(),
+    )
+    .await?;
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "<@&{}> now has the **{}** permission level.",
+                role.id,
+                level.label()
+            ))
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Take a role's bot permission level away.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Role to remove the level from"] role: Option<serenity::Role>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(role) = role else {
+        ctx.say("Usage: `!permlevels remove <role>`").await?;
+        return Ok(());
+    };
+
+    let removed =
+        remove_role_permission_level(&ctx.data().db, guild_id.get(), role.id.get()).await?;
+    let content = if removed {
+        format!("<@&{}> no longer has a permission level.", role.id)
+    } else {
+        format!("<@&{}> had no permission level.", role.id)
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(content)
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use poise::serenity_prelude as serenity;
+use std::time::Duration;
+use tracing::info;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::moderation::retention::discard_archived_attachments;
+use autumn_core::{Context, Error};
+use autumn_database::impls::retention::erase_user_data;
+use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "privacy",
+    desc: "Erase a user's stored data from this server.",
+    category: "moderation",
+    usage: "!privacy erase <user>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!privacy erase 123456789012345678"],
+    related: &["backup"],
+};
+
+const ERASE_CONFIRM_TIMEOUT_SECS: u64 = 30;
+
+/// Manage personal data stored for this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("erase")
+)]
+pub async fn privacy(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.say(usage_message(META.usage)).await?;
+    Ok(())
+}
+
+/// Delete a user's warnings, notes, chat history and logs, and anonymize
+/// their cases.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn erase(
+    ctx: Context<'_>,
+    #[description = "The user whose data to erase"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let confirm_embed = serenity::CreateEmbed::new().description(format!(
+        "This will permanently delete the warnings, notes, AI chat history and \
+         user log records of <@{}> in this server and their command usage \
+         records, and remove them and the reason from their cases. This can't \
+         be undone.",
+        user.id.get()
+    ));
+
+    let confirmation = prompt_confirm_decline(
+        ctx,
+        "Confirm data erasure",
+        confirm_embed,
+        Duration::from_secs(ERASE_CONFIRM_TIMEOUT_SECS),
+    )
+    .await?;
+
+    let Some(interaction) = resolve_confirmation_result(
+        ctx,
+        confirmation,
+        "Timed out",
+        "Data erasure cancelled.",
+        "Erasing data...",
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let report = erase_user_data(&ctx.data().db, guild_id.get(), user.id.get()).await?;
+    discard_archived_attachments(
+        ctx.data().attachment_store.as_deref(),
+        &report.message_snapshots,
+    )
+    .await;
+
+    info!(
+        guild_id = guild_id.get(),
+        moderator_id = ctx.author().id.get(),
+        erased_rows = report.total(),
+        "Erased user data on request."
+    );
+
+    let content = if report.total() == 0 {
+        format!("No stored data found for <@{}>.", user.id.get())
+    } else {
+        format!(
+            "Erased data for <@{}>.\n\
+             **Warnings :** {}\n\
+             **Notes :** {}\n\
+             **Cases anonymized :** {}\n\
+             **AI chat messages :** {}\n\
+             **User log records :** {}\n\
+             **Reports :** {}\n\
+             **Modmail conversations :** {}\n\
+             **Watchlist entries :** {}\n\
+             **Command usage records :** {}",
+            user.id.get(),
+            report.warnings,
+            report.notes,
+            report.anonymized_cases,
+            report.llm_chat_messages,
+            report.user_logs + report.message_snapshots.len() as u64,
+            report.reports,
+            report.modmail_conversations,
+            report.watchlist_entries,
+            report.command_invocations,
+        )
+    };
+
+    interaction
+        .edit_response(
+            ctx.http(),
+            serenity::EditInteractionResponse::new()
+                .content(content)
+                .embeds(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
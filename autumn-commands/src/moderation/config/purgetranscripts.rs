@@ -0,0 +1,73 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::modlog_config::{
+    get_purge_transcripts_enabled, set_purge_transcripts_enabled,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "purgetranscripts",
+    desc: "Attach transcripts of purged messages to modlog entries.",
+    category: "moderation",
+    usage: "!purgetranscripts <on|off|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!purgetranscripts on", "!purgetranscripts status"],
+    related: &["purge", "modlogchannel"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn purgetranscripts(
+    ctx: Context<'_>,
+    #[description = "Desired state: on, off, or status"] state: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_state) = state.as_deref().map(str::trim) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if raw_state.eq_ignore_ascii_case("status") {
+        let enabled = get_purge_transcripts_enabled(&ctx.data().db, guild_id.get()).await?;
+        ctx.say(format!(
+            "Purge transcripts are currently **{}** for this server.",
+            if enabled { "enabled" } else { "disabled" }
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let new_state = if raw_state.eq_ignore_ascii_case("on") {
+        true
+    } else if raw_state.eq_ignore_ascii_case("off") {
+        false
+    } else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if !set_purge_transcripts_enabled(&ctx.data().db, guild_id.get(), new_state).await? {
+        ctx.say("Set a modlog channel first with `!modlogchannel`; transcripts are posted there.")
+            .await?;
+        return Ok(());
+    }
+
+    ctx.say(format!(
+        "Purge transcripts are now **{}** for this server.",
+        if new_state { "enabled" } else { "disabled" }
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::raidmode::{
+    get_raidmode_snapshot, save_raidmode_snapshot, take_raidmode_snapshot,
+};
+use autumn_database::impls::word_filter::{
+    get_word_filter_config, set_word_filter_action, set_word_filter_enabled,
+};
+use autumn_database::model::raidmode::RaidmodeSnapshot;
+use autumn_utils::embed::guild_embed;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "raidmode",
+    desc: "Lock the server down during a raid, or lift the lockdown.",
+    category: "moderation",
+    usage: "!raidmode <on|off>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!raidmode on", "!raidmode off"],
+    related: &["antiraid"],
+};
+
+/// Guild feature Discord uses to pause invites.
+const INVITES_DISABLED_FEATURE: &str = "INVITES_DISABLED";
+
+/// Word filter action applied while raid mode is on.
+const RAIDMODE_WORD_FILTER_ACTION: &str = "timeout_delete_and_log";
+
+/// Lock the server down during a raid, or lift the lockdown.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("raidmode_on", "raidmode_off"),
+    check = "require_manage_guild"
+)]
+pub async fn raidmode(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let snapshot = get_raidmode_snapshot(&ctx.data().db, guild_id.get()).await?;
+
+    let status = match &snapshot {
+        Some(snapshot) => format!(
+            "**Status :** On\n**Enabled By :** <@{}>\n**Since :** <t:{}:R>",
+            snapshot.enabled_by, snapshot.enabled_at
+        ),
+        None => "**Status :** Off".to_owned(),
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Raid Mode")
+        .description(format!(
+            "{}\n\n\
+             Raid mode pauses invites, raises the verification level to Highest, \
+             kicks everyone who joins, and switches the word filter on with the \
+             timeout action. Turning it off restores the previous settings.",
+            status
+        ))
+        .footer(serenity::CreateEmbedFooter::new("Subcommands: on, off"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Turn raid mode on.
+#[poise::command(prefix_command, slash_command, category = "Moderation", rename = "on")]
+pub async fn raidmode_on(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let guild = guild_id.to_partial_guild(ctx.http()).await?;
+    let word_filter = get_word_filter_config(db, guild_id.get()).await?;
+
+    let snapshot = RaidmodeSnapshot {
+        guild_id: i64::try_from(guild_id.get())?,
+        enabled_by: i64::try_from(ctx.author().id.get())?,
+        enabled_at: unix_now(),
+        verification_level: i16::from(u8::from(guild.verification_level)),
+        invites_disabled: guild
+            .features
+            .iter()
+            .any(|feature| feature == INVITES_DISABLED_FEATURE),
+        word_filter_enabled: word_filter.as_ref().is_some_and(|cfg| cfg.enabled),
+        word_filter_action: word_filter
+            .map(|cfg| cfg.action)
+            .unwrap_or_else(|| "log_only".to_owned()),
+    };
+
+    if !save_raidmode_snapshot(db, &snapshot).await? {
+        ctx.say("Raid mode is already **on**.").await?;
+        return Ok(());
+    }
+
+    let mut features = guild.features.clone();
+    if !snapshot.invites_disabled {
+        features.push(INVITES_DISABLED_FEATURE.to_owned());
+    }
+
+    let reason = format!("Raid mode enabled by {}", ctx.author().name);
+    let edited = guild_id
+        .edit(
+            ctx.http(),
+            serenity::EditGuild::new()
+                .verification_level(serenity::VerificationLevel::Higher)
+                .features(features)
+                .audit_log_reason(&reason),
+        )
+        .await;
+
+    if edited.is_err() {
+        take_raidmode_snapshot(db, guild_id.get()).await?;
+        ctx.say(
+            "I couldn't update the server settings, so raid mode was not turned on. \
+             Please check my **Manage Server** permission.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    set_word_filter_enabled(db, guild_id.get(), true).await?;
+    set_word_filter_action(db, guild_id.get(), RAIDMODE_WORD_FILTER_ACTION).await?;
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Raid Mode On")
+        .description(
+            "**Invites :** Paused\n\
+             **Verification Level :** Highest\n\
+             **New Joins :** Kicked\n\
+             **Word Filter :** Enabled (Timeout, Delete & Log)\n\n\
+             Use `!raidmode off` to restore the previous settings.",
+        );
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Turn raid mode off and restore the previous settings.
+#[poise::command(prefix_command, slash_command, category = "Moderation", rename = "off")]
+pub async fn raidmode_off(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let Some(snapshot) = take_raidmode_snapshot(db, guild_id.get()).await? else {
+        ctx.say("Raid mode is not on.").await?;
+        return Ok(());
+    };
+
+    set_word_filter_enabled(db, guild_id.get(), snapshot.word_filter_enabled).await?;
+    set_word_filter_action(db, guild_id.get(), &snapshot.word_filter_action).await?;
+
+    let guild = guild_id.to_partial_guild(ctx.http()).await?;
+    let mut features = guild.features.clone();
+    if !snapshot.invites_disabled {
+        features.retain(|feature| feature != INVITES_DISABLED_FEATURE);
+    }
+
+    let verification_level = u8::try_from(snapshot.verification_level)
+        .map(serenity::VerificationLevel::from)
+        .unwrap_or_default();
+
+    let reason = format!("Raid mode disabled by {}", ctx.author().name);
+    let restored = guild_id
+        .edit(
+            ctx.http(),
+            serenity::EditGuild::new()
+                .verification_level(verification_level)
+                .features(features)
+                .audit_log_reason(&reason),
+        )
+        .await;
+
+    if restored.is_err() {
+        ctx.say(
+            "Raid mode is **off** and the word filter was restored, but I couldn't restore \
+             the invite and verification settings. Please check my **Manage Server** permission.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.say("Raid mode is **off**. Previous settings have been restored.")
+        .await?;
+
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
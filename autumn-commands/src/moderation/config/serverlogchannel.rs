@@ -0,0 +1,86 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::serverlog_config::{
+    clear_serverlog_channel_id, get_serverlog_channel_id, set_serverlog_channel_id,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "serverlogchannel",
+    desc: "Set or view the channel and role change log channel.",
+    category: "moderation",
+    usage: "!serverlogchannel [#channel|channel_id|clear]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!serverlogchannel #server-log", "!serverlogchannel clear"],
+    related: &["modlogchannel", "userlogchannel"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn serverlogchannel(
+    ctx: Context<'_>,
+    #[description = "Channel mention/id, or 'clear'"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    if let Some(input) = input
+        .as_deref()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        if input.eq_ignore_ascii_case("clear") {
+            clear_serverlog_channel_id(&ctx.data().db, guild_id.get()).await?;
+            ctx.say("Server log channel cleared.").await?;
+            return Ok(());
+        }
+
+        if let Some(channel_id) = parse_channel_id(input) {
+            set_serverlog_channel_id(&ctx.data().db, guild_id.get(), channel_id).await?;
+            ctx.say(format!("Server log channel set to <#{}>.", channel_id))
+                .await?;
+            return Ok(());
+        }
+
+        ctx.say("Provide a valid channel mention/id, or `clear`.")
+            .await?;
+        return Ok(());
+    }
+
+    let current = get_serverlog_channel_id(&ctx.data().db, guild_id.get()).await?;
+    if let Some(channel_id) = current {
+        ctx.say(format!("Current server log channel: <#{}>", channel_id))
+            .await?;
+    } else {
+        ctx.say("No server log channel configured.").await?;
+    }
+
+    Ok(())
+}
+
+fn parse_channel_id(raw: &str) -> Option<u64> {
+    if let Ok(id) = raw.parse::<u64>() {
+        return Some(id);
+    }
+
+    if raw.starts_with("<#") && raw.ends_with('>') {
+        return raw
+            .trim_start_matches("<#")
+            .trim_end_matches('>')
+            .parse::<u64>()
+            .ok();
+    }
+
+    None
+}
@@ -1,6 +1,8 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
 use crate::moderation::embeds::guild_only_message;
 use autumn_core::{Context, Error};
 use autumn_database::impls::escalation::{
@@ -11,14 +13,16 @@ use autumn_database::impls::userlog_config::set_userlog_channel_id;
 use autumn_database::impls::word_filter::{
     load_preset_words, set_word_filter_action, set_word_filter_enabled,
 };
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::embed::guild_embed;
 
 pub const META: CommandMeta = CommandMeta {
     name: "setup",
     desc: "Apply a moderation configuration preset to this server.",
     category: "moderation",
     usage: "!setup <basic|standard|strict> [#modlog-channel] [#userlog-channel]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!setup standard", "!setup strict #mod-log #user-log"],
+    related: &["config", "modlogchannel", "permcheck"],
 };
 
 enum SetupPreset {
@@ -90,7 +94,12 @@ impl SetupPreset {
 }
 
 /// Apply a moderation configuration preset to this server.
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
 pub async fn setup(
     ctx: Context<'_>,
     #[description = "Preset: basic, standard, or strict"] preset: Option<String>,
@@ -105,17 +114,6 @@ pub async fn setup(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw_preset) = preset.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
         ctx.say(
             "**Autumn Setup Presets**\n\n\
@@ -230,7 +228,7 @@ pub async fn setup(
         "Disabled".to_owned()
     };
 
-    let embed = serenity::CreateEmbed::new()
+    let embed = guild_embed(accent_color(ctx).await)
         .title(format!("{} Preset Applied", preset.name()))
         .description(format!(
             "**Modlog Channel :** <#{}>\n\
@@ -240,7 +238,6 @@ pub async fn setup(
             modlog_id.get(),
             userlog_id.get(),
         ))
-        .color(DEFAULT_EMBED_COLOR)
         .footer(serenity::CreateEmbedFooter::new(
             "You can fine-tune these settings with !modlogchannel, !userlogchannel, !escalation, and !wordfilter.",
         ));
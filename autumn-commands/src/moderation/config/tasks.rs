@@ -0,0 +1,195 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use autumn_core::{Context, Error};
+use autumn_database::impls::recurring_tasks::{
+    NewRecurringTask, RECURRING_TASK_KINDS, TASK_AUTO_PURGE, TASK_MODLOG_DIGEST,
+    TASK_STATS_SUMMARY, create_recurring_task, delete_recurring_task, list_recurring_tasks,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::recurrence::Recurrence;
+use autumn_utils::time::now_unix_secs;
+
+const MAX_TASKS_PER_GUILD: usize = 10;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "tasks",
+    desc: "Schedule recurring digests, stats summaries and channel purges.",
+    category: "moderation",
+    usage: "!tasks <add|remove|list>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!tasks list",
+        "!tasks add digest #mod-log daily 09:00",
+        "!tasks remove 3",
+    ],
+    related: &["webhooks"],
+};
+
+/// Schedule recurring digests, stats summaries and channel purges.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("add", "remove", "list"),
+    check = "require_manage_guild"
+)]
+pub async fn tasks(ctx: Context<'_>) -> Result<(), Error> {
+    show_tasks(ctx).await
+}
+
+/// Add a recurring task, e.g. `!tasks add digest #mod-log weekly mon 09:00`.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "digest, stats or purge"] kind: Option<String>,
+    #[description = "Channel to post in (or purge)"] channel: Option<serenity::GuildChannel>,
+    #[description = "daily HH:MM or weekly <day> HH:MM (UTC)"]
+    #[rest]
+    schedule: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let kind = kind.map(|kind| kind.trim().to_ascii_lowercase());
+    let (Some(kind), Some(channel), Some(recurrence)) = (
+        kind.filter(|kind| RECURRING_TASK_KINDS.contains(&kind.as_str())),
+        channel,
+        schedule.as_deref().and_then(Recurrence::parse),
+    ) else {
+        ctx.say(
+            "Usage: `!tasks add <digest|stats|purge> <#channel> <daily HH:MM | weekly <day> HH:MM>` \
+             (times are UTC)",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if channel.guild_id != guild_id {
+        ctx.say("That channel isn't in this server.").await?;
+        return Ok(());
+    }
+
+    let db = &ctx.data().db;
+    let existing = list_recurring_tasks(db, guild_id.get()).await?;
+    if existing.len() >= MAX_TASKS_PER_GUILD {
+        ctx.say(format!(
+            "This server already has {} tasks. Remove one first.",
+            MAX_TASKS_PER_GUILD
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let schedule = recurrence.to_string();
+    let first_run_at = recurrence.next_after(now_unix_secs());
+    let id = create_recurring_task(
+        db,
+        NewRecurringTask {
+            guild_id: guild_id.get(),
+            kind: &kind,
+            channel_id: channel.id.get(),
+            schedule: &schedule,
+            created_by: ctx.author().id.get(),
+        },
+        first_run_at,
+    )
+    .await?;
+
+    ctx.say(format!(
+        "Task `{}` added: {} in <#{}>, {} UTC. First run <t:{}:R>.",
+        id,
+        task_kind_label(&kind),
+        channel.id.get(),
+        schedule,
+        first_run_at
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Remove a recurring task.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Task ID from the list"] id: Option<i64>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(id) = id else {
+        ctx.say("Usage: `!tasks remove <id>`").await?;
+        return Ok(());
+    };
+
+    if delete_recurring_task(&ctx.data().db, guild_id.get(), id).await? {
+        ctx.say(format!("Task `{}` removed.", id)).await?;
+    } else {
+        ctx.say(format!("No task with ID `{}`.", id)).await?;
+    }
+
+    Ok(())
+}
+
+/// List this server's recurring tasks.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    show_tasks(ctx).await
+}
+
+async fn show_tasks(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let tasks = list_recurring_tasks(&ctx.data().db, guild_id.get()).await?;
+
+    let description = if tasks.is_empty() {
+        "No recurring tasks.".to_owned()
+    } else {
+        tasks
+            .iter()
+            .map(|task| {
+                format!(
+                    "**ID :** `{}`\n**Task :** {} in <#{}>\n**Schedule :** {} UTC\n**Added :** <@{}> <t:{}:R>",
+                    task.id,
+                    task_kind_label(&task.kind),
+                    task.channel_id,
+                    task.schedule,
+                    task.created_by,
+                    task.created_at
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Recurring Tasks")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Kinds: digest, stats, purge • Subcommands: add, remove, list",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn task_kind_label(kind: &str) -> &'static str {
+    match kind {
+        TASK_MODLOG_DIGEST => "Modlog digest",
+        TASK_STATS_SUMMARY => "Stats summary",
+        TASK_AUTO_PURGE => "Auto-purge",
+        _ => "Unknown task",
+    }
+}
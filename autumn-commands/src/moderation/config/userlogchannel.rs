@@ -1,21 +1,29 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::checks::require_manage_guild;
 use crate::moderation::embeds::guild_only_message;
 use autumn_core::{Context, Error};
 use autumn_database::impls::userlog_config::{
     clear_userlog_channel_id, get_userlog_channel_id, set_userlog_channel_id,
 };
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "userlogchannel",
     desc: "Set or view the user activity log channel.",
     category: "moderation",
     usage: "!userlogchannel [#channel|channel_id|clear]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!userlogchannel #user-log", "!userlogchannel clear"],
+    related: &["userlogs", "modlogchannel", "serverlogchannel"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
 pub async fn userlogchannel(
     ctx: Context<'_>,
     #[description = "Channel mention/id, or 'clear'"]
@@ -27,17 +35,6 @@ pub async fn userlogchannel(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     if let Some(input) = input
         .as_deref()
         .map(str::trim)
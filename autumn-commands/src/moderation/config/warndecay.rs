@@ -0,0 +1,88 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::warning_decay::{
+    disable_warning_decay, enable_warning_decay, get_warning_decay_config,
+};
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::parse_duration_seconds;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "warndecay",
+    desc: "Expire warnings automatically once they reach a given age.",
+    category: "moderation",
+    usage: "!warndecay <age|off|status>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!warndecay 90d", "!warndecay off"],
+    related: &["warnings", "escalation"],
+};
+
+const MIN_WARNING_DECAY_SECS: u64 = 24 * 60 * 60;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_guild"
+)]
+pub async fn warndecay(
+    ctx: Context<'_>,
+    #[description = "Warning age to expire at (e.g. 90d), off, or status"] age: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw_age) = age.as_deref().map(str::trim) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+
+    if raw_age.eq_ignore_ascii_case("status") {
+        let content = match get_warning_decay_config(db, guild_id.get()).await? {
+            Some(config) => format!(
+                "Warnings expire after **{}**. A weekly digest of expired warnings is posted to the modlog channel.",
+                format_compact_duration(config.max_age_seconds)
+            ),
+            None => "Warning decay is **disabled** for this server.".to_owned(),
+        };
+        ctx.say(content).await?;
+        return Ok(());
+    }
+
+    if raw_age.eq_ignore_ascii_case("off") {
+        let content = if disable_warning_decay(db, guild_id.get()).await? {
+            "Warning decay is now **disabled**. Warnings that already expired stay expired."
+        } else {
+            "Warning decay is already disabled."
+        };
+        ctx.say(content).await?;
+        return Ok(());
+    }
+
+    let Some(max_age_seconds) = parse_duration_seconds(raw_age) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if max_age_seconds < MIN_WARNING_DECAY_SECS {
+        ctx.say("Warnings must be kept for at least a day.").await?;
+        return Ok(());
+    }
+
+    enable_warning_decay(db, guild_id.get(), max_age_seconds, ctx.author().id.get()).await?;
+
+    ctx.say(format!(
+        "Warnings older than **{}** will now expire. Expired warnings no longer count toward `!warnings` or escalation.",
+        format_compact_duration(max_age_seconds)
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_guild;
+use crate::moderation::embeds::guild_only_message;
+use crate::moderation::webhook_dispatcher::{generate_webhook_secret, resolve_webhook_target};
+use autumn_core::{Context, Error};
+use autumn_database::impls::webhooks::{
+    add_guild_webhook, list_guild_webhooks, remove_guild_webhook,
+};
+use autumn_utils::embed::guild_embed;
+
+const MAX_WEBHOOKS_PER_GUILD: usize = 5;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "webhooks",
+    desc: "Send case events to external URLs.",
+    category: "moderation",
+    usage: "!webhooks <add|remove|list>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!webhooks list",
+        "!webhooks add https://example.com/hook",
+        "!webhooks remove 2",
+    ],
+    related: &["tasks"],
+};
+
+/// Send case events to external URLs.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("add", "remove", "list"),
+    check = "require_manage_guild"
+)]
+pub async fn webhooks(ctx: Context<'_>) -> Result<(), Error> {
+    show_webhooks(ctx).await
+}
+
+/// Register a URL to receive signed case events.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "HTTPS URL that receives the events"] url: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(url) = url
+        .as_deref()
+        .map(|url| url.trim().trim_start_matches('<').trim_end_matches('>'))
+        .filter(|url| url.starts_with("https://") && url.len() > "https://".len())
+    else {
+        ctx.say("Usage: `!webhooks add <https://...>`").await?;
+        return Ok(());
+    };
+
+    if let Err(problem) = resolve_webhook_target(url).await {
+        ctx.say(problem).await?;
+        return Ok(());
+    }
+
+    let existing = list_guild_webhooks(&ctx.data().db, guild_id.get()).await?;
+    if existing.len() >= MAX_WEBHOOKS_PER_GUILD {
+        ctx.say(format!(
+            "This server already has {} webhooks. Remove one first.",
+            MAX_WEBHOOKS_PER_GUILD
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let secret = generate_webhook_secret();
+    let Some(id) = add_guild_webhook(
+        &ctx.data().db,
+        guild_id.get(),
+        url,
+        &secret,
+        ctx.author().id.get(),
+    )
+    .await?
+    else {
+        ctx.say("That URL is already registered.").await?;
+        return Ok(());
+    };
+
+    let secret_message = format!(
+        "Webhook `{}` registered for <{}>.\n\
+         **Signing secret :** `{}`\n\
+         Requests carry `X-Autumn-Signature: sha256=<hex>`, an HMAC-SHA256 of \
+         `<X-Autumn-Timestamp>.<body>` with this secret. It won't be shown again.",
+        id, url, secret
+    );
+
+    // The secret must not land in a public channel.
+    match ctx {
+        poise::Context::Application(_) => {
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(secret_message)
+                    .ephemeral(true),
+            )
+            .await?;
+        }
+        poise::Context::Prefix(_) => {
+            let sent = ctx
+                .author()
+                .direct_message(
+                    ctx.http(),
+                    serenity::CreateMessage::new().content(secret_message),
+                )
+                .await;
+
+            if sent.is_ok() {
+                ctx.say(format!(
+                    "Webhook `{}` registered. The signing secret was sent to your DMs.",
+                    id
+                ))
+                .await?;
+            } else {
+                remove_guild_webhook(&ctx.data().db, guild_id.get(), id).await?;
+                ctx.say("I couldn't DM you the signing secret, so the webhook wasn't added. Enable DMs or use `/webhooks add`.")
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Stop sending case events to a webhook.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Webhook ID from the list"] id: Option<i64>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(id) = id else {
+        ctx.say("Usage: `!webhooks remove <id>`").await?;
+        return Ok(());
+    };
+
+    if remove_guild_webhook(&ctx.data().db, guild_id.get(), id).await? {
+        ctx.say(format!("Webhook `{}` removed.", id)).await?;
+    } else {
+        ctx.say(format!("No webhook with ID `{}`.", id)).await?;
+    }
+
+    Ok(())
+}
+
+/// List the webhooks receiving case events.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    show_webhooks(ctx).await
+}
+
+async fn show_webhooks(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let webhooks = list_guild_webhooks(&ctx.data().db, guild_id.get()).await?;
+
+    let description = if webhooks.is_empty() {
+        "No webhooks registered.".to_owned()
+    } else {
+        webhooks
+            .iter()
+            .map(|webhook| {
+                format!(
+                    "**ID :** `{}`\n**URL :** <{}>\n**Added :** <@{}> <t:{}:R>",
+                    webhook.id, webhook.url, webhook.created_by, webhook.created_at
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Webhooks")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Events: case.created, case.updated • Subcommands: add, remove, list",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
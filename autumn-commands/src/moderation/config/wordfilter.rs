@@ -1,29 +1,69 @@
 use poise::serenity_prelude as serenity;
+use tracing::error;
 
+use super::automod::{FILTER_ACTION_HELP, filter_action_display, parse_filter_action};
+use super::exemptions::{exempt_add_for_scope, exempt_list_for_scope, exempt_remove_for_scope};
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::branding::pagination_options;
+use crate::checks::{has_command_permission, require_manage_guild};
 use crate::moderation::embeds::guild_only_message;
 use autumn_core::{Context, Error};
+use autumn_database::impls::exemptions::list_exemptions;
 use autumn_database::impls::word_filter::{
-    add_filter_word, clear_preset_words, get_word_filter_config, list_filter_words,
-    load_preset_words, remove_filter_word, set_word_filter_action, set_word_filter_enabled,
+    AUTOMOD_MAX_KEYWORDS, MATCH_MODES, add_filter_word, automod_keyword, clear_preset_words,
+    clear_word_filter_channel_override, find_matching_pattern, get_all_filter_words_for_guild,
+    get_word_filter_automod_rule_id, get_word_filter_config, get_word_filter_for_channel,
+    import_filter_words, list_filter_words, list_word_filter_channel_overrides, load_preset_words,
+    record_word_filter_automod_sync, remove_filter_word, set_word_filter_action,
+    set_word_filter_ai_action, set_word_filter_ai_check, set_word_filter_ai_threshold,
+    set_word_filter_channel_override, set_word_filter_enabled, suggest_filter_words,
 };
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
-use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::model::word_filter::{DEFAULT_AI_THRESHOLD, WordFilterPattern};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::pagination::paginate_embed_pages_with_options;
 
 pub const META: CommandMeta = CommandMeta {
     name: "wordfilter",
     desc: "Manage the word filter for this server.",
     category: "moderation",
-    usage: "!wordfilter <enable|disable|action|preset|add|remove|list>",
+    usage: "!wordfilter <enable|disable|action|preset|add|remove|list|exempt|channel|ai|test|export|import|sync-automod>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &[
+        "!wordfilter enable",
+        "!wordfilter add scam --mode contains",
+        "!wordfilter action timeout",
+        "!wordfilter test free nitro here",
+    ],
+    related: &["linkfilter", "namefilter", "escalation"],
 };
 
+/// Most suggestions Discord shows for one autocomplete request.
+const AUTOCOMPLETE_LIMIT: usize = 25;
+
 /// Manage the word filter for this server.
 #[poise::command(
     prefix_command,
     slash_command,
     category = "Moderation",
-    subcommands("enable", "disable", "action", "preset", "add", "remove", "list")
+    subcommands(
+        "enable",
+        "disable",
+        "action",
+        "preset",
+        "add",
+        "remove",
+        "list",
+        "exempt",
+        "channel",
+        "ai",
+        "test",
+        "export",
+        "import",
+        "sync_automod"
+    ),
+    check = "require_manage_guild",
+    default_member_permissions = "MANAGE_GUILD"
 )]
 pub async fn wordfilter(ctx: Context<'_>) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
@@ -31,35 +71,26 @@ pub async fn wordfilter(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let config = get_word_filter_config(&ctx.data().db, guild_id.get()).await?;
 
     let (enabled, action_label) = match &config {
-        Some(cfg) => (cfg.enabled, action_display(&cfg.action)),
-        None => (false, action_display("log_only")),
+        Some(cfg) => (cfg.enabled, filter_action_display(&cfg.action)),
+        None => (false, filter_action_display("log_only")),
     };
+    let ai_check = config.as_ref().is_some_and(|cfg| cfg.ai_check);
 
     let status = if enabled { "Enabled" } else { "Disabled" };
+    let ai_status = if ai_check { "Enabled" } else { "Disabled" };
 
-    let embed = serenity::CreateEmbed::new()
+    let embed = guild_embed(accent_color(ctx).await)
         .title("Word Filter Status")
         .description(format!(
-            "**Status :** {}\n**Action :** {}",
-            status, action_label
+            "**Status :** {}\n**Action :** {}\n**AI Check :** {}",
+            status, action_label, ai_status
         ))
-        .color(DEFAULT_EMBED_COLOR)
         .footer(serenity::CreateEmbedFooter::new(
-            "Subcommands: enable, disable, action, preset, add, remove, list",
+            "Subcommands: enable, disable, action, preset, add, remove, list, exempt, channel, ai, \
+             test, export, import, sync-automod",
         ));
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
@@ -67,6 +98,63 @@ pub async fn wordfilter(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Manage word filter exemptions for roles, channels, and users.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("exempt_add", "exempt_remove", "exempt_list")
+)]
+pub async fn exempt(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.say(
+        "Usage:\n\
+         `!wordfilter exempt add <@role|#channel|@user>` — exempt a target\n\
+         `!wordfilter exempt remove <@role|#channel|@user>` — remove an exemption\n\
+         `!wordfilter exempt list` — list exemptions",
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Exempt a role, channel, or user.
+#[poise::command(prefix_command, slash_command, category = "Moderation", rename = "add")]
+pub async fn exempt_add(
+    ctx: Context<'_>,
+    #[description = "Role, channel, or user to exempt"]
+    #[rest]
+    target: Option<String>,
+) -> Result<(), Error> {
+    exempt_add_for_scope(ctx, "word_filter", "wordfilter", target).await
+}
+
+/// Remove an exemption.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "remove"
+)]
+pub async fn exempt_remove(
+    ctx: Context<'_>,
+    #[description = "Role, channel, or user to un-exempt"]
+    #[rest]
+    target: Option<String>,
+) -> Result<(), Error> {
+    exempt_remove_for_scope(ctx, "word_filter", "wordfilter", target).await
+}
+
+/// List exemptions.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "list"
+)]
+pub async fn exempt_list(ctx: Context<'_>) -> Result<(), Error> {
+    exempt_list_for_scope(ctx, "word_filter", "Word filter Exemptions").await
+}
+
 /// Enable the word filter.
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
 pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
@@ -75,17 +163,6 @@ pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_word_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
     ctx.say("Word filter has been **enabled**.").await?;
 
@@ -100,17 +177,6 @@ pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_word_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
     ctx.say("Word filter has been **disabled**.").await?;
 
@@ -130,52 +196,29 @@ pub async fn action(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say(
-            "Usage: `!wordfilter action <log|delete|warn|timeout>`\n\
-             • `log` — Only log the violation\n\
-             • `delete` — Delete message and log\n\
-             • `warn` — Warn user, delete message, and log\n\
-             • `timeout` — Timeout user, delete message, and log",
-        )
+        ctx.say(format!(
+            "Usage: `!wordfilter action <log|delete|warn|timeout>`\n{}",
+            FILTER_ACTION_HELP
+        ))
         .await?;
         return Ok(());
     };
 
-    let action_str = match raw.to_lowercase().as_str() {
-        "log" => "log_only",
-        "delete" => "delete_and_log",
-        "warn" => "warn_and_log",
-        "timeout" => "timeout_delete_and_log",
-        _ => {
-            ctx.say(
-                "Invalid action. Use one of: `log`, `delete`, `warn`, `timeout`.\n\n\
-                 • `log` — Only log the violation\n\
-                 • `delete` — Delete message and log\n\
-                 • `warn` — Warn user, delete message, and log\n\
-                 • `timeout` — Timeout user, delete message, and log",
-            )
-            .await?;
-            return Ok(());
-        }
+    let Some(action_str) = parse_filter_action(raw) else {
+        ctx.say(format!(
+            "Invalid action. Use one of: `log`, `delete`, `warn`, `timeout`.\n\n{}",
+            FILTER_ACTION_HELP
+        ))
+        .await?;
+        return Ok(());
     };
 
     set_word_filter_action(&ctx.data().db, guild_id.get(), action_str).await?;
 
     ctx.say(format!(
         "Word filter action set to **{}**.",
-        action_display(action_str)
+        filter_action_display(action_str)
     ))
     .await?;
 
@@ -195,17 +238,6 @@ pub async fn preset(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
         ctx.say("Usage: `!wordfilter preset <load|clear>`").await?;
         return Ok(());
@@ -241,7 +273,7 @@ pub async fn preset(
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
 pub async fn add(
     ctx: Context<'_>,
-    #[description = "Word to add to the filter"]
+    #[description = "Word to add, optionally followed by --mode contains|startswith"]
     #[rest]
     word: Option<String>,
 ) -> Result<(), Error> {
@@ -250,28 +282,28 @@ pub async fn add(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
+    let usage = "Usage: `!wordfilter add <word> [--mode whole|contains|startswith]`";
 
     let Some(raw) = word.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say("Usage: `!wordfilter add <word>`").await?;
+        ctx.say(usage).await?;
         return Ok(());
     };
 
-    let word = raw.to_lowercase();
-    let inserted = add_filter_word(&ctx.data().db, guild_id.get(), &word, false).await?;
+    let Some((word, match_mode)) = parse_word_and_mode(raw) else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    let inserted =
+        add_filter_word(&ctx.data().db, guild_id.get(), &word, false, &match_mode).await?;
 
     if inserted {
-        ctx.say(format!("Added `{}` to the word filter list.", word))
-            .await?;
+        ctx.say(format!(
+            "Added `{}` to the word filter list ({}).",
+            word,
+            match_mode_display(&match_mode)
+        ))
+        .await?;
     } else {
         ctx.say(format!("`{}` is already in the word filter list.", word))
             .await?;
@@ -285,6 +317,7 @@ pub async fn add(
 pub async fn remove(
     ctx: Context<'_>,
     #[description = "Word to remove from the filter"]
+    #[autocomplete = "autocomplete_filter_word"]
     #[rest]
     word: Option<String>,
 ) -> Result<(), Error> {
@@ -293,17 +326,6 @@ pub async fn remove(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = word.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
         ctx.say("Usage: `!wordfilter remove <word>`").await?;
         return Ok(());
@@ -323,6 +345,51 @@ pub async fn remove(
     Ok(())
 }
 
+/// Suggest words from this server's filter list as a moderator types.
+/// Autocomplete runs without the command's checks, so the caller's
+/// permission is checked here.
+async fn autocomplete_filter_word(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+
+    let can_manage =
+        match has_command_permission(ctx, guild_id, serenity::Permissions::MANAGE_GUILD).await {
+            Ok(can_manage) => can_manage,
+            Err(source) => {
+                error!(
+                    ?source,
+                    "failed to check word filter autocomplete permission"
+                );
+                false
+            }
+        };
+    let patterns = if can_manage {
+        get_all_filter_words_for_guild(&ctx.data().db, guild_id.get())
+            .await
+            .unwrap_or_else(|source| {
+                error!(?source, "failed to load word filter words for autocomplete");
+                Vec::new()
+            })
+    } else {
+        Vec::new()
+    };
+
+    filter_word_suggestions(can_manage, &patterns, partial)
+}
+
+/// Autocomplete suggestions, none for callers who can't manage the filter.
+fn filter_word_suggestions(
+    can_manage: bool,
+    patterns: &[WordFilterPattern],
+    partial: &str,
+) -> Vec<String> {
+    if !can_manage {
+        return Vec::new();
+    }
+    suggest_filter_words(patterns, partial, AUTOCOMPLETE_LIMIT)
+}
+
 /// List all filtered words for this server.
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
 pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
@@ -331,17 +398,6 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let words = list_filter_words(&ctx.data().db, guild_id.get()).await?;
 
     if words.is_empty() {
@@ -354,7 +410,17 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         .enumerate()
         .map(|(i, w)| {
             let tag = if w.is_preset { " (preset)" } else { "" };
-            format!("{}. `{}`{}", i + 1, w.word, tag)
+            let synced = if w.synced_to_automod {
+                " (AutoMod)"
+            } else {
+                ""
+            };
+            let mode = if w.match_mode == "whole" {
+                String::new()
+            } else {
+                format!(" [{}]", match_mode_display(&w.match_mode))
+            };
+            format!("{}. `{}`{}{}{}", i + 1, w.word, mode, tag, synced)
         })
         .collect();
 
@@ -364,17 +430,632 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         .map(|chunk| chunk.join("\n"))
         .collect();
 
-    paginate_embed_pages(ctx, "Word Filter List", &pages, 1).await?;
+    paginate_embed_pages_with_options(
+        ctx,
+        "Word Filter List",
+        &pages,
+        1,
+        pagination_options(ctx).await,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Enable, disable, or reset the word filter for a single channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn channel(
+    ctx: Context<'_>,
+    #[description = "Channel and setting: <#channel> <enable|disable|reset>"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+        let overrides = list_word_filter_channel_overrides(&ctx.data().db, guild_id.get()).await?;
+
+        let description = if overrides.is_empty() {
+            "No channel overrides. The server-wide setting applies everywhere.".to_owned()
+        } else {
+            overrides
+                .iter()
+                .map(|entry| {
+                    let state = if entry.enabled { "Enforced" } else { "Allowed" };
+                    format!("<#{}> — {}", entry.channel_id, state)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let embed = guild_embed(accent_color(ctx).await)
+            .title("Word Filter Channel Overrides")
+            .description(description)
+            .footer(serenity::CreateEmbedFooter::new(
+                "Usage: !wordfilter channel <#channel> <enable|disable|reset>",
+            ));
+
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let usage = "Usage: `!wordfilter channel <#channel> <enable|disable|reset>`";
+
+    let mut parts = raw.split_whitespace();
+    let (Some(channel_raw), Some(setting), None) = (parts.next(), parts.next(), parts.next())
+    else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    let Some(channel_id) = parse_channel_id(channel_raw) else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    match setting.to_ascii_lowercase().as_str() {
+        "enable" | "on" => {
+            set_word_filter_channel_override(&ctx.data().db, guild_id.get(), channel_id, true)
+                .await?;
+            ctx.say(format!(
+                "Word filter is now **enforced** in <#{}>.",
+                channel_id
+            ))
+            .await?;
+        }
+        "disable" | "off" => {
+            set_word_filter_channel_override(&ctx.data().db, guild_id.get(), channel_id, false)
+                .await?;
+            ctx.say(format!(
+                "Word filter is now **disabled** in <#{}>.",
+                channel_id
+            ))
+            .await?;
+        }
+        "reset" => {
+            let removed =
+                clear_word_filter_channel_override(&ctx.data().db, guild_id.get(), channel_id)
+                    .await?;
+            if removed {
+                ctx.say(format!(
+                    "<#{}> now follows the server-wide word filter setting.",
+                    channel_id
+                ))
+                .await?;
+            } else {
+                ctx.say(format!("<#{}> has no word filter override.", channel_id))
+                    .await?;
+            }
+        }
+        _ => {
+            ctx.say(usage).await?;
+        }
+    }
 
     Ok(())
 }
 
-fn action_display(action: &str) -> &str {
-    match action {
-        "log_only" => "Only Log",
-        "delete_and_log" => "Delete and Log",
-        "warn_and_log" => "Warn, Delete and Log",
-        "timeout_delete_and_log" => "Timeout, Delete and Log",
-        _ => "Unknown",
+/// Configure the optional LLM check for messages the word list lets through.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn ai(
+    ctx: Context<'_>,
+    #[description = "Setting: on, off, threshold <1-100>, or action <log|delete|warn|timeout>"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let usage =
+        "Usage: `!wordfilter ai <on|off|threshold <1-100>|action <log|delete|warn|timeout>>`";
+
+    let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+        let config = get_word_filter_config(&ctx.data().db, guild_id.get()).await?;
+        let (ai_check, threshold, action) = match &config {
+            Some(cfg) => (cfg.ai_check, cfg.ai_threshold, cfg.ai_action.as_str()),
+            None => (false, DEFAULT_AI_THRESHOLD, "log_only"),
+        };
+
+        let mut description = format!(
+            "**Status :** {}\n**Threshold :** {}% confidence\n**Action :** {}",
+            if ai_check { "Enabled" } else { "Disabled" },
+            threshold,
+            filter_action_display(action)
+        );
+        if ctx.data().llm.is_none() {
+            description.push_str("\n\nNo LLM backend is configured, so the check will not run.");
+        }
+
+        let embed = guild_embed(accent_color(ctx).await)
+            .title("Word Filter AI Check")
+            .description(description)
+            .footer(serenity::CreateEmbedFooter::new(
+                "Usage: !wordfilter ai <on|off|threshold <1-100>|action <action>>",
+            ));
+
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let (setting, value) = match raw.split_once(char::is_whitespace) {
+        Some((setting, value)) => (setting, Some(value.trim())),
+        None => (raw, None),
+    };
+
+    match (setting.to_ascii_lowercase().as_str(), value) {
+        ("on" | "enable", None) => {
+            set_word_filter_ai_check(&ctx.data().db, guild_id.get(), true).await?;
+            let mut reply =
+                "Word filter AI check **enabled**. Messages that pass the word list are now \
+                 classified by the LLM."
+                    .to_owned();
+            if ctx.data().llm.is_none() {
+                reply.push_str(" No LLM backend is configured yet, so nothing will be checked.");
+            }
+            ctx.say(reply).await?;
+        }
+        ("off" | "disable", None) => {
+            set_word_filter_ai_check(&ctx.data().db, guild_id.get(), false).await?;
+            ctx.say("Word filter AI check **disabled**.").await?;
+        }
+        ("threshold", Some(value)) => {
+            let Some(threshold) = value
+                .trim_end_matches('%')
+                .parse::<u8>()
+                .ok()
+                .filter(|threshold| (1..=100).contains(threshold))
+            else {
+                ctx.say("Threshold must be a whole number from 1 to 100.")
+                    .await?;
+                return Ok(());
+            };
+
+            set_word_filter_ai_threshold(&ctx.data().db, guild_id.get(), threshold).await?;
+            ctx.say(format!(
+                "The AI check now acts at **{}%** confidence or higher.",
+                threshold
+            ))
+            .await?;
+        }
+        ("action", Some(value)) => {
+            let Some(action_str) = parse_filter_action(value) else {
+                ctx.say(format!(
+                    "Invalid action. Use one of: `log`, `delete`, `warn`, `timeout`.\n\n{}",
+                    FILTER_ACTION_HELP
+                ))
+                .await?;
+                return Ok(());
+            };
+
+            set_word_filter_ai_action(&ctx.data().db, guild_id.get(), action_str).await?;
+            ctx.say(format!(
+                "Word filter AI action set to **{}**.",
+                filter_action_display(action_str)
+            ))
+            .await?;
+        }
+        _ => {
+            ctx.say(usage).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run text through the word filter without taking any action.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn test(
+    ctx: Context<'_>,
+    #[description = "Text to check against the word filter"]
+    #[rest]
+    message: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(text) = message.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+        ctx.say("Usage: `!wordfilter test <message>`").await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let channel_config =
+        get_word_filter_for_channel(db, guild_id.get(), ctx.channel_id().get()).await?;
+    let action = match &channel_config {
+        Some(cfg) => cfg.action.clone(),
+        None => get_word_filter_config(db, guild_id.get())
+            .await?
+            .map(|cfg| cfg.action)
+            .unwrap_or_else(|| "log_only".to_owned()),
+    };
+
+    let patterns = get_all_filter_words_for_guild(db, guild_id.get()).await?;
+    let matched = find_matching_pattern(text, &patterns);
+
+    let status = if channel_config.is_some() {
+        "Enabled in this channel"
+    } else {
+        "Disabled in this channel"
+    };
+
+    let mut fields = vec![format!("**Status :** {}", status)];
+    match matched {
+        Some(pattern) => {
+            fields.push(format!(
+                "**Match :** `{}` ({})",
+                pattern.word,
+                match_mode_display(&pattern.match_mode)
+            ));
+            fields.push(format!("**Action :** {}", filter_action_display(&action)));
+        }
+        None => fields.push("**Match :** None".to_owned()),
+    }
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Word Filter Test")
+        .description(fields.join("\n"))
+        .footer(serenity::CreateEmbedFooter::new(
+            "No action was taken. Exemptions are not applied to tests.",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Largest import file accepted, in bytes.
+const MAX_IMPORT_BYTES: u32 = 1024 * 1024;
+
+/// Export the word list as a text or JSON attachment.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "Format: text or json"] format: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let format = format
+        .as_deref()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_else(|| "text".to_owned());
+
+    let words = list_filter_words(&ctx.data().db, guild_id.get()).await?;
+    if words.is_empty() {
+        ctx.say("The word filter list is empty.").await?;
+        return Ok(());
+    }
+
+    let patterns: Vec<WordFilterPattern> = words
+        .into_iter()
+        .map(|w| WordFilterPattern {
+            word: w.word,
+            match_mode: w.match_mode,
+        })
+        .collect();
+
+    let (bytes, filename) = match format.as_str() {
+        "json" => (serde_json::to_vec_pretty(&patterns)?, "wordfilter.json"),
+        "text" | "txt" => {
+            let lines: Vec<String> = patterns
+                .iter()
+                .map(|p| {
+                    if p.match_mode == "whole" {
+                        p.word.clone()
+                    } else {
+                        format!("{} --mode {}", p.word, p.match_mode)
+                    }
+                })
+                .collect();
+            (lines.join("\n").into_bytes(), "wordfilter.txt")
+        }
+        _ => {
+            ctx.say("Usage: `!wordfilter export [text|json]`").await?;
+            return Ok(());
+        }
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("Exported **{}** word(s).", patterns.len()))
+            .attachment(serenity::CreateAttachment::bytes(bytes, filename)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Import words from an attached text or JSON file.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn import(
+    ctx: Context<'_>,
+    #[description = "Text (one word per line) or JSON export file"] file: Option<
+        serenity::Attachment,
+    >,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(file) = file else {
+        ctx.say("Usage: `!wordfilter import` with a `.txt` or `.json` file attached.")
+            .await?;
+        return Ok(());
+    };
+
+    if file.size > MAX_IMPORT_BYTES {
+        ctx.say("That file is too large to import (max 1 MB).")
+            .await?;
+        return Ok(());
+    }
+
+    let bytes = file.download().await?;
+    let Ok(content) = String::from_utf8(bytes) else {
+        ctx.say("That file is not valid UTF-8 text.").await?;
+        return Ok(());
+    };
+
+    let Some(patterns) = parse_import(&content) else {
+        ctx.say(
+            "Could not parse that file. Use one word per line \
+             (optionally `--mode <mode>`) or a JSON export.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if patterns.is_empty() {
+        ctx.say("No words found in that file.").await?;
+        return Ok(());
+    }
+
+    let inserted = import_filter_words(&ctx.data().db, guild_id.get(), &patterns).await?;
+    let skipped = patterns.len() as u64 - inserted;
+
+    ctx.say(format!(
+        "Imported **{}** word(s) ({} already present).",
+        inserted, skipped
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Push the word list into a Discord AutoMod rule to block messages before posting.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    rename = "sync-automod"
+)]
+pub async fn sync_automod(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let patterns = get_all_filter_words_for_guild(db, guild_id.get()).await?;
+
+    let mut synced_words = Vec::new();
+    let mut keywords = Vec::new();
+    for pattern in &patterns {
+        if keywords.len() >= AUTOMOD_MAX_KEYWORDS {
+            break;
+        }
+        if let Some(keyword) = automod_keyword(pattern) {
+            keywords.push(keyword);
+            synced_words.push(pattern.word.clone());
+        }
+    }
+
+    if keywords.is_empty() {
+        ctx.say("There are no words that can be synced to AutoMod.")
+            .await?;
+        return Ok(());
+    }
+
+    // Mirror role and channel exemptions; Discord caps these at 20 and 50.
+    let exemptions = list_exemptions(db, guild_id.get(), "word_filter").await?;
+    let exempt_roles: Vec<serenity::RoleId> = exemptions
+        .iter()
+        .filter(|e| e.target_kind == "role")
+        .take(20)
+        .map(|e| serenity::RoleId::new(e.target_id))
+        .collect();
+    let exempt_channels: Vec<serenity::ChannelId> = exemptions
+        .iter()
+        .filter(|e| e.target_kind == "channel")
+        .take(50)
+        .map(|e| serenity::ChannelId::new(e.target_id))
+        .collect();
+
+    let reason = format!("Word filter sync by {}", ctx.author().name);
+    let rule = || {
+        serenity::EditAutoModRule::new()
+            .name(AUTOMOD_RULE_NAME)
+            .event_type(serenity::automod::EventType::MessageSend)
+            .trigger(serenity::automod::Trigger::Keyword {
+                strings: keywords.clone(),
+                regex_patterns: Vec::new(),
+                allow_list: Vec::new(),
+            })
+            .actions(vec![serenity::automod::Action::BlockMessage {
+                custom_message: Some("Your message contains a filtered word.".to_owned()),
+            }])
+            .exempt_roles(exempt_roles.clone())
+            .exempt_channels(exempt_channels.clone())
+            .enabled(true)
+            .audit_log_reason(&reason)
+    };
+
+    let existing_rule_id = get_word_filter_automod_rule_id(db, guild_id.get()).await?;
+    let result = match existing_rule_id {
+        Some(rule_id) => match guild_id
+            .edit_automod_rule(ctx.http(), serenity::RuleId::new(rule_id), rule())
+            .await
+        {
+            // The rule was deleted in Discord; create a new one.
+            Err(source) if is_unknown_automod_rule(&source) => {
+                guild_id.create_automod_rule(ctx.http(), rule()).await
+            }
+            other => other,
+        },
+        None => guild_id.create_automod_rule(ctx.http(), rule()).await,
+    };
+
+    let rule = match result {
+        Ok(rule) => rule,
+        Err(_) => {
+            ctx.say(
+                "I couldn't update the AutoMod rule. Please check my **Manage Server** \
+                 permission and that the server has a free keyword rule slot.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    record_word_filter_automod_sync(db, guild_id.get(), rule.id.get(), &synced_words).await?;
+
+    let skipped = patterns.len() - synced_words.len();
+    let mut reply = format!(
+        "Synced **{}** word(s) to the AutoMod rule **{}**.",
+        synced_words.len(),
+        AUTOMOD_RULE_NAME
+    );
+    if skipped > 0 {
+        reply.push_str(&format!(
+            "\n{} word(s) were skipped (over 60 characters, containing `*`, or past the \
+             {} keyword limit).",
+            skipped, AUTOMOD_MAX_KEYWORDS
+        ));
+    }
+    ctx.say(reply).await?;
+
+    Ok(())
+}
+
+const AUTOMOD_RULE_NAME: &str = "Autumn Word Filter";
+
+fn is_unknown_automod_rule(source: &serenity::Error) -> bool {
+    matches!(
+        source,
+        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
+            if response.status_code.as_u16() == 404 || response.error.code == 10066
+    )
+}
+
+/// Parse an import file into deduplicated patterns. JSON exports are detected
+/// by a leading `[`; otherwise each non-empty, non-`#` line is parsed like
+/// `!wordfilter add`.
+fn parse_import(content: &str) -> Option<Vec<WordFilterPattern>> {
+    let parsed: Vec<WordFilterPattern> = if content.trim_start().starts_with('[') {
+        let entries: Vec<WordFilterPattern> = serde_json::from_str(content).ok()?;
+        entries
+            .into_iter()
+            .filter(|p| MATCH_MODES.contains(&p.match_mode.as_str()))
+            .map(|p| WordFilterPattern {
+                word: p.word.trim().to_lowercase(),
+                match_mode: p.match_mode,
+            })
+            .filter(|p| !p.word.is_empty())
+            .collect()
+    } else {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                parse_word_and_mode(line)
+                    .map(|(word, match_mode)| WordFilterPattern { word, match_mode })
+            })
+            .collect::<Option<Vec<_>>>()?
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    Some(
+        parsed
+            .into_iter()
+            .filter(|p| seen.insert(p.word.clone()))
+            .collect(),
+    )
+}
+
+fn parse_channel_id(raw: &str) -> Option<u64> {
+    raw.strip_prefix("<#")
+        .and_then(|value| value.strip_suffix('>'))
+        .unwrap_or(raw)
+        .parse::<u64>()
+        .ok()
+}
+
+/// Split `<word> [--mode <mode>]` into a lowercased word and a validated match mode.
+fn parse_word_and_mode(raw: &str) -> Option<(String, String)> {
+    let mut word = None;
+    let mut match_mode = "whole".to_owned();
+
+    let mut parts = raw.split_whitespace();
+    while let Some(part) = parts.next() {
+        if part.eq_ignore_ascii_case("--mode") {
+            let mode = parts.next()?.to_ascii_lowercase();
+            if !MATCH_MODES.contains(&mode.as_str()) {
+                return None;
+            }
+            match_mode = mode;
+        } else if word.is_none() {
+            word = Some(part.to_lowercase());
+        } else {
+            return None;
+        }
+    }
+
+    word.map(|word| (word, match_mode))
+}
+
+fn match_mode_display(match_mode: &str) -> &str {
+    match match_mode {
+        "contains" => "contains",
+        "startswith" => "starts with",
+        _ => "whole word",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns() -> Vec<WordFilterPattern> {
+        ["scam", "scammer", "spam"]
+            .into_iter()
+            .map(|word| WordFilterPattern {
+                word: word.to_owned(),
+                match_mode: "whole".to_owned(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn managers_get_suggestions() {
+        assert_eq!(
+            filter_word_suggestions(true, &patterns(), "sca"),
+            ["scam", "scammer"]
+        );
+    }
+
+    #[test]
+    fn callers_without_permission_get_no_suggestions() {
+        assert!(filter_word_suggestions(false, &patterns(), "sca").is_empty());
+        assert!(filter_word_suggestions(false, &patterns(), "").is_empty());
     }
 }
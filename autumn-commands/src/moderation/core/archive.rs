@@ -0,0 +1,294 @@
+use tracing::error;
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::checks::require_manage_channels;
+use crate::moderation::embeds::{guild_only_message, is_missing_permissions_error, usage_message};
+use autumn_core::{Context, Error};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "archive",
+    desc: "Export a channel's history to a JSON transcript, then optionally lock or delete it.",
+    category: "moderation",
+    usage: "!archive [#channel] [lock|delete]",
+    required_permissions: Some(serenity::Permissions::MANAGE_CHANNELS),
+    examples: &["!archive", "!archive #old-events lock"],
+    related: &["purge"],
+};
+
+const MAX_ARCHIVE_MESSAGES: usize = 50_000;
+const PROGRESS_EVERY: usize = 1_000;
+/// Stay under Discord's default 10 MiB upload limit.
+const MAX_ARCHIVE_FILE_BYTES: usize = 8 * 1024 * 1024;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_channels"
+)]
+pub async fn archive(
+    ctx: Context<'_>,
+    #[description = "Channel to archive (defaults to this one)"] channel: Option<
+        serenity::GuildChannel,
+    >,
+    #[description = "Afterwards: lock or delete the channel"] then: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let after_export = match then.as_deref().map(str::trim) {
+        None | Some("") => AfterExport::Keep,
+        Some(value) if value.eq_ignore_ascii_case("lock") => AfterExport::Lock,
+        Some(value) if value.eq_ignore_ascii_case("delete") => AfterExport::Delete,
+        Some(_) => {
+            ctx.say(usage_message(META.usage)).await?;
+            return Ok(());
+        }
+    };
+
+    let channel = match channel {
+        Some(channel) => channel,
+        None => match ctx.channel_id().to_channel(ctx.http()).await?.guild() {
+            Some(channel) => channel,
+            None => {
+                ctx.say(guild_only_message()).await?;
+                return Ok(());
+            }
+        },
+    };
+
+    if channel.guild_id != guild_id {
+        ctx.say("That channel is not in this server.").await?;
+        return Ok(());
+    }
+
+    // The transcript is posted where the command ran, so that channel must survive.
+    if after_export == AfterExport::Delete && channel.id == ctx.channel_id() {
+        ctx.say("Run this from another channel to delete the archived one.")
+            .await?;
+        return Ok(());
+    }
+
+    let progress = ctx
+        .say(format!("Archiving <#{}>…", channel.id.get()))
+        .await?;
+
+    let messages = match export_channel_history(ctx, &channel, &progress).await {
+        Ok(messages) => messages,
+        Err(source) => {
+            if !is_missing_permissions_error(&source) {
+                error!(?source, "archive history export failed");
+            }
+            progress
+                .edit(
+                    ctx,
+                    poise::CreateReply::default()
+                        .content("I couldn't read that channel's history."),
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let message_count = messages.len();
+    let parts = split_archive(&channel, messages)?;
+    let part_count = parts.len();
+
+    for (index, part) in parts.into_iter().enumerate() {
+        let filename = if part_count == 1 {
+            format!("archive-{}.json", channel.id.get())
+        } else {
+            format!("archive-{}-part{}.json", channel.id.get(), index + 1)
+        };
+        ctx.send(
+            poise::CreateReply::default()
+                .attachment(serenity::CreateAttachment::bytes(part, filename)),
+        )
+        .await?;
+    }
+
+    let mut channel_display = format!("<#{}>", channel.id.get());
+    let outcome = match after_export {
+        AfterExport::Keep => "",
+        AfterExport::Lock => match lock_channel(ctx, guild_id, &channel).await {
+            Ok(()) => " Channel locked.",
+            Err(source) => {
+                if !is_missing_permissions_error(&source) {
+                    error!(?source, "archive lock failed");
+                }
+                " I couldn't lock the channel."
+            }
+        },
+        AfterExport::Delete => match channel.delete(ctx.http()).await {
+            Ok(_) => {
+                // The mention no longer resolves once the channel is gone.
+                channel_display = format!("`#{}`", channel.name);
+                " Channel deleted."
+            }
+            Err(source) => {
+                if !is_missing_permissions_error(&source) {
+                    error!(?source, "archive delete failed");
+                }
+                " I couldn't delete the channel."
+            }
+        },
+    };
+
+    let truncated_note = if message_count >= MAX_ARCHIVE_MESSAGES {
+        format!(" (stopped at the {} most recent)", MAX_ARCHIVE_MESSAGES)
+    } else {
+        String::new()
+    };
+    let summary = format!(
+        "Archived **{}** message(s) from {}{}.{}",
+        message_count, channel_display, truncated_note, outcome
+    );
+
+    progress
+        .edit(ctx, poise::CreateReply::default().content(summary))
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AfterExport {
+    Keep,
+    Lock,
+    Delete,
+}
+
+/// Page backwards through the whole channel, editing `progress` as it goes.
+/// Returns messages oldest first.
+async fn export_channel_history(
+    ctx: Context<'_>,
+    channel: &serenity::GuildChannel,
+    progress: &poise::ReplyHandle<'_>,
+) -> Result<Vec<serde_json::Value>, serenity::Error> {
+    let mut messages = Vec::new();
+    let mut before: Option<serenity::MessageId> = None;
+    let mut next_progress = PROGRESS_EVERY;
+
+    while messages.len() < MAX_ARCHIVE_MESSAGES {
+        let mut request = serenity::GetMessages::new().limit(100);
+        if let Some(before) = before {
+            request = request.before(before);
+        }
+
+        let batch = channel.id.messages(ctx.http(), request).await?;
+        if batch.is_empty() {
+            break;
+        }
+
+        before = batch.last().map(|message| message.id);
+        let batch_len = batch.len();
+        messages.extend(batch.iter().map(archived_message));
+
+        if messages.len() >= next_progress {
+            next_progress += PROGRESS_EVERY;
+            let _ = progress
+                .edit(
+                    ctx,
+                    poise::CreateReply::default().content(format!(
+                        "Archiving <#{}>… {} message(s) so far.",
+                        channel.id.get(),
+                        messages.len()
+                    )),
+                )
+                .await;
+        }
+
+        if batch_len < 100 {
+            break;
+        }
+    }
+
+    messages.truncate(MAX_ARCHIVE_MESSAGES);
+    messages.reverse();
+    Ok(messages)
+}
+
+fn archived_message(message: &serenity::Message) -> serde_json::Value {
+    serde_json::json!({
+        "id": message.id.get().to_string(),
+        "author_id": message.author.id.get().to_string(),
+        "author": message.author.tag(),
+        "bot": message.author.bot,
+        "timestamp": message.timestamp.to_string(),
+        "edited_timestamp": message.edited_timestamp.map(|timestamp| timestamp.to_string()),
+        "content": message.content,
+        "attachments": message
+            .attachments
+            .iter()
+            .map(|attachment| serde_json::json!({
+                "filename": attachment.filename,
+                "url": attachment.url,
+            }))
+            .collect::<Vec<_>>(),
+        "embeds": message.embeds.len(),
+        "reply_to": message
+            .message_reference
+            .as_ref()
+            .and_then(|reference| reference.message_id)
+            .map(|id| id.get().to_string()),
+    })
+}
+
+/// Serialize the archive into one or more JSON files under the upload limit.
+fn split_archive(
+    channel: &serenity::GuildChannel,
+    messages: Vec<serde_json::Value>,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut parts: Vec<Vec<serde_json::Value>> = vec![Vec::new()];
+    let mut part_bytes = 0;
+
+    for message in messages {
+        let message_bytes = serde_json::to_vec(&message)?.len() + 1;
+        if part_bytes + message_bytes > MAX_ARCHIVE_FILE_BYTES
+            && parts.last().is_some_and(|part| !part.is_empty())
+        {
+            parts.push(Vec::new());
+            part_bytes = 0;
+        }
+        part_bytes += message_bytes;
+        if let Some(part) = parts.last_mut() {
+            part.push(message);
+        }
+    }
+
+    parts
+        .into_iter()
+        .map(|part| {
+            Ok(serde_json::to_vec_pretty(&serde_json::json!({
+                "guild_id": channel.guild_id.get().to_string(),
+                "channel_id": channel.id.get().to_string(),
+                "channel_name": channel.name,
+                "messages": part,
+            }))?)
+        })
+        .collect()
+}
+
+/// Deny @everyone from sending messages in the channel.
+async fn lock_channel(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    channel: &serenity::GuildChannel,
+) -> Result<(), serenity::Error> {
+    channel
+        .create_permission(
+            ctx.http(),
+            serenity::PermissionOverwrite {
+                allow: serenity::Permissions::empty(),
+                deny: serenity::Permissions::SEND_MESSAGES
+                    | serenity::Permissions::SEND_MESSAGES_IN_THREADS
+                    | serenity::Permissions::ADD_REACTIONS,
+                kind: serenity::PermissionOverwriteType::Role(guild_id.everyone_role()),
+            },
+        )
+        .await
+}
@@ -3,6 +3,11 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{bot_outranks_target, moderator_outranks_target, require_ban_members};
+use crate::moderation::approval::{ApprovalRequest, finish_approval, request_approval};
+use crate::moderation::ban_sync::mirror_ban;
+use crate::moderation::deferred::schedule_deferred_action;
 use crate::moderation::embeds::{
     guild_only_message, is_missing_permissions_error, moderation_action_embed,
     moderation_bot_target_message, send_moderation_target_dm_for_guild, target_profile_from_user,
@@ -11,20 +16,38 @@ use crate::moderation::embeds::{
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::impls::moderation_settings::get_dual_approval;
+use autumn_database::impls::scheduled_jobs::{
+    JOB_BAN_EXPIRY, NewScheduledJob, cancel_jobs_for_target, schedule_job,
+};
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::split_leading_duration;
+use autumn_utils::time::{now_unix_secs, split_deferred_start};
 
 pub const META: CommandMeta = CommandMeta {
     name: "ban",
-    desc: "Ban a user from the server.",
+    desc: "Ban a user from the server, optionally for a limited time.",
     category: "moderation",
-    usage: "!ban <user> [reason]",
+    usage: "!ban <user> [--at HH:MM | in <delay>] [duration] [reason]",
+    required_permissions: Some(serenity::Permissions::BAN_MEMBERS),
+    examples: &[
+        "!ban @user spamming",
+        "!ban @user 7d raiding",
+        "!ban @user in 2h ban evasion",
+    ],
+    related: &["unban", "kick", "terminate"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_ban_members"
+)]
 pub async fn ban(
     ctx: Context<'_>,
     #[description = "The user to ban"] user: Option<serenity::User>,
-    #[description = "Reason for the ban"]
+    #[description = "Optional start (--at 22:00, in 2h) and duration (7d), then the reason"]
     #[rest]
     reason: Option<String>,
 ) -> Result<(), Error> {
@@ -33,17 +56,6 @@ pub async fn ban(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::BAN_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -59,55 +71,183 @@ pub async fn ban(
         return Ok(());
     }
 
-    let ban_result = guild_id
-        .ban_with_reason(
-            ctx.http(),
-            user.id,
-            0,
+    if !moderator_outranks_target(ctx, guild_id, user.id, "ban").await? {
+        return Ok(());
+    }
+
+    if !bot_outranks_target(ctx, guild_id, user.id, "ban").await? {
+        return Ok(());
+    }
+
+    let (run_at, reason) = match reason
+        .as_deref()
+        .and_then(|raw| split_deferred_start(raw, now_unix_secs()))
+    {
+        Some((run_at, rest)) => (Some(run_at), rest),
+        None => (None, reason),
+    };
+    let (duration_seconds, reason) = match reason.as_deref() {
+        Some(raw) => split_leading_duration(raw),
+        None => (None, None),
+    };
+    let duration_label = duration_seconds.map(format_compact_duration);
+
+    let dual_approval = get_dual_approval(&ctx.data().db, guild_id.get()).await?;
+
+    if let Some(run_at) = run_at {
+        if dual_approval {
+            ctx.say("Scheduled bans aren't available while dual approval is on.")
+                .await?;
+            return Ok(());
+        }
+        return schedule_deferred_action(
+            ctx,
+            guild_id,
+            &user,
+            "ban",
             reason.as_deref().unwrap_or("No reason provided"),
+            duration_seconds,
+            run_at,
         )
         .await;
+    }
+
+    let case_reason = reason.as_deref().unwrap_or("No reason provided").to_owned();
+
+    let approval = if dual_approval {
+        let request = ApprovalRequest {
+            user: &user,
+            action: "ban",
+            reason: &case_reason,
+            duration_seconds,
+            required: serenity::Permissions::BAN_MEMBERS,
+            attachment: None,
+        };
+        match request_approval(ctx, guild_id, request).await? {
+            Some(approval) => Some(approval),
+            None => return Ok(()),
+        }
+    } else {
+        None
+    };
+
+    let ban_result = guild_id
+        .ban_with_reason(ctx.http(), user.id, 0, &case_reason)
+        .await;
 
     if let Err(source) = ban_result {
         if !is_missing_permissions_error(&source) {
             error!(?source, "ban request failed");
         }
+        if let Some(approval) = &approval {
+            finish_approval(ctx, guild_id, approval, false).await;
+        }
         ctx.say("I couldn't ban that user. Check role hierarchy and permissions.")
             .await?;
         return Ok(());
     }
 
-    let case_reason = reason.as_deref().unwrap_or("No reason provided").to_owned();
-
     let _ = send_moderation_target_dm_for_guild(
         ctx.http(),
         &user,
         guild_id,
         "banned",
         Some(&case_reason),
-        None,
+        duration_label.as_deref(),
+        accent_color(ctx).await,
     )
     .await;
 
-    let _case_label = create_case_and_publish(
-        &ctx,
-        guild_id,
-        NewCase {
-            guild_id: guild_id.get(),
-            target_user_id: Some(user.id.get()),
-            moderator_user_id: ctx.author().id.get(),
-            action: "ban",
-            reason: &case_reason,
-            status: "active",
-            duration_seconds: None,
-        },
+    let case_label = match &approval {
+        Some(approval) => {
+            finish_approval(ctx, guild_id, approval, true).await;
+            Some(approval.case_label.clone())
+        }
+        None => {
+            create_case_and_publish(
+                &ctx,
+                guild_id,
+                NewCase {
+                    guild_id: guild_id.get(),
+                    target_user_id: Some(user.id.get()),
+                    moderator_user_id: ctx.author().id.get(),
+                    action: "ban",
+                    reason: &case_reason,
+                    status: "active",
+                    duration_seconds,
+                },
+            )
+            .await
+        }
+    };
+
+    // A new ban replaces the expiry of any earlier tempban.
+    if let Err(source) = cancel_jobs_for_target(
+        &ctx.data().db,
+        guild_id.get(),
+        JOB_BAN_EXPIRY,
+        user.id.get(),
     )
-    .await;
+    .await
+    {
+        error!(?source, "failed to cancel earlier tempban expiry");
+    }
+
+    if let Some(duration_seconds) = duration_seconds {
+        let scheduled = schedule_job(
+            &ctx.data().db,
+            NewScheduledJob {
+                guild_id: guild_id.get(),
+                kind: JOB_BAN_EXPIRY,
+                target_user_id: Some(user.id.get()),
+                channel_id: None,
+                payload: case_label.as_deref().unwrap_or_default(),
+                run_at: now_unix_secs().saturating_add(duration_seconds),
+                created_by: ctx.author().id.get(),
+            },
+        )
+        .await;
+
+        if let Err(source) = scheduled {
+            error!(?source, "failed to schedule tempban expiry");
+            ctx.say("The ban was applied, but I couldn't schedule its expiry. Unban manually.")
+                .await?;
+        }
+    }
+
+    // Temporary bans stay local; partners only mirror permanent ones.
+    let mirrored = if duration_seconds.is_none() {
+        mirror_ban(
+            ctx.http(),
+            &ctx.data().db,
+            guild_id,
+            user.id,
+            &case_reason,
+            ctx.framework().bot_id.get(),
+        )
+        .await
+    } else {
+        0
+    };
 
     let target_profile = target_profile_from_user(&user);
-    let embed =
-        moderation_action_embed(&target_profile, user.id, "banned", reason.as_deref(), None);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        "banned",
+        reason.as_deref(),
+        duration_label.as_deref(),
+        accent_color(ctx).await,
+    );
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
+    if mirrored > 0 {
+        ctx.say(format!(
+            "Ban sync: mirrored to {} partner server(s). Undo with `!bansync undo {}`.",
+            mirrored, user.id
+        ))
+        .await?;
+    }
+
     Ok(())
 }
@@ -3,6 +3,8 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{bot_outranks_target, moderator_outranks_target, require_kick_members};
 use crate::moderation::embeds::{
     guild_only_message, is_missing_permissions_error, moderation_action_embed,
     moderation_bot_target_message, send_moderation_target_dm_for_guild, target_profile_from_user,
@@ -11,16 +13,23 @@ use crate::moderation::embeds::{
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "kick",
     desc: "Kick a user from the server.",
     category: "moderation",
     usage: "!kick <user> [reason]",
+    required_permissions: Some(serenity::Permissions::KICK_MEMBERS),
+    examples: &["!kick @user spamming"],
+    related: &["ban", "timeout"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_kick_members"
+)]
 pub async fn kick(
     ctx: Context<'_>,
     #[description = "The user to kick"] user: Option<serenity::User>,
@@ -33,17 +42,6 @@ pub async fn kick(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::KICK_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -59,6 +57,14 @@ pub async fn kick(
         return Ok(());
     }
 
+    if !moderator_outranks_target(ctx, guild_id, user.id, "kick").await? {
+        return Ok(());
+    }
+
+    if !bot_outranks_target(ctx, guild_id, user.id, "kick").await? {
+        return Ok(());
+    }
+
     let kick_result = guild_id
         .kick_with_reason(
             ctx.http(),
@@ -85,6 +91,7 @@ pub async fn kick(
         "kicked",
         Some(&case_reason),
         None,
+        accent_color(ctx).await,
     )
     .await;
 
@@ -104,8 +111,14 @@ pub async fn kick(
     .await;
 
     let target_profile = target_profile_from_user(&user);
-    let embed =
-        moderation_action_embed(&target_profile, user.id, "kicked", reason.as_deref(), None);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        "kicked",
+        reason.as_deref(),
+        None,
+        accent_color(ctx).await,
+    );
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
     Ok(())
@@ -1,6 +1,10 @@
+pub mod archive;
 pub mod ban;
 pub mod kick;
 pub mod purge;
+pub mod quarantine;
+pub mod shadowmute;
+pub mod temprole;
 pub mod terminate;
 pub mod timeout;
 pub mod warn;
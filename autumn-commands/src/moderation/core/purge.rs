@@ -4,26 +4,142 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::checks::require_manage_messages;
 use crate::moderation::embeds::is_missing_permissions_error;
-use crate::moderation::logging::create_case_and_publish;
+use crate::moderation::logging::create_case_and_publish_with_attachment;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::impls::modlog_config::get_purge_transcripts_enabled;
+use autumn_database::impls::modlog_outbox::ModlogAttachment;
+use autumn_utils::cleanup::delete_messages_with_fallback;
+use autumn_utils::parse::parse_message_link;
+use autumn_utils::transcript::{TranscriptMessage, render_transcript};
 
 pub const META: CommandMeta = CommandMeta {
     name: "purge",
     desc: "Delete the latest messages in this channel.",
     category: "moderation",
-    usage: "!purge <amount>",
+    usage: "!purge <amount> | !purge between <link1> <link2>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!purge 50", "!purge between <link1> <link2>"],
+    related: &["purgetranscripts", "snipe"],
 };
 
 const MAX_PURGE: u16 = 100;
+const MAX_PURGE_BETWEEN: usize = 1000;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("latest", "between"),
+    check = "require_manage_messages"
+)]
 pub async fn purge(
     ctx: Context<'_>,
     #[description = "Amount of messages to purge"] amount: Option<u16>,
 ) -> Result<(), Error> {
+    purge_latest(ctx, amount).await
+}
+
+/// Delete the latest messages in this channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn latest(
+    ctx: Context<'_>,
+    #[description = "Amount of messages to purge"] amount: Option<u16>,
+) -> Result<(), Error> {
+    purge_latest(ctx, amount).await
+}
+
+/// Delete every message between two message links (inclusive).
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn between(
+    ctx: Context<'_>,
+    #[description = "Link to the first message"] start: Option<String>,
+    #[description = "Link to the last message"] end: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say("This command only works in servers.").await?;
+        return Ok(());
+    };
+
+    let (Some(start), Some(end)) = (start, end) else {
+        ctx.say("Usage: `!purge between <link1> <link2>`").await?;
+        return Ok(());
+    };
+
+    let (Some(start), Some(end)) = (parse_message_link(&start), parse_message_link(&end)) else {
+        ctx.say("Provide two valid message links.").await?;
+        return Ok(());
+    };
+
+    let (start_guild_id, start_channel_id, start_message_id) = start;
+    let (end_guild_id, end_channel_id, end_message_id) = end;
+    if start_guild_id != guild_id.get() || end_guild_id != guild_id.get() {
+        ctx.say("Both messages must be in this server.").await?;
+        return Ok(());
+    }
+    if start_channel_id != end_channel_id {
+        ctx.say("Both messages must be in the same channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let channel_id = serenity::ChannelId::new(start_channel_id);
+    let first_id = start_message_id.min(end_message_id);
+    let last_id = start_message_id.max(end_message_id);
+
+    ctx.defer_ephemeral().await?;
+
+    let messages = match fetch_messages_between(ctx, channel_id, first_id, last_id).await {
+        Ok(messages) => messages,
+        Err(source) => {
+            if !is_missing_permissions_error(&source) {
+                error!(?source, "purge between history fetch failed");
+            }
+            ctx.say("I couldn't read that channel's history.").await?;
+            return Ok(());
+        }
+    };
+
+    if messages.is_empty() {
+        ctx.say("No messages found between those links.").await?;
+        return Ok(());
+    }
+
+    if messages.len() > MAX_PURGE_BETWEEN {
+        ctx.say(format!(
+            "That range has more than {} messages. Pick a smaller range.",
+            MAX_PURGE_BETWEEN
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let transcript = purge_transcript_if_enabled(ctx, guild_id, channel_id, &messages).await;
+    suppress_userlog(ctx, &messages).await;
+
+    let targets: Vec<(serenity::MessageId, i64)> = messages
+        .iter()
+        .map(|message| (message.id, message.timestamp.unix_timestamp()))
+        .collect();
+    let deleted_count = delete_messages_with_fallback(ctx.http(), channel_id, &targets).await;
+
+    if deleted_count == 0 {
+        ctx.say("I couldn't delete messages. I likely need the 'Manage Messages' permission.")
+            .await?;
+        return Ok(());
+    }
+
+    record_purge_case(ctx, guild_id, transcript).await;
+
+    ctx.say(format!("Purged {} message(s).", deleted_count))
+        .await?;
+
+    Ok(())
+}
+
+async fn purge_latest(ctx: Context<'_>, amount: Option<u16>) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
         ctx.say("This command only works in servers.").await?;
         return Ok(());
@@ -39,17 +155,6 @@ pub async fn purge(
         return Ok(());
     }
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let delete_count = requested.saturating_add(1).min(MAX_PURGE);
 
     let channel_id = ctx.channel_id();
@@ -60,21 +165,17 @@ pub async fn purge(
         )
         .await?;
 
-    let ids: Vec<serenity::MessageId> = messages.into_iter().map(|message| message.id).collect();
-    let deleted_count = ids.len();
-
-    if ids.is_empty() {
+    if messages.is_empty() {
         ctx.say("No messages found to delete.").await?;
         return Ok(());
     }
 
-    // Suppress these message IDs from user-log recording.
-    {
-        let mut suppressed = ctx.data().suppressed_deletes.write().await;
-        for id in &ids {
-            suppressed.insert(id.get());
-        }
-    }
+    // Capture the content before it's gone.
+    let transcript = purge_transcript_if_enabled(ctx, guild_id, channel_id, &messages).await;
+    suppress_userlog(ctx, &messages).await;
+
+    let ids: Vec<serenity::MessageId> = messages.into_iter().map(|message| message.id).collect();
+    let deleted_count = ids.len();
 
     let delete_result = if ids.len() == 1 {
         channel_id.delete_message(ctx.http(), ids[0]).await
@@ -91,8 +192,93 @@ pub async fn purge(
         return Ok(());
     }
 
+    record_purge_case(ctx, guild_id, transcript).await;
+
+    let success_message = channel_id
+        .say(ctx.http(), format!("Purged {} message(s).", deleted_count))
+        .await?;
+
+    sleep(Duration::from_secs(3)).await;
+    let _ = success_message.delete(ctx.http()).await;
+
+    Ok(())
+}
+
+/// Page forward from `first_id` until `last_id`, returning the messages in
+/// that inclusive range, oldest first. Stops early once the range exceeds
+/// [`MAX_PURGE_BETWEEN`].
+async fn fetch_messages_between(
+    ctx: Context<'_>,
+    channel_id: serenity::ChannelId,
+    first_id: u64,
+    last_id: u64,
+) -> Result<Vec<serenity::Message>, serenity::Error> {
+    let mut messages = Vec::new();
+    // `after` is exclusive, so start just before the first message.
+    let mut cursor = first_id.saturating_sub(1).max(1);
+
+    while messages.len() <= MAX_PURGE_BETWEEN {
+        let mut batch = channel_id
+            .messages(
+                ctx.http(),
+                serenity::GetMessages::new()
+                    .after(serenity::MessageId::new(cursor))
+                    .limit(100),
+            )
+            .await?;
+        if batch.is_empty() {
+            break;
+        }
+
+        batch.sort_by_key(|message| message.id);
+        let batch_len = batch.len();
+        cursor = batch.last().map_or(last_id, |message| message.id.get());
+
+        messages.extend(
+            batch
+                .into_iter()
+                .filter(|message| message.id.get() <= last_id),
+        );
+
+        if cursor >= last_id || batch_len < 100 {
+            break;
+        }
+    }
+
+    Ok(messages)
+}
+
+async fn purge_transcript_if_enabled(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    messages: &[serenity::Message],
+) -> Option<ModlogAttachment> {
+    match get_purge_transcripts_enabled(&ctx.data().db, guild_id.get()).await {
+        Ok(true) => Some(purge_transcript(channel_id, ctx.author(), messages)),
+        Ok(false) => None,
+        Err(source) => {
+            error!(?source, "failed to read purge transcript config");
+            None
+        }
+    }
+}
+
+/// Suppress these message IDs from user-log recording.
+async fn suppress_userlog(ctx: Context<'_>, messages: &[serenity::Message]) {
+    let mut suppressed = ctx.data().suppressed_deletes.write().await;
+    for message in messages {
+        suppressed.insert(message.id.get());
+    }
+}
+
+async fn record_purge_case(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    transcript: Option<ModlogAttachment>,
+) {
     let case_reason = "No reason provided".to_owned();
-    let _case_label = create_case_and_publish(
+    let _case_label = create_case_and_publish_with_attachment(
         &ctx,
         guild_id,
         NewCase {
@@ -104,15 +290,46 @@ pub async fn purge(
             status: "active",
             duration_seconds: None,
         },
+        transcript,
     )
     .await;
+}
 
-    let success_message = channel_id
-        .say(ctx.http(), format!("Purged {} message(s).", deleted_count))
-        .await?;
+fn purge_transcript(
+    channel_id: serenity::ChannelId,
+    moderator: &serenity::User,
+    messages: &[serenity::Message],
+) -> ModlogAttachment {
+    // Transcripts read top to bottom, oldest first.
+    let mut ordered: Vec<&serenity::Message> = messages.iter().collect();
+    ordered.sort_by_key(|message| message.id);
 
-    sleep(Duration::from_secs(3)).await;
-    let _ = success_message.delete(ctx.http()).await;
+    let entries: Vec<TranscriptMessage> = ordered
+        .into_iter()
+        .map(|message| TranscriptMessage {
+            author_id: message.author.id.get(),
+            author_name: message.author.tag(),
+            channel_id: None,
+            timestamp: message.timestamp.to_string(),
+            content: message.content.clone(),
+            attachments: message
+                .attachments
+                .iter()
+                .map(|attachment| format!("{} ({})", attachment.filename, attachment.url))
+                .collect(),
+        })
+        .collect();
 
-    Ok(())
+    ModlogAttachment {
+        filename: format!("purge-{}.txt", channel_id.get()),
+        content: render_transcript(
+            &format!(
+                "Purge in channel {} by {} ({})",
+                channel_id.get(),
+                moderator.tag(),
+                moderator.id.get()
+            ),
+            &entries,
+        ),
+    }
 }
@@ -0,0 +1,299 @@
+use tracing::{error, warn};
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{bot_outranks_target, moderator_outranks_target, require_manage_roles};
+use crate::moderation::embeds::{
+    guild_only_message, is_missing_permissions_error, moderation_action_embed,
+    moderation_bot_target_message, send_moderation_target_dm_for_guild, target_profile_from_user,
+    usage_message,
+};
+use crate::moderation::logging::create_case_and_publish;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::quarantine::{
+    NewQuarantine, create_quarantine, delete_quarantine, get_quarantine, get_quarantine_role_id,
+    set_quarantine_role_id,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "quarantine",
+    desc: "Strip a member's roles and isolate them until released.",
+    category: "moderation",
+    usage: "!quarantine <user> [reason]",
+    required_permissions: Some(serenity::Permissions::MANAGE_ROLES),
+    examples: &["!quarantine @user compromised account posting scam links"],
+    related: &["release", "timeout"],
+};
+
+const QUARANTINE_ROLE_NAME: &str = "Quarantined";
+
+/// What the quarantine role is denied in every channel. The role itself has
+/// no permissions, so members keep only what @everyone grants, minus these.
+const QUARANTINE_DENY: serenity::Permissions = serenity::Permissions::SEND_MESSAGES
+    .union(serenity::Permissions::SEND_MESSAGES_IN_THREADS)
+    .union(serenity::Permissions::CREATE_PUBLIC_THREADS)
+    .union(serenity::Permissions::CREATE_PRIVATE_THREADS)
+    .union(serenity::Permissions::ADD_REACTIONS)
+    .union(serenity::Permissions::CONNECT)
+    .union(serenity::Permissions::SPEAK);
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_roles"
+)]
+pub async fn quarantine(
+    ctx: Context<'_>,
+    #[description = "The member to quarantine"] user: Option<serenity::User>,
+    #[description = "Reason for the quarantine"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if user.bot {
+        ctx.say(moderation_bot_target_message()).await?;
+        return Ok(());
+    }
+
+    if user.id == ctx.author().id {
+        ctx.say("You can't quarantine yourself.").await?;
+        return Ok(());
+    }
+
+    if !moderator_outranks_target(ctx, guild_id, user.id, "quarantine").await? {
+        return Ok(());
+    }
+
+    if !bot_outranks_target(ctx, guild_id, user.id, "quarantine").await? {
+        return Ok(());
+    }
+
+    let db = &ctx.data().db;
+    if get_quarantine(db, guild_id.get(), user.id.get())
+        .await?
+        .is_some()
+    {
+        ctx.say("That member is already quarantined. Use `!release` to restore their roles.")
+            .await?;
+        return Ok(());
+    }
+
+    let Ok(member) = guild_id.member(ctx.http(), user.id).await else {
+        ctx.say("That user isn't in this server.").await?;
+        return Ok(());
+    };
+
+    let roles = guild_id.roles(ctx.http()).await?;
+    let Some(quarantine_role) = ensure_quarantine_role(ctx, guild_id, &roles).await? else {
+        ctx.say("I couldn't set up the quarantine role. I need Manage Roles and Manage Channels.")
+            .await?;
+        return Ok(());
+    };
+
+    let (kept, stripped) = split_roles(&member.roles, quarantine_role, |role_id| {
+        roles.get(&role_id).is_some_and(|role| role.managed)
+    });
+
+    let reason = reason.as_deref().unwrap_or("No reason provided");
+    let stripped_ids: Vec<u64> = stripped.iter().map(|role_id| role_id.get()).collect();
+    if !create_quarantine(
+        db,
+        NewQuarantine {
+            guild_id: guild_id.get(),
+            user_id: user.id.get(),
+            role_ids: &stripped_ids,
+            reason,
+            moderator_id: ctx.author().id.get(),
+        },
+    )
+    .await?
+    {
+        ctx.say("That member is already quarantined. Use `!release` to restore their roles.")
+            .await?;
+        return Ok(());
+    }
+
+    let edit = serenity::EditMember::new()
+        .roles(kept.into_iter().chain(std::iter::once(quarantine_role)))
+        .audit_log_reason(reason);
+    if let Err(source) = guild_id.edit_member(ctx.http(), user.id, edit).await {
+        if !is_missing_permissions_error(&source) {
+            error!(?source, "quarantine role update failed");
+        }
+        delete_quarantine(db, guild_id.get(), user.id.get()).await?;
+        ctx.say("I couldn't change that member's roles. Check role hierarchy and permissions.")
+            .await?;
+        return Ok(());
+    }
+
+    let _ = send_moderation_target_dm_for_guild(
+        ctx.http(),
+        &user,
+        guild_id,
+        "quarantined",
+        Some(reason),
+        None,
+        accent_color(ctx).await,
+    )
+    .await;
+
+    let case_reason = format!("{} ({} role(s) stored)", reason, stripped_ids.len());
+    let _case_label = create_case_and_publish(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(user.id.get()),
+            moderator_user_id: ctx.author().id.get(),
+            action: "quarantine",
+            reason: &case_reason,
+            status: "active",
+            duration_seconds: None,
+        },
+    )
+    .await;
+
+    let target_profile = target_profile_from_user(&user);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        "quarantined",
+        Some(reason),
+        None,
+        accent_color(ctx).await,
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// The overwrite that isolates the quarantine role in a channel.
+pub fn quarantine_overwrite(role_id: serenity::RoleId) -> serenity::PermissionOverwrite {
+    serenity::PermissionOverwrite {
+        allow: serenity::Permissions::empty(),
+        deny: QUARANTINE_DENY,
+        kind: serenity::PermissionOverwriteType::Role(role_id),
+    }
+}
+
+/// Split a member's roles into those kept through the quarantine and those
+/// stripped and stored. Managed roles (boosts, integrations) can't be
+/// removed, so they stay.
+fn split_roles(
+    member_roles: &[serenity::RoleId],
+    quarantine_role: serenity::RoleId,
+    is_managed: impl Fn(serenity::RoleId) -> bool,
+) -> (Vec<serenity::RoleId>, Vec<serenity::RoleId>) {
+    member_roles
+        .iter()
+        .copied()
+        .filter(|role_id| *role_id != quarantine_role)
+        .partition(|role_id| is_managed(*role_id))
+}
+
+/// The guild's quarantine role, creating it with channel overwrites when it
+/// doesn't exist yet or was deleted. `None` when the bot can't create it.
+async fn ensure_quarantine_role(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    roles: &std::collections::HashMap<serenity::RoleId, serenity::Role>,
+) -> Result<Option<serenity::RoleId>, Error> {
+    let db = &ctx.data().db;
+    if let Some(role_id) = get_quarantine_role_id(db, guild_id.get()).await? {
+        let role_id = serenity::RoleId::new(role_id);
+        if roles.contains_key(&role_id) {
+            return Ok(Some(role_id));
+        }
+    }
+
+    let role = match guild_id
+        .create_role(
+            ctx.http(),
+            serenity::EditRole::new()
+                .name(QUARANTINE_ROLE_NAME)
+                .permissions(serenity::Permissions::empty())
+                .audit_log_reason("Quarantine role for !quarantine"),
+        )
+        .await
+    {
+        Ok(role) => role,
+        Err(source) => {
+            if !is_missing_permissions_error(&source) {
+                error!(?source, "failed to create quarantine role");
+            }
+            return Ok(None);
+        }
+    };
+
+    for channel in guild_id.channels(ctx.http()).await?.into_values() {
+        if let Err(source) = channel
+            .create_permission(ctx.http(), quarantine_overwrite(role.id))
+            .await
+        {
+            warn!(?source, channel_id = %channel.id, "failed to deny quarantine role in channel");
+        }
+    }
+
+    set_quarantine_role_id(db, guild_id.get(), role.id.get()).await?;
+
+    Ok(Some(role.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(id: u64) -> serenity::RoleId {
+        serenity::RoleId::new(id)
+    }
+
+    #[test]
+    fn overwrite_denies_speaking_for_the_role() {
+        let overwrite = quarantine_overwrite(role(9));
+
+        assert!(overwrite.allow.is_empty());
+        assert!(
+            overwrite
+                .deny
+                .contains(serenity::Permissions::SEND_MESSAGES)
+        );
+        assert!(overwrite.deny.contains(serenity::Permissions::CONNECT));
+        assert!(!overwrite.deny.contains(serenity::Permissions::VIEW_CHANNEL));
+        assert_eq!(
+            overwrite.kind,
+            serenity::PermissionOverwriteType::Role(role(9))
+        );
+    }
+
+    #[test]
+    fn managed_roles_are_kept_and_the_rest_stripped() {
+        let managed = [role(2)];
+        let (kept, stripped) = split_roles(&[role(1), role(2), role(3)], role(9), |role_id| {
+            managed.contains(&role_id)
+        });
+
+        assert_eq!(kept, [role(2)]);
+        assert_eq!(stripped, [role(1), role(3)]);
+    }
+
+    #[test]
+    fn quarantine_role_is_neither_kept_nor_stored() {
+        let (kept, stripped) = split_roles(&[role(1), role(9)], role(9), |_| false);
+
+        assert!(kept.is_empty());
+        assert_eq!(stripped, [role(1)]);
+    }
+}
@@ -0,0 +1,158 @@
+use tracing::error;
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{moderator_outranks_target, require_moderate_members};
+use crate::moderation::embeds::{
+    guild_only_message, moderation_action_embed, moderation_bot_target_message,
+    target_profile_from_user, usage_message,
+};
+use crate::moderation::logging::create_case_and_publish;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::scheduled_jobs::{
+    JOB_SHADOW_MUTE_EXPIRY, NewScheduledJob, cancel_jobs_for_target, schedule_job,
+};
+use autumn_database::impls::shadow_mutes::add_shadow_mute;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::parse_duration_seconds;
+use autumn_utils::time::now_unix_secs;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "shadowmute",
+    desc: "Silently delete a member's messages for a while, without telling them.",
+    category: "moderation",
+    usage: "!shadowmute <user> <duration> [reason]",
+    required_permissions: Some(serenity::Permissions::MODERATE_MEMBERS),
+    examples: &["!shadowmute @user 6h baiting arguments"],
+    related: &["unshadowmute", "timeout"],
+};
+
+const MAX_SHADOW_MUTE_SECS: u64 = 30 * 24 * 60 * 60;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_moderate_members"
+)]
+pub async fn shadowmute(
+    ctx: Context<'_>,
+    #[description = "The member to shadow mute"] user: Option<serenity::User>,
+    #[description = "How long to shadow mute them (e.g. 6h, 1d)"] duration: Option<String>,
+    #[description = "Reason for the shadow mute"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let (Some(user), Some(duration)) = (user, duration) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if user.bot {
+        ctx.say(moderation_bot_target_message()).await?;
+        return Ok(());
+    }
+
+    if user.id == ctx.author().id {
+        ctx.say("You can't shadow mute yourself.").await?;
+        return Ok(());
+    }
+
+    let Some(duration_seconds) = parse_duration_seconds(&duration) else {
+        ctx.say(format!(
+            "Invalid duration. Usage: `{}` (examples: 30m, 6h, 1d)",
+            META.usage
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    if duration_seconds > MAX_SHADOW_MUTE_SECS {
+        ctx.say("Shadow mutes can last at most 30 days.").await?;
+        return Ok(());
+    }
+
+    if !moderator_outranks_target(ctx, guild_id, user.id, "shadow mute").await? {
+        return Ok(());
+    }
+
+    let db = &ctx.data().db;
+    let reason = reason.as_deref().unwrap_or("No reason provided");
+    let expires_at = now_unix_secs().saturating_add(duration_seconds);
+    add_shadow_mute(
+        db,
+        guild_id.get(),
+        user.id.get(),
+        reason,
+        ctx.author().id.get(),
+        expires_at,
+    )
+    .await?;
+
+    let case_label = create_case_and_publish(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(user.id.get()),
+            moderator_user_id: ctx.author().id.get(),
+            action: "shadowmute",
+            reason,
+            status: "active",
+            duration_seconds: Some(duration_seconds),
+        },
+    )
+    .await;
+
+    // Shadow muting again replaces the earlier expiry.
+    if let Err(source) =
+        cancel_jobs_for_target(db, guild_id.get(), JOB_SHADOW_MUTE_EXPIRY, user.id.get()).await
+    {
+        error!(?source, "failed to cancel earlier shadow mute expiry");
+    }
+
+    let scheduled = schedule_job(
+        db,
+        NewScheduledJob {
+            guild_id: guild_id.get(),
+            kind: JOB_SHADOW_MUTE_EXPIRY,
+            target_user_id: Some(user.id.get()),
+            channel_id: None,
+            payload: case_label.as_deref().unwrap_or_default(),
+            run_at: expires_at,
+            created_by: ctx.author().id.get(),
+        },
+    )
+    .await;
+
+    if let Err(source) = scheduled {
+        error!(?source, "failed to schedule shadow mute expiry");
+        ctx.say(
+            "The shadow mute is on, but I couldn't schedule its end. Lift it with `!unshadowmute`.",
+        )
+        .await?;
+    }
+
+    let duration_label = format_compact_duration(duration_seconds);
+    let target_profile = target_profile_from_user(&user);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        "shadow muted",
+        Some(reason),
+        Some(&duration_label),
+        accent_color(ctx).await,
+    );
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use tracing::error;
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_roles;
+use crate::moderation::embeds::{
+    guild_only_message, is_missing_permissions_error, moderation_action_embed,
+    moderation_bot_target_message, target_profile_from_user, usage_message,
+};
+use crate::moderation::logging::create_case_and_publish;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::scheduled_jobs::{
+    JOB_TEMPROLE_EXPIRY, NewScheduledJob, cancel_jobs_for_target_with_payload, schedule_job,
+};
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::parse_duration_seconds;
+use autumn_utils::permissions::can_manage_role;
+use autumn_utils::time::now_unix_secs;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "temprole",
+    desc: "Give a user a role that is removed again after a duration.",
+    category: "moderation",
+    usage: "!temprole <user> <role> <duration> [reason]",
+    required_permissions: Some(serenity::Permissions::MANAGE_ROLES),
+    examples: &["!temprole @user @Muted 1d cooling off"],
+    related: &["timeout"],
+};
+
+const MAX_TEMPROLE_SECS: u64 = 365 * 24 * 60 * 60;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_roles"
+)]
+pub async fn temprole(
+    ctx: Context<'_>,
+    #[description = "The user to give the role to"] user: Option<serenity::User>,
+    #[description = "The role to give"] role: Option<serenity::Role>,
+    #[description = "How long to keep the role (e.g. 1d, 12h)"] duration: Option<String>,
+    #[description = "Reason for the role"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let (Some(user), Some(role), Some(duration)) = (user, role, duration) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if user.bot {
+        ctx.say(moderation_bot_target_message()).await?;
+        return Ok(());
+    }
+
+    let Some(duration_seconds) = parse_duration_seconds(&duration) else {
+        ctx.say(format!(
+            "Invalid duration. Usage: `{}` (examples: 12h, 1d, 7d)",
+            META.usage
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    if duration_seconds > MAX_TEMPROLE_SECS {
+        ctx.say("Temporary roles can last at most a year.").await?;
+        return Ok(());
+    }
+
+    if role.id.get() == guild_id.get() || role.managed {
+        ctx.say("That role can't be assigned.").await?;
+        return Ok(());
+    }
+
+    if !can_manage_role(ctx.http(), guild_id, ctx.author().id, role.id).await? {
+        ctx.say("You can only assign roles below your highest role.")
+            .await?;
+        return Ok(());
+    }
+
+    let reason = reason.as_deref().unwrap_or("No reason provided");
+    let add_result = ctx
+        .http()
+        .add_member_role(guild_id, user.id, role.id, Some(reason))
+        .await;
+
+    if let Err(source) = add_result {
+        if !is_missing_permissions_error(&source) {
+            error!(?source, "temprole assignment failed");
+        }
+        ctx.say("I couldn't assign that role. Check role hierarchy and permissions.")
+            .await?;
+        return Ok(());
+    }
+
+    let case_reason = format!("{} (role: {})", reason, role.name);
+    let _case_label = create_case_and_publish(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(user.id.get()),
+            moderator_user_id: ctx.author().id.get(),
+            action: "temprole",
+            reason: &case_reason,
+            status: "active",
+            duration_seconds: Some(duration_seconds),
+        },
+    )
+    .await;
+
+    let db = &ctx.data().db;
+    let role_payload = role.id.get().to_string();
+
+    // Handing the role out again restarts the clock.
+    if let Err(source) = cancel_jobs_for_target_with_payload(
+        db,
+        guild_id.get(),
+        JOB_TEMPROLE_EXPIRY,
+        user.id.get(),
+        &role_payload,
+    )
+    .await
+    {
+        error!(?source, "failed to cancel earlier temprole expiry");
+    }
+
+    let scheduled = schedule_job(
+        db,
+        NewScheduledJob {
+            guild_id: guild_id.get(),
+            kind: JOB_TEMPROLE_EXPIRY,
+            target_user_id: Some(user.id.get()),
+            channel_id: None,
+            payload: &role_payload,
+            run_at: now_unix_secs().saturating_add(duration_seconds),
+            created_by: ctx.author().id.get(),
+        },
+    )
+    .await;
+
+    if let Err(source) = scheduled {
+        error!(?source, "failed to schedule temprole expiry");
+        ctx.say("The role was assigned, but I couldn't schedule its removal. Remove it manually.")
+            .await?;
+    }
+
+    let duration_label = format_compact_duration(duration_seconds);
+    let target_profile = target_profile_from_user(&user);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        "given a temporary role",
+        Some(&case_reason),
+        Some(&duration_label),
+        accent_color(ctx).await,
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use tracing::error;
@@ -5,30 +6,60 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{
+    bot_outranks_target, moderator_outranks_target, require_ban_members, require_manage_messages,
+};
+use crate::moderation::approval::{ApprovalRequest, finish_approval, request_approval};
 use crate::moderation::embeds::{
     guild_only_message, is_missing_permissions_error, moderation_action_embed,
     moderation_bot_target_message, moderation_self_action_message, target_profile_from_user,
     usage_message,
 };
-use crate::moderation::logging::create_case_and_publish;
+use crate::moderation::logging::create_case_and_publish_with_attachment;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::confirmation::{ConfirmationResult, prompt_confirm_decline};
+use autumn_database::impls::moderation_settings::get_dual_approval;
+use autumn_database::impls::modlog_config::get_purge_transcripts_enabled;
+use autumn_database::impls::modlog_outbox::ModlogAttachment;
+use autumn_database::impls::user_logs::list_message_snapshots_by_author;
+use autumn_utils::cleanup::purge_user_globally;
+use autumn_utils::confirmation::{
+    ReasonConfirmationResult, TYPED_CONFIRMATION_MISMATCH_TEXT, TypedConfirmation,
+    prompt_confirm_typed_with_reason,
+};
 use autumn_utils::parse::parse_duration_seconds;
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::time::{now_unix_secs, snowflake_at_unix_secs};
+use autumn_utils::transcript::{TranscriptMessage, render_transcript};
 
 pub const META: CommandMeta = CommandMeta {
     name: "terminate",
     desc: "Ban a user and purge their messages (DANGER)",
     category: "moderation",
     usage: "!terminate <user> [period] [reason]",
+    required_permissions: Some(
+        serenity::Permissions::BAN_MEMBERS.union(serenity::Permissions::MANAGE_MESSAGES),
+    ),
+    examples: &[
+        "!terminate @user",
+        "!terminate @user 7d raiding",
+        "!terminate @user 30d spam",
+    ],
+    related: &["ban", "purge"],
 };
 
 const SECONDS_PER_DAY: u64 = 86_400;
 const MAX_NATIVE_BAN_DELETE_DAYS: u8 = 7;
 const TERMINATE_CONFIRM_TIMEOUT_SECS: u64 = 30;
+const MAX_TRANSCRIPT_MESSAGES: u32 = 1000;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_ban_members",
+    check = "require_manage_messages"
+)]
 pub async fn terminate(
     ctx: Context<'_>,
     #[description = "The user to terminate"] user: Option<serenity::User>,
@@ -42,12 +73,6 @@ pub async fn terminate(
         return Ok(());
     };
 
-    let required_permissions =
-        serenity::Permissions::BAN_MEMBERS | serenity::Permissions::MANAGE_MESSAGES;
-    if !has_user_permission(ctx.http(), guild_id, ctx.author().id, required_permissions).await? {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -63,6 +88,14 @@ pub async fn terminate(
         return Ok(());
     }
 
+    if !moderator_outranks_target(ctx, guild_id, user.id, "ban").await? {
+        return Ok(());
+    }
+
+    if !bot_outranks_target(ctx, guild_id, user.id, "ban").await? {
+        return Ok(());
+    }
+
     let default_duration_secs = u64::from(MAX_NATIVE_BAN_DELETE_DAYS) * SECONDS_PER_DAY;
     let (purge_duration_secs, cutoff_display, reason) = match period_or_reason.as_deref() {
         Some(first) => {
@@ -75,15 +108,6 @@ pub async fn terminate(
                 return Ok(());
             };
 
-            if duration_secs > u64::from(MAX_NATIVE_BAN_DELETE_DAYS) * SECONDS_PER_DAY {
-                ctx.say(format!(
-                    "Invalid purge period. Max is 7d. Usage: `{}` (examples: 30s, 10m, 2h, 7d)",
-                    META.usage
-                ))
-                .await?;
-                return Ok(());
-            }
-
             (duration_secs, first.to_owned(), reason_rest)
         }
         None => (
@@ -98,15 +122,17 @@ pub async fn terminate(
         .min(u64::from(MAX_NATIVE_BAN_DELETE_DAYS)) as u8;
 
     let target_profile = target_profile_from_user(&user);
+    let accent = accent_color(ctx).await;
     let confirmation_embed = moderation_action_embed(
         &target_profile,
         user.id,
         "queued for termination",
         reason.as_deref(),
         None,
+        accent,
     );
 
-    let confirmation_result = prompt_confirm_decline(
+    let confirmation_result = prompt_confirm_typed_with_reason(
         ctx,
         format!(
             "Ban and purge pending moderator confirmation.\nPeriod: {}",
@@ -114,17 +140,24 @@ pub async fn terminate(
         ),
         confirmation_embed,
         Duration::from_secs(TERMINATE_CONFIRM_TIMEOUT_SECS),
+        reason.as_deref(),
+        TypedConfirmation {
+            label: "Type their username to confirm",
+            expected: &user.name,
+        },
     )
     .await?;
 
-    let interaction = match confirmation_result {
-        ConfirmationResult::TimedOut(message) => {
+    // The reason typed into the confirmation modal is the one on record.
+    let (interaction, reason) = match confirmation_result {
+        ReasonConfirmationResult::TimedOut(message) => {
             let timeout_embed = moderation_action_embed(
                 &target_profile,
                 user.id,
                 "left unchanged",
                 Some("Timed out"),
                 None,
+                accent,
             );
 
             message
@@ -140,7 +173,7 @@ pub async fn terminate(
                 .await?;
             return Ok(());
         }
-        ConfirmationResult::Declined(interaction) => {
+        ReasonConfirmationResult::Declined(interaction) => {
             interaction
                 .create_response(
                     ctx.http(),
@@ -153,6 +186,7 @@ pub async fn terminate(
                                 "left unchanged",
                                 Some("Termination cancelled."),
                                 None,
+                                accent,
                             ))
                             .components(vec![]),
                     ),
@@ -160,39 +194,117 @@ pub async fn terminate(
                 .await?;
             return Ok(());
         }
-        ConfirmationResult::Confirmed(interaction) => interaction,
+        ReasonConfirmationResult::Mismatched(interaction) => {
+            interaction
+                .create_response(
+                    ctx.http(),
+                    serenity::CreateInteractionResponse::UpdateMessage(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content(TYPED_CONFIRMATION_MISMATCH_TEXT)
+                            .embed(moderation_action_embed(
+                                &target_profile,
+                                user.id,
+                                "left unchanged",
+                                Some(TYPED_CONFIRMATION_MISMATCH_TEXT),
+                                None,
+                                accent,
+                            ))
+                            .components(vec![]),
+                    ),
+                )
+                .await?;
+            return Ok(());
+        }
+        ReasonConfirmationResult::Confirmed {
+            interaction,
+            reason,
+        } => (interaction, Some(reason)),
     };
 
+    let case_reason = reason.as_deref().unwrap_or("No reason provided").to_owned();
+    let dual_approval = get_dual_approval(&ctx.data().db, guild_id.get()).await?;
+
     interaction
         .create_response(
             ctx.http(),
             serenity::CreateInteractionResponse::UpdateMessage(
                 serenity::CreateInteractionResponseMessage::new()
-                    .content("Terminating...")
+                    .content(if dual_approval {
+                        "Waiting for a second moderator to approve..."
+                    } else {
+                        "Terminating..."
+                    })
                     .embed(moderation_action_embed(
                         &target_profile,
                         user.id,
                         "queued for termination",
                         reason.as_deref(),
                         None,
+                        accent,
                     ))
                     .components(vec![]),
             ),
         )
         .await?;
 
+    // Discord deletes the messages with the ban, so capture them first.
+    let mut transcript = match get_purge_transcripts_enabled(&ctx.data().db, guild_id.get()).await {
+        Ok(true) => terminate_transcript(ctx, guild_id, &user, purge_duration_secs).await,
+        Ok(false) => None,
+        Err(source) => {
+            error!(?source, "failed to read purge transcript config");
+            None
+        }
+    };
+
+    let approval = if dual_approval {
+        let request = ApprovalRequest {
+            user: &user,
+            action: "terminate",
+            reason: &case_reason,
+            duration_seconds: Some(purge_duration_secs),
+            required: META.required_permissions.unwrap_or_default(),
+            attachment: transcript.take(),
+        };
+        let Some(approval) = request_approval(ctx, guild_id, request).await? else {
+            interaction
+                .edit_response(
+                    ctx.http(),
+                    serenity::EditInteractionResponse::new()
+                        .content("Termination not approved.")
+                        .embed(moderation_action_embed(
+                            &target_profile,
+                            user.id,
+                            "left unchanged",
+                            Some("Termination not approved."),
+                            None,
+                            accent,
+                        )),
+                )
+                .await?;
+            return Ok(());
+        };
+        interaction
+            .edit_response(
+                ctx.http(),
+                serenity::EditInteractionResponse::new().content("Terminating..."),
+            )
+            .await?;
+        Some(approval)
+    } else {
+        None
+    };
+
     if let Err(source) = guild_id
-        .ban_with_reason(
-            ctx.http(),
-            user.id,
-            native_delete_days,
-            reason.as_deref().unwrap_or("No reason provided"),
-        )
+        .ban_with_reason(ctx.http(), user.id, native_delete_days, &case_reason)
         .await
     {
         if !is_missing_permissions_error(&source) {
             error!(?source, "terminate ban failed");
         }
+        if let Some(approval) = &approval {
+            finish_approval(ctx, guild_id, approval, false).await;
+        }
         interaction
             .edit_response(
                 ctx.http(),
@@ -204,6 +316,7 @@ pub async fn terminate(
                         "not terminated",
                         Some("Ban failed. Check hierarchy and permissions."),
                         None,
+                        accent,
                     )),
             )
             .await?;
@@ -224,31 +337,46 @@ pub async fn terminate(
                     "termination in progress",
                     reason.as_deref(),
                     None,
+                    accent,
                 )),
         )
         .await?;
 
-    let final_content = format!(
+    let mut final_content = format!(
         "Ban applied. Native cleanup done.\nPurge period: last {} day(s)",
         native_delete_days
     );
 
-    let case_reason = reason.as_deref().unwrap_or("No reason provided").to_owned();
+    // Discord's ban cleanup stops at 7 days; sweep channel history for the rest.
+    if purge_duration_secs > u64::from(MAX_NATIVE_BAN_DELETE_DAYS) * SECONDS_PER_DAY {
+        let sweep =
+            sweep_older_messages(ctx, &interaction, guild_id, user.id, purge_duration_secs).await?;
+        final_content = format!(
+            "Ban applied. Native cleanup done.\nPurge period: {}\n{}",
+            cutoff_display, sweep
+        );
+    }
 
-    let _case_label = create_case_and_publish(
-        &ctx,
-        guild_id,
-        NewCase {
-            guild_id: guild_id.get(),
-            target_user_id: Some(user.id.get()),
-            moderator_user_id: ctx.author().id.get(),
-            action: "terminate",
-            reason: &case_reason,
-            status: "active",
-            duration_seconds: Some(purge_duration_secs),
-        },
-    )
-    .await;
+    match &approval {
+        Some(approval) => finish_approval(ctx, guild_id, approval, true).await,
+        None => {
+            let _case_label = create_case_and_publish_with_attachment(
+                &ctx,
+                guild_id,
+                NewCase {
+                    guild_id: guild_id.get(),
+                    target_user_id: Some(user.id.get()),
+                    moderator_user_id: ctx.author().id.get(),
+                    action: "terminate",
+                    reason: &case_reason,
+                    status: "active",
+                    duration_seconds: Some(purge_duration_secs),
+                },
+                transcript,
+            )
+            .await;
+        }
+    }
 
     let final_embed = moderation_action_embed(
         &target_profile,
@@ -256,6 +384,7 @@ pub async fn terminate(
         "terminated",
         reason.as_deref(),
         None,
+        accent,
     );
 
     interaction
@@ -263,9 +392,141 @@ pub async fn terminate(
             ctx.http(),
             serenity::EditInteractionResponse::new()
                 .content(final_content)
-                .embed(final_embed),
+                .embed(final_embed)
+                .components(Vec::new()),
         )
         .await?;
 
     Ok(())
 }
+
+/// Delete the user's messages that Discord's ban cleanup can't reach, showing
+/// progress on the confirmation message with an Abort button for the
+/// invoker. Returns the line summarizing what was deleted.
+async fn sweep_older_messages(
+    ctx: Context<'_>,
+    interaction: &serenity::ModalInteraction,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+    purge_duration_secs: u64,
+) -> Result<String, Error> {
+    let abort_id = format!("{}_terminate_abort", ctx.id());
+    let abort_row = vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&abort_id)
+            .label("Abort")
+            .style(serenity::ButtonStyle::Danger),
+    ])];
+
+    interaction
+        .edit_response(
+            ctx.http(),
+            serenity::EditInteractionResponse::new()
+                .content("Sweeping older messages...")
+                .components(abort_row.clone()),
+        )
+        .await?;
+
+    let aborted = AtomicBool::new(false);
+    let cutoff_secs = now_unix_secs().saturating_sub(purge_duration_secs);
+    let purge = purge_user_globally(
+        ctx.http(),
+        guild_id,
+        user_id,
+        Some(cutoff_secs),
+        &aborted,
+        |progress| {
+            let edit = serenity::EditInteractionResponse::new()
+                .content(format!(
+                    "Sweeping older messages...\n{}",
+                    progress.summary()
+                ))
+                .components(abort_row.clone());
+            async move {
+                let _ = interaction.edit_response(ctx.http(), edit).await;
+            }
+        },
+    );
+    tokio::pin!(purge);
+
+    let author_id = ctx.author().id;
+    let progress = loop {
+        let abort_press = serenity::ComponentInteractionCollector::new(ctx).filter({
+            let abort_id = abort_id.clone();
+            move |press| press.data.custom_id == abort_id && press.user.id == author_id
+        });
+
+        tokio::select! {
+            progress = &mut purge => break progress?,
+            Some(press) = abort_press => {
+                aborted.store(true, Ordering::Relaxed);
+                let _ = press
+                    .create_response(ctx.http(), serenity::CreateInteractionResponse::Acknowledge)
+                    .await;
+            }
+        }
+    };
+
+    Ok(if aborted.load(Ordering::Relaxed) {
+        format!("Sweep aborted. {}.", progress.summary())
+    } else {
+        format!("Sweep done. {}.", progress.summary())
+    })
+}
+
+/// Build a transcript of the user's messages within the purge period from the
+/// message snapshot archive.
+async fn terminate_transcript(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    user: &serenity::User,
+    purge_duration_secs: u64,
+) -> Option<ModlogAttachment> {
+    let since = now_unix_secs().saturating_sub(purge_duration_secs);
+    let snapshots = match list_message_snapshots_by_author(
+        &ctx.data().db,
+        guild_id.get(),
+        user.id.get(),
+        snowflake_at_unix_secs(since),
+        MAX_TRANSCRIPT_MESSAGES,
+    )
+    .await
+    {
+        Ok(snapshots) => snapshots,
+        Err(source) => {
+            error!(?source, "failed to load messages for terminate transcript");
+            return None;
+        }
+    };
+
+    let entries: Vec<TranscriptMessage> = snapshots
+        .into_iter()
+        .map(|snapshot| TranscriptMessage {
+            author_id: snapshot.author_user_id,
+            author_name: user.tag(),
+            channel_id: Some(snapshot.channel_id),
+            timestamp: serenity::MessageId::new(snapshot.message_id)
+                .created_at()
+                .to_string(),
+            content: snapshot.content,
+            attachments: snapshot
+                .attachment_summary
+                .as_deref()
+                .map(|summary| summary.lines().map(str::to_owned).collect())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    Some(ModlogAttachment {
+        filename: format!("terminate-{}.txt", user.id.get()),
+        content: render_transcript(
+            &format!(
+                "Terminate of {} ({}) by {} ({})",
+                user.tag(),
+                user.id.get(),
+                ctx.author().tag(),
+                ctx.author().id.get()
+            ),
+            &entries,
+        ),
+    })
+}
@@ -5,6 +5,9 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{bot_outranks_target, moderator_outranks_target, require_moderate_members};
+use crate::moderation::deferred::schedule_deferred_action;
 use crate::moderation::embeds::{
     guild_only_message, is_missing_permissions_error, moderation_action_embed,
     moderation_bot_target_message, send_moderation_target_dm_for_guild, target_profile_from_user,
@@ -13,70 +16,71 @@ use crate::moderation::embeds::{
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::scheduled_jobs::{
+    JOB_TIMEOUT_EXPIRY, NewScheduledJob, cancel_jobs_for_target, schedule_job,
+};
 use autumn_utils::formatting::format_compact_duration;
-use autumn_utils::parse::{has_duration_unit, parse_duration_seconds};
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::parse::{parse_duration_seconds, split_duration_prefix_at};
+use autumn_utils::time::{now_unix_secs, split_deferred_start};
 
 pub const META: CommandMeta = CommandMeta {
     name: "timeout",
     desc: "Timeout a user for a duration (default: 10m).",
     category: "moderation",
-    usage: "!timeout <user> [duration] [reason]",
+    usage: "!timeout <user> [--at HH:MM | in <delay>] [duration] [reason]",
+    required_permissions: Some(serenity::Permissions::MODERATE_MEMBERS),
+    examples: &[
+        "!timeout @user 10m spamming",
+        "!timeout @user 2 hours",
+        "!timeout @user until tomorrow 9am",
+    ],
+    related: &["untimeout", "warn", "temprole"],
 };
 
 const DEFAULT_TIMEOUT_SECS: u64 = 10 * 60;
+/// Timeouts at least this long get an expiry case logged when they end.
+pub(crate) const TIMEOUT_FOLLOWUP_MIN_SECS: u64 = 24 * 60 * 60;
 
-fn is_explicit_unit_duration_token(raw: &str) -> bool {
-    has_duration_unit(raw) && parse_duration_seconds(raw).is_some()
-}
-
+/// Split the duration off the front of the arguments. Durations may span
+/// several words (`2 hours`, `until tomorrow 9am`); otherwise the first word
+/// is the duration, so a bare `30` still means seconds. A `--` after the
+/// duration starts the reason.
 fn split_timeout_duration_and_reason(
     duration: Option<&str>,
     reason: Option<&str>,
 ) -> (Option<String>, Option<String>) {
-    let mut duration_parts = Vec::new();
-    if let Some(raw_duration) = duration.map(str::trim).filter(|value| !value.is_empty()) {
-        duration_parts.push(raw_duration.to_owned());
-    }
-
-    let mut reason_tokens = Vec::new();
-    if let Some(rest) = reason {
-        let mut tokens = rest.split_whitespace();
-        let collect_more_duration = !duration_parts.is_empty();
-
-        while let Some(token) = tokens.next() {
-            if token == "--" {
-                reason_tokens.extend(tokens.map(str::to_owned));
-                break;
-            }
-
-            if collect_more_duration && is_explicit_unit_duration_token(token) {
-                duration_parts.push(token.to_owned());
-                continue;
-            }
-
-            reason_tokens.push(token.to_owned());
-            reason_tokens.extend(tokens.map(str::to_owned));
-            break;
-        }
-    }
+    let reason = reason.map(str::trim).filter(|value| !value.is_empty());
+    let Some(duration) = duration.map(str::trim).filter(|value| !value.is_empty()) else {
+        return (None, reason.map(str::to_owned));
+    };
 
-    let parsed_duration_input = if duration_parts.is_empty() {
-        None
-    } else {
-        Some(duration_parts.join(" "))
+    let combined = match reason {
+        Some(reason) => format!("{} {}", duration, reason),
+        None => duration.to_owned(),
     };
+    let (duration, rest) = split_duration_prefix_at(&combined, now_unix_secs())
+        .unwrap_or_else(|| combined.split_once(' ').unwrap_or((&combined, "")));
 
-    let parsed_reason = if reason_tokens.is_empty() {
-        None
-    } else {
-        Some(reason_tokens.join(" "))
+    let rest = rest.trim_start();
+    let rest = match rest.strip_prefix("--") {
+        Some(after) if after.is_empty() || after.starts_with(char::is_whitespace) => {
+            after.trim_start()
+        }
+        _ => rest,
     };
 
-    (parsed_duration_input, parsed_reason)
+    (
+        Some(duration.to_owned()),
+        (!rest.is_empty()).then(|| rest.to_owned()),
+    )
 }
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_moderate_members"
+)]
 pub async fn timeout(
     ctx: Context<'_>,
     #[description = "The user to timeout"] user: Option<serenity::User>,
@@ -84,23 +88,35 @@ pub async fn timeout(
     #[description = "Reason for timeout"]
     #[rest]
     reason: Option<String>,
+) -> Result<(), Error> {
+    timeout_member(ctx, user, duration, reason).await
+}
+
+/// Time a user out for the default 10 minutes from the Apps menu on their
+/// profile.
+#[poise::command(
+    context_menu_command = "Timeout 10m",
+    category = "Moderation",
+    check = "require_moderate_members"
+)]
+pub async fn timeout_user(
+    ctx: Context<'_>,
+    #[description = "The user to timeout"] user: serenity::User,
+) -> Result<(), Error> {
+    timeout_member(ctx, Some(user), None, None).await
+}
+
+async fn timeout_member(
+    ctx: Context<'_>,
+    user: Option<serenity::User>,
+    duration: Option<String>,
+    reason: Option<String>,
 ) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
         ctx.say(guild_only_message()).await?;
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MODERATE_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -116,14 +132,36 @@ pub async fn timeout(
         return Ok(());
     }
 
-    let (duration_input, parsed_reason) =
-        split_timeout_duration_and_reason(duration.as_deref(), reason.as_deref());
+    if !moderator_outranks_target(ctx, guild_id, user.id, "timeout").await? {
+        return Ok(());
+    }
+
+    if !bot_outranks_target(ctx, guild_id, user.id, "timeout").await? {
+        return Ok(());
+    }
+
+    let combined = [duration.as_deref(), reason.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let deferred = split_deferred_start(&combined, now_unix_secs());
+    let (duration, reason) = match &deferred {
+        Some((_, rest)) => match rest.as_deref().map(|rest| rest.split_once(' ')) {
+            Some(Some((duration, reason))) => (Some(duration), Some(reason)),
+            Some(None) => (rest.as_deref(), None),
+            None => (None, None),
+        },
+        None => (duration.as_deref(), reason.as_deref()),
+    };
+
+    let (duration_input, parsed_reason) = split_timeout_duration_and_reason(duration, reason);
 
     let parsed_duration = match duration_input.as_deref().map(str::trim) {
         Some(raw) if !raw.is_empty() => {
             let Some(seconds) = parse_duration_seconds(raw) else {
                 ctx.say(format!(
-                    "Invalid duration. Usage: `{}` (examples: 30s, 10m, 2h, 1d)",
+                    "Invalid duration. Usage: `{}` (examples: 30s, 10m, 2 hours, until 9pm)",
                     META.usage
                 ))
                 .await?;
@@ -135,6 +173,19 @@ pub async fn timeout(
     };
     let duration_label = format_compact_duration(parsed_duration);
 
+    if let Some((run_at, _)) = deferred {
+        return schedule_deferred_action(
+            ctx,
+            guild_id,
+            &user,
+            "timeout",
+            parsed_reason.as_deref().unwrap_or("No reason provided"),
+            Some(parsed_duration),
+            run_at,
+        )
+        .await;
+    }
+
     let until_system_time = SystemTime::now()
         .checked_add(Duration::from_secs(parsed_duration))
         .unwrap_or(SystemTime::now());
@@ -167,10 +218,11 @@ pub async fn timeout(
         "timed out",
         Some(&case_reason),
         Some(&duration_label),
+        accent_color(ctx).await,
     )
     .await;
 
-    let _case_label = create_case_and_publish(
+    let case_label = create_case_and_publish(
         &ctx,
         guild_id,
         NewCase {
@@ -185,6 +237,36 @@ pub async fn timeout(
     )
     .await;
 
+    // A new timeout replaces any earlier one, along with its follow-up.
+    if let Err(source) = cancel_jobs_for_target(
+        &ctx.data().db,
+        guild_id.get(),
+        JOB_TIMEOUT_EXPIRY,
+        user.id.get(),
+    )
+    .await
+    {
+        error!(?source, "failed to cancel earlier timeout expiry");
+    }
+
+    if parsed_duration >= TIMEOUT_FOLLOWUP_MIN_SECS
+        && let Err(source) = schedule_job(
+            &ctx.data().db,
+            NewScheduledJob {
+                guild_id: guild_id.get(),
+                kind: JOB_TIMEOUT_EXPIRY,
+                target_user_id: Some(user.id.get()),
+                channel_id: None,
+                payload: case_label.as_deref().unwrap_or_default(),
+                run_at: u64::try_from(until_unix).unwrap_or(0),
+                created_by: ctx.author().id.get(),
+            },
+        )
+        .await
+    {
+        error!(?source, "failed to schedule timeout expiry");
+    }
+
     let target_profile = target_profile_from_user(&user);
     let embed = moderation_action_embed(
         &target_profile,
@@ -192,6 +274,7 @@ pub async fn timeout(
         "timed out",
         parsed_reason.as_deref(),
         Some(&duration_label),
+        accent_color(ctx).await,
     );
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
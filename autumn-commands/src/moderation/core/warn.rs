@@ -1,79 +1,177 @@
 use poise::serenity_prelude as serenity;
+use tracing::error;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{moderator_outranks_target, require_manage_messages};
 use crate::moderation::embeds::{
-    guild_only_message, moderation_action_embed, moderation_bot_target_message,
-    send_moderation_target_dm_for_guild, target_profile_from_user, usage_message,
+    guild_only_message, is_missing_permissions_error, moderation_action_embed,
+    moderation_bot_target_message, send_moderation_target_dm_for_guild, target_profile_from_user,
+    usage_message,
 };
 use crate::moderation::escalation_check::check_and_escalate;
-use crate::moderation::logging::create_case_and_publish;
+use crate::moderation::logging::create_case_and_publish_with_attachment;
+use crate::moderation::reason_suggestion::{ReasonChoice, replied_message, suggest_reason};
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_database::impls::warnings::record_warning;
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::impls::modlog_outbox::ModlogAttachment;
+use autumn_utils::transcript::{TranscriptMessage, render_transcript};
 
 pub const META: CommandMeta = CommandMeta {
     name: "warn",
     desc: "Issue a warning to a user.",
     category: "moderation",
-    usage: "!warn <user> [reason]",
+    usage: "!warn <user> [reason] (or reply to their message)",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!warn @user spamming", "!warn off-topic (as a reply)"],
+    related: &["warnings", "unwarn", "escalation"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+/// How much of a deleted message the case reason quotes.
+const QUOTED_MESSAGE_CHARS: usize = 150;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn warn(
     ctx: Context<'_>,
     #[description = "The user to warn"] user: Option<serenity::User>,
     #[description = "Reason for warning"]
     #[rest]
     reason: Option<String>,
+) -> Result<(), Error> {
+    issue_warning(ctx, user, reason).await
+}
+
+/// Warn a user from the Apps menu on their profile.
+#[poise::command(
+    context_menu_command = "Warn",
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn warn_user(
+    ctx: Context<'_>,
+    #[description = "The user to warn"] user: serenity::User,
+) -> Result<(), Error> {
+    issue_warning(ctx, Some(user), None).await
+}
+
+async fn issue_warning(
+    ctx: Context<'_>,
+    user: Option<serenity::User>,
+    reason: Option<String>,
 ) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
         ctx.say(guild_only_message()).await?;
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
+    // Replying to a message warns its author when no user is given.
+    let replied = replied_message(ctx);
+    let Some(user) = user.or_else(|| replied.as_ref().map(|message| message.author.clone())) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if user.bot {
+        ctx.say(moderation_bot_target_message()).await?;
         return Ok(());
     }
 
-    let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+    if !moderator_outranks_target(ctx, guild_id, user.id, "warn").await? {
         return Ok(());
+    }
+
+    let reason = match (
+        reason,
+        replied.filter(|message| message.author.id == user.id),
+    ) {
+        (Some(reason), _) => reason,
+        (None, Some(message)) => match suggest_reason(ctx, guild_id, "warn", &message).await? {
+            ReasonChoice::Accepted(reason) => reason,
+            ReasonChoice::Skipped => "No reason provided".to_owned(),
+            ReasonChoice::Cancelled => return Ok(()),
+        },
+        (None, None) => "No reason provided".to_owned(),
     };
 
+    apply_warning(ctx, guild_id, &user, &reason, None).await
+}
+
+/// Delete a message and warn its author from the message's Apps menu. The
+/// message goes into the case reason and, in full, a modlog transcript.
+#[poise::command(
+    context_menu_command = "Delete & Warn",
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
+pub async fn delete_and_warn(
+    ctx: Context<'_>,
+    #[description = "The message to delete"] message: serenity::Message,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let user = message.author.clone();
     if user.bot {
         ctx.say(moderation_bot_target_message()).await?;
         return Ok(());
     }
 
-    let reason = reason.unwrap_or_else(|| "No reason provided".to_owned());
-    record_warning(
-        &ctx.data().db,
-        guild_id.get(),
-        user.id.get(),
-        ctx.author().id.get(),
-        &reason,
-    )
-    .await?;
+    if !moderator_outranks_target(ctx, guild_id, user.id, "warn").await? {
+        return Ok(());
+    }
+
+    let transcript = message_transcript(ctx.author(), &message);
+    ctx.data()
+        .suppressed_deletes
+        .write()
+        .await
+        .insert(message.id.get());
+
+    if let Err(source) = message.delete(ctx.http()).await {
+        if !is_missing_permissions_error(&source) {
+            error!(?source, "failed to delete warned message");
+        }
+        ctx.say("I couldn't delete that message. I likely need the 'Manage Messages' permission.")
+            .await?;
+        return Ok(());
+    }
+
+    let reason = deleted_message_reason(&message);
+    apply_warning(ctx, guild_id, &user, &reason, Some(transcript)).await
+}
+
+/// Record the warning, DM the user, log the case and run escalation.
+async fn apply_warning(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    user: &serenity::User,
+    reason: &str,
+    evidence: Option<ModlogAttachment>,
+) -> Result<(), Error> {
+    ctx.data()
+        .warnings
+        .record_warning(guild_id.get(), user.id.get(), ctx.author().id.get(), reason)
+        .await?;
 
     let _ = send_moderation_target_dm_for_guild(
         ctx.http(),
-        &user,
+        user,
         guild_id,
         "warned",
-        Some(&reason),
+        Some(reason),
         None,
+        accent_color(ctx).await,
     )
     .await;
 
-    let _case_label = create_case_and_publish(
+    let _case_label = create_case_and_publish_with_attachment(
         &ctx,
         guild_id,
         NewCase {
@@ -81,22 +179,37 @@ pub async fn warn(
             target_user_id: Some(user.id.get()),
             moderator_user_id: ctx.author().id.get(),
             action: "warn",
-            reason: &reason,
+            reason,
             status: "active",
             duration_seconds: None,
         },
+        evidence,
     )
     .await;
 
     let action = "warned";
-    let target_profile = target_profile_from_user(&user);
-    let embed = moderation_action_embed(&target_profile, user.id, action, Some(&reason), None);
+    let target_profile = target_profile_from_user(user);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        action,
+        Some(reason),
+        None,
+        accent_color(ctx).await,
+    );
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
     // Check for automatic escalation (warn threshold → auto-timeout).
     let bot_user_id = ctx.cache().current_user().id.get();
-    if let Some(result) =
-        check_and_escalate(ctx.http(), &ctx.data().db, guild_id, &user, bot_user_id).await
+    if let Some(result) = check_and_escalate(
+        ctx.http(),
+        ctx.data(),
+        guild_id,
+        user,
+        Some(ctx.channel_id()),
+        bot_user_id,
+    )
+    .await
         && result.timed_out
         && let Some(secs) = result.timeout_seconds
     {
@@ -110,3 +223,52 @@ pub async fn warn(
 
     Ok(())
 }
+
+/// Case reason for a warning over a deleted message, quoting the start of it.
+fn deleted_message_reason(message: &serenity::Message) -> String {
+    let content = message
+        .content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if content.is_empty() {
+        return format!("Deleted message in <#{}>", message.channel_id);
+    }
+
+    let mut quoted: String = content.chars().take(QUOTED_MESSAGE_CHARS).collect();
+    if content.chars().count() > QUOTED_MESSAGE_CHARS {
+        quoted.push('…');
+    }
+    format!(
+        "Deleted message in <#{}>: \"{}\"",
+        message.channel_id, quoted
+    )
+}
+
+fn message_transcript(moderator: &serenity::User, message: &serenity::Message) -> ModlogAttachment {
+    let entry = TranscriptMessage {
+        author_id: message.author.id.get(),
+        author_name: message.author.tag(),
+        channel_id: None,
+        timestamp: message.timestamp.to_string(),
+        content: message.content.clone(),
+        attachments: message
+            .attachments
+            .iter()
+            .map(|attachment| format!("{} ({})", attachment.filename, attachment.url))
+            .collect(),
+    };
+
+    ModlogAttachment {
+        filename: format!("warn-{}.txt", message.id.get()),
+        content: render_transcript(
+            &format!(
+                "Message deleted in channel {} by {} ({})",
+                message.channel_id.get(),
+                moderator.tag(),
+                moderator.id.get()
+            ),
+            &[entry],
+        ),
+    }
+}
@@ -0,0 +1,78 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use crate::moderation::logging::create_case_and_publish;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::scheduled_jobs::{JOB_DEFERRED_ACTION, NewScheduledJob, schedule_job};
+
+/// Record a ban or timeout as a pending case now and leave it to the
+/// scheduler to carry out at `run_at`.
+pub(crate) async fn schedule_deferred_action(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    user: &serenity::User,
+    action: &str,
+    reason: &str,
+    duration_seconds: Option<u64>,
+    run_at: u64,
+) -> Result<(), Error> {
+    let Some(case_label) = create_case_and_publish(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(user.id.get()),
+            moderator_user_id: ctx.author().id.get(),
+            action,
+            reason,
+            status: "pending",
+            duration_seconds,
+        },
+    )
+    .await
+    else {
+        ctx.say("Failed to create the pending case. Nothing was scheduled.")
+            .await?;
+        return Ok(());
+    };
+
+    let scheduled = schedule_job(
+        &ctx.data().db,
+        NewScheduledJob {
+            guild_id: guild_id.get(),
+            kind: JOB_DEFERRED_ACTION,
+            target_user_id: Some(user.id.get()),
+            channel_id: None,
+            payload: &case_label,
+            run_at,
+            created_by: ctx.author().id.get(),
+        },
+    )
+    .await;
+
+    if let Err(source) = scheduled {
+        error!(?source, "failed to schedule deferred action");
+        ctx.say(format!(
+            "Case #{case_label} was created but I couldn't schedule it. \
+             Cancel it with `!case {case_label} cancel`."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "Scheduled {} of <@{}> for <t:{}:f> as case #{}. Cancel with `!case {} cancel`.",
+                action,
+                user.id.get(),
+                run_at,
+                case_label,
+                case_label
+            ))
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use poise::serenity_prelude as serenity;
 
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::embed::guild_embed;
 
 #[derive(Clone, Debug)]
 pub struct TargetProfile {
@@ -37,6 +37,7 @@ pub fn moderation_action_embed(
     action_past_tense: &str,
     reason: Option<&str>,
     duration: Option<&str>,
+    accent_color: Option<u32>,
 ) -> serenity::CreateEmbed {
     let reason = reason
         .unwrap_or("No reason provided")
@@ -56,9 +57,7 @@ pub fn moderation_action_embed(
         ),
     };
 
-    let mut embed = serenity::CreateEmbed::new()
-        .color(DEFAULT_EMBED_COLOR)
-        .description(description);
+    let mut embed = guild_embed(accent_color).description(description);
 
     if let Some(url) = target_profile.avatar_url.as_deref() {
         embed = embed.author(
@@ -83,6 +82,7 @@ pub fn moderation_target_dm_embed(
     action_past_tense: &str,
     reason: Option<&str>,
     duration: Option<&str>,
+    accent_color: Option<u32>,
 ) -> serenity::CreateEmbed {
     let mut details = Vec::new();
 
@@ -101,8 +101,7 @@ pub fn moderation_target_dm_embed(
         details.join("\n")
     };
 
-    serenity::CreateEmbed::new()
-        .color(DEFAULT_EMBED_COLOR)
+    guild_embed(accent_color)
         .title(format!(
             "You have been {} in {}",
             action_past_tense, guild_name
@@ -117,6 +116,7 @@ pub async fn send_moderation_target_dm(
     action_past_tense: &str,
     reason: Option<&str>,
     duration: Option<&str>,
+    accent_color: Option<u32>,
 ) -> Result<(), serenity::Error> {
     let dm_channel = target_user.create_dm_channel(http).await?;
     dm_channel
@@ -127,6 +127,7 @@ pub async fn send_moderation_target_dm(
                 action_past_tense,
                 reason,
                 duration,
+                accent_color,
             )),
         )
         .await?;
@@ -141,6 +142,7 @@ pub async fn send_moderation_target_dm_for_guild(
     action_past_tense: &str,
     reason: Option<&str>,
     duration: Option<&str>,
+    accent_color: Option<u32>,
 ) -> Result<(), serenity::Error> {
     let guild_name = match guild_id.to_partial_guild(http).await {
         Ok(guild) => guild.name,
@@ -154,6 +156,7 @@ pub async fn send_moderation_target_dm_for_guild(
         action_past_tense,
         reason,
         duration,
+        accent_color,
     )
     .await
 }
@@ -9,16 +9,16 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use poise::serenity_prelude as serenity;
 use tracing::{error, info, warn};
 
+use autumn_core::Data;
 use autumn_database::Database;
-use autumn_database::impls::cases::{NewCase, create_case};
-use autumn_database::impls::escalation::{
-    count_timeouts_in_window, count_warnings_in_window, escalation_timeout_seconds,
-    get_escalation_if_enabled,
-};
+use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::escalation::evaluate_escalation;
+use autumn_database::impls::exemptions::{is_exempt, list_exemptions};
 use autumn_database::impls::modlog_config::get_modlog_channel_id;
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::embed::guild_embed;
 use autumn_utils::formatting::{format_case_label, format_compact_duration};
 
+use crate::branding::guild_accent_color;
 use crate::moderation::embeds::send_moderation_target_dm;
 
 /// Result of an escalation check.
@@ -34,66 +34,75 @@ pub struct EscalationResult {
 /// Check whether a user should be auto-timed-out after receiving a warning.
 ///
 /// This function:
-/// 1. Checks if escalation is enabled for the guild.
-/// 2. Counts the user's warnings within the configured window.
-/// 3. If threshold is met, counts past timeouts to determine the escalation tier.
-/// 4. Applies the timeout, creates a moderation case, publishes to modlog, and DMs the user.
+/// 1. Asks [`evaluate_escalation`] whether the warning threshold is met and
+///    which tier applies.
+/// 2. Skips exempt users and roles, and warnings issued in exempt channels.
+/// 3. Applies the timeout, creates a moderation case, publishes to modlog, and DMs the user.
+///
+/// `channel_id` is where the warning was issued, if anywhere.
 ///
 /// Returns `None` if escalation is disabled or the threshold was not met.
 pub async fn check_and_escalate(
     http: &serenity::Http,
-    db: &Database,
+    data: &Data,
     guild_id: serenity::GuildId,
     target_user: &serenity::User,
+    channel_id: Option<serenity::ChannelId>,
     bot_user_id: u64,
 ) -> Option<EscalationResult> {
-    // 1. Check if escalation is enabled.
-    let config = match get_escalation_if_enabled(db, guild_id.get()).await {
-        Ok(Some(cfg)) => cfg,
-        Ok(None) => return None,
-        Err(source) => {
-            error!(?source, "failed to read escalation config");
-            return None;
-        }
-    };
+    let db = &data.db;
 
-    // 2. Count warnings in window.
-    let warn_count = match count_warnings_in_window(
-        db,
+    // 1. Check the threshold and work out the tier.
+    let decision = match evaluate_escalation(
+        data.escalation.as_ref(),
+        data.warnings.as_ref(),
+        data.cases.as_ref(),
         guild_id.get(),
         target_user.id.get(),
-        config.warn_window_seconds,
     )
     .await
     {
-        Ok(count) => count,
+        Ok(Some(decision)) => decision,
+        Ok(None) => return None,
         Err(source) => {
-            error!(?source, "failed to count warnings for escalation");
+            error!(?source, "failed to evaluate escalation");
             return None;
         }
     };
 
-    if warn_count < config.warn_threshold as i64 {
-        return None;
-    }
-
-    // 3. Count past timeouts to determine escalation tier.
-    let timeout_count = match count_timeouts_in_window(
-        db,
-        guild_id.get(),
-        target_user.id.get(),
-        config.timeout_window_seconds,
-    )
-    .await
-    {
-        Ok(count) => count,
+    // 2. Exempt users, roles and channels are never auto-timed-out.
+    let exemptions = match list_exemptions(db, guild_id.get(), "escalation").await {
+        Ok(list) => list,
         Err(source) => {
-            error!(?source, "failed to count timeouts for escalation");
-            return None;
+            error!(?source, "failed to load escalation exemptions");
+            Vec::new()
         }
     };
 
-    let timeout_secs = escalation_timeout_seconds(timeout_count);
+    if !exemptions.is_empty() {
+        let role_ids: Vec<u64> = if exemptions.iter().any(|e| e.target_kind == "role") {
+            match guild_id.member(http, target_user.id).await {
+                Ok(member) => member.roles.iter().map(|role_id| role_id.get()).collect(),
+                Err(_) => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        };
+
+        if is_exempt(
+            &exemptions,
+            target_user.id.get(),
+            channel_id.map(serenity::ChannelId::get),
+            &role_ids,
+        ) {
+            return None;
+        }
+    }
+
+    let config = decision.config;
+    let warn_count = decision.warn_count;
+    let timeout_count = decision.timeout_count;
+    let timeout_secs = decision.timeout_seconds;
 
     info!(
         user_id = %target_user.id,
@@ -104,7 +113,7 @@ pub async fn check_and_escalate(
         "escalation triggered: auto-timeout"
     );
 
-    // 4. Apply the timeout.
+    // 3. Apply the timeout.
     let timeout_duration = Duration::from_secs(timeout_secs as u64);
     let until_system_time = SystemTime::now()
         .checked_add(timeout_duration)
@@ -128,7 +137,7 @@ pub async fn check_and_escalate(
         }
     }
 
-    // 5. Create a moderation case.
+    // 4. Create a moderation case.
     let reason = format!(
         "Auto-escalation: {} warning(s) in {}",
         warn_count,
@@ -145,7 +154,7 @@ pub async fn check_and_escalate(
         duration_seconds: Some(timeout_secs as u64),
     };
 
-    let case = match create_case(db, new_case).await {
+    let case = match data.cases.create_case(new_case).await {
         Ok(case) => case,
         Err(source) => {
             error!(?source, "failed to create auto-timeout case");
@@ -157,14 +166,14 @@ pub async fn check_and_escalate(
         }
     };
 
-    // 6. Publish to modlog channel.
+    // 5. Publish to modlog channel.
     if let Err(source) =
         publish_auto_timeout_to_modlog(http, db, guild_id, &case, &reason, timeout_secs).await
     {
         error!(?source, "failed to publish auto-timeout case to modlog");
     }
 
-    // 7. DM the user.
+    // 6. DM the user.
     let guild_name = match guild_id.to_partial_guild(http).await {
         Ok(guild) => guild.name,
         Err(_) => format!("Server {}", guild_id.get()),
@@ -177,6 +186,7 @@ pub async fn check_and_escalate(
         "automatically timed out",
         Some(&reason),
         Some(&format_compact_duration(timeout_secs as u64)),
+        guild_accent_color(db, guild_id.get()).await,
     )
     .await;
 
@@ -225,8 +235,7 @@ async fn publish_auto_timeout_to_modlog(
     let title = format!("Auto Timeout - #{}", case_label);
     let description = fields.join("\n");
 
-    let embed = serenity::CreateEmbed::new()
-        .color(DEFAULT_EMBED_COLOR)
+    let embed = guild_embed(guild_accent_color(db, guild_id.get()).await)
         .title(title)
         .description(description);
 
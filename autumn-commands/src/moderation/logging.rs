@@ -2,46 +2,121 @@ use tracing::error;
 
 use poise::serenity_prelude as serenity;
 
-use crate::moderation::embeds::fetch_target_profile;
+use crate::moderation::embeds::{TargetProfile, fetch_target_profile};
+use crate::moderation::modlog_publisher::{deliver_modlog_outbox_entry, encode_modlog_embed};
 use autumn_core::Context;
+use autumn_database::Database;
 use autumn_database::impls::cases::{NewCase, create_case};
-use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_database::impls::modlog_config::{get_case_review_sla_seconds, get_modlog_channel_id};
+use autumn_database::impls::modlog_outbox::{ModlogAttachment, create_case_with_modlog_outbox};
+use autumn_database::impls::modlog_template::get_modlog_layout;
+use autumn_database::impls::scheduled_jobs::{JOB_CASE_REVIEW, NewScheduledJob, schedule_job};
 use autumn_database::model::cases::CaseSummary;
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
 use autumn_utils::formatting::{
     action_display_name, action_past_tense, format_case_label, format_compact_duration,
+    is_filter_action,
 };
+use autumn_utils::modlog_template::{ModlogField, ModlogLayout, build_modlog_embed};
+use autumn_utils::time::now_unix_secs;
 
 /// Orchestrator: create moderation case and publish to optional modlog channel.
+///
+/// The modlog message is queued in the same transaction as the case and
+/// retried in the background if the first delivery fails.
 pub async fn create_case_and_publish(
     ctx: &Context<'_>,
     guild_id: serenity::GuildId,
     new_case: NewCase<'_>,
 ) -> Option<String> {
-    let case = match create_case(&ctx.data().db, new_case).await {
-        Ok(case) => case,
+    create_case_and_publish_with_attachment(ctx, guild_id, new_case, None).await
+}
+
+/// Like [`create_case_and_publish`], attaching a file to the modlog message.
+/// The attachment is dropped when no modlog channel is configured.
+pub async fn create_case_and_publish_with_attachment(
+    ctx: &Context<'_>,
+    guild_id: serenity::GuildId,
+    new_case: NewCase<'_>,
+    attachment: Option<ModlogAttachment>,
+) -> Option<String> {
+    publish_case(ctx.http(), &ctx.data().db, guild_id, new_case, attachment).await
+}
+
+/// Context-free core of [`create_case_and_publish`], for background workers.
+///
+/// `open` cases also get a review reminder queued when the guild has a review
+/// window configured.
+pub async fn publish_case(
+    http: &serenity::Http,
+    db: &Database,
+    guild_id: serenity::GuildId,
+    new_case: NewCase<'_>,
+    attachment: Option<ModlogAttachment>,
+) -> Option<String> {
+    let open = new_case.status == "open";
+    let target_user_id = new_case.target_user_id;
+    let moderator_user_id = new_case.moderator_user_id;
+
+    let case_label = create_and_deliver_case(http, db, guild_id, new_case, attachment).await?;
+
+    if open {
+        schedule_case_review(
+            db,
+            guild_id.get(),
+            &case_label,
+            target_user_id,
+            moderator_user_id,
+        )
+        .await;
+    }
+
+    Some(case_label)
+}
+
+async fn schedule_case_review(
+    db: &Database,
+    guild_id: u64,
+    case_label: &str,
+    target_user_id: Option<u64>,
+    moderator_user_id: u64,
+) {
+    let sla_seconds = match get_case_review_sla_seconds(db, guild_id).await {
+        Ok(Some(sla_seconds)) => sla_seconds,
+        Ok(None) => return,
         Err(source) => {
-            error!(?source, "failed to create moderation case");
-            return None;
+            error!(?source, "failed to read case review window");
+            return;
         }
     };
 
-    if let Err(source) = publish_case_to_modlog_channel(ctx, guild_id, &case).await {
-        error!(
-            ?source,
-            "failed to publish case to configured modlog channel"
-        );
-    }
+    let scheduled = schedule_job(
+        db,
+        NewScheduledJob {
+            guild_id,
+            kind: JOB_CASE_REVIEW,
+            target_user_id,
+            channel_id: None,
+            payload: case_label,
+            run_at: now_unix_secs().saturating_add(sla_seconds),
+            created_by: moderator_user_id,
+        },
+    )
+    .await;
 
-    Some(format_case_label(&case.case_code, case.action_case_number))
+    if let Err(source) = scheduled {
+        error!(?source, "failed to schedule case review reminder");
+    }
 }
 
-async fn publish_case_to_modlog_channel(
-    ctx: &Context<'_>,
+async fn create_and_deliver_case(
+    http: &serenity::Http,
+    db: &Database,
     guild_id: serenity::GuildId,
-    case: &CaseSummary,
-) -> Result<(), serenity::Error> {
-    let channel_id = match get_modlog_channel_id(&ctx.data().db, guild_id.get()).await {
+    new_case: NewCase<'_>,
+    attachment: Option<ModlogAttachment>,
+) -> Option<String> {
+    let pending = new_case.status == "pending";
+    let channel_id = match get_modlog_channel_id(db, guild_id.get()).await {
         Ok(channel_id) => channel_id,
         Err(source) => {
             error!(?source, "failed to read modlog channel config");
@@ -50,47 +125,107 @@ async fn publish_case_to_modlog_channel(
     };
 
     let Some(channel_id) = channel_id else {
-        return Ok(());
+        return match create_case(db, new_case).await {
+            Ok(case) => Some(format_case_label(&case.case_code, case.action_case_number)),
+            Err(source) => {
+                error!(?source, "failed to create moderation case");
+                None
+            }
+        };
+    };
+
+    let layout = match get_modlog_layout(db, guild_id.get()).await {
+        Ok(layout) => layout,
+        Err(source) => {
+            error!(?source, "failed to read modlog template");
+            None
+        }
     };
 
+    let target_profile = match new_case.target_user_id {
+        Some(target_user_id) => {
+            Some(fetch_target_profile(http, serenity::UserId::new(target_user_id)).await)
+        }
+        None => None,
+    };
+
+    let (case, outbox_id) =
+        match create_case_with_modlog_outbox(db, new_case, channel_id, attachment, |case| {
+            encode_modlog_embed(&modlog_case_embed(
+                case,
+                target_profile.as_ref(),
+                layout.as_ref(),
+                pending,
+            ))
+        })
+        .await
+        {
+            Ok(created) => created,
+            Err(source) => {
+                error!(?source, "failed to create moderation case");
+                return None;
+            }
+        };
+
+    deliver_modlog_outbox_entry(http, db, outbox_id).await;
+
+    Some(format_case_label(&case.case_code, case.action_case_number))
+}
+
+fn modlog_case_embed(
+    case: &CaseSummary,
+    target_profile: Option<&TargetProfile>,
+    layout: Option<&ModlogLayout>,
+    pending: bool,
+) -> serenity::CreateEmbed {
     let action_name = action_display_name(&case.action);
     let case_label = format_case_label(&case.case_code, case.action_case_number);
     let action_past = action_past_tense(&case.action);
     let mut fields = Vec::new();
 
     if let Some(target_user_id) = case.target_user_id {
-        fields.push(format!("**Target :** <@{}>", target_user_id));
+        fields.push(ModlogField::new(
+            "target",
+            format!("**Target :** <@{}>", target_user_id),
+        ));
     }
 
     // Skip reason for purge actions (they never have a meaningful reason).
-    // For word filter actions, show "Violation" instead of "Reason".
-    let is_word_filter = case.action.starts_with("word_filter_");
+    // For automod filter actions, show "Violation" instead of "Reason".
     if case.action != "purge" {
-        let label = if is_word_filter {
+        let label = if is_filter_action(&case.action) {
             "Violation"
         } else {
             "Reason"
         };
-        fields.push(format!(
-            "**{} :** {}",
-            label,
-            case.reason.replace('@', "@\u{200B}")
+        fields.push(ModlogField::new(
+            "reason",
+            format!("**{} :** {}", label, case.reason.replace('@', "@\u{200B}")),
         ));
     }
 
     if let Some(duration_seconds) = case.duration_seconds {
-        fields.push(format!(
-            "**Duration :** {}",
-            format_compact_duration(duration_seconds)
+        fields.push(ModlogField::new(
+            "duration",
+            format!(
+                "**Duration :** {}",
+                format_compact_duration(duration_seconds)
+            ),
         ));
     }
 
     // Blank line separator before metadata section.
-    fields.push(String::new());
+    fields.push(ModlogField::separator());
 
-    fields.push(format!("**Moderator :** <@{}>", case.moderator_user_id));
+    fields.push(ModlogField::new(
+        "moderator",
+        format!("**Moderator :** <@{}>", case.moderator_user_id),
+    ));
 
-    fields.push(format!("**When :** <t:{}:R>", case.created_at));
+    fields.push(ModlogField::new(
+        "when",
+        format!("**When :** <t:{}:R>", case.created_at),
+    ));
 
     let title = if case.target_user_id.is_some() {
         format!("#{}", case_label)
@@ -98,35 +233,24 @@ async fn publish_case_to_modlog_channel(
         format!("{} - #{}", action_name, case_label)
     };
 
-    let description = fields.join("\n");
-
-    let mut embed = serenity::CreateEmbed::new()
-        .color(DEFAULT_EMBED_COLOR)
-        .title(title)
-        .description(description);
+    let mut embed = build_modlog_embed(
+        title,
+        &fields,
+        layout,
+        &[("{case}", &case_label), ("{action}", &action_name)],
+    );
 
-    if let Some(target_user_id) = case.target_user_id {
-        let target_profile =
-            fetch_target_profile(ctx.http(), serenity::UserId::new(target_user_id)).await;
-        if let Some(url) = target_profile.avatar_url {
-            embed = embed.author(
-                serenity::CreateEmbedAuthor::new(format!(
-                    "{} has been {}",
-                    target_profile.display_name, action_past
-                ))
-                .icon_url(url),
-            );
-        } else {
-            embed = embed.author(serenity::CreateEmbedAuthor::new(format!(
-                "{} has been {}",
-                target_profile.display_name, action_past
-            )));
-        }
+    if let Some(target_profile) = target_profile {
+        let tense = if pending { "will be" } else { "has been" };
+        let author = serenity::CreateEmbedAuthor::new(format!(
+            "{} {} {}",
+            target_profile.display_name, tense, action_past
+        ));
+        embed = match &target_profile.avatar_url {
+            Some(url) => embed.author(author.icon_url(url)),
+            None => embed.author(author),
+        };
     }
 
-    serenity::ChannelId::new(channel_id)
-        .send_message(ctx.http(), serenity::CreateMessage::new().embed(embed))
-        .await?;
-
-    Ok(())
+    embed
 }
@@ -7,14 +7,32 @@ mod core_group;
 #[path = "reversals/mod.rs"]
 mod reversals_group;
 
-pub use case_group::{case, modlogs, notes, userlogs, warnings};
+pub use case_group::{
+    case, editsnipe, modlogs, notes, remind, reports, search, snipe, summarize, unwatch, userlogs,
+    warnings, watch, watchlist,
+};
 pub use config_group::{
-    aitoggle, escalation, modlogchannel, permissions, setup, userlogchannel, wordfilter,
+    ai, aitoggle, alias, antiraid, antispam, attachmentfilter, backup, bansync, blacklistmode,
+    capsfilter, casereview, command, config, dualapproval, embedcolor, escalation, imagefilter,
+    import, invitefilter, linkfilter, modhierarchy, modlogchannel, modlogtemplate, modmail,
+    namefilter, permalertchannel, permcheck, permissions, permlevels, privacy, purgetranscripts,
+    raidmode, serverlogchannel, setup, tasks, userlogchannel, warndecay, webhooks, wordfilter,
+};
+pub use core_group::{
+    archive, ban, kick, purge, quarantine, shadowmute, temprole, terminate, timeout, warn,
 };
-pub use core_group::{ban, kick, purge, terminate, timeout, warn};
 pub use embeds::send_moderation_target_dm_for_guild;
-pub use reversals_group::{unban, untimeout, unwarn};
+pub use reversals_group::{release, unban, unshadowmute, untimeout, unwarn};
 
+mod approval;
+mod ban_sync;
+mod deferred;
 pub(crate) mod embeds;
 pub mod escalation_check;
 mod logging;
+pub mod modlog_publisher;
+mod reason_suggestion;
+mod recurring_tasks;
+pub mod retention;
+pub mod scheduler;
+pub mod webhook_dispatcher;
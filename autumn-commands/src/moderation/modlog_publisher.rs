@@ -0,0 +1,173 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use crate::moderation::embeds::is_missing_permissions_error;
+use autumn_database::Database;
+use autumn_database::impls::modlog_config::{
+    clear_modlog_webhook, get_modlog_webhook, set_modlog_webhook,
+};
+use autumn_database::impls::modlog_outbox::{
+    ModlogOutboxEntry, complete_modlog_outbox, fail_modlog_outbox, get_modlog_outbox_entry,
+    list_due_modlog_outbox,
+};
+
+const MODLOG_WEBHOOK_NAME: &str = "Autumn Modlog";
+const OUTBOX_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+const OUTBOX_RETRY_BATCH: i64 = 25;
+
+/// Serialize an embed for the modlog outbox.
+pub fn encode_modlog_embed(embed: &serenity::CreateEmbed) -> anyhow::Result<String> {
+    Ok(serde_json::to_string(embed)?)
+}
+
+/// Try to deliver a queued modlog entry right away. Failures stay queued for
+/// the retry worker.
+pub async fn deliver_modlog_outbox_entry(http: &serenity::Http, db: &Database, id: i64) {
+    match get_modlog_outbox_entry(db, id).await {
+        Ok(Some(entry)) => deliver_entry(http, db, entry).await,
+        Ok(None) => {}
+        Err(source) => error!(?source, "failed to load modlog outbox entry"),
+    }
+}
+
+/// Periodically retry modlog entries that failed to deliver.
+pub async fn run_modlog_outbox_worker(http: Arc<serenity::Http>, db: Database) {
+    let mut interval = tokio::time::interval(OUTBOX_RETRY_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let entries = match list_due_modlog_outbox(&db, OUTBOX_RETRY_BATCH).await {
+            Ok(entries) => entries,
+            Err(source) => {
+                error!(?source, "failed to list due modlog outbox entries");
+                continue;
+            }
+        };
+
+        for entry in entries {
+            deliver_entry(&http, &db, entry).await;
+        }
+    }
+}
+
+async fn deliver_entry(http: &serenity::Http, db: &Database, entry: ModlogOutboxEntry) {
+    let result = match serde_json::from_str::<serenity::Embed>(&entry.payload) {
+        Ok(embed) => publish_modlog_embed(http, db, &entry, embed.into()).await,
+        Err(source) => Err(source.into()),
+    };
+
+    let outcome = match result {
+        Ok(()) => complete_modlog_outbox(db, entry.id).await,
+        Err(source) => {
+            warn!(
+                ?source,
+                outbox_id = entry.id,
+                attempts = entry.attempts + 1,
+                "modlog delivery failed; queued for retry"
+            );
+            fail_modlog_outbox(db, entry.id, entry.attempts, &source.to_string()).await
+        }
+    };
+
+    if let Err(source) = outcome {
+        error!(?source, "failed to update modlog outbox entry");
+    }
+}
+
+/// Publish through the channel's modlog webhook, creating (or recreating) it
+/// as needed. Falls back to a regular bot message when webhooks can't be managed.
+async fn publish_modlog_embed(
+    http: &serenity::Http,
+    db: &Database,
+    entry: &ModlogOutboxEntry,
+    embed: serenity::CreateEmbed,
+) -> anyhow::Result<()> {
+    let channel_id = serenity::ChannelId::new(entry.channel_id);
+    let files: Vec<serenity::CreateAttachment> = entry
+        .attachment
+        .iter()
+        .map(|attachment| {
+            serenity::CreateAttachment::bytes(
+                attachment.content.as_bytes().to_vec(),
+                attachment.filename.clone(),
+            )
+        })
+        .collect();
+
+    for _ in 0..2 {
+        let Some((webhook_id, token)) =
+            modlog_webhook(http, db, entry.guild_id, channel_id).await?
+        else {
+            channel_id
+                .send_message(
+                    http,
+                    serenity::CreateMessage::new().embed(embed).add_files(files),
+                )
+                .await?;
+            return Ok(());
+        };
+
+        let builder = serenity::ExecuteWebhook::new().embed(embed.clone());
+        match http
+            .execute_webhook(
+                serenity::WebhookId::new(webhook_id),
+                None,
+                &token,
+                false,
+                files.clone(),
+                &builder,
+            )
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(source) if is_unknown_webhook_error(&source) => {
+                // Someone deleted the webhook; forget it and create a new one.
+                clear_modlog_webhook(db, entry.guild_id).await?;
+            }
+            Err(source) => return Err(source.into()),
+        }
+    }
+
+    anyhow::bail!("modlog webhook kept disappearing")
+}
+
+/// The stored webhook for the modlog channel, creating one if missing.
+/// Returns `None` when the bot lacks Manage Webhooks.
+async fn modlog_webhook(
+    http: &serenity::Http,
+    db: &Database,
+    guild_id: u64,
+    channel_id: serenity::ChannelId,
+) -> anyhow::Result<Option<(u64, String)>> {
+    if let Some(webhook) = get_modlog_webhook(db, guild_id).await? {
+        return Ok(Some(webhook));
+    }
+
+    let webhook = match channel_id
+        .create_webhook(http, serenity::CreateWebhook::new(MODLOG_WEBHOOK_NAME))
+        .await
+    {
+        Ok(webhook) => webhook,
+        Err(source) if is_missing_permissions_error(&source) => return Ok(None),
+        Err(source) => return Err(source.into()),
+    };
+
+    let url = webhook.url()?;
+    let Some(token) = url.rsplit('/').next().filter(|token| !token.is_empty()) else {
+        anyhow::bail!("created modlog webhook has no token");
+    };
+
+    set_modlog_webhook(db, guild_id, webhook.id.get(), token).await?;
+    Ok(Some((webhook.id.get(), token.to_owned())))
+}
+
+fn is_unknown_webhook_error(source: &serenity::Error) -> bool {
+    matches!(
+        source,
+        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
+            if response.status_code.as_u16() == 404 || response.error.code == 10015
+    )
+}
@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use tracing::warn;
+
+use crate::branding::accent_color;
+use autumn_core::{Context, Error};
+use autumn_database::impls::ai_config::get_llm_suggest_reasons;
+use autumn_utils::embed::guild_embed;
+
+const BUTTON_TIMEOUT_SECS: u64 = 60;
+const MODAL_TIMEOUT_SECS: u64 = 300;
+
+/// How the moderator answered a suggested reason.
+pub(crate) enum ReasonChoice {
+    /// Use this reason, as suggested or after editing.
+    Accepted(String),
+    /// No suggestion was offered; carry on without a reason.
+    Skipped,
+    /// The moderator cancelled or didn't answer; the action must not go ahead.
+    Cancelled,
+}
+
+/// The message a prefix command was sent in reply to.
+pub(crate) fn replied_message(ctx: Context<'_>) -> Option<serenity::Message> {
+    match ctx {
+        poise::Context::Prefix(prefix) => prefix.msg.referenced_message.as_deref().cloned(),
+        poise::Context::Application(_) => None,
+    }
+}
+
+/// Ask the LLM for a reason based on `message` and let the moderator confirm,
+/// edit or cancel it. Skipped when the guild hasn't enabled suggestions, no
+/// LLM is configured, or the model fails.
+pub(crate) async fn suggest_reason(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    action: &str,
+    message: &serenity::Message,
+) -> Result<ReasonChoice, Error> {
+    let Some(llm) = ctx.data().llm.as_ref() else {
+        return Ok(ReasonChoice::Skipped);
+    };
+
+    if message.content.trim().is_empty()
+        || !get_llm_suggest_reasons(&ctx.data().db, guild_id.get()).await?
+    {
+        return Ok(ReasonChoice::Skipped);
+    }
+
+    let suggestion = match llm.suggest_case_reason(action, &message.content).await {
+        Ok(suggestion) if !suggestion.is_empty() => suggestion,
+        Ok(_) => return Ok(ReasonChoice::Skipped),
+        Err(source) => {
+            warn!(?source, "failed to suggest a case reason");
+            return Ok(ReasonChoice::Skipped);
+        }
+    };
+
+    let ctx_id = ctx.id();
+    let confirm_id = format!("{}_reason_confirm", ctx_id);
+    let edit_id = format!("{}_reason_edit", ctx_id);
+    let cancel_id = format!("{}_reason_cancel", ctx_id);
+    let modal_id = format!("{}_reason_modal", ctx_id);
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Suggested Reason")
+        .description(suggestion.replace('@', "@\u{200B}"))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Written by the LLM from the replied message. Confirm, edit or cancel.",
+        ));
+
+    let buttons = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&confirm_id)
+            .label("Confirm")
+            .style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new(&edit_id)
+            .label("Edit")
+            .style(serenity::ButtonStyle::Secondary),
+        serenity::CreateButton::new(&cancel_id)
+            .label("Cancel")
+            .style(serenity::ButtonStyle::Secondary),
+    ]);
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(vec![buttons]),
+        )
+        .await?;
+    let prompt = reply.message().await?.into_owned();
+
+    let Some(interaction) = prompt
+        .await_component_interaction(ctx)
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(BUTTON_TIMEOUT_SECS))
+        .await
+    else {
+        close_prompt(ctx, &prompt, "Timed out").await?;
+        return Ok(ReasonChoice::Cancelled);
+    };
+
+    if interaction.data.custom_id == cancel_id {
+        respond_and_close(ctx, &interaction, "Cancelled.").await?;
+        return Ok(ReasonChoice::Cancelled);
+    }
+
+    if interaction.data.custom_id == confirm_id {
+        respond_and_close(ctx, &interaction, "Using the suggested reason.").await?;
+        return Ok(ReasonChoice::Accepted(suggestion));
+    }
+
+    let modal = serenity::CreateModal::new(&modal_id, "Edit Reason").components(vec![
+        serenity::CreateActionRow::InputText(
+            serenity::CreateInputText::new(serenity::InputTextStyle::Paragraph, "Reason", "reason")
+                .value(suggestion)
+                .max_length(500),
+        ),
+    ]);
+    interaction
+        .create_response(
+            ctx.http(),
+            serenity::CreateInteractionResponse::Modal(modal),
+        )
+        .await?;
+
+    let Some(submission) = serenity::ModalInteractionCollector::new(ctx)
+        .custom_ids(vec![modal_id])
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(MODAL_TIMEOUT_SECS))
+        .await
+    else {
+        close_prompt(ctx, &prompt, "Timed out").await?;
+        return Ok(ReasonChoice::Cancelled);
+    };
+
+    let edited = submission
+        .data
+        .components
+        .iter()
+        .flat_map(|row| &row.components)
+        .find_map(|component| match component {
+            serenity::ActionRowComponent::InputText(input) if input.custom_id == "reason" => {
+                input.value.clone()
+            }
+            _ => None,
+        })
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty());
+
+    let (content, choice) = match edited {
+        Some(edited) => ("Using the edited reason.", ReasonChoice::Accepted(edited)),
+        None => ("Cancelled: the reason was empty.", ReasonChoice::Cancelled),
+    };
+    submission
+        .create_response(
+            ctx.http(),
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .embeds(vec![])
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    Ok(choice)
+}
+
+async fn respond_and_close(
+    ctx: Context<'_>,
+    interaction: &serenity::ComponentInteraction,
+    content: &str,
+) -> Result<(), Error> {
+    interaction
+        .create_response(
+            ctx.http(),
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .embeds(vec![])
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn close_prompt(
+    ctx: Context<'_>,
+    prompt: &serenity::Message,
+    content: &str,
+) -> Result<(), Error> {
+    prompt
+        .channel_id
+        .edit_message(
+            ctx.http(),
+            prompt.id,
+            serenity::EditMessage::new()
+                .content(content)
+                .embeds(vec![])
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use poise::serenity_prelude as serenity;
+
+use crate::branding::guild_accent_color;
+use crate::moderation::logging::publish_case;
+use autumn_core::SuppressedDeletes;
+use autumn_database::Database;
+use autumn_database::impls::cases::{NewCase, count_cases_by_action_since};
+use autumn_database::impls::command_usage::get_command_usage_stats;
+use autumn_database::impls::recurring_tasks::{
+    RecurringTask, TASK_AUTO_PURGE, TASK_MODLOG_DIGEST, TASK_STATS_SUMMARY,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::{action_display_name, format_compact_duration};
+use autumn_utils::time::now_unix_secs;
+
+/// Most messages one auto-purge run deletes.
+const MAX_AUTO_PURGE: usize = 500;
+/// Discord refuses to bulk delete messages older than two weeks.
+const BULK_DELETE_MAX_AGE_SECS: i64 = 14 * 24 * 60 * 60;
+
+/// Carry out one run of `task`, covering the last `period_secs`.
+pub(crate) async fn run_recurring_task(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    suppressed_deletes: &SuppressedDeletes,
+    task: &RecurringTask,
+    period_secs: u64,
+) -> anyhow::Result<()> {
+    let channel_id = serenity::ChannelId::new(task.channel_id);
+    let since = now_unix_secs().saturating_sub(period_secs);
+
+    let embed = match task.kind.as_str() {
+        TASK_MODLOG_DIGEST => modlog_digest_embed(db, task.guild_id, since, period_secs).await?,
+        TASK_STATS_SUMMARY => stats_summary_embed(db, task.guild_id, since, period_secs).await?,
+        TASK_AUTO_PURGE => {
+            return auto_purge(http, db, bot_user_id, suppressed_deletes, task).await;
+        }
+        _ => return Ok(()),
+    };
+
+    channel_id
+        .send_message(http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+    Ok(())
+}
+
+async fn modlog_digest_embed(
+    db: &Database,
+    guild_id: u64,
+    since: u64,
+    period_secs: u64,
+) -> anyhow::Result<serenity::CreateEmbed> {
+    let counts = count_cases_by_action_since(db, guild_id, since).await?;
+    let total: u64 = counts.iter().map(|(_, count)| count).sum();
+
+    let mut lines = vec![
+        format!("**Period :** last {}", format_compact_duration(period_secs)),
+        format!("**Total cases :** {}", total),
+    ];
+    if !counts.is_empty() {
+        lines.push(String::new());
+        lines.extend(
+            counts
+                .iter()
+                .map(|(action, count)| format!("**{} :** {}", action_display_name(action), count)),
+        );
+    }
+
+    Ok(guild_embed(guild_accent_color(db, guild_id).await)
+        .title("Modlog Digest")
+        .description(lines.join("\n")))
+}
+
+async fn stats_summary_embed(
+    db: &Database,
+    guild_id: u64,
+    since: u64,
+    period_secs: u64,
+) -> anyhow::Result<serenity::CreateEmbed> {
+    let counts = count_cases_by_action_since(db, guild_id, since).await?;
+    let cases: u64 = counts.iter().map(|(_, count)| count).sum();
+    let usage = get_command_usage_stats(db, guild_id, since, 3).await?;
+
+    let mut lines = vec![
+        format!("**Period :** last {}", format_compact_duration(period_secs)),
+        format!("**Cases :** {}", cases),
+        format!(
+            "**Commands run :** {} ({} failed)",
+            usage.total, usage.errors
+        ),
+    ];
+    if !usage.commands.is_empty() {
+        let top: Vec<String> = usage
+            .commands
+            .iter()
+            .map(|command| format!("`{}` ({})", command.command, command.total))
+            .collect();
+        lines.push(format!("**Top commands :** {}", top.join(", ")));
+    }
+
+    Ok(guild_embed(guild_accent_color(db, guild_id).await)
+        .title("Stats Summary")
+        .description(lines.join("\n")))
+}
+
+/// Bulk delete unpinned messages young enough for the bulk endpoint and log
+/// a purge case.
+async fn auto_purge(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    suppressed_deletes: &SuppressedDeletes,
+    task: &RecurringTask,
+) -> anyhow::Result<()> {
+    let channel_id = serenity::ChannelId::new(task.channel_id);
+    let cutoff = serenity::Timestamp::now().unix_timestamp() - BULK_DELETE_MAX_AGE_SECS;
+
+    let mut ids = Vec::new();
+    let mut before: Option<serenity::MessageId> = None;
+    'pages: while ids.len() < MAX_AUTO_PURGE {
+        let mut request = serenity::GetMessages::new().limit(100);
+        if let Some(before) = before {
+            request = request.before(before);
+        }
+        let page = channel_id.messages(http, request).await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(last.id);
+
+        for message in &page {
+            if message.timestamp.unix_timestamp() <= cutoff {
+                break 'pages;
+            }
+            if !message.pinned {
+                ids.push(message.id);
+            }
+        }
+        if page.len() < 100 {
+            break;
+        }
+    }
+    ids.truncate(MAX_AUTO_PURGE);
+
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    {
+        let mut suppressed = suppressed_deletes.write().await;
+        suppressed.extend(ids.iter().map(|id| id.get()));
+    }
+
+    for chunk in ids.chunks(100) {
+        if let [id] = chunk {
+            channel_id.delete_message(http, *id).await?;
+        } else {
+            channel_id.delete_messages(http, chunk).await?;
+        }
+    }
+
+    let reason = format!(
+        "Scheduled auto-purge of {} message(s) in <#{}>",
+        ids.len(),
+        task.channel_id
+    );
+    publish_case(
+        http,
+        db,
+        serenity::GuildId::new(task.guild_id),
+        NewCase {
+            guild_id: task.guild_id,
+            target_user_id: None,
+            moderator_user_id: bot_user_id.get(),
+            action: "purge",
+            reason: &reason,
+            status: "active",
+            duration_seconds: None,
+        },
+        None,
+    )
+    .await;
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{error, info};
+
+use autumn_core::storage::{AttachmentStore, message_attachment_prefix};
+use autumn_database::Database;
+use autumn_database::impls::retention::{RetentionPolicy, SnapshotMessage, apply_retention};
+
+const RETENTION_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Periodically delete or anonymize data older than `policy` allows,
+/// along with the archived attachments of pruned message snapshots.
+pub async fn run_retention_worker(
+    db: Database,
+    policy: RetentionPolicy,
+    attachment_store: Option<Arc<dyn AttachmentStore>>,
+) {
+    let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        match apply_retention(&db, &policy).await {
+            Ok(report) => {
+                discard_archived_attachments(
+                    attachment_store.as_deref(),
+                    &report.message_snapshots,
+                )
+                .await;
+                info!(
+                    user_logs = report.user_logs,
+                    message_snapshots = report.message_snapshots.len(),
+                    llm_chat_messages = report.llm_chat_messages,
+                    anonymized_cases = report.anonymized_cases,
+                    command_invocations = report.command_invocations,
+                    "Retention pass complete."
+                );
+            }
+            Err(source) => error!(?source, "failed to apply retention policy"),
+        }
+    }
+}
+
+/// Remove the archived attachments of messages whose snapshots are gone.
+pub(crate) async fn discard_archived_attachments(
+    store: Option<&dyn AttachmentStore>,
+    messages: &[SnapshotMessage],
+) {
+    let Some(store) = store else {
+        return;
+    };
+
+    for message in messages {
+        let prefix =
+            message_attachment_prefix(message.guild_id, message.channel_id, message.message_id);
+        if let Err(source) = store.delete_prefix(&prefix).await {
+            error!(?source, "failed to discard archived attachments");
+        }
+    }
+}
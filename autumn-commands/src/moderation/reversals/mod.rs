@@ -1,3 +1,5 @@
+pub mod release;
 pub mod unban;
+pub mod unshadowmute;
 pub mod untimeout;
 pub mod unwarn;
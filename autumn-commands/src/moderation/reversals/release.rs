@@ -0,0 +1,199 @@
+use tracing::error;
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_manage_roles;
+use crate::moderation::embeds::{
+    guild_only_message, is_missing_permissions_error, moderation_action_embed,
+    send_moderation_target_dm_for_guild, target_profile_from_user, usage_message,
+};
+use crate::moderation::logging::create_case_and_publish;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::quarantine::{
+    delete_quarantine, get_quarantine, get_quarantine_role_id,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "release",
+    desc: "Release a quarantined member and restore their roles.",
+    category: "moderation",
+    usage: "!release <user> [reason]",
+    required_permissions: Some(serenity::Permissions::MANAGE_ROLES),
+    examples: &["!release @user account secured"],
+    related: &["quarantine"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_roles"
+)]
+pub async fn release(
+    ctx: Context<'_>,
+    #[description = "The member to release"] user: Option<serenity::User>,
+    #[description = "Reason for the release"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let Some(quarantine) = get_quarantine(db, guild_id.get(), user.id.get()).await? else {
+        ctx.say("That member isn't quarantined.").await?;
+        return Ok(());
+    };
+
+    let Ok(member) = guild_id.member(ctx.http(), user.id).await else {
+        ctx.say(
+            "That user isn't in this server. Their stored roles are kept for when they return.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let quarantine_role = get_quarantine_role_id(db, guild_id.get())
+        .await?
+        .map(serenity::RoleId::new);
+    let roles = guild_id.roles(ctx.http()).await?;
+
+    let (restored, missing) = restored_roles(
+        &member.roles,
+        quarantine_role,
+        &quarantine.role_ids,
+        |role_id| roles.get(&role_id).is_some_and(|role| !role.managed),
+    );
+
+    let reason = reason.as_deref().unwrap_or("No reason provided");
+    let edit = serenity::EditMember::new()
+        .roles(restored)
+        .audit_log_reason(reason);
+    if let Err(source) = guild_id.edit_member(ctx.http(), user.id, edit).await {
+        if !is_missing_permissions_error(&source) {
+            error!(?source, "quarantine release role update failed");
+        }
+        ctx.say("I couldn't restore that member's roles. Check role hierarchy and permissions.")
+            .await?;
+        return Ok(());
+    }
+
+    delete_quarantine(db, guild_id.get(), user.id.get()).await?;
+
+    let _ = send_moderation_target_dm_for_guild(
+        ctx.http(),
+        &user,
+        guild_id,
+        "released from quarantine",
+        Some(reason),
+        None,
+        accent_color(ctx).await,
+    )
+    .await;
+
+    let _case_label = create_case_and_publish(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(user.id.get()),
+            moderator_user_id: ctx.author().id.get(),
+            action: "release",
+            reason,
+            status: "active",
+            duration_seconds: None,
+        },
+    )
+    .await;
+
+    let target_profile = target_profile_from_user(&user);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        "released from quarantine",
+        Some(reason),
+        None,
+        accent_color(ctx).await,
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    if missing > 0 {
+        ctx.say(format!(
+            "{} stored role(s) no longer exist or can't be assigned and were skipped.",
+            missing
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Keep whatever the member holds now besides the quarantine role, and give
+/// back stored roles that still exist and can still be assigned. Also
+/// returns how many stored roles were skipped.
+fn restored_roles(
+    current: &[serenity::RoleId],
+    quarantine_role: Option<serenity::RoleId>,
+    stored: &[u64],
+    is_assignable: impl Fn(serenity::RoleId) -> bool,
+) -> (Vec<serenity::RoleId>, usize) {
+    let mut restored: Vec<serenity::RoleId> = current
+        .iter()
+        .copied()
+        .filter(|role_id| Some(*role_id) != quarantine_role)
+        .collect();
+    let mut missing = 0;
+    for role_id in stored.iter().copied().map(serenity::RoleId::new) {
+        if !is_assignable(role_id) {
+            missing += 1;
+        } else if !restored.contains(&role_id) {
+            restored.push(role_id);
+        }
+    }
+    (restored, missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(id: u64) -> serenity::RoleId {
+        serenity::RoleId::new(id)
+    }
+
+    #[test]
+    fn stored_roles_come_back_without_the_quarantine_role() {
+        let (restored, missing) =
+            restored_roles(&[role(2), role(9)], Some(role(9)), &[1, 3], |_| true);
+
+        assert_eq!(restored, [role(2), role(1), role(3)]);
+        assert_eq!(missing, 0);
+    }
+
+    #[test]
+    fn unassignable_roles_are_skipped_and_counted() {
+        let (restored, missing) = restored_roles(&[role(9)], Some(role(9)), &[1, 3], |role_id| {
+            role_id == role(3)
+        });
+
+        assert_eq!(restored, [role(3)]);
+        assert_eq!(missing, 1);
+    }
+
+    #[test]
+    fn roles_held_again_are_not_duplicated() {
+        let (restored, _) = restored_roles(&[role(1)], None, &[1], |_| true);
+
+        assert_eq!(restored, [role(1)]);
+    }
+}
@@ -3,6 +3,8 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_ban_members;
 use crate::moderation::embeds::{
     guild_only_message, is_missing_permissions_error, moderation_action_embed,
     moderation_bot_target_message, send_moderation_target_dm_for_guild, target_profile_from_user,
@@ -11,16 +13,24 @@ use crate::moderation::embeds::{
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::impls::scheduled_jobs::{JOB_BAN_EXPIRY, cancel_jobs_for_target};
 
 pub const META: CommandMeta = CommandMeta {
     name: "unban",
     desc: "Unban a user from the server.",
     category: "moderation",
     usage: "!unban <user> [reason]",
+    required_permissions: Some(serenity::Permissions::BAN_MEMBERS),
+    examples: &["!unban 123456789012345678 appeal accepted"],
+    related: &["ban"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_ban_members"
+)]
 pub async fn unban(
     ctx: Context<'_>,
     #[description = "The user to unban"] user: Option<serenity::User>,
@@ -33,17 +43,6 @@ pub async fn unban(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::BAN_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -70,6 +69,17 @@ pub async fn unban(
         return Ok(());
     }
 
+    if let Err(source) = cancel_jobs_for_target(
+        &ctx.data().db,
+        guild_id.get(),
+        JOB_BAN_EXPIRY,
+        user.id.get(),
+    )
+    .await
+    {
+        error!(?source, "failed to cancel pending tempban expiry");
+    }
+
     let case_reason = reason.as_deref().unwrap_or("No reason provided").to_owned();
 
     let _ = send_moderation_target_dm_for_guild(
@@ -79,6 +89,7 @@ pub async fn unban(
         "unbanned",
         Some(&case_reason),
         None,
+        accent_color(ctx).await,
     )
     .await;
 
@@ -104,6 +115,7 @@ pub async fn unban(
         "unbanned",
         reason.as_deref(),
         None,
+        accent_color(ctx).await,
     );
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
@@ -0,0 +1,91 @@
+use tracing::error;
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_moderate_members;
+use crate::moderation::embeds::{
+    guild_only_message, moderation_action_embed, target_profile_from_user, usage_message,
+};
+use crate::moderation::logging::create_case_and_publish;
+use autumn_core::{Context, Error};
+use autumn_database::impls::cases::NewCase;
+use autumn_database::impls::scheduled_jobs::{JOB_SHADOW_MUTE_EXPIRY, cancel_jobs_for_target};
+use autumn_database::impls::shadow_mutes::remove_shadow_mute;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "unshadowmute",
+    desc: "Lift a shadow mute early.",
+    category: "moderation",
+    usage: "!unshadowmute <user> [reason]",
+    required_permissions: Some(serenity::Permissions::MODERATE_MEMBERS),
+    examples: &["!unshadowmute @user"],
+    related: &["shadowmute"],
+};
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_moderate_members"
+)]
+pub async fn unshadowmute(
+    ctx: Context<'_>,
+    #[description = "The member to lift the shadow mute from"] user: Option<serenity::User>,
+    #[description = "Reason for lifting it"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    if !remove_shadow_mute(db, guild_id.get(), user.id.get()).await? {
+        ctx.say("That member isn't shadow muted.").await?;
+        return Ok(());
+    }
+
+    if let Err(source) =
+        cancel_jobs_for_target(db, guild_id.get(), JOB_SHADOW_MUTE_EXPIRY, user.id.get()).await
+    {
+        error!(?source, "failed to cancel shadow mute expiry");
+    }
+
+    let reason = reason.as_deref().unwrap_or("No reason provided");
+    let _case_label = create_case_and_publish(
+        &ctx,
+        guild_id,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(user.id.get()),
+            moderator_user_id: ctx.author().id.get(),
+            action: "unshadowmute",
+            reason,
+            status: "active",
+            duration_seconds: None,
+        },
+    )
+    .await;
+
+    let target_profile = target_profile_from_user(&user);
+    let embed = moderation_action_embed(
+        &target_profile,
+        user.id,
+        "no longer shadow muted",
+        Some(reason),
+        None,
+        accent_color(ctx).await,
+    );
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -3,6 +3,8 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::{bot_outranks_target, moderator_outranks_target, require_moderate_members};
 use crate::moderation::embeds::{
     guild_only_message, is_missing_permissions_error, moderation_action_embed,
     moderation_bot_target_message, send_moderation_target_dm_for_guild, target_profile_from_user,
@@ -11,16 +13,24 @@ use crate::moderation::embeds::{
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::impls::scheduled_jobs::{JOB_TIMEOUT_EXPIRY, cancel_jobs_for_target};
 
 pub const META: CommandMeta = CommandMeta {
     name: "untimeout",
     desc: "Remove timeout from a user.",
     category: "moderation",
     usage: "!untimeout <user> [reason]",
+    required_permissions: Some(serenity::Permissions::MODERATE_MEMBERS),
+    examples: &["!untimeout @user appeal accepted"],
+    related: &["timeout"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_moderate_members"
+)]
 pub async fn untimeout(
     ctx: Context<'_>,
     #[description = "The user to untimeout"] user: Option<serenity::User>,
@@ -33,17 +43,6 @@ pub async fn untimeout(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MODERATE_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -54,6 +53,14 @@ pub async fn untimeout(
         return Ok(());
     }
 
+    if !moderator_outranks_target(ctx, guild_id, user.id, "remove the timeout from").await? {
+        return Ok(());
+    }
+
+    if !bot_outranks_target(ctx, guild_id, user.id, "remove the timeout from").await? {
+        return Ok(());
+    }
+
     let member = match guild_id.member(ctx.http(), user.id).await {
         Ok(member) => member,
         Err(source) => {
@@ -87,6 +94,17 @@ pub async fn untimeout(
         return Ok(());
     }
 
+    if let Err(source) = cancel_jobs_for_target(
+        &ctx.data().db,
+        guild_id.get(),
+        JOB_TIMEOUT_EXPIRY,
+        user.id.get(),
+    )
+    .await
+    {
+        error!(?source, "failed to cancel pending timeout expiry");
+    }
+
     let case_reason = reason.as_deref().unwrap_or("No reason provided").to_owned();
 
     let _ = send_moderation_target_dm_for_guild(
@@ -96,6 +114,7 @@ pub async fn untimeout(
         "untimed out",
         Some(&case_reason),
         None,
+        accent_color(ctx).await,
     )
     .await;
 
@@ -121,6 +140,7 @@ pub async fn untimeout(
         "untimed out",
         reason.as_deref(),
         None,
+        accent_color(ctx).await,
     );
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
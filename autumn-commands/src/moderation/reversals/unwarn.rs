@@ -2,24 +2,34 @@ use poise::serenity_prelude as serenity;
 use std::time::Duration;
 
 use crate::CommandMeta;
+use crate::checks::require_manage_messages;
 use crate::moderation::embeds::{guild_only_message, moderation_bot_target_message, usage_message};
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
 use autumn_database::impls::warnings::{clear_warnings, remove_warning_by_number};
-use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
-use autumn_utils::permissions::has_user_permission;
+use autumn_utils::confirmation::{
+    TypedConfirmation, prompt_confirm_typed_with_reason, resolve_reason_confirmation_result,
+};
 
 pub const META: CommandMeta = CommandMeta {
     name: "unwarn",
     desc: "Remove a warning from a user.",
     category: "moderation",
     usage: "!unwarn <user> <warn_number|all>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    examples: &["!unwarn @user 2", "!unwarn @user all"],
+    related: &["warn", "warnings"],
 };
 
 const UNWARN_ALL_CONFIRM_TIMEOUT_SECS: u64 = 30;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    check = "require_manage_messages"
+)]
 pub async fn unwarn(
     ctx: Context<'_>,
     #[description = "The user to modify warnings for"] user: Option<serenity::User>,
@@ -30,17 +40,6 @@ pub async fn unwarn(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
         ctx.say(usage_message(META.usage)).await?;
         return Ok(());
@@ -64,15 +63,20 @@ pub async fn unwarn(
             user.id.get()
         ));
 
-        let confirmation = prompt_confirm_decline(
+        let confirmation = prompt_confirm_typed_with_reason(
             ctx,
             "Confirm warning clear",
             confirm_embed,
             Duration::from_secs(UNWARN_ALL_CONFIRM_TIMEOUT_SECS),
+            None,
+            TypedConfirmation {
+                label: "Type their username to confirm",
+                expected: &user.name,
+            },
         )
         .await?;
 
-        let Some(interaction) = resolve_confirmation_result(
+        let Some((interaction, case_reason)) = resolve_reason_confirmation_result(
             ctx,
             confirmation,
             "Timed out",
@@ -97,8 +101,6 @@ pub async fn unwarn(
             return Ok(());
         }
 
-        let case_reason = "No reason provided".to_owned();
-
         let _case_label = create_case_and_publish(
             &ctx,
             guild_id,
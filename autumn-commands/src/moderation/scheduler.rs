@@ -0,0 +1,771 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, info, warn};
+
+use crate::branding::guild_accent_color;
+use crate::moderation::embeds::{
+    is_missing_permissions_error, send_moderation_target_dm_for_guild,
+};
+use crate::moderation::logging::publish_case;
+use crate::moderation::recurring_tasks::run_recurring_task;
+use crate::moderation::timeout::TIMEOUT_FOLLOWUP_MIN_SECS;
+use crate::utility::poll::close_poll;
+use autumn_core::SuppressedDeletes;
+use autumn_database::Database;
+use autumn_database::impls::cases::{
+    CaseStatusChange, NewCase, get_case_by_label, update_case_status,
+};
+use autumn_database::impls::modlog_config::{get_case_review_sla_seconds, get_modlog_channel_id};
+use autumn_database::impls::recurring_tasks::get_recurring_task;
+use autumn_database::impls::scheduled_jobs::{
+    JOB_APPROVAL_EXPIRY, JOB_BAN_EXPIRY, JOB_CASE_REVIEW, JOB_DEFERRED_ACTION, JOB_POLL_CLOSE,
+    JOB_RECURRING_TASK, JOB_REMINDER, JOB_SHADOW_MUTE_EXPIRY, JOB_TEMPROLE_EXPIRY,
+    JOB_TIMEOUT_EXPIRY, JOB_WARNING_DECAY, NewScheduledJob, ScheduledJob, claim_due_job,
+    complete_job, delete_exhausted_jobs, fail_job, reschedule_job, schedule_job,
+};
+use autumn_database::impls::shadow_mutes::{get_shadow_mute, remove_shadow_mute};
+use autumn_database::impls::warning_decay::{
+    decay_warnings, decayed_warning_counts_since, get_warning_decay_config,
+    mark_warning_digest_sent,
+};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::{
+    action_display_name, action_past_tense, format_compact_duration, parse_case_label,
+};
+use autumn_utils::recurrence::Recurrence;
+use autumn_utils::time::now_unix_secs;
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(30);
+/// Most jobs run per tick. Each is claimed right before it runs, so a slow
+/// job never eats into the lease of the ones after it.
+const SCHEDULER_BATCH: usize = 25;
+/// How long a claimed job stays reserved for this worker. Jobs still leased
+/// when a worker dies become claimable again once this runs out.
+const SCHEDULER_LEASE_SECS: u64 = 5 * 60;
+/// How often a guild's warning decay job runs.
+const WARNING_DECAY_INTERVAL_SECS: u64 = 60 * 60;
+const WARNING_DIGEST_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+/// Users listed by name in a warning decay digest.
+const WARNING_DIGEST_MAX_USERS: usize = 20;
+/// Extra wait when a timeout turns out to have been extended past the job.
+const TIMEOUT_RECHECK_GRACE_SECS: u64 = 5;
+
+enum JobOutcome {
+    Done,
+    RunAgainAt(u64),
+}
+
+/// Periodically run scheduled jobs that have come due.
+pub async fn run_scheduled_job_worker(
+    http: Arc<serenity::Http>,
+    db: Database,
+    bot_user_id: serenity::UserId,
+    suppressed_deletes: SuppressedDeletes,
+) {
+    // Unique per process, so a restarted bot never mistakes a dead run's
+    // leases for its own.
+    let worker = format!("{}-{:08x}", std::process::id(), rand::random::<u32>());
+    info!(worker, "Scheduled job worker started.");
+
+    let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        match delete_exhausted_jobs(&db).await {
+            Ok(jobs) => {
+                for job in jobs {
+                    warn!(
+                        job_id = job.id,
+                        kind = job.kind,
+                        payload = job.payload,
+                        "dropping scheduled job that ran out of attempts"
+                    );
+                }
+            }
+            Err(source) => error!(?source, "failed to drop exhausted scheduled jobs"),
+        }
+
+        for _ in 0..SCHEDULER_BATCH {
+            let job = match claim_due_job(&db, &worker, SCHEDULER_LEASE_SECS).await {
+                Ok(Some(job)) => job,
+                Ok(None) => break,
+                Err(source) => {
+                    error!(?source, "failed to claim a due scheduled job");
+                    break;
+                }
+            };
+
+            run_job(&http, &db, &worker, bot_user_id, &suppressed_deletes, job).await;
+        }
+    }
+}
+
+async fn run_job(
+    http: &serenity::Http,
+    db: &Database,
+    worker: &str,
+    bot_user_id: serenity::UserId,
+    suppressed_deletes: &SuppressedDeletes,
+    job: ScheduledJob,
+) {
+    let result = match job.kind.as_str() {
+        JOB_BAN_EXPIRY => run_ban_expiry(http, db, bot_user_id, &job).await,
+        JOB_TIMEOUT_EXPIRY => run_timeout_expiry(http, db, bot_user_id, &job).await,
+        JOB_REMINDER => run_reminder(http, &job).await,
+        JOB_DEFERRED_ACTION => run_deferred_action(http, db, bot_user_id, &job).await,
+        JOB_TEMPROLE_EXPIRY => run_temprole_expiry(http, db, bot_user_id, &job).await,
+        JOB_SHADOW_MUTE_EXPIRY => run_shadow_mute_expiry(http, db, bot_user_id, &job).await,
+        JOB_APPROVAL_EXPIRY => run_approval_expiry(db, bot_user_id, &job).await,
+        JOB_CASE_REVIEW => run_case_review(http, db, &job).await,
+        JOB_WARNING_DECAY => run_warning_decay(http, db, &job).await,
+        JOB_POLL_CLOSE => run_poll_close(http, db, &job).await,
+        JOB_RECURRING_TASK => {
+            run_recurring_task_job(http, db, bot_user_id, suppressed_deletes, &job).await
+        }
+        other => {
+            warn!(
+                job_id = job.id,
+                kind = other,
+                "dropping scheduled job of unknown kind"
+            );
+            Ok(JobOutcome::Done)
+        }
+    };
+
+    let outcome = match result {
+        Ok(JobOutcome::Done) => complete_job(db, job.id, worker).await,
+        Ok(JobOutcome::RunAgainAt(run_at)) => reschedule_job(db, job.id, worker, run_at).await,
+        Err(source) => {
+            match fail_job(db, job.id, worker, job.attempts, &source.to_string()).await {
+                Ok(true) => {
+                    warn!(
+                        ?source,
+                        job_id = job.id,
+                        kind = job.kind,
+                        payload = job.payload,
+                        "scheduled job failed for the last time; dropped"
+                    );
+                    Ok(())
+                }
+                Ok(false) => {
+                    warn!(
+                        ?source,
+                        job_id = job.id,
+                        kind = job.kind,
+                        attempts = job.attempts,
+                        "scheduled job failed; queued for retry"
+                    );
+                    Ok(())
+                }
+                Err(update_error) => Err(update_error),
+            }
+        }
+    };
+
+    if let Err(source) = outcome {
+        error!(?source, job_id = job.id, "failed to update scheduled job");
+    }
+}
+
+/// Lift a tempban and log the unban. A ban that is already gone was lifted
+/// by hand outside the bot, so there is nothing to log.
+async fn run_ban_expiry(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Some(target_user_id) = job.target_user_id else {
+        return Ok(JobOutcome::Done);
+    };
+    let guild_id = serenity::GuildId::new(job.guild_id);
+    let user_id = serenity::UserId::new(target_user_id);
+
+    if guild_id.get_ban(http, user_id).await?.is_none() {
+        return Ok(JobOutcome::Done);
+    }
+
+    guild_id.unban(http, user_id).await?;
+
+    let reason = expiry_reason("Temporary ban expired", &job.payload);
+    publish_case(
+        http,
+        db,
+        guild_id,
+        NewCase {
+            guild_id: job.guild_id,
+            target_user_id: Some(target_user_id),
+            moderator_user_id: bot_user_id.get(),
+            action: "unban",
+            reason: &reason,
+            status: "active",
+            duration_seconds: None,
+        },
+        None,
+    )
+    .await;
+
+    info!(
+        guild_id = job.guild_id,
+        target_user_id, "Lifted expired temporary ban."
+    );
+    Ok(JobOutcome::Done)
+}
+
+/// Confirm a timeout ran out and log the reversal. Discord lifts timeouts on
+/// its own; if the member's timeout was extended, check again once the new
+/// one ends.
+async fn run_timeout_expiry(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Some(target_user_id) = job.target_user_id else {
+        return Ok(JobOutcome::Done);
+    };
+    let guild_id = serenity::GuildId::new(job.guild_id);
+
+    let member = match guild_id
+        .member(http, serenity::UserId::new(target_user_id))
+        .await
+    {
+        Ok(member) => member,
+        Err(source) if is_unknown_member_error(&source) => return Ok(JobOutcome::Done),
+        Err(source) => return Err(source.into()),
+    };
+
+    let now = serenity::Timestamp::now().unix_timestamp();
+    if let Some(until) = member.communication_disabled_until
+        && until.unix_timestamp() > now
+    {
+        let until = u64::try_from(until.unix_timestamp()).unwrap_or(0);
+        return Ok(JobOutcome::RunAgainAt(until + TIMEOUT_RECHECK_GRACE_SECS));
+    }
+
+    let reason = expiry_reason("Timeout expired", &job.payload);
+    publish_case(
+        http,
+        db,
+        guild_id,
+        NewCase {
+            guild_id: job.guild_id,
+            target_user_id: Some(target_user_id),
+            moderator_user_id: bot_user_id.get(),
+            action: "untimeout",
+            reason: &reason,
+            status: "active",
+            duration_seconds: None,
+        },
+        None,
+    )
+    .await;
+
+    Ok(JobOutcome::Done)
+}
+
+/// Take a temporary role back and log it. Nothing is logged if the member
+/// left or the role was already removed by hand.
+async fn run_temprole_expiry(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let (Some(target_user_id), Ok(role_id)) = (job.target_user_id, job.payload.parse::<u64>())
+    else {
+        return Ok(JobOutcome::Done);
+    };
+    let guild_id = serenity::GuildId::new(job.guild_id);
+    let user_id = serenity::UserId::new(target_user_id);
+    let role_id = serenity::RoleId::new(role_id);
+
+    let member = match guild_id.member(http, user_id).await {
+        Ok(member) => member,
+        Err(source) if is_unknown_member_error(&source) => return Ok(JobOutcome::Done),
+        Err(source) => return Err(source.into()),
+    };
+    if !member.roles.contains(&role_id) {
+        return Ok(JobOutcome::Done);
+    }
+
+    http.remove_member_role(guild_id, user_id, role_id, Some("Temporary role expired"))
+        .await?;
+
+    let role_name = guild_id
+        .roles(http)
+        .await
+        .ok()
+        .and_then(|roles| roles.get(&role_id).map(|role| role.name.clone()))
+        .unwrap_or_else(|| role_id.get().to_string());
+    let reason = format!("Temporary role expired (role: {role_name})");
+    publish_case(
+        http,
+        db,
+        guild_id,
+        NewCase {
+            guild_id: job.guild_id,
+            target_user_id: Some(target_user_id),
+            moderator_user_id: bot_user_id.get(),
+            action: "temprole_remove",
+            reason: &reason,
+            status: "active",
+            duration_seconds: None,
+        },
+        None,
+    )
+    .await;
+
+    Ok(JobOutcome::Done)
+}
+
+/// Lift a shadow mute and log it. A shadow mute that was extended waits for
+/// its new expiry; one lifted by hand has nothing left to log.
+async fn run_shadow_mute_expiry(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Some(target_user_id) = job.target_user_id else {
+        return Ok(JobOutcome::Done);
+    };
+
+    let Some(shadow_mute) = get_shadow_mute(db, job.guild_id, target_user_id).await? else {
+        return Ok(JobOutcome::Done);
+    };
+    if shadow_mute.expires_at > now_unix_secs() {
+        return Ok(JobOutcome::RunAgainAt(shadow_mute.expires_at));
+    }
+
+    if !remove_shadow_mute(db, job.guild_id, target_user_id).await? {
+        return Ok(JobOutcome::Done);
+    }
+
+    let reason = expiry_reason("Shadow mute expired", &job.payload);
+    publish_case(
+        http,
+        db,
+        serenity::GuildId::new(job.guild_id),
+        NewCase {
+            guild_id: job.guild_id,
+            target_user_id: Some(target_user_id),
+            moderator_user_id: bot_user_id.get(),
+            action: "unshadowmute",
+            reason: &reason,
+            status: "active",
+            duration_seconds: None,
+        },
+        None,
+    )
+    .await;
+
+    Ok(JobOutcome::Done)
+}
+
+/// Run a recurring task, then move the job on to the task's next slot. A
+/// failed run is logged and skipped rather than retried, so a broken task
+/// can't pile up late runs.
+async fn run_recurring_task_job(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    suppressed_deletes: &SuppressedDeletes,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Ok(task_id) = job.payload.parse::<i64>() else {
+        return Ok(JobOutcome::Done);
+    };
+    let Some(task) = get_recurring_task(db, task_id).await? else {
+        return Ok(JobOutcome::Done);
+    };
+    let Some(recurrence) = Recurrence::parse(&task.schedule) else {
+        warn!(
+            task_id,
+            schedule = task.schedule,
+            "dropping recurring task with bad schedule"
+        );
+        return Ok(JobOutcome::Done);
+    };
+
+    if let Err(source) = run_recurring_task(
+        http,
+        db,
+        bot_user_id,
+        suppressed_deletes,
+        &task,
+        recurrence.period_secs(),
+    )
+    .await
+    {
+        warn!(
+            ?source,
+            task_id,
+            kind = task.kind,
+            "recurring task run failed"
+        );
+    }
+
+    Ok(JobOutcome::RunAgainAt(
+        recurrence.next_after(now_unix_secs()),
+    ))
+}
+
+/// Remind the modlog about a case that is still `open` once its review
+/// window has passed. Resolved cases and guilds without a modlog are skipped.
+/// Cancel a dual-approval case still pending after its window, e.g. because
+/// the bot restarted while it waited for a second moderator.
+async fn run_approval_expiry(
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Some((case_code, action_case_number)) = parse_case_label(&job.payload) else {
+        return Ok(JobOutcome::Done);
+    };
+
+    let change = CaseStatusChange {
+        from: "pending",
+        to: "cancelled",
+        note: "Approval expired",
+    };
+    update_case_status(
+        db,
+        job.guild_id,
+        &case_code,
+        action_case_number,
+        bot_user_id.get(),
+        change,
+    )
+    .await?;
+
+    Ok(JobOutcome::Done)
+}
+
+async fn run_case_review(
+    http: &serenity::Http,
+    db: &Database,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Some((case_code, action_case_number)) = parse_case_label(&job.payload) else {
+        return Ok(JobOutcome::Done);
+    };
+    let Some(case) = get_case_by_label(db, job.guild_id, &case_code, action_case_number).await?
+    else {
+        return Ok(JobOutcome::Done);
+    };
+    if case.status != "open" {
+        return Ok(JobOutcome::Done);
+    }
+    // Reminders queued before the feature was switched off are dropped.
+    if get_case_review_sla_seconds(db, job.guild_id)
+        .await?
+        .is_none()
+    {
+        return Ok(JobOutcome::Done);
+    }
+    let Some(channel_id) = get_modlog_channel_id(db, job.guild_id).await? else {
+        return Ok(JobOutcome::Done);
+    };
+
+    let mut lines = vec![format!(
+        "**Action :** {}",
+        action_display_name(&case.action)
+    )];
+    if let Some(target_user_id) = case.target_user_id {
+        lines.push(format!("**Target :** <@{}>", target_user_id));
+    }
+    lines.push(format!(
+        "**Reason :** {}",
+        case.reason.replace('@', "@\u{200B}")
+    ));
+    lines.push(format!("**Opened :** <t:{}:R>", case.created_at));
+    lines.push(String::new());
+    lines.push(format!(
+        "Resolve it with `!case {} resolve [note]`.",
+        job.payload
+    ));
+
+    let embed = guild_embed(guild_accent_color(db, job.guild_id).await)
+        .title(format!("Case #{} is awaiting review", job.payload))
+        .description(lines.join("\n"));
+
+    serenity::ChannelId::new(channel_id)
+        .send_message(http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+    Ok(JobOutcome::Done)
+}
+
+/// Decay warnings past the guild's configured age, and once a week post a
+/// digest of what decayed to the modlog. Stops once decay is turned off.
+async fn run_warning_decay(
+    http: &serenity::Http,
+    db: &Database,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Some(config) = get_warning_decay_config(db, job.guild_id).await? else {
+        return Ok(JobOutcome::Done);
+    };
+
+    let decayed = decay_warnings(db, job.guild_id, config.max_age_seconds).await?;
+    if decayed > 0 {
+        info!(guild_id = job.guild_id, decayed, "Decayed old warnings.");
+    }
+
+    let now = now_unix_secs();
+    if now >= config.last_digest_at + WARNING_DIGEST_INTERVAL_SECS {
+        let counts = decayed_warning_counts_since(db, job.guild_id, config.last_digest_at).await?;
+        if !counts.is_empty()
+            && let Some(channel_id) = get_modlog_channel_id(db, job.guild_id).await?
+        {
+            let accent_color = guild_accent_color(db, job.guild_id).await;
+            let embed = warning_digest_embed(&counts, config.max_age_seconds, accent_color);
+            serenity::ChannelId::new(channel_id)
+                .send_message(http, serenity::CreateMessage::new().embed(embed))
+                .await?;
+        }
+        mark_warning_digest_sent(db, job.guild_id, now).await?;
+    }
+
+    Ok(JobOutcome::RunAgainAt(now + WARNING_DECAY_INTERVAL_SECS))
+}
+
+fn warning_digest_embed(
+    counts: &[(u64, u64)],
+    max_age_seconds: u64,
+    accent_color: Option<u32>,
+) -> serenity::CreateEmbed {
+    let total: u64 = counts.iter().map(|(_, count)| count).sum();
+    let mut lines = vec![
+        format!(
+            "**Decayed :** {} warning(s) from {} user(s)",
+            total,
+            counts.len()
+        ),
+        format!(
+            "**Decay age :** {}",
+            format_compact_duration(max_age_seconds)
+        ),
+        String::new(),
+    ];
+    lines.extend(
+        counts
+            .iter()
+            .take(WARNING_DIGEST_MAX_USERS)
+            .map(|(user_id, count)| format!("<@{}> : {}", user_id, count)),
+    );
+    if counts.len() > WARNING_DIGEST_MAX_USERS {
+        lines.push(format!(
+            "...and {} more",
+            counts.len() - WARNING_DIGEST_MAX_USERS
+        ));
+    }
+
+    guild_embed(accent_color)
+        .title("Weekly Warning Decay")
+        .description(lines.join("\n"))
+}
+
+/// Post a poll's results. A poll with no channel on record can't be found
+/// again, so it is dropped.
+async fn run_poll_close(
+    http: &serenity::Http,
+    db: &Database,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    if let Some(channel_id) = job.channel_id {
+        let accent_color = guild_accent_color(db, job.guild_id).await;
+        close_poll(
+            http,
+            serenity::ChannelId::new(channel_id),
+            &job.payload,
+            accent_color,
+        )
+        .await?;
+    }
+    Ok(JobOutcome::Done)
+}
+
+/// Ping the moderator in the channel they set the reminder in, or by DM if
+/// that channel is gone or the bot can no longer post there.
+async fn run_reminder(http: &serenity::Http, job: &ScheduledJob) -> anyhow::Result<JobOutcome> {
+    let moderator_id = serenity::UserId::new(job.created_by);
+    let content = format!(
+        "<@{}> **Reminder** (set <t:{}:R>): {}",
+        job.created_by, job.created_at, job.payload
+    );
+
+    if let Some(channel_id) = job.channel_id {
+        let message = serenity::CreateMessage::new()
+            .content(&content)
+            .allowed_mentions(serenity::CreateAllowedMentions::new().users([moderator_id]));
+        match serenity::ChannelId::new(channel_id)
+            .send_message(http, message)
+            .await
+        {
+            Ok(_) => return Ok(JobOutcome::Done),
+            Err(source) => warn!(
+                ?source,
+                job_id = job.id,
+                "reminder channel unavailable; sending by DM"
+            ),
+        }
+    }
+
+    moderator_id
+        .direct_message(http, serenity::CreateMessage::new().content(content))
+        .await?;
+    Ok(JobOutcome::Done)
+}
+
+/// Carry out a pending ban or timeout case. Cancelled cases are skipped; if
+/// the bot can no longer act on the member the case is marked failed.
+async fn run_deferred_action(
+    http: &serenity::Http,
+    db: &Database,
+    bot_user_id: serenity::UserId,
+    job: &ScheduledJob,
+) -> anyhow::Result<JobOutcome> {
+    let Some((case_code, action_case_number)) = parse_case_label(&job.payload) else {
+        return Ok(JobOutcome::Done);
+    };
+    let Some(case) = get_case_by_label(db, job.guild_id, &case_code, action_case_number).await?
+    else {
+        return Ok(JobOutcome::Done);
+    };
+    let Some(target_user_id) = case.target_user_id.filter(|_| case.status == "pending") else {
+        return Ok(JobOutcome::Done);
+    };
+
+    let guild_id = serenity::GuildId::new(job.guild_id);
+    let user_id = serenity::UserId::new(target_user_id);
+    let now = now_unix_secs();
+
+    let result = match (case.action.as_str(), case.duration_seconds) {
+        ("ban", _) => {
+            guild_id
+                .ban_with_reason(http, user_id, 0, &case.reason)
+                .await
+        }
+        ("timeout", Some(duration_seconds)) => {
+            let until = i64::try_from(now.saturating_add(duration_seconds)).unwrap_or(i64::MAX);
+            let edit = serenity::EditMember::new().disable_communication_until_datetime(
+                serenity::Timestamp::from_unix_timestamp(until)?,
+            );
+            guild_id.edit_member(http, user_id, edit).await.map(|_| ())
+        }
+        _ => return Ok(JobOutcome::Done),
+    };
+
+    let (to, note) = match result {
+        Ok(()) => ("active", "Scheduled action carried out"),
+        Err(source)
+            if is_missing_permissions_error(&source) || is_unknown_member_error(&source) =>
+        {
+            warn!(
+                ?source,
+                job_id = job.id,
+                "deferred action could not be carried out"
+            );
+            (
+                "failed",
+                "Scheduled action failed: member left or bot lacks permission",
+            )
+        }
+        Err(source) => return Err(source.into()),
+    };
+
+    let change = CaseStatusChange {
+        from: "pending",
+        to,
+        note,
+    };
+    if update_case_status(
+        db,
+        job.guild_id,
+        &case_code,
+        action_case_number,
+        bot_user_id.get(),
+        change,
+    )
+    .await?
+    .is_none()
+    {
+        warn!(
+            job_id = job.id,
+            "deferred case changed status while running"
+        );
+    }
+
+    if to != "active" {
+        return Ok(JobOutcome::Done);
+    }
+
+    let duration_label = case.duration_seconds.map(format_compact_duration);
+    if let Ok(user) = user_id.to_user(http).await {
+        let _ = send_moderation_target_dm_for_guild(
+            http,
+            &user,
+            guild_id,
+            &action_past_tense(&case.action),
+            Some(&case.reason),
+            duration_label.as_deref(),
+            guild_accent_color(db, job.guild_id).await,
+        )
+        .await;
+    }
+
+    let followup_kind = match case.action.as_str() {
+        "ban" => Some(JOB_BAN_EXPIRY),
+        "timeout"
+            if case
+                .duration_seconds
+                .is_some_and(|secs| secs >= TIMEOUT_FOLLOWUP_MIN_SECS) =>
+        {
+            Some(JOB_TIMEOUT_EXPIRY)
+        }
+        _ => None,
+    };
+    if let (Some(kind), Some(duration_seconds)) = (followup_kind, case.duration_seconds)
+        && let Err(source) = schedule_job(
+            db,
+            NewScheduledJob {
+                guild_id: job.guild_id,
+                kind,
+                target_user_id: Some(target_user_id),
+                channel_id: None,
+                payload: &job.payload,
+                run_at: now.saturating_add(duration_seconds),
+                created_by: job.created_by,
+            },
+        )
+        .await
+    {
+        error!(
+            ?source,
+            job_id = job.id,
+            "failed to schedule deferred action expiry"
+        );
+    }
+
+    Ok(JobOutcome::Done)
+}
+
+/// `payload` is the original case label, empty if that case failed to save.
+fn expiry_reason(summary: &str, payload: &str) -> String {
+    if payload.is_empty() {
+        summary.to_owned()
+    } else {
+        format!("{summary} (#{payload})")
+    }
+}
+
+fn is_unknown_member_error(source: &serenity::Error) -> bool {
+    matches!(
+        source,
+        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
+            if response.status_code.as_u16() == 404 || response.error.code == 10007
+    )
+}
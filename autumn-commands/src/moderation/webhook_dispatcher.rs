@@ -0,0 +1,228 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::{error, warn};
+
+use autumn_database::Database;
+use autumn_database::impls::webhooks::{
+    WebhookDelivery, complete_webhook_delivery, fail_webhook_delivery, list_due_webhook_deliveries,
+};
+use autumn_utils::time::now_unix_secs;
+
+const DELIVERY_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const DELIVERY_BATCH: i64 = 25;
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Periodically POST queued case events to guild webhooks.
+pub async fn run_webhook_delivery_worker(db: Database) {
+    let mut interval = tokio::time::interval(DELIVERY_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let deliveries = match list_due_webhook_deliveries(&db, DELIVERY_BATCH).await {
+            Ok(deliveries) => deliveries,
+            Err(source) => {
+                error!(?source, "failed to list due webhook deliveries");
+                continue;
+            }
+        };
+
+        for delivery in deliveries {
+            deliver(&db, delivery).await;
+        }
+    }
+}
+
+async fn deliver(db: &Database, delivery: WebhookDelivery) {
+    let outcome = match post_delivery(&delivery).await {
+        Ok(()) => complete_webhook_delivery(db, delivery.id).await,
+        Err(source) => {
+            warn!(
+                ?source,
+                delivery_id = delivery.id,
+                attempts = delivery.attempts + 1,
+                "webhook delivery failed; queued for retry"
+            );
+            fail_webhook_delivery(db, delivery.id, delivery.attempts, &source.to_string()).await
+        }
+    };
+
+    if let Err(source) = outcome {
+        error!(?source, "failed to update webhook delivery");
+    }
+}
+
+/// POST one delivery. The host is resolved and checked again first, since
+/// its DNS can change after the webhook was added, and the request is
+/// pinned to the checked addresses. Redirects aren't followed, as they could
+/// lead anywhere.
+async fn post_delivery(delivery: &WebhookDelivery) -> anyhow::Result<()> {
+    let (host, addrs) = resolve_webhook_target(&delivery.url)
+        .await
+        .map_err(anyhow::Error::msg)?;
+    let client = reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve_to_addrs(&host, &addrs)
+        .build()?;
+
+    let timestamp = now_unix_secs();
+    let signature = sign_payload(&delivery.secret, timestamp, &delivery.payload);
+
+    client
+        .post(&delivery.url)
+        .header("Content-Type", "application/json")
+        .header("X-Autumn-Event", &delivery.event)
+        .header("X-Autumn-Timestamp", timestamp.to_string())
+        .header("X-Autumn-Signature", format!("sha256={}", signature))
+        .body(delivery.payload.clone())
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+/// Resolve a webhook URL's host, refusing it unless every address is
+/// public, so guild managers can't point the bot at the network it runs in.
+/// The error is fit to show them.
+pub(crate) async fn resolve_webhook_target(url: &str) -> Result<(String, Vec<SocketAddr>), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| "That isn't a valid URL.".to_owned())?;
+    if parsed.scheme() != "https" {
+        return Err("Webhook URLs must use https.".to_owned());
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "That URL has no host.".to_owned())?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_owned();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|_| format!("I couldn't resolve `{}`.", host))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("I couldn't resolve `{}`.", host));
+    }
+    if addrs.iter().any(|addr| !is_public_ip(addr.ip())) {
+        return Err(
+            "Webhooks can't point at loopback, private or link-local addresses.".to_owned(),
+        );
+    }
+
+    Ok((host, addrs))
+}
+
+/// Whether `ip` is reachable on the public internet, as opposed to loopback,
+/// private, link-local, shared or otherwise reserved ranges.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8 and carrier-grade NAT, 100.64.0.0/10.
+        || a == 0
+        || (a == 100 && (b & 0xc0) == 64))
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // Unique local, fc00::/7, and link-local, fe80::/10.
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{payload}"`, sent as `X-Autumn-Signature`.
+/// Including the timestamp lets receivers reject replayed requests.
+pub fn sign_payload(secret: &str, timestamp: u64, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// A random 32-byte signing secret, hex encoded.
+pub fn generate_webhook_secret() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public_ip(ip.parse().expect("valid IP"))
+    }
+
+    #[test]
+    fn internal_addresses_are_refused() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!public(ip), "{} should be refused", ip);
+        }
+    }
+
+    #[test]
+    fn public_addresses_are_allowed() {
+        for ip in ["1.1.1.1", "93.184.216.34", "100.128.0.1", "2606:4700::1111"] {
+            assert!(public(ip), "{} should be allowed", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn literal_internal_hosts_are_refused() {
+        assert!(
+            resolve_webhook_target("https://127.0.0.1:8080/hook")
+                .await
+                .is_err()
+        );
+        assert!(resolve_webhook_target("https://[::1]/hook").await.is_err());
+        assert!(resolve_webhook_target("http://1.1.1.1/hook").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn literal_public_hosts_resolve_to_themselves() {
+        let (host, addrs) = resolve_webhook_target("https://1.1.1.1/hook")
+            .await
+            .expect("public address");
+
+        assert_eq!(host, "1.1.1.1");
+        assert_eq!(addrs, ["1.1.1.1:443".parse::<SocketAddr>().unwrap()]);
+    }
+}
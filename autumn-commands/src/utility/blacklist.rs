@@ -0,0 +1,155 @@
+use crate::branding::accent_color;
+use autumn_core::{Context, Error};
+use autumn_database::impls::global_blacklist::{
+    BLACKLIST_REASON_MAX_CHARS, add_to_global_blacklist, list_global_blacklist,
+    remove_from_global_blacklist,
+};
+use autumn_utils::embed::guild_embed;
+use poise::serenity_prelude as serenity;
+
+/// Entries shown by `!blacklist list`, newest first.
+const MAX_LISTED_ENTRIES: usize = 25;
+
+// Owner-only and hidden from help, so it has no `CommandMeta` entry.
+
+/// Manage the global blacklist. Listed users are banned or quarantined when
+/// they join any server running the bot, per each server's `!blacklistmode`.
+#[poise::command(
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Utility",
+    subcommands("blacklist_add", "blacklist_remove", "blacklist_list")
+)]
+pub async fn blacklist(ctx: Context<'_>) -> Result<(), Error> {
+    show_list(ctx).await
+}
+
+/// Blacklist a user everywhere.
+#[poise::command(
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Utility",
+    rename = "add"
+)]
+pub async fn blacklist_add(
+    ctx: Context<'_>,
+    user: Option<serenity::User>,
+    #[rest] reason: Option<String>,
+) -> Result<(), Error> {
+    let reason = reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    let (Some(user), Some(reason)) = (user, reason) else {
+        ctx.say("Usage: `!blacklist add <user> <reason>`").await?;
+        return Ok(());
+    };
+    if reason.chars().count() > BLACKLIST_REASON_MAX_CHARS {
+        ctx.say(format!(
+            "Keep the reason under {} characters.",
+            BLACKLIST_REASON_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+    if user.bot {
+        ctx.say("Bots can't be blacklisted.").await?;
+        return Ok(());
+    }
+
+    let inserted =
+        add_to_global_blacklist(&ctx.data().db, user.id.get(), reason, ctx.author().id.get())
+            .await?;
+
+    ctx.say(if inserted {
+        format!(
+            "Blacklisted **{}** (`{}`). They'll be actioned when they join a server.",
+            user.tag(),
+            user.id
+        )
+    } else {
+        format!(
+            "**{}** (`{}`) was already blacklisted; updated the reason.",
+            user.tag(),
+            user.id
+        )
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Take a user off the global blacklist. Existing bans stay in place.
+#[poise::command(
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Utility",
+    rename = "remove"
+)]
+pub async fn blacklist_remove(ctx: Context<'_>, user_id: Option<u64>) -> Result<(), Error> {
+    let Some(user_id) = user_id else {
+        ctx.say("Usage: `!blacklist remove <user id>`").await?;
+        return Ok(());
+    };
+
+    if remove_from_global_blacklist(&ctx.data().db, user_id).await? {
+        ctx.say(format!(
+            "Removed `{}` from the global blacklist. Bans already issued stay in place.",
+            user_id
+        ))
+        .await?;
+    } else {
+        ctx.say(format!("`{}` isn't blacklisted.", user_id)).await?;
+    }
+
+    Ok(())
+}
+
+/// List blacklisted users.
+#[poise::command(
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Utility",
+    rename = "list"
+)]
+pub async fn blacklist_list(ctx: Context<'_>) -> Result<(), Error> {
+    show_list(ctx).await
+}
+
+async fn show_list(ctx: Context<'_>) -> Result<(), Error> {
+    let entries = list_global_blacklist(&ctx.data().db).await?;
+
+    let description = if entries.is_empty() {
+        "No one is blacklisted.".to_owned()
+    } else {
+        let mut lines: Vec<String> = entries
+            .iter()
+            .take(MAX_LISTED_ENTRIES)
+            .map(|entry| {
+                format!(
+                    "<@{}> (`{}`) · <t:{}:d> by <@{}>\n{}",
+                    entry.user_id, entry.user_id, entry.created_at, entry.added_by, entry.reason
+                )
+            })
+            .collect();
+        if entries.len() > MAX_LISTED_ENTRIES {
+            lines.push(format!("…and {} more.", entries.len() - MAX_LISTED_ENTRIES));
+        }
+        lines.join("\n\n")
+    };
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title(format!("Global Blacklist ({})", entries.len()))
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: add <user> <reason>, remove <user id>, list",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
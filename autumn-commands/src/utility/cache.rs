@@ -0,0 +1,142 @@
+use crate::branding::accent_color;
+use autumn_core::{Context, Error};
+use autumn_database::cache::guild_key_prefix;
+use autumn_utils::embed::guild_embed;
+use poise::serenity_prelude as serenity;
+
+/// Longest value shown by `inspect`, leaving room for the code fence.
+const MAX_INSPECT_CHARS: usize = 1800;
+
+// Owner-only and hidden from help, so it has no `CommandMeta` entry.
+
+/// Inspect and manage the cache.
+#[poise::command(
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Utility",
+    subcommands("stats", "flush", "inspect")
+)]
+pub async fn cache(ctx: Context<'_>) -> Result<(), Error> {
+    show_stats(ctx).await
+}
+
+/// Show cache counters and the active backend.
+#[poise::command(prefix_command, owners_only, hide_in_help, category = "Utility")]
+pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
+    show_stats(ctx).await
+}
+
+/// Delete every cached key for a guild.
+#[poise::command(prefix_command, owners_only, hide_in_help, category = "Utility")]
+pub async fn flush(ctx: Context<'_>, guild_id: Option<u64>) -> Result<(), Error> {
+    let Some(guild_id) = guild_id.or_else(|| ctx.guild_id().map(|guild_id| guild_id.get())) else {
+        ctx.say("Usage: `!cache flush <guild id>`").await?;
+        return Ok(());
+    };
+
+    let cache = ctx.data().db.cache();
+    match cache.del_prefix(&guild_key_prefix(cache, guild_id)).await {
+        Ok(deleted) => {
+            ctx.say(format!(
+                "Flushed {} cached key(s) for guild `{}`.",
+                deleted, guild_id
+            ))
+            .await?;
+        }
+        Err(source) => {
+            ctx.say(format!("Couldn't flush the cache: {}", source))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Show the raw value stored under a cache key.
+#[poise::command(prefix_command, owners_only, hide_in_help, category = "Utility")]
+pub async fn inspect(ctx: Context<'_>, key: Option<String>) -> Result<(), Error> {
+    let Some(key) = key
+        .map(|key| key.trim().to_owned())
+        .filter(|key| !key.is_empty())
+    else {
+        ctx.say("Usage: `!cache inspect <key>`").await?;
+        return Ok(());
+    };
+
+    let cache = ctx.data().db.cache();
+    // Accept keys with or without the deployment prefix.
+    let key = if key.starts_with(&format!("{}:", cache.key_prefix())) {
+        key
+    } else {
+        cache.key(&key)
+    };
+
+    let (value, in_memory) = match cache.inspect(&key).await {
+        Ok(found) => found,
+        Err(source) => {
+            ctx.say(format!("Couldn't read `{}`: {}", key, source))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let Some(value) = value else {
+        ctx.say(format!("`{}` is not cached.", key)).await?;
+        return Ok(());
+    };
+
+    let size = value.len();
+    let mut shown = match serde_json::from_slice::<serde_json::Value>(&value) {
+        Ok(json) => serde_json::to_string_pretty(&json)?,
+        Err(_) => String::from_utf8_lossy(&value).into_owned(),
+    };
+    if shown.chars().count() > MAX_INSPECT_CHARS {
+        shown = shown.chars().take(MAX_INSPECT_CHARS).collect();
+        shown.push_str("\n…");
+    }
+
+    ctx.say(format!(
+        "**Key :** `{}`\n**Size :** {} bytes\n**In Memory Tier :** {}\n```json\n{}\n```",
+        key,
+        size,
+        if in_memory { "Yes" } else { "No" },
+        shown.replace("```", "`\u{200b}``")
+    ))
+    .await?;
+
+    Ok(())
+}
+
+async fn show_stats(ctx: Context<'_>) -> Result<(), Error> {
+    let db = &ctx.data().db;
+    let cache = db.cache_stats_snapshot();
+
+    let lines = [
+        format!("**Backend :** {}", db.cache().backend_description()),
+        format!("**Key Prefix :** `{}`", db.cache().key_prefix()),
+        format!(
+            "**Hits / Misses :** {} / {} ({} from memory)",
+            cache.hit, cache.miss, cache.memory_hit
+        ),
+        format!("**Sets / Deletes :** {} / {}", cache.set, cache.del),
+        format!(
+            "**Errors / Fallback Loads :** {} / {} ({} coalesced)",
+            cache.error, cache.fallback_load, cache.coalesced_load
+        ),
+        format!(
+            "**Rate-limit Blocks :** {} of {} checks",
+            cache.ratelimit_blocks, cache.ratelimit_checks
+        ),
+    ];
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title("Cache")
+        .description(lines.join("\n"))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: stats, flush <guild id>, inspect <key>",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use autumn_database::model::command_aliases::CommandAlias;
+
 use crate::CommandMeta;
 
 pub fn unknown_category_message(wanted_category: &str, valid_categories: &[&str]) -> String {
@@ -28,7 +30,9 @@ pub fn page_out_of_range_message(requested_page: usize, total_pages: usize) -> S
     )
 }
 
-pub fn grouped_help_description(commands: &[&CommandMeta]) -> String {
+/// Help text for `commands`, grouped by category, with any of the guild's
+/// `aliases` listed next to the command they point at.
+pub fn grouped_help_description(commands: &[&CommandMeta], aliases: &[CommandAlias]) -> String {
     let mut out = String::new();
     let mut current_category: Option<&str> = None;
 
@@ -41,7 +45,21 @@ pub fn grouped_help_description(commands: &[&CommandMeta]) -> String {
             current_category = Some(command.category);
         }
 
-        out.push_str(&format!("`{}`: {}\n", command.name, command.desc));
+        let command_aliases: Vec<String> = aliases
+            .iter()
+            .filter(|alias| alias.command_name == command.name)
+            .map(|alias| format!("`{}`", alias.alias))
+            .collect();
+        if command_aliases.is_empty() {
+            out.push_str(&format!("`{}`: {}\n", command.name, command.desc));
+        } else {
+            out.push_str(&format!(
+                "`{}`: {} (aliases: {})\n",
+                command.name,
+                command.desc,
+                command_aliases.join(", ")
+            ));
+        }
     }
 
     if out.is_empty() {
@@ -51,7 +69,7 @@ pub fn grouped_help_description(commands: &[&CommandMeta]) -> String {
     out.trim_end().to_owned()
 }
 
-fn display_category(category: &str) -> String {
+pub fn display_category(category: &str) -> String {
     let mut chars = category.chars();
     match chars.next() {
         Some(first) => format!("{}{}", first.to_uppercase(), chars.as_str()),
@@ -1,18 +1,28 @@
+use crate::branding::{accent_color, pagination_options};
+use crate::checks::has_command_permission;
 use crate::utility::embeds::{
-    grouped_help_description, no_commands_message, page_out_of_range_message,
+    display_category, grouped_help_description, no_commands_message, page_out_of_range_message,
     unknown_category_message,
 };
 use crate::{COMMANDS, CommandMeta};
-use autumn_core::{Context, Error};
-use autumn_utils::pagination::{page_window, paginate_embed_pages, total_pages};
-use autumn_utils::permissions::has_user_permission;
+use autumn_core::{Context, Data, Error};
+use autumn_database::impls::command_aliases::{list_command_aliases, resolve_command_alias};
+use autumn_database::model::command_aliases::CommandAlias;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::pagination::{
+    PaginationOptions, first_page_sections, page_window, paginate_embed_pages_with_options,
+    total_pages,
+};
 use poise::serenity_prelude as serenity;
 
 pub const META: CommandMeta = CommandMeta {
     name: "help",
-    desc: "Lists out all available commands.",
+    desc: "Lists out all available commands, or explains one in detail.",
     category: "utility",
-    usage: "!help [page|category]",
+    usage: "!help [page|category|command]",
+    required_permissions: None,
+    examples: &["!help", "!help moderation", "!help timeout"],
+    related: &["usage"],
 };
 
 const HELP_COMMANDS_PER_PAGE: usize = 20;
@@ -20,7 +30,7 @@ const HELP_COMMANDS_PER_PAGE: usize = 20;
 #[poise::command(prefix_command, slash_command, category = "Utility")]
 pub async fn help(
     ctx: Context<'_>,
-    #[description = "Page number or category"] query: Option<String>,
+    #[description = "Page number, category or command"] query: Option<String>,
 ) -> Result<(), Error> {
     let query_trimmed = query
         .as_deref()
@@ -28,6 +38,18 @@ pub async fn help(
         .filter(|value| !value.is_empty());
     let parsed_page =
         query_trimmed.and_then(|raw| raw.parse::<usize>().ok().filter(|page| *page >= 1));
+
+    let aliases = match ctx.guild_id() {
+        Some(guild_id) => list_command_aliases(&ctx.data().db, guild_id.get()).await?,
+        None => Vec::new(),
+    };
+
+    if let (Some(raw), None) = (query_trimmed, parsed_page)
+        && let Some(command) = find_command(raw, &aliases)
+    {
+        return command_help(ctx, command, &aliases).await;
+    }
+
     let category = match (query_trimmed, parsed_page) {
         (Some(raw), None) => Some(raw.to_ascii_lowercase()),
         _ => None,
@@ -35,13 +57,7 @@ pub async fn help(
 
     let can_view_moderation = match ctx.guild_id() {
         Some(guild_id) => {
-            has_user_permission(
-                ctx.http(),
-                guild_id,
-                ctx.author().id,
-                serenity::Permissions::MANAGE_MESSAGES,
-            )
-            .await?
+            has_command_permission(ctx, guild_id, serenity::Permissions::MANAGE_MESSAGES).await?
         }
         None => false,
     };
@@ -66,7 +82,7 @@ pub async fn help(
         return Ok(());
     }
 
-    let commands = sorted_commands(category.as_deref());
+    let commands = sorted_commands(category.as_deref(), can_view_moderation);
     if commands.is_empty() {
         ctx.say(no_commands_message(category.as_deref())).await?;
         return Ok(());
@@ -84,19 +100,34 @@ pub async fn help(
     let pages = (1..=total)
         .map(|page| {
             let (start, end) = page_window(commands.len(), HELP_COMMANDS_PER_PAGE, page);
-            grouped_help_description(&commands[start..end])
+            grouped_help_description(&commands[start..end], &aliases)
         })
         .collect::<Vec<_>>();
-    paginate_embed_pages(ctx, "Available Commands", &pages, requested_page).await?;
+    let category_names: Vec<String> = commands
+        .iter()
+        .map(|command| display_category(command.category))
+        .collect();
+    let sections = first_page_sections(
+        category_names.iter().map(String::as_str),
+        HELP_COMMANDS_PER_PAGE,
+    );
+    let options = PaginationOptions {
+        sections: &sections,
+        ..pagination_options(ctx).await
+    };
+    paginate_embed_pages_with_options(ctx, "Available Commands", &pages, requested_page, options)
+        .await?;
     Ok(())
 }
 
-fn sorted_commands(category: Option<&str>) -> Vec<&'static CommandMeta> {
+/// Commands in `category`, or every command the caller may see when no
+/// category was asked for.
+fn sorted_commands(category: Option<&str>, include_moderation: bool) -> Vec<&'static CommandMeta> {
     let mut filtered: Vec<&'static CommandMeta> = COMMANDS
         .iter()
         .filter(|cmd| match category {
             Some(wanted) => cmd.category == wanted,
-            None => cmd.category != "moderation",
+            None => include_moderation || cmd.category != "moderation",
         })
         .collect();
 
@@ -108,3 +139,118 @@ fn sorted_commands(category: Option<&str>) -> Vec<&'static CommandMeta> {
 
     filtered
 }
+
+/// The command `raw` names, directly or through one of the guild's aliases.
+fn find_command(raw: &str, aliases: &[CommandAlias]) -> Option<&'static CommandMeta> {
+    let lookup = raw.trim_start_matches(['!', '/']).to_ascii_lowercase();
+    let name = resolve_command_alias(aliases, &lookup).unwrap_or(&lookup);
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/// Show everything `!help` knows about one command. Commands the caller
+/// can't run stay hidden, the same way the moderation category does.
+async fn command_help(
+    ctx: Context<'_>,
+    command: &CommandMeta,
+    aliases: &[CommandAlias],
+) -> Result<(), Error> {
+    if let Some(required_permissions) = command.required_permissions {
+        let allowed = match ctx.guild_id() {
+            Some(guild_id) => has_command_permission(ctx, guild_id, required_permissions).await?,
+            None => false,
+        };
+        if !allowed {
+            return Ok(());
+        }
+    }
+
+    let mut description = command.desc.to_owned();
+    if let Some(registered) = ctx
+        .framework()
+        .options()
+        .commands
+        .iter()
+        .find(|registered| registered.name == command.name)
+    {
+        let (heading, lines) = argument_lines(registered);
+        if !lines.is_empty() {
+            description.push_str(&format!("\n\n**{}**\n{}", heading, lines.join("\n")));
+        }
+    }
+
+    let permissions = match command.required_permissions {
+        Some(permissions) => permissions.get_permission_names().join(", "),
+        None => "None".to_owned(),
+    };
+
+    let mut embed = guild_embed(accent_color(ctx).await)
+        .title(format!("!{}", command.name))
+        .description(description)
+        .field("Usage", format!("`{}`", command.usage), false)
+        .field("Permissions", permissions, false);
+
+    if !command.examples.is_empty() {
+        let examples = command
+            .examples
+            .iter()
+            .map(|example| format!("`{}`", example))
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("Examples", examples, false);
+    }
+
+    if !command.related.is_empty() {
+        let related = command
+            .related
+            .iter()
+            .map(|name| format!("`!{}`", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        embed = embed.field("Related", related, false);
+    }
+
+    let command_aliases: Vec<String> = aliases
+        .iter()
+        .filter(|alias| alias.command_name == command.name)
+        .map(|alias| format!("`!{}`", alias.alias))
+        .collect();
+    if !command_aliases.is_empty() {
+        embed = embed.field("Aliases", command_aliases.join(", "), false);
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Describe a registered command's subcommands, or its parameters if it has
+/// none, with `<required>` and `[optional]` markers.
+fn argument_lines(command: &poise::Command<Data, Error>) -> (&'static str, Vec<String>) {
+    if !command.subcommands.is_empty() {
+        let lines = command
+            .subcommands
+            .iter()
+            .map(|subcommand| match &subcommand.description {
+                Some(description) => format!("`{}` — {}", subcommand.name, description),
+                None => format!("`{}`", subcommand.name),
+            })
+            .collect();
+        return ("Subcommands", lines);
+    }
+
+    let lines = command
+        .parameters
+        .iter()
+        .map(|parameter| {
+            let name = if parameter.required {
+                format!("<{}>", parameter.name)
+            } else {
+                format!("[{}]", parameter.name)
+            };
+            match &parameter.description {
+                Some(description) => format!("`{}` — {}", name, description),
+                None => format!("`{}`", name),
+            }
+        })
+        .collect();
+    ("Arguments", lines)
+}
@@ -1,6 +1,12 @@
+pub mod blacklist;
+pub mod cache;
 mod embeds;
 pub mod help;
 pub mod pagetest;
 pub mod ping;
+pub mod poll;
+pub mod report;
+pub mod status;
+pub mod translate;
 pub mod universe;
 pub mod usage;
@@ -1,13 +1,17 @@
 use autumn_core::{Context, Error};
-use autumn_utils::pagination::{page_window, paginate_embed_pages, total_pages};
+use autumn_utils::pagination::{page_window, paginate_embed_pages_with_options, total_pages};
 
 use crate::CommandMeta;
+use crate::branding::pagination_options;
 
 pub const META: CommandMeta = CommandMeta {
     name: "pagetest",
     desc: "Test embed pagination behavior.",
     category: "utility",
     usage: "!pagetest [page]",
+    required_permissions: None,
+    examples: &["!pagetest", "!pagetest 3"],
+    related: &[],
 };
 
 const ITEMS_PER_PAGE: usize = 5;
@@ -40,7 +44,14 @@ pub async fn pagetest(
                 .join("\n")
         })
         .collect::<Vec<_>>();
-    paginate_embed_pages(ctx, "Pagination Test", &pages, requested_page).await?;
+    paginate_embed_pages_with_options(
+        ctx,
+        "Pagination Test",
+        &pages,
+        requested_page,
+        pagination_options(ctx).await,
+    )
+    .await?;
     Ok(())
 }
 
@@ -0,0 +1,230 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::{Context, Error};
+use autumn_database::impls::scheduled_jobs::{JOB_POLL_CLOSE, NewScheduledJob, schedule_job};
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::parse::split_leading_duration;
+use autumn_utils::permissions::missing_permissions;
+use autumn_utils::poll::{POLL_OPTION_EMOJIS, Poll};
+use autumn_utils::time::now_unix_secs;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "poll",
+    desc: "Start a reaction poll that posts its results when time is up.",
+    category: "utility",
+    usage: "!poll <duration> <question> | <option> | <option> [| ...]",
+    required_permissions: None,
+    examples: &[
+        "!poll 1h Movie night? | Friday | Saturday",
+        "!poll 2d Next event | Trivia | Art contest | Game night",
+    ],
+    related: &[],
+};
+
+const MIN_POLL_SECS: u64 = 60;
+const MAX_POLL_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// What the bot needs in the channel to post a poll and count its votes.
+const BOT_POLL_PERMISSIONS: serenity::Permissions = serenity::Permissions::SEND_MESSAGES
+    .union(serenity::Permissions::EMBED_LINKS)
+    .union(serenity::Permissions::ADD_REACTIONS)
+    .union(serenity::Permissions::READ_MESSAGE_HISTORY);
+
+/// Start a reaction poll in this channel; results replace the options at close.
+#[poise::command(prefix_command, slash_command, category = "Utility")]
+pub async fn poll(
+    ctx: Context<'_>,
+    #[description = "Duration, then the question and options separated by |"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let (Some(seconds), Some(rest)) = input
+        .as_deref()
+        .map_or((None, None), split_leading_duration)
+    else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    if !(MIN_POLL_SECS..=MAX_POLL_SECS).contains(&seconds) {
+        ctx.say(format!(
+            "Polls can run from {} to {}.",
+            format_compact_duration(MIN_POLL_SECS),
+            format_compact_duration(MAX_POLL_SECS)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let poll = match Poll::parse(&rest) {
+        Ok(poll) => poll,
+        Err(message) => {
+            ctx.say(message).await?;
+            return Ok(());
+        }
+    };
+
+    if let Some(problem) = channel_permission_problem(ctx, guild_id).await? {
+        ctx.say(problem).await?;
+        return Ok(());
+    }
+
+    let closes_at = now_unix_secs().saturating_add(seconds);
+    let embed = guild_embed(accent_color(ctx).await)
+        .author(
+            serenity::CreateEmbedAuthor::new(format!("Poll by {}", ctx.author().name))
+                .icon_url(ctx.author().face()),
+        )
+        .title(&poll.question)
+        .description(format!(
+            "{}\n\nReact to vote. Closes <t:{}:R>.",
+            poll.option_lines(),
+            closes_at
+        ));
+
+    let reply = ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    let message = reply.message().await?;
+
+    for emoji in POLL_OPTION_EMOJIS.iter().take(poll.options.len()) {
+        message
+            .react(
+                ctx.http(),
+                serenity::ReactionType::Unicode((*emoji).to_owned()),
+            )
+            .await?;
+    }
+
+    let payload = poll.to_payload(message.id.get());
+    let scheduled = schedule_job(
+        &ctx.data().db,
+        NewScheduledJob {
+            guild_id: guild_id.get(),
+            kind: JOB_POLL_CLOSE,
+            target_user_id: None,
+            channel_id: Some(message.channel_id.get()),
+            payload: &payload,
+            run_at: closes_at,
+            created_by: ctx.author().id.get(),
+        },
+    )
+    .await;
+
+    if let Err(source) = scheduled {
+        error!(?source, "failed to schedule poll close");
+        ctx.say(
+            "The poll is up, but I couldn't schedule its results. Count the reactions by hand.",
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Why a poll can't run in this channel: the author needs Discord's Send
+/// Polls permission here, and the bot needs to post and react.
+async fn channel_permission_problem(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+) -> Result<Option<String>, Error> {
+    let http = ctx.http();
+    let Some(channel) = ctx.guild_channel().await else {
+        return Ok(None);
+    };
+    let guild = guild_id.to_partial_guild(http).await?;
+    let author = guild_id.member(http, ctx.author().id).await?;
+    let bot = guild_id.member(http, ctx.framework().bot_id).await?;
+
+    let author_permissions = guild.user_permissions_in(&channel, &author);
+    if !missing_permissions(author_permissions, serenity::Permissions::SEND_POLLS).is_empty() {
+        return Ok(Some(
+            "You need the Send Polls permission in this channel to start a poll.".to_owned(),
+        ));
+    }
+
+    let missing = missing_permissions(
+        guild.user_permissions_in(&channel, &bot),
+        BOT_POLL_PERMISSIONS,
+    );
+    if !missing.is_empty() {
+        return Ok(Some(format!(
+            "I need {} in this channel to run a poll.",
+            missing.get_permission_names().join(", ")
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Count a poll's votes and show the results in place of its options. A
+/// poll whose message is gone has nothing left to close.
+pub(crate) async fn close_poll(
+    http: &serenity::Http,
+    channel_id: serenity::ChannelId,
+    payload: &str,
+    accent_color: Option<u32>,
+) -> anyhow::Result<()> {
+    let Some((message_id, poll)) = Poll::from_payload(payload) else {
+        return Ok(());
+    };
+    let Ok(mut message) = channel_id
+        .message(http, serenity::MessageId::new(message_id))
+        .await
+    else {
+        return Ok(());
+    };
+
+    let votes: Vec<u64> = POLL_OPTION_EMOJIS
+        .iter()
+        .take(poll.options.len())
+        .map(|emoji| {
+            message
+                .reactions
+                .iter()
+                .find(|reaction| is_unicode_reaction(&reaction.reaction_type, emoji))
+                // The bot's own reaction isn't a vote.
+                .map_or(0, |reaction| {
+                    reaction.count.saturating_sub(u64::from(reaction.me))
+                })
+        })
+        .collect();
+
+    let mut embed = guild_embed(accent_color)
+        .title(&poll.question)
+        .description(poll.result_lines(&votes))
+        .footer(serenity::CreateEmbedFooter::new("Poll closed"));
+    if let Some(author) = message
+        .embeds
+        .first()
+        .and_then(|embed| embed.author.as_ref())
+    {
+        let mut embed_author = serenity::CreateEmbedAuthor::new(&author.name);
+        if let Some(icon_url) = &author.icon_url {
+            embed_author = embed_author.icon_url(icon_url);
+        }
+        embed = embed.author(embed_author);
+    }
+
+    message
+        .edit(http, serenity::EditMessage::new().embed(embed))
+        .await?;
+    message
+        .reply(http, format!("**Poll closed:** {}", poll.question))
+        .await?;
+
+    Ok(())
+}
+
+fn is_unicode_reaction(reaction_type: &serenity::ReactionType, emoji: &str) -> bool {
+    matches!(reaction_type, serenity::ReactionType::Unicode(unicode) if unicode == emoji)
+}
@@ -0,0 +1,252 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::{Context, Error};
+use autumn_database::impls::user_reports::{
+    MAX_OPEN_REPORTS_PER_REPORTER, NewUserReport, REPORT_REASON_MAX_CHARS, count_open_reports_by,
+    create_user_report,
+};
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, is_missing_permissions_error, usage_message};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "report",
+    desc: "Report a member to the moderators.",
+    category: "utility",
+    usage: "!report <user> <reason> (or the Report app command on a message)",
+    required_permissions: None,
+    examples: &["!report @user sending scam links in DMs"],
+    related: &["reports"],
+};
+
+const REPORT_MODAL_TIMEOUT_SECS: u64 = 300;
+
+/// Report a member to this server's moderators.
+#[poise::command(prefix_command, slash_command, category = "Utility")]
+pub async fn report(
+    ctx: Context<'_>,
+    #[description = "The member to report"] user: Option<serenity::User>,
+    #[description = "What they did"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let reason = reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    let (Some(user), Some(reason)) = (user, reason) else {
+        ctx.say(usage_message(META.usage)).await?;
+        return Ok(());
+    };
+
+    let outcome = file_report(ctx, guild_id, &user, reason, None).await?;
+    confirm_privately(ctx, outcome).await
+}
+
+/// Report a message to this server's moderators from its Apps menu.
+#[poise::command(context_menu_command = "Report", category = "Utility")]
+pub async fn report_message(
+    ctx: Context<'_>,
+    #[description = "The message to report"] message: serenity::Message,
+) -> Result<(), Error> {
+    let (poise::Context::Application(app_ctx), Some(guild_id)) = (ctx, ctx.guild_id()) else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let modal_id = format!("{}_report_modal", ctx.id());
+    let modal = serenity::CreateModal::new(&modal_id, "Report Message").components(vec![
+        serenity::CreateActionRow::InputText(
+            serenity::CreateInputText::new(
+                serenity::InputTextStyle::Paragraph,
+                "What's wrong with this message?",
+                "reason",
+            )
+            .max_length(REPORT_REASON_MAX_CHARS as u16),
+        ),
+    ]);
+    app_ctx
+        .interaction
+        .create_response(
+            ctx.http(),
+            serenity::CreateInteractionResponse::Modal(modal),
+        )
+        .await?;
+    app_ctx
+        .has_sent_initial_response
+        .store(true, Ordering::SeqCst);
+
+    let Some(submission) = serenity::ModalInteractionCollector::new(ctx)
+        .custom_ids(vec![modal_id])
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(REPORT_MODAL_TIMEOUT_SECS))
+        .await
+    else {
+        return Ok(());
+    };
+
+    let reason = submission
+        .data
+        .components
+        .iter()
+        .flat_map(|row| &row.components)
+        .find_map(|component| match component {
+            serenity::ActionRowComponent::InputText(input) if input.custom_id == "reason" => {
+                input.value.clone()
+            }
+            _ => None,
+        })
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "No reason provided".to_owned());
+
+    let outcome = file_report(ctx, guild_id, &message.author, &reason, Some(&message)).await?;
+    submission
+        .create_response(
+            ctx.http(),
+            serenity::CreateInteractionResponse::Message(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(outcome)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Queue a report and say how it went, for the reporter's eyes only.
+async fn file_report(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    target: &serenity::User,
+    reason: &str,
+    message: Option<&serenity::Message>,
+) -> Result<String, Error> {
+    let reporter = ctx.author();
+    if let Some(problem) = report_problem(reporter.id, target.id, ctx.framework().bot_id, reason) {
+        return Ok(problem);
+    }
+
+    let db = &ctx.data().db;
+    if count_open_reports_by(db, guild_id.get(), reporter.id.get()).await?
+        >= MAX_OPEN_REPORTS_PER_REPORTER
+    {
+        return Ok(format!(
+            "You already have {} reports waiting for a moderator. Try again once they're handled.",
+            MAX_OPEN_REPORTS_PER_REPORTER
+        ));
+    }
+
+    let id = create_user_report(
+        db,
+        NewUserReport {
+            guild_id: guild_id.get(),
+            reporter_user_id: reporter.id.get(),
+            target_user_id: target.id.get(),
+            reason,
+            channel_id: message.map(|message| message.channel_id.get()),
+            message_id: message.map(|message| message.id.get()),
+            message_content: message.map(|message| message.content.as_str()),
+        },
+    )
+    .await?;
+
+    Ok(format!(
+        "Report #{} sent. The moderators will take a look.",
+        id
+    ))
+}
+
+/// Why a report can't be filed, before looking at the reporter's queue.
+fn report_problem(
+    reporter_id: serenity::UserId,
+    target_id: serenity::UserId,
+    bot_id: serenity::UserId,
+    reason: &str,
+) -> Option<String> {
+    if target_id == reporter_id {
+        return Some("You can't report yourself.".to_owned());
+    }
+    if target_id == bot_id {
+        return Some("You can't report me.".to_owned());
+    }
+    if reason.chars().count() > REPORT_REASON_MAX_CHARS {
+        return Some(format!(
+            "Reasons can be at most {} characters.",
+            REPORT_REASON_MAX_CHARS
+        ));
+    }
+    None
+}
+
+/// Slash replies are ephemeral. A prefix report is removed from the channel
+/// and confirmed by DM so other members don't see who filed it.
+async fn confirm_privately(ctx: Context<'_>, content: String) -> Result<(), Error> {
+    let poise::Context::Prefix(prefix) = ctx else {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(content)
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if let Err(source) = prefix.msg.delete(ctx.http()).await
+        && !is_missing_permissions_error(&source)
+    {
+        error!(?source, "failed to delete report command message");
+    }
+
+    let dm = serenity::CreateMessage::new().content(&content);
+    if ctx.author().direct_message(ctx.http(), dm).await.is_err() {
+        ctx.say(content).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORTER: serenity::UserId = serenity::UserId::new(10);
+    const TARGET: serenity::UserId = serenity::UserId::new(20);
+    const BOT: serenity::UserId = serenity::UserId::new(30);
+
+    #[test]
+    fn valid_reports_pass() {
+        assert_eq!(report_problem(REPORTER, TARGET, BOT, "scam links"), None);
+    }
+
+    #[test]
+    fn self_and_bot_reports_are_refused() {
+        assert_eq!(
+            report_problem(REPORTER, REPORTER, BOT, "me").as_deref(),
+            Some("You can't report yourself.")
+        );
+        assert_eq!(
+            report_problem(REPORTER, BOT, BOT, "you").as_deref(),
+            Some("You can't report me.")
+        );
+    }
+
+    #[test]
+    fn reason_length_is_counted_in_chars() {
+        let longest = "é".repeat(REPORT_REASON_MAX_CHARS);
+        assert_eq!(report_problem(REPORTER, TARGET, BOT, &longest), None);
+
+        let too_long = "é".repeat(REPORT_REASON_MAX_CHARS + 1);
+        assert!(report_problem(REPORTER, TARGET, BOT, &too_long).is_some());
+    }
+}
@@ -0,0 +1,121 @@
+use autumn_core::{Context, Error};
+use autumn_database::resilience::BreakerState;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::formatting::format_compact_duration;
+use autumn_utils::process::{format_bytes, resident_memory_bytes};
+use autumn_utils::time::now_unix_secs;
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::checks::require_permission;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "status",
+    desc: "Show uptime, latency, database, cache and LLM stats.",
+    category: "utility",
+    usage: "!status",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    examples: &["!status"],
+    related: &["ping"],
+};
+
+/// Show uptime, latency, database, cache and LLM stats.
+#[poise::command(prefix_command, slash_command, category = "Utility")]
+pub async fn status(ctx: Context<'_>) -> Result<(), Error> {
+    if !require_permission(ctx, serenity::Permissions::MANAGE_GUILD).await? {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+    let uptime = now_unix_secs().saturating_sub(data.started_at);
+
+    // Zero until the shard's first heartbeat is acknowledged.
+    let latency = ctx.ping().await;
+    let latency = if latency.is_zero() {
+        "Unavailable".to_owned()
+    } else {
+        format!("{} ms", latency.as_millis())
+    };
+
+    let memory = resident_memory_bytes()
+        .map(format_bytes)
+        .unwrap_or_else(|| "Unavailable".to_owned());
+
+    let pool = data.db.pool();
+    let pool_size = pool.size();
+    let pool_idle = pool.num_idle();
+    let pool_max = pool.options().get_max_connections();
+
+    let cache = data.db.cache_stats_snapshot();
+    let cache_backend = data.db.cache().backend_description();
+
+    let process = [
+        format!(
+            "**Uptime :** {} (since <t:{}:f>)",
+            format_compact_duration(uptime),
+            data.started_at
+        ),
+        format!("**Shard Latency :** {}", latency),
+        format!("**Memory :** {}", memory),
+    ];
+    let breaker = data.db.breaker_snapshot();
+    let breaker_state = match breaker.state {
+        BreakerState::Closed => "Closed".to_owned(),
+        BreakerState::Open { retry_in } => format!(
+            "Open (retrying in {})",
+            format_compact_duration(retry_in.as_secs().max(1))
+        ),
+        BreakerState::HalfOpen => "Half-open".to_owned(),
+    };
+
+    let database = [
+        format!(
+            "**Pool :** {} in use, {} idle, {} max",
+            (pool_size as usize).saturating_sub(pool_idle),
+            pool_idle,
+            pool_max
+        ),
+        format!(
+            "**Circuit Breaker :** {} ({} recent failures, opened {} times)",
+            breaker_state, breaker.consecutive_failures, breaker.times_opened
+        ),
+    ];
+    let cache_lines = [
+        format!("**Backend :** {}", cache_backend),
+        format!(
+            "**Hits / Misses :** {} / {} ({} from memory)",
+            cache.hit, cache.miss, cache.memory_hit
+        ),
+        format!("**Sets / Deletes :** {} / {}", cache.set, cache.del),
+        format!(
+            "**Errors / Fallback Loads :** {} / {}",
+            cache.error, cache.fallback_load
+        ),
+        format!(
+            "**Rate-limit Blocks :** {} of {} checks",
+            cache.ratelimit_blocks, cache.ratelimit_checks
+        ),
+    ];
+
+    let mut embed = guild_embed(accent_color(ctx).await)
+        .title("Status")
+        .field("Process", process.join("\n"), false)
+        .field("Database", database.join("\n"), false)
+        .field("Cache", cache_lines.join("\n"), false);
+
+    if let Some(llm) = data.llm.as_ref() {
+        let llm_stats = llm.stats_snapshot();
+        let llm_lines = [
+            format!("**Model Calls :** {}", llm_stats.model_calls),
+            format!(
+                "**Cached Replies :** {} (calls saved)",
+                llm_stats.cached_replies
+            ),
+        ];
+        embed = embed.field("LLM", llm_lines.join("\n"), false);
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
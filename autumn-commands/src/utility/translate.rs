@@ -0,0 +1,157 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::{Context, Error};
+use autumn_database::impls::ai_config::{
+    TRANSLATE_LANGUAGE_MAX_CHARS, get_translate_language, is_valid_translate_language,
+};
+use autumn_database::impls::rate_limit::llm_mention_within_limit;
+use autumn_utils::embed::guild_embed;
+
+use crate::CommandMeta;
+use crate::branding::accent_color;
+use crate::moderation::embeds::guild_only_message;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "translate",
+    desc: "Translate the message you reply to.",
+    category: "utility",
+    usage: "!translate [language] (as a reply, or the Translate app command)",
+    required_permissions: None,
+    examples: &["!translate (as a reply)", "!translate spanish (as a reply)"],
+    related: &[],
+};
+
+/// Discord's limit on embed descriptions.
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+#[poise::command(prefix_command, category = "Utility")]
+pub async fn translate(
+    ctx: Context<'_>,
+    #[description = "Language to translate into"]
+    #[rest]
+    language: Option<String>,
+) -> Result<(), Error> {
+    let poise::Context::Prefix(prefix) = ctx else {
+        return Ok(());
+    };
+
+    let Some(message) = prefix.msg.referenced_message.as_deref() else {
+        ctx.say("Reply to a message with `!translate [language]` to translate it.")
+            .await?;
+        return Ok(());
+    };
+
+    let language = language
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty());
+    if let Some(language) = language
+        && !is_valid_translate_language(language)
+    {
+        ctx.say(format!(
+            "Languages are names like `Spanish` or `Brazilian Portuguese`, up to {} characters.",
+            TRANSLATE_LANGUAGE_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    translate_and_reply(ctx, message, language).await
+}
+
+/// Translate a message into this server's language.
+#[poise::command(context_menu_command = "Translate", category = "Utility")]
+pub async fn translate_message(
+    ctx: Context<'_>,
+    #[description = "Message to translate"] message: serenity::Message,
+) -> Result<(), Error> {
+    translate_and_reply(ctx, &message, None).await
+}
+
+async fn translate_and_reply(
+    ctx: Context<'_>,
+    message: &serenity::Message,
+    language: Option<&str>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(llm) = ctx.data().llm.as_ref() else {
+        ctx.say("No LLM backend is configured, so translation is unavailable.")
+            .await?;
+        return Ok(());
+    };
+
+    if message.content.trim().is_empty() {
+        ctx.say("That message has no text to translate.").await?;
+        return Ok(());
+    }
+
+    let within_limit = match llm_mention_within_limit(
+        &ctx.data().db,
+        guild_id.get(),
+        ctx.channel_id().get(),
+        ctx.author().id.get(),
+    )
+    .await
+    {
+        Ok(value) => value,
+        Err(source) => {
+            error!(?source, "failed to evaluate llm rate limit");
+            true
+        }
+    };
+    if !within_limit {
+        ctx.say(
+            "You're sending LLM requests too quickly. Please wait a few seconds and try again.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let language = match language {
+        Some(language) => language.to_owned(),
+        None => get_translate_language(&ctx.data().db, guild_id.get()).await?,
+    };
+
+    ctx.defer().await?;
+
+    let translation = match llm.translate(&message.content, &language).await {
+        Ok(translation) if !translation.is_empty() => translation,
+        Ok(_) => {
+            ctx.say("The LLM returned an empty translation.").await?;
+            return Ok(());
+        }
+        Err(source) => {
+            error!(?source, "llm translation failed");
+            ctx.say("I ran into an LLM error. Try again in a moment.")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let description: String = translation.chars().take(EMBED_DESCRIPTION_LIMIT).collect();
+    let author_name = message
+        .author
+        .global_name
+        .clone()
+        .unwrap_or_else(|| message.author.name.clone());
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .author(serenity::CreateEmbedAuthor::new(author_name).icon_url(message.author.face()))
+        .title(format!("Translation ({})", language))
+        .url(message.link())
+        .description(description);
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    Ok(())
+}
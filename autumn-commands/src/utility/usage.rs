@@ -1,20 +1,30 @@
 use autumn_core::{Context, Error};
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::impls::command_usage::get_command_usage_stats;
+use autumn_utils::embed::guild_embed;
+use autumn_utils::time::now_unix_secs;
 use poise::serenity_prelude as serenity;
 
+use crate::branding::accent_color;
+use crate::checks::{has_command_permission, require_permission};
 use crate::{COMMANDS, CommandMeta};
 
 pub const META: CommandMeta = CommandMeta {
     name: "usage",
-    desc: "Show usage syntax for a specific command.",
+    desc: "Show usage syntax for a specific command, or command usage stats.",
     category: "utility",
-    usage: "!usage <command>",
+    usage: "!usage <command|stats>",
+    required_permissions: None,
+    examples: &["!usage ban", "!usage stats"],
+    related: &["help"],
 };
 
+const STATS_WINDOW_DAYS: u64 = 7;
+const STATS_TOP_COMMANDS: i64 = 10;
+
 #[poise::command(prefix_command, slash_command, category = "Utility")]
 pub async fn usage(
     ctx: Context<'_>,
-    #[description = "Command name"] command: Option<String>,
+    #[description = "Command name, or 'stats'"] command: Option<String>,
 ) -> Result<(), Error> {
     let Some(raw_name) = command.as_deref() else {
         ctx.say(format!("Usage: `{}`", META.usage)).await?;
@@ -23,16 +33,18 @@ pub async fn usage(
 
     let lookup = raw_name.trim().trim_start_matches('!').to_ascii_lowercase();
 
+    if lookup == "stats" {
+        return usage_stats(ctx).await;
+    }
+
     let Some(command) = COMMANDS.iter().find(|command| command.name == lookup) else {
         ctx.say(format!("Unknown command: `{}`", lookup)).await?;
         return Ok(());
     };
 
-    if let (Some(guild_id), Some(required_permissions)) = (
-        ctx.guild_id(),
-        required_permissions_for_command(command.name),
-    ) && !has_user_permission(ctx.http(), guild_id, ctx.author().id, required_permissions)
-        .await?
+    if let (Some(guild_id), Some(required_permissions)) =
+        (ctx.guild_id(), command.required_permissions)
+        && !has_command_permission(ctx, guild_id, required_permissions).await?
     {
         return Ok(());
     }
@@ -41,19 +53,47 @@ pub async fn usage(
     Ok(())
 }
 
-fn required_permissions_for_command(command_name: &str) -> Option<serenity::Permissions> {
-    match command_name {
-        "ban" | "unban" => Some(serenity::Permissions::BAN_MEMBERS),
-        "kick" => Some(serenity::Permissions::KICK_MEMBERS),
-        "timeout" | "untimeout" => Some(serenity::Permissions::MODERATE_MEMBERS),
-        "warn" | "warnings" | "unwarn" | "purge" | "permissions" | "modlogs" | "userlogs"
-        | "case" | "notes" => Some(serenity::Permissions::MANAGE_MESSAGES),
-        "modlogchannel" | "userlogchannel" | "aitoggle" | "setup" => {
-            Some(serenity::Permissions::MANAGE_GUILD)
-        }
-        "terminate" => {
-            Some(serenity::Permissions::BAN_MEMBERS | serenity::Permissions::MANAGE_MESSAGES)
-        }
-        _ => None,
+/// Summarize the last week of command invocations in this server.
+async fn usage_stats(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say("Usage stats are only available in servers.")
+            .await?;
+        return Ok(());
+    };
+
+    if !require_permission(ctx, serenity::Permissions::MANAGE_GUILD).await? {
+        return Ok(());
+    }
+
+    let since = now_unix_secs().saturating_sub(STATS_WINDOW_DAYS * 24 * 60 * 60);
+    let stats =
+        get_command_usage_stats(&ctx.data().db, guild_id.get(), since, STATS_TOP_COMMANDS).await?;
+
+    let mut lines = vec![
+        format!("**Invocations :** {}", stats.total),
+        format!(
+            "**Errors :** {} ({}%)",
+            stats.errors,
+            (stats.errors * 100).checked_div(stats.total).unwrap_or(0)
+        ),
+        format!("**Avg Latency :** {} ms", stats.avg_latency_ms),
+    ];
+
+    if !stats.commands.is_empty() {
+        lines.push(String::new());
+        lines.push("**Top Commands :**".to_owned());
+        lines.extend(stats.commands.iter().map(|command| {
+            format!(
+                "`{}` — {} uses, {} errors, {} ms avg",
+                command.command, command.total, command.errors, command.avg_latency_ms
+            )
+        }));
     }
+
+    let embed = guild_embed(accent_color(ctx).await)
+        .title(format!("Command Usage (last {} days)", STATS_WINDOW_DAYS))
+        .description(lines.join("\n"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
 }
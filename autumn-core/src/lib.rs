@@ -2,9 +2,14 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use autumn_database::Database;
+use autumn_database::repository::{CaseRepository, EscalationRepository, WarningRepository};
 use autumn_llm::LlmService;
 use tokio::sync::RwLock;
 
+pub mod storage;
+
+use storage::AttachmentStore;
+
 pub type Error = anyhow::Error;
 
 /// Set of message IDs to suppress from user-log recording.
@@ -14,8 +19,16 @@ pub type SuppressedDeletes = Arc<RwLock<HashSet<u64>>>;
 #[derive(Clone, Debug)]
 pub struct Data {
     pub db: Database,
+    /// Trait views over `db`, swappable for in-memory fakes in tests.
+    pub cases: Arc<dyn CaseRepository>,
+    pub warnings: Arc<dyn WarningRepository>,
+    pub escalation: Arc<dyn EscalationRepository>,
     pub llm: Option<LlmService>,
     pub suppressed_deletes: SuppressedDeletes,
+    /// Where deleted-message attachments are archived; `None` disables archival.
+    pub attachment_store: Option<Arc<dyn AttachmentStore>>,
+    /// Unix time the process started, for uptime reporting.
+    pub started_at: u64,
 }
 
 pub type Context<'a> = poise::Context<'a, Data, Error>;
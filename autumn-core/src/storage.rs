@@ -0,0 +1,92 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+
+use anyhow::Context as _;
+
+pub type StorageFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// Blob storage for archived message attachments.
+///
+/// Keys are `/`-separated relative paths. Backends other than the local
+/// filesystem (e.g. S3) only need to implement these three operations.
+pub trait AttachmentStore: Debug + Send + Sync {
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> StorageFuture<'a, ()>;
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<Vec<u8>>>;
+    /// Remove every blob stored under the `prefix/` key path.
+    fn delete_prefix<'a>(&'a self, prefix: &'a str) -> StorageFuture<'a, ()>;
+}
+
+/// Key prefix holding every archived attachment of one message.
+pub fn message_attachment_prefix(guild_id: u64, channel_id: u64, message_id: u64) -> String {
+    format!("{}/{}/{}", guild_id, channel_id, message_id)
+}
+
+/// Stores attachments as files under a root directory.
+#[derive(Debug)]
+pub struct LocalAttachmentStore {
+    root: PathBuf,
+}
+
+impl LocalAttachmentStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> anyhow::Result<PathBuf> {
+        let relative = Path::new(key);
+        // Keys are built from ids and sanitized filenames, but never let one
+        // escape the root.
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            anyhow::bail!("invalid attachment storage key: {key}");
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+impl AttachmentStore for LocalAttachmentStore {
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path_for(key)?;
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            tokio::fs::write(&path, bytes)
+                .await
+                .with_context(|| format!("failed to write {}", path.display()))
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            let path = self.path_for(key)?;
+            match tokio::fs::read(&path).await {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(source) if source.kind() == ErrorKind::NotFound => Ok(None),
+                Err(source) => {
+                    Err(source).with_context(|| format!("failed to read {}", path.display()))
+                }
+            }
+        })
+    }
+
+    fn delete_prefix<'a>(&'a self, prefix: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path_for(prefix)?;
+            match tokio::fs::remove_dir_all(&path).await {
+                Ok(()) => Ok(()),
+                Err(source) if source.kind() == ErrorKind::NotFound => Ok(()),
+                Err(source) => {
+                    Err(source).with_context(|| format!("failed to remove {}", path.display()))
+                }
+            }
+        })
+    }
+}
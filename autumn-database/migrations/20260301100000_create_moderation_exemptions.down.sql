@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_moderation_exemptions_guild_scope;
+DROP TABLE IF EXISTS moderation_exemptions;
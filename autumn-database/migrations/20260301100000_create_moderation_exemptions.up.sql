@@ -0,0 +1,16 @@
+-- Role/channel/user exemptions per guild, scoped to a moderation subsystem.
+CREATE TABLE IF NOT EXISTS moderation_exemptions (
+    id          BIGSERIAL PRIMARY KEY,
+    guild_id    BIGINT NOT NULL,
+    scope       TEXT   NOT NULL,
+    -- scope values: 'word_filter', 'escalation'
+    target_kind TEXT   NOT NULL,
+    -- target_kind values: 'role', 'channel', 'user'
+    target_id   BIGINT NOT NULL,
+    created_by  BIGINT NOT NULL,
+    created_at  BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    UNIQUE (guild_id, scope, target_kind, target_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_exemptions_guild_scope
+    ON moderation_exemptions (guild_id, scope);
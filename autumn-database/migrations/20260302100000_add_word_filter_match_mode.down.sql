@@ -0,0 +1 @@
+ALTER TABLE word_filter_words DROP COLUMN IF EXISTS match_mode;
@@ -0,0 +1,4 @@
+-- Per-word match mode for the word filter
+ALTER TABLE word_filter_words
+    ADD COLUMN IF NOT EXISTS match_mode TEXT NOT NULL DEFAULT 'whole';
+    -- match_mode values: 'whole', 'contains', 'startswith'
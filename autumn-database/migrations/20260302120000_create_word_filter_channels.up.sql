@@ -0,0 +1,8 @@
+-- Per-channel word filter overrides. A row replaces the guild-wide enabled
+-- flag for that channel (e.g. disabled in an NSFW channel).
+CREATE TABLE IF NOT EXISTS word_filter_channels (
+    guild_id   BIGINT  NOT NULL,
+    channel_id BIGINT  NOT NULL,
+    enabled    BOOLEAN NOT NULL,
+    PRIMARY KEY (guild_id, channel_id)
+);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_link_filter_domains_guild;
+DROP TABLE IF EXISTS link_filter_domains;
+DROP TABLE IF EXISTS link_filter_config;
@@ -0,0 +1,22 @@
+-- Link filter configuration per guild
+CREATE TABLE IF NOT EXISTS link_filter_config (
+    guild_id BIGINT PRIMARY KEY,
+    enabled  BOOLEAN NOT NULL DEFAULT FALSE,
+    mode     TEXT    NOT NULL DEFAULT 'denylist',
+    -- mode values: 'denylist', 'allowlist', 'block_all'
+    action   TEXT    NOT NULL DEFAULT 'delete_and_log'
+    -- action values: 'log_only', 'delete_and_log', 'warn_and_log', 'timeout_delete_and_log'
+);
+
+-- Allowed / denied domains per guild
+CREATE TABLE IF NOT EXISTS link_filter_domains (
+    id         BIGSERIAL PRIMARY KEY,
+    guild_id   BIGINT NOT NULL,
+    domain     TEXT   NOT NULL,
+    list_kind  TEXT   NOT NULL,
+    -- list_kind values: 'allow', 'deny'
+    created_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    UNIQUE (guild_id, domain)
+);
+
+CREATE INDEX IF NOT EXISTS idx_link_filter_domains_guild ON link_filter_domains (guild_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS invite_filter_allowed_guilds;
+DROP TABLE IF EXISTS invite_filter_config;
@@ -0,0 +1,15 @@
+-- Discord invite filter configuration per guild
+CREATE TABLE IF NOT EXISTS invite_filter_config (
+    guild_id BIGINT PRIMARY KEY,
+    enabled  BOOLEAN NOT NULL DEFAULT FALSE,
+    action   TEXT    NOT NULL DEFAULT 'delete_and_log'
+    -- action values: 'log_only', 'delete_and_log', 'warn_and_log', 'timeout_delete_and_log'
+);
+
+-- Guilds whose invites may be posted
+CREATE TABLE IF NOT EXISTS invite_filter_allowed_guilds (
+    guild_id         BIGINT NOT NULL,
+    allowed_guild_id BIGINT NOT NULL,
+    created_at       BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    PRIMARY KEY (guild_id, allowed_guild_id)
+);
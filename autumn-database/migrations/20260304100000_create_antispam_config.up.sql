@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS antispam_config (
+    guild_id                 BIGINT PRIMARY KEY,
+    enabled                  BOOLEAN NOT NULL DEFAULT FALSE,
+    message_threshold        INT NOT NULL DEFAULT 6,
+    message_window_seconds   BIGINT NOT NULL DEFAULT 5,
+    duplicate_threshold      INT NOT NULL DEFAULT 3,
+    duplicate_window_seconds BIGINT NOT NULL DEFAULT 30,
+    action                   TEXT NOT NULL DEFAULT 'timeout_delete_and_log'
+    -- action values: 'log_only', 'delete_and_log', 'warn_and_log', 'timeout_delete_and_log'
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS antiraid_config;
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS antiraid_config (
+    guild_id                    BIGINT PRIMARY KEY,
+    enabled                     BOOLEAN NOT NULL DEFAULT FALSE,
+    join_threshold              INT NOT NULL DEFAULT 10,
+    join_window_seconds         BIGINT NOT NULL DEFAULT 10,
+    raid_duration_seconds       BIGINT NOT NULL DEFAULT 600,
+    raise_verification          BOOLEAN NOT NULL DEFAULT TRUE,
+    kick_new_joins              BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Unix timestamp until which the guild is in raid state, NULL when idle.
+    raid_active_until           BIGINT,
+    -- Verification level before the raid response raised it, restored on end.
+    previous_verification_level SMALLINT
+);
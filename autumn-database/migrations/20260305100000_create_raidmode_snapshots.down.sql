@@ -0,0 +1 @@
+DROP TABLE IF EXISTS raidmode_snapshots;
@@ -0,0 +1,10 @@
+-- One row per guild while raid mode is on, holding the settings to restore.
+CREATE TABLE IF NOT EXISTS raidmode_snapshots (
+    guild_id            BIGINT PRIMARY KEY,
+    enabled_by          BIGINT NOT NULL,
+    enabled_at          BIGINT NOT NULL,
+    verification_level  SMALLINT NOT NULL,
+    invites_disabled    BOOLEAN NOT NULL,
+    word_filter_enabled BOOLEAN NOT NULL,
+    word_filter_action  TEXT NOT NULL
+);
@@ -0,0 +1,23 @@
+-- Attachment filter configuration per guild
+CREATE TABLE IF NOT EXISTS attachment_filter_config (
+    guild_id BIGINT PRIMARY KEY,
+    enabled  BOOLEAN NOT NULL DEFAULT FALSE,
+    action   TEXT    NOT NULL DEFAULT 'delete_and_log'
+    -- action values: 'log_only', 'delete_and_log', 'warn_and_log', 'timeout_delete_and_log'
+);
+
+-- File extensions blocked in every channel
+CREATE TABLE IF NOT EXISTS attachment_filter_extensions (
+    guild_id   BIGINT NOT NULL,
+    extension  TEXT   NOT NULL,
+    created_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    PRIMARY KEY (guild_id, extension)
+);
+
+-- Channels where all attachments are blocked
+CREATE TABLE IF NOT EXISTS attachment_filter_channels (
+    guild_id   BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    created_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    PRIMARY KEY (guild_id, channel_id)
+);
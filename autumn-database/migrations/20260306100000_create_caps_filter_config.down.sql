@@ -0,0 +1 @@
+DROP TABLE IF EXISTS caps_filter_config;
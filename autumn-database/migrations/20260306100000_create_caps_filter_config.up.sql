@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS caps_filter_config (
+    guild_id         BIGINT PRIMARY KEY,
+    enabled          BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Messages with fewer letters than this are never flagged.
+    min_length       INT NOT NULL DEFAULT 10,
+    max_caps_percent INT NOT NULL DEFAULT 70,
+    action           TEXT NOT NULL DEFAULT 'delete_and_log'
+    -- action values: 'log_only', 'delete_and_log', 'warn_and_log', 'timeout_delete_and_log'
+);
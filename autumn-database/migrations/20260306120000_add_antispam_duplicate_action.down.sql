@@ -0,0 +1 @@
+ALTER TABLE antispam_config DROP COLUMN IF EXISTS duplicate_action;
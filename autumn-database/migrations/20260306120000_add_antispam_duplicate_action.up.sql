@@ -0,0 +1,6 @@
+-- Repeated-message (copypasta) detection gets its own action, starting from
+-- whatever the guild used for all anti-spam hits so far.
+ALTER TABLE antispam_config
+    ADD COLUMN IF NOT EXISTS duplicate_action TEXT NOT NULL DEFAULT 'delete_and_log';
+
+UPDATE antispam_config SET duplicate_action = action;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS name_filter_config (
+    guild_id    BIGINT PRIMARY KEY,
+    enabled     BOOLEAN NOT NULL DEFAULT FALSE,
+    action      TEXT NOT NULL DEFAULT 'rename',
+    -- action values: 'rename', 'kick', 'log'
+    placeholder TEXT NOT NULL DEFAULT 'Moderated Nickname'
+);
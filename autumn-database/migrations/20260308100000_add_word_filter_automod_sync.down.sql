@@ -0,0 +1,2 @@
+ALTER TABLE word_filter_words DROP COLUMN IF EXISTS synced_to_automod;
+ALTER TABLE word_filter_config DROP COLUMN IF EXISTS automod_rule_id;
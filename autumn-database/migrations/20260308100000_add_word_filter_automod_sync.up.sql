@@ -0,0 +1,6 @@
+-- Discord AutoMod rule the word list is pushed to by `!wordfilter sync-automod`.
+ALTER TABLE word_filter_config ADD COLUMN IF NOT EXISTS automod_rule_id BIGINT;
+
+-- Whether the word was included in the last AutoMod sync.
+ALTER TABLE word_filter_words
+    ADD COLUMN IF NOT EXISTS synced_to_automod BOOLEAN NOT NULL DEFAULT FALSE;
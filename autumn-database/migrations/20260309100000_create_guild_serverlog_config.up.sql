@@ -0,0 +1,5 @@
+-- Channel for server structure changes (channels, roles), separate from user logs.
+CREATE TABLE IF NOT EXISTS guild_serverlog_config (
+    guild_id BIGINT PRIMARY KEY,
+    serverlog_channel_id BIGINT
+);
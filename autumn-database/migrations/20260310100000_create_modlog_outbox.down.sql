@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS modlog_outbox;
+
+ALTER TABLE guild_mod_config
+    DROP COLUMN IF EXISTS modlog_webhook_token,
+    DROP COLUMN IF EXISTS modlog_webhook_id;
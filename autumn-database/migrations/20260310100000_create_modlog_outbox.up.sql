@@ -0,0 +1,18 @@
+ALTER TABLE guild_mod_config
+    ADD COLUMN IF NOT EXISTS modlog_webhook_id BIGINT,
+    ADD COLUMN IF NOT EXISTS modlog_webhook_token TEXT;
+
+CREATE TABLE IF NOT EXISTS modlog_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    case_id BIGINT REFERENCES mod_cases (id) ON DELETE CASCADE,
+    channel_id BIGINT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at BIGINT NOT NULL,
+    last_error TEXT,
+    created_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS modlog_outbox_next_attempt_idx
+    ON modlog_outbox (next_attempt_at);
@@ -0,0 +1,7 @@
+-- Per-guild modlog embed layout. NULL columns fall back to the built-in layout.
+CREATE TABLE IF NOT EXISTS modlog_templates (
+    guild_id BIGINT PRIMARY KEY,
+    field_order TEXT,
+    color INTEGER,
+    footer TEXT
+);
@@ -0,0 +1,6 @@
+ALTER TABLE modlog_outbox
+    DROP COLUMN IF EXISTS attachment_content,
+    DROP COLUMN IF EXISTS attachment_name;
+
+ALTER TABLE guild_mod_config
+    DROP COLUMN IF EXISTS purge_transcripts;
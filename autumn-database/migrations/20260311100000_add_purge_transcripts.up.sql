@@ -0,0 +1,6 @@
+ALTER TABLE guild_mod_config
+    ADD COLUMN IF NOT EXISTS purge_transcripts BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE modlog_outbox
+    ADD COLUMN IF NOT EXISTS attachment_name TEXT,
+    ADD COLUMN IF NOT EXISTS attachment_content TEXT;
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS command_invocations (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT,
+    user_id BIGINT NOT NULL,
+    command TEXT NOT NULL,
+    args_hash TEXT,
+    success BOOLEAN NOT NULL,
+    error TEXT,
+    latency_ms BIGINT NOT NULL,
+    created_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS command_invocations_guild_created_idx
+    ON command_invocations (guild_id, created_at);
@@ -0,0 +1,20 @@
+-- Next case numbers per guild. `scope` is '' for the guild-wide case number
+-- and the case code (W, B, ...) for the per-action number.
+CREATE TABLE IF NOT EXISTS guild_case_counters (
+    guild_id BIGINT NOT NULL,
+    scope TEXT NOT NULL,
+    last_number BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, scope)
+);
+
+INSERT INTO guild_case_counters (guild_id, scope, last_number)
+SELECT guild_id, '', MAX(case_number)
+FROM mod_cases
+GROUP BY guild_id
+ON CONFLICT (guild_id, scope) DO NOTHING;
+
+INSERT INTO guild_case_counters (guild_id, scope, last_number)
+SELECT guild_id, case_code, MAX(action_case_number)
+FROM mod_cases
+GROUP BY guild_id, case_code
+ON CONFLICT (guild_id, scope) DO NOTHING;
@@ -0,0 +1,2 @@
+ALTER TABLE mod_cases
+    DROP COLUMN IF EXISTS anonymized_at;
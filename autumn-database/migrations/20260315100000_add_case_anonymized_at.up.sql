@@ -0,0 +1,2 @@
+ALTER TABLE mod_cases
+    ADD COLUMN IF NOT EXISTS anonymized_at BIGINT;
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS scheduled_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    target_user_id BIGINT,
+    payload TEXT NOT NULL DEFAULT '',
+    run_at BIGINT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_by BIGINT NOT NULL,
+    created_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS scheduled_jobs_run_at_idx
+    ON scheduled_jobs (run_at);
+
+CREATE INDEX IF NOT EXISTS scheduled_jobs_target_idx
+    ON scheduled_jobs (guild_id, kind, target_user_id);
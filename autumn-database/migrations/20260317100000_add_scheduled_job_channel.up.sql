@@ -0,0 +1,2 @@
+ALTER TABLE scheduled_jobs
+    ADD COLUMN IF NOT EXISTS channel_id BIGINT;
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS recurring_tasks (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    schedule TEXT NOT NULL,
+    created_by BIGINT NOT NULL,
+    created_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS recurring_tasks_guild_idx
+    ON recurring_tasks (guild_id);
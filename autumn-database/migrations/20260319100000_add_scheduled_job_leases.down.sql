@@ -0,0 +1,3 @@
+ALTER TABLE scheduled_jobs
+    DROP COLUMN IF EXISTS locked_until,
+    DROP COLUMN IF EXISTS locked_by;
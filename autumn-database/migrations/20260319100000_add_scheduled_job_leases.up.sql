@@ -0,0 +1,3 @@
+ALTER TABLE scheduled_jobs
+    ADD COLUMN IF NOT EXISTS locked_by TEXT,
+    ADD COLUMN IF NOT EXISTS locked_until BIGINT;
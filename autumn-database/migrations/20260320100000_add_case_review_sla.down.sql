@@ -0,0 +1,2 @@
+ALTER TABLE guild_mod_config
+    DROP COLUMN IF EXISTS case_review_sla_seconds;
@@ -0,0 +1,2 @@
+ALTER TABLE guild_mod_config
+    ADD COLUMN IF NOT EXISTS case_review_sla_seconds BIGINT;
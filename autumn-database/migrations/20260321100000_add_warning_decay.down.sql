@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS warning_decay_config;
+
+DROP INDEX IF EXISTS warnings_guild_active_warned_at_idx;
+
+ALTER TABLE warnings
+    DROP COLUMN IF EXISTS decayed_at;
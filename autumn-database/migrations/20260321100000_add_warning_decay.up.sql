@@ -0,0 +1,12 @@
+ALTER TABLE warnings
+    ADD COLUMN IF NOT EXISTS decayed_at BIGINT;
+
+CREATE INDEX IF NOT EXISTS warnings_guild_active_warned_at_idx
+    ON warnings (guild_id, warned_at)
+    WHERE decayed_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS warning_decay_config (
+    guild_id BIGINT PRIMARY KEY,
+    max_age_seconds BIGINT NOT NULL,
+    last_digest_at BIGINT NOT NULL
+);
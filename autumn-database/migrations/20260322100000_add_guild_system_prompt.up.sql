@@ -0,0 +1,2 @@
+ALTER TABLE guild_ai_config
+    ADD COLUMN IF NOT EXISTS system_prompt TEXT;
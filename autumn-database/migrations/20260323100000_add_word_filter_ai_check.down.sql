@@ -0,0 +1,4 @@
+ALTER TABLE word_filter_config
+    DROP COLUMN IF EXISTS ai_action,
+    DROP COLUMN IF EXISTS ai_threshold,
+    DROP COLUMN IF EXISTS ai_check_enabled;
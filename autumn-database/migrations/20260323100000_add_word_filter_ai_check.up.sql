@@ -0,0 +1,8 @@
+-- Optional second-stage LLM check for messages that pass the word list.
+-- `ai_threshold` is the minimum confidence (percent) before acting, and
+-- `ai_action` defaults to logging so guilds can review it before trusting it.
+ALTER TABLE word_filter_config
+    ADD COLUMN IF NOT EXISTS ai_check_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS ai_threshold SMALLINT NOT NULL DEFAULT 80
+        CHECK (ai_threshold BETWEEN 1 AND 100),
+    ADD COLUMN IF NOT EXISTS ai_action TEXT NOT NULL DEFAULT 'log_only';
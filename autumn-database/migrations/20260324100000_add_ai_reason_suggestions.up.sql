@@ -0,0 +1,3 @@
+-- Offer an LLM-written reason when `!warn` replies to a message without one.
+ALTER TABLE guild_ai_config
+    ADD COLUMN IF NOT EXISTS suggest_reasons BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Target language for `!translate`; NULL means English.
+ALTER TABLE guild_ai_config
+    ADD COLUMN IF NOT EXISTS translate_language TEXT;
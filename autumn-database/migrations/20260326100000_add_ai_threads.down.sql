@@ -0,0 +1 @@
+ALTER TABLE guild_ai_config DROP COLUMN IF EXISTS use_threads;
@@ -0,0 +1,3 @@
+-- Answer mentions in a new thread and keep talking there without mentions.
+ALTER TABLE guild_ai_config
+    ADD COLUMN IF NOT EXISTS use_threads BOOLEAN NOT NULL DEFAULT FALSE;
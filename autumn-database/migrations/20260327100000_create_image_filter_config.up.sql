@@ -0,0 +1,9 @@
+-- Optional LLM vision check on image attachments. `threshold` is the minimum
+-- confidence (percent) that an image is NSFW or gore before `action` runs.
+CREATE TABLE IF NOT EXISTS image_filter_config (
+    guild_id  BIGINT PRIMARY KEY,
+    enabled   BOOLEAN NOT NULL DEFAULT FALSE,
+    threshold SMALLINT NOT NULL DEFAULT 80 CHECK (threshold BETWEEN 1 AND 100),
+    action    TEXT NOT NULL DEFAULT 'delete_and_log'
+    -- action values: 'log_only', 'delete_and_log', 'warn_and_log', 'timeout_delete_and_log'
+);
@@ -0,0 +1,11 @@
+-- Bot permission levels granted to roles. A level stands in for the Discord
+-- permissions commands check, so e.g. a helper role can `!warn` without
+-- MANAGE_MESSAGES.
+CREATE TABLE IF NOT EXISTS permission_level_roles (
+    guild_id   BIGINT NOT NULL,
+    role_id    BIGINT NOT NULL,
+    level      TEXT   NOT NULL CHECK (level IN ('helper', 'mod', 'admin')),
+    created_by BIGINT NOT NULL,
+    created_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    PRIMARY KEY (guild_id, role_id)
+);
@@ -0,0 +1,10 @@
+-- Commands switched off with `!command disable`. channel_id 0 disables the
+-- command in every channel of the guild.
+CREATE TABLE IF NOT EXISTS disabled_commands (
+    guild_id     BIGINT NOT NULL,
+    command_name TEXT   NOT NULL,
+    channel_id   BIGINT NOT NULL DEFAULT 0,
+    created_by   BIGINT NOT NULL,
+    created_at   BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    PRIMARY KEY (guild_id, command_name, channel_id)
+);
@@ -0,0 +1,9 @@
+-- Per-guild prefix command aliases, e.g. `!mute` for `!timeout`.
+CREATE TABLE IF NOT EXISTS command_aliases (
+    guild_id     BIGINT NOT NULL,
+    alias        TEXT   NOT NULL,
+    command_name TEXT   NOT NULL,
+    created_by   BIGINT NOT NULL,
+    created_at   BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    PRIMARY KEY (guild_id, alias)
+);
@@ -0,0 +1,5 @@
+-- General moderation settings that don't belong to a filter or the modlog.
+CREATE TABLE IF NOT EXISTS guild_moderation_settings (
+    guild_id          BIGINT PRIMARY KEY,
+    enforce_hierarchy BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1 @@
+ALTER TABLE guild_moderation_settings DROP COLUMN IF EXISTS embed_color;
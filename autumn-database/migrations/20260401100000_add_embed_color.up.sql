@@ -0,0 +1,2 @@
+-- Accent color for the bot's embeds in this guild; NULL keeps the default.
+ALTER TABLE guild_moderation_settings ADD COLUMN IF NOT EXISTS embed_color INTEGER;
@@ -0,0 +1,20 @@
+-- Member reports waiting in the moderation queue (`!reports`). Claiming a
+-- report opens a case for it; resolving the report resolves that case.
+CREATE TABLE IF NOT EXISTS user_reports (
+    id               BIGSERIAL PRIMARY KEY,
+    guild_id         BIGINT NOT NULL,
+    reporter_user_id BIGINT NOT NULL,
+    target_user_id   BIGINT NOT NULL,
+    reason           TEXT   NOT NULL,
+    channel_id       BIGINT,
+    message_id       BIGINT,
+    message_content  TEXT,
+    status           TEXT   NOT NULL DEFAULT 'open',
+    handled_by       BIGINT,
+    case_label       TEXT,
+    created_at       BIGINT NOT NULL,
+    updated_at       BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_reports_guild_status
+    ON user_reports (guild_id, status, id);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS modmail_messages;
+DROP TABLE IF EXISTS modmail_conversations;
+DROP TABLE IF EXISTS guild_modmail_config;
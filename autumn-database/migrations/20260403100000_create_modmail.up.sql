@@ -0,0 +1,41 @@
+-- Staff channel that DMs to the bot are relayed into, one thread per member.
+CREATE TABLE IF NOT EXISTS guild_modmail_config (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL
+);
+
+-- A member's modmail conversation with one guild's staff. The thread is set
+-- once Discord has created it.
+CREATE TABLE IF NOT EXISTS modmail_conversations (
+    id          BIGSERIAL PRIMARY KEY,
+    guild_id    BIGINT NOT NULL,
+    user_id     BIGINT NOT NULL,
+    thread_id   BIGINT,
+    status      TEXT   NOT NULL DEFAULT 'open',
+    opened_at   BIGINT NOT NULL,
+    closed_at   BIGINT,
+    closed_by   BIGINT
+);
+
+-- DMs are routed by user, so a member has at most one open conversation.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_modmail_conversations_open_user
+    ON modmail_conversations (user_id) WHERE status = 'open';
+
+CREATE INDEX IF NOT EXISTS idx_modmail_conversations_thread
+    ON modmail_conversations (thread_id);
+
+-- Every relayed message, kept for the transcript posted on close.
+CREATE TABLE IF NOT EXISTS modmail_messages (
+    id              BIGSERIAL PRIMARY KEY,
+    conversation_id BIGINT NOT NULL REFERENCES modmail_conversations (id) ON DELETE CASCADE,
+    author_user_id  BIGINT NOT NULL,
+    author_name     TEXT   NOT NULL,
+    from_staff      BOOLEAN NOT NULL,
+    content         TEXT   NOT NULL,
+    -- Attachment URLs, one per line.
+    attachments     TEXT   NOT NULL DEFAULT '',
+    created_at      BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_modmail_messages_conversation
+    ON modmail_messages (conversation_id, id);
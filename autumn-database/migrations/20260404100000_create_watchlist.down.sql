@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS guild_watchlist_config;
+DROP TABLE IF EXISTS watchlist;
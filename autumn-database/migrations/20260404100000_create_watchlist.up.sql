@@ -0,0 +1,15 @@
+-- Members staff want to keep an eye on; their activity is posted to the
+-- watchlist channel.
+CREATE TABLE IF NOT EXISTS watchlist (
+    guild_id   BIGINT NOT NULL,
+    user_id    BIGINT NOT NULL,
+    note       TEXT,
+    added_by   BIGINT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS guild_watchlist_config (
+    guild_id   BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE guild_moderation_settings DROP COLUMN IF EXISTS global_blacklist_action;
+DROP TABLE IF EXISTS global_blacklist;
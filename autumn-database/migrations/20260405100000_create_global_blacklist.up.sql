@@ -0,0 +1,12 @@
+-- Users the bot owners have blacklisted everywhere Autumn runs.
+CREATE TABLE IF NOT EXISTS global_blacklist (
+    user_id    BIGINT PRIMARY KEY,
+    reason     TEXT   NOT NULL,
+    added_by   BIGINT NOT NULL,
+    created_at BIGINT NOT NULL
+);
+
+-- What a guild does when a blacklisted user joins: ban, quarantine or off.
+-- NULL means ban.
+ALTER TABLE guild_moderation_settings
+    ADD COLUMN IF NOT EXISTS global_blacklist_action TEXT;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS ban_sync_mirrors;
+DROP TABLE IF EXISTS ban_sync_members;
+DROP TABLE IF EXISTS ban_sync_groups;
@@ -0,0 +1,39 @@
+-- Guilds that mirror tagged bans to each other.
+CREATE TABLE IF NOT EXISTS ban_sync_groups (
+    id          BIGSERIAL PRIMARY KEY,
+    name        TEXT   NOT NULL,
+    -- Bans whose reason contains this tag are mirrored to the other members.
+    tag         TEXT   NOT NULL,
+    invite_code TEXT   NOT NULL UNIQUE,
+    created_by  BIGINT NOT NULL,
+    created_at  BIGINT NOT NULL
+);
+
+-- A guild belongs to at most one group.
+CREATE TABLE IF NOT EXISTS ban_sync_members (
+    guild_id  BIGINT PRIMARY KEY,
+    group_id  BIGINT NOT NULL REFERENCES ban_sync_groups (id) ON DELETE CASCADE,
+    joined_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ban_sync_members_group
+    ON ban_sync_members (group_id);
+
+-- One row per ban applied in a partner guild, kept so it can be undone after
+-- either guild leaves the group.
+CREATE TABLE IF NOT EXISTS ban_sync_mirrors (
+    id              BIGSERIAL PRIMARY KEY,
+    source_guild_id BIGINT NOT NULL,
+    target_guild_id BIGINT NOT NULL,
+    user_id         BIGINT NOT NULL,
+    created_at      BIGINT NOT NULL,
+    undone_at       BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS idx_ban_sync_mirrors_source
+    ON ban_sync_mirrors (source_guild_id, user_id)
+    WHERE undone_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_ban_sync_mirrors_target
+    ON ban_sync_mirrors (target_guild_id, user_id)
+    WHERE undone_at IS NULL;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS quarantines;
+DROP TABLE IF EXISTS guild_quarantine_config;
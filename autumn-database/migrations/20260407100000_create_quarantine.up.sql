@@ -0,0 +1,16 @@
+-- The role `!quarantine` assigns, created on first use.
+CREATE TABLE IF NOT EXISTS guild_quarantine_config (
+    guild_id BIGINT PRIMARY KEY,
+    role_id  BIGINT NOT NULL
+);
+
+-- Quarantined members and the roles `!release` gives back.
+CREATE TABLE IF NOT EXISTS quarantines (
+    guild_id     BIGINT   NOT NULL,
+    user_id      BIGINT   NOT NULL,
+    role_ids     BIGINT[] NOT NULL,
+    reason       TEXT     NOT NULL,
+    moderator_id BIGINT   NOT NULL,
+    created_at   BIGINT   NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
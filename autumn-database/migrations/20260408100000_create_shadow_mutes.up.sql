@@ -0,0 +1,10 @@
+-- Members whose messages are silently deleted until `expires_at`.
+CREATE TABLE IF NOT EXISTS shadow_mutes (
+    guild_id     BIGINT NOT NULL,
+    user_id      BIGINT NOT NULL,
+    reason       TEXT   NOT NULL,
+    moderator_id BIGINT NOT NULL,
+    created_at   BIGINT NOT NULL,
+    expires_at   BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
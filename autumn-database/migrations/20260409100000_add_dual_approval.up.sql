@@ -0,0 +1,3 @@
+-- Bans and terminations wait for a second moderator's approval.
+ALTER TABLE guild_moderation_settings
+    ADD COLUMN IF NOT EXISTS dual_approval BOOLEAN NOT NULL DEFAULT FALSE;
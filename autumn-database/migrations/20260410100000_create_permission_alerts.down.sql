@@ -0,0 +1 @@
+DROP TABLE IF EXISTS guild_permission_alert_config;
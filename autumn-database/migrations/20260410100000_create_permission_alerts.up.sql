@@ -0,0 +1,6 @@
+-- Staff channel alerted when a role or channel overwrite is granted a
+-- dangerous permission.
+CREATE TABLE IF NOT EXISTS guild_permission_alert_config (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL
+);
@@ -0,0 +1,113 @@
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+
+/// In-process LRU tier kept in front of the shared backend. Entries expire
+/// after their own TTL, capped at `max_ttl` so values written by other
+/// processes are picked up within that bound.
+#[derive(Clone, Debug)]
+pub struct MemoryCacheStore {
+    entries: Arc<Mutex<LruCache<String, MemoryEntry>>>,
+    max_ttl: Duration,
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    value: Vec<u8>,
+    expires_at: Instant,
+}
+
+impl MemoryCacheStore {
+    pub fn new(capacity: NonZeroUsize, max_ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
+            max_ttl,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.get(key)?;
+        if entry.expires_at <= Instant::now() {
+            entries.pop(key);
+            return None;
+        }
+        Some(entry.value.clone())
+    }
+
+    pub fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) {
+        let expires_at = Instant::now() + ttl.min(self.max_ttl);
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(key.to_owned(), MemoryEntry { value, expires_at });
+    }
+
+    pub fn del(&self, key: &str) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop(key);
+    }
+
+    pub fn del_prefix(&self, prefix: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let keys: Vec<String> = entries
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            entries.pop(&key);
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .peek(key)
+            .is_some_and(|entry| entry.expires_at > Instant::now())
+    }
+
+    pub fn max_ttl(&self) -> Duration {
+        self.max_ttl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(capacity: usize) -> MemoryCacheStore {
+        MemoryCacheStore::new(
+            NonZeroUsize::new(capacity).unwrap(),
+            Duration::from_secs(30),
+        )
+    }
+
+    #[test]
+    fn stores_and_invalidates_entries() {
+        let store = store(4);
+        store.set("a", b"1".to_vec(), Duration::from_secs(60));
+        assert_eq!(store.get("a"), Some(b"1".to_vec()));
+
+        store.del("a");
+        assert_eq!(store.get("a"), None);
+    }
+
+    #[test]
+    fn expires_and_evicts_entries() {
+        let store = store(2);
+        store.set("expired", b"1".to_vec(), Duration::ZERO);
+        assert_eq!(store.get("expired"), None);
+
+        store.set("a", b"1".to_vec(), Duration::from_secs(60));
+        store.set("b", b"2".to_vec(), Duration::from_secs(60));
+        store.set("c", b"3".to_vec(), Duration::from_secs(60));
+        assert_eq!(store.get("a"), None);
+        assert_eq!(store.get("c"), Some(b"3".to_vec()));
+    }
+}
@@ -1,41 +1,101 @@
+mod memory_store;
 mod noop_store;
+mod rate_limit;
 mod redis_store;
+mod store;
+mod ttl;
 
+use std::collections::HashMap;
 use std::future::Future;
-use std::sync::Arc;
+use std::num::NonZeroUsize;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use tokio::sync::OnceCell;
 use tracing::warn;
 
-use noop_store::NoopCacheStore;
+use memory_store::MemoryCacheStore;
+pub use noop_store::NoopCacheStore;
+pub use rate_limit::{
+    DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_MAX_HITS, DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_WINDOW,
+    RateLimit, RateLimitDecision, RateLimiter,
+};
 use redis_store::RedisCacheStore;
+pub use redis_store::RedisTopology;
+pub use store::{CacheFuture, CacheStore};
+pub use ttl::{CacheTtlClass, CacheTtls};
 
+/// The built-in stores, plus any [`CacheStore`] supplied by the embedder.
 #[derive(Clone, Debug)]
 enum CacheBackend {
     Disabled(NoopCacheStore),
     Redis(RedisCacheStore),
+    Custom(Arc<dyn CacheStore>),
+}
+
+impl CacheBackend {
+    fn store(&self) -> &dyn CacheStore {
+        match self {
+            Self::Disabled(store) => store,
+            Self::Redis(store) => store,
+            Self::Custom(store) => store.as_ref(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct CacheService {
     key_prefix: String,
     backend: CacheBackend,
+    /// In-process tier checked before `backend`; `None` when disabled.
+    memory: Option<MemoryCacheStore>,
+    /// Loads currently running in `get_or_load_json`, by key.
+    inflight: Arc<Mutex<HashMap<String, InflightLoad>>>,
     stats: Arc<CacheStatsInner>,
-    llm_rate_limit_window: Duration,
-    llm_rate_limit_max_hits: u64,
+    ttls: CacheTtls,
+    /// Overrides for named limiters; the rest use their defaults.
+    rate_limits: HashMap<RateLimiter, RateLimit>,
+}
+
+/// Serialized result of a load shared with callers waiting on the same key.
+type InflightLoad = Arc<OnceCell<Vec<u8>>>;
+
+/// Drops the `inflight` entry once its last caller is done, including when
+/// a caller's future is cancelled mid-load.
+struct InflightGuard<'a> {
+    inflight: &'a Mutex<HashMap<String, InflightLoad>>,
+    key: &'a str,
+    load: InflightLoad,
+}
+
+impl Drop for InflightGuard<'_> {
+    fn drop(&mut self) {
+        let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+        // Clones are only taken under this lock, so the count is stable:
+        // the map and this guard hold the last two references.
+        if Arc::strong_count(&self.load) == 2
+            && inflight
+                .get(self.key)
+                .is_some_and(|load| Arc::ptr_eq(load, &self.load))
+        {
+            inflight.remove(self.key);
+        }
+    }
 }
 
 #[derive(Debug, Default)]
 struct CacheStatsInner {
     hit: AtomicU64,
+    memory_hit: AtomicU64,
     miss: AtomicU64,
     set: AtomicU64,
     del: AtomicU64,
     error: AtomicU64,
     fallback_load: AtomicU64,
+    coalesced_load: AtomicU64,
     ratelimit_checks: AtomicU64,
     ratelimit_blocks: AtomicU64,
 }
@@ -43,11 +103,15 @@ struct CacheStatsInner {
 #[derive(Clone, Debug, Default)]
 pub struct CacheStatsSnapshot {
     pub hit: u64,
+    /// Hits served by the in-process tier; included in `hit`.
+    pub memory_hit: u64,
     pub miss: u64,
     pub set: u64,
     pub del: u64,
     pub error: u64,
     pub fallback_load: u64,
+    /// Misses that waited on another caller's load instead of running their own.
+    pub coalesced_load: u64,
     pub ratelimit_checks: u64,
     pub ratelimit_blocks: u64,
 }
@@ -56,19 +120,30 @@ impl CacheStatsInner {
     fn snapshot(&self) -> CacheStatsSnapshot {
         CacheStatsSnapshot {
             hit: self.hit.load(Ordering::Relaxed),
+            memory_hit: self.memory_hit.load(Ordering::Relaxed),
             miss: self.miss.load(Ordering::Relaxed),
             set: self.set.load(Ordering::Relaxed),
             del: self.del.load(Ordering::Relaxed),
             error: self.error.load(Ordering::Relaxed),
             fallback_load: self.fallback_load.load(Ordering::Relaxed),
+            coalesced_load: self.coalesced_load.load(Ordering::Relaxed),
             ratelimit_checks: self.ratelimit_checks.load(Ordering::Relaxed),
             ratelimit_blocks: self.ratelimit_blocks.load(Ordering::Relaxed),
         }
     }
 }
 
+/// Default TTL for guild config values; see [`CacheTtls`].
 pub const CONFIG_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
 pub const WORD_LIST_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+pub const INVITE_RESOLVE_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+/// How long deleted/edited messages stay available to `snipe`/`editsnipe`.
+pub const SNIPE_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+pub const DEFAULT_MEMORY_CACHE_CAPACITY: usize = 10_000;
+/// Upper bound on how long the in-process tier keeps an entry. Invalidations
+/// from this process apply immediately; ones from other processes sharing
+/// Redis are seen once the local copy expires.
+pub const DEFAULT_MEMORY_CACHE_MAX_TTL: Duration = Duration::from_secs(30);
 pub const DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
 pub const DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS: u64 = 2;
 
@@ -77,61 +152,114 @@ impl CacheService {
         Self {
             key_prefix: prefix.into(),
             backend: CacheBackend::Disabled(NoopCacheStore),
+            memory: default_memory_store(),
+            inflight: Arc::default(),
             stats: Arc::new(CacheStatsInner::default()),
-            llm_rate_limit_window: DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
-            llm_rate_limit_max_hits: DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
+            ttls: CacheTtls::default(),
+            rate_limits: HashMap::new(),
         }
     }
 
     pub fn redis(redis_url: &str, prefix: impl Into<String>) -> anyhow::Result<Self> {
+        Self::redis_with_topology(
+            &RedisTopology::Single {
+                url: redis_url.to_owned(),
+            },
+            prefix,
+        )
+    }
+
+    /// Connect to a single node, a Redis Cluster or a Sentinel-managed primary.
+    pub fn redis_with_topology(
+        topology: &RedisTopology,
+        prefix: impl Into<String>,
+    ) -> anyhow::Result<Self> {
         Ok(Self {
             key_prefix: prefix.into(),
-            backend: CacheBackend::Redis(RedisCacheStore::from_url(redis_url)?),
+            backend: CacheBackend::Redis(RedisCacheStore::from_topology(topology)?),
+            memory: default_memory_store(),
+            inflight: Arc::default(),
             stats: Arc::new(CacheStatsInner::default()),
-            llm_rate_limit_window: DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
-            llm_rate_limit_max_hits: DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
+            ttls: CacheTtls::default(),
+            rate_limits: HashMap::new(),
         })
     }
 
-    pub fn configure_llm_rate_limit(&mut self, window: Duration, max_hits: u64) {
-        let (window, max_hits) = normalize_llm_rate_limit(window, max_hits);
-        self.llm_rate_limit_window = window;
-        self.llm_rate_limit_max_hits = max_hits;
+    /// Use a caller-provided store (memcached, an in-memory map, a test
+    /// double, ...) instead of the built-in backends.
+    pub fn with_store(store: Arc<dyn CacheStore>, prefix: impl Into<String>) -> Self {
+        Self {
+            key_prefix: prefix.into(),
+            backend: CacheBackend::Custom(store),
+            memory: default_memory_store(),
+            inflight: Arc::default(),
+            stats: Arc::new(CacheStatsInner::default()),
+            ttls: CacheTtls::default(),
+            rate_limits: HashMap::new(),
+        }
+    }
+
+    pub fn configure_rate_limit(&mut self, limiter: RateLimiter, limit: RateLimit) {
+        self.rate_limits
+            .insert(limiter, RateLimit::new(limit.window, limit.max_hits));
+    }
+
+    /// Resize the in-process tier. A capacity of zero disables it.
+    pub fn configure_memory_cache(&mut self, capacity: usize, max_ttl: Duration) {
+        self.memory = NonZeroUsize::new(capacity)
+            .map(|capacity| MemoryCacheStore::new(capacity, max_ttl.max(Duration::from_secs(1))));
+    }
+
+    pub fn is_memory_cache_enabled(&self) -> bool {
+        self.memory.is_some()
     }
 
-    pub fn llm_rate_limit_window(&self) -> Duration {
-        self.llm_rate_limit_window
+    pub fn configure_ttls(&mut self, ttls: CacheTtls) {
+        self.ttls = ttls;
     }
 
-    pub fn llm_rate_limit_max_hits(&self) -> u64 {
-        self.llm_rate_limit_max_hits
+    pub fn ttl(&self, class: CacheTtlClass) -> Duration {
+        self.ttls.get(class)
+    }
+
+    pub fn rate_limit(&self, limiter: RateLimiter) -> RateLimit {
+        self.rate_limits
+            .get(&limiter)
+            .copied()
+            .unwrap_or_else(|| limiter.default_limit())
     }
 
     pub fn key(&self, suffix: impl AsRef<str>) -> String {
         format!("{}:{}", self.key_prefix, suffix.as_ref())
     }
 
+    pub fn key_prefix(&self) -> &str {
+        &self.key_prefix
+    }
+
     pub async fn get_json<T>(&self, key: &str) -> anyhow::Result<Option<T>>
     where
         T: DeserializeOwned,
     {
-        let value = match &self.backend {
-            CacheBackend::Disabled(store) => store.get(key).await,
-            CacheBackend::Redis(store) => store.get(key).await,
+        if let Some(bytes) = self.memory.as_ref().and_then(|memory| memory.get(key)) {
+            let parsed = self.parse_cached(key, &bytes)?;
+            self.stats.memory_hit.fetch_add(1, Ordering::Relaxed);
+            self.stats.hit.fetch_add(1, Ordering::Relaxed);
+            return Ok(Some(parsed));
         }
-        .inspect_err(|_| {
+
+        let value = self.backend.store().get(key).await.inspect_err(|_| {
             self.stats.error.fetch_add(1, Ordering::Relaxed);
         })?;
 
         match value {
             Some(bytes) => {
-                let parsed = serde_json::from_slice(&bytes)
-                    .map_err(|e| {
-                        anyhow::anyhow!("failed to deserialize cache value for `{key}`: {e}")
-                    })
-                    .inspect_err(|_| {
-                        self.stats.error.fetch_add(1, Ordering::Relaxed);
-                    })?;
+                let parsed = self.parse_cached(key, &bytes)?;
+                // The backend's remaining TTL isn't known here, so the local
+                // copy lives for the memory tier's maximum.
+                if let Some(memory) = &self.memory {
+                    memory.set(key, bytes, memory.max_ttl());
+                }
                 self.stats.hit.fetch_add(1, Ordering::Relaxed);
                 Ok(Some(parsed))
             }
@@ -150,10 +278,13 @@ impl CacheService {
         let payload = serde_json::to_vec(value)
             .map_err(|e| anyhow::anyhow!("failed to serialize cache value for `{key}`: {e}"))?;
 
-        let result = match &self.backend {
-            CacheBackend::Disabled(store) => store.set(key, payload, ttl_seconds).await,
-            CacheBackend::Redis(store) => store.set(key, payload, ttl_seconds).await,
-        };
+        // Write-through: the local copy is kept even if the backend write
+        // fails, since the value itself is current.
+        if let Some(memory) = &self.memory {
+            memory.set(key, payload.clone(), ttl);
+        }
+
+        let result = self.backend.store().set(key, payload, ttl_seconds).await;
 
         match result {
             Ok(()) => {
@@ -167,11 +298,97 @@ impl CacheService {
         }
     }
 
+    /// Fetch several keys in one backend round trip. Keys held by the
+    /// in-process tier are answered locally; the rest are fetched together.
+    pub async fn get_many_json<T>(&self, keys: &[&str]) -> anyhow::Result<Vec<Option<T>>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut values: Vec<Option<T>> = Vec::with_capacity(keys.len());
+        let mut pending: Vec<usize> = Vec::new();
+
+        for (index, key) in keys.iter().enumerate() {
+            match self.memory.as_ref().and_then(|memory| memory.get(key)) {
+                Some(bytes) => {
+                    values.push(Some(self.parse_cached(key, &bytes)?));
+                    self.stats.memory_hit.fetch_add(1, Ordering::Relaxed);
+                    self.stats.hit.fetch_add(1, Ordering::Relaxed);
+                }
+                None => {
+                    values.push(None);
+                    pending.push(index);
+                }
+            }
+        }
+
+        if pending.is_empty() {
+            return Ok(values);
+        }
+
+        let pending_keys: Vec<&str> = pending.iter().map(|&index| keys[index]).collect();
+        let fetched = self
+            .backend
+            .store()
+            .get_many(&pending_keys)
+            .await
+            .inspect_err(|_| {
+                self.stats.error.fetch_add(1, Ordering::Relaxed);
+            })?;
+
+        for (&index, bytes) in pending.iter().zip(fetched) {
+            let key = keys[index];
+            match bytes {
+                Some(bytes) => {
+                    values[index] = Some(self.parse_cached(key, &bytes)?);
+                    if let Some(memory) = &self.memory {
+                        memory.set(key, bytes, memory.max_ttl());
+                    }
+                    self.stats.hit.fetch_add(1, Ordering::Relaxed);
+                }
+                None => {
+                    self.stats.miss.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Store several keys with one TTL, pipelined where the backend allows.
+    pub async fn set_many_json<T>(&self, entries: &[(&str, T)], ttl: Duration) -> anyhow::Result<()>
+    where
+        T: Serialize,
+    {
+        let ttl_seconds = ttl.as_secs().max(1);
+        let mut payloads = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            let payload = serde_json::to_vec(value)
+                .map_err(|e| anyhow::anyhow!("failed to serialize cache value for `{key}`: {e}"))?;
+            if let Some(memory) = &self.memory {
+                memory.set(key, payload.clone(), ttl);
+            }
+            payloads.push((*key, payload));
+        }
+
+        let count = payloads.len() as u64;
+        match self.backend.store().set_many(payloads, ttl_seconds).await {
+            Ok(()) => {
+                self.stats.set.fetch_add(count, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(e) => {
+                self.stats.error.fetch_add(1, Ordering::Relaxed);
+                Err(e)
+            }
+        }
+    }
+
     pub async fn del(&self, key: &str) -> anyhow::Result<()> {
-        let result = match &self.backend {
-            CacheBackend::Disabled(store) => store.del(key).await,
-            CacheBackend::Redis(store) => store.del(key).await,
-        };
+        if let Some(memory) = &self.memory {
+            memory.del(key);
+        }
+
+        let result = self.backend.store().del(key).await;
 
         match result {
             Ok(()) => {
@@ -185,6 +402,44 @@ impl CacheService {
         }
     }
 
+    /// The stored bytes for `key` in the backend, bypassing the in-process
+    /// tier. Returns whether the in-process tier also holds it.
+    pub async fn inspect(&self, key: &str) -> anyhow::Result<(Option<Vec<u8>>, bool)> {
+        let in_memory = self
+            .memory
+            .as_ref()
+            .is_some_and(|memory| memory.contains(key));
+        let value = self.backend.store().get(key).await.inspect_err(|_| {
+            self.stats.error.fetch_add(1, Ordering::Relaxed);
+        })?;
+        Ok((value, in_memory))
+    }
+
+    /// Delete every key starting with `prefix`, in both tiers. Returns how
+    /// many backend keys were removed.
+    pub async fn del_prefix(&self, prefix: &str) -> anyhow::Result<u64> {
+        if let Some(memory) = &self.memory {
+            memory.del_prefix(prefix);
+        }
+
+        let store = self.backend.store();
+        let result = match store.scan_prefix(prefix).await {
+            Ok(keys) => store.del_many(&keys).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(deleted) => {
+                self.stats.del.fetch_add(deleted, Ordering::Relaxed);
+                Ok(deleted)
+            }
+            Err(e) => {
+                self.stats.error.fetch_add(1, Ordering::Relaxed);
+                Err(e)
+            }
+        }
+    }
+
     pub async fn get_or_load_json<T, F, Fut>(
         &self,
         key: &str,
@@ -206,28 +461,57 @@ impl CacheService {
             ),
         }
 
-        self.stats.fallback_load.fetch_add(1, Ordering::Relaxed);
-
-        let loaded = loader().await?;
+        // Concurrent misses on the same key share one load. A failed load
+        // isn't shared: the next waiter runs its own loader instead.
+        let load = {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(inflight.entry(key.to_owned()).or_default())
+        };
+        let guard = InflightGuard {
+            inflight: &self.inflight,
+            key,
+            load,
+        };
 
-        if let Err(e) = self.set_json(key, &loaded, ttl).await {
-            warn!(
-                ?e,
-                cache_key = key,
-                "cache set failed; returning database value"
-            );
+        let mut own = None;
+        let shared = guard
+            .load
+            .get_or_try_init(|| async {
+                self.stats.fallback_load.fetch_add(1, Ordering::Relaxed);
+
+                let loaded = loader().await?;
+                let bytes = serde_json::to_vec(&loaded)?;
+
+                if let Err(e) = self.set_json(key, &loaded, ttl).await {
+                    warn!(
+                        ?e,
+                        cache_key = key,
+                        "cache set failed; returning database value"
+                    );
+                }
+
+                own = Some(loaded);
+                anyhow::Ok(bytes)
+            })
+            .await?;
+
+        match own {
+            Some(loaded) => Ok(loaded),
+            None => {
+                self.stats.coalesced_load.fetch_add(1, Ordering::Relaxed);
+                Ok(serde_json::from_slice(shared)?)
+            }
         }
-
-        Ok(loaded)
     }
 
     pub async fn increment_with_window(&self, key: &str, window: Duration) -> anyhow::Result<u64> {
         self.stats.ratelimit_checks.fetch_add(1, Ordering::Relaxed);
         let window_seconds = window.as_secs().max(1);
-        let result = match &self.backend {
-            CacheBackend::Disabled(store) => store.increment_with_window(key, window_seconds).await,
-            CacheBackend::Redis(store) => store.increment_with_window(key, window_seconds).await,
-        };
+        let result = self
+            .backend
+            .store()
+            .increment_with_window(key, window_seconds)
+            .await;
 
         match result {
             Ok(value) => Ok(value),
@@ -238,6 +522,65 @@ impl CacheService {
         }
     }
 
+    /// Count a hit against `key` and return the hits in the trailing
+    /// `window`, approximated from the current and previous fixed windows.
+    pub async fn sliding_window_count(&self, key: &str, window: Duration) -> anyhow::Result<u64> {
+        let window_ms = u64::try_from(window.as_millis())
+            .unwrap_or(u64::MAX)
+            .max(1000);
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|now| u64::try_from(now.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or(0);
+        let bucket = now_ms / window_ms;
+
+        // Each counter has to outlive the window after its own.
+        let current = self
+            .increment_with_window(&format!("{key}:{bucket}"), window * 2)
+            .await?;
+        let previous = match self
+            .backend
+            .store()
+            .get(&format!("{key}:{}", bucket.saturating_sub(1)))
+            .await
+        {
+            Ok(value) => value
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .and_then(|count| count.trim().parse::<u64>().ok())
+                .unwrap_or(0),
+            Err(e) => {
+                self.stats.error.fetch_add(1, Ordering::Relaxed);
+                return Err(e);
+            }
+        };
+
+        Ok(rate_limit::sliding_window_estimate(
+            previous,
+            current,
+            now_ms % window_ms,
+            window_ms,
+        ))
+    }
+
+    /// Count a hit against `key` and decide whether it fits in `limit`.
+    pub async fn check_rate_limit(
+        &self,
+        key: &str,
+        limit: RateLimit,
+    ) -> anyhow::Result<RateLimitDecision> {
+        let count = self.sliding_window_count(key, limit.window).await?;
+        let allowed = count <= limit.max_hits;
+        if !allowed {
+            self.record_rate_limit_block();
+        }
+
+        Ok(RateLimitDecision {
+            allowed,
+            count,
+            retry_after: limit.window,
+        })
+    }
+
     pub fn record_rate_limit_block(&self) {
         self.stats.ratelimit_blocks.fetch_add(1, Ordering::Relaxed);
     }
@@ -247,41 +590,125 @@ impl CacheService {
     }
 
     pub async fn ping(&self) -> anyhow::Result<()> {
-        match &self.backend {
-            CacheBackend::Disabled(store) => store.ping().await,
-            CacheBackend::Redis(store) => store.ping().await,
-        }
+        self.backend.store().ping().await
     }
 
     pub fn is_redis_enabled(&self) -> bool {
         matches!(self.backend, CacheBackend::Redis(_))
     }
+
+    /// Human-readable backend name for status output.
+    pub fn backend_description(&self) -> String {
+        match &self.backend {
+            CacheBackend::Disabled(_) => "Disabled".to_owned(),
+            CacheBackend::Redis(store) => format!("Redis ({})", store.mode()),
+            CacheBackend::Custom(_) => "Custom".to_owned(),
+        }
+    }
+
+    /// `single`, `cluster` or `sentinel` when Redis is enabled.
+    pub fn redis_mode(&self) -> Option<&'static str> {
+        match &self.backend {
+            CacheBackend::Disabled(_) | CacheBackend::Custom(_) => None,
+            CacheBackend::Redis(store) => Some(store.mode()),
+        }
+    }
+
+    fn parse_cached<T>(&self, key: &str, bytes: &[u8]) -> anyhow::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_slice(bytes)
+            .map_err(|e| anyhow::anyhow!("failed to deserialize cache value for `{key}`: {e}"))
+            .inspect_err(|_| {
+                self.stats.error.fetch_add(1, Ordering::Relaxed);
+            })
+    }
 }
 
-fn normalize_llm_rate_limit(window: Duration, max_hits: u64) -> (Duration, u64) {
-    let window_seconds = window.as_secs().clamp(1, 3600);
-    let normalized_hits = max_hits.max(1);
-    (Duration::from_secs(window_seconds), normalized_hits)
+fn default_memory_store() -> Option<MemoryCacheStore> {
+    NonZeroUsize::new(DEFAULT_MEMORY_CACHE_CAPACITY)
+        .map(|capacity| MemoryCacheStore::new(capacity, DEFAULT_MEMORY_CACHE_MAX_TTL))
 }
 
-pub fn ai_config_key(cache: &CacheService, guild_id: u64) -> String {
-    cache.key(format!("guild:{guild_id}:config:ai"))
+/// Prefix shared by every key scoped to `guild_id`.
+pub fn guild_key_prefix(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:"))
 }
 
-pub fn modlog_config_key(cache: &CacheService, guild_id: u64) -> String {
-    cache.key(format!("guild:{guild_id}:config:modlog"))
+pub fn guild_config_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config"))
 }
 
-pub fn escalation_config_key(cache: &CacheService, guild_id: u64) -> String {
-    cache.key(format!("guild:{guild_id}:config:escalation"))
+/// Versioned since the cached words gained a match mode; entries in the old
+/// shape are left to expire instead of failing to decode.
+pub fn word_filter_words_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:word_filter_words:v2"))
 }
 
-pub fn word_filter_config_key(cache: &CacheService, guild_id: u64) -> String {
-    cache.key(format!("guild:{guild_id}:config:word_filter"))
+pub fn link_filter_domains_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:link_filter_domains"))
 }
 
-pub fn word_filter_words_key(cache: &CacheService, guild_id: u64) -> String {
-    cache.key(format!("guild:{guild_id}:config:word_filter_words"))
+pub fn modlog_template_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:modlog_template"))
+}
+
+pub fn name_filter_config_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:name_filter"))
+}
+
+pub fn invite_guild_key(cache: &CacheService, code: &str) -> String {
+    cache.key(format!("invite:{code}:guild"))
+}
+
+pub fn antispam_rate_key(cache: &CacheService, guild_id: u64, user_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:user:{user_id}:antispam:rate"))
+}
+
+pub fn antispam_duplicate_key(
+    cache: &CacheService,
+    guild_id: u64,
+    user_id: u64,
+    fingerprint: &str,
+) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:user:{user_id}:antispam:dup:{fingerprint}"
+    ))
+}
+
+pub fn antiraid_config_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:antiraid"))
+}
+
+pub fn antiraid_join_rate_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:antiraid:joins"))
+}
+
+pub fn raidmode_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:raidmode"))
+}
+
+pub fn snipe_key(cache: &CacheService, guild_id: u64, channel_id: u64, kind: &str) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:channel:{channel_id}:snipe:{kind}"
+    ))
+}
+
+pub fn exemptions_key(cache: &CacheService, guild_id: u64, scope: &str) -> String {
+    cache.key(format!("guild:{guild_id}:config:exemptions:{scope}"))
+}
+
+pub fn permission_levels_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:permission_levels"))
+}
+
+pub fn command_aliases_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:command_aliases"))
+}
+
+pub fn disabled_commands_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:disabled_commands"))
 }
 
 pub fn llm_mention_rate_limit_key(
@@ -295,26 +722,106 @@ pub fn llm_mention_rate_limit_key(
     ))
 }
 
-pub async fn invalidate_ai_config(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
-    cache.del(&ai_config_key(cache, guild_id)).await
+/// A cached LLM reply; `prompt_digest` identifies the normalized prompt.
+pub fn llm_reply_key(
+    cache: &CacheService,
+    guild_id: u64,
+    channel_id: u64,
+    prompt_digest: &str,
+) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:channel:{channel_id}:llm_reply:{prompt_digest}"
+    ))
 }
 
-pub async fn invalidate_modlog_config(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
-    cache.del(&modlog_config_key(cache, guild_id)).await
+/// The recap of a channel's trimmed LLM history.
+pub fn llm_recap_key(cache: &CacheService, guild_id: u64, channel_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:channel:{channel_id}:llm_recap"))
 }
 
-pub async fn invalidate_escalation_config(
+pub fn moderator_command_rate_limit_key(
     cache: &CacheService,
     guild_id: u64,
-) -> anyhow::Result<()> {
-    cache.del(&escalation_config_key(cache, guild_id)).await
+    user_id: u64,
+) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:user:{user_id}:ratelimit:moderator_command"
+    ))
+}
+
+pub async fn invalidate_guild_config(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    cache.del(&guild_config_key(cache, guild_id)).await
 }
 
 pub async fn invalidate_word_filter(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
-    cache.del(&word_filter_config_key(cache, guild_id)).await?;
+    invalidate_guild_config(cache, guild_id).await?;
     cache.del(&word_filter_words_key(cache, guild_id)).await
 }
 
+pub async fn invalidate_link_filter(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    invalidate_guild_config(cache, guild_id).await?;
+    cache.del(&link_filter_domains_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_modlog_template(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    cache.del(&modlog_template_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_name_filter_config(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache.del(&name_filter_config_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_antiraid_config(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    cache.del(&antiraid_config_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_raidmode(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    cache.del(&raidmode_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_exemptions(
+    cache: &CacheService,
+    guild_id: u64,
+    scope: &str,
+) -> anyhow::Result<()> {
+    cache.del(&exemptions_key(cache, guild_id, scope)).await
+}
+
+pub async fn invalidate_permission_levels(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache.del(&permission_levels_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_command_aliases(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    cache.del(&command_aliases_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_disabled_commands(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache.del(&disabled_commands_key(cache, guild_id)).await
+}
+
+/// Drop every cached LLM reply for a channel, along with its history recap.
+pub async fn invalidate_llm_replies(
+    cache: &CacheService,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<u64> {
+    cache
+        .del(&llm_recap_key(cache, guild_id, channel_id))
+        .await?;
+    cache
+        .del_prefix(&llm_reply_key(cache, guild_id, channel_id, ""))
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,14 +836,104 @@ mod tests {
         );
     }
 
-    #[test]
-    fn normalize_llm_rate_limit_applies_bounds() {
-        let (window, max_hits) = normalize_llm_rate_limit(Duration::from_secs(0), 0);
-        assert_eq!(window, Duration::from_secs(1));
-        assert_eq!(max_hits, 1);
-
-        let (window, max_hits) = normalize_llm_rate_limit(Duration::from_secs(7200), 5);
-        assert_eq!(window, Duration::from_secs(3600));
-        assert_eq!(max_hits, 5);
+    #[derive(Debug, Default)]
+    struct MapStore(std::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>);
+
+    impl CacheStore for MapStore {
+        fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+            Box::pin(async move { Ok(self.0.lock().unwrap().get(key).cloned()) })
+        }
+
+        fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, _ttl: u64) -> CacheFuture<'a, ()> {
+            Box::pin(async move {
+                self.0.lock().unwrap().insert(key.to_owned(), value);
+                Ok(())
+            })
+        }
+
+        fn del<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+            Box::pin(async move {
+                self.0.lock().unwrap().remove(key);
+                Ok(())
+            })
+        }
+
+        fn increment_with_window<'a>(
+            &'a self,
+            _key: &'a str,
+            _window: u64,
+        ) -> CacheFuture<'a, u64> {
+            Box::pin(async { Ok(7) })
+        }
+
+        fn ping(&self) -> CacheFuture<'_, ()> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_store_backs_the_service() {
+        let mut cache = CacheService::with_store(Arc::new(MapStore::default()), "autumn:test");
+        cache.configure_memory_cache(0, Duration::from_secs(1));
+
+        cache
+            .set_json("k", &42u32, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(cache.get_json::<u32>("k").await.unwrap(), Some(42));
+
+        cache.del("k").await.unwrap();
+        assert_eq!(cache.get_json::<u32>("k").await.unwrap(), None);
+
+        cache
+            .set_many_json(&[("a", 1u32), ("b", 2u32)], Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(
+            cache.get_many_json::<u32>(&["a", "k", "b"]).await.unwrap(),
+            vec![Some(1), None, Some(2)]
+        );
+
+        assert_eq!(
+            cache
+                .increment_with_window("n", Duration::from_secs(1))
+                .await
+                .unwrap(),
+            7
+        );
+        assert_eq!(cache.backend_description(), "Custom");
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_share_one_load() {
+        let cache = CacheService::disabled("autumn:test");
+        let loads = AtomicU64::new(0);
+
+        let load = || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(5u32)
+        };
+        let results = tokio::join!(
+            cache.get_or_load_json("k", Duration::from_secs(60), load),
+            cache.get_or_load_json("k", Duration::from_secs(60), load),
+            cache.get_or_load_json("k", Duration::from_secs(60), load),
+        );
+
+        assert_eq!(results.0.unwrap(), 5);
+        assert_eq!(results.1.unwrap(), 5);
+        assert_eq!(results.2.unwrap(), 5);
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.stats_snapshot().coalesced_load, 2);
+        assert!(cache.inflight.lock().unwrap().is_empty());
+
+        let failing = || async { anyhow::bail!("database unavailable") };
+        assert!(
+            cache
+                .get_or_load_json::<u32, _, _>("e", Duration::from_secs(60), failing)
+                .await
+                .is_err()
+        );
+        assert!(cache.inflight.lock().unwrap().is_empty());
     }
 }
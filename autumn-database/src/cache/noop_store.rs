@@ -1,28 +1,39 @@
+use super::store::{CacheFuture, CacheStore};
+
+/// Stores nothing; every lookup misses and counters never exceed one.
 #[derive(Clone, Debug, Default)]
 pub struct NoopCacheStore;
 
-impl NoopCacheStore {
-    pub async fn get(&self, _key: &str) -> anyhow::Result<Option<Vec<u8>>> {
-        Ok(None)
+impl CacheStore for NoopCacheStore {
+    fn get<'a>(&'a self, _key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    fn set<'a>(&'a self, _key: &'a str, _value: Vec<u8>, _ttl_seconds: u64) -> CacheFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn del<'a>(&'a self, _key: &'a str) -> CacheFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
     }
 
-    pub async fn set(&self, _key: &str, _value: Vec<u8>, _ttl_seconds: u64) -> anyhow::Result<()> {
-        Ok(())
+    fn scan_prefix<'a>(&'a self, _prefix: &'a str) -> CacheFuture<'a, Vec<String>> {
+        Box::pin(async { Ok(Vec::new()) })
     }
 
-    pub async fn del(&self, _key: &str) -> anyhow::Result<()> {
-        Ok(())
+    fn del_many<'a>(&'a self, _keys: &'a [String]) -> CacheFuture<'a, u64> {
+        Box::pin(async { Ok(0) })
     }
 
-    pub async fn increment_with_window(
-        &self,
-        _key: &str,
+    fn increment_with_window<'a>(
+        &'a self,
+        _key: &'a str,
         _window_seconds: u64,
-    ) -> anyhow::Result<u64> {
-        Ok(1)
+    ) -> CacheFuture<'a, u64> {
+        Box::pin(async { Ok(1) })
     }
 
-    pub async fn ping(&self) -> anyhow::Result<()> {
-        Ok(())
+    fn ping(&self) -> CacheFuture<'_, ()> {
+        Box::pin(async { Ok(()) })
     }
 }
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use super::{DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS, DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW};
+
+pub const DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+pub const DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_MAX_HITS: u64 = 20;
+
+/// Named limiters whose limits are configured per deployment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RateLimiter {
+    /// Bot mentions answered by the LLM, per user and channel.
+    LlmMention,
+    /// Moderation commands run by one moderator in a guild.
+    ModeratorCommand,
+}
+
+impl RateLimiter {
+    pub fn default_limit(self) -> RateLimit {
+        match self {
+            Self::LlmMention => RateLimit::new(
+                DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
+                DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
+            ),
+            Self::ModeratorCommand => RateLimit::new(
+                DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_WINDOW,
+                DEFAULT_MODERATOR_COMMAND_RATE_LIMIT_MAX_HITS,
+            ),
+        }
+    }
+}
+
+/// At most `max_hits` within any `window`-long span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub window: Duration,
+    pub max_hits: u64,
+}
+
+impl RateLimit {
+    /// Clamp the window to 1s..=1h and require at least one hit.
+    pub fn new(window: Duration, max_hits: u64) -> Self {
+        Self {
+            window: Duration::from_secs(window.as_secs().clamp(1, 3600)),
+            max_hits: max_hits.max(1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    /// Estimated hits in the trailing window, including this one.
+    pub count: u64,
+    /// Roughly how long until the oldest counted hits stop counting.
+    pub retry_after: Duration,
+}
+
+/// Sliding-window estimate from two fixed-window counters: the previous
+/// window's hits weighted by how much of it still overlaps the trailing
+/// window, plus the current window's hits.
+pub(crate) fn sliding_window_estimate(
+    previous: u64,
+    current: u64,
+    elapsed_ms: u64,
+    window_ms: u64,
+) -> u64 {
+    let window_ms = window_ms.max(1);
+    let remaining_ms = window_ms.saturating_sub(elapsed_ms.min(window_ms));
+    let weighted = u128::from(previous) * u128::from(remaining_ms) / u128::from(window_ms);
+    u64::try_from(weighted)
+        .unwrap_or(u64::MAX)
+        .saturating_add(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit_applies_bounds() {
+        let limit = RateLimit::new(Duration::from_secs(0), 0);
+        assert_eq!(limit.window, Duration::from_secs(1));
+        assert_eq!(limit.max_hits, 1);
+
+        let limit = RateLimit::new(Duration::from_secs(7200), 5);
+        assert_eq!(limit.window, Duration::from_secs(3600));
+        assert_eq!(limit.max_hits, 5);
+    }
+
+    #[test]
+    fn previous_window_fades_out() {
+        assert_eq!(sliding_window_estimate(10, 1, 0, 1000), 11);
+        assert_eq!(sliding_window_estimate(10, 1, 250, 1000), 8);
+        assert_eq!(sliding_window_estimate(10, 3, 999, 1000), 3);
+        assert_eq!(sliding_window_estimate(10, 3, 5000, 1000), 3);
+    }
+}
@@ -1,109 +1,404 @@
-use deadpool_redis::{Config, Pool, Runtime};
+use deadpool_redis::sentinel::SentinelServerType;
+use deadpool_redis::{Config, Pool, Runtime, cluster, sentinel};
 use redis::AsyncCommands;
 
-#[derive(Clone, Debug)]
+use super::store::{CacheFuture, CacheStore};
+
+/// How the Redis deployment is laid out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RedisTopology {
+    /// One node at `url`.
+    Single { url: String },
+    /// A Redis Cluster reached through any of `urls`; the client follows
+    /// slot moves and topology changes on its own.
+    Cluster { urls: Vec<String> },
+    /// A primary managed by Sentinel. New connections ask the sentinels for
+    /// the current primary, so a failover is picked up once the pool
+    /// replaces its broken connections.
+    Sentinel {
+        urls: Vec<String>,
+        master_name: String,
+    },
+}
+
+impl RedisTopology {
+    /// Build a topology from `REDIS_MODE` (`single`, `cluster`, `sentinel`),
+    /// a comma-separated `REDIS_URL` list and the sentinel master name.
+    pub fn from_parts(
+        mode: Option<&str>,
+        urls: &str,
+        master_name: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let urls: Vec<String> = urls
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_owned)
+            .collect();
+
+        let Some(first_url) = urls.first().cloned() else {
+            anyhow::bail!("no redis url configured");
+        };
+
+        match mode
+            .map(str::trim)
+            .filter(|mode| !mode.is_empty())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            None | Some("single") => {
+                if urls.len() > 1 {
+                    anyhow::bail!("single-node redis takes one url; set REDIS_MODE for more");
+                }
+                Ok(Self::Single { url: first_url })
+            }
+            Some("cluster") => Ok(Self::Cluster { urls }),
+            Some("sentinel") => Ok(Self::Sentinel {
+                urls,
+                master_name: master_name
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or("mymaster")
+                    .to_owned(),
+            }),
+            Some(other) => anyhow::bail!("unknown redis mode `{other}`"),
+        }
+    }
+
+    pub fn mode(&self) -> &'static str {
+        match self {
+            Self::Single { .. } => "single",
+            Self::Cluster { .. } => "cluster",
+            Self::Sentinel { .. } => "sentinel",
+        }
+    }
+}
+
+/// Keys requested per SCAN step and deleted per DEL.
+const SCAN_BATCH: usize = 500;
+
+/// Escape glob metacharacters so a key prefix matches literally in `SCAN MATCH`.
+fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if matches!(character, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+#[derive(Clone)]
+enum RedisPool {
+    Single(Pool),
+    Cluster(cluster::Pool),
+    Sentinel(sentinel::Pool),
+}
+
+#[derive(Clone)]
 pub struct RedisCacheStore {
-    pool: Pool,
+    pool: RedisPool,
+    mode: &'static str,
+}
+
+impl std::fmt::Debug for RedisCacheStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisCacheStore")
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Run `$body` with `$conn` bound to a pooled connection of whichever
+/// topology is configured.
+macro_rules! with_connection {
+    ($store:expr, |$conn:ident| $body:expr) => {
+        match &$store.pool {
+            RedisPool::Single(pool) => {
+                let mut $conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+                $body
+            }
+            RedisPool::Cluster(pool) => {
+                let mut $conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+                $body
+            }
+            RedisPool::Sentinel(pool) => {
+                let mut $conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+                $body
+            }
+        }
+    };
 }
 
 impl RedisCacheStore {
-    pub fn from_url(redis_url: &str) -> anyhow::Result<Self> {
-        let config = Config::from_url(redis_url);
-        let pool = config
-            .create_pool(Some(Runtime::Tokio1))
-            .map_err(|e| anyhow::anyhow!("failed to create redis pool: {e}"))?;
+    pub fn from_topology(topology: &RedisTopology) -> anyhow::Result<Self> {
+        let pool = match topology {
+            RedisTopology::Single { url } => RedisPool::Single(
+                Config::from_url(url.as_str())
+                    .create_pool(Some(Runtime::Tokio1))
+                    .map_err(|e| anyhow::anyhow!("failed to create redis pool: {e}"))?,
+            ),
+            RedisTopology::Cluster { urls } => RedisPool::Cluster(
+                cluster::Config::from_urls(urls.clone())
+                    .create_pool(Some(Runtime::Tokio1))
+                    .map_err(|e| anyhow::anyhow!("failed to create redis cluster pool: {e}"))?,
+            ),
+            RedisTopology::Sentinel { urls, master_name } => RedisPool::Sentinel(
+                sentinel::Config::from_urls(
+                    urls.clone(),
+                    master_name.clone(),
+                    SentinelServerType::Master,
+                )
+                .create_pool(Some(Runtime::Tokio1))
+                .map_err(|e| anyhow::anyhow!("failed to create redis sentinel pool: {e}"))?,
+            ),
+        };
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            mode: topology.mode(),
+        })
     }
 
-    pub async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
-        let mut conn = self
-            .pool
-            .get()
-            .await
-            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+    pub fn mode(&self) -> &'static str {
+        self.mode
+    }
+}
 
-        let value = conn
-            .get::<_, Option<Vec<u8>>>(key)
-            .await
-            .map_err(|e| anyhow::anyhow!("redis GET failed for key `{key}`: {e}"))?;
+impl CacheStore for RedisCacheStore {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            with_connection!(self, |conn| {
+                let value = conn
+                    .get::<_, Option<Vec<u8>>>(key)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("redis GET failed for key `{key}`: {e}"))?;
 
-        Ok(value)
+                Ok(value)
+            })
+        })
     }
 
-    pub async fn set(&self, key: &str, value: Vec<u8>, ttl_seconds: u64) -> anyhow::Result<()> {
-        let mut conn = self
-            .pool
-            .get()
-            .await
-            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+    fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl_seconds: u64) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            with_connection!(self, |conn| {
+                conn.set_ex::<_, _, ()>(key, value, ttl_seconds)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("redis SETEX failed for key `{key}`: {e}"))?;
 
-        conn.set_ex::<_, _, ()>(key, value, ttl_seconds)
-            .await
-            .map_err(|e| anyhow::anyhow!("redis SETEX failed for key `{key}`: {e}"))?;
+                Ok(())
+            })
+        })
+    }
+
+    fn get_many<'a>(&'a self, keys: &'a [&'a str]) -> CacheFuture<'a, Vec<Option<Vec<u8>>>> {
+        Box::pin(async move {
+            if keys.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            with_connection!(self, |conn| {
+                let values = conn
+                    .mget::<_, Vec<Option<Vec<u8>>>>(keys)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("redis MGET failed: {e}"))?;
+
+                Ok(values)
+            })
+        })
+    }
+
+    fn set_many<'a>(
+        &'a self,
+        entries: Vec<(&'a str, Vec<u8>)>,
+        ttl_seconds: u64,
+    ) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            if entries.is_empty() {
+                return Ok(());
+            }
+
+            // Cluster pipelines must stay within one slot, so keys there are
+            // written one at a time.
+            if matches!(self.pool, RedisPool::Cluster(_)) {
+                for (key, value) in entries {
+                    self.set(key, value, ttl_seconds).await?;
+                }
+                return Ok(());
+            }
+
+            let mut pipe = redis::pipe();
+            for (key, value) in entries {
+                pipe.set_ex(key, value, ttl_seconds).ignore();
+            }
+
+            with_connection!(self, |conn| {
+                pipe.query_async::<()>(&mut conn)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("redis SETEX pipeline failed: {e}"))?;
+
+                Ok(())
+            })
+        })
+    }
+
+    fn del<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            with_connection!(self, |conn| {
+                let _ = conn
+                    .del::<_, u64>(key)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("redis DEL failed for key `{key}`: {e}"))?;
+
+                Ok(())
+            })
+        })
+    }
+
+    fn scan_prefix<'a>(&'a self, prefix: &'a str) -> CacheFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            // A cluster spreads keys over nodes, each needing its own SCAN.
+            if matches!(self.pool, RedisPool::Cluster(_)) {
+                anyhow::bail!("prefix scans aren't supported in redis cluster mode");
+            }
 
-        Ok(())
+            let pattern = format!("{}*", escape_glob(prefix));
+            with_connection!(self, |conn| {
+                let mut keys = Vec::new();
+                let mut cursor: u64 = 0;
+                loop {
+                    let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                        .arg(cursor)
+                        .arg("MATCH")
+                        .arg(&pattern)
+                        .arg("COUNT")
+                        .arg(SCAN_BATCH)
+                        .query_async(&mut conn)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("redis SCAN failed for `{pattern}`: {e}"))?;
+                    keys.extend(batch);
+                    if next == 0 {
+                        break;
+                    }
+                    cursor = next;
+                }
+
+                Ok(keys)
+            })
+        })
     }
 
-    pub async fn del(&self, key: &str) -> anyhow::Result<()> {
-        let mut conn = self
-            .pool
-            .get()
-            .await
-            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+    fn del_many<'a>(&'a self, keys: &'a [String]) -> CacheFuture<'a, u64> {
+        Box::pin(async move {
+            if keys.is_empty() {
+                return Ok(0);
+            }
 
-        let _ = conn
-            .del::<_, u64>(key)
-            .await
-            .map_err(|e| anyhow::anyhow!("redis DEL failed for key `{key}`: {e}"))?;
+            with_connection!(self, |conn| {
+                let mut deleted = 0;
+                for chunk in keys.chunks(SCAN_BATCH) {
+                    deleted += conn
+                        .del::<_, u64>(chunk)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("redis DEL failed: {e}"))?;
+                }
 
-        Ok(())
+                Ok(deleted)
+            })
+        })
     }
 
-    pub async fn increment_with_window(
-        &self,
-        key: &str,
+    fn increment_with_window<'a>(
+        &'a self,
+        key: &'a str,
         window_seconds: u64,
-    ) -> anyhow::Result<u64> {
-        let mut conn = self
-            .pool
-            .get()
-            .await
-            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
-
-        let count = conn
-            .incr::<_, _, u64>(key, 1)
-            .await
-            .map_err(|e| anyhow::anyhow!("redis INCR failed for key `{key}`: {e}"))?;
-
-        if count == 1 {
-            let _ = conn
-                .expire::<_, bool>(key, i64::try_from(window_seconds).unwrap_or(i64::MAX))
-                .await
-                .map_err(|e| anyhow::anyhow!("redis EXPIRE failed for key `{key}`: {e}"))?;
-        }
+    ) -> CacheFuture<'a, u64> {
+        Box::pin(async move {
+            with_connection!(self, |conn| {
+                let count = conn
+                    .incr::<_, _, u64>(key, 1)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("redis INCR failed for key `{key}`: {e}"))?;
 
-        Ok(count)
+                if count == 1 {
+                    let _ = conn
+                        .expire::<_, bool>(key, i64::try_from(window_seconds).unwrap_or(i64::MAX))
+                        .await
+                        .map_err(|e| anyhow::anyhow!("redis EXPIRE failed for key `{key}`: {e}"))?;
+                }
+
+                Ok(count)
+            })
+        })
     }
 
-    pub async fn ping(&self) -> anyhow::Result<()> {
-        let mut conn = self
-            .pool
-            .get()
-            .await
-            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+    fn ping(&self) -> CacheFuture<'_, ()> {
+        Box::pin(async move {
+            with_connection!(self, |conn| {
+                let response = conn
+                    .ping::<String>()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("redis PING failed: {e}"))?;
 
-        let response = conn
-            .ping::<String>()
-            .await
-            .map_err(|e| anyhow::anyhow!("redis PING failed: {e}"))?;
+                if response != "PONG" {
+                    return Err(anyhow::anyhow!(
+                        "unexpected redis ping response: {response}"
+                    ));
+                }
 
-        if response != "PONG" {
-            return Err(anyhow::anyhow!(
-                "unexpected redis ping response: {response}"
-            ));
-        }
+                Ok(())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RedisTopology, escape_glob};
+
+    #[test]
+    fn parses_topologies() {
+        assert_eq!(
+            RedisTopology::from_parts(None, "redis://a:6379", None).unwrap(),
+            RedisTopology::Single {
+                url: "redis://a:6379".to_owned()
+            }
+        );
+        assert_eq!(
+            RedisTopology::from_parts(Some("Cluster"), "redis://a, redis://b,", None).unwrap(),
+            RedisTopology::Cluster {
+                urls: vec!["redis://a".to_owned(), "redis://b".to_owned()]
+            }
+        );
+        assert_eq!(
+            RedisTopology::from_parts(Some("sentinel"), "redis://s1:26379", None).unwrap(),
+            RedisTopology::Sentinel {
+                urls: vec!["redis://s1:26379".to_owned()],
+                master_name: "mymaster".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn escapes_scan_patterns() {
+        assert_eq!(escape_glob("autumn:prod:guild:1:"), "autumn:prod:guild:1:");
+        assert_eq!(escape_glob("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+    }
 
-        Ok(())
+    #[test]
+    fn rejects_invalid_topologies() {
+        assert!(RedisTopology::from_parts(None, " , ", None).is_err());
+        assert!(RedisTopology::from_parts(None, "redis://a,redis://b", None).is_err());
+        assert!(RedisTopology::from_parts(Some("ring"), "redis://a", None).is_err());
     }
 }
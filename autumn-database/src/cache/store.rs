@@ -0,0 +1,64 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// Key/value storage behind [`CacheService`](super::CacheService).
+///
+/// Redis and a no-op store are built in; other backends (memcached, an
+/// in-memory map, test doubles) only need these operations. Keys arrive
+/// already prefixed and values are opaque serialized bytes.
+pub trait CacheStore: Debug + Send + Sync {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>>;
+    fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl_seconds: u64) -> CacheFuture<'a, ()>;
+    fn del<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()>;
+    /// Fetch several keys, in order. Stores that can batch (e.g. Redis
+    /// `MGET`) should override the one-by-one default.
+    fn get_many<'a>(&'a self, keys: &'a [&'a str]) -> CacheFuture<'a, Vec<Option<Vec<u8>>>> {
+        Box::pin(async move {
+            let mut values = Vec::with_capacity(keys.len());
+            for key in keys {
+                values.push(self.get(key).await?);
+            }
+            Ok(values)
+        })
+    }
+    /// Store several keys with the same TTL.
+    fn set_many<'a>(
+        &'a self,
+        entries: Vec<(&'a str, Vec<u8>)>,
+        ttl_seconds: u64,
+    ) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            for (key, value) in entries {
+                self.set(key, value, ttl_seconds).await?;
+            }
+            Ok(())
+        })
+    }
+    /// Every key starting with `prefix`. Stores that can't enumerate keys
+    /// return an error.
+    fn scan_prefix<'a>(&'a self, prefix: &'a str) -> CacheFuture<'a, Vec<String>> {
+        Box::pin(
+            async move { anyhow::bail!("this cache store can't list keys (prefix `{prefix}`)") },
+        )
+    }
+    /// Delete several keys, returning how many existed.
+    fn del_many<'a>(&'a self, keys: &'a [String]) -> CacheFuture<'a, u64> {
+        Box::pin(async move {
+            for key in keys {
+                self.del(key).await?;
+            }
+            Ok(keys.len() as u64)
+        })
+    }
+    /// Increment a counter, starting its expiry window when it is created.
+    /// Returns the new count.
+    fn increment_with_window<'a>(
+        &'a self,
+        key: &'a str,
+        window_seconds: u64,
+    ) -> CacheFuture<'a, u64>;
+    fn ping(&self) -> CacheFuture<'_, ()>;
+}
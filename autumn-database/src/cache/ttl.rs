@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{CONFIG_CACHE_TTL, INVITE_RESOLVE_CACHE_TTL, SNIPE_CACHE_TTL, WORD_LIST_CACHE_TTL};
+
+/// The kinds of cached values, each of which can have its own TTL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CacheTtlClass {
+    AntiraidConfig,
+    CommandAliases,
+    DisabledCommands,
+    Exemptions,
+    GuildConfig,
+    InviteResolve,
+    LinkFilterDomains,
+    ModlogTemplate,
+    NameFilterConfig,
+    PermissionLevels,
+    Raidmode,
+    Snipe,
+    WordList,
+}
+
+impl CacheTtlClass {
+    pub const ALL: [Self; 13] = [
+        Self::AntiraidConfig,
+        Self::CommandAliases,
+        Self::DisabledCommands,
+        Self::Exemptions,
+        Self::GuildConfig,
+        Self::InviteResolve,
+        Self::LinkFilterDomains,
+        Self::ModlogTemplate,
+        Self::NameFilterConfig,
+        Self::PermissionLevels,
+        Self::Raidmode,
+        Self::Snipe,
+        Self::WordList,
+    ];
+
+    /// Name used in `CACHE_TTL_OVERRIDES`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AntiraidConfig => "antiraid_config",
+            Self::CommandAliases => "command_aliases",
+            Self::DisabledCommands => "disabled_commands",
+            Self::Exemptions => "exemptions",
+            Self::GuildConfig => "guild_config",
+            Self::InviteResolve => "invite_resolve",
+            Self::LinkFilterDomains => "link_filter_domains",
+            Self::ModlogTemplate => "modlog_template",
+            Self::NameFilterConfig => "name_filter_config",
+            Self::PermissionLevels => "permission_levels",
+            Self::Raidmode => "raidmode",
+            Self::Snipe => "snipe",
+            Self::WordList => "word_list",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| class.name() == name)
+    }
+}
+
+/// TTLs for cached values. Classes without an override use the config or
+/// word list TTL, or their own fixed default.
+#[derive(Clone, Debug)]
+pub struct CacheTtls {
+    pub config: Duration,
+    pub word_list: Duration,
+    pub overrides: HashMap<CacheTtlClass, Duration>,
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        Self {
+            config: CONFIG_CACHE_TTL,
+            word_list: WORD_LIST_CACHE_TTL,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl CacheTtls {
+    pub fn get(&self, class: CacheTtlClass) -> Duration {
+        if let Some(ttl) = self.overrides.get(&class) {
+            return *ttl;
+        }
+
+        match class {
+            CacheTtlClass::WordList => self.word_list,
+            CacheTtlClass::InviteResolve => INVITE_RESOLVE_CACHE_TTL,
+            CacheTtlClass::Snipe => SNIPE_CACHE_TTL,
+            _ => self.config,
+        }
+    }
+
+    /// Parse `class=seconds` pairs separated by commas, e.g.
+    /// `guild_config=60,word_list=120`.
+    pub fn parse_overrides(raw: &str) -> anyhow::Result<HashMap<CacheTtlClass, Duration>> {
+        let mut overrides = HashMap::new();
+        for entry in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let Some((name, seconds)) = entry.split_once('=') else {
+                anyhow::bail!("expected `class=seconds`, got `{entry}`");
+            };
+            let class = CacheTtlClass::from_name(name.trim())
+                .ok_or_else(|| anyhow::anyhow!("unknown cache TTL class `{}`", name.trim()))?;
+            let seconds = seconds
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("invalid seconds for `{}`", class.name()))?;
+            // A zero TTL would make Redis reject the write.
+            overrides.insert(class, Duration::from_secs(seconds.max(1)));
+        }
+        Ok(overrides)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_take_precedence_over_defaults() {
+        let mut ttls = CacheTtls {
+            config: Duration::from_secs(100),
+            ..CacheTtls::default()
+        };
+        ttls.overrides = CacheTtls::parse_overrides(" guild_config=60 , word_list=0,").unwrap();
+
+        assert_eq!(
+            ttls.get(CacheTtlClass::GuildConfig),
+            Duration::from_secs(60)
+        );
+        assert_eq!(ttls.get(CacheTtlClass::WordList), Duration::from_secs(1));
+        assert_eq!(ttls.get(CacheTtlClass::Raidmode), Duration::from_secs(100));
+        assert_eq!(ttls.get(CacheTtlClass::Snipe), SNIPE_CACHE_TTL);
+    }
+
+    #[test]
+    fn rejects_malformed_overrides() {
+        assert!(CacheTtls::parse_overrides("guild_config").is_err());
+        assert!(CacheTtls::parse_overrides("nope=5").is_err());
+        assert!(CacheTtls::parse_overrides("raidmode=soon").is_err());
+        assert!(CacheTtls::parse_overrides("").unwrap().is_empty());
+    }
+}
@@ -1,6 +1,10 @@
+use std::future::Future;
+use std::sync::Arc;
+
 use sqlx::{PgPool, migrate::Migrator};
 
 use crate::cache::{CacheService, CacheStatsSnapshot};
+use crate::resilience::{BreakerSnapshot, CircuitBreaker};
 
 /// Compile-time discovered SQLx migrations for the `autumn-database` crate.
 pub static MIGRATOR: Migrator = sqlx::migrate!();
@@ -10,6 +14,7 @@ pub static MIGRATOR: Migrator = sqlx::migrate!();
 pub struct Database {
     pool: PgPool,
     cache: CacheService,
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl Database {
@@ -18,12 +23,17 @@ impl Database {
         Self {
             pool,
             cache: CacheService::disabled("autumn:prod"),
+            breaker: Arc::default(),
         }
     }
 
     /// Create a database handle from an existing pool and cache service.
     pub fn with_cache(pool: PgPool, cache: CacheService) -> Self {
-        Self { pool, cache }
+        Self {
+            pool,
+            cache,
+            breaker: Arc::default(),
+        }
     }
 
     /// Expose the underlying pool for query modules.
@@ -39,4 +49,19 @@ impl Database {
     pub fn cache_stats_snapshot(&self) -> CacheStatsSnapshot {
         self.cache.stats_snapshot()
     }
+
+    pub fn breaker_snapshot(&self) -> BreakerSnapshot {
+        self.breaker.snapshot()
+    }
+
+    /// Run a query from the event hot path with bounded retry behind the
+    /// shared circuit breaker, so a short Postgres outage is ridden out
+    /// instead of dropping the event.
+    pub async fn with_retry<T, F, Fut>(&self, op_name: &'static str, op: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        self.breaker.call(op_name, op).await
+    }
 }
@@ -1,40 +1,155 @@
 use anyhow::Context as _;
 
-use crate::cache::{CONFIG_CACHE_TTL, ai_config_key, invalidate_ai_config};
+use crate::cache::invalidate_guild_config;
 use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
 
-pub async fn get_llm_enabled(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
-    let cache_key = ai_config_key(db.cache(), guild_id);
-    db.cache()
-        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
-            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+/// Longest custom system prompt a guild may set, in characters.
+pub const LLM_SYSTEM_PROMPT_MAX_CHARS: usize = 1500;
+
+/// Language `!translate` uses when the guild hasn't picked one.
+pub const DEFAULT_TRANSLATE_LANGUAGE: &str = "English";
 
-            let enabled: Option<bool> =
-                sqlx::query_scalar("SELECT llm_enabled FROM guild_ai_config WHERE guild_id = $1")
-                    .bind(guild_id_i64)
-                    .fetch_optional(db.pool())
-                    .await?
-                    .flatten();
+/// Longest language name a guild may set, in characters.
+pub const TRANSLATE_LANGUAGE_MAX_CHARS: usize = 40;
+
+/// Whether `language` looks like a language name (`Spanish`, `Brazilian
+/// Portuguese`) rather than arbitrary text to splice into a prompt.
+pub fn is_valid_translate_language(language: &str) -> bool {
+    !language.is_empty()
+        && language.chars().count() <= TRANSLATE_LANGUAGE_MAX_CHARS
+        && language
+            .chars()
+            .all(|c| c.is_alphabetic() || matches!(c, ' ' | '-' | '(' | ')'))
+}
 
-            Ok(enabled.unwrap_or(true))
-        })
-        .await
+pub async fn get_llm_enabled(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    Ok(get_guild_config(db, guild_id).await?.llm_enabled)
 }
 
 pub async fn set_llm_enabled(db: &Database, guild_id: u64, enabled: bool) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO guild_ai_config (guild_id, llm_enabled)
          VALUES ($1, $2)
          ON CONFLICT (guild_id) DO UPDATE SET llm_enabled = EXCLUDED.llm_enabled",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// The guild's custom system prompt, if it set one.
+pub async fn get_llm_system_prompt(db: &Database, guild_id: u64) -> anyhow::Result<Option<String>> {
+    Ok(get_guild_config(db, guild_id).await?.llm_system_prompt)
+}
+
+/// Set the guild's system prompt, or go back to the default with `None`.
+pub async fn set_llm_system_prompt(
+    db: &Database,
+    guild_id: u64,
+    prompt: Option<&str>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_ai_config (guild_id, system_prompt)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET system_prompt = EXCLUDED.system_prompt",
+        guild_id_i64,
+        prompt,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Whether `!warn` offers an LLM-written reason when replying without one.
+pub async fn get_llm_suggest_reasons(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    Ok(get_guild_config(db, guild_id).await?.llm_suggest_reasons)
+}
+
+pub async fn set_llm_suggest_reasons(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_ai_config (guild_id, suggest_reasons)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET suggest_reasons = EXCLUDED.suggest_reasons",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// The language `!translate` targets in this guild.
+pub async fn get_translate_language(db: &Database, guild_id: u64) -> anyhow::Result<String> {
+    Ok(get_guild_config(db, guild_id)
+        .await?
+        .translate_language
+        .unwrap_or_else(|| DEFAULT_TRANSLATE_LANGUAGE.to_owned()))
+}
+
+/// Set the guild's translation language, or go back to the default with `None`.
+pub async fn set_translate_language(
+    db: &Database,
+    guild_id: u64,
+    language: Option<&str>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_ai_config (guild_id, translate_language)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET translate_language = EXCLUDED.translate_language",
+        guild_id_i64,
+        language,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Whether mentions are answered in a new thread.
+pub async fn get_llm_threads(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    Ok(get_guild_config(db, guild_id).await?.llm_threads)
+}
+
+pub async fn set_llm_threads(db: &Database, guild_id: u64, enabled: bool) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_ai_config (guild_id, use_threads)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET use_threads = EXCLUDED.use_threads",
+        guild_id_i64,
+        enabled,
     )
-    .bind(guild_id_i64)
-    .bind(enabled)
     .execute(db.pool())
     .await?;
 
-    invalidate_ai_config(db.cache(), guild_id).await?;
+    invalidate_guild_config(db.cache(), guild_id).await?;
 
     Ok(())
 }
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+use crate::cache::{
+    CacheTtlClass, antiraid_config_key, antiraid_join_rate_key, invalidate_antiraid_config,
+};
+use crate::database::Database;
+use crate::model::antiraid::AntiraidConfig;
+
+// ---------------------------------------------------------------------------
+// Config CRUD
+// ---------------------------------------------------------------------------
+
+pub async fn get_antiraid_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AntiraidConfig>> {
+    let cache_key = antiraid_config_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::AntiraidConfig),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let row = sqlx::query_as!(
+                    AntiraidConfig,
+                    "SELECT guild_id, enabled, join_threshold, join_window_seconds, \
+                 raid_duration_seconds, raise_verification, kick_new_joins, \
+                 raid_active_until, previous_verification_level \
+                 FROM antiraid_config WHERE guild_id = $1",
+                    guild_id_i64,
+                )
+                .fetch_optional(db.pool())
+                .await?;
+
+                Ok(row)
+            },
+        )
+        .await
+}
+
+/// Get the anti-raid config only if it is enabled.
+pub async fn get_antiraid_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AntiraidConfig>> {
+    let row = get_antiraid_config(db, guild_id).await?;
+    Ok(row.filter(|cfg| cfg.enabled))
+}
+
+pub async fn set_antiraid_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antiraid_config (guild_id, enabled) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = $2",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_antiraid_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the join limit: more than `threshold` joins within `window_seconds`
+/// counts as a raid.
+pub async fn set_antiraid_threshold(
+    db: &Database,
+    guild_id: u64,
+    threshold: i32,
+    window_seconds: i64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antiraid_config (guild_id, join_threshold, join_window_seconds) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (guild_id) DO UPDATE SET join_threshold = $2, join_window_seconds = $3",
+        guild_id_i64,
+        threshold,
+        window_seconds,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_antiraid_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set what happens while a raid is active. The modlog alert is always sent.
+pub async fn set_antiraid_response(
+    db: &Database,
+    guild_id: u64,
+    raise_verification: bool,
+    kick_new_joins: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antiraid_config (guild_id, raise_verification, kick_new_joins) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (guild_id) DO UPDATE SET raise_verification = $2, kick_new_joins = $3",
+        guild_id_i64,
+        raise_verification,
+        kick_new_joins,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_antiraid_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set how long the raid state lasts once triggered.
+pub async fn set_antiraid_duration(
+    db: &Database,
+    guild_id: u64,
+    duration_seconds: i64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antiraid_config (guild_id, raid_duration_seconds) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET raid_duration_seconds = $2",
+        guild_id_i64,
+        duration_seconds,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_antiraid_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Raid state
+// ---------------------------------------------------------------------------
+
+/// Put the guild into raid state until `until` (unix seconds). Returns `false`
+/// if a raid was already active at `now`, so concurrent joins only trigger the
+/// response once.
+pub async fn start_raid(
+    db: &Database,
+    guild_id: u64,
+    now: i64,
+    until: i64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "UPDATE antiraid_config SET raid_active_until = $3 \
+         WHERE guild_id = $1 AND (raid_active_until IS NULL OR raid_active_until <= $2)",
+        guild_id_i64,
+        now,
+        until,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_antiraid_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remember the verification level the raid response replaced.
+pub async fn set_previous_verification_level(
+    db: &Database,
+    guild_id: u64,
+    level: i16,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE antiraid_config SET previous_verification_level = $2 WHERE guild_id = $1",
+        guild_id_i64,
+        level,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_antiraid_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Clear the raid state and return the verification level to restore, if the
+/// raid response changed it.
+pub async fn end_raid(db: &Database, guild_id: u64) -> anyhow::Result<Option<i16>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let previous: Option<Option<i16>> = sqlx::query_scalar!(
+        "UPDATE antiraid_config AS cfg \
+         SET raid_active_until = NULL, previous_verification_level = NULL \
+         FROM (SELECT guild_id, previous_verification_level FROM antiraid_config \
+               WHERE guild_id = $1 FOR UPDATE) AS old \
+         WHERE cfg.guild_id = old.guild_id \
+         RETURNING old.previous_verification_level",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    invalidate_antiraid_config(db.cache(), guild_id).await?;
+
+    Ok(previous.flatten())
+}
+
+// ---------------------------------------------------------------------------
+// Counters
+// ---------------------------------------------------------------------------
+
+/// Count a member join towards the guild's join window and return the new count.
+pub async fn record_member_join(
+    db: &Database,
+    guild_id: u64,
+    window_seconds: u64,
+) -> anyhow::Result<u64> {
+    let cache = db.cache();
+    let key = antiraid_join_rate_key(cache, guild_id);
+    cache
+        .increment_with_window(&key, Duration::from_secs(window_seconds))
+        .await
+}
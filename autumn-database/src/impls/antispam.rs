@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+use crate::cache::{antispam_duplicate_key, antispam_rate_key, invalidate_guild_config};
+use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
+use crate::model::antispam::AntispamConfig;
+
+// ---------------------------------------------------------------------------
+// Config CRUD
+// ---------------------------------------------------------------------------
+
+pub async fn get_antispam_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AntispamConfig>> {
+    Ok(get_guild_config(db, guild_id).await?.antispam)
+}
+
+/// Get the anti-spam config only if it is enabled.
+pub async fn get_antispam_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AntispamConfig>> {
+    let row = get_antispam_config(db, guild_id).await?;
+    Ok(row.filter(|cfg| cfg.enabled))
+}
+
+pub async fn set_antispam_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antispam_config (guild_id, enabled) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = $2",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_antispam_action(db: &Database, guild_id: u64, action: &str) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antispam_config (guild_id, action) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET action = $2",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the action taken for repeated messages.
+pub async fn set_antispam_duplicate_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antispam_config (guild_id, duplicate_action) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET duplicate_action = $2",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the message-rate limit: more than `threshold` messages within
+/// `window_seconds` counts as spam.
+pub async fn set_antispam_rate(
+    db: &Database,
+    guild_id: u64,
+    threshold: i32,
+    window_seconds: i64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antispam_config (guild_id, message_threshold, message_window_seconds) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (guild_id) DO UPDATE SET message_threshold = $2, message_window_seconds = $3",
+        guild_id_i64,
+        threshold,
+        window_seconds,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the duplicate limit: more than `threshold` identical messages within
+/// `window_seconds` counts as spam.
+pub async fn set_antispam_duplicates(
+    db: &Database,
+    guild_id: u64,
+    threshold: i32,
+    window_seconds: i64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO antispam_config (guild_id, duplicate_threshold, duplicate_window_seconds) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (guild_id) DO UPDATE SET duplicate_threshold = $2, duplicate_window_seconds = $3",
+        guild_id_i64,
+        threshold,
+        window_seconds,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Counters
+// ---------------------------------------------------------------------------
+
+/// Count a message towards the user's rate window and return the number of
+/// messages in the trailing window.
+pub async fn record_message_rate(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+    window_seconds: u64,
+) -> anyhow::Result<u64> {
+    let cache = db.cache();
+    let key = antispam_rate_key(cache, guild_id, user_id);
+    cache
+        .sliding_window_count(&key, Duration::from_secs(window_seconds))
+        .await
+}
+
+/// Count a message with the given content fingerprint towards the user's
+/// duplicate window and return the number in the trailing window.
+pub async fn record_duplicate_message(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+    fingerprint: &str,
+    window_seconds: u64,
+) -> anyhow::Result<u64> {
+    let cache = db.cache();
+    let key = antispam_duplicate_key(cache, guild_id, user_id, fingerprint);
+    cache
+        .sliding_window_count(&key, Duration::from_secs(window_seconds))
+        .await
+}
@@ -0,0 +1,162 @@
+use anyhow::Context as _;
+
+use crate::cache::invalidate_guild_config;
+use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
+use crate::model::attachment_filter::AttachmentFilterConfig;
+
+pub async fn get_attachment_filter_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AttachmentFilterConfig>> {
+    Ok(get_guild_config(db, guild_id).await?.attachment_filter)
+}
+
+/// Check whether the attachment filter is enabled for a guild and return the config.
+pub async fn get_attachment_filter_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AttachmentFilterConfig>> {
+    let config = get_attachment_filter_config(db, guild_id).await?;
+    Ok(config.filter(|cfg| cfg.enabled))
+}
+
+pub async fn set_attachment_filter_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO attachment_filter_config (guild_id, enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_attachment_filter_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO attachment_filter_config (guild_id, action)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET action = EXCLUDED.action",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Make sure a config row exists so the block lists are loaded with it.
+async fn ensure_attachment_filter_config(db: &Database, guild_id_i64: i64) -> anyhow::Result<()> {
+    sqlx::query!(
+        "INSERT INTO attachment_filter_config (guild_id) VALUES ($1)
+         ON CONFLICT (guild_id) DO NOTHING",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Block a file extension in every channel. Returns `true` if inserted.
+pub async fn add_blocked_extension(
+    db: &Database,
+    guild_id: u64,
+    extension: &str,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    ensure_attachment_filter_config(db, guild_id_i64).await?;
+
+    let result = sqlx::query!(
+        "INSERT INTO attachment_filter_extensions (guild_id, extension)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id, extension) DO NOTHING",
+        guild_id_i64,
+        extension,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Unblock a file extension. Returns `true` if removed.
+pub async fn remove_blocked_extension(
+    db: &Database,
+    guild_id: u64,
+    extension: &str,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM attachment_filter_extensions WHERE guild_id = $1 AND extension = $2",
+        guild_id_i64,
+        extension,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Block or allow all attachments in a channel. Returns `true` if changed.
+pub async fn set_attachment_channel_blocked(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    blocked: bool,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    let result = if blocked {
+        ensure_attachment_filter_config(db, guild_id_i64).await?;
+
+        sqlx::query!(
+            "INSERT INTO attachment_filter_channels (guild_id, channel_id)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id, channel_id) DO NOTHING",
+            guild_id_i64,
+            channel_id_i64,
+        )
+        .execute(db.pool())
+        .await?
+    } else {
+        sqlx::query!(
+            "DELETE FROM attachment_filter_channels WHERE guild_id = $1 AND channel_id = $2",
+            guild_id_i64,
+            channel_id_i64,
+        )
+        .execute(db.pool())
+        .await?
+    };
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,784 @@
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+
+use crate::cache::guild_key_prefix;
+use crate::database::Database;
+use crate::impls::antiraid::get_antiraid_config;
+use crate::impls::guild_config::get_guild_config;
+use crate::impls::modlog_template::get_modlog_template;
+use crate::impls::name_filter::get_name_filter_config;
+use crate::impls::serverlog_config::get_serverlog_channel_id;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::backup::{
+    BackupCase, BackupCaseEvent, BackupExemption, BackupLinkDomain, BackupNote, BackupWarning,
+    BackupWord, GUILD_BACKUP_VERSION, GuildBackup,
+};
+
+/// Rows written by one [`restore_guild_backup`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RestoreReport {
+    pub words: u64,
+    pub link_domains: u64,
+    pub exemptions: u64,
+    pub cases: u64,
+    pub warnings: u64,
+    pub notes: u64,
+}
+
+/// Export a guild's configuration, word and domain lists, exemptions, cases,
+/// warnings and notes. Deleted notes, modlog webhook credentials, live raid
+/// state and permission levels are left out; restoring levels could hand a
+/// role moderation powers the restoring moderator doesn't have.
+pub async fn export_guild_backup(db: &Database, guild_id: u64) -> anyhow::Result<GuildBackup> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let config = get_guild_config(db, guild_id).await?;
+    let serverlog_channel_id = get_serverlog_channel_id(db, guild_id).await?;
+    let antiraid = get_antiraid_config(db, guild_id).await?;
+    let name_filter = get_name_filter_config(db, guild_id).await?;
+    let modlog_template = get_modlog_template(db, guild_id).await?;
+
+    let words = sqlx::query_as!(
+        BackupWord,
+        "SELECT word, is_preset, match_mode, created_at
+         FROM word_filter_words
+         WHERE guild_id = $1
+         ORDER BY word",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    let link_domains = sqlx::query_as!(
+        BackupLinkDomain,
+        "SELECT domain, list_kind, created_at
+         FROM link_filter_domains
+         WHERE guild_id = $1
+         ORDER BY domain",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    let exemptions = sqlx::query_as!(
+        BackupExemption,
+        "SELECT scope, target_kind, target_id, created_by, created_at
+         FROM moderation_exemptions
+         WHERE guild_id = $1
+         ORDER BY id",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    let cases = sqlx::query_as!(
+        BackupCase,
+        "SELECT case_number, case_code, action_case_number, target_user_id, moderator_user_id,
+                action, reason, status, duration_seconds, created_at, updated_at, anonymized_at
+         FROM mod_cases
+         WHERE guild_id = $1
+         ORDER BY case_number",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    let case_events = sqlx::query_as!(
+        BackupCaseEvent,
+        "SELECT c.case_number, e.event_type, e.actor_user_id, e.old_reason, e.new_reason,
+                e.note, e.created_at
+         FROM mod_case_events e
+         JOIN mod_cases c ON c.id = e.case_id
+         WHERE c.guild_id = $1
+         ORDER BY e.id",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    let warnings = sqlx::query_as!(
+        BackupWarning,
+        "SELECT user_id, moderator_id, reason, warned_at
+         FROM warnings
+         WHERE guild_id = $1 AND decayed_at IS NULL
+         ORDER BY id",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    let notes = sqlx::query_as!(
+        BackupNote,
+        "SELECT target_user_id, author_user_id, content, created_at, updated_at
+         FROM user_notes
+         WHERE guild_id = $1 AND deleted_at IS NULL
+         ORDER BY id",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(GuildBackup {
+        version: GUILD_BACKUP_VERSION,
+        source_guild_id: guild_id,
+        created_at: now_unix_secs(),
+        config,
+        serverlog_channel_id,
+        antiraid,
+        name_filter,
+        modlog_template,
+        words,
+        link_domains,
+        exemptions,
+        cases,
+        case_events,
+        warnings,
+        notes,
+    })
+}
+
+/// Whether the guild already has cases, warnings or notes. Restores are only
+/// allowed into guilds without any, so case numbers never collide.
+pub async fn guild_has_moderation_history(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    has_moderation_history(db.pool(), guild_id_i64).await
+}
+
+async fn has_moderation_history(
+    executor: impl sqlx::PgExecutor<'_>,
+    guild_id_i64: i64,
+) -> anyhow::Result<bool> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT (
+            EXISTS (SELECT 1 FROM mod_cases WHERE guild_id = $1)
+            OR EXISTS (SELECT 1 FROM warnings WHERE guild_id = $1)
+            OR EXISTS (SELECT 1 FROM user_notes WHERE guild_id = $1)
+        ) AS "exists!""#,
+        guild_id_i64,
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(exists)
+}
+
+/// Import a backup into `guild_id` in one transaction. Configuration present
+/// in the backup overwrites the guild's current settings; lists and history
+/// are added. Fails if the guild already has moderation history.
+pub async fn restore_guild_backup(
+    db: &Database,
+    guild_id: u64,
+    backup: &GuildBackup,
+) -> anyhow::Result<RestoreReport> {
+    if backup.version != GUILD_BACKUP_VERSION {
+        anyhow::bail!("unsupported backup version {}", backup.version);
+    }
+
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let config = &backup.config;
+    let mut report = RestoreReport::default();
+
+    let mut tx = db.pool().begin().await?;
+
+    if has_moderation_history(&mut *tx, guild_id_i64).await? {
+        anyhow::bail!("guild {guild_id} already has moderation history");
+    }
+
+    if config.modlog_channel_id.is_some() || config.purge_transcripts {
+        let modlog_channel_id = config
+            .modlog_channel_id
+            .map(i64::try_from)
+            .transpose()
+            .context("modlog_channel_id out of i64 range")?;
+        sqlx::query!(
+            "INSERT INTO guild_mod_config (guild_id, modlog_channel_id, purge_transcripts)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET modlog_channel_id = EXCLUDED.modlog_channel_id,
+                           purge_transcripts = EXCLUDED.purge_transcripts",
+            guild_id_i64,
+            modlog_channel_id,
+            config.purge_transcripts,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(channel_id) = config.userlog_channel_id {
+        let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+        sqlx::query!(
+            "INSERT INTO guild_userlog_config (guild_id, userlog_channel_id)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id) DO UPDATE SET userlog_channel_id = EXCLUDED.userlog_channel_id",
+            guild_id_i64,
+            channel_id_i64,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(channel_id) = backup.serverlog_channel_id {
+        let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+        sqlx::query!(
+            "INSERT INTO guild_serverlog_config (guild_id, serverlog_channel_id)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id) DO UPDATE SET serverlog_channel_id = EXCLUDED.serverlog_channel_id",
+            guild_id_i64,
+            channel_id_i64,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !config.llm_enabled {
+        sqlx::query!(
+            "INSERT INTO guild_ai_config (guild_id, llm_enabled)
+             VALUES ($1, FALSE)
+             ON CONFLICT (guild_id) DO UPDATE SET llm_enabled = FALSE",
+            guild_id_i64,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(system_prompt) = &config.llm_system_prompt {
+        sqlx::query!(
+            "INSERT INTO guild_ai_config (guild_id, system_prompt)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id) DO UPDATE SET system_prompt = EXCLUDED.system_prompt",
+            guild_id_i64,
+            system_prompt,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(translate_language) = &config.translate_language {
+        sqlx::query!(
+            "INSERT INTO guild_ai_config (guild_id, translate_language)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id) DO UPDATE SET translate_language = EXCLUDED.translate_language",
+            guild_id_i64,
+            translate_language,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if config.llm_threads {
+        sqlx::query!(
+            "INSERT INTO guild_ai_config (guild_id, use_threads)
+             VALUES ($1, TRUE)
+             ON CONFLICT (guild_id) DO UPDATE SET use_threads = TRUE",
+            guild_id_i64,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if config.llm_suggest_reasons {
+        sqlx::query!(
+            "INSERT INTO guild_ai_config (guild_id, suggest_reasons)
+             VALUES ($1, TRUE)
+             ON CONFLICT (guild_id) DO UPDATE SET suggest_reasons = TRUE",
+            guild_id_i64,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if config.enforce_mod_hierarchy {
+        sqlx::query!(
+            "INSERT INTO guild_moderation_settings (guild_id, enforce_hierarchy)
+             VALUES ($1, TRUE)
+             ON CONFLICT (guild_id) DO UPDATE SET enforce_hierarchy = TRUE",
+            guild_id_i64,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(color) = config.embed_color {
+        let color_i32 = i32::try_from(color).context("embed_color out of i32 range")?;
+        sqlx::query!(
+            "INSERT INTO guild_moderation_settings (guild_id, embed_color)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id) DO UPDATE SET embed_color = EXCLUDED.embed_color",
+            guild_id_i64,
+            color_i32,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(escalation) = &config.escalation {
+        sqlx::query!(
+            "INSERT INTO escalation_config
+                (guild_id, enabled, warn_threshold, warn_window_seconds, timeout_window_seconds)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled,
+                           warn_threshold = EXCLUDED.warn_threshold,
+                           warn_window_seconds = EXCLUDED.warn_window_seconds,
+                           timeout_window_seconds = EXCLUDED.timeout_window_seconds",
+            guild_id_i64,
+            escalation.enabled,
+            escalation.warn_threshold,
+            escalation.warn_window_seconds,
+            escalation.timeout_window_seconds,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(antispam) = &config.antispam {
+        sqlx::query!(
+            "INSERT INTO antispam_config
+                (guild_id, enabled, message_threshold, message_window_seconds,
+                 duplicate_threshold, duplicate_window_seconds, action, duplicate_action)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled,
+                           message_threshold = EXCLUDED.message_threshold,
+                           message_window_seconds = EXCLUDED.message_window_seconds,
+                           duplicate_threshold = EXCLUDED.duplicate_threshold,
+                           duplicate_window_seconds = EXCLUDED.duplicate_window_seconds,
+                           action = EXCLUDED.action,
+                           duplicate_action = EXCLUDED.duplicate_action",
+            guild_id_i64,
+            antispam.enabled,
+            antispam.message_threshold,
+            antispam.message_window_seconds,
+            antispam.duplicate_threshold,
+            antispam.duplicate_window_seconds,
+            antispam.action,
+            antispam.duplicate_action,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(antiraid) = &backup.antiraid {
+        // Raid state belongs to the source guild and is not carried over.
+        sqlx::query!(
+            "INSERT INTO antiraid_config
+                (guild_id, enabled, join_threshold, join_window_seconds,
+                 raid_duration_seconds, raise_verification, kick_new_joins)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled,
+                           join_threshold = EXCLUDED.join_threshold,
+                           join_window_seconds = EXCLUDED.join_window_seconds,
+                           raid_duration_seconds = EXCLUDED.raid_duration_seconds,
+                           raise_verification = EXCLUDED.raise_verification,
+                           kick_new_joins = EXCLUDED.kick_new_joins",
+            guild_id_i64,
+            antiraid.enabled,
+            antiraid.join_threshold,
+            antiraid.join_window_seconds,
+            antiraid.raid_duration_seconds,
+            antiraid.raise_verification,
+            antiraid.kick_new_joins,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(word_filter) = &config.word_filter {
+        sqlx::query!(
+            "INSERT INTO word_filter_config
+                (guild_id, enabled, action, ai_check_enabled, ai_threshold, ai_action)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled, action = EXCLUDED.action,
+                ai_check_enabled = EXCLUDED.ai_check_enabled,
+                ai_threshold = EXCLUDED.ai_threshold,
+                ai_action = EXCLUDED.ai_action",
+            guild_id_i64,
+            word_filter.enabled,
+            word_filter.action,
+            word_filter.ai_check,
+            i16::from(word_filter.ai_threshold.clamp(1, 100)),
+            word_filter.ai_action,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !config.word_filter_channels.is_empty() {
+        let channel_ids = config
+            .word_filter_channels
+            .iter()
+            .map(|o| i64::try_from(o.channel_id).context("channel_id out of i64 range"))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let enabled: Vec<bool> = config
+            .word_filter_channels
+            .iter()
+            .map(|o| o.enabled)
+            .collect();
+        sqlx::query!(
+            "INSERT INTO word_filter_channels (guild_id, channel_id, enabled)
+             SELECT $1, channel_id, enabled
+             FROM UNNEST($2::BIGINT[], $3::BOOLEAN[]) AS input(channel_id, enabled)
+             ON CONFLICT (guild_id, channel_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+            guild_id_i64,
+            &channel_ids,
+            &enabled,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !backup.words.is_empty() {
+        let words: Vec<&str> = backup.words.iter().map(|w| w.word.as_str()).collect();
+        let presets: Vec<bool> = backup.words.iter().map(|w| w.is_preset).collect();
+        let modes: Vec<&str> = backup.words.iter().map(|w| w.match_mode.as_str()).collect();
+        let created: Vec<i64> = backup.words.iter().map(|w| w.created_at).collect();
+        report.words = sqlx::query!(
+            "INSERT INTO word_filter_words (guild_id, word, is_preset, match_mode, created_at)
+             SELECT $1, word, is_preset, match_mode, created_at
+             FROM UNNEST($2::TEXT[], $3::BOOLEAN[], $4::TEXT[], $5::BIGINT[])
+                 AS input(word, is_preset, match_mode, created_at)
+             ON CONFLICT (guild_id, word) DO NOTHING",
+            guild_id_i64,
+            &words as &[&str],
+            &presets,
+            &modes as &[&str],
+            &created,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    if let Some(invite_filter) = &config.invite_filter {
+        sqlx::query!(
+            "INSERT INTO invite_filter_config (guild_id, enabled, action)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled, action = EXCLUDED.action",
+            guild_id_i64,
+            invite_filter.enabled,
+            invite_filter.action,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let allowed = invite_filter
+            .allowed_guild_ids
+            .iter()
+            .map(|id| i64::try_from(*id).context("allowed_guild_id out of i64 range"))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        sqlx::query!(
+            "INSERT INTO invite_filter_allowed_guilds (guild_id, allowed_guild_id)
+             SELECT $1, allowed_guild_id FROM UNNEST($2::BIGINT[]) AS input(allowed_guild_id)
+             ON CONFLICT (guild_id, allowed_guild_id) DO NOTHING",
+            guild_id_i64,
+            &allowed,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(attachment_filter) = &config.attachment_filter {
+        sqlx::query!(
+            "INSERT INTO attachment_filter_config (guild_id, enabled, action)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled, action = EXCLUDED.action",
+            guild_id_i64,
+            attachment_filter.enabled,
+            attachment_filter.action,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO attachment_filter_extensions (guild_id, extension)
+             SELECT $1, extension FROM UNNEST($2::TEXT[]) AS input(extension)
+             ON CONFLICT (guild_id, extension) DO NOTHING",
+            guild_id_i64,
+            &attachment_filter.blocked_extensions,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let channel_ids = attachment_filter
+            .blocked_channel_ids
+            .iter()
+            .map(|id| i64::try_from(*id).context("channel_id out of i64 range"))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        sqlx::query!(
+            "INSERT INTO attachment_filter_channels (guild_id, channel_id)
+             SELECT $1, channel_id FROM UNNEST($2::BIGINT[]) AS input(channel_id)
+             ON CONFLICT (guild_id, channel_id) DO NOTHING",
+            guild_id_i64,
+            &channel_ids,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(link_filter) = &config.link_filter {
+        sqlx::query!(
+            "INSERT INTO link_filter_config (guild_id, enabled, mode, action)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled, mode = EXCLUDED.mode,
+                           action = EXCLUDED.action",
+            guild_id_i64,
+            link_filter.enabled,
+            link_filter.mode,
+            link_filter.action,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !backup.link_domains.is_empty() {
+        let domains: Vec<&str> = backup
+            .link_domains
+            .iter()
+            .map(|d| d.domain.as_str())
+            .collect();
+        let kinds: Vec<&str> = backup
+            .link_domains
+            .iter()
+            .map(|d| d.list_kind.as_str())
+            .collect();
+        let created: Vec<i64> = backup.link_domains.iter().map(|d| d.created_at).collect();
+        report.link_domains = sqlx::query!(
+            "INSERT INTO link_filter_domains (guild_id, domain, list_kind, created_at)
+             SELECT $1, domain, list_kind, created_at
+             FROM UNNEST($2::TEXT[], $3::TEXT[], $4::BIGINT[])
+                 AS input(domain, list_kind, created_at)
+             ON CONFLICT (guild_id, domain) DO NOTHING",
+            guild_id_i64,
+            &domains as &[&str],
+            &kinds as &[&str],
+            &created,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    if let Some(caps_filter) = &config.caps_filter {
+        sqlx::query!(
+            "INSERT INTO caps_filter_config
+                (guild_id, enabled, min_length, max_caps_percent, action)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled,
+                           min_length = EXCLUDED.min_length,
+                           max_caps_percent = EXCLUDED.max_caps_percent,
+                           action = EXCLUDED.action",
+            guild_id_i64,
+            caps_filter.enabled,
+            caps_filter.min_length,
+            caps_filter.max_caps_percent,
+            caps_filter.action,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(image_filter) = &config.image_filter {
+        sqlx::query!(
+            "INSERT INTO image_filter_config (guild_id, enabled, threshold, action)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled, threshold = EXCLUDED.threshold,
+                           action = EXCLUDED.action",
+            guild_id_i64,
+            image_filter.enabled,
+            i16::from(image_filter.threshold),
+            image_filter.action,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(name_filter) = &backup.name_filter {
+        sqlx::query!(
+            "INSERT INTO name_filter_config (guild_id, enabled, action, placeholder)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET enabled = EXCLUDED.enabled, action = EXCLUDED.action,
+                           placeholder = EXCLUDED.placeholder",
+            guild_id_i64,
+            name_filter.enabled,
+            name_filter.action,
+            name_filter.placeholder,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(template) = &backup.modlog_template {
+        sqlx::query!(
+            "INSERT INTO modlog_templates (guild_id, field_order, color, footer)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (guild_id)
+             DO UPDATE SET field_order = EXCLUDED.field_order, color = EXCLUDED.color,
+                           footer = EXCLUDED.footer",
+            guild_id_i64,
+            template.field_order,
+            template.color,
+            template.footer,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !backup.exemptions.is_empty() {
+        let scopes: Vec<&str> = backup.exemptions.iter().map(|e| e.scope.as_str()).collect();
+        let kinds: Vec<&str> = backup
+            .exemptions
+            .iter()
+            .map(|e| e.target_kind.as_str())
+            .collect();
+        let target_ids: Vec<i64> = backup.exemptions.iter().map(|e| e.target_id).collect();
+        let created_by: Vec<i64> = backup.exemptions.iter().map(|e| e.created_by).collect();
+        let created: Vec<i64> = backup.exemptions.iter().map(|e| e.created_at).collect();
+        report.exemptions = sqlx::query!(
+            "INSERT INTO moderation_exemptions
+                (guild_id, scope, target_kind, target_id, created_by, created_at)
+             SELECT $1, scope, target_kind, target_id, created_by, created_at
+             FROM UNNEST($2::TEXT[], $3::TEXT[], $4::BIGINT[], $5::BIGINT[], $6::BIGINT[])
+                 AS input(scope, target_kind, target_id, created_by, created_at)
+             ON CONFLICT (guild_id, scope, target_kind, target_id) DO NOTHING",
+            guild_id_i64,
+            &scopes as &[&str],
+            &kinds as &[&str],
+            &target_ids,
+            &created_by,
+            &created,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    let mut case_ids = HashMap::with_capacity(backup.cases.len());
+    for case in &backup.cases {
+        let case_id = sqlx::query_scalar!(
+            "INSERT INTO mod_cases
+                (guild_id, case_number, case_code, action_case_number, target_user_id,
+                 moderator_user_id, action, reason, status, duration_seconds,
+                 created_at, updated_at, anonymized_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             RETURNING id",
+            guild_id_i64,
+            case.case_number,
+            case.case_code,
+            case.action_case_number,
+            case.target_user_id,
+            case.moderator_user_id,
+            case.action,
+            case.reason,
+            case.status,
+            case.duration_seconds,
+            case.created_at,
+            case.updated_at,
+            case.anonymized_at,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        case_ids.insert(case.case_number, case_id);
+        report.cases += 1;
+    }
+
+    for event in &backup.case_events {
+        let Some(case_id) = case_ids.get(&event.case_number) else {
+            continue;
+        };
+        sqlx::query!(
+            "INSERT INTO mod_case_events
+                (case_id, guild_id, event_type, actor_user_id, old_reason, new_reason,
+                 note, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            case_id,
+            guild_id_i64,
+            event.event_type,
+            event.actor_user_id,
+            event.old_reason,
+            event.new_reason,
+            event.note,
+            event.created_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // New cases continue numbering after the restored ones.
+    sqlx::query!(
+        "INSERT INTO guild_case_counters (guild_id, scope, last_number)
+         SELECT guild_id, '', MAX(case_number) FROM mod_cases
+         WHERE guild_id = $1 GROUP BY guild_id
+         UNION ALL
+         SELECT guild_id, case_code, MAX(action_case_number) FROM mod_cases
+         WHERE guild_id = $1 GROUP BY guild_id, case_code
+         ON CONFLICT (guild_id, scope)
+         DO UPDATE SET last_number = GREATEST(guild_case_counters.last_number, EXCLUDED.last_number)",
+        guild_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if !backup.warnings.is_empty() {
+        let user_ids: Vec<i64> = backup.warnings.iter().map(|w| w.user_id).collect();
+        let moderator_ids: Vec<i64> = backup.warnings.iter().map(|w| w.moderator_id).collect();
+        let reasons: Vec<&str> = backup.warnings.iter().map(|w| w.reason.as_str()).collect();
+        let warned_at: Vec<i64> = backup.warnings.iter().map(|w| w.warned_at).collect();
+        report.warnings = sqlx::query!(
+            "INSERT INTO warnings (guild_id, user_id, moderator_id, reason, warned_at)
+             SELECT $1, user_id, moderator_id, reason, warned_at
+             FROM UNNEST($2::BIGINT[], $3::BIGINT[], $4::TEXT[], $5::BIGINT[])
+                 AS input(user_id, moderator_id, reason, warned_at)",
+            guild_id_i64,
+            &user_ids,
+            &moderator_ids,
+            &reasons as &[&str],
+            &warned_at,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    if !backup.notes.is_empty() {
+        let target_ids: Vec<i64> = backup.notes.iter().map(|n| n.target_user_id).collect();
+        let author_ids: Vec<i64> = backup.notes.iter().map(|n| n.author_user_id).collect();
+        let contents: Vec<&str> = backup.notes.iter().map(|n| n.content.as_str()).collect();
+        let created: Vec<i64> = backup.notes.iter().map(|n| n.created_at).collect();
+        let updated: Vec<i64> = backup.notes.iter().map(|n| n.updated_at).collect();
+        report.notes = sqlx::query!(
+            "INSERT INTO user_notes
+                (guild_id, target_user_id, author_user_id, content, created_at, updated_at)
+             SELECT $1, target_user_id, author_user_id, content, created_at, updated_at
+             FROM UNNEST($2::BIGINT[], $3::BIGINT[], $4::TEXT[], $5::BIGINT[], $6::BIGINT[])
+                 AS input(target_user_id, author_user_id, content, created_at, updated_at)",
+            guild_id_i64,
+            &target_ids,
+            &author_ids,
+            &contents as &[&str],
+            &created,
+            &updated,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    tx.commit().await?;
+
+    db.cache()
+        .del_prefix(&guild_key_prefix(db.cache(), guild_id))
+        .await?;
+
+    Ok(report)
+}
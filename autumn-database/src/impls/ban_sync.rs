@@ -0,0 +1,351 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+pub const BAN_SYNC_NAME_MAX_CHARS: usize = 50;
+pub const BAN_SYNC_TAG_MAX_CHARS: usize = 32;
+/// Guilds one group can hold; every tagged ban is mirrored to each of them.
+pub const MAX_BAN_SYNC_MEMBERS: u64 = 10;
+
+#[derive(Clone, Debug)]
+pub struct BanSyncGroup {
+    pub id: i64,
+    pub name: String,
+    pub tag: String,
+    pub invite_code: String,
+    pub created_by: u64,
+    pub created_at: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct BanSyncMirror {
+    pub id: i64,
+    pub source_guild_id: u64,
+    pub target_guild_id: u64,
+    pub user_id: u64,
+    pub created_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct BanSyncGroupRow {
+    id: i64,
+    name: String,
+    tag: String,
+    invite_code: String,
+    created_by: i64,
+    created_at: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct BanSyncMirrorRow {
+    id: i64,
+    source_guild_id: i64,
+    target_guild_id: i64,
+    user_id: i64,
+    created_at: i64,
+}
+
+/// Create a group with `guild_id` as its first member. Returns `None` when the
+/// guild already belongs to a group.
+pub async fn create_ban_sync_group(
+    db: &Database,
+    guild_id: u64,
+    name: &str,
+    tag: &str,
+    invite_code: &str,
+    created_by: u64,
+) -> anyhow::Result<Option<BanSyncGroup>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let created_by = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let row: BanSyncGroupRow = sqlx::query_as!(
+        BanSyncGroupRow,
+        "INSERT INTO ban_sync_groups (name, tag, invite_code, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING id, name, tag, invite_code, created_by, created_at",
+        name,
+        tag,
+        invite_code,
+        created_by,
+        now,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let joined = sqlx::query!(
+        "INSERT INTO ban_sync_members (guild_id, group_id, joined_at)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id) DO NOTHING",
+        guild_id,
+        row.id,
+        now,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if joined == 0 {
+        tx.rollback().await?;
+        return Ok(None);
+    }
+
+    tx.commit().await?;
+
+    to_group(row).map(Some)
+}
+
+pub async fn get_ban_sync_group_by_code(
+    db: &Database,
+    invite_code: &str,
+) -> anyhow::Result<Option<BanSyncGroup>> {
+    let row: Option<BanSyncGroupRow> = sqlx::query_as!(
+        BanSyncGroupRow,
+        "SELECT id, name, tag, invite_code, created_by, created_at
+         FROM ban_sync_groups
+         WHERE invite_code = $1",
+        invite_code,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_group).transpose()
+}
+
+pub async fn get_ban_sync_group_for_guild(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<BanSyncGroup>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let row: Option<BanSyncGroupRow> = sqlx::query_as!(
+        BanSyncGroupRow,
+        "SELECT g.id, g.name, g.tag, g.invite_code, g.created_by, g.created_at
+         FROM ban_sync_groups g
+         JOIN ban_sync_members m ON m.group_id = g.id
+         WHERE m.guild_id = $1",
+        guild_id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_group).transpose()
+}
+
+/// Guilds in the group, earliest to join first.
+pub async fn list_ban_sync_members(db: &Database, group_id: i64) -> anyhow::Result<Vec<u64>> {
+    let guild_ids = sqlx::query_scalar!(
+        "SELECT guild_id FROM ban_sync_members WHERE group_id = $1 ORDER BY joined_at, guild_id",
+        group_id,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    guild_ids
+        .into_iter()
+        .map(|guild_id| u64::try_from(guild_id).context("negative guild_id"))
+        .collect()
+}
+
+/// Add a guild to the group holding `invite_code`, replacing the code with
+/// `next_invite_code` so each code admits a single guild. Returns `false` when
+/// the guild already belongs to a group or the code has just been used.
+pub async fn join_ban_sync_group(
+    db: &Database,
+    group_id: i64,
+    invite_code: &str,
+    next_invite_code: &str,
+    guild_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let rotated = sqlx::query!(
+        "UPDATE ban_sync_groups SET invite_code = $1 WHERE id = $2 AND invite_code = $3",
+        next_invite_code,
+        group_id,
+        invite_code,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let joined = sqlx::query!(
+        "INSERT INTO ban_sync_members (guild_id, group_id, joined_at)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id) DO NOTHING",
+        guild_id,
+        group_id,
+        now,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if rotated == 0 || joined == 0 {
+        tx.rollback().await?;
+        return Ok(false);
+    }
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Take a guild out of its group, deleting the group once it's empty.
+/// Returns `false` if the guild wasn't in one. Mirrored bans stay recorded
+/// so they can still be undone.
+pub async fn leave_ban_sync_group(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let group_id = sqlx::query_scalar!(
+        "DELETE FROM ban_sync_members WHERE guild_id = $1 RETURNING group_id",
+        guild_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(group_id) = group_id else {
+        return Ok(false);
+    };
+
+    sqlx::query!(
+        "DELETE FROM ban_sync_groups g
+         WHERE g.id = $1
+           AND NOT EXISTS (SELECT 1 FROM ban_sync_members m WHERE m.group_id = g.id)",
+        group_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+pub async fn record_ban_sync_mirror(
+    db: &Database,
+    source_guild_id: u64,
+    target_guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<()> {
+    let source_guild_id =
+        i64::try_from(source_guild_id).context("source_guild_id out of i64 range")?;
+    let target_guild_id =
+        i64::try_from(target_guild_id).context("target_guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO ban_sync_mirrors (source_guild_id, target_guild_id, user_id, created_at)
+         VALUES ($1, $2, $3, $4)",
+        source_guild_id,
+        target_guild_id,
+        user_id,
+        now,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Mirrored bans of `user_id` still in place that `guild_id` either sent or
+/// received.
+pub async fn list_active_ban_sync_mirrors(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<Vec<BanSyncMirror>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let rows: Vec<BanSyncMirrorRow> = sqlx::query_as!(
+        BanSyncMirrorRow,
+        "SELECT id, source_guild_id, target_guild_id, user_id, created_at
+         FROM ban_sync_mirrors
+         WHERE user_id = $1
+           AND (source_guild_id = $2 OR target_guild_id = $2)
+           AND undone_at IS NULL
+         ORDER BY id",
+        user_id,
+        guild_id,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_mirror).collect()
+}
+
+pub async fn mark_ban_sync_mirror_undone(db: &Database, id: i64) -> anyhow::Result<()> {
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE ban_sync_mirrors SET undone_at = $1 WHERE id = $2 AND undone_at IS NULL",
+        now,
+        id,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Whether a ban reason carries the group's sync tag as a word of its own,
+/// so a tag like `spam` doesn't match "antispam".
+pub fn reason_has_sync_tag(reason: &str, tag: &str) -> bool {
+    let tag = tag.to_lowercase();
+    !tag.is_empty()
+        && reason
+            .split_whitespace()
+            .any(|word| word.to_lowercase() == tag)
+}
+
+fn to_group(row: BanSyncGroupRow) -> anyhow::Result<BanSyncGroup> {
+    Ok(BanSyncGroup {
+        id: row.id,
+        name: row.name,
+        tag: row.tag,
+        invite_code: row.invite_code,
+        created_by: u64::try_from(row.created_by).context("negative created_by")?,
+        created_at: u64::try_from(row.created_at).context("negative created_at")?,
+    })
+}
+
+fn to_mirror(row: BanSyncMirrorRow) -> anyhow::Result<BanSyncMirror> {
+    Ok(BanSyncMirror {
+        id: row.id,
+        source_guild_id: u64::try_from(row.source_guild_id).context("negative source_guild_id")?,
+        target_guild_id: u64::try_from(row.target_guild_id).context("negative target_guild_id")?,
+        user_id: u64::try_from(row.user_id).context("negative user_id")?,
+        created_at: u64::try_from(row.created_at).context("negative created_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reason_has_sync_tag;
+
+    #[test]
+    fn sync_tag_matches_case_insensitively() {
+        assert!(reason_has_sync_tag("raiding #Shared", "#shared"));
+        assert!(reason_has_sync_tag("[SYNC] scam links", "[sync]"));
+        assert!(!reason_has_sync_tag("raiding", "#shared"));
+        assert!(!reason_has_sync_tag("raiding", ""));
+    }
+
+    #[test]
+    fn sync_tag_must_be_a_whole_word() {
+        assert!(reason_has_sync_tag("spam bot", "spam"));
+        assert!(reason_has_sync_tag("bot\tspam", "spam"));
+        assert!(!reason_has_sync_tag("antispam false positive", "spam"));
+        assert!(!reason_has_sync_tag("#shared-list", "#shared"));
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Context as _;
+
+use crate::cache::invalidate_guild_config;
+use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
+use crate::model::caps_filter::CapsFilterConfig;
+
+pub async fn get_caps_filter_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<CapsFilterConfig>> {
+    Ok(get_guild_config(db, guild_id).await?.caps_filter)
+}
+
+/// Get the caps filter config only if it is enabled.
+pub async fn get_caps_filter_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<CapsFilterConfig>> {
+    let row = get_caps_filter_config(db, guild_id).await?;
+    Ok(row.filter(|cfg| cfg.enabled))
+}
+
+pub async fn set_caps_filter_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO caps_filter_config (guild_id, enabled) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = $2",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_caps_filter_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO caps_filter_config (guild_id, action) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET action = $2",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the highest allowed percentage of uppercase letters.
+pub async fn set_caps_filter_percent(
+    db: &Database,
+    guild_id: u64,
+    max_caps_percent: i32,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO caps_filter_config (guild_id, max_caps_percent) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET max_caps_percent = $2",
+        guild_id_i64,
+        max_caps_percent,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the minimum number of letters a message needs before it is checked.
+pub async fn set_caps_filter_min_length(
+    db: &Database,
+    guild_id: u64,
+    min_length: i32,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO caps_filter_config (guild_id, min_length) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET min_length = $2",
+        guild_id_i64,
+        min_length,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
@@ -3,6 +3,7 @@ use anyhow::Context as _;
 use crate::{
     database::Database,
     impls::warnings::now_unix_secs,
+    impls::webhooks::enqueue_case_webhooks,
     model::cases::{CaseEvent, CaseSummary, ModerationCase},
 };
 
@@ -23,6 +24,14 @@ pub struct CaseFilters<'a> {
     pub limit: u32,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct CaseStatusChange<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    /// Recorded in the case history.
+    pub note: &'a str,
+}
+
 #[derive(sqlx::FromRow)]
 struct CaseSummaryRow {
     case_number: i64,
@@ -36,7 +45,7 @@ struct CaseSummaryRow {
     created_at: i64,
 }
 
-#[derive(sqlx::FromRow)]
+#[derive(Clone, sqlx::FromRow)]
 struct ModerationCaseRow {
     id: i64,
     case_number: i64,
@@ -64,6 +73,18 @@ struct CaseEventRow {
 }
 
 pub async fn create_case(db: &Database, new_case: NewCase<'_>) -> anyhow::Result<CaseSummary> {
+    let mut tx = db.pool().begin().await?;
+    let (_, case) = insert_case(&mut tx, new_case).await?;
+    tx.commit().await?;
+
+    Ok(case)
+}
+
+/// Insert a case inside an existing transaction, returning its row id and summary.
+pub(crate) async fn insert_case(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    new_case: NewCase<'_>,
+) -> anyhow::Result<(i64, CaseSummary)> {
     let guild_id_i64 = i64::try_from(new_case.guild_id).context("guild_id out of i64 range")?;
     let target_user_id_i64 = new_case
         .target_user_id
@@ -80,31 +101,32 @@ pub async fn create_case(db: &Database, new_case: NewCase<'_>) -> anyhow::Result
     let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
     let case_code = action_code(new_case.action);
 
-    let mut tx = db.pool().begin().await?;
-
-    sqlx::query("SELECT pg_advisory_xact_lock($1)")
-        .bind(guild_id_i64)
-        .execute(&mut *tx)
-        .await?;
-
-    let next_case_number: i64 = sqlx::query_scalar(
-        "SELECT COALESCE(MAX(case_number), 0) + 1 FROM mod_cases WHERE guild_id = $1",
+    // Bumps the guild-wide and per-action counters in one statement. The
+    // rows stay locked until commit, so numbers are never handed out twice.
+    let counters = sqlx::query!(
+        "INSERT INTO guild_case_counters (guild_id, scope, last_number)
+         VALUES ($1, '', 1), ($1, $2, 1)
+         ON CONFLICT (guild_id, scope)
+         DO UPDATE SET last_number = guild_case_counters.last_number + 1
+         RETURNING scope, last_number",
+        guild_id_i64,
+        case_code,
     )
-    .bind(guild_id_i64)
-    .fetch_one(&mut *tx)
+    .fetch_all(&mut **tx)
     .await?;
 
-    let next_action_case_number: i64 = sqlx::query_scalar(
-        "SELECT COALESCE(MAX(action_case_number), 0) + 1
-         FROM mod_cases
-         WHERE guild_id = $1 AND case_code = $2",
-    )
-    .bind(guild_id_i64)
-    .bind(case_code)
-    .fetch_one(&mut *tx)
-    .await?;
+    let counter = |scope: &str| {
+        counters
+            .iter()
+            .find(|counter| counter.scope == scope)
+            .map(|counter| counter.last_number)
+            .with_context(|| format!("missing case counter `{scope}`"))
+    };
+    let next_case_number = counter("")?;
+    let next_action_case_number = counter(case_code)?;
 
-    let case_row: ModerationCaseRow = sqlx::query_as(
+    let case_row: ModerationCaseRow = sqlx::query_as!(
+        ModerationCaseRow,
         "INSERT INTO mod_cases (
             guild_id,
             case_number,
@@ -120,22 +142,22 @@ pub async fn create_case(db: &Database, new_case: NewCase<'_>) -> anyhow::Result
             updated_at
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
             RETURNING id, case_number, case_code, action_case_number, guild_id, target_user_id, moderator_user_id, action, reason, status, duration_seconds, created_at, updated_at",
+        guild_id_i64,
+        next_case_number,
+        case_code,
+        next_action_case_number,
+        target_user_id_i64,
+        moderator_user_id_i64,
+        new_case.action,
+        new_case.reason,
+        new_case.status,
+        duration_seconds_i64,
+        now,
     )
-    .bind(guild_id_i64)
-    .bind(next_case_number)
-        .bind(case_code)
-        .bind(next_action_case_number)
-    .bind(target_user_id_i64)
-    .bind(moderator_user_id_i64)
-    .bind(new_case.action)
-    .bind(new_case.reason)
-    .bind(new_case.status)
-    .bind(duration_seconds_i64)
-    .bind(now)
-    .fetch_one(&mut *tx)
+    .fetch_one(&mut **tx)
     .await?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO mod_case_events (
             case_id,
             guild_id,
@@ -144,18 +166,20 @@ pub async fn create_case(db: &Database, new_case: NewCase<'_>) -> anyhow::Result
             note,
             created_at
          ) VALUES ($1, $2, 'created', $3, $4, $5)",
+        case_row.id,
+        guild_id_i64,
+        moderator_user_id_i64,
+        Some("Case created"),
+        now,
     )
-    .bind(case_row.id)
-    .bind(guild_id_i64)
-    .bind(moderator_user_id_i64)
-    .bind(Some("Case created"))
-    .bind(now)
-    .execute(&mut *tx)
+    .execute(&mut **tx)
     .await?;
 
-    tx.commit().await?;
+    let case = to_moderation_case(case_row.clone())?;
+    enqueue_case_webhooks(tx, "case.created", &case, None).await?;
 
-    to_case_summary(case_row)
+    let case_id = case_row.id;
+    Ok((case_id, to_case_summary(case_row)?))
 }
 
 pub async fn list_recent_cases(
@@ -176,7 +200,8 @@ pub async fn list_recent_cases(
         .context("moderator_user_id out of i64 range")?;
     let limit_i64 = i64::from(filters.limit.clamp(1, 200));
 
-    let rows: Vec<CaseSummaryRow> = sqlx::query_as(
+    let rows: Vec<CaseSummaryRow> = sqlx::query_as!(
+        CaseSummaryRow,
         "SELECT case_number, case_code, action_case_number, target_user_id, moderator_user_id, action, reason, duration_seconds, created_at
          FROM mod_cases
          WHERE guild_id = $1
@@ -185,12 +210,12 @@ pub async fn list_recent_cases(
            AND ($4::TEXT IS NULL OR LOWER(action) = LOWER($4))
          ORDER BY case_number DESC
          LIMIT $5",
+        guild_id_i64,
+        target_user_id_i64,
+        moderator_user_id_i64,
+        filters.action,
+        limit_i64,
     )
-    .bind(guild_id_i64)
-    .bind(target_user_id_i64)
-    .bind(moderator_user_id_i64)
-    .bind(filters.action)
-    .bind(limit_i64)
     .fetch_all(db.pool())
     .await?;
 
@@ -222,6 +247,38 @@ pub async fn list_recent_cases(
     Ok(out)
 }
 
+/// Number of cases per action created since `since` (unix seconds), most
+/// frequent first.
+pub async fn count_cases_by_action_since(
+    db: &Database,
+    guild_id: u64,
+    since: u64,
+) -> anyhow::Result<Vec<(String, u64)>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let since_i64 = i64::try_from(since).context("since out of i64 range")?;
+
+    let rows = sqlx::query!(
+        r#"SELECT action, COUNT(*) AS "total!"
+         FROM mod_cases
+         WHERE guild_id = $1 AND created_at >= $2
+         GROUP BY action
+         ORDER BY 2 DESC, action ASC"#,
+        guild_id_i64,
+        since_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok((
+                row.action,
+                u64::try_from(row.total).context("total row out of u64 range")?,
+            ))
+        })
+        .collect()
+}
+
 pub async fn get_case_by_label(
     db: &Database,
     guild_id: u64,
@@ -232,14 +289,15 @@ pub async fn get_case_by_label(
     let action_case_number_i64 =
         i64::try_from(action_case_number).context("action_case_number out of i64 range")?;
 
-    let row: Option<ModerationCaseRow> = sqlx::query_as(
+    let row: Option<ModerationCaseRow> = sqlx::query_as!(
+        ModerationCaseRow,
         "SELECT id, case_number, case_code, action_case_number, guild_id, target_user_id, moderator_user_id, action, reason, status, duration_seconds, created_at, updated_at
          FROM mod_cases
          WHERE guild_id = $1 AND case_code = $2 AND action_case_number = $3",
+        guild_id_i64,
+        case_code,
+        action_case_number_i64,
     )
-    .bind(guild_id_i64)
-    .bind(case_code)
-    .bind(action_case_number_i64)
     .fetch_optional(db.pool())
     .await?;
 
@@ -256,14 +314,14 @@ pub async fn get_case_events(
     let action_case_number_i64 =
         i64::try_from(action_case_number).context("action_case_number out of i64 range")?;
 
-    let case_id: Option<i64> = sqlx::query_scalar(
+    let case_id: Option<i64> = sqlx::query_scalar!(
         "SELECT id
          FROM mod_cases
          WHERE guild_id = $1 AND case_code = $2 AND action_case_number = $3",
+        guild_id_i64,
+        case_code,
+        action_case_number_i64,
     )
-    .bind(guild_id_i64)
-    .bind(case_code)
-    .bind(action_case_number_i64)
     .fetch_optional(db.pool())
     .await?;
 
@@ -271,14 +329,15 @@ pub async fn get_case_events(
         return Ok(Vec::new());
     };
 
-    let rows: Vec<CaseEventRow> = sqlx::query_as(
+    let rows: Vec<CaseEventRow> = sqlx::query_as!(
+        CaseEventRow,
         "SELECT event_type, actor_user_id, old_reason, new_reason, note, created_at
          FROM mod_case_events
          WHERE guild_id = $1 AND case_id = $2
          ORDER BY created_at ASC, id ASC",
+        guild_id_i64,
+        case_id,
     )
-    .bind(guild_id_i64)
-    .bind(case_id)
     .fetch_all(db.pool())
     .await?;
 
@@ -315,36 +374,38 @@ pub async fn update_case_reason(
 
     let mut tx = db.pool().begin().await?;
 
-    let existing: Option<(i64, String)> = sqlx::query_as(
+    let existing = sqlx::query!(
         "SELECT id, reason
          FROM mod_cases
             WHERE guild_id = $1 AND case_code = $2 AND action_case_number = $3
          FOR UPDATE",
+        guild_id_i64,
+        case_code,
+        action_case_number_i64,
     )
-    .bind(guild_id_i64)
-    .bind(case_code)
-    .bind(action_case_number_i64)
     .fetch_optional(&mut *tx)
     .await?;
 
-    let Some((case_id, old_reason)) = existing else {
+    let Some(existing) = existing else {
         tx.rollback().await?;
         return Ok(None);
     };
+    let (case_id, old_reason) = (existing.id, existing.reason);
 
-    let updated: ModerationCaseRow = sqlx::query_as(
+    let updated: ModerationCaseRow = sqlx::query_as!(
+        ModerationCaseRow,
         "UPDATE mod_cases
          SET reason = $1, updated_at = $2
          WHERE id = $3
             RETURNING id, case_number, case_code, action_case_number, guild_id, target_user_id, moderator_user_id, action, reason, status, duration_seconds, created_at, updated_at",
+        new_reason,
+        now,
+        case_id,
     )
-    .bind(new_reason)
-    .bind(now)
-    .bind(case_id)
     .fetch_one(&mut *tx)
     .await?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO mod_case_events (
             case_id,
             guild_id,
@@ -355,20 +416,34 @@ pub async fn update_case_reason(
             note,
             created_at
          ) VALUES ($1, $2, 'reason_updated', $3, $4, $5, $6, $7)",
+        case_id,
+        guild_id_i64,
+        actor_user_id_i64,
+        Some(old_reason.clone()),
+        Some(new_reason.to_owned()),
+        Some("Reason edited"),
+        now,
     )
-    .bind(case_id)
-    .bind(guild_id_i64)
-    .bind(actor_user_id_i64)
-    .bind(Some(old_reason))
-    .bind(Some(new_reason.to_owned()))
-    .bind(Some("Reason edited"))
-    .bind(now)
     .execute(&mut *tx)
     .await?;
 
+    let case = to_moderation_case(updated)?;
+    enqueue_case_webhooks(
+        &mut tx,
+        "case.updated",
+        &case,
+        Some(serde_json::json!({
+            "type": "reason_updated",
+            "actor_user_id": actor_user_id.to_string(),
+            "old_reason": old_reason,
+            "new_reason": new_reason,
+        })),
+    )
+    .await?;
+
     tx.commit().await?;
 
-    Ok(Some(to_moderation_case(updated)?))
+    Ok(Some(case))
 }
 
 pub async fn add_case_note(
@@ -388,15 +463,15 @@ pub async fn add_case_note(
 
     let mut tx = db.pool().begin().await?;
 
-    let case_id: Option<i64> = sqlx::query_scalar(
+    let case_id: Option<i64> = sqlx::query_scalar!(
         "SELECT id
          FROM mod_cases
          WHERE guild_id = $1 AND case_code = $2 AND action_case_number = $3
          FOR UPDATE",
+        guild_id_i64,
+        case_code,
+        action_case_number_i64,
     )
-    .bind(guild_id_i64)
-    .bind(case_code)
-    .bind(action_case_number_i64)
     .fetch_optional(&mut *tx)
     .await?;
 
@@ -405,7 +480,7 @@ pub async fn add_case_note(
         return Ok(false);
     };
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO mod_case_events (
             case_id,
             guild_id,
@@ -414,25 +489,120 @@ pub async fn add_case_note(
             note,
             created_at
          ) VALUES ($1, $2, 'note_added', $3, $4, $5)",
+        case_id,
+        guild_id_i64,
+        actor_user_id_i64,
+        Some(note.to_owned()),
+        now,
     )
-    .bind(case_id)
-    .bind(guild_id_i64)
-    .bind(actor_user_id_i64)
-    .bind(Some(note.to_owned()))
-    .bind(now)
     .execute(&mut *tx)
     .await?;
 
-    sqlx::query("UPDATE mod_cases SET updated_at = $1 WHERE id = $2")
-        .bind(now)
-        .bind(case_id)
-        .execute(&mut *tx)
-        .await?;
+    let updated: ModerationCaseRow = sqlx::query_as!(
+        ModerationCaseRow,
+        "UPDATE mod_cases
+         SET updated_at = $1
+         WHERE id = $2
+         RETURNING id, case_number, case_code, action_case_number, guild_id, target_user_id, moderator_user_id, action, reason, status, duration_seconds, created_at, updated_at",
+        now,
+        case_id,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    enqueue_case_webhooks(
+        &mut tx,
+        "case.updated",
+        &to_moderation_case(updated)?,
+        Some(serde_json::json!({
+            "type": "note_added",
+            "actor_user_id": actor_user_id.to_string(),
+            "note": note,
+        })),
+    )
+    .await?;
 
     tx.commit().await?;
     Ok(true)
 }
 
+/// Move a case between statuses. Returns `None` if the case doesn't exist or
+/// isn't in `change.from`, so concurrent transitions can't both win.
+pub async fn update_case_status(
+    db: &Database,
+    guild_id: u64,
+    case_code: &str,
+    action_case_number: u64,
+    actor_user_id: u64,
+    change: CaseStatusChange<'_>,
+) -> anyhow::Result<Option<ModerationCase>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let action_case_number_i64 =
+        i64::try_from(action_case_number).context("action_case_number out of i64 range")?;
+    let actor_user_id_i64 =
+        i64::try_from(actor_user_id).context("actor_user_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let updated: Option<ModerationCaseRow> = sqlx::query_as!(
+        ModerationCaseRow,
+        "UPDATE mod_cases
+         SET status = $1, updated_at = $2
+         WHERE guild_id = $3 AND case_code = $4 AND action_case_number = $5 AND status = $6
+         RETURNING id, case_number, case_code, action_case_number, guild_id, target_user_id, moderator_user_id, action, reason, status, duration_seconds, created_at, updated_at",
+        change.to,
+        now,
+        guild_id_i64,
+        case_code,
+        action_case_number_i64,
+        change.from,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(updated) = updated else {
+        tx.rollback().await?;
+        return Ok(None);
+    };
+
+    sqlx::query!(
+        "INSERT INTO mod_case_events (
+            case_id,
+            guild_id,
+            event_type,
+            actor_user_id,
+            note,
+            created_at
+         ) VALUES ($1, $2, 'status_changed', $3, $4, $5)",
+        updated.id,
+        guild_id_i64,
+        actor_user_id_i64,
+        Some(change.note.to_owned()),
+        now,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let case = to_moderation_case(updated)?;
+    enqueue_case_webhooks(
+        &mut tx,
+        "case.updated",
+        &case,
+        Some(serde_json::json!({
+            "type": "status_changed",
+            "actor_user_id": actor_user_id.to_string(),
+            "old_status": change.from,
+            "new_status": change.to,
+        })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(case))
+}
+
 fn to_case_summary(row: ModerationCaseRow) -> anyhow::Result<CaseSummary> {
     Ok(CaseSummary {
         case_number: u64::try_from(row.case_number).context("case_number out of u64 range")?,
@@ -485,7 +655,7 @@ fn to_moderation_case(row: ModerationCaseRow) -> anyhow::Result<ModerationCase>
     })
 }
 
-fn action_code(action: &str) -> &'static str {
+pub(crate) fn action_code(action: &str) -> &'static str {
     match action {
         "warn" => "W",
         "ban" => "B",
@@ -497,10 +667,51 @@ fn action_code(action: &str) -> &'static str {
         "unwarn_all" => "UWA",
         "purge" => "P",
         "terminate" => "TR",
+        "temprole" => "RO",
+        "temprole_remove" => "URO",
+        "report" => "RP",
+        "quarantine" => "Q",
+        "release" => "RL",
+        "shadowmute" => "SM",
+        "unshadowmute" => "USM",
         "word_filter_timeout" | "word_filter_delete" | "word_filter_log" | "word_filter_warn" => {
             "WF"
         }
+        "link_filter_timeout" | "link_filter_delete" | "link_filter_log" | "link_filter_warn" => {
+            "LF"
+        }
+        "invite_filter_timeout"
+        | "invite_filter_delete"
+        | "invite_filter_log"
+        | "invite_filter_warn" => "IF",
+        "attachment_filter_timeout"
+        | "attachment_filter_delete"
+        | "attachment_filter_log"
+        | "attachment_filter_warn" => "AF",
+        "caps_filter_timeout" | "caps_filter_delete" | "caps_filter_log" | "caps_filter_warn" => {
+            "CF"
+        }
+        "image_filter_timeout"
+        | "image_filter_delete"
+        | "image_filter_log"
+        | "image_filter_warn" => "IM",
+        "name_filter_rename" | "name_filter_kick" | "name_filter_log" => "NF",
+        "spam_filter_timeout" | "spam_filter_delete" | "spam_filter_log" | "spam_filter_warn" => {
+            "SP"
+        }
+        "spam_repeat_timeout" | "spam_repeat_delete" | "spam_repeat_log" | "spam_repeat_warn" => {
+            "SR"
+        }
         "auto_timeout" => "AT",
+        "global_blacklist_ban" | "global_blacklist_quarantine" => "GB",
+        "bansync_ban" => "BS",
         _ => "M",
     }
 }
+
+/// Whether `action` is a timeout, matching the filter in
+/// [`count_timeouts_in_window`](crate::impls::escalation::count_timeouts_in_window).
+pub(crate) fn is_timeout_action(action: &str) -> bool {
+    matches!(action, "timeout" | "auto_timeout" | "spam_repeat_timeout")
+        || action.ends_with("_filter_timeout")
+}
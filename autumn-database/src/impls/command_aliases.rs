@@ -0,0 +1,160 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, command_aliases_key, invalidate_command_aliases};
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::command_aliases::CommandAlias;
+
+/// Most aliases one guild may define.
+pub const MAX_COMMAND_ALIASES_PER_GUILD: usize = 50;
+
+/// Longest alias name, in characters.
+pub const COMMAND_ALIAS_MAX_CHARS: usize = 32;
+
+/// Whether `alias` is usable as a single prefix-command word: lowercase
+/// letters, digits, `-` and `_`.
+pub fn is_valid_command_alias(alias: &str) -> bool {
+    !alias.is_empty()
+        && alias.chars().count() <= COMMAND_ALIAS_MAX_CHARS
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
+}
+
+/// Point `alias` at `command_name`, replacing whatever it pointed at before.
+pub async fn set_command_alias(
+    db: &Database,
+    guild_id: u64,
+    alias: &str,
+    command_name: &str,
+    created_by: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let created_by_i64 = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO command_aliases (guild_id, alias, command_name, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (guild_id, alias)
+         DO UPDATE SET command_name = EXCLUDED.command_name, created_by = EXCLUDED.created_by,
+                       created_at = EXCLUDED.created_at",
+        guild_id_i64,
+        alias,
+        command_name,
+        created_by_i64,
+        now,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_command_aliases(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Delete an alias. Returns `true` if it existed.
+pub async fn remove_command_alias(
+    db: &Database,
+    guild_id: u64,
+    alias: &str,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM command_aliases WHERE guild_id = $1 AND alias = $2",
+        guild_id_i64,
+        alias,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_command_aliases(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Every alias in the guild, sorted by alias (cached; read whenever a prefix
+/// message doesn't name a command).
+pub async fn list_command_aliases(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<CommandAlias>> {
+    let cache_key = command_aliases_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::CommandAliases),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let rows = sqlx::query!(
+                    "SELECT alias, command_name, created_by, created_at
+                     FROM command_aliases
+                     WHERE guild_id = $1
+                     ORDER BY alias",
+                    guild_id_i64,
+                )
+                .fetch_all(db.pool())
+                .await?;
+
+                rows.into_iter()
+                    .map(|row| {
+                        Ok(CommandAlias {
+                            alias: row.alias,
+                            command_name: row.command_name,
+                            created_by: u64::try_from(row.created_by)
+                                .context("created_by row out of u64 range")?,
+                            created_at: u64::try_from(row.created_at)
+                                .context("created_at row out of u64 range")?,
+                        })
+                    })
+                    .collect()
+            },
+        )
+        .await
+}
+
+/// The command `word` is an alias for, ignoring case.
+pub fn resolve_command_alias<'a>(aliases: &'a [CommandAlias], word: &str) -> Option<&'a str> {
+    aliases
+        .iter()
+        .find(|entry| entry.alias.eq_ignore_ascii_case(word))
+        .map(|entry| entry.command_name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(alias: &str, command_name: &str) -> CommandAlias {
+        CommandAlias {
+            alias: alias.to_owned(),
+            command_name: command_name.to_owned(),
+            created_by: 1,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn aliases_resolve_case_insensitively() {
+        let aliases = [entry("mute", "timeout"), entry("to", "timeout")];
+
+        assert_eq!(resolve_command_alias(&aliases, "MUTE"), Some("timeout"));
+        assert_eq!(resolve_command_alias(&aliases, "to"), Some("timeout"));
+        assert_eq!(resolve_command_alias(&aliases, "timeout"), None);
+        assert_eq!(resolve_command_alias(&[], "mute"), None);
+    }
+
+    #[test]
+    fn alias_names_are_single_lowercase_words() {
+        assert!(is_valid_command_alias("mute"));
+        assert!(is_valid_command_alias("to_2"));
+        assert!(!is_valid_command_alias(""));
+        assert!(!is_valid_command_alias("Mute"));
+        assert!(!is_valid_command_alias("two words"));
+        assert!(!is_valid_command_alias(
+            &"a".repeat(COMMAND_ALIAS_MAX_CHARS + 1)
+        ));
+    }
+}
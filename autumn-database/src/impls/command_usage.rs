@@ -0,0 +1,118 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::command_usage::{CommandUsageStats, CommandUsageSummary};
+
+pub struct NewCommandInvocation<'a> {
+    pub guild_id: Option<u64>,
+    pub user_id: u64,
+    pub command: &'a str,
+    pub args_hash: Option<&'a str>,
+    /// `None` when the command succeeded.
+    pub error: Option<&'a str>,
+    pub latency_ms: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct CommandUsageRow {
+    command: String,
+    total: i64,
+    errors: i64,
+    avg_latency_ms: i64,
+}
+
+pub async fn record_command_invocation(
+    db: &Database,
+    invocation: NewCommandInvocation<'_>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = invocation
+        .guild_id
+        .map(i64::try_from)
+        .transpose()
+        .context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(invocation.user_id).context("user_id out of i64 range")?;
+    let latency_ms_i64 =
+        i64::try_from(invocation.latency_ms).context("latency_ms out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO command_invocations
+            (guild_id, user_id, command, args_hash, success, error, latency_ms, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        guild_id_i64,
+        user_id_i64,
+        invocation.command,
+        invocation.args_hash,
+        invocation.error.is_none(),
+        invocation.error,
+        latency_ms_i64,
+        now,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Summarize a guild's invocations since `since` (unix seconds).
+pub async fn get_command_usage_stats(
+    db: &Database,
+    guild_id: u64,
+    since: u64,
+    limit: i64,
+) -> anyhow::Result<CommandUsageStats> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let since_i64 = i64::try_from(since).context("since out of i64 range")?;
+
+    let totals = sqlx::query!(
+        r#"SELECT
+            COUNT(*) AS "total!",
+            COUNT(*) FILTER (WHERE NOT success) AS "errors!",
+            COALESCE(AVG(latency_ms), 0)::BIGINT AS "avg_latency_ms!"
+         FROM command_invocations
+         WHERE guild_id = $1 AND created_at >= $2"#,
+        guild_id_i64,
+        since_i64,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    let rows: Vec<CommandUsageRow> = sqlx::query_as!(
+        CommandUsageRow,
+        r#"SELECT
+            command,
+            COUNT(*) AS "total!",
+            COUNT(*) FILTER (WHERE NOT success) AS "errors!",
+            AVG(latency_ms)::BIGINT AS "avg_latency_ms!"
+         FROM command_invocations
+         WHERE guild_id = $1 AND created_at >= $2
+         GROUP BY command
+         ORDER BY 2 DESC, command ASC
+         LIMIT $3"#,
+        guild_id_i64,
+        since_i64,
+        limit,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(CommandUsageStats {
+        total: u64::try_from(totals.total).context("total out of u64 range")?,
+        errors: u64::try_from(totals.errors).context("errors out of u64 range")?,
+        avg_latency_ms: u64::try_from(totals.avg_latency_ms)
+            .context("avg_latency_ms out of u64 range")?,
+        commands: rows
+            .into_iter()
+            .map(|row| {
+                Ok(CommandUsageSummary {
+                    command: row.command,
+                    total: u64::try_from(row.total).context("total row out of u64 range")?,
+                    errors: u64::try_from(row.errors).context("errors row out of u64 range")?,
+                    avg_latency_ms: u64::try_from(row.avg_latency_ms)
+                        .context("avg_latency_ms row out of u64 range")?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?,
+    })
+}
@@ -0,0 +1,157 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, disabled_commands_key, invalidate_disabled_commands};
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::disabled_commands::DisabledCommand;
+
+/// Stored in place of a channel id for guild-wide entries.
+const GUILD_WIDE_CHANNEL_ID: i64 = 0;
+
+/// Switch `command_name` off in `channel_id`, or everywhere with `None`.
+/// Returns `false` if it was already off there.
+pub async fn disable_command(
+    db: &Database,
+    guild_id: u64,
+    command_name: &str,
+    channel_id: Option<u64>,
+    created_by: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = channel_id_to_i64(channel_id)?;
+    let created_by_i64 = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let result = sqlx::query!(
+        "INSERT INTO disabled_commands (guild_id, command_name, channel_id, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (guild_id, command_name, channel_id) DO NOTHING",
+        guild_id_i64,
+        command_name,
+        channel_id_i64,
+        created_by_i64,
+        now,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_disabled_commands(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Switch `command_name` back on in `channel_id`, or guild-wide with `None`.
+/// Returns `true` if it had been disabled there.
+pub async fn enable_command(
+    db: &Database,
+    guild_id: u64,
+    command_name: &str,
+    channel_id: Option<u64>,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = channel_id_to_i64(channel_id)?;
+
+    let result = sqlx::query!(
+        "DELETE FROM disabled_commands
+         WHERE guild_id = $1 AND command_name = $2 AND channel_id = $3",
+        guild_id_i64,
+        command_name,
+        channel_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_disabled_commands(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Every disabled command in the guild (cached; read on each command).
+pub async fn list_disabled_commands(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<DisabledCommand>> {
+    let cache_key = disabled_commands_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::DisabledCommands),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let rows = sqlx::query!(
+                    "SELECT command_name, channel_id, created_by, created_at
+                     FROM disabled_commands
+                     WHERE guild_id = $1
+                     ORDER BY command_name, channel_id",
+                    guild_id_i64,
+                )
+                .fetch_all(db.pool())
+                .await?;
+
+                rows.into_iter()
+                    .map(|row| {
+                        Ok(DisabledCommand {
+                            command_name: row.command_name,
+                            channel_id: match row.channel_id {
+                                GUILD_WIDE_CHANNEL_ID => None,
+                                channel_id => Some(
+                                    u64::try_from(channel_id)
+                                        .context("channel_id row out of u64 range")?,
+                                ),
+                            },
+                            created_by: u64::try_from(row.created_by)
+                                .context("created_by row out of u64 range")?,
+                            created_at: u64::try_from(row.created_at)
+                                .context("created_at row out of u64 range")?,
+                        })
+                    })
+                    .collect()
+            },
+        )
+        .await
+}
+
+/// Whether `command_name` is off guild-wide or in `channel_id`.
+pub fn is_command_disabled(
+    disabled: &[DisabledCommand],
+    command_name: &str,
+    channel_id: u64,
+) -> bool {
+    disabled.iter().any(|entry| {
+        entry.command_name == command_name && entry.channel_id.is_none_or(|id| id == channel_id)
+    })
+}
+
+fn channel_id_to_i64(channel_id: Option<u64>) -> anyhow::Result<i64> {
+    match channel_id {
+        Some(channel_id) => i64::try_from(channel_id).context("channel_id out of i64 range"),
+        None => Ok(GUILD_WIDE_CHANNEL_ID),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(command_name: &str, channel_id: Option<u64>) -> DisabledCommand {
+        DisabledCommand {
+            command_name: command_name.to_owned(),
+            channel_id,
+            created_by: 1,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn guild_wide_entries_cover_every_channel() {
+        let disabled = [entry("purge", Some(10)), entry("ping", None)];
+
+        assert!(is_command_disabled(&disabled, "purge", 10));
+        assert!(!is_command_disabled(&disabled, "purge", 11));
+        assert!(is_command_disabled(&disabled, "ping", 10));
+        assert!(is_command_disabled(&disabled, "ping", 99));
+        assert!(!is_command_disabled(&disabled, "warn", 10));
+        assert!(!is_command_disabled(&[], "purge", 10));
+    }
+}
@@ -2,9 +2,11 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Context as _;
 
-use crate::cache::{CONFIG_CACHE_TTL, escalation_config_key, invalidate_escalation_config};
+use crate::cache::invalidate_guild_config;
 use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
 use crate::model::escalation::EscalationConfig;
+use crate::repository::{CaseRepository, EscalationRepository, WarningRepository};
 
 // ---------------------------------------------------------------------------
 // Escalation tiers: number of past timeouts → duration in seconds
@@ -28,6 +30,52 @@ pub fn escalation_timeout_seconds(previous_timeout_count: i64) -> i64 {
     }
 }
 
+/// Outcome of [`evaluate_escalation`] when the warning threshold is met.
+#[derive(Clone, Debug)]
+pub struct EscalationDecision {
+    pub config: EscalationConfig,
+    /// Warnings in the warn window, including the one just issued.
+    pub warn_count: i64,
+    /// Earlier timeouts in the timeout window; the escalation tier.
+    pub timeout_count: i64,
+    pub timeout_seconds: i64,
+}
+
+/// Decide whether a user who was just warned should be auto-timed-out.
+///
+/// Returns `None` if escalation is disabled or the threshold was not met.
+pub async fn evaluate_escalation(
+    escalation: &dyn EscalationRepository,
+    warnings: &dyn WarningRepository,
+    cases: &dyn CaseRepository,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<Option<EscalationDecision>> {
+    let Some(config) = escalation.escalation_config_if_enabled(guild_id).await? else {
+        return Ok(None);
+    };
+
+    let warn_count = warnings
+        .count_warnings_in_window(guild_id, user_id, config.warn_window_seconds)
+        .await
+        .context("failed to count warnings for escalation")?;
+    if warn_count < i64::from(config.warn_threshold) {
+        return Ok(None);
+    }
+
+    let timeout_count = cases
+        .count_timeouts_in_window(guild_id, user_id, config.timeout_window_seconds)
+        .await
+        .context("failed to count timeouts for escalation")?;
+
+    Ok(Some(EscalationDecision {
+        config,
+        warn_count,
+        timeout_count,
+        timeout_seconds: escalation_timeout_seconds(timeout_count),
+    }))
+}
+
 // ---------------------------------------------------------------------------
 // Config CRUD
 // ---------------------------------------------------------------------------
@@ -36,22 +84,7 @@ pub async fn get_escalation_config(
     db: &Database,
     guild_id: u64,
 ) -> anyhow::Result<Option<EscalationConfig>> {
-    let cache_key = escalation_config_key(db.cache(), guild_id);
-    db.cache()
-        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
-            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
-
-            let row = sqlx::query_as::<_, EscalationConfig>(
-                "SELECT guild_id, enabled, warn_threshold, warn_window_seconds, timeout_window_seconds \
-                 FROM escalation_config WHERE guild_id = $1",
-            )
-            .bind(guild_id_i64)
-            .fetch_optional(db.pool())
-            .await?;
-
-            Ok(row)
-        })
-        .await
+    Ok(get_guild_config(db, guild_id).await?.escalation)
 }
 
 /// Get the escalation config only if it is enabled.
@@ -70,16 +103,16 @@ pub async fn set_escalation_enabled(
 ) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO escalation_config (guild_id, enabled) VALUES ($1, $2) \
          ON CONFLICT (guild_id) DO UPDATE SET enabled = $2",
+        guild_id_i64,
+        enabled,
     )
-    .bind(guild_id_i64)
-    .bind(enabled)
     .execute(db.pool())
     .await?;
 
-    invalidate_escalation_config(db.cache(), guild_id).await?;
+    invalidate_guild_config(db.cache(), guild_id).await?;
 
     Ok(())
 }
@@ -91,16 +124,16 @@ pub async fn set_warn_threshold(
 ) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO escalation_config (guild_id, warn_threshold) VALUES ($1, $2) \
          ON CONFLICT (guild_id) DO UPDATE SET warn_threshold = $2",
+        guild_id_i64,
+        threshold,
     )
-    .bind(guild_id_i64)
-    .bind(threshold)
     .execute(db.pool())
     .await?;
 
-    invalidate_escalation_config(db.cache(), guild_id).await?;
+    invalidate_guild_config(db.cache(), guild_id).await?;
 
     Ok(())
 }
@@ -112,16 +145,16 @@ pub async fn set_warn_window(
 ) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO escalation_config (guild_id, warn_window_seconds) VALUES ($1, $2) \
          ON CONFLICT (guild_id) DO UPDATE SET warn_window_seconds = $2",
+        guild_id_i64,
+        window_seconds,
     )
-    .bind(guild_id_i64)
-    .bind(window_seconds)
     .execute(db.pool())
     .await?;
 
-    invalidate_escalation_config(db.cache(), guild_id).await?;
+    invalidate_guild_config(db.cache(), guild_id).await?;
 
     Ok(())
 }
@@ -133,16 +166,16 @@ pub async fn set_timeout_window(
 ) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO escalation_config (guild_id, timeout_window_seconds) VALUES ($1, $2) \
          ON CONFLICT (guild_id) DO UPDATE SET timeout_window_seconds = $2",
+        guild_id_i64,
+        window_seconds,
     )
-    .bind(guild_id_i64)
-    .bind(window_seconds)
     .execute(db.pool())
     .await?;
 
-    invalidate_escalation_config(db.cache(), guild_id).await?;
+    invalidate_guild_config(db.cache(), guild_id).await?;
 
     Ok(())
 }
@@ -168,13 +201,13 @@ pub async fn count_warnings_in_window(
     let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
     let since = now_unix_secs() - window_seconds;
 
-    let count: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM warnings \
-         WHERE guild_id = $1 AND user_id = $2 AND warned_at >= $3",
+    let count: i64 = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM warnings
+         WHERE guild_id = $1 AND user_id = $2 AND warned_at >= $3 AND decayed_at IS NULL"#,
+        guild_id_i64,
+        user_id_i64,
+        since,
     )
-    .bind(guild_id_i64)
-    .bind(user_id_i64)
-    .bind(since)
     .fetch_one(db.pool())
     .await?;
 
@@ -182,7 +215,7 @@ pub async fn count_warnings_in_window(
 }
 
 /// Count timeout cases for a user within a guild in the given time window.
-/// Includes both manual timeouts, auto-timeouts, and word-filter timeouts.
+/// Includes manual timeouts, auto-timeouts, and automod filter timeouts.
 pub async fn count_timeouts_in_window(
     db: &Database,
     guild_id: u64,
@@ -193,16 +226,67 @@ pub async fn count_timeouts_in_window(
     let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
     let since = now_unix_secs() - window_seconds;
 
-    let count: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM mod_cases \
-         WHERE guild_id = $1 AND target_user_id = $2 AND created_at >= $3 \
-         AND action IN ('timeout', 'auto_timeout', 'word_filter_timeout')",
+    let count: i64 = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM mod_cases
+         WHERE guild_id = $1 AND target_user_id = $2 AND created_at >= $3
+         AND (action IN ('timeout', 'auto_timeout', 'spam_repeat_timeout')
+              OR action LIKE '%\_filter\_timeout')"#,
+        guild_id_i64,
+        user_id_i64,
+        since,
     )
-    .bind(guild_id_i64)
-    .bind(user_id_i64)
-    .bind(since)
     .fetch_one(db.pool())
     .await?;
 
     Ok(count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::cases::NewCase;
+    use crate::repository::InMemoryRepository;
+
+    fn enabled_config(guild_id: u64) -> EscalationConfig {
+        EscalationConfig {
+            guild_id: guild_id as i64,
+            enabled: true,
+            warn_threshold: 2,
+            warn_window_seconds: 3_600,
+            timeout_window_seconds: 86_400,
+        }
+    }
+
+    #[tokio::test]
+    async fn escalates_once_threshold_is_met() {
+        let repo = InMemoryRepository::new();
+        let evaluate = || evaluate_escalation(&repo, &repo, &repo, 1, 10);
+
+        repo.record_warning(1, 10, 99, "spam").await.unwrap();
+        assert!(evaluate().await.unwrap().is_none());
+
+        repo.set_escalation_config(1, enabled_config(1));
+        assert!(evaluate().await.unwrap().is_none());
+
+        repo.record_warning(1, 10, 99, "spam").await.unwrap();
+        let decision = evaluate().await.unwrap().unwrap();
+        assert_eq!(decision.warn_count, 2);
+        assert_eq!(decision.timeout_count, 0);
+        assert_eq!(decision.timeout_seconds, 300);
+
+        repo.create_case(NewCase {
+            guild_id: 1,
+            target_user_id: Some(10),
+            moderator_user_id: 99,
+            action: "word_filter_timeout",
+            reason: "filtered",
+            status: "completed",
+            duration_seconds: Some(60),
+        })
+        .await
+        .unwrap();
+        let decision = evaluate().await.unwrap().unwrap();
+        assert_eq!(decision.timeout_count, 1);
+        assert_eq!(decision.timeout_seconds, 1_800);
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, exemptions_key, invalidate_exemptions};
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::exemptions::ModerationExemption;
+
+#[derive(sqlx::FromRow)]
+struct ExemptionRow {
+    id: i64,
+    guild_id: i64,
+    scope: String,
+    target_kind: String,
+    target_id: i64,
+    created_by: i64,
+    created_at: i64,
+}
+
+/// Add an exemption for a role, channel, or user. Returns `true` if inserted,
+/// `false` if the exemption already existed.
+pub async fn add_exemption(
+    db: &Database,
+    guild_id: u64,
+    scope: &str,
+    target_kind: &str,
+    target_id: u64,
+    created_by: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let target_id_i64 = i64::try_from(target_id).context("target_id out of i64 range")?;
+    let created_by_i64 = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let result = sqlx::query!(
+        "INSERT INTO moderation_exemptions (guild_id, scope, target_kind, target_id, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (guild_id, scope, target_kind, target_id) DO NOTHING",
+        guild_id_i64,
+        scope,
+        target_kind,
+        target_id_i64,
+        created_by_i64,
+        now,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_exemptions(db.cache(), guild_id, scope).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove an exemption. Returns `true` if removed.
+pub async fn remove_exemption(
+    db: &Database,
+    guild_id: u64,
+    scope: &str,
+    target_kind: &str,
+    target_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let target_id_i64 = i64::try_from(target_id).context("target_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM moderation_exemptions
+         WHERE guild_id = $1 AND scope = $2 AND target_kind = $3 AND target_id = $4",
+        guild_id_i64,
+        scope,
+        target_kind,
+        target_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_exemptions(db.cache(), guild_id, scope).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// List all exemptions for a guild within a scope (cached, used on the hot path).
+pub async fn list_exemptions(
+    db: &Database,
+    guild_id: u64,
+    scope: &str,
+) -> anyhow::Result<Vec<ModerationExemption>> {
+    let cache_key = exemptions_key(db.cache(), guild_id, scope);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::Exemptions),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let rows: Vec<ExemptionRow> = sqlx::query_as!(
+                    ExemptionRow,
+                    "SELECT id, guild_id, scope, target_kind, target_id, created_by, created_at
+                 FROM moderation_exemptions
+                 WHERE guild_id = $1 AND scope = $2
+                 ORDER BY target_kind ASC, created_at ASC",
+                    guild_id_i64,
+                    scope,
+                )
+                .fetch_all(db.pool())
+                .await?;
+
+                rows.into_iter().map(to_exemption).collect()
+            },
+        )
+        .await
+}
+
+/// Check whether a user, channel, or any of the user's roles is exempt.
+pub fn is_exempt(
+    exemptions: &[ModerationExemption],
+    user_id: u64,
+    channel_id: Option<u64>,
+    role_ids: &[u64],
+) -> bool {
+    exemptions
+        .iter()
+        .any(|exemption| match exemption.target_kind.as_str() {
+            "user" => exemption.target_id == user_id,
+            "channel" => channel_id == Some(exemption.target_id),
+            "role" => role_ids.contains(&exemption.target_id),
+            _ => false,
+        })
+}
+
+fn to_exemption(row: ExemptionRow) -> anyhow::Result<ModerationExemption> {
+    Ok(ModerationExemption {
+        id: u64::try_from(row.id).context("id row out of u64 range")?,
+        guild_id: u64::try_from(row.guild_id).context("guild_id row out of u64 range")?,
+        scope: row.scope,
+        target_kind: row.target_kind,
+        target_id: u64::try_from(row.target_id).context("target_id row out of u64 range")?,
+        created_by: u64::try_from(row.created_by).context("created_by row out of u64 range")?,
+        created_at: u64::try_from(row.created_at).context("created_at row out of u64 range")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exemption(target_kind: &str, target_id: u64) -> ModerationExemption {
+        ModerationExemption {
+            id: 1,
+            guild_id: 1,
+            scope: "word_filter".to_owned(),
+            target_kind: target_kind.to_owned(),
+            target_id,
+            created_by: 1,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn exemption_matches_user_channel_and_role() {
+        let list = vec![
+            exemption("user", 10),
+            exemption("channel", 20),
+            exemption("role", 30),
+        ];
+
+        assert!(is_exempt(&list, 10, None, &[]));
+        assert!(is_exempt(&list, 11, Some(20), &[]));
+        assert!(is_exempt(&list, 11, Some(21), &[5, 30]));
+        assert!(!is_exempt(&list, 11, Some(21), &[5]));
+        assert!(!is_exempt(&[], 10, Some(20), &[30]));
+    }
+}
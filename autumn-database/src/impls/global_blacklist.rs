@@ -0,0 +1,118 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+/// Ban blacklisted users when they join. The default.
+pub const BLACKLIST_ACTION_BAN: &str = "ban";
+/// Time them out for as long as Discord allows, so staff can review.
+pub const BLACKLIST_ACTION_QUARANTINE: &str = "quarantine";
+/// Ignore the global blacklist in this guild.
+pub const BLACKLIST_ACTION_OFF: &str = "off";
+
+pub const BLACKLIST_REASON_MAX_CHARS: usize = 500;
+
+pub const BLACKLIST_ACTIONS: &[&str] = &[
+    BLACKLIST_ACTION_BAN,
+    BLACKLIST_ACTION_QUARANTINE,
+    BLACKLIST_ACTION_OFF,
+];
+
+#[derive(Clone, Debug)]
+pub struct GlobalBlacklistEntry {
+    pub user_id: u64,
+    pub reason: String,
+    pub added_by: u64,
+    pub created_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct GlobalBlacklistRow {
+    user_id: i64,
+    reason: String,
+    added_by: i64,
+    created_at: i64,
+}
+
+/// Blacklist a user, or replace the reason for one already listed. Returns
+/// `false` when they were already on the list.
+pub async fn add_to_global_blacklist(
+    db: &Database,
+    user_id: u64,
+    reason: &str,
+    added_by: u64,
+) -> anyhow::Result<bool> {
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let added_by = i64::try_from(added_by).context("added_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let inserted = sqlx::query_scalar!(
+        r#"INSERT INTO global_blacklist (user_id, reason, added_by, created_at)
+           VALUES ($1, $2, $3, $4)
+           ON CONFLICT (user_id) DO UPDATE
+           SET reason = EXCLUDED.reason, added_by = EXCLUDED.added_by
+           RETURNING (xmax = 0) AS "inserted!""#,
+        user_id,
+        reason,
+        added_by,
+        now,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok(inserted)
+}
+
+/// Returns `false` if the user wasn't blacklisted.
+pub async fn remove_from_global_blacklist(db: &Database, user_id: u64) -> anyhow::Result<bool> {
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let removed = sqlx::query!("DELETE FROM global_blacklist WHERE user_id = $1", user_id)
+        .execute(db.pool())
+        .await?
+        .rows_affected();
+
+    Ok(removed > 0)
+}
+
+pub async fn get_global_blacklist_entry(
+    db: &Database,
+    user_id: u64,
+) -> anyhow::Result<Option<GlobalBlacklistEntry>> {
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let row: Option<GlobalBlacklistRow> = sqlx::query_as!(
+        GlobalBlacklistRow,
+        "SELECT user_id, reason, added_by, created_at
+         FROM global_blacklist
+         WHERE user_id = $1",
+        user_id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_entry).transpose()
+}
+
+/// Every blacklisted user, most recently added first.
+pub async fn list_global_blacklist(db: &Database) -> anyhow::Result<Vec<GlobalBlacklistEntry>> {
+    let rows: Vec<GlobalBlacklistRow> = sqlx::query_as!(
+        GlobalBlacklistRow,
+        "SELECT user_id, reason, added_by, created_at
+         FROM global_blacklist
+         ORDER BY created_at DESC",
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_entry).collect()
+}
+
+fn to_entry(row: GlobalBlacklistRow) -> anyhow::Result<GlobalBlacklistEntry> {
+    Ok(GlobalBlacklistEntry {
+        user_id: u64::try_from(row.user_id).context("negative user_id")?,
+        reason: row.reason,
+        added_by: u64::try_from(row.added_by).context("negative added_by")?,
+        created_at: u64::try_from(row.created_at).context("negative created_at")?,
+    })
+}
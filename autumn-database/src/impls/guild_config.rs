@@ -0,0 +1,334 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, guild_config_key};
+use crate::database::Database;
+use crate::model::antispam::AntispamConfig;
+use crate::model::attachment_filter::AttachmentFilterConfig;
+use crate::model::caps_filter::CapsFilterConfig;
+use crate::model::escalation::EscalationConfig;
+use crate::model::guild_config::{GuildConfig, ShadowMuteExpiry};
+use crate::model::image_filter::ImageFilterConfig;
+use crate::model::invite_filter::InviteFilterConfig;
+use crate::model::link_filter::LinkFilterConfig;
+use crate::model::word_filter::{WordFilterChannelOverride, WordFilterConfig};
+
+/// Every setting the message handlers need for a guild, from one cache key.
+pub async fn get_guild_config(db: &Database, guild_id: u64) -> anyhow::Result<GuildConfig> {
+    let cache_key = guild_config_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::GuildConfig),
+            || load_guild_config(db, guild_id),
+        )
+        .await
+}
+
+/// Read every section in one query, left-joining each config table on the
+/// guild so missing rows come back as NULL columns.
+async fn load_guild_config(db: &Database, guild_id: u64) -> anyhow::Result<GuildConfig> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let row = sqlx::query!(
+        r#"SELECT
+            m.modlog_channel_id AS "modlog_channel_id?",
+            m.purge_transcripts AS "purge_transcripts?",
+            u.userlog_channel_id AS "userlog_channel_id?",
+            ai.llm_enabled AS "llm_enabled?",
+            ai.system_prompt AS "llm_system_prompt?",
+            ai.suggest_reasons AS "llm_suggest_reasons?",
+            ai.use_threads AS "llm_threads?",
+            ai.translate_language AS "translate_language?",
+            ms.enforce_hierarchy AS "enforce_mod_hierarchy?",
+            ms.embed_color AS "embed_color?",
+            ms.global_blacklist_action AS "global_blacklist_action?",
+            ms.dual_approval AS "dual_approval?",
+            wl.channel_id AS "watchlist_channel_id?",
+            ARRAY(
+                SELECT user_id FROM watchlist
+                WHERE guild_id = g.guild_id ORDER BY user_id
+            ) AS "watched_user_ids!",
+            ARRAY(
+                SELECT user_id FROM shadow_mutes
+                WHERE guild_id = g.guild_id ORDER BY user_id
+            ) AS "shadow_muted_user_ids!",
+            ARRAY(
+                SELECT expires_at FROM shadow_mutes
+                WHERE guild_id = g.guild_id ORDER BY user_id
+            ) AS "shadow_mute_expires_at!",
+            e.enabled AS "escalation_enabled?",
+            e.warn_threshold AS "escalation_warn_threshold?",
+            e.warn_window_seconds AS "escalation_warn_window_seconds?",
+            e.timeout_window_seconds AS "escalation_timeout_window_seconds?",
+            s.enabled AS "antispam_enabled?",
+            s.message_threshold AS "antispam_message_threshold?",
+            s.message_window_seconds AS "antispam_message_window_seconds?",
+            s.duplicate_threshold AS "antispam_duplicate_threshold?",
+            s.duplicate_window_seconds AS "antispam_duplicate_window_seconds?",
+            s.action AS "antispam_action?",
+            s.duplicate_action AS "antispam_duplicate_action?",
+            wf.enabled AS "word_filter_enabled?",
+            wf.action AS "word_filter_action?",
+            wf.ai_check_enabled AS "word_filter_ai_check?",
+            wf.ai_threshold AS "word_filter_ai_threshold?",
+            wf.ai_action AS "word_filter_ai_action?",
+            ARRAY(
+                SELECT channel_id FROM word_filter_channels
+                WHERE guild_id = g.guild_id ORDER BY channel_id
+            ) AS "word_filter_channel_ids!",
+            ARRAY(
+                SELECT enabled FROM word_filter_channels
+                WHERE guild_id = g.guild_id ORDER BY channel_id
+            ) AS "word_filter_channel_enabled!",
+            inv.enabled AS "invite_filter_enabled?",
+            inv.action AS "invite_filter_action?",
+            ARRAY(
+                SELECT allowed_guild_id FROM invite_filter_allowed_guilds
+                WHERE guild_id = g.guild_id ORDER BY created_at
+            ) AS "invite_filter_allowed_guild_ids!",
+            att.enabled AS "attachment_filter_enabled?",
+            att.action AS "attachment_filter_action?",
+            ARRAY(
+                SELECT extension FROM attachment_filter_extensions
+                WHERE guild_id = g.guild_id ORDER BY extension
+            ) AS "attachment_filter_extensions!",
+            ARRAY(
+                SELECT channel_id FROM attachment_filter_channels
+                WHERE guild_id = g.guild_id ORDER BY created_at
+            ) AS "attachment_filter_channel_ids!",
+            lf.enabled AS "link_filter_enabled?",
+            lf.mode AS "link_filter_mode?",
+            lf.action AS "link_filter_action?",
+            cf.enabled AS "caps_filter_enabled?",
+            cf.min_length AS "caps_filter_min_length?",
+            cf.max_caps_percent AS "caps_filter_max_caps_percent?",
+            cf.action AS "caps_filter_action?",
+            img.enabled AS "image_filter_enabled?",
+            img.threshold AS "image_filter_threshold?",
+            img.action AS "image_filter_action?"
+         FROM (SELECT $1::BIGINT AS guild_id) g
+         LEFT JOIN guild_mod_config m ON m.guild_id = g.guild_id
+         LEFT JOIN guild_userlog_config u ON u.guild_id = g.guild_id
+         LEFT JOIN guild_ai_config ai ON ai.guild_id = g.guild_id
+         LEFT JOIN guild_moderation_settings ms ON ms.guild_id = g.guild_id
+         LEFT JOIN escalation_config e ON e.guild_id = g.guild_id
+         LEFT JOIN antispam_config s ON s.guild_id = g.guild_id
+         LEFT JOIN word_filter_config wf ON wf.guild_id = g.guild_id
+         LEFT JOIN invite_filter_config inv ON inv.guild_id = g.guild_id
+         LEFT JOIN attachment_filter_config att ON att.guild_id = g.guild_id
+         LEFT JOIN link_filter_config lf ON lf.guild_id = g.guild_id
+         LEFT JOIN caps_filter_config cf ON cf.guild_id = g.guild_id
+         LEFT JOIN image_filter_config img ON img.guild_id = g.guild_id
+         LEFT JOIN guild_watchlist_config wl ON wl.guild_id = g.guild_id"#,
+        guild_id_i64,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    let escalation = match (
+        row.escalation_enabled,
+        row.escalation_warn_threshold,
+        row.escalation_warn_window_seconds,
+        row.escalation_timeout_window_seconds,
+    ) {
+        (
+            Some(enabled),
+            Some(warn_threshold),
+            Some(warn_window_seconds),
+            Some(timeout_window_seconds),
+        ) => Some(EscalationConfig {
+            guild_id: guild_id_i64,
+            enabled,
+            warn_threshold,
+            warn_window_seconds,
+            timeout_window_seconds,
+        }),
+        _ => None,
+    };
+
+    let antispam = match (
+        row.antispam_enabled,
+        row.antispam_message_threshold,
+        row.antispam_message_window_seconds,
+        row.antispam_duplicate_threshold,
+        row.antispam_duplicate_window_seconds,
+        row.antispam_action,
+        row.antispam_duplicate_action,
+    ) {
+        (
+            Some(enabled),
+            Some(message_threshold),
+            Some(message_window_seconds),
+            Some(duplicate_threshold),
+            Some(duplicate_window_seconds),
+            Some(action),
+            Some(duplicate_action),
+        ) => Some(AntispamConfig {
+            guild_id: guild_id_i64,
+            enabled,
+            message_threshold,
+            message_window_seconds,
+            duplicate_threshold,
+            duplicate_window_seconds,
+            action,
+            duplicate_action,
+        }),
+        _ => None,
+    };
+
+    let word_filter = match (
+        row.word_filter_enabled,
+        row.word_filter_action,
+        row.word_filter_ai_check,
+        row.word_filter_ai_threshold,
+        row.word_filter_ai_action,
+    ) {
+        (Some(enabled), Some(action), Some(ai_check), Some(ai_threshold), Some(ai_action)) => {
+            Some(WordFilterConfig {
+                guild_id,
+                enabled,
+                action,
+                ai_check,
+                ai_threshold: u8::try_from(ai_threshold).context("ai_threshold out of u8 range")?,
+                ai_action,
+            })
+        }
+        _ => None,
+    };
+
+    let word_filter_channels = row
+        .word_filter_channel_ids
+        .into_iter()
+        .zip(row.word_filter_channel_enabled)
+        .map(|(channel_id, enabled)| {
+            Ok(WordFilterChannelOverride {
+                channel_id: u64::try_from(channel_id).context("channel_id out of u64 range")?,
+                enabled,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let invite_filter = match (row.invite_filter_enabled, row.invite_filter_action) {
+        (Some(enabled), Some(action)) => Some(InviteFilterConfig {
+            guild_id,
+            enabled,
+            action,
+            allowed_guild_ids: to_u64_ids(row.invite_filter_allowed_guild_ids, "allowed_guild_id")?,
+        }),
+        _ => None,
+    };
+
+    let attachment_filter = match (row.attachment_filter_enabled, row.attachment_filter_action) {
+        (Some(enabled), Some(action)) => Some(AttachmentFilterConfig {
+            guild_id,
+            enabled,
+            action,
+            blocked_extensions: row.attachment_filter_extensions,
+            blocked_channel_ids: to_u64_ids(row.attachment_filter_channel_ids, "channel_id")?,
+        }),
+        _ => None,
+    };
+
+    let link_filter = match (
+        row.link_filter_enabled,
+        row.link_filter_mode,
+        row.link_filter_action,
+    ) {
+        (Some(enabled), Some(mode), Some(action)) => Some(LinkFilterConfig {
+            guild_id,
+            enabled,
+            mode,
+            action,
+        }),
+        _ => None,
+    };
+
+    let caps_filter = match (
+        row.caps_filter_enabled,
+        row.caps_filter_min_length,
+        row.caps_filter_max_caps_percent,
+        row.caps_filter_action,
+    ) {
+        (Some(enabled), Some(min_length), Some(max_caps_percent), Some(action)) => {
+            Some(CapsFilterConfig {
+                guild_id: guild_id_i64,
+                enabled,
+                min_length,
+                max_caps_percent,
+                action,
+            })
+        }
+        _ => None,
+    };
+
+    let image_filter = match (
+        row.image_filter_enabled,
+        row.image_filter_threshold,
+        row.image_filter_action,
+    ) {
+        (Some(enabled), Some(threshold), Some(action)) => Some(ImageFilterConfig {
+            guild_id,
+            enabled,
+            threshold: u8::try_from(threshold).context("threshold out of u8 range")?,
+            action,
+        }),
+        _ => None,
+    };
+
+    Ok(GuildConfig {
+        guild_id,
+        modlog_channel_id: row
+            .modlog_channel_id
+            .map(u64::try_from)
+            .transpose()
+            .context("modlog_channel_id out of u64 range")?,
+        purge_transcripts: row.purge_transcripts.unwrap_or(false),
+        userlog_channel_id: row
+            .userlog_channel_id
+            .map(u64::try_from)
+            .transpose()
+            .context("userlog_channel_id out of u64 range")?,
+        llm_enabled: row.llm_enabled.unwrap_or(true),
+        llm_system_prompt: row.llm_system_prompt,
+        llm_suggest_reasons: row.llm_suggest_reasons.unwrap_or(false),
+        llm_threads: row.llm_threads.unwrap_or(false),
+        translate_language: row.translate_language,
+        enforce_mod_hierarchy: row.enforce_mod_hierarchy.unwrap_or(false),
+        embed_color: row
+            .embed_color
+            .map(u32::try_from)
+            .transpose()
+            .context("embed_color out of u32 range")?,
+        global_blacklist_action: row.global_blacklist_action,
+        dual_approval: row.dual_approval.unwrap_or(false),
+        watchlist_channel_id: row
+            .watchlist_channel_id
+            .map(u64::try_from)
+            .transpose()
+            .context("watchlist_channel_id out of u64 range")?,
+        watched_user_ids: to_u64_ids(row.watched_user_ids, "user_id")?,
+        shadow_mutes: to_u64_ids(row.shadow_muted_user_ids, "user_id")?
+            .into_iter()
+            .zip(to_u64_ids(row.shadow_mute_expires_at, "expires_at")?)
+            .map(|(user_id, expires_at)| ShadowMuteExpiry {
+                user_id,
+                expires_at,
+            })
+            .collect(),
+        escalation,
+        antispam,
+        word_filter,
+        word_filter_channels,
+        invite_filter,
+        attachment_filter,
+        link_filter,
+        caps_filter,
+        image_filter,
+    })
+}
+
+fn to_u64_ids(ids: Vec<i64>, column: &str) -> anyhow::Result<Vec<u64>> {
+    ids.into_iter()
+        .map(|id| u64::try_from(id).with_context(|| format!("{column} out of u64 range")))
+        .collect()
+}
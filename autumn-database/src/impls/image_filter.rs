@@ -0,0 +1,84 @@
+use anyhow::Context as _;
+
+use crate::cache::invalidate_guild_config;
+use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
+use crate::model::image_filter::ImageFilterConfig;
+
+pub async fn get_image_filter_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<ImageFilterConfig>> {
+    Ok(get_guild_config(db, guild_id).await?.image_filter)
+}
+
+pub async fn set_image_filter_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO image_filter_config (guild_id, enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_image_filter_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO image_filter_config (guild_id, action)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET action = EXCLUDED.action",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the minimum confidence, from 1 to 100, before the action runs.
+pub async fn set_image_filter_threshold(
+    db: &Database,
+    guild_id: u64,
+    threshold: u8,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    anyhow::ensure!(
+        (1..=100).contains(&threshold),
+        "threshold must be between 1 and 100"
+    );
+
+    sqlx::query!(
+        "INSERT INTO image_filter_config (guild_id, threshold)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET threshold = EXCLUDED.threshold",
+        guild_id_i64,
+        i16::from(threshold),
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::future::Future;
+
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, invalidate_guild_config, invite_guild_key};
+use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
+use crate::model::invite_filter::InviteFilterConfig;
+
+pub async fn get_invite_filter_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<InviteFilterConfig>> {
+    Ok(get_guild_config(db, guild_id).await?.invite_filter)
+}
+
+pub async fn set_invite_filter_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO invite_filter_config (guild_id, enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_invite_filter_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO invite_filter_config (guild_id, action)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET action = EXCLUDED.action",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Whitelist a guild's invites. Returns `true` if inserted.
+pub async fn add_invite_allowed_guild(
+    db: &Database,
+    guild_id: u64,
+    allowed_guild_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let allowed_i64 =
+        i64::try_from(allowed_guild_id).context("allowed_guild_id out of i64 range")?;
+
+    // Make sure a config row exists so the allow list is loaded with it.
+    sqlx::query!(
+        "INSERT INTO invite_filter_config (guild_id) VALUES ($1)
+         ON CONFLICT (guild_id) DO NOTHING",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    let result = sqlx::query!(
+        "INSERT INTO invite_filter_allowed_guilds (guild_id, allowed_guild_id)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id, allowed_guild_id) DO NOTHING",
+        guild_id_i64,
+        allowed_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove a guild from the invite whitelist. Returns `true` if removed.
+pub async fn remove_invite_allowed_guild(
+    db: &Database,
+    guild_id: u64,
+    allowed_guild_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let allowed_i64 =
+        i64::try_from(allowed_guild_id).context("allowed_guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM invite_filter_allowed_guilds WHERE guild_id = $1 AND allowed_guild_id = $2",
+        guild_id_i64,
+        allowed_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Check whether the invite filter is enabled for a guild and return the config.
+pub async fn get_invite_filter_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<InviteFilterConfig>> {
+    let config = get_invite_filter_config(db, guild_id).await?;
+    match config {
+        Some(cfg) if cfg.enabled => Ok(Some(cfg)),
+        _ => Ok(None),
+    }
+}
+
+/// Resolve an invite code to its guild id, caching the result (including
+/// invalid invites, cached as `None`) so repeated posts don't hit the API.
+pub async fn resolve_invite_guild_cached<F, Fut>(
+    db: &Database,
+    code: &str,
+    resolve: F,
+) -> anyhow::Result<Option<u64>>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<u64>>>,
+{
+    let cache_key = invite_guild_key(db.cache(), code);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::InviteResolve),
+            resolve,
+        )
+        .await
+}
@@ -0,0 +1,256 @@
+use anyhow::Context as _;
+use autumn_utils::links::domain_matches;
+
+use crate::cache::{CacheTtlClass, invalidate_link_filter, link_filter_domains_key};
+use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
+use crate::model::link_filter::{LinkFilterConfig, LinkFilterDomain};
+
+/// Supported link filter modes.
+pub const LINK_FILTER_MODES: &[&str] = &["denylist", "allowlist", "block_all"];
+
+#[derive(sqlx::FromRow)]
+struct LinkFilterDomainRow {
+    id: i64,
+    guild_id: i64,
+    domain: String,
+    list_kind: String,
+    created_at: i64,
+}
+
+// ---------------------------------------------------------------------------
+// Config CRUD
+// ---------------------------------------------------------------------------
+
+pub async fn get_link_filter_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<LinkFilterConfig>> {
+    Ok(get_guild_config(db, guild_id).await?.link_filter)
+}
+
+pub async fn set_link_filter_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO link_filter_config (guild_id, enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_link_filter(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_link_filter_mode(db: &Database, guild_id: u64, mode: &str) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO link_filter_config (guild_id, mode)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET mode = EXCLUDED.mode",
+        guild_id_i64,
+        mode,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_link_filter(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_link_filter_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO link_filter_config (guild_id, action)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET action = EXCLUDED.action",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_link_filter(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Check whether the link filter is enabled for a guild and return the config.
+pub async fn get_link_filter_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<LinkFilterConfig>> {
+    let config = get_link_filter_config(db, guild_id).await?;
+    match config {
+        Some(cfg) if cfg.enabled => Ok(Some(cfg)),
+        _ => Ok(None),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Domain CRUD
+// ---------------------------------------------------------------------------
+
+/// Add or move a domain to the allow or deny list. Returns `true` if the
+/// domain was inserted or changed lists, `false` if it was already there.
+pub async fn set_link_filter_domain(
+    db: &Database,
+    guild_id: u64,
+    domain: &str,
+    list_kind: &str,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "INSERT INTO link_filter_domains (guild_id, domain, list_kind)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id, domain) DO UPDATE SET list_kind = EXCLUDED.list_kind
+         WHERE link_filter_domains.list_kind <> EXCLUDED.list_kind",
+        guild_id_i64,
+        domain,
+        list_kind,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_link_filter(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove a domain from either list. Returns `true` if removed.
+pub async fn remove_link_filter_domain(
+    db: &Database,
+    guild_id: u64,
+    domain: &str,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM link_filter_domains WHERE guild_id = $1 AND domain = $2",
+        guild_id_i64,
+        domain,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_link_filter(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// List all allowed and denied domains for a guild.
+pub async fn list_link_filter_domains(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<LinkFilterDomain>> {
+    let cache_key = link_filter_domains_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::LinkFilterDomains),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let rows = sqlx::query_as!(
+                    LinkFilterDomainRow,
+                    "SELECT id, guild_id, domain, list_kind, created_at
+                 FROM link_filter_domains
+                 WHERE guild_id = $1
+                 ORDER BY domain ASC",
+                    guild_id_i64,
+                )
+                .fetch_all(db.pool())
+                .await?;
+
+                rows.into_iter().map(to_link_filter_domain).collect()
+            },
+        )
+        .await
+}
+
+/// Return the first link host that violates the filter `mode`, if any.
+///
+/// `block_all` blocks every link, `allowlist` blocks every domain that is not
+/// allow-listed, and `denylist` blocks only denied domains.
+pub fn find_blocked_link<'a>(
+    mode: &str,
+    domains: &[LinkFilterDomain],
+    link_hosts: &'a [String],
+) -> Option<&'a String> {
+    let listed = |host: &str, kind: &str| {
+        domains
+            .iter()
+            .any(|entry| entry.list_kind == kind && domain_matches(host, &entry.domain))
+    };
+
+    link_hosts.iter().find(|host| match mode {
+        "block_all" => true,
+        "allowlist" => !listed(host, "allow"),
+        _ => listed(host, "deny"),
+    })
+}
+
+fn to_link_filter_domain(row: LinkFilterDomainRow) -> anyhow::Result<LinkFilterDomain> {
+    Ok(LinkFilterDomain {
+        id: u64::try_from(row.id).context("id row out of u64 range")?,
+        guild_id: u64::try_from(row.guild_id).context("guild_id row out of u64 range")?,
+        domain: row.domain,
+        list_kind: row.list_kind,
+        created_at: u64::try_from(row.created_at).context("created_at row out of u64 range")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_blocked_link;
+    use crate::model::link_filter::LinkFilterDomain;
+
+    fn entry(domain: &str, list_kind: &str) -> LinkFilterDomain {
+        LinkFilterDomain {
+            id: 1,
+            guild_id: 1,
+            domain: domain.to_owned(),
+            list_kind: list_kind.to_owned(),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn modes_decide_which_links_are_blocked() {
+        let domains = vec![entry("youtube.com", "allow"), entry("bad.example", "deny")];
+        let hosts = vec!["www.youtube.com".to_owned(), "cdn.bad.example".to_owned()];
+        let other = vec!["site.org".to_owned()];
+
+        assert_eq!(
+            find_blocked_link("denylist", &domains, &hosts),
+            Some(&hosts[1])
+        );
+        assert_eq!(find_blocked_link("denylist", &domains, &other), None);
+        assert_eq!(
+            find_blocked_link("allowlist", &domains, &other),
+            Some(&other[0])
+        );
+        assert_eq!(find_blocked_link("allowlist", &domains, &hosts[..1]), None);
+        assert_eq!(
+            find_blocked_link("block_all", &domains, &hosts),
+            Some(&hosts[0])
+        );
+    }
+}
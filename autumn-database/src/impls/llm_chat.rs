@@ -2,6 +2,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Context as _;
 
+use crate::cache::invalidate_llm_replies;
 use crate::{database::Database, model::llm_chat::LlmChatEntry};
 
 #[derive(sqlx::FromRow)]
@@ -27,17 +28,17 @@ pub async fn insert_llm_chat_message(
     let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
     let created_at_i64 = i64::try_from(now_unix_secs()).context("created_at out of i64 range")?;
 
-    sqlx::query(
-           "INSERT INTO llm_chat_history (guild_id, channel_id, user_id, display_name, role, content, created_at)
+    sqlx::query!(
+        "INSERT INTO llm_chat_history (guild_id, channel_id, user_id, display_name, role, content, created_at)
             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        guild_id_i64,
+        channel_id_i64,
+        user_id_i64,
+        display_name,
+        role,
+        content,
+        created_at_i64,
     )
-    .bind(guild_id_i64)
-    .bind(channel_id_i64)
-    .bind(user_id_i64)
-        .bind(display_name)
-    .bind(role)
-    .bind(content)
-    .bind(created_at_i64)
     .execute(db.pool())
     .await?;
 
@@ -54,16 +55,17 @@ pub async fn list_recent_llm_chat_messages(
     let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
     let limit_i64 = i64::from(limit.clamp(1, 200));
 
-    let rows: Vec<LlmChatRow> = sqlx::query_as(
+    let rows: Vec<LlmChatRow> = sqlx::query_as!(
+        LlmChatRow,
         "SELECT user_id, display_name, role, content, created_at
          FROM llm_chat_history
          WHERE guild_id = $1 AND channel_id = $2
          ORDER BY created_at DESC, id DESC
          LIMIT $3",
+        guild_id_i64,
+        channel_id_i64,
+        limit_i64,
     )
-    .bind(guild_id_i64)
-    .bind(channel_id_i64)
-    .bind(limit_i64)
     .fetch_all(db.pool())
     .await?;
 
@@ -81,6 +83,30 @@ pub async fn list_recent_llm_chat_messages(
     Ok(out)
 }
 
+/// Forget a channel's conversation and its cached replies so the next mention
+/// starts fresh. Returns how many messages were removed.
+pub async fn clear_llm_chat_history(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<u64> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    let deleted = sqlx::query!(
+        "DELETE FROM llm_chat_history WHERE guild_id = $1 AND channel_id = $2",
+        guild_id_i64,
+        channel_id_i64,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    invalidate_llm_replies(db.cache(), guild_id, channel_id).await?;
+
+    Ok(deleted)
+}
+
 fn now_unix_secs() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1,12 +1,46 @@
 pub mod ai_config;
+pub mod antiraid;
+pub mod antispam;
+pub mod attachment_filter;
+pub mod backup;
+pub mod ban_sync;
+pub mod caps_filter;
 pub mod cases;
+pub mod command_aliases;
+pub mod command_usage;
+pub mod disabled_commands;
 pub mod escalation;
+pub mod exemptions;
+pub mod global_blacklist;
+pub mod guild_config;
+pub mod image_filter;
+pub mod invite_filter;
 pub mod leveling;
+pub mod link_filter;
 pub mod llm_chat;
+pub mod moderation_settings;
 pub mod modlog_config;
+pub mod modlog_outbox;
+pub mod modlog_template;
+pub mod modmail;
+pub mod name_filter;
 pub mod notes;
+pub mod permission_alerts;
+pub mod permission_levels;
+pub mod quarantine;
+pub mod raidmode;
 pub mod rate_limit;
+pub mod recurring_tasks;
+pub mod retention;
+pub mod scheduled_jobs;
+pub mod serverlog_config;
+pub mod shadow_mutes;
+pub mod snipe;
 pub mod user_logs;
+pub mod user_reports;
 pub mod userlog_config;
+pub mod warning_decay;
 pub mod warnings;
+pub mod watchlist;
+pub mod webhooks;
 pub mod word_filter;
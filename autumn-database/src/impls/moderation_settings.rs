@@ -0,0 +1,121 @@
+use anyhow::Context as _;
+
+use crate::cache::invalidate_guild_config;
+use crate::database::Database;
+use crate::impls::global_blacklist::BLACKLIST_ACTION_BAN;
+use crate::impls::guild_config::get_guild_config;
+
+/// Whether moderators are kept from actioning members whose highest role is
+/// equal to or above their own.
+pub async fn get_enforce_mod_hierarchy(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    Ok(get_guild_config(db, guild_id).await?.enforce_mod_hierarchy)
+}
+
+pub async fn set_enforce_mod_hierarchy(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_moderation_settings (guild_id, enforce_hierarchy)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enforce_hierarchy = EXCLUDED.enforce_hierarchy",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// The guild's embed accent color, if it set one.
+pub async fn get_embed_color(db: &Database, guild_id: u64) -> anyhow::Result<Option<u32>> {
+    Ok(get_guild_config(db, guild_id).await?.embed_color)
+}
+
+/// Set the embed accent color; `None` restores the default.
+pub async fn set_embed_color(
+    db: &Database,
+    guild_id: u64,
+    color: Option<u32>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let color_i32 = color
+        .map(i32::try_from)
+        .transpose()
+        .context("color out of i32 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_moderation_settings (guild_id, embed_color)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET embed_color = EXCLUDED.embed_color",
+        guild_id_i64,
+        color_i32,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// What the guild does when a globally blacklisted user joins. Defaults to a
+/// ban.
+pub async fn get_global_blacklist_action(db: &Database, guild_id: u64) -> anyhow::Result<String> {
+    Ok(get_guild_config(db, guild_id)
+        .await?
+        .global_blacklist_action
+        .unwrap_or_else(|| BLACKLIST_ACTION_BAN.to_owned()))
+}
+
+pub async fn set_global_blacklist_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_moderation_settings (guild_id, global_blacklist_action)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE
+         SET global_blacklist_action = EXCLUDED.global_blacklist_action",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Whether bans and terminations need a second moderator's approval.
+pub async fn get_dual_approval(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    Ok(get_guild_config(db, guild_id).await?.dual_approval)
+}
+
+pub async fn set_dual_approval(db: &Database, guild_id: u64, enabled: bool) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_moderation_settings (guild_id, dual_approval)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET dual_approval = EXCLUDED.dual_approval",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
@@ -1,28 +1,11 @@
 use anyhow::Context as _;
 
-use crate::cache::{CONFIG_CACHE_TTL, invalidate_modlog_config, modlog_config_key};
+use crate::cache::invalidate_guild_config;
 use crate::database::Database;
+use crate::impls::guild_config::get_guild_config;
 
 pub async fn get_modlog_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<Option<u64>> {
-    let cache_key = modlog_config_key(db.cache(), guild_id);
-    db.cache()
-        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
-            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
-
-            let channel_id: Option<i64> = sqlx::query_scalar(
-                "SELECT modlog_channel_id FROM guild_mod_config WHERE guild_id = $1",
-            )
-            .bind(guild_id_i64)
-            .fetch_optional(db.pool())
-            .await?
-            .flatten();
-
-            channel_id
-                .map(u64::try_from)
-                .transpose()
-                .context("modlog_channel_id out of u64 range")
-        })
-        .await
+    Ok(get_guild_config(db, guild_id).await?.modlog_channel_id)
 }
 
 pub async fn set_modlog_channel_id(
@@ -33,17 +16,26 @@ pub async fn set_modlog_channel_id(
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
     let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO guild_mod_config (guild_id, modlog_channel_id)
          VALUES ($1, $2)
-         ON CONFLICT (guild_id) DO UPDATE SET modlog_channel_id = EXCLUDED.modlog_channel_id",
+         ON CONFLICT (guild_id) DO UPDATE SET
+            modlog_channel_id = EXCLUDED.modlog_channel_id,
+            modlog_webhook_id = CASE
+                WHEN guild_mod_config.modlog_channel_id = EXCLUDED.modlog_channel_id
+                THEN guild_mod_config.modlog_webhook_id
+            END,
+            modlog_webhook_token = CASE
+                WHEN guild_mod_config.modlog_channel_id = EXCLUDED.modlog_channel_id
+                THEN guild_mod_config.modlog_webhook_token
+            END",
+        guild_id_i64,
+        channel_id_i64,
     )
-    .bind(guild_id_i64)
-    .bind(channel_id_i64)
     .execute(db.pool())
     .await?;
 
-    invalidate_modlog_config(db.cache(), guild_id).await?;
+    invalidate_guild_config(db.cache(), guild_id).await?;
 
     Ok(())
 }
@@ -51,12 +43,147 @@ pub async fn set_modlog_channel_id(
 pub async fn clear_modlog_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query("DELETE FROM guild_mod_config WHERE guild_id = $1")
-        .bind(guild_id_i64)
-        .execute(db.pool())
-        .await?;
+    sqlx::query!(
+        "DELETE FROM guild_mod_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Whether purges attach a transcript of the deleted messages to their modlog entry.
+pub async fn get_purge_transcripts_enabled(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    Ok(get_guild_config(db, guild_id).await?.purge_transcripts)
+}
+
+/// Toggle purge transcripts. Returns `false` when no modlog channel is
+/// configured, since transcripts are posted there.
+pub async fn set_purge_transcripts_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "UPDATE guild_mod_config SET purge_transcripts = $2
+         WHERE guild_id = $1 AND modlog_channel_id IS NOT NULL",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
 
-    invalidate_modlog_config(db.cache(), guild_id).await?;
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// How long an `open` case may wait before the modlog gets a review reminder.
+pub async fn get_case_review_sla_seconds(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<u64>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let sla = sqlx::query_scalar!(
+        "SELECT case_review_sla_seconds FROM guild_mod_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?
+    .flatten();
+
+    sla.map(u64::try_from)
+        .transpose()
+        .context("case_review_sla_seconds out of u64 range")
+}
+
+/// Set or clear the case review window. Returns `false` when no modlog
+/// channel is configured, since reminders are posted there.
+pub async fn set_case_review_sla_seconds(
+    db: &Database,
+    guild_id: u64,
+    sla_seconds: Option<u64>,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let sla_seconds = sla_seconds
+        .map(i64::try_from)
+        .transpose()
+        .context("sla_seconds out of i64 range")?;
+
+    let result = sqlx::query!(
+        "UPDATE guild_mod_config SET case_review_sla_seconds = $2
+         WHERE guild_id = $1 AND modlog_channel_id IS NOT NULL",
+        guild_id_i64,
+        sla_seconds,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The webhook used to publish to the modlog channel, as `(webhook_id, token)`.
+pub async fn get_modlog_webhook(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<(u64, String)>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let row = sqlx::query!(
+        "SELECT modlog_webhook_id, modlog_webhook_token FROM guild_mod_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    let Some((Some(webhook_id), Some(token))) =
+        row.map(|row| (row.modlog_webhook_id, row.modlog_webhook_token))
+    else {
+        return Ok(None);
+    };
+
+    let webhook_id = u64::try_from(webhook_id).context("modlog_webhook_id out of u64 range")?;
+    Ok(Some((webhook_id, token)))
+}
+
+pub async fn set_modlog_webhook(
+    db: &Database,
+    guild_id: u64,
+    webhook_id: u64,
+    token: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let webhook_id_i64 = i64::try_from(webhook_id).context("webhook_id out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE guild_mod_config SET modlog_webhook_id = $2, modlog_webhook_token = $3
+         WHERE guild_id = $1",
+        guild_id_i64,
+        webhook_id_i64,
+        token,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_modlog_webhook(db: &Database, guild_id: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE guild_mod_config SET modlog_webhook_id = NULL, modlog_webhook_token = NULL
+         WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
 
     Ok(())
 }
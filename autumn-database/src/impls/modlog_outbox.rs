@@ -0,0 +1,196 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::cases::{NewCase, insert_case};
+use crate::impls::warnings::now_unix_secs;
+use crate::model::cases::CaseSummary;
+
+/// Deliveries are dropped after this many failed attempts.
+pub const MODLOG_OUTBOX_MAX_ATTEMPTS: i32 = 10;
+
+/// A text file sent along with a modlog message (e.g. a purge transcript).
+#[derive(Clone, Debug)]
+pub struct ModlogAttachment {
+    pub filename: String,
+    pub content: String,
+}
+
+/// A modlog message waiting to be delivered.
+#[derive(Clone, Debug)]
+pub struct ModlogOutboxEntry {
+    pub id: i64,
+    pub guild_id: u64,
+    pub channel_id: u64,
+    /// Serialized embed JSON.
+    pub payload: String,
+    pub attachment: Option<ModlogAttachment>,
+    pub attempts: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct ModlogOutboxRow {
+    id: i64,
+    guild_id: i64,
+    channel_id: i64,
+    payload: String,
+    attachment_name: Option<String>,
+    attachment_content: Option<String>,
+    attempts: i32,
+}
+
+/// Create a case and queue its modlog message in the same transaction, so the
+/// entry can't be lost if delivery fails. `render` builds the payload from
+/// the new case. Returns the case and the outbox entry id.
+pub async fn create_case_with_modlog_outbox<F>(
+    db: &Database,
+    new_case: NewCase<'_>,
+    channel_id: u64,
+    attachment: Option<ModlogAttachment>,
+    render: F,
+) -> anyhow::Result<(CaseSummary, i64)>
+where
+    F: FnOnce(&CaseSummary) -> anyhow::Result<String>,
+{
+    let guild_id_i64 = i64::try_from(new_case.guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let (case_id, case) = insert_case(&mut tx, new_case).await?;
+    let payload = render(&case)?;
+
+    let (attachment_name, attachment_content) = attachment
+        .map(|attachment| (attachment.filename, attachment.content))
+        .unzip();
+
+    let outbox_id: i64 = sqlx::query_scalar!(
+        "INSERT INTO modlog_outbox
+            (guild_id, case_id, channel_id, payload, attachment_name, attachment_content,
+             next_attempt_at, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
+         RETURNING id",
+        guild_id_i64,
+        case_id,
+        channel_id_i64,
+        payload,
+        attachment_name,
+        attachment_content,
+        now,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((case, outbox_id))
+}
+
+pub async fn get_modlog_outbox_entry(
+    db: &Database,
+    id: i64,
+) -> anyhow::Result<Option<ModlogOutboxEntry>> {
+    let row: Option<ModlogOutboxRow> = sqlx::query_as!(
+        ModlogOutboxRow,
+        "SELECT id, guild_id, channel_id, payload, attachment_name, attachment_content, attempts
+         FROM modlog_outbox
+         WHERE id = $1",
+        id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_outbox_entry).transpose()
+}
+
+/// Entries whose next attempt is due, oldest first.
+pub async fn list_due_modlog_outbox(
+    db: &Database,
+    limit: i64,
+) -> anyhow::Result<Vec<ModlogOutboxEntry>> {
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let rows: Vec<ModlogOutboxRow> = sqlx::query_as!(
+        ModlogOutboxRow,
+        "SELECT id, guild_id, channel_id, payload, attachment_name, attachment_content, attempts
+         FROM modlog_outbox
+         WHERE next_attempt_at <= $1 AND attempts < $2
+         ORDER BY id ASC
+         LIMIT $3",
+        now,
+        MODLOG_OUTBOX_MAX_ATTEMPTS,
+        limit,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_outbox_entry).collect()
+}
+
+/// Remove a delivered entry.
+pub async fn complete_modlog_outbox(db: &Database, id: i64) -> anyhow::Result<()> {
+    sqlx::query!("DELETE FROM modlog_outbox WHERE id = $1", id,)
+        .execute(db.pool())
+        .await?;
+
+    Ok(())
+}
+
+/// Record a failed attempt and schedule the next one with exponential backoff.
+pub async fn fail_modlog_outbox(
+    db: &Database,
+    id: i64,
+    attempts: i32,
+    error: &str,
+) -> anyhow::Result<()> {
+    let now = now_unix_secs();
+    let next_attempt_at = i64::try_from(now + modlog_outbox_backoff_secs(attempts))
+        .context("next_attempt_at out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE modlog_outbox
+         SET attempts = attempts + 1, next_attempt_at = $2, last_error = $3
+         WHERE id = $1",
+        id,
+        next_attempt_at,
+        error,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Delay before retrying after `attempts` earlier failures: 30s doubling up to an hour.
+pub fn modlog_outbox_backoff_secs(attempts: i32) -> u64 {
+    let exponent = u32::try_from(attempts.clamp(0, 7)).unwrap_or(0);
+    (30u64 << exponent).min(3600)
+}
+
+fn to_outbox_entry(row: ModlogOutboxRow) -> anyhow::Result<ModlogOutboxEntry> {
+    Ok(ModlogOutboxEntry {
+        id: row.id,
+        guild_id: u64::try_from(row.guild_id).context("guild_id row out of u64 range")?,
+        channel_id: u64::try_from(row.channel_id).context("channel_id row out of u64 range")?,
+        payload: row.payload,
+        attachment: row
+            .attachment_name
+            .zip(row.attachment_content)
+            .map(|(filename, content)| ModlogAttachment { filename, content }),
+        attempts: row.attempts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::modlog_outbox_backoff_secs;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        assert_eq!(modlog_outbox_backoff_secs(0), 30);
+        assert_eq!(modlog_outbox_backoff_secs(1), 60);
+        assert_eq!(modlog_outbox_backoff_secs(6), 1920);
+        assert_eq!(modlog_outbox_backoff_secs(7), 3600);
+        assert_eq!(modlog_outbox_backoff_secs(50), 3600);
+    }
+}
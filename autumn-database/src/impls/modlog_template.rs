@@ -0,0 +1,139 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, invalidate_modlog_template, modlog_template_key};
+use crate::database::Database;
+use crate::impls::moderation_settings::get_embed_color;
+use crate::model::modlog_template::ModlogTemplate;
+use autumn_utils::modlog_template::ModlogLayout;
+
+pub async fn get_modlog_template(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<ModlogTemplate>> {
+    let cache_key = modlog_template_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::ModlogTemplate),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let row = sqlx::query_as!(
+                    ModlogTemplate,
+                    "SELECT guild_id, field_order, color, footer \
+                 FROM modlog_templates WHERE guild_id = $1",
+                    guild_id_i64,
+                )
+                .fetch_optional(db.pool())
+                .await?;
+
+                Ok(row)
+            },
+        )
+        .await
+}
+
+/// The guild's modlog layout, if a template or embed color is set. The
+/// template's own color wins over the guild's embed color.
+pub async fn get_modlog_layout(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<ModlogLayout>> {
+    let template = get_modlog_template(db, guild_id).await?;
+    let embed_color = get_embed_color(db, guild_id).await?;
+
+    let mut layout = match (template, embed_color) {
+        (Some(template), _) => template.layout(),
+        (None, Some(_)) => ModlogLayout::default(),
+        (None, None) => return Ok(None),
+    };
+    layout.color = layout.color.or(embed_color);
+
+    Ok(Some(layout))
+}
+
+/// Set the field order; `None` restores the built-in layout.
+pub async fn set_modlog_template_fields(
+    db: &Database,
+    guild_id: u64,
+    fields: Option<&[String]>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let field_order = fields.map(|fields| fields.join(","));
+
+    sqlx::query!(
+        "INSERT INTO modlog_templates (guild_id, field_order) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET field_order = $2",
+        guild_id_i64,
+        field_order,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_modlog_template(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the embed color; `None` restores the default color.
+pub async fn set_modlog_template_color(
+    db: &Database,
+    guild_id: u64,
+    color: Option<u32>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let color_i32 = color
+        .map(i32::try_from)
+        .transpose()
+        .context("color out of i32 range")?;
+
+    sqlx::query!(
+        "INSERT INTO modlog_templates (guild_id, color) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET color = $2",
+        guild_id_i64,
+        color_i32,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_modlog_template(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the footer text; `None` removes the footer.
+pub async fn set_modlog_template_footer(
+    db: &Database,
+    guild_id: u64,
+    footer: Option<&str>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO modlog_templates (guild_id, footer) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET footer = $2",
+        guild_id_i64,
+        footer,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_modlog_template(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn reset_modlog_template(db: &Database, guild_id: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "DELETE FROM modlog_templates WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_modlog_template(db.cache(), guild_id).await?;
+
+    Ok(())
+}
@@ -0,0 +1,332 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+const CONVERSATION_OPEN: &str = "open";
+const CONVERSATION_CLOSED: &str = "closed";
+
+#[derive(Clone, Debug)]
+pub struct ModmailConversation {
+    pub id: i64,
+    pub guild_id: u64,
+    pub user_id: u64,
+    /// `None` while the staff thread is still being created.
+    pub thread_id: Option<u64>,
+    pub opened_at: u64,
+}
+
+pub struct NewModmailMessage<'a> {
+    pub conversation_id: i64,
+    pub author_user_id: u64,
+    pub author_name: &'a str,
+    pub from_staff: bool,
+    pub content: &'a str,
+    pub attachments: &'a [String],
+}
+
+#[derive(Clone, Debug)]
+pub struct ModmailMessage {
+    pub author_user_id: u64,
+    pub author_name: String,
+    pub from_staff: bool,
+    pub content: String,
+    pub attachments: Vec<String>,
+    pub created_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct ConversationRow {
+    id: i64,
+    guild_id: i64,
+    user_id: i64,
+    thread_id: Option<i64>,
+    opened_at: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct MessageRow {
+    author_user_id: i64,
+    author_name: String,
+    from_staff: bool,
+    content: String,
+    attachments: String,
+    created_at: i64,
+}
+
+pub async fn get_modmail_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<Option<u64>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let channel_id: Option<i64> = sqlx::query_scalar!(
+        "SELECT channel_id FROM guild_modmail_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    channel_id
+        .map(u64::try_from)
+        .transpose()
+        .context("channel_id out of u64 range")
+}
+
+pub async fn set_modmail_channel_id(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_modmail_config (guild_id, channel_id)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET channel_id = EXCLUDED.channel_id",
+        guild_id_i64,
+        channel_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_modmail_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "DELETE FROM guild_modmail_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Every guild taking modmail, with its staff channel.
+pub async fn list_modmail_channels(db: &Database) -> anyhow::Result<Vec<(u64, u64)>> {
+    let rows = sqlx::query!("SELECT guild_id, channel_id FROM guild_modmail_config")
+        .fetch_all(db.pool())
+        .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok((
+                u64::try_from(row.guild_id).context("negative guild_id")?,
+                u64::try_from(row.channel_id).context("negative channel_id")?,
+            ))
+        })
+        .collect()
+}
+
+/// Start a conversation before its thread exists. Returns `None` when the
+/// user already has one open.
+pub async fn open_modmail_conversation(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<Option<i64>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let id = sqlx::query_scalar!(
+        "INSERT INTO modmail_conversations (guild_id, user_id, status, opened_at)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (user_id) WHERE status = 'open' DO NOTHING
+         RETURNING id",
+        guild_id,
+        user_id,
+        CONVERSATION_OPEN,
+        now,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn set_modmail_thread(db: &Database, id: i64, thread_id: u64) -> anyhow::Result<()> {
+    let thread_id = i64::try_from(thread_id).context("thread_id out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE modmail_conversations SET thread_id = $1 WHERE id = $2",
+        thread_id,
+        id,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Drop a conversation whose thread couldn't be created.
+pub async fn delete_modmail_conversation(db: &Database, id: i64) -> anyhow::Result<()> {
+    sqlx::query!("DELETE FROM modmail_conversations WHERE id = $1", id)
+        .execute(db.pool())
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_open_conversation_for_user(
+    db: &Database,
+    user_id: u64,
+) -> anyhow::Result<Option<ModmailConversation>> {
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let row: Option<ConversationRow> = sqlx::query_as!(
+        ConversationRow,
+        "SELECT id, guild_id, user_id, thread_id, opened_at
+         FROM modmail_conversations
+         WHERE user_id = $1 AND status = $2",
+        user_id,
+        CONVERSATION_OPEN,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_conversation).transpose()
+}
+
+pub async fn get_open_conversation_for_thread(
+    db: &Database,
+    guild_id: u64,
+    thread_id: u64,
+) -> anyhow::Result<Option<ModmailConversation>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let thread_id = i64::try_from(thread_id).context("thread_id out of i64 range")?;
+
+    let row: Option<ConversationRow> = sqlx::query_as!(
+        ConversationRow,
+        "SELECT id, guild_id, user_id, thread_id, opened_at
+         FROM modmail_conversations
+         WHERE guild_id = $1 AND thread_id = $2 AND status = $3",
+        guild_id,
+        thread_id,
+        CONVERSATION_OPEN,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_conversation).transpose()
+}
+
+pub async fn count_open_conversations(db: &Database, guild_id: u64) -> anyhow::Result<u64> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM modmail_conversations
+           WHERE guild_id = $1 AND status = $2"#,
+        guild_id,
+        CONVERSATION_OPEN,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok(u64::try_from(count).unwrap_or(0))
+}
+
+/// Returns `false` if the conversation was already closed.
+pub async fn close_modmail_conversation(
+    db: &Database,
+    id: i64,
+    closed_by: u64,
+) -> anyhow::Result<bool> {
+    let closed_by = i64::try_from(closed_by).context("closed_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let updated = sqlx::query!(
+        "UPDATE modmail_conversations
+         SET status = $1, closed_at = $2, closed_by = $3
+         WHERE id = $4 AND status = $5",
+        CONVERSATION_CLOSED,
+        now,
+        closed_by,
+        id,
+        CONVERSATION_OPEN,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+pub async fn record_modmail_message(
+    db: &Database,
+    message: NewModmailMessage<'_>,
+) -> anyhow::Result<()> {
+    let author_user_id =
+        i64::try_from(message.author_user_id).context("author_user_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO modmail_messages (
+            conversation_id, author_user_id, author_name, from_staff,
+            content, attachments, created_at
+         ) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        message.conversation_id,
+        author_user_id,
+        message.author_name,
+        message.from_staff,
+        message.content,
+        message.attachments.join("\n"),
+        now,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// A conversation's messages, oldest first.
+pub async fn list_modmail_messages(
+    db: &Database,
+    conversation_id: i64,
+) -> anyhow::Result<Vec<ModmailMessage>> {
+    let rows: Vec<MessageRow> = sqlx::query_as!(
+        MessageRow,
+        "SELECT author_user_id, author_name, from_staff, content, attachments, created_at
+         FROM modmail_messages
+         WHERE conversation_id = $1
+         ORDER BY id ASC",
+        conversation_id,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ModmailMessage {
+                author_user_id: u64::try_from(row.author_user_id)
+                    .context("negative author_user_id")?,
+                author_name: row.author_name,
+                from_staff: row.from_staff,
+                content: row.content,
+                attachments: row
+                    .attachments
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_owned)
+                    .collect(),
+                created_at: u64::try_from(row.created_at).context("negative created_at")?,
+            })
+        })
+        .collect()
+}
+
+fn to_conversation(row: ConversationRow) -> anyhow::Result<ModmailConversation> {
+    Ok(ModmailConversation {
+        id: row.id,
+        guild_id: u64::try_from(row.guild_id).context("negative guild_id")?,
+        user_id: u64::try_from(row.user_id).context("negative user_id")?,
+        thread_id: row
+            .thread_id
+            .map(u64::try_from)
+            .transpose()
+            .context("negative thread_id")?,
+        opened_at: u64::try_from(row.opened_at).context("negative opened_at")?,
+    })
+}
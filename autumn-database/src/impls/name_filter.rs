@@ -0,0 +1,106 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, invalidate_name_filter_config, name_filter_config_key};
+use crate::database::Database;
+use crate::model::name_filter::NameFilterConfig;
+
+pub const NAME_FILTER_ACTIONS: &[&str] = &["rename", "kick", "log"];
+
+pub async fn get_name_filter_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<NameFilterConfig>> {
+    let cache_key = name_filter_config_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::NameFilterConfig),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let row = sqlx::query_as!(
+                    NameFilterConfig,
+                    "SELECT guild_id, enabled, action, placeholder \
+                 FROM name_filter_config WHERE guild_id = $1",
+                    guild_id_i64,
+                )
+                .fetch_optional(db.pool())
+                .await?;
+
+                Ok(row)
+            },
+        )
+        .await
+}
+
+/// Get the name filter config only if it is enabled.
+pub async fn get_name_filter_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<NameFilterConfig>> {
+    let row = get_name_filter_config(db, guild_id).await?;
+    Ok(row.filter(|cfg| cfg.enabled))
+}
+
+pub async fn set_name_filter_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO name_filter_config (guild_id, enabled) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = $2",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_name_filter_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_name_filter_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO name_filter_config (guild_id, action) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET action = $2",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_name_filter_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_name_filter_placeholder(
+    db: &Database,
+    guild_id: u64,
+    placeholder: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO name_filter_config (guild_id, placeholder) VALUES ($1, $2) \
+         ON CONFLICT (guild_id) DO UPDATE SET placeholder = $2",
+        guild_id_i64,
+        placeholder,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_name_filter_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
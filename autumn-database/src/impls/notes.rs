@@ -28,7 +28,8 @@ pub async fn add_user_note(
         i64::try_from(author_user_id).context("author_user_id out of i64 range")?;
     let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
 
-    let row: UserNoteRow = sqlx::query_as(
+    let row: UserNoteRow = sqlx::query_as!(
+        UserNoteRow,
         "INSERT INTO user_notes (
             guild_id,
             target_user_id,
@@ -38,12 +39,12 @@ pub async fn add_user_note(
             updated_at
          ) VALUES ($1, $2, $3, $4, $5, $5)
          RETURNING id, guild_id, target_user_id, author_user_id, content, created_at, updated_at, deleted_at",
+        guild_id_i64,
+        target_user_id_i64,
+        author_user_id_i64,
+        content,
+        now,
     )
-    .bind(guild_id_i64)
-    .bind(target_user_id_i64)
-    .bind(author_user_id_i64)
-    .bind(content)
-    .bind(now)
     .fetch_one(db.pool())
     .await?;
 
@@ -59,14 +60,15 @@ pub async fn list_user_notes(
     let target_user_id_i64 =
         i64::try_from(target_user_id).context("target_user_id out of i64 range")?;
 
-    let rows: Vec<UserNoteRow> = sqlx::query_as(
+    let rows: Vec<UserNoteRow> = sqlx::query_as!(
+        UserNoteRow,
         "SELECT id, guild_id, target_user_id, author_user_id, content, created_at, updated_at, deleted_at
          FROM user_notes
          WHERE guild_id = $1 AND target_user_id = $2 AND deleted_at IS NULL
          ORDER BY created_at DESC, id DESC",
+        guild_id_i64,
+        target_user_id_i64,
     )
-    .bind(guild_id_i64)
-    .bind(target_user_id_i64)
     .fetch_all(db.pool())
     .await?;
 
@@ -81,13 +83,14 @@ pub async fn get_user_note(
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
     let note_id_i64 = i64::try_from(note_id).context("note_id out of i64 range")?;
 
-    let row: Option<UserNoteRow> = sqlx::query_as(
+    let row: Option<UserNoteRow> = sqlx::query_as!(
+        UserNoteRow,
         "SELECT id, guild_id, target_user_id, author_user_id, content, created_at, updated_at, deleted_at
          FROM user_notes
          WHERE guild_id = $1 AND id = $2 AND deleted_at IS NULL",
+        guild_id_i64,
+        note_id_i64,
     )
-    .bind(guild_id_i64)
-    .bind(note_id_i64)
     .fetch_optional(db.pool())
     .await?;
 
@@ -104,15 +107,15 @@ pub async fn edit_user_note(
     let note_id_i64 = i64::try_from(note_id).context("note_id out of i64 range")?;
     let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
 
-    let updated = sqlx::query(
+    let updated = sqlx::query!(
         "UPDATE user_notes
          SET content = $1, updated_at = $2
          WHERE guild_id = $3 AND id = $4 AND deleted_at IS NULL",
+        content,
+        now,
+        guild_id_i64,
+        note_id_i64,
     )
-    .bind(content)
-    .bind(now)
-    .bind(guild_id_i64)
-    .bind(note_id_i64)
     .execute(db.pool())
     .await?
     .rows_affected();
@@ -125,14 +128,14 @@ pub async fn delete_user_note(db: &Database, guild_id: u64, note_id: u64) -> any
     let note_id_i64 = i64::try_from(note_id).context("note_id out of i64 range")?;
     let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
 
-    let updated = sqlx::query(
+    let updated = sqlx::query!(
         "UPDATE user_notes
          SET deleted_at = $1, updated_at = $1
          WHERE guild_id = $2 AND id = $3 AND deleted_at IS NULL",
+        now,
+        guild_id_i64,
+        note_id_i64,
     )
-    .bind(now)
-    .bind(guild_id_i64)
-    .bind(note_id_i64)
     .execute(db.pool())
     .await?
     .rows_affected();
@@ -150,14 +153,14 @@ pub async fn clear_user_notes(
         i64::try_from(target_user_id).context("target_user_id out of i64 range")?;
     let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
 
-    let updated = sqlx::query(
+    let updated = sqlx::query!(
         "UPDATE user_notes
          SET deleted_at = $1, updated_at = $1
          WHERE guild_id = $2 AND target_user_id = $3 AND deleted_at IS NULL",
+        now,
+        guild_id_i64,
+        target_user_id_i64,
     )
-    .bind(now)
-    .bind(guild_id_i64)
-    .bind(target_user_id_i64)
     .execute(db.pool())
     .await?
     .rows_affected();
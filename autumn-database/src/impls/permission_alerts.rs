@@ -0,0 +1,56 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+
+pub async fn get_permission_alert_channel_id(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<u64>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let channel_id: Option<i64> = sqlx::query_scalar!(
+        "SELECT channel_id FROM guild_permission_alert_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    channel_id
+        .map(u64::try_from)
+        .transpose()
+        .context("channel_id out of u64 range")
+}
+
+pub async fn set_permission_alert_channel_id(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_permission_alert_config (guild_id, channel_id)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET channel_id = EXCLUDED.channel_id",
+        guild_id_i64,
+        channel_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_permission_alert_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "DELETE FROM guild_permission_alert_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, invalidate_permission_levels, permission_levels_key};
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::permission_levels::{PermissionLevel, PermissionLevelRole};
+
+/// Grant `level` to a role, replacing any level it had.
+pub async fn set_role_permission_level(
+    db: &Database,
+    guild_id: u64,
+    role_id: u64,
+    level: PermissionLevel,
+    created_by: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let role_id_i64 = i64::try_from(role_id).context("role_id out of i64 range")?;
+    let created_by_i64 = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO permission_level_roles (guild_id, role_id, level, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (guild_id, role_id)
+         DO UPDATE SET level = EXCLUDED.level, created_by = EXCLUDED.created_by,
+                       created_at = EXCLUDED.created_at",
+        guild_id_i64,
+        role_id_i64,
+        level.as_str(),
+        created_by_i64,
+        now,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_permission_levels(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Take a role's level away. Returns `true` if it had one.
+pub async fn remove_role_permission_level(
+    db: &Database,
+    guild_id: u64,
+    role_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let role_id_i64 = i64::try_from(role_id).context("role_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM permission_level_roles WHERE guild_id = $1 AND role_id = $2",
+        guild_id_i64,
+        role_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_permission_levels(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Every role with a level in the guild, highest level first (cached; read
+/// on each permission check).
+pub async fn list_permission_levels(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<PermissionLevelRole>> {
+    let cache_key = permission_levels_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::PermissionLevels),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let rows = sqlx::query!(
+                    "SELECT role_id, level, created_by, created_at
+                     FROM permission_level_roles
+                     WHERE guild_id = $1
+                     ORDER BY created_at, role_id",
+                    guild_id_i64,
+                )
+                .fetch_all(db.pool())
+                .await?;
+
+                let mut levels = rows
+                    .into_iter()
+                    .map(|row| {
+                        Ok(PermissionLevelRole {
+                            role_id: u64::try_from(row.role_id)
+                                .context("role_id row out of u64 range")?,
+                            level: PermissionLevel::parse(&row.level).with_context(|| {
+                                format!("unknown permission level `{}`", row.level)
+                            })?,
+                            created_by: u64::try_from(row.created_by)
+                                .context("created_by row out of u64 range")?,
+                            created_at: u64::try_from(row.created_at)
+                                .context("created_at row out of u64 range")?,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                levels.sort_by_key(|entry| std::cmp::Reverse(entry.level));
+                Ok(levels)
+            },
+        )
+        .await
+}
+
+/// The highest level granted by any of `role_ids`.
+pub fn highest_permission_level(
+    levels: &[PermissionLevelRole],
+    role_ids: &[u64],
+) -> Option<PermissionLevel> {
+    levels
+        .iter()
+        .filter(|entry| role_ids.contains(&entry.role_id))
+        .map(|entry| entry.level)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(role_id: u64, level: PermissionLevel) -> PermissionLevelRole {
+        PermissionLevelRole {
+            role_id,
+            level,
+            created_by: 1,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn highest_level_picks_the_strongest_held_role() {
+        let levels = [
+            entry(10, PermissionLevel::Helper),
+            entry(20, PermissionLevel::Admin),
+            entry(30, PermissionLevel::Mod),
+        ];
+
+        assert_eq!(
+            highest_permission_level(&levels, &[10, 30]),
+            Some(PermissionLevel::Mod)
+        );
+        assert_eq!(
+            highest_permission_level(&levels, &[30, 20]),
+            Some(PermissionLevel::Admin)
+        );
+        assert_eq!(highest_permission_level(&levels, &[40]), None);
+        assert_eq!(highest_permission_level(&[], &[10]), None);
+    }
+
+    #[test]
+    fn levels_parse_from_names_and_aliases() {
+        assert_eq!(
+            PermissionLevel::parse(" Moderator "),
+            Some(PermissionLevel::Mod)
+        );
+        for level in PermissionLevel::ALL {
+            assert_eq!(PermissionLevel::parse(level.as_str()), Some(level));
+        }
+        assert_eq!(PermissionLevel::parse("owner"), None);
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+#[derive(Clone, Debug)]
+pub struct Quarantine {
+    pub user_id: u64,
+    /// Roles stripped on quarantine, restored on release.
+    pub role_ids: Vec<u64>,
+    pub reason: String,
+    pub moderator_id: u64,
+    pub created_at: u64,
+}
+
+pub struct NewQuarantine<'a> {
+    pub guild_id: u64,
+    pub user_id: u64,
+    pub role_ids: &'a [u64],
+    pub reason: &'a str,
+    pub moderator_id: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct QuarantineRow {
+    user_id: i64,
+    role_ids: Vec<i64>,
+    reason: String,
+    moderator_id: i64,
+    created_at: i64,
+}
+
+pub async fn get_quarantine_role_id(db: &Database, guild_id: u64) -> anyhow::Result<Option<u64>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let role_id: Option<i64> = sqlx::query_scalar!(
+        "SELECT role_id FROM guild_quarantine_config WHERE guild_id = $1",
+        guild_id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    role_id
+        .map(u64::try_from)
+        .transpose()
+        .context("role_id out of u64 range")
+}
+
+pub async fn set_quarantine_role_id(
+    db: &Database,
+    guild_id: u64,
+    role_id: u64,
+) -> anyhow::Result<()> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let role_id = i64::try_from(role_id).context("role_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_quarantine_config (guild_id, role_id)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET role_id = EXCLUDED.role_id",
+        guild_id,
+        role_id,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Record a quarantine. Returns `false` when the member is already
+/// quarantined, leaving the stored roles untouched.
+pub async fn create_quarantine(
+    db: &Database,
+    quarantine: NewQuarantine<'_>,
+) -> anyhow::Result<bool> {
+    let guild_id = i64::try_from(quarantine.guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(quarantine.user_id).context("user_id out of i64 range")?;
+    let moderator_id =
+        i64::try_from(quarantine.moderator_id).context("moderator_id out of i64 range")?;
+    let role_ids = quarantine
+        .role_ids
+        .iter()
+        .map(|role_id| i64::try_from(*role_id).context("role_id out of i64 range"))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let inserted = sqlx::query!(
+        "INSERT INTO quarantines (guild_id, user_id, role_ids, reason, moderator_id, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (guild_id, user_id) DO NOTHING",
+        guild_id,
+        user_id,
+        &role_ids,
+        quarantine.reason,
+        moderator_id,
+        now,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    Ok(inserted > 0)
+}
+
+pub async fn get_quarantine(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<Option<Quarantine>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let row: Option<QuarantineRow> = sqlx::query_as!(
+        QuarantineRow,
+        "SELECT user_id, role_ids, reason, moderator_id, created_at
+         FROM quarantines
+         WHERE guild_id = $1 AND user_id = $2",
+        guild_id,
+        user_id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_quarantine).transpose()
+}
+
+/// Returns `false` if the member wasn't quarantined.
+pub async fn delete_quarantine(db: &Database, guild_id: u64, user_id: u64) -> anyhow::Result<bool> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let deleted = sqlx::query!(
+        "DELETE FROM quarantines WHERE guild_id = $1 AND user_id = $2",
+        guild_id,
+        user_id,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    Ok(deleted > 0)
+}
+
+fn to_quarantine(row: QuarantineRow) -> anyhow::Result<Quarantine> {
+    Ok(Quarantine {
+        user_id: u64::try_from(row.user_id).context("negative user_id")?,
+        role_ids: row
+            .role_ids
+            .into_iter()
+            .map(|role_id| u64::try_from(role_id).context("negative role_id"))
+            .collect::<anyhow::Result<_>>()?,
+        reason: row.reason,
+        moderator_id: u64::try_from(row.moderator_id).context("negative moderator_id")?,
+        created_at: u64::try_from(row.created_at).context("negative created_at")?,
+    })
+}
@@ -0,0 +1,84 @@
+use anyhow::Context as _;
+
+use crate::cache::{CacheTtlClass, invalidate_raidmode, raidmode_key};
+use crate::database::Database;
+use crate::model::raidmode::RaidmodeSnapshot;
+
+/// Get the raid mode snapshot, present only while raid mode is on.
+pub async fn get_raidmode_snapshot(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<RaidmodeSnapshot>> {
+    let cache_key = raidmode_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(
+            &cache_key,
+            db.cache().ttl(CacheTtlClass::Raidmode),
+            || async {
+                let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+                let row = sqlx::query_as!(
+                    RaidmodeSnapshot,
+                    "SELECT guild_id, enabled_by, enabled_at, verification_level, \
+                 invites_disabled, word_filter_enabled, word_filter_action \
+                 FROM raidmode_snapshots WHERE guild_id = $1",
+                    guild_id_i64,
+                )
+                .fetch_optional(db.pool())
+                .await?;
+
+                Ok(row)
+            },
+        )
+        .await
+}
+
+/// Store the pre-raid-mode settings. Returns `false` if raid mode is already on.
+pub async fn save_raidmode_snapshot(
+    db: &Database,
+    snapshot: &RaidmodeSnapshot,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        "INSERT INTO raidmode_snapshots \
+         (guild_id, enabled_by, enabled_at, verification_level, invites_disabled, \
+          word_filter_enabled, word_filter_action) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7) \
+         ON CONFLICT (guild_id) DO NOTHING",
+        snapshot.guild_id,
+        snapshot.enabled_by,
+        snapshot.enabled_at,
+        snapshot.verification_level,
+        snapshot.invites_disabled,
+        snapshot.word_filter_enabled,
+        &snapshot.word_filter_action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    let guild_id = u64::try_from(snapshot.guild_id).context("guild_id out of u64 range")?;
+    invalidate_raidmode(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove and return the snapshot, turning raid mode off.
+pub async fn take_raidmode_snapshot(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<RaidmodeSnapshot>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let row = sqlx::query_as!(
+        RaidmodeSnapshot,
+        "DELETE FROM raidmode_snapshots WHERE guild_id = $1 \
+         RETURNING guild_id, enabled_by, enabled_at, verification_level, \
+         invites_disabled, word_filter_enabled, word_filter_action",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    invalidate_raidmode(db.cache(), guild_id).await?;
+
+    Ok(row)
+}
@@ -1,4 +1,6 @@
-use crate::cache::llm_mention_rate_limit_key;
+use crate::cache::{
+    RateLimitDecision, RateLimiter, llm_mention_rate_limit_key, moderator_command_rate_limit_key,
+};
 use crate::database::Database;
 
 pub async fn llm_mention_within_limit(
@@ -9,14 +11,22 @@ pub async fn llm_mention_within_limit(
 ) -> anyhow::Result<bool> {
     let cache = db.cache();
     let key = llm_mention_rate_limit_key(cache, guild_id, channel_id, user_id);
-    let count = cache
-        .increment_with_window(&key, cache.llm_rate_limit_window())
+    let decision = cache
+        .check_rate_limit(&key, cache.rate_limit(RateLimiter::LlmMention))
         .await?;
-    let max_hits = cache.llm_rate_limit_max_hits();
 
-    if count > max_hits {
-        cache.record_rate_limit_block();
-    }
+    Ok(decision.allowed)
+}
 
-    Ok(count <= max_hits)
+/// Count a moderation command run by `user_id` against their per-guild limit.
+pub async fn check_moderator_command_limit(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<RateLimitDecision> {
+    let cache = db.cache();
+    let key = moderator_command_rate_limit_key(cache, guild_id, user_id);
+    cache
+        .check_rate_limit(&key, cache.rate_limit(RateLimiter::ModeratorCommand))
+        .await
 }
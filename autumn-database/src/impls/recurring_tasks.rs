@@ -0,0 +1,166 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::scheduled_jobs::JOB_RECURRING_TASK;
+use crate::impls::warnings::now_unix_secs;
+
+/// Post a summary of recent cases by action.
+pub const TASK_MODLOG_DIGEST: &str = "digest";
+/// Post case and command usage counts.
+pub const TASK_STATS_SUMMARY: &str = "stats";
+/// Clear unpinned messages from a channel.
+pub const TASK_AUTO_PURGE: &str = "purge";
+
+pub const RECURRING_TASK_KINDS: &[&str] =
+    &[TASK_MODLOG_DIGEST, TASK_STATS_SUMMARY, TASK_AUTO_PURGE];
+
+pub struct NewRecurringTask<'a> {
+    pub guild_id: u64,
+    pub kind: &'a str,
+    pub channel_id: u64,
+    /// A `Recurrence` in its display form, e.g. `weekly mon 09:00`.
+    pub schedule: &'a str,
+    pub created_by: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct RecurringTask {
+    pub id: i64,
+    pub guild_id: u64,
+    pub kind: String,
+    pub channel_id: u64,
+    pub schedule: String,
+    pub created_by: u64,
+    pub created_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct RecurringTaskRow {
+    id: i64,
+    guild_id: i64,
+    kind: String,
+    channel_id: i64,
+    schedule: String,
+    created_by: i64,
+    created_at: i64,
+}
+
+/// Create a task and schedule its first run in one transaction.
+pub async fn create_recurring_task(
+    db: &Database,
+    task: NewRecurringTask<'_>,
+    first_run_at: u64,
+) -> anyhow::Result<i64> {
+    let guild_id = i64::try_from(task.guild_id).context("guild_id out of i64 range")?;
+    let channel_id = i64::try_from(task.channel_id).context("channel_id out of i64 range")?;
+    let created_by = i64::try_from(task.created_by).context("created_by out of i64 range")?;
+    let first_run_at = i64::try_from(first_run_at).context("first_run_at out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let id = sqlx::query_scalar!(
+        "INSERT INTO recurring_tasks (guild_id, kind, channel_id, schedule, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING id",
+        guild_id,
+        task.kind,
+        channel_id,
+        task.schedule,
+        created_by,
+        now,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO scheduled_jobs (guild_id, kind, payload, run_at, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+        guild_id,
+        JOB_RECURRING_TASK,
+        id.to_string(),
+        first_run_at,
+        created_by,
+        now,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(id)
+}
+
+pub async fn list_recurring_tasks(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<RecurringTask>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let rows: Vec<RecurringTaskRow> = sqlx::query_as!(
+        RecurringTaskRow,
+        "SELECT id, guild_id, kind, channel_id, schedule, created_by, created_at
+         FROM recurring_tasks
+         WHERE guild_id = $1
+         ORDER BY id ASC",
+        guild_id,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_recurring_task).collect()
+}
+
+pub async fn get_recurring_task(db: &Database, id: i64) -> anyhow::Result<Option<RecurringTask>> {
+    let row: Option<RecurringTaskRow> = sqlx::query_as!(
+        RecurringTaskRow,
+        "SELECT id, guild_id, kind, channel_id, schedule, created_by, created_at
+         FROM recurring_tasks
+         WHERE id = $1",
+        id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_recurring_task).transpose()
+}
+
+/// Delete a task along with its pending run. Returns `false` if no such task
+/// exists in the guild.
+pub async fn delete_recurring_task(db: &Database, guild_id: u64, id: i64) -> anyhow::Result<bool> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let deleted = sqlx::query!(
+        "DELETE FROM recurring_tasks WHERE guild_id = $1 AND id = $2",
+        guild_id,
+        id,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query!(
+        "DELETE FROM scheduled_jobs WHERE guild_id = $1 AND kind = $2 AND payload = $3",
+        guild_id,
+        JOB_RECURRING_TASK,
+        id.to_string(),
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(deleted > 0)
+}
+
+fn to_recurring_task(row: RecurringTaskRow) -> anyhow::Result<RecurringTask> {
+    Ok(RecurringTask {
+        id: row.id,
+        guild_id: u64::try_from(row.guild_id).context("guild_id row out of u64 range")?,
+        kind: row.kind,
+        channel_id: u64::try_from(row.channel_id).context("channel_id row out of u64 range")?,
+        schedule: row.schedule,
+        created_by: u64::try_from(row.created_by).context("created_by row out of u64 range")?,
+        created_at: u64::try_from(row.created_at).context("created_at row out of u64 range")?,
+    })
+}
@@ -0,0 +1,394 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+use crate::cache::invalidate_guild_config;
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+/// Reason stored on cases once their subject has been anonymized.
+pub const REDACTED_REASON: &str = "[redacted]";
+
+pub const DEFAULT_USERLOG_RETENTION_DAYS: u64 = 90;
+pub const DEFAULT_LLM_CHAT_RETENTION_DAYS: u64 = 90;
+pub const DEFAULT_CASE_ANONYMIZE_AFTER_DAYS: u64 = 730;
+pub const DEFAULT_COMMAND_USAGE_RETENTION_DAYS: u64 = 90;
+
+/// How long personal data is kept. `None` keeps it indefinitely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// User log entries and message snapshots.
+    pub userlog_max_age: Option<Duration>,
+    /// LLM chat history.
+    pub llm_chat_max_age: Option<Duration>,
+    /// Cases older than this lose their target and reason.
+    pub case_anonymize_after: Option<Duration>,
+    /// Per-user command invocation records behind `!usage`.
+    pub command_usage_max_age: Option<Duration>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            userlog_max_age: retention_days(DEFAULT_USERLOG_RETENTION_DAYS),
+            llm_chat_max_age: retention_days(DEFAULT_LLM_CHAT_RETENTION_DAYS),
+            case_anonymize_after: retention_days(DEFAULT_CASE_ANONYMIZE_AFTER_DAYS),
+            command_usage_max_age: retention_days(DEFAULT_COMMAND_USAGE_RETENTION_DAYS),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.userlog_max_age.is_some()
+            || self.llm_chat_max_age.is_some()
+            || self.case_anonymize_after.is_some()
+            || self.command_usage_max_age.is_some()
+    }
+}
+
+/// A retention window of `days`, where `0` means keep forever.
+pub fn retention_days(days: u64) -> Option<Duration> {
+    (days > 0).then(|| Duration::from_secs(days.saturating_mul(24 * 60 * 60)))
+}
+
+/// A message whose snapshot was deleted, so its archived attachments can be
+/// removed too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotMessage {
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub message_id: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct SnapshotMessageRow {
+    guild_id: i64,
+    channel_id: i64,
+    message_id: i64,
+}
+
+/// Rows removed or anonymized by one [`apply_retention`] pass.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    pub user_logs: u64,
+    pub message_snapshots: Vec<SnapshotMessage>,
+    pub llm_chat_messages: u64,
+    pub anonymized_cases: u64,
+    pub command_invocations: u64,
+}
+
+/// Rows removed or anonymized by [`erase_user_data`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErasureReport {
+    pub warnings: u64,
+    pub notes: u64,
+    pub anonymized_cases: u64,
+    pub llm_chat_messages: u64,
+    pub user_logs: u64,
+    pub message_snapshots: Vec<SnapshotMessage>,
+    pub reports: u64,
+    pub modmail_conversations: u64,
+    pub watchlist_entries: u64,
+    pub command_invocations: u64,
+}
+
+impl ErasureReport {
+    pub fn total(&self) -> u64 {
+        self.warnings
+            + self.notes
+            + self.anonymized_cases
+            + self.llm_chat_messages
+            + self.user_logs
+            + self.message_snapshots.len() as u64
+            + self.reports
+            + self.modmail_conversations
+            + self.watchlist_entries
+            + self.command_invocations
+    }
+}
+
+/// Delete or anonymize everything older than the policy allows, across all
+/// guilds.
+pub async fn apply_retention(
+    db: &Database,
+    policy: &RetentionPolicy,
+) -> anyhow::Result<RetentionReport> {
+    let now = now_unix_secs();
+    let mut report = RetentionReport::default();
+
+    if let Some(cutoff) = cutoff(now, policy.userlog_max_age)? {
+        report.user_logs = sqlx::query!("DELETE FROM user_logs WHERE created_at < $1", cutoff)
+            .execute(db.pool())
+            .await?
+            .rows_affected();
+
+        let rows: Vec<SnapshotMessageRow> = sqlx::query_as!(
+            SnapshotMessageRow,
+            "DELETE FROM message_snapshots WHERE updated_at < $1
+             RETURNING guild_id, channel_id, message_id",
+            cutoff,
+        )
+        .fetch_all(db.pool())
+        .await?;
+        report.message_snapshots = rows
+            .into_iter()
+            .map(to_snapshot_message)
+            .collect::<anyhow::Result<_>>()?;
+    }
+
+    if let Some(cutoff) = cutoff(now, policy.llm_chat_max_age)? {
+        report.llm_chat_messages =
+            sqlx::query!("DELETE FROM llm_chat_history WHERE created_at < $1", cutoff,)
+                .execute(db.pool())
+                .await?
+                .rows_affected();
+    }
+
+    if let Some(cutoff) = cutoff(now, policy.case_anonymize_after)? {
+        let now_i64 = i64::try_from(now).context("now out of i64 range")?;
+        let anonymized = sqlx::query_scalar!(
+            r#"WITH anonymized AS (
+                UPDATE mod_cases
+                SET target_user_id = NULL, reason = $1, anonymized_at = $2, updated_at = $2
+                WHERE created_at < $3 AND anonymized_at IS NULL
+                RETURNING id
+            ), events AS (
+                UPDATE mod_case_events e
+                SET old_reason = NULL, new_reason = NULL, note = NULL
+                FROM anonymized a
+                WHERE e.case_id = a.id
+                RETURNING e.id
+            )
+            SELECT COUNT(*) AS "count!" FROM anonymized"#,
+            REDACTED_REASON,
+            now_i64,
+            cutoff,
+        )
+        .fetch_one(db.pool())
+        .await?;
+        report.anonymized_cases = u64::try_from(anonymized).unwrap_or(0);
+    }
+
+    if let Some(cutoff) = cutoff(now, policy.command_usage_max_age)? {
+        report.command_invocations = sqlx::query!(
+            "DELETE FROM command_invocations WHERE created_at < $1",
+            cutoff,
+        )
+        .execute(db.pool())
+        .await?
+        .rows_affected();
+    }
+
+    Ok(report)
+}
+
+/// Remove a user's personal data from one guild: warnings, notes, LLM chat
+/// history and user log records are deleted, and cases against them are
+/// anonymized so the guild's case numbering and counts stay intact. Their
+/// command usage records are deleted everywhere. The caller removes the
+/// archived attachments of the returned snapshots.
+pub async fn erase_user_data(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<ErasureReport> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let now_i64 = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let warnings = sqlx::query!(
+        "DELETE FROM warnings WHERE guild_id = $1 AND user_id = $2",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let notes = sqlx::query!(
+        "DELETE FROM user_notes WHERE guild_id = $1 AND target_user_id = $2",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let anonymized_cases = sqlx::query_scalar!(
+        r#"WITH anonymized AS (
+            UPDATE mod_cases
+            SET target_user_id = NULL, reason = $3, anonymized_at = $4, updated_at = $4
+            WHERE guild_id = $1 AND target_user_id = $2
+            RETURNING id
+        ), events AS (
+            UPDATE mod_case_events e
+            SET old_reason = NULL, new_reason = NULL, note = NULL
+            FROM anonymized a
+            WHERE e.case_id = a.id
+            RETURNING e.id
+        )
+        SELECT COUNT(*) AS "count!" FROM anonymized"#,
+        guild_id_i64,
+        user_id_i64,
+        REDACTED_REASON,
+        now_i64,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let llm_chat_messages = sqlx::query!(
+        "DELETE FROM llm_chat_history WHERE guild_id = $1 AND user_id = $2",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let user_logs = sqlx::query!(
+        "DELETE FROM user_logs WHERE guild_id = $1 AND author_user_id = $2",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let message_snapshots: Vec<SnapshotMessageRow> = sqlx::query_as!(
+        SnapshotMessageRow,
+        "DELETE FROM message_snapshots WHERE guild_id = $1 AND author_user_id = $2
+         RETURNING guild_id, channel_id, message_id",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let reports = sqlx::query!(
+        "DELETE FROM user_reports
+         WHERE guild_id = $1 AND (reporter_user_id = $2 OR target_user_id = $2)",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    // Relayed messages go with their conversation.
+    let modmail_conversations = sqlx::query!(
+        "DELETE FROM modmail_conversations WHERE guild_id = $1 AND user_id = $2",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let watchlist_entries = sqlx::query!(
+        "DELETE FROM watchlist WHERE guild_id = $1 AND user_id = $2",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    // Invocations aren't tied to one guild (DMs have none), so every record
+    // of the user goes.
+    let command_invocations = sqlx::query!(
+        "DELETE FROM command_invocations WHERE user_id = $1",
+        user_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+    if watchlist_entries > 0 {
+        invalidate_guild_config(db.cache(), guild_id).await?;
+    }
+
+    Ok(ErasureReport {
+        warnings,
+        notes,
+        anonymized_cases: u64::try_from(anonymized_cases).unwrap_or(0),
+        llm_chat_messages,
+        user_logs,
+        message_snapshots: message_snapshots
+            .into_iter()
+            .map(to_snapshot_message)
+            .collect::<anyhow::Result<_>>()?,
+        reports,
+        modmail_conversations,
+        watchlist_entries,
+        command_invocations,
+    })
+}
+
+fn to_snapshot_message(row: SnapshotMessageRow) -> anyhow::Result<SnapshotMessage> {
+    Ok(SnapshotMessage {
+        guild_id: u64::try_from(row.guild_id).context("guild_id row out of u64 range")?,
+        channel_id: u64::try_from(row.channel_id).context("channel_id row out of u64 range")?,
+        message_id: u64::try_from(row.message_id).context("message_id row out of u64 range")?,
+    })
+}
+
+fn cutoff(now: u64, max_age: Option<Duration>) -> anyhow::Result<Option<i64>> {
+    max_age
+        .map(|max_age| {
+            i64::try_from(now.saturating_sub(max_age.as_secs())).context("cutoff out of i64 range")
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_days_keeps_data_forever() {
+        assert_eq!(retention_days(0), None);
+        assert_eq!(retention_days(2), Some(Duration::from_secs(172_800)));
+        assert!(
+            !RetentionPolicy {
+                userlog_max_age: None,
+                llm_chat_max_age: None,
+                case_anonymize_after: None,
+                command_usage_max_age: None,
+            }
+            .is_enabled()
+        );
+    }
+
+    #[test]
+    fn erasure_total_counts_every_kind_of_record() {
+        let report = ErasureReport {
+            warnings: 1,
+            message_snapshots: vec![SnapshotMessage {
+                guild_id: 1,
+                channel_id: 2,
+                message_id: 3,
+            }],
+            command_invocations: 4,
+            ..ErasureReport::default()
+        };
+
+        assert_eq!(report.total(), 6);
+    }
+
+    #[test]
+    fn cutoff_subtracts_max_age() {
+        assert_eq!(cutoff(1_000, None).unwrap(), None);
+        assert_eq!(
+            cutoff(1_000, Some(Duration::from_secs(400))).unwrap(),
+            Some(600)
+        );
+        assert_eq!(
+            cutoff(1_000, Some(Duration::from_secs(5_000))).unwrap(),
+            Some(0)
+        );
+    }
+}
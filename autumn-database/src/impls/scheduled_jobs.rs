@@ -0,0 +1,375 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+/// Jobs are deleted after this many failed attempts.
+pub const SCHEDULED_JOB_MAX_ATTEMPTS: i32 = 5;
+
+/// Unban a tempbanned user and log the reversal. Payload: the ban's case label.
+pub const JOB_BAN_EXPIRY: &str = "ban_expiry";
+/// Confirm a long timeout ran out and log the reversal. Payload: the timeout's
+/// case label.
+pub const JOB_TIMEOUT_EXPIRY: &str = "timeout_expiry";
+/// Ping `created_by` in `channel_id`. Payload: the reminder text.
+pub const JOB_REMINDER: &str = "reminder";
+/// Carry out a pending ban or timeout case. Payload: the case label.
+pub const JOB_DEFERRED_ACTION: &str = "deferred_action";
+/// Take a temporary role back off a member. Payload: the role id.
+pub const JOB_TEMPROLE_EXPIRY: &str = "temprole_expiry";
+/// Run a recurring task and schedule its next run. Payload: the task id.
+pub const JOB_RECURRING_TASK: &str = "recurring_task";
+/// Nudge the modlog about an `open` case nobody has resolved. Payload: the
+/// case label.
+pub const JOB_CASE_REVIEW: &str = "case_review";
+/// Decay a guild's old warnings and post the weekly digest. Runs hourly while
+/// decay is enabled. Payload: empty.
+pub const JOB_WARNING_DECAY: &str = "warning_decay";
+/// Tally a poll's reactions and post the results in `channel_id`. Payload:
+/// the poll and its message id, as JSON.
+pub const JOB_POLL_CLOSE: &str = "poll_close";
+/// Lift a shadow mute and log it. Payload: the shadow mute's case label.
+pub const JOB_SHADOW_MUTE_EXPIRY: &str = "shadow_mute_expiry";
+/// Cancel a dual-approval case nobody answered, e.g. because the bot
+/// restarted while it waited. Payload: the case label.
+pub const JOB_APPROVAL_EXPIRY: &str = "approval_expiry";
+
+#[derive(Clone, Copy, Debug)]
+pub struct NewScheduledJob<'a> {
+    pub guild_id: u64,
+    pub kind: &'a str,
+    pub target_user_id: Option<u64>,
+    pub channel_id: Option<u64>,
+    /// Kind-specific data; see the `JOB_*` constants.
+    pub payload: &'a str,
+    /// Unix seconds.
+    pub run_at: u64,
+    pub created_by: u64,
+}
+
+/// A job waiting to run.
+#[derive(Clone, Debug)]
+pub struct ScheduledJob {
+    pub id: i64,
+    pub guild_id: u64,
+    pub kind: String,
+    pub target_user_id: Option<u64>,
+    pub channel_id: Option<u64>,
+    pub payload: String,
+    pub run_at: u64,
+    pub attempts: i32,
+    pub created_by: u64,
+    pub created_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduledJobRow {
+    id: i64,
+    guild_id: i64,
+    kind: String,
+    target_user_id: Option<i64>,
+    channel_id: Option<i64>,
+    payload: String,
+    run_at: i64,
+    attempts: i32,
+    created_by: i64,
+    created_at: i64,
+}
+
+pub async fn schedule_job(db: &Database, job: NewScheduledJob<'_>) -> anyhow::Result<i64> {
+    let guild_id = i64::try_from(job.guild_id).context("guild_id out of i64 range")?;
+    let target_user_id = job
+        .target_user_id
+        .map(i64::try_from)
+        .transpose()
+        .context("target_user_id out of i64 range")?;
+    let channel_id = job
+        .channel_id
+        .map(i64::try_from)
+        .transpose()
+        .context("channel_id out of i64 range")?;
+    let run_at = i64::try_from(job.run_at).context("run_at out of i64 range")?;
+    let created_by = i64::try_from(job.created_by).context("created_by out of i64 range")?;
+    let created_at = i64::try_from(now_unix_secs()).context("created_at out of i64 range")?;
+
+    let id = sqlx::query_scalar!(
+        "INSERT INTO scheduled_jobs
+            (guild_id, kind, target_user_id, channel_id, payload, run_at, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING id",
+        guild_id,
+        job.kind,
+        target_user_id,
+        channel_id,
+        job.payload,
+        run_at,
+        created_by,
+        created_at,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok(id)
+}
+
+/// Lease the oldest due job to `worker` for `lease_secs`. Jobs leased by
+/// another worker are skipped until that lease runs out, so a worker that
+/// crashes mid-run hands its job on instead of losing it. Each claim counts
+/// as an attempt; a job that keeps taking its worker down is eventually
+/// deleted by [`delete_exhausted_jobs`].
+///
+/// One job at a time, so the lease only has to cover that job's run.
+pub async fn claim_due_job(
+    db: &Database,
+    worker: &str,
+    lease_secs: u64,
+) -> anyhow::Result<Option<ScheduledJob>> {
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+    let locked_until =
+        i64::try_from(now_unix_secs() + lease_secs).context("locked_until out of i64 range")?;
+
+    let row: Option<ScheduledJobRow> = sqlx::query_as!(
+        ScheduledJobRow,
+        "UPDATE scheduled_jobs
+         SET locked_by = $1, locked_until = $2, attempts = attempts + 1
+         WHERE id IN (
+             SELECT id FROM scheduled_jobs
+             WHERE run_at <= $3
+               AND attempts < $4
+               AND (locked_until IS NULL OR locked_until <= $3)
+             ORDER BY run_at ASC, id ASC
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, guild_id, kind, target_user_id, channel_id, payload, run_at, attempts,
+                   created_by, created_at",
+        worker,
+        locked_until,
+        now,
+        SCHEDULED_JOB_MAX_ATTEMPTS,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_scheduled_job).transpose()
+}
+
+/// Delete unleased jobs that used up their attempts without [`fail_job`]
+/// seeing the last one, i.e. the worker died running them. Returns them so
+/// the caller can log what was given up on.
+pub async fn delete_exhausted_jobs(db: &Database) -> anyhow::Result<Vec<ScheduledJob>> {
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let rows: Vec<ScheduledJobRow> = sqlx::query_as!(
+        ScheduledJobRow,
+        "DELETE FROM scheduled_jobs
+         WHERE attempts >= $1
+           AND (locked_until IS NULL OR locked_until <= $2)
+         RETURNING id, guild_id, kind, target_user_id, channel_id, payload, run_at, attempts,
+                   created_by, created_at",
+        SCHEDULED_JOB_MAX_ATTEMPTS,
+        now,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_scheduled_job).collect()
+}
+
+/// Remove a job that has run. Does nothing if `worker` no longer holds the
+/// lease, e.g. because the job was cancelled while it ran.
+pub async fn complete_job(db: &Database, id: i64, worker: &str) -> anyhow::Result<()> {
+    sqlx::query!(
+        "DELETE FROM scheduled_jobs WHERE id = $1 AND locked_by = $2",
+        id,
+        worker,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Push a job back and release it without counting the run as a failure;
+/// earlier failures are forgotten.
+pub async fn reschedule_job(
+    db: &Database,
+    id: i64,
+    worker: &str,
+    run_at: u64,
+) -> anyhow::Result<()> {
+    let run_at = i64::try_from(run_at).context("run_at out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE scheduled_jobs
+         SET run_at = $3, attempts = 0, locked_by = NULL, locked_until = NULL
+         WHERE id = $1 AND locked_by = $2",
+        id,
+        worker,
+        run_at,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Release a failed job for a retry with exponential backoff. `attempts` is
+/// the count returned by the claim, which already includes this run. A job
+/// out of attempts is deleted instead; returns `true` when that happened.
+pub async fn fail_job(
+    db: &Database,
+    id: i64,
+    worker: &str,
+    attempts: i32,
+    error: &str,
+) -> anyhow::Result<bool> {
+    if attempts >= SCHEDULED_JOB_MAX_ATTEMPTS {
+        let deleted = sqlx::query!(
+            "DELETE FROM scheduled_jobs WHERE id = $1 AND locked_by = $2",
+            id,
+            worker,
+        )
+        .execute(db.pool())
+        .await?
+        .rows_affected();
+
+        return Ok(deleted > 0);
+    }
+
+    let run_at = i64::try_from(now_unix_secs() + scheduled_job_backoff_secs(attempts - 1))
+        .context("run_at out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE scheduled_jobs
+         SET run_at = $3, last_error = $4, locked_by = NULL, locked_until = NULL
+         WHERE id = $1 AND locked_by = $2",
+        id,
+        worker,
+        run_at,
+        error,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(false)
+}
+
+/// Drop pending `kind` jobs for a user, e.g. when a moderator lifts a tempban
+/// by hand. Returns how many were removed.
+pub async fn cancel_jobs_for_target(
+    db: &Database,
+    guild_id: u64,
+    kind: &str,
+    target_user_id: u64,
+) -> anyhow::Result<u64> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let target_user_id =
+        i64::try_from(target_user_id).context("target_user_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM scheduled_jobs
+         WHERE guild_id = $1 AND kind = $2 AND target_user_id = $3",
+        guild_id,
+        kind,
+        target_user_id,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Drop pending `kind` jobs for a user that carry `payload`, e.g. the expiry
+/// of a temporary role that is being handed out again.
+pub async fn cancel_jobs_for_target_with_payload(
+    db: &Database,
+    guild_id: u64,
+    kind: &str,
+    target_user_id: u64,
+    payload: &str,
+) -> anyhow::Result<u64> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let target_user_id =
+        i64::try_from(target_user_id).context("target_user_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM scheduled_jobs
+         WHERE guild_id = $1 AND kind = $2 AND target_user_id = $3 AND payload = $4",
+        guild_id,
+        kind,
+        target_user_id,
+        payload,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Drop pending `kind` jobs with the given payload, e.g. a deferred action
+/// whose case was cancelled. Returns how many were removed.
+pub async fn cancel_jobs_with_payload(
+    db: &Database,
+    guild_id: u64,
+    kind: &str,
+    payload: &str,
+) -> anyhow::Result<u64> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM scheduled_jobs
+         WHERE guild_id = $1 AND kind = $2 AND payload = $3",
+        guild_id,
+        kind,
+        payload,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Delay before retrying after `attempts` earlier failures: one minute
+/// doubling up to an hour.
+pub fn scheduled_job_backoff_secs(attempts: i32) -> u64 {
+    let exponent = u32::try_from(attempts.clamp(0, 6)).unwrap_or(0);
+    (60u64 << exponent).min(3600)
+}
+
+fn to_scheduled_job(row: ScheduledJobRow) -> anyhow::Result<ScheduledJob> {
+    Ok(ScheduledJob {
+        id: row.id,
+        guild_id: u64::try_from(row.guild_id).context("guild_id row out of u64 range")?,
+        kind: row.kind,
+        target_user_id: row
+            .target_user_id
+            .map(u64::try_from)
+            .transpose()
+            .context("target_user_id row out of u64 range")?,
+        channel_id: row
+            .channel_id
+            .map(u64::try_from)
+            .transpose()
+            .context("channel_id row out of u64 range")?,
+        payload: row.payload,
+        run_at: u64::try_from(row.run_at).context("run_at row out of u64 range")?,
+        attempts: row.attempts,
+        created_by: u64::try_from(row.created_by).context("created_by row out of u64 range")?,
+        created_at: u64::try_from(row.created_at).context("created_at row out of u64 range")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scheduled_job_backoff_secs;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        assert_eq!(scheduled_job_backoff_secs(0), 60);
+        assert_eq!(scheduled_job_backoff_secs(1), 120);
+        assert_eq!(scheduled_job_backoff_secs(5), 1920);
+        assert_eq!(scheduled_job_backoff_secs(6), 3600);
+        assert_eq!(scheduled_job_backoff_secs(40), 3600);
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+
+pub async fn get_serverlog_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<Option<u64>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let channel_id: Option<i64> = sqlx::query_scalar!(
+        "SELECT serverlog_channel_id FROM guild_serverlog_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?
+    .flatten();
+
+    channel_id
+        .map(u64::try_from)
+        .transpose()
+        .context("serverlog_channel_id out of u64 range")
+}
+
+pub async fn set_serverlog_channel_id(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_serverlog_config (guild_id, serverlog_channel_id)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET serverlog_channel_id = EXCLUDED.serverlog_channel_id",
+        guild_id_i64,
+        channel_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_serverlog_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "DELETE FROM guild_serverlog_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anyhow::Context as _;
+
+use crate::cache::invalidate_guild_config;
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+#[derive(Clone, Debug)]
+pub struct ShadowMute {
+    pub user_id: u64,
+    pub reason: String,
+    pub moderator_id: u64,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct ShadowMuteRow {
+    user_id: i64,
+    reason: String,
+    moderator_id: i64,
+    created_at: i64,
+    expires_at: i64,
+}
+
+/// Shadow mute a member until `expires_at`, or move the expiry of an existing
+/// shadow mute. Returns `false` when they were already shadow muted.
+pub async fn add_shadow_mute(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+    reason: &str,
+    moderator_id: u64,
+    expires_at: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let moderator_id = i64::try_from(moderator_id).context("moderator_id out of i64 range")?;
+    let expires_at = i64::try_from(expires_at).context("expires_at out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let inserted = sqlx::query_scalar!(
+        r#"INSERT INTO shadow_mutes (guild_id, user_id, reason, moderator_id, created_at, expires_at)
+           VALUES ($1, $2, $3, $4, $5, $6)
+           ON CONFLICT (guild_id, user_id) DO UPDATE
+           SET reason = EXCLUDED.reason,
+               moderator_id = EXCLUDED.moderator_id,
+               expires_at = EXCLUDED.expires_at
+           RETURNING (xmax = 0) AS "inserted!""#,
+        guild_id_i64,
+        user_id,
+        reason,
+        moderator_id,
+        now,
+        expires_at,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(inserted)
+}
+
+/// Returns `false` if the member wasn't shadow muted.
+pub async fn remove_shadow_mute(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let removed = sqlx::query!(
+        "DELETE FROM shadow_mutes WHERE guild_id = $1 AND user_id = $2",
+        guild_id_i64,
+        user_id,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(removed > 0)
+}
+
+pub async fn get_shadow_mute(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<Option<ShadowMute>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let row: Option<ShadowMuteRow> = sqlx::query_as!(
+        ShadowMuteRow,
+        "SELECT user_id, reason, moderator_id, created_at, expires_at
+         FROM shadow_mutes
+         WHERE guild_id = $1 AND user_id = $2",
+        guild_id,
+        user_id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(|row| {
+        Ok(ShadowMute {
+            user_id: u64::try_from(row.user_id).context("negative user_id")?,
+            reason: row.reason,
+            moderator_id: u64::try_from(row.moderator_id).context("negative moderator_id")?,
+            created_at: u64::try_from(row.created_at).context("negative created_at")?,
+            expires_at: u64::try_from(row.expires_at).context("negative expires_at")?,
+        })
+    })
+    .transpose()
+}
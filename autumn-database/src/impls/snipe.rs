@@ -0,0 +1,56 @@
+use crate::cache::{CacheTtlClass, snipe_key};
+use crate::database::Database;
+use crate::model::snipe::SnipedMessage;
+
+const DELETED: &str = "deleted";
+const EDITED: &str = "edited";
+
+/// Remember the last deleted message in a channel. Only kept when the Redis
+/// cache is enabled.
+pub async fn record_deleted_message(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    message: &SnipedMessage,
+) -> anyhow::Result<()> {
+    let cache = db.cache();
+    let key = snipe_key(cache, guild_id, channel_id, DELETED);
+    cache
+        .set_json(&key, message, cache.ttl(CacheTtlClass::Snipe))
+        .await
+}
+
+pub async fn get_deleted_message(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<Option<SnipedMessage>> {
+    let cache = db.cache();
+    let key = snipe_key(cache, guild_id, channel_id, DELETED);
+    cache.get_json(&key).await
+}
+
+/// Remember the last edited message in a channel. Only kept when the Redis
+/// cache is enabled.
+pub async fn record_edited_message(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    message: &SnipedMessage,
+) -> anyhow::Result<()> {
+    let cache = db.cache();
+    let key = snipe_key(cache, guild_id, channel_id, EDITED);
+    cache
+        .set_json(&key, message, cache.ttl(CacheTtlClass::Snipe))
+        .await
+}
+
+pub async fn get_edited_message(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<Option<SnipedMessage>> {
+    let cache = db.cache();
+    let key = snipe_key(cache, guild_id, channel_id, EDITED);
+    cache.get_json(&key).await
+}
@@ -11,7 +11,7 @@ pub struct MessageSnapshot {
     pub attachment_summary: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct NewMessageSnapshot<'a> {
     pub guild_id: u64,
     pub channel_id: u64,
@@ -22,7 +22,7 @@ pub struct NewMessageSnapshot<'a> {
     pub updated_at: u64,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct NewUserLog<'a> {
     pub guild_id: u64,
     pub channel_id: u64,
@@ -88,7 +88,7 @@ pub async fn upsert_message_snapshot(
     let updated_at_i64 =
         i64::try_from(snapshot.updated_at).context("updated_at out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO message_snapshots (
             guild_id,
             channel_id,
@@ -104,14 +104,14 @@ pub async fn upsert_message_snapshot(
             content = EXCLUDED.content,
             attachment_summary = EXCLUDED.attachment_summary,
             updated_at = EXCLUDED.updated_at",
+        guild_id_i64,
+        channel_id_i64,
+        message_id_i64,
+        author_user_id_i64,
+        snapshot.content,
+        snapshot.attachment_summary,
+        updated_at_i64,
     )
-    .bind(guild_id_i64)
-    .bind(channel_id_i64)
-    .bind(message_id_i64)
-    .bind(author_user_id_i64)
-    .bind(snapshot.content)
-    .bind(snapshot.attachment_summary)
-    .bind(updated_at_i64)
     .execute(db.pool())
     .await?;
 
@@ -128,30 +128,63 @@ pub async fn get_message_snapshot(
     let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
     let message_id_i64 = i64::try_from(message_id).context("message_id out of i64 range")?;
 
-    let row: Option<SnapshotRow> = sqlx::query_as(
+    let row: Option<SnapshotRow> = sqlx::query_as!(
+        SnapshotRow,
         "SELECT channel_id, message_id, author_user_id, content, attachment_summary
          FROM message_snapshots
          WHERE guild_id = $1 AND channel_id = $2 AND message_id = $3",
+        guild_id_i64,
+        channel_id_i64,
+        message_id_i64,
     )
-    .bind(guild_id_i64)
-    .bind(channel_id_i64)
-    .bind(message_id_i64)
     .fetch_optional(db.pool())
     .await?;
 
-    row.map(|entry| {
-        Ok(MessageSnapshot {
-            channel_id: u64::try_from(entry.channel_id)
-                .context("channel_id row out of u64 range")?,
-            message_id: u64::try_from(entry.message_id)
-                .context("message_id row out of u64 range")?,
-            author_user_id: u64::try_from(entry.author_user_id)
-                .context("author_user_id row out of u64 range")?,
-            content: entry.content,
-            attachment_summary: entry.attachment_summary,
-        })
+    row.map(to_message_snapshot).transpose()
+}
+
+fn to_message_snapshot(entry: SnapshotRow) -> anyhow::Result<MessageSnapshot> {
+    Ok(MessageSnapshot {
+        channel_id: u64::try_from(entry.channel_id).context("channel_id row out of u64 range")?,
+        message_id: u64::try_from(entry.message_id).context("message_id row out of u64 range")?,
+        author_user_id: u64::try_from(entry.author_user_id)
+            .context("author_user_id row out of u64 range")?,
+        content: entry.content,
+        attachment_summary: entry.attachment_summary,
     })
-    .transpose()
+}
+
+/// Snapshots of an author's messages with ids at or above `min_message_id`,
+/// oldest first.
+pub async fn list_message_snapshots_by_author(
+    db: &Database,
+    guild_id: u64,
+    author_user_id: u64,
+    min_message_id: u64,
+    limit: u32,
+) -> anyhow::Result<Vec<MessageSnapshot>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let author_user_id_i64 =
+        i64::try_from(author_user_id).context("author_user_id out of i64 range")?;
+    let min_message_id_i64 =
+        i64::try_from(min_message_id).context("min_message_id out of i64 range")?;
+
+    let rows: Vec<SnapshotRow> = sqlx::query_as!(
+        SnapshotRow,
+        "SELECT channel_id, message_id, author_user_id, content, attachment_summary
+         FROM message_snapshots
+         WHERE guild_id = $1 AND author_user_id = $2 AND message_id >= $3
+         ORDER BY message_id ASC
+         LIMIT $4",
+        guild_id_i64,
+        author_user_id_i64,
+        min_message_id_i64,
+        i64::from(limit),
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_message_snapshot).collect()
 }
 
 pub async fn delete_message_snapshot(
@@ -164,13 +197,13 @@ pub async fn delete_message_snapshot(
     let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
     let message_id_i64 = i64::try_from(message_id).context("message_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "DELETE FROM message_snapshots
          WHERE guild_id = $1 AND channel_id = $2 AND message_id = $3",
+        guild_id_i64,
+        channel_id_i64,
+        message_id_i64,
     )
-    .bind(guild_id_i64)
-    .bind(channel_id_i64)
-    .bind(message_id_i64)
     .execute(db.pool())
     .await?;
 
@@ -192,7 +225,7 @@ pub async fn insert_user_log(db: &Database, entry: NewUserLog<'_>) -> anyhow::Re
         .context("author_user_id out of i64 range")?;
     let created_at_i64 = i64::try_from(entry.created_at).context("created_at out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO user_logs (
             guild_id,
             channel_id,
@@ -204,16 +237,16 @@ pub async fn insert_user_log(db: &Database, entry: NewUserLog<'_>) -> anyhow::Re
             attachment_summary,
             created_at
         ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        guild_id_i64,
+        channel_id_i64,
+        message_id_i64,
+        author_user_id_i64,
+        entry.event_type,
+        entry.before_content,
+        entry.after_content,
+        entry.attachment_summary,
+        created_at_i64,
     )
-    .bind(guild_id_i64)
-    .bind(channel_id_i64)
-    .bind(message_id_i64)
-    .bind(author_user_id_i64)
-    .bind(entry.event_type)
-    .bind(entry.before_content)
-    .bind(entry.after_content)
-    .bind(entry.attachment_summary)
-    .bind(created_at_i64)
     .execute(db.pool())
     .await?;
 
@@ -233,7 +266,8 @@ pub async fn list_recent_user_logs(
         .context("author_user_id out of i64 range")?;
     let limit_i64 = i64::from(filters.limit.clamp(1, 200));
 
-    let rows: Vec<UserLogRow> = sqlx::query_as(
+    let rows: Vec<UserLogRow> = sqlx::query_as!(
+        UserLogRow,
         "SELECT
             channel_id,
             message_id,
@@ -249,11 +283,11 @@ pub async fn list_recent_user_logs(
            AND ($3::TEXT IS NULL OR LOWER(event_type) = LOWER($3))
          ORDER BY created_at DESC
          LIMIT $4",
+        guild_id_i64,
+        author_user_id_i64,
+        filters.event_type,
+        limit_i64,
     )
-    .bind(guild_id_i64)
-    .bind(author_user_id_i64)
-    .bind(filters.event_type)
-    .bind(limit_i64)
     .fetch_all(db.pool())
     .await?;
 
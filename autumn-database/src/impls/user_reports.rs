@@ -0,0 +1,277 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+/// Waiting for a moderator.
+pub const REPORT_OPEN: &str = "open";
+/// A moderator took it and an `open` case was filed for it.
+pub const REPORT_CLAIMED: &str = "claimed";
+/// Handled; the report's case is resolved too.
+pub const REPORT_RESOLVED: &str = "resolved";
+
+/// Reports one member can have waiting in a guild at once.
+pub const MAX_OPEN_REPORTS_PER_REPORTER: u64 = 5;
+pub const REPORT_REASON_MAX_CHARS: usize = 500;
+
+pub struct NewUserReport<'a> {
+    pub guild_id: u64,
+    pub reporter_user_id: u64,
+    pub target_user_id: u64,
+    pub reason: &'a str,
+    /// Set when a message was reported.
+    pub channel_id: Option<u64>,
+    pub message_id: Option<u64>,
+    pub message_content: Option<&'a str>,
+}
+
+#[derive(Clone, Debug)]
+pub struct UserReport {
+    pub id: i64,
+    pub guild_id: u64,
+    pub reporter_user_id: u64,
+    pub target_user_id: u64,
+    pub reason: String,
+    pub channel_id: Option<u64>,
+    pub message_id: Option<u64>,
+    pub message_content: Option<String>,
+    pub status: String,
+    pub handled_by: Option<u64>,
+    /// The case opened when the report was claimed.
+    pub case_label: Option<String>,
+    pub created_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct UserReportRow {
+    id: i64,
+    guild_id: i64,
+    reporter_user_id: i64,
+    target_user_id: i64,
+    reason: String,
+    channel_id: Option<i64>,
+    message_id: Option<i64>,
+    message_content: Option<String>,
+    status: String,
+    handled_by: Option<i64>,
+    case_label: Option<String>,
+    created_at: i64,
+}
+
+pub async fn create_user_report(db: &Database, report: NewUserReport<'_>) -> anyhow::Result<i64> {
+    let guild_id = i64::try_from(report.guild_id).context("guild_id out of i64 range")?;
+    let reporter_user_id =
+        i64::try_from(report.reporter_user_id).context("reporter_user_id out of i64 range")?;
+    let target_user_id =
+        i64::try_from(report.target_user_id).context("target_user_id out of i64 range")?;
+    let channel_id = report
+        .channel_id
+        .map(i64::try_from)
+        .transpose()
+        .context("channel_id out of i64 range")?;
+    let message_id = report
+        .message_id
+        .map(i64::try_from)
+        .transpose()
+        .context("message_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let id = sqlx::query_scalar!(
+        "INSERT INTO user_reports (
+            guild_id, reporter_user_id, target_user_id, reason,
+            channel_id, message_id, message_content, status, created_at, updated_at
+         ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9)
+         RETURNING id",
+        guild_id,
+        reporter_user_id,
+        target_user_id,
+        report.reason,
+        channel_id,
+        message_id,
+        report.message_content,
+        REPORT_OPEN,
+        now,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok(id)
+}
+
+/// How many of `reporter_user_id`'s reports are still waiting for a
+/// moderator.
+pub async fn count_open_reports_by(
+    db: &Database,
+    guild_id: u64,
+    reporter_user_id: u64,
+) -> anyhow::Result<u64> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let reporter_user_id =
+        i64::try_from(reporter_user_id).context("reporter_user_id out of i64 range")?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM user_reports
+           WHERE guild_id = $1 AND reporter_user_id = $2 AND status = $3"#,
+        guild_id,
+        reporter_user_id,
+        REPORT_OPEN,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok(u64::try_from(count).unwrap_or(0))
+}
+
+/// Open and claimed reports, oldest first.
+pub async fn list_active_reports(db: &Database, guild_id: u64) -> anyhow::Result<Vec<UserReport>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let rows: Vec<UserReportRow> = sqlx::query_as!(
+        UserReportRow,
+        "SELECT id, guild_id, reporter_user_id, target_user_id, reason, channel_id,
+                message_id, message_content, status, handled_by, case_label, created_at
+         FROM user_reports
+         WHERE guild_id = $1 AND status IN ($2, $3)
+         ORDER BY id ASC",
+        guild_id,
+        REPORT_OPEN,
+        REPORT_CLAIMED,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_user_report).collect()
+}
+
+pub async fn get_user_report(
+    db: &Database,
+    guild_id: u64,
+    id: i64,
+) -> anyhow::Result<Option<UserReport>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let row: Option<UserReportRow> = sqlx::query_as!(
+        UserReportRow,
+        "SELECT id, guild_id, reporter_user_id, target_user_id, reason, channel_id,
+                message_id, message_content, status, handled_by, case_label, created_at
+         FROM user_reports
+         WHERE guild_id = $1 AND id = $2",
+        guild_id,
+        id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_user_report).transpose()
+}
+
+/// Take an open report. Returns `false` if someone else got there first.
+pub async fn claim_user_report(
+    db: &Database,
+    guild_id: u64,
+    id: i64,
+    moderator_user_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let moderator_user_id =
+        i64::try_from(moderator_user_id).context("moderator_user_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let updated = sqlx::query!(
+        "UPDATE user_reports
+         SET status = $1, handled_by = $2, updated_at = $3
+         WHERE guild_id = $4 AND id = $5 AND status = $6",
+        REPORT_CLAIMED,
+        moderator_user_id,
+        now,
+        guild_id,
+        id,
+        REPORT_OPEN,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+/// Record the case opened for a claimed report.
+pub async fn set_user_report_case(
+    db: &Database,
+    guild_id: u64,
+    id: i64,
+    case_label: &str,
+) -> anyhow::Result<()> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE user_reports SET case_label = $1 WHERE guild_id = $2 AND id = $3",
+        case_label,
+        guild_id,
+        id,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Close a claimed report. Returns `false` if it isn't claimed.
+pub async fn resolve_user_report(
+    db: &Database,
+    guild_id: u64,
+    id: i64,
+    moderator_user_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let moderator_user_id =
+        i64::try_from(moderator_user_id).context("moderator_user_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let updated = sqlx::query!(
+        "UPDATE user_reports
+         SET status = $1, handled_by = $2, updated_at = $3
+         WHERE guild_id = $4 AND id = $5 AND status = $6",
+        REPORT_RESOLVED,
+        moderator_user_id,
+        now,
+        guild_id,
+        id,
+        REPORT_CLAIMED,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+fn to_user_report(row: UserReportRow) -> anyhow::Result<UserReport> {
+    Ok(UserReport {
+        id: row.id,
+        guild_id: u64::try_from(row.guild_id).context("negative guild_id")?,
+        reporter_user_id: u64::try_from(row.reporter_user_id)
+            .context("negative reporter_user_id")?,
+        target_user_id: u64::try_from(row.target_user_id).context("negative target_user_id")?,
+        reason: row.reason,
+        channel_id: row
+            .channel_id
+            .map(u64::try_from)
+            .transpose()
+            .context("negative channel_id")?,
+        message_id: row
+            .message_id
+            .map(u64::try_from)
+            .transpose()
+            .context("negative message_id")?,
+        message_content: row.message_content,
+        status: row.status,
+        handled_by: row
+            .handled_by
+            .map(u64::try_from)
+            .transpose()
+            .context("negative handled_by")?,
+        case_label: row.case_label,
+        created_at: u64::try_from(row.created_at).context("negative created_at")?,
+    })
+}
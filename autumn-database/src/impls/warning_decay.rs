@@ -0,0 +1,189 @@
+use anyhow::Context as _;
+
+use crate::database::Database;
+use crate::impls::scheduled_jobs::JOB_WARNING_DECAY;
+use crate::impls::warnings::now_unix_secs;
+
+#[derive(Clone, Copy, Debug)]
+pub struct WarningDecayConfig {
+    pub guild_id: u64,
+    /// Warnings older than this are decayed.
+    pub max_age_seconds: u64,
+    /// When the last weekly digest covered decays up to.
+    pub last_digest_at: u64,
+}
+
+pub async fn get_warning_decay_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<WarningDecayConfig>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let row = sqlx::query!(
+        "SELECT max_age_seconds, last_digest_at FROM warning_decay_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(|row| {
+        Ok(WarningDecayConfig {
+            guild_id,
+            max_age_seconds: u64::try_from(row.max_age_seconds)
+                .context("max_age_seconds row out of u64 range")?,
+            last_digest_at: u64::try_from(row.last_digest_at)
+                .context("last_digest_at row out of u64 range")?,
+        })
+    })
+    .transpose()
+}
+
+/// Turn decay on (or change its age) and queue the guild's decay job to run
+/// straight away. An earlier job for the guild is replaced.
+pub async fn enable_warning_decay(
+    db: &Database,
+    guild_id: u64,
+    max_age_seconds: u64,
+    created_by: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let max_age_seconds =
+        i64::try_from(max_age_seconds).context("max_age_seconds out of i64 range")?;
+    let created_by = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    sqlx::query!(
+        "INSERT INTO warning_decay_config (guild_id, max_age_seconds, last_digest_at)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id) DO UPDATE SET max_age_seconds = EXCLUDED.max_age_seconds",
+        guild_id_i64,
+        max_age_seconds,
+        now,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM scheduled_jobs WHERE guild_id = $1 AND kind = $2",
+        guild_id_i64,
+        JOB_WARNING_DECAY,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO scheduled_jobs (guild_id, kind, run_at, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $3)",
+        guild_id_i64,
+        JOB_WARNING_DECAY,
+        now,
+        created_by,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Turn decay off. Warnings already decayed stay decayed. Returns `false` if
+/// decay was not enabled.
+pub async fn disable_warning_decay(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let deleted = sqlx::query!(
+        "DELETE FROM warning_decay_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query!(
+        "DELETE FROM scheduled_jobs WHERE guild_id = $1 AND kind = $2",
+        guild_id_i64,
+        JOB_WARNING_DECAY,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(deleted > 0)
+}
+
+/// Soft-delete the guild's warnings older than `max_age_seconds`. Returns how
+/// many were decayed.
+pub async fn decay_warnings(
+    db: &Database,
+    guild_id: u64,
+    max_age_seconds: u64,
+) -> anyhow::Result<u64> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let now = now_unix_secs();
+    let cutoff =
+        i64::try_from(now.saturating_sub(max_age_seconds)).context("cutoff out of i64 range")?;
+    let now = i64::try_from(now).context("now out of i64 range")?;
+
+    let decayed = sqlx::query!(
+        "UPDATE warnings SET decayed_at = $3
+         WHERE guild_id = $1 AND warned_at < $2 AND decayed_at IS NULL",
+        guild_id_i64,
+        cutoff,
+        now,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    Ok(decayed)
+}
+
+/// Warnings decayed since `since`, counted per user, most first.
+pub async fn decayed_warning_counts_since(
+    db: &Database,
+    guild_id: u64,
+    since: u64,
+) -> anyhow::Result<Vec<(u64, u64)>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let since = i64::try_from(since).context("since out of i64 range")?;
+
+    let rows = sqlx::query!(
+        r#"SELECT user_id, COUNT(*) AS "count!"
+         FROM warnings
+         WHERE guild_id = $1 AND decayed_at >= $2
+         GROUP BY user_id
+         ORDER BY COUNT(*) DESC, user_id ASC"#,
+        guild_id_i64,
+        since,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok((
+                u64::try_from(row.user_id).context("user_id row out of u64 range")?,
+                u64::try_from(row.count).context("count row out of u64 range")?,
+            ))
+        })
+        .collect()
+}
+
+pub async fn mark_warning_digest_sent(db: &Database, guild_id: u64, at: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let at = i64::try_from(at).context("at out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE warning_decay_config SET last_digest_at = $2 WHERE guild_id = $1",
+        guild_id_i64,
+        at,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
@@ -28,23 +28,25 @@ pub async fn record_warning(
     let moderator_id_i64 = i64::try_from(moderator_id).context("moderator_id out of i64 range")?;
     let warned_at_i64 = i64::try_from(warned_at).context("warned_at out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO warnings (guild_id, user_id, moderator_id, reason, warned_at) VALUES ($1, $2, $3, $4, $5)",
+        guild_id_i64,
+        user_id_i64,
+        moderator_id_i64,
+        reason,
+        warned_at_i64,
     )
-    .bind(guild_id_i64)
-    .bind(user_id_i64)
-    .bind(moderator_id_i64)
-    .bind(reason)
-    .bind(warned_at_i64)
     .execute(db.pool())
     .await?;
 
-    let warn_number: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM warnings WHERE guild_id = $1 AND user_id = $2")
-            .bind(guild_id_i64)
-            .bind(user_id_i64)
-            .fetch_one(db.pool())
-            .await?;
+    let warn_number = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM warnings
+         WHERE guild_id = $1 AND user_id = $2 AND decayed_at IS NULL"#,
+        guild_id_i64,
+        user_id_i64,
+    )
+    .fetch_one(db.pool())
+    .await?;
 
     let warn_number = usize::try_from(warn_number).context("warn count out of usize range")?;
 
@@ -52,6 +54,7 @@ pub async fn record_warning(
 }
 
 /// Return warning entries for a target user in the inclusive [since, now] range.
+/// Decayed warnings are left out.
 pub async fn warnings_since(
     db: &Database,
     guild_id: u64,
@@ -62,15 +65,16 @@ pub async fn warnings_since(
     let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
     let since_i64 = i64::try_from(since).context("since out of i64 range")?;
 
-    let rows: Vec<WarningRow> = sqlx::query_as(
+    let rows: Vec<WarningRow> = sqlx::query_as!(
+        WarningRow,
         "SELECT warned_at, moderator_id, reason
          FROM warnings
-         WHERE guild_id = $1 AND user_id = $2 AND warned_at >= $3
+         WHERE guild_id = $1 AND user_id = $2 AND warned_at >= $3 AND decayed_at IS NULL
          ORDER BY warned_at ASC",
+        guild_id_i64,
+        user_id_i64,
+        since_i64,
     )
-    .bind(guild_id_i64)
-    .bind(user_id_i64)
-    .bind(since_i64)
     .fetch_all(db.pool())
     .await?;
 
@@ -94,12 +98,14 @@ pub async fn clear_warnings(db: &Database, guild_id: u64, user_id: u64) -> anyho
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
     let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
 
-    let deleted = sqlx::query("DELETE FROM warnings WHERE guild_id = $1 AND user_id = $2")
-        .bind(guild_id_i64)
-        .bind(user_id_i64)
-        .execute(db.pool())
-        .await?
-        .rows_affected();
+    let deleted = sqlx::query!(
+        "DELETE FROM warnings WHERE guild_id = $1 AND user_id = $2",
+        guild_id_i64,
+        user_id_i64,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
 
     Ok(deleted)
 }
@@ -115,20 +121,20 @@ pub async fn remove_warning_by_number(
     let warning_number_i64 =
         i64::try_from(warning_number).context("warning_number out of i64 range")?;
 
-    let deleted_row: Option<i64> = sqlx::query_scalar(
+    let deleted_row: Option<i64> = sqlx::query_scalar!(
         "WITH ranked AS (
             SELECT id, ROW_NUMBER() OVER (ORDER BY warned_at ASC, id ASC) AS rn
             FROM warnings
-            WHERE guild_id = $1 AND user_id = $2
+            WHERE guild_id = $1 AND user_id = $2 AND decayed_at IS NULL
         )
         DELETE FROM warnings w
         USING ranked r
         WHERE w.id = r.id AND r.rn = $3
         RETURNING w.id",
+        guild_id_i64,
+        user_id_i64,
+        warning_number_i64,
     )
-    .bind(guild_id_i64)
-    .bind(user_id_i64)
-    .bind(warning_number_i64)
     .fetch_optional(db.pool())
     .await?;
 
@@ -140,3 +146,45 @@ pub fn now_unix_secs() -> u64 {
         .duration_since(UNIX_EPOCH)
         .map_or(0, |duration| duration.as_secs())
 }
+
+/// Insert warnings imported from another bot, as `(user_id, entry)` pairs.
+/// Returns the number of rows written.
+pub async fn import_warnings(
+    db: &Database,
+    guild_id: u64,
+    warnings: &[(u64, WarningEntry)],
+) -> anyhow::Result<u64> {
+    if warnings.is_empty() {
+        return Ok(0);
+    }
+
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let mut user_ids = Vec::with_capacity(warnings.len());
+    let mut moderator_ids = Vec::with_capacity(warnings.len());
+    let mut reasons = Vec::with_capacity(warnings.len());
+    let mut warned_at = Vec::with_capacity(warnings.len());
+    for (user_id, entry) in warnings {
+        user_ids.push(i64::try_from(*user_id).context("user_id out of i64 range")?);
+        moderator_ids
+            .push(i64::try_from(entry.moderator_id).context("moderator_id out of i64 range")?);
+        reasons.push(entry.reason.clone());
+        warned_at.push(i64::try_from(entry.warned_at).context("warned_at out of i64 range")?);
+    }
+
+    let inserted = sqlx::query!(
+        "INSERT INTO warnings (guild_id, user_id, moderator_id, reason, warned_at)
+         SELECT $1, user_id, moderator_id, reason, warned_at
+         FROM UNNEST($2::BIGINT[], $3::BIGINT[], $4::TEXT[], $5::BIGINT[])
+             AS input(user_id, moderator_id, reason, warned_at)",
+        guild_id_i64,
+        &user_ids,
+        &moderator_ids,
+        &reasons,
+        &warned_at,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    Ok(inserted)
+}
@@ -0,0 +1,179 @@
+use anyhow::Context as _;
+
+use crate::cache::invalidate_guild_config;
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+
+/// Members one guild can watch at once. Watched ids ride along in the cached
+/// guild config, so the list stays small.
+pub const MAX_WATCHED_USERS: u64 = 100;
+pub const WATCH_NOTE_MAX_CHARS: usize = 300;
+
+#[derive(Clone, Debug)]
+pub struct WatchEntry {
+    pub user_id: u64,
+    pub note: Option<String>,
+    pub added_by: u64,
+    pub created_at: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct WatchEntryRow {
+    user_id: i64,
+    note: Option<String>,
+    added_by: i64,
+    created_at: i64,
+}
+
+/// Watch a member, or replace the note on one already watched. Returns
+/// `false` when they were already on the list.
+pub async fn add_watch(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+    note: Option<&str>,
+    added_by: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let added_by = i64::try_from(added_by).context("added_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let inserted = sqlx::query_scalar!(
+        r#"INSERT INTO watchlist (guild_id, user_id, note, added_by, created_at)
+           VALUES ($1, $2, $3, $4, $5)
+           ON CONFLICT (guild_id, user_id) DO UPDATE
+           SET note = EXCLUDED.note, added_by = EXCLUDED.added_by
+           RETURNING (xmax = 0) AS "inserted!""#,
+        guild_id_i64,
+        user_id,
+        note,
+        added_by,
+        now,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(inserted)
+}
+
+/// Returns `false` if the member wasn't watched.
+pub async fn remove_watch(db: &Database, guild_id: u64, user_id: u64) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let removed = sqlx::query!(
+        "DELETE FROM watchlist WHERE guild_id = $1 AND user_id = $2",
+        guild_id_i64,
+        user_id,
+    )
+    .execute(db.pool())
+    .await?
+    .rows_affected();
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(removed > 0)
+}
+
+pub async fn count_watches(db: &Database, guild_id: u64) -> anyhow::Result<u64> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM watchlist WHERE guild_id = $1"#,
+        guild_id,
+    )
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok(u64::try_from(count).unwrap_or(0))
+}
+
+/// Watched members, most recently added first.
+pub async fn list_watches(db: &Database, guild_id: u64) -> anyhow::Result<Vec<WatchEntry>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let rows: Vec<WatchEntryRow> = sqlx::query_as!(
+        WatchEntryRow,
+        "SELECT user_id, note, added_by, created_at
+         FROM watchlist
+         WHERE guild_id = $1
+         ORDER BY created_at DESC",
+        guild_id,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter().map(to_watch_entry).collect()
+}
+
+pub async fn get_watch(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<Option<WatchEntry>> {
+    let guild_id = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let row: Option<WatchEntryRow> = sqlx::query_as!(
+        WatchEntryRow,
+        "SELECT user_id, note, added_by, created_at
+         FROM watchlist
+         WHERE guild_id = $1 AND user_id = $2",
+        guild_id,
+        user_id,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(to_watch_entry).transpose()
+}
+
+pub async fn set_watchlist_channel_id(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO guild_watchlist_config (guild_id, channel_id)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET channel_id = EXCLUDED.channel_id",
+        guild_id_i64,
+        channel_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn clear_watchlist_channel_id(db: &Database, guild_id: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "DELETE FROM guild_watchlist_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+fn to_watch_entry(row: WatchEntryRow) -> anyhow::Result<WatchEntry> {
+    Ok(WatchEntry {
+        user_id: u64::try_from(row.user_id).context("negative user_id")?,
+        note: row.note,
+        added_by: u64::try_from(row.added_by).context("negative added_by")?,
+        created_at: u64::try_from(row.created_at).context("negative created_at")?,
+    })
+}
@@ -0,0 +1,238 @@
+use anyhow::Context as _;
+use serde_json::json;
+
+use crate::database::Database;
+use crate::impls::modlog_outbox::modlog_outbox_backoff_secs;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::cases::ModerationCase;
+use autumn_utils::formatting::format_case_label;
+
+/// Deliveries are dropped after this many failed attempts.
+pub const WEBHOOK_DELIVERY_MAX_ATTEMPTS: i32 = 10;
+
+/// An external endpoint registered for a guild's moderation events.
+#[derive(Clone, Debug)]
+pub struct GuildWebhook {
+    pub id: i64,
+    pub url: String,
+    pub created_by: u64,
+    pub created_at: u64,
+}
+
+/// A signed payload waiting to be POSTed.
+#[derive(Clone, Debug)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub event: String,
+    pub payload: String,
+    pub attempts: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct GuildWebhookRow {
+    id: i64,
+    url: String,
+    created_by: i64,
+    created_at: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookDeliveryRow {
+    id: i64,
+    url: String,
+    secret: String,
+    event: String,
+    payload: String,
+    attempts: i32,
+}
+
+/// Register a webhook. Returns `None` if the URL is already registered.
+pub async fn add_guild_webhook(
+    db: &Database,
+    guild_id: u64,
+    url: &str,
+    secret: &str,
+    created_by: u64,
+) -> anyhow::Result<Option<i64>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let created_by_i64 = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let id: Option<i64> = sqlx::query_scalar!(
+        "INSERT INTO guild_webhooks (guild_id, url, secret, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (guild_id, url) DO NOTHING
+         RETURNING id",
+        guild_id_i64,
+        url,
+        secret,
+        created_by_i64,
+        now,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn list_guild_webhooks(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<GuildWebhook>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let rows: Vec<GuildWebhookRow> = sqlx::query_as!(
+        GuildWebhookRow,
+        "SELECT id, url, created_by, created_at
+         FROM guild_webhooks
+         WHERE guild_id = $1
+         ORDER BY id ASC",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(GuildWebhook {
+                id: row.id,
+                url: row.url,
+                created_by: u64::try_from(row.created_by)
+                    .context("created_by row out of u64 range")?,
+                created_at: u64::try_from(row.created_at)
+                    .context("created_at row out of u64 range")?,
+            })
+        })
+        .collect()
+}
+
+/// Remove a webhook and its pending deliveries.
+pub async fn remove_guild_webhook(db: &Database, guild_id: u64, id: i64) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM guild_webhooks WHERE guild_id = $1 AND id = $2",
+        guild_id_i64,
+        id,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Queue `event` for every webhook registered in the case's guild, inside the
+/// transaction that changed the case.
+pub(crate) async fn enqueue_case_webhooks(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    event: &str,
+    case: &ModerationCase,
+    change: Option<serde_json::Value>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(case.guild_id).context("guild_id out of i64 range")?;
+    let now = now_unix_secs();
+    let now_i64 = i64::try_from(now).context("now out of i64 range")?;
+
+    let payload = json!({
+        "event": event,
+        "guild_id": case.guild_id.to_string(),
+        "timestamp": now,
+        "case": {
+            "label": format_case_label(&case.case_code, case.action_case_number),
+            "case_number": case.case_number,
+            "action": case.action,
+            "status": case.status,
+            "target_user_id": case.target_user_id.map(|id| id.to_string()),
+            "moderator_user_id": case.moderator_user_id.to_string(),
+            "reason": case.reason,
+            "duration_seconds": case.duration_seconds,
+            "created_at": case.created_at,
+            "updated_at": case.updated_at,
+        },
+        "change": change,
+    })
+    .to_string();
+
+    sqlx::query!(
+        "INSERT INTO webhook_deliveries (webhook_id, event, payload, next_attempt_at, created_at)
+         SELECT id, $2, $3, $4, $4 FROM guild_webhooks WHERE guild_id = $1",
+        guild_id_i64,
+        event,
+        payload,
+        now_i64,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Deliveries whose next attempt is due, oldest first.
+pub async fn list_due_webhook_deliveries(
+    db: &Database,
+    limit: i64,
+) -> anyhow::Result<Vec<WebhookDelivery>> {
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let rows: Vec<WebhookDeliveryRow> = sqlx::query_as!(
+        WebhookDeliveryRow,
+        "SELECT d.id, w.url, w.secret, d.event, d.payload, d.attempts
+         FROM webhook_deliveries d
+         JOIN guild_webhooks w ON w.id = d.webhook_id
+         WHERE d.next_attempt_at <= $1 AND d.attempts < $2
+         ORDER BY d.id ASC
+         LIMIT $3",
+        now,
+        WEBHOOK_DELIVERY_MAX_ATTEMPTS,
+        limit,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| WebhookDelivery {
+            id: row.id,
+            url: row.url,
+            secret: row.secret,
+            event: row.event,
+            payload: row.payload,
+            attempts: row.attempts,
+        })
+        .collect())
+}
+
+/// Remove a delivered payload.
+pub async fn complete_webhook_delivery(db: &Database, id: i64) -> anyhow::Result<()> {
+    sqlx::query!("DELETE FROM webhook_deliveries WHERE id = $1", id,)
+        .execute(db.pool())
+        .await?;
+
+    Ok(())
+}
+
+/// Record a failed attempt and back off on the same schedule as the modlog outbox.
+pub async fn fail_webhook_delivery(
+    db: &Database,
+    id: i64,
+    attempts: i32,
+    error: &str,
+) -> anyhow::Result<()> {
+    let next_attempt_at = i64::try_from(now_unix_secs() + modlog_outbox_backoff_secs(attempts))
+        .context("next_attempt_at out of i64 range")?;
+
+    sqlx::query!(
+        "UPDATE webhook_deliveries
+         SET attempts = attempts + 1, next_attempt_at = $2, last_error = $3
+         WHERE id = $1",
+        id,
+        next_attempt_at,
+        error,
+    )
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
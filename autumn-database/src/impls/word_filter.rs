@@ -1,11 +1,16 @@
 use anyhow::Context as _;
+use autumn_utils::normalize::normalize_for_matching;
 
 use crate::cache::{
-    CONFIG_CACHE_TTL, WORD_LIST_CACHE_TTL, invalidate_word_filter, word_filter_config_key,
-    word_filter_words_key,
+    CacheTtlClass, invalidate_guild_config, invalidate_word_filter, word_filter_words_key,
 };
 use crate::database::Database;
-use crate::model::word_filter::{WordFilterConfig, WordFilterWord};
+use crate::impls::guild_config::get_guild_config;
+use crate::model::guild_config::GuildConfig;
+use crate::model::word_filter::{
+    DEFAULT_AI_THRESHOLD, WordFilterChannelOverride, WordFilterConfig, WordFilterPattern,
+    WordFilterWord,
+};
 
 /// Curated preset list of commonly offensive words that would not be allowed
 /// in most communities. These are loaded on demand via `load_preset_words`.
@@ -53,6 +58,9 @@ pub const PRESET_WORDS: &[&str] = &[
     "autist",
 ];
 
+/// Supported per-word match modes.
+pub const MATCH_MODES: &[&str] = &["whole", "contains", "startswith"];
+
 // ---------------------------------------------------------------------------
 // Config CRUD
 // ---------------------------------------------------------------------------
@@ -61,25 +69,7 @@ pub async fn get_word_filter_config(
     db: &Database,
     guild_id: u64,
 ) -> anyhow::Result<Option<WordFilterConfig>> {
-    let cache_key = word_filter_config_key(db.cache(), guild_id);
-    db.cache()
-        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
-            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
-
-            let row = sqlx::query_as::<_, (bool, String)>(
-                "SELECT enabled, action FROM word_filter_config WHERE guild_id = $1",
-            )
-            .bind(guild_id_i64)
-            .fetch_optional(db.pool())
-            .await?;
-
-            Ok(row.map(|(enabled, action)| WordFilterConfig {
-                guild_id,
-                enabled,
-                action,
-            }))
-        })
-        .await
+    Ok(get_guild_config(db, guild_id).await?.word_filter)
 }
 
 pub async fn set_word_filter_enabled(
@@ -89,13 +79,13 @@ pub async fn set_word_filter_enabled(
 ) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO word_filter_config (guild_id, enabled)
          VALUES ($1, $2)
          ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+        guild_id_i64,
+        enabled,
     )
-    .bind(guild_id_i64)
-    .bind(enabled)
     .execute(db.pool())
     .await?;
 
@@ -111,13 +101,13 @@ pub async fn set_word_filter_action(
 ) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    sqlx::query(
+    sqlx::query!(
         "INSERT INTO word_filter_config (guild_id, action)
          VALUES ($1, $2)
          ON CONFLICT (guild_id) DO UPDATE SET action = EXCLUDED.action",
+        guild_id_i64,
+        action,
     )
-    .bind(guild_id_i64)
-    .bind(action)
     .execute(db.pool())
     .await?;
 
@@ -126,6 +116,141 @@ pub async fn set_word_filter_action(
     Ok(())
 }
 
+/// Turn the second-stage LLM check on or off.
+pub async fn set_word_filter_ai_check(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO word_filter_config (guild_id, ai_check_enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET ai_check_enabled = EXCLUDED.ai_check_enabled",
+        guild_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the minimum LLM confidence (1-100) needed before the AI action runs.
+pub async fn set_word_filter_ai_threshold(
+    db: &Database,
+    guild_id: u64,
+    threshold: u8,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    anyhow::ensure!(
+        (1..=100).contains(&threshold),
+        "ai_threshold must be between 1 and 100"
+    );
+
+    sqlx::query!(
+        "INSERT INTO word_filter_config (guild_id, ai_threshold)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET ai_threshold = EXCLUDED.ai_threshold",
+        guild_id_i64,
+        i16::from(threshold),
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the action taken when the LLM flags a message.
+pub async fn set_word_filter_ai_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO word_filter_config (guild_id, ai_action)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET ai_action = EXCLUDED.ai_action",
+        guild_id_i64,
+        action,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Channel overrides
+// ---------------------------------------------------------------------------
+
+/// List all per-channel overrides for a guild.
+pub async fn list_word_filter_channel_overrides(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<WordFilterChannelOverride>> {
+    Ok(get_guild_config(db, guild_id).await?.word_filter_channels)
+}
+
+/// Enable or disable the word filter for a single channel, overriding the
+/// guild-wide setting.
+pub async fn set_word_filter_channel_override(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    sqlx::query!(
+        "INSERT INTO word_filter_channels (guild_id, channel_id, enabled)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id, channel_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+        guild_id_i64,
+        channel_id_i64,
+        enabled,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Remove a channel override so the guild-wide setting applies again.
+/// Returns `true` if an override was removed.
+pub async fn clear_word_filter_channel_override(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM word_filter_channels WHERE guild_id = $1 AND channel_id = $2",
+        guild_id_i64,
+        channel_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 // ---------------------------------------------------------------------------
 // Word CRUD
 // ---------------------------------------------------------------------------
@@ -137,8 +262,9 @@ pub async fn add_filter_word(
     guild_id: u64,
     word: &str,
     is_preset: bool,
+    match_mode: &str,
 ) -> anyhow::Result<bool> {
-    add_filter_word_internal(db, guild_id, word, is_preset, true).await
+    add_filter_word_internal(db, guild_id, word, is_preset, match_mode, true).await
 }
 
 async fn add_filter_word_internal(
@@ -146,19 +272,21 @@ async fn add_filter_word_internal(
     guild_id: u64,
     word: &str,
     is_preset: bool,
+    match_mode: &str,
     invalidate_cache: bool,
 ) -> anyhow::Result<bool> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
     let lower = word.to_lowercase();
 
-    let result = sqlx::query(
-        "INSERT INTO word_filter_words (guild_id, word, is_preset)
-         VALUES ($1, $2, $3)
+    let result = sqlx::query!(
+        "INSERT INTO word_filter_words (guild_id, word, is_preset, match_mode)
+         VALUES ($1, $2, $3, $4)
          ON CONFLICT (guild_id, word) DO NOTHING",
+        guild_id_i64,
+        &lower,
+        is_preset,
+        match_mode,
     )
-    .bind(guild_id_i64)
-    .bind(&lower)
-    .bind(is_preset)
     .execute(db.pool())
     .await?;
 
@@ -169,16 +297,50 @@ async fn add_filter_word_internal(
     Ok(result.rows_affected() > 0)
 }
 
+/// Bulk-insert custom words in a single query with one cache invalidation.
+/// Duplicates are skipped. Returns the number of newly inserted words.
+pub async fn import_filter_words(
+    db: &Database,
+    guild_id: u64,
+    patterns: &[WordFilterPattern],
+) -> anyhow::Result<u64> {
+    if patterns.is_empty() {
+        return Ok(0);
+    }
+
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let words: Vec<String> = patterns.iter().map(|p| p.word.to_lowercase()).collect();
+    let modes: Vec<String> = patterns.iter().map(|p| p.match_mode.clone()).collect();
+
+    let result = sqlx::query!(
+        "INSERT INTO word_filter_words (guild_id, word, is_preset, match_mode)
+         SELECT $1, word, FALSE, match_mode
+         FROM UNNEST($2::TEXT[], $3::TEXT[]) AS input(word, match_mode)
+         ON CONFLICT (guild_id, word) DO NOTHING",
+        guild_id_i64,
+        &words,
+        &modes,
+    )
+    .execute(db.pool())
+    .await?;
+
+    invalidate_word_filter(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected())
+}
+
 /// Remove a word from the guild's filter list. Returns `true` if removed.
 pub async fn remove_filter_word(db: &Database, guild_id: u64, word: &str) -> anyhow::Result<bool> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
     let lower = word.to_lowercase();
 
-    let result = sqlx::query("DELETE FROM word_filter_words WHERE guild_id = $1 AND word = $2")
-        .bind(guild_id_i64)
-        .bind(&lower)
-        .execute(db.pool())
-        .await?;
+    let result = sqlx::query!(
+        "DELETE FROM word_filter_words WHERE guild_id = $1 AND word = $2",
+        guild_id_i64,
+        &lower,
+    )
+    .execute(db.pool())
+    .await?;
 
     invalidate_word_filter(db.cache(), guild_id).await?;
 
@@ -192,56 +354,182 @@ pub async fn list_filter_words(
 ) -> anyhow::Result<Vec<WordFilterWord>> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    let rows = sqlx::query_as::<_, (i64, i64, String, bool, i64)>(
-        "SELECT id, guild_id, word, is_preset, created_at
+    let rows = sqlx::query!(
+        "SELECT id, guild_id, word, is_preset, match_mode, synced_to_automod, created_at
          FROM word_filter_words
          WHERE guild_id = $1
          ORDER BY word ASC",
+        guild_id_i64,
     )
-    .bind(guild_id_i64)
     .fetch_all(db.pool())
     .await?;
 
     rows.into_iter()
-        .map(|(id, gid, word, is_preset, created_at)| {
+        .map(|row| {
             Ok(WordFilterWord {
-                id: u64::try_from(id).context("id out of u64 range")?,
-                guild_id: u64::try_from(gid).context("guild_id out of u64 range")?,
-                word,
-                is_preset,
-                created_at: u64::try_from(created_at).context("created_at out of u64 range")?,
+                id: u64::try_from(row.id).context("id out of u64 range")?,
+                guild_id: u64::try_from(row.guild_id).context("guild_id out of u64 range")?,
+                word: row.word,
+                is_preset: row.is_preset,
+                match_mode: row.match_mode,
+                synced_to_automod: row.synced_to_automod,
+                created_at: u64::try_from(row.created_at).context("created_at out of u64 range")?,
             })
         })
         .collect()
 }
 
-/// Get just the word strings for a guild (used by the event handler for fast matching).
+/// Get the words and match modes for a guild (used by the event handler for fast matching).
 pub async fn get_all_filter_words_for_guild(
     db: &Database,
     guild_id: u64,
-) -> anyhow::Result<Vec<String>> {
+) -> anyhow::Result<Vec<WordFilterPattern>> {
     let cache_key = word_filter_words_key(db.cache(), guild_id);
     db.cache()
-        .get_or_load_json(&cache_key, WORD_LIST_CACHE_TTL, || async {
-            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
-
-            let words: Vec<String> =
-                sqlx::query_scalar("SELECT word FROM word_filter_words WHERE guild_id = $1")
-                    .bind(guild_id_i64)
-                    .fetch_all(db.pool())
-                    .await?;
-
-            Ok(words)
+        .get_or_load_json(&cache_key, db.cache().ttl(CacheTtlClass::WordList), || {
+            load_filter_patterns(db, guild_id)
         })
         .await
 }
 
+async fn load_filter_patterns(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<WordFilterPattern>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let rows = sqlx::query_as!(
+        WordFilterPattern,
+        "SELECT word, match_mode FROM word_filter_words WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(rows)
+}
+
+/// Run message content through the word filter matching pipeline and return
+/// the first pattern that triggers, if any.
+///
+/// Both the lowercased content and its normalized form (confusables, leet
+/// speak, zero-width characters) are checked so evasion attempts are caught
+/// without losing matches on words that legitimately contain digits.
+pub fn find_matching_pattern<'a>(
+    content: &str,
+    patterns: &'a [WordFilterPattern],
+) -> Option<&'a WordFilterPattern> {
+    let content_lower = content.to_lowercase();
+    let normalized = normalize_for_matching(content);
+    patterns.iter().find(|pattern| {
+        content_matches_pattern(&content_lower, pattern)
+            || content_matches_pattern(&normalized, pattern)
+    })
+}
+
+/// Check whether already-lowercased `content` matches `pattern` under its match mode.
+///
+/// `whole` matches complete tokens only, `startswith` matches tokens that begin
+/// with the word, and `contains` matches anywhere in the content.
+pub fn content_matches_pattern(content: &str, pattern: &WordFilterPattern) -> bool {
+    let word = pattern.word.as_str();
+    if word.is_empty() {
+        return false;
+    }
+
+    let mut tokens = content.split(|c: char| !c.is_alphanumeric());
+    match pattern.match_mode.as_str() {
+        "contains" => content.contains(word),
+        "startswith" => tokens.any(|token| token.starts_with(word)),
+        _ => tokens.any(|token| token == word),
+    }
+}
+
+/// Discord AutoMod limits for keyword rules.
+pub const AUTOMOD_MAX_KEYWORDS: usize = 1000;
+pub const AUTOMOD_MAX_KEYWORD_LENGTH: usize = 60;
+
+/// Convert a filter pattern to a Discord AutoMod keyword using its wildcard
+/// syntax (`word`, `word*`, `*word*`). Returns `None` if the keyword would be
+/// too long or contains a `*` of its own.
+pub fn automod_keyword(pattern: &WordFilterPattern) -> Option<String> {
+    let word = pattern.word.as_str();
+    if word.is_empty() || word.contains('*') {
+        return None;
+    }
+
+    let keyword = match pattern.match_mode.as_str() {
+        "contains" => format!("*{}*", word),
+        "startswith" => format!("{}*", word),
+        _ => word.to_owned(),
+    };
+
+    (keyword.chars().count() <= AUTOMOD_MAX_KEYWORD_LENGTH).then_some(keyword)
+}
+
+/// Get the id of the Discord AutoMod rule the word list was last synced to.
+pub async fn get_word_filter_automod_rule_id(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<u64>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let rule_id: Option<Option<i64>> = sqlx::query_scalar!(
+        "SELECT automod_rule_id FROM word_filter_config WHERE guild_id = $1",
+        guild_id_i64,
+    )
+    .fetch_optional(db.pool())
+    .await?;
+
+    rule_id
+        .flatten()
+        .map(|id| u64::try_from(id).context("automod_rule_id out of u64 range"))
+        .transpose()
+}
+
+/// Record a completed AutoMod sync: store the rule id and flag exactly the
+/// given words as synced.
+pub async fn record_word_filter_automod_sync(
+    db: &Database,
+    guild_id: u64,
+    rule_id: u64,
+    synced_words: &[String],
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let rule_id_i64 = i64::try_from(rule_id).context("rule_id out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    sqlx::query!(
+        "INSERT INTO word_filter_config (guild_id, automod_rule_id)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET automod_rule_id = EXCLUDED.automod_rule_id",
+        guild_id_i64,
+        rule_id_i64,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE word_filter_words SET synced_to_automod = (word = ANY($2))
+         WHERE guild_id = $1",
+        guild_id_i64,
+        synced_words,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
 /// Load all preset words into the guild's filter list. Duplicates are skipped.
 /// Returns the number of newly inserted words.
 pub async fn load_preset_words(db: &Database, guild_id: u64) -> anyhow::Result<u64> {
     let mut inserted: u64 = 0;
     for word in PRESET_WORDS {
-        if add_filter_word_internal(db, guild_id, word, true, false).await? {
+        if add_filter_word_internal(db, guild_id, word, true, "whole", false).await? {
             inserted += 1;
         }
     }
@@ -257,17 +545,73 @@ pub async fn load_preset_words(db: &Database, guild_id: u64) -> anyhow::Result<u
 pub async fn clear_preset_words(db: &Database, guild_id: u64) -> anyhow::Result<u64> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
 
-    let result =
-        sqlx::query("DELETE FROM word_filter_words WHERE guild_id = $1 AND is_preset = TRUE")
-            .bind(guild_id_i64)
-            .execute(db.pool())
-            .await?;
+    let result = sqlx::query!(
+        "DELETE FROM word_filter_words WHERE guild_id = $1 AND is_preset = TRUE",
+        guild_id_i64,
+    )
+    .execute(db.pool())
+    .await?;
 
     invalidate_word_filter(db.cache(), guild_id).await?;
 
     Ok(result.rows_affected())
 }
 
+/// Resolve the word filter config for a specific channel, applying any
+/// channel override on top of the guild-wide enabled flag. Returns `None` when
+/// the filter should not run in that channel.
+pub async fn get_word_filter_for_channel(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<Option<WordFilterConfig>> {
+    let config = get_guild_config(db, guild_id).await?;
+    Ok(resolve_channel_config(&config, channel_id))
+}
+
+fn resolve_channel_config(config: &GuildConfig, channel_id: u64) -> Option<WordFilterConfig> {
+    let channel_override = config
+        .word_filter_channels
+        .iter()
+        .find(|entry| entry.channel_id == channel_id)
+        .map(|entry| entry.enabled);
+
+    let enabled = channel_override.unwrap_or_else(|| {
+        config
+            .word_filter
+            .as_ref()
+            .is_some_and(|word_filter| word_filter.enabled)
+    });
+    if !enabled {
+        return None;
+    }
+
+    Some(config.word_filter.clone().unwrap_or(WordFilterConfig {
+        guild_id: config.guild_id,
+        enabled: true,
+        action: "log_only".to_owned(),
+        ai_check: false,
+        ai_threshold: DEFAULT_AI_THRESHOLD,
+        ai_action: "log_only".to_owned(),
+    }))
+}
+
+/// The channel-resolved word filter config and the guild's word list, or
+/// `None` when the filter does not run in `channel_id`. The word list is only
+/// read when the filter applies.
+pub async fn get_word_filter_for_message(
+    db: &Database,
+    config: &GuildConfig,
+    channel_id: u64,
+) -> anyhow::Result<Option<(WordFilterConfig, Vec<WordFilterPattern>)>> {
+    let Some(word_filter) = resolve_channel_config(config, channel_id) else {
+        return Ok(None);
+    };
+
+    let words = get_all_filter_words_for_guild(db, config.guild_id).await?;
+    Ok(Some((word_filter, words)))
+}
+
 /// Check whether the word filter is enabled for a guild and return the config.
 pub async fn get_word_filter_if_enabled(
     db: &Database,
@@ -279,3 +623,103 @@ pub async fn get_word_filter_if_enabled(
         _ => Ok(None),
     }
 }
+
+/// Filter words containing `partial`, ignoring case, for autocomplete: words
+/// starting with it first, then alphabetically, at most `limit`.
+pub fn suggest_filter_words(
+    patterns: &[WordFilterPattern],
+    partial: &str,
+    limit: usize,
+) -> Vec<String> {
+    let partial = partial.trim().to_lowercase();
+    let mut matches: Vec<&str> = patterns
+        .iter()
+        .map(|pattern| pattern.word.as_str())
+        .filter(|word| word.to_lowercase().contains(&partial))
+        .collect();
+    matches.sort_by_cached_key(|word| {
+        let lower = word.to_lowercase();
+        (!lower.starts_with(&partial), lower)
+    });
+    matches.dedup();
+
+    matches.into_iter().take(limit).map(str::to_owned).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        automod_keyword, content_matches_pattern, find_matching_pattern, suggest_filter_words,
+    };
+    use crate::model::word_filter::WordFilterPattern;
+
+    fn pattern(word: &str, match_mode: &str) -> WordFilterPattern {
+        WordFilterPattern {
+            word: word.to_owned(),
+            match_mode: match_mode.to_owned(),
+        }
+    }
+
+    #[test]
+    fn match_modes_respect_token_boundaries() {
+        let whole = pattern("fag", "whole");
+        assert!(content_matches_pattern("you fag!", &whole));
+        assert!(!content_matches_pattern("leafage", &whole));
+        assert!(!content_matches_pattern("fagz", &whole));
+
+        let startswith = pattern("fag", "startswith");
+        assert!(content_matches_pattern("fagz lol", &startswith));
+        assert!(!content_matches_pattern("leafage", &startswith));
+
+        let contains = pattern("fag", "contains");
+        assert!(content_matches_pattern("leafage", &contains));
+        assert!(!content_matches_pattern("hello", &contains));
+    }
+
+    #[test]
+    fn pipeline_catches_normalized_evasion() {
+        let patterns = vec![pattern("tard", "whole"), pattern("h4x", "whole")];
+        assert!(find_matching_pattern("you t4rd", &patterns).is_some());
+        assert!(find_matching_pattern("you ｔａ\u{200B}ｒｄ", &patterns).is_some());
+        assert!(find_matching_pattern("H4X", &patterns).is_some());
+        assert!(find_matching_pattern("standard", &patterns).is_none());
+    }
+
+    #[test]
+    fn suggestions_put_prefix_matches_first() {
+        let patterns = vec![
+            pattern("scam", "whole"),
+            pattern("crypto scam", "contains"),
+            pattern("Scammer", "startswith"),
+            pattern("spam", "whole"),
+        ];
+
+        assert_eq!(
+            suggest_filter_words(&patterns, "sca", 25),
+            vec!["scam", "Scammer", "crypto scam"]
+        );
+        assert_eq!(
+            suggest_filter_words(&patterns, "", 2),
+            vec!["crypto scam", "scam"]
+        );
+        assert!(suggest_filter_words(&patterns, "zzz", 25).is_empty());
+    }
+
+    #[test]
+    fn converts_patterns_to_automod_keywords() {
+        assert_eq!(
+            automod_keyword(&pattern("tard", "whole")),
+            Some("tard".to_owned())
+        );
+        assert_eq!(
+            automod_keyword(&pattern("tard", "startswith")),
+            Some("tard*".to_owned())
+        );
+        assert_eq!(
+            automod_keyword(&pattern("tard", "contains")),
+            Some("*tard*".to_owned())
+        );
+        assert_eq!(automod_keyword(&pattern("a*b", "whole")), None);
+        assert_eq!(automod_keyword(&pattern(&"x".repeat(59), "contains")), None);
+    }
+}
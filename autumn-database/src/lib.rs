@@ -2,6 +2,8 @@ pub mod cache;
 pub mod database;
 pub mod impls;
 pub mod model;
+pub mod repository;
+pub mod resilience;
 
 pub use cache::CacheService;
 pub use database::{Database, MIGRATOR};
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AntiraidConfig {
+    pub guild_id: i64,
+    pub enabled: bool,
+    pub join_threshold: i32,
+    pub join_window_seconds: i64,
+    pub raid_duration_seconds: i64,
+    pub raise_verification: bool,
+    pub kick_new_joins: bool,
+    pub raid_active_until: Option<i64>,
+    pub previous_verification_level: Option<i16>,
+}
+
+impl AntiraidConfig {
+    /// Whether the guild is currently in raid state at unix time `now`.
+    pub fn raid_active(&self, now: i64) -> bool {
+        self.raid_active_until.is_some_and(|until| until > now)
+    }
+
+    /// Joins allowed inside one window before it counts as a raid.
+    pub fn join_threshold(&self) -> u64 {
+        u64::try_from(self.join_threshold.max(1)).unwrap_or(1)
+    }
+
+    /// Length of the window joins are counted in, in seconds.
+    pub fn join_window(&self) -> u64 {
+        u64::try_from(self.join_window_seconds.max(1)).unwrap_or(1)
+    }
+
+    /// Whether `join_count` joins inside one window make a raid.
+    pub fn exceeds_threshold(&self, join_count: u64) -> bool {
+        join_count > self.join_threshold()
+    }
+
+    /// When a raid starting at unix time `now` ends.
+    pub fn raid_ends_at(&self, now: i64) -> i64 {
+        now.saturating_add(self.raid_duration_seconds.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(join_threshold: i32, join_window_seconds: i64) -> AntiraidConfig {
+        AntiraidConfig {
+            guild_id: 1,
+            enabled: true,
+            join_threshold,
+            join_window_seconds,
+            raid_duration_seconds: 600,
+            raise_verification: false,
+            kick_new_joins: false,
+            raid_active_until: None,
+            previous_verification_level: None,
+        }
+    }
+
+    #[test]
+    fn raid_starts_only_past_the_threshold() {
+        let config = config(10, 30);
+
+        assert!(!config.exceeds_threshold(9));
+        assert!(!config.exceeds_threshold(10));
+        assert!(config.exceeds_threshold(11));
+    }
+
+    #[test]
+    fn threshold_and_window_are_at_least_one() {
+        let config = config(0, -5);
+
+        assert_eq!(config.join_threshold(), 1);
+        assert_eq!(config.join_window(), 1);
+        assert!(!config.exceeds_threshold(1));
+        assert!(config.exceeds_threshold(2));
+    }
+
+    #[test]
+    fn raid_stays_active_until_it_ends() {
+        let mut config = config(10, 30);
+        assert!(!config.raid_active(1_000));
+
+        config.raid_active_until = Some(config.raid_ends_at(1_000));
+
+        assert_eq!(config.raid_active_until, Some(1_600));
+        assert!(config.raid_active(1_599));
+        assert!(!config.raid_active(1_600));
+    }
+
+    #[test]
+    fn raid_lasts_at_least_a_second() {
+        let mut config = config(10, 30);
+        config.raid_duration_seconds = 0;
+
+        assert_eq!(config.raid_ends_at(1_000), 1_001);
+    }
+}
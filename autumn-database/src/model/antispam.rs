@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AntispamConfig {
+    pub guild_id: i64,
+    pub enabled: bool,
+    pub message_threshold: i32,
+    pub message_window_seconds: i64,
+    pub duplicate_threshold: i32,
+    pub duplicate_window_seconds: i64,
+    pub action: String,
+    /// Action for repeated messages; `action` covers the message rate.
+    pub duplicate_action: String,
+}
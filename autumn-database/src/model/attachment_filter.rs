@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AttachmentFilterConfig {
+    pub guild_id: u64,
+    pub enabled: bool,
+    pub action: String,
+    /// Lowercased extensions (without the dot) blocked in every channel.
+    pub blocked_extensions: Vec<String>,
+    /// Channels where every attachment is blocked.
+    pub blocked_channel_ids: Vec<u64>,
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::antiraid::AntiraidConfig;
+use crate::model::guild_config::GuildConfig;
+use crate::model::modlog_template::ModlogTemplate;
+use crate::model::name_filter::NameFilterConfig;
+
+/// Format version written by `!backup create`; restore rejects anything else.
+pub const GUILD_BACKUP_VERSION: u32 = 1;
+
+/// A guild's configuration and moderation history as exported by
+/// `!backup create`. Row ids are left out so the backup can be restored into a
+/// different guild; channel, role and user ids are kept as they were.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GuildBackup {
+    pub version: u32,
+    pub source_guild_id: u64,
+    pub created_at: u64,
+    pub config: GuildConfig,
+    pub serverlog_channel_id: Option<u64>,
+    pub antiraid: Option<AntiraidConfig>,
+    pub name_filter: Option<NameFilterConfig>,
+    pub modlog_template: Option<ModlogTemplate>,
+    pub words: Vec<BackupWord>,
+    pub link_domains: Vec<BackupLinkDomain>,
+    pub exemptions: Vec<BackupExemption>,
+    pub cases: Vec<BackupCase>,
+    pub case_events: Vec<BackupCaseEvent>,
+    pub warnings: Vec<BackupWarning>,
+    pub notes: Vec<BackupNote>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupWord {
+    pub word: String,
+    pub is_preset: bool,
+    pub match_mode: String,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupLinkDomain {
+    pub domain: String,
+    pub list_kind: String,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupExemption {
+    pub scope: String,
+    pub target_kind: String,
+    pub target_id: i64,
+    pub created_by: i64,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupCase {
+    pub case_number: i64,
+    pub case_code: String,
+    pub action_case_number: i64,
+    pub target_user_id: Option<i64>,
+    pub moderator_user_id: i64,
+    pub action: String,
+    pub reason: String,
+    pub status: String,
+    pub duration_seconds: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub anonymized_at: Option<i64>,
+}
+
+/// A case event, linked to its case by guild case number.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupCaseEvent {
+    pub case_number: i64,
+    pub event_type: String,
+    pub actor_user_id: i64,
+    pub old_reason: Option<String>,
+    pub new_reason: Option<String>,
+    pub note: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupWarning {
+    pub user_id: i64,
+    pub moderator_id: i64,
+    pub reason: String,
+    pub warned_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupNote {
+    pub target_user_id: i64,
+    pub author_user_id: i64,
+    pub content: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
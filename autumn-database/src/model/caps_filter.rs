@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CapsFilterConfig {
+    pub guild_id: i64,
+    pub enabled: bool,
+    pub min_length: i32,
+    pub max_caps_percent: i32,
+    pub action: String,
+}
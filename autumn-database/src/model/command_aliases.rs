@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// A guild-defined prefix alias for a command.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandAlias {
+    pub alias: String,
+    pub command_name: String,
+    pub created_by: u64,
+    pub created_at: u64,
+}
@@ -0,0 +1,17 @@
+/// Aggregated command invocations for a guild over a time window.
+#[derive(Debug, Clone, Default)]
+pub struct CommandUsageStats {
+    pub total: u64,
+    pub errors: u64,
+    pub avg_latency_ms: u64,
+    /// Most used commands first.
+    pub commands: Vec<CommandUsageSummary>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandUsageSummary {
+    pub command: String,
+    pub total: u64,
+    pub errors: u64,
+    pub avg_latency_ms: u64,
+}
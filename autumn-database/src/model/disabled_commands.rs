@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// A command switched off guild-wide (`channel_id` is `None`) or in one
+/// channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisabledCommand {
+    pub command_name: String,
+    pub channel_id: Option<u64>,
+    pub created_by: u64,
+    pub created_at: u64,
+}
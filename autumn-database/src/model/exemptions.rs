@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModerationExemption {
+    pub id: u64,
+    pub guild_id: u64,
+    pub scope: String,
+    pub target_kind: String,
+    pub target_id: u64,
+    pub created_by: u64,
+    pub created_at: u64,
+}
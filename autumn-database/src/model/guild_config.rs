@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::antispam::AntispamConfig;
+use crate::model::attachment_filter::AttachmentFilterConfig;
+use crate::model::caps_filter::CapsFilterConfig;
+use crate::model::escalation::EscalationConfig;
+use crate::model::image_filter::ImageFilterConfig;
+use crate::model::invite_filter::InviteFilterConfig;
+use crate::model::link_filter::LinkFilterConfig;
+use crate::model::word_filter::{WordFilterChannelOverride, WordFilterConfig};
+
+/// The per-guild settings read while handling messages, loaded with one query
+/// and cached under one key. A section is `None` when the guild has never
+/// configured it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GuildConfig {
+    pub guild_id: u64,
+    pub modlog_channel_id: Option<u64>,
+    pub purge_transcripts: bool,
+    pub userlog_channel_id: Option<u64>,
+    pub llm_enabled: bool,
+    /// Replaces the default LLM persona when set.
+    pub llm_system_prompt: Option<String>,
+    /// Offer an LLM-written reason for `!warn` replies without one.
+    #[serde(default)]
+    pub llm_suggest_reasons: bool,
+    /// Answer mentions in a new thread that continues without mentions.
+    #[serde(default)]
+    pub llm_threads: bool,
+    /// Language `!translate` targets when set.
+    pub translate_language: Option<String>,
+    /// Keep moderators from actioning members at or above their own role.
+    #[serde(default)]
+    pub enforce_mod_hierarchy: bool,
+    /// Accent color for the bot's embeds; the default when unset.
+    #[serde(default)]
+    pub embed_color: Option<u32>,
+    /// What happens when a globally blacklisted user joins; ban when unset.
+    #[serde(default)]
+    pub global_blacklist_action: Option<String>,
+    /// Bans and terminations wait for a second moderator's approval.
+    #[serde(default)]
+    pub dual_approval: bool,
+    /// Where activity from watched members is posted.
+    #[serde(default)]
+    pub watchlist_channel_id: Option<u64>,
+    #[serde(default)]
+    pub watched_user_ids: Vec<u64>,
+    /// Members whose messages are silently deleted, until each mute ends.
+    #[serde(default)]
+    pub shadow_mutes: Vec<ShadowMuteExpiry>,
+    pub escalation: Option<EscalationConfig>,
+    pub antispam: Option<AntispamConfig>,
+    pub word_filter: Option<WordFilterConfig>,
+    pub word_filter_channels: Vec<WordFilterChannelOverride>,
+    pub invite_filter: Option<InviteFilterConfig>,
+    pub attachment_filter: Option<AttachmentFilterConfig>,
+    pub link_filter: Option<LinkFilterConfig>,
+    pub caps_filter: Option<CapsFilterConfig>,
+    pub image_filter: Option<ImageFilterConfig>,
+}
+
+/// A shadow-muted member and when the mute ends, in unix seconds.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ShadowMuteExpiry {
+    pub user_id: u64,
+    pub expires_at: u64,
+}
+
+impl GuildConfig {
+    /// Whether activity from `user_id` should be posted to the watchlist
+    /// channel.
+    pub fn watchlist_alert_channel(&self, user_id: u64) -> Option<u64> {
+        self.watchlist_channel_id
+            .filter(|_| self.watched_user_ids.contains(&user_id))
+    }
+
+    /// Whether `user_id` is shadow muted at `now`. A mute past its expiry no
+    /// longer counts, even if the job lifting it hasn't run.
+    pub fn is_shadow_muted(&self, user_id: u64, now: u64) -> bool {
+        self.shadow_mutes
+            .iter()
+            .any(|mute| mute.user_id == user_id && mute.expires_at > now)
+    }
+
+    pub fn escalation_if_enabled(&self) -> Option<&EscalationConfig> {
+        self.escalation.as_ref().filter(|cfg| cfg.enabled)
+    }
+
+    pub fn antispam_if_enabled(&self) -> Option<&AntispamConfig> {
+        self.antispam.as_ref().filter(|cfg| cfg.enabled)
+    }
+
+    pub fn invite_filter_if_enabled(&self) -> Option<&InviteFilterConfig> {
+        self.invite_filter.as_ref().filter(|cfg| cfg.enabled)
+    }
+
+    pub fn attachment_filter_if_enabled(&self) -> Option<&AttachmentFilterConfig> {
+        self.attachment_filter.as_ref().filter(|cfg| cfg.enabled)
+    }
+
+    pub fn link_filter_if_enabled(&self) -> Option<&LinkFilterConfig> {
+        self.link_filter.as_ref().filter(|cfg| cfg.enabled)
+    }
+
+    pub fn caps_filter_if_enabled(&self) -> Option<&CapsFilterConfig> {
+        self.caps_filter.as_ref().filter(|cfg| cfg.enabled)
+    }
+
+    pub fn image_filter_if_enabled(&self) -> Option<&ImageFilterConfig> {
+        self.image_filter.as_ref().filter(|cfg| cfg.enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GuildConfig;
+
+    #[test]
+    fn expired_shadow_mutes_no_longer_count() {
+        let config: GuildConfig = serde_json::from_value(serde_json::json!({
+            "guild_id": 1,
+            "purge_transcripts": false,
+            "llm_enabled": false,
+            "word_filter_channels": [],
+            "shadow_mutes": [
+                { "user_id": 10, "expires_at": 1_000 },
+                { "user_id": 20, "expires_at": 5_000 },
+            ],
+        }))
+        .expect("config deserializes");
+
+        assert!(config.is_shadow_muted(10, 999));
+        assert!(!config.is_shadow_muted(10, 1_000));
+        assert!(config.is_shadow_muted(20, 1_000));
+        assert!(!config.is_shadow_muted(30, 0));
+    }
+
+    #[test]
+    fn watchlist_alerts_need_a_channel_and_a_watched_member() {
+        let mut config: GuildConfig = serde_json::from_value(serde_json::json!({
+            "guild_id": 1,
+            "purge_transcripts": false,
+            "llm_enabled": false,
+            "word_filter_channels": [],
+            "watched_user_ids": [10],
+        }))
+        .expect("config deserializes");
+        assert_eq!(config.watchlist_alert_channel(10), None);
+
+        config.watchlist_channel_id = Some(500);
+        assert_eq!(config.watchlist_alert_channel(10), Some(500));
+        assert_eq!(config.watchlist_alert_channel(20), None);
+    }
+}
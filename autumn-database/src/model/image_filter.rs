@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImageFilterConfig {
+    pub guild_id: u64,
+    pub enabled: bool,
+    /// Minimum LLM confidence, in percent, before `action` runs.
+    pub threshold: u8,
+    pub action: String,
+}
+
+pub const DEFAULT_IMAGE_FILTER_THRESHOLD: u8 = 80;
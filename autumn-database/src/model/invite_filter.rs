@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InviteFilterConfig {
+    pub guild_id: u64,
+    pub enabled: bool,
+    pub action: String,
+    /// Guilds whose invites are allowed, in addition to the guild itself.
+    pub allowed_guild_ids: Vec<u64>,
+}
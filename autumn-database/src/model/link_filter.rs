@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinkFilterConfig {
+    pub guild_id: u64,
+    pub enabled: bool,
+    pub mode: String,
+    pub action: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinkFilterDomain {
+    pub id: u64,
+    pub guild_id: u64,
+    pub domain: String,
+    pub list_kind: String,
+    pub created_at: u64,
+}
@@ -1,7 +1,25 @@
+pub mod antiraid;
+pub mod antispam;
+pub mod attachment_filter;
+pub mod backup;
+pub mod caps_filter;
 pub mod cases;
+pub mod command_aliases;
+pub mod command_usage;
+pub mod disabled_commands;
 pub mod escalation;
+pub mod exemptions;
+pub mod guild_config;
+pub mod image_filter;
+pub mod invite_filter;
 pub mod leveling;
+pub mod link_filter;
 pub mod llm_chat;
+pub mod modlog_template;
+pub mod name_filter;
 pub mod notes;
+pub mod permission_levels;
+pub mod raidmode;
+pub mod snipe;
 pub mod warnings;
 pub mod word_filter;
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use autumn_utils::modlog_template::ModlogLayout;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ModlogTemplate {
+    pub guild_id: i64,
+    /// Comma-separated template field keys.
+    pub field_order: Option<String>,
+    pub color: Option<i32>,
+    pub footer: Option<String>,
+}
+
+impl ModlogTemplate {
+    pub fn layout(&self) -> ModlogLayout {
+        ModlogLayout {
+            field_order: self
+                .field_order
+                .as_deref()
+                .map(|order| order.split(',').map(str::to_owned).collect()),
+            color: self.color.and_then(|color| u32::try_from(color).ok()),
+            footer: self.footer.clone(),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct NameFilterConfig {
+    pub guild_id: i64,
+    pub enabled: bool,
+    pub action: String,
+    /// Nickname given to members whose name matches the word filter.
+    pub placeholder: String,
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Bot permission levels, lowest first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionLevel {
+    Helper,
+    Mod,
+    Admin,
+}
+
+impl PermissionLevel {
+    pub const ALL: [Self; 3] = [Self::Helper, Self::Mod, Self::Admin];
+
+    /// Stored form, also accepted by [`Self::parse`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Helper => "helper",
+            Self::Mod => "mod",
+            Self::Admin => "admin",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Helper => "Helper",
+            Self::Mod => "Mod",
+            Self::Admin => "Admin",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "helper" => Some(Self::Helper),
+            "mod" | "moderator" => Some(Self::Mod),
+            "admin" | "administrator" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PermissionLevelRole {
+    pub role_id: u64,
+    pub level: PermissionLevel,
+    pub created_by: u64,
+    pub created_at: u64,
+}
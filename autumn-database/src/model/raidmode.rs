@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Guild settings captured when raid mode was turned on.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RaidmodeSnapshot {
+    pub guild_id: i64,
+    pub enabled_by: i64,
+    pub enabled_at: i64,
+    pub verification_level: i16,
+    pub invites_disabled: bool,
+    pub word_filter_enabled: bool,
+    pub word_filter_action: String,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// The last deleted or edited message in a channel, kept briefly in the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnipedMessage {
+    pub message_id: u64,
+    pub author_user_id: u64,
+    /// Content before the delete/edit.
+    pub content: String,
+    /// Content after the edit; `None` for deletions.
+    pub edited_content: Option<String>,
+    pub attachment_summary: Option<String>,
+    pub sniped_at: u64,
+}
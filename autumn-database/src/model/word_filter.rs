@@ -5,6 +5,24 @@ pub struct WordFilterConfig {
     pub guild_id: u64,
     pub enabled: bool,
     pub action: String,
+    /// Ask the LLM about messages the word list lets through.
+    #[serde(default)]
+    pub ai_check: bool,
+    /// Minimum LLM confidence, in percent, before `ai_action` runs.
+    #[serde(default = "default_ai_threshold")]
+    pub ai_threshold: u8,
+    #[serde(default = "default_ai_action")]
+    pub ai_action: String,
+}
+
+pub const DEFAULT_AI_THRESHOLD: u8 = 80;
+
+fn default_ai_threshold() -> u8 {
+    DEFAULT_AI_THRESHOLD
+}
+
+fn default_ai_action() -> String {
+    "log_only".to_owned()
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -13,5 +31,22 @@ pub struct WordFilterWord {
     pub guild_id: u64,
     pub word: String,
     pub is_preset: bool,
+    pub match_mode: String,
+    /// Whether the word was included in the last Discord AutoMod sync.
+    pub synced_to_automod: bool,
     pub created_at: u64,
 }
+
+/// A filtered word and how it should be matched against message content.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WordFilterPattern {
+    pub word: String,
+    pub match_mode: String,
+}
+
+/// Per-channel override of the guild-wide word filter enabled flag.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WordFilterChannelOverride {
+    pub channel_id: u64,
+    pub enabled: bool,
+}
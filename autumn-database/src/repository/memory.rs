@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::{CaseRepository, EscalationRepository, RepositoryFuture, WarningRepository};
+use crate::impls::cases::{CaseFilters, NewCase, action_code, is_timeout_action};
+use crate::impls::warnings::now_unix_secs;
+use crate::model::cases::CaseSummary;
+use crate::model::escalation::EscalationConfig;
+use crate::model::warnings::{WarningEntry, WarningRecord};
+
+/// Repositories backed by in-process collections, for tests.
+#[derive(Debug, Default)]
+pub struct InMemoryRepository {
+    cases: Mutex<Vec<(u64, CaseSummary)>>,
+    warnings: Mutex<Vec<(u64, u64, WarningEntry)>>,
+    escalation: Mutex<HashMap<u64, EscalationConfig>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_escalation_config(&self, guild_id: u64, config: EscalationConfig) {
+        self.escalation
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(guild_id, config);
+    }
+
+    /// Every case recorded for `guild_id`, oldest first.
+    pub fn cases(&self, guild_id: u64) -> Vec<CaseSummary> {
+        self.cases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(case_guild_id, _)| *case_guild_id == guild_id)
+            .map(|(_, case)| case.clone())
+            .collect()
+    }
+}
+
+impl CaseRepository for InMemoryRepository {
+    fn create_case<'a>(&'a self, new_case: NewCase<'a>) -> RepositoryFuture<'a, CaseSummary> {
+        Box::pin(async move {
+            let mut cases = self.cases.lock().unwrap_or_else(|e| e.into_inner());
+            let case_code = action_code(new_case.action);
+            let in_guild = || {
+                cases
+                    .iter()
+                    .filter(|(guild_id, _)| *guild_id == new_case.guild_id)
+            };
+            let case_number = in_guild().count() as u64 + 1;
+            let action_case_number = in_guild()
+                .filter(|(_, case)| case.case_code == case_code)
+                .count() as u64
+                + 1;
+
+            let case = CaseSummary {
+                case_number,
+                case_code: case_code.to_owned(),
+                action_case_number,
+                target_user_id: new_case.target_user_id,
+                moderator_user_id: new_case.moderator_user_id,
+                action: new_case.action.to_owned(),
+                reason: new_case.reason.to_owned(),
+                duration_seconds: new_case.duration_seconds,
+                created_at: now_unix_secs(),
+            };
+            cases.push((new_case.guild_id, case.clone()));
+            Ok(case)
+        })
+    }
+
+    fn list_recent_cases<'a>(
+        &'a self,
+        guild_id: u64,
+        filters: CaseFilters<'a>,
+    ) -> RepositoryFuture<'a, Vec<CaseSummary>> {
+        Box::pin(async move {
+            let mut cases: Vec<CaseSummary> = self
+                .cases(guild_id)
+                .into_iter()
+                .filter(|case| {
+                    filters
+                        .target_user_id
+                        .is_none_or(|user_id| case.target_user_id == Some(user_id))
+                        && filters
+                            .moderator_user_id
+                            .is_none_or(|user_id| case.moderator_user_id == user_id)
+                        && filters
+                            .action
+                            .is_none_or(|action| case.action.eq_ignore_ascii_case(action))
+                })
+                .collect();
+            cases.reverse();
+            cases.truncate(filters.limit.clamp(1, 200) as usize);
+            Ok(cases)
+        })
+    }
+
+    fn count_timeouts_in_window(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        window_seconds: i64,
+    ) -> RepositoryFuture<'_, i64> {
+        Box::pin(async move {
+            let since = window_start(window_seconds);
+            let count = self
+                .cases(guild_id)
+                .iter()
+                .filter(|case| {
+                    case.target_user_id == Some(user_id)
+                        && case.created_at as i64 >= since
+                        && is_timeout_action(&case.action)
+                })
+                .count();
+            Ok(count as i64)
+        })
+    }
+}
+
+impl WarningRepository for InMemoryRepository {
+    fn record_warning<'a>(
+        &'a self,
+        guild_id: u64,
+        user_id: u64,
+        moderator_id: u64,
+        reason: &'a str,
+    ) -> RepositoryFuture<'a, WarningRecord> {
+        Box::pin(async move {
+            let mut warnings = self.warnings.lock().unwrap_or_else(|e| e.into_inner());
+            warnings.push((
+                guild_id,
+                user_id,
+                WarningEntry {
+                    warned_at: now_unix_secs(),
+                    moderator_id,
+                    reason: reason.to_owned(),
+                },
+            ));
+            let warn_number = warnings
+                .iter()
+                .filter(|(g, u, _)| *g == guild_id && *u == user_id)
+                .count();
+            Ok(WarningRecord { warn_number })
+        })
+    }
+
+    fn warnings_since(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        since: u64,
+    ) -> RepositoryFuture<'_, Vec<WarningEntry>> {
+        Box::pin(async move {
+            let warnings = self.warnings.lock().unwrap_or_else(|e| e.into_inner());
+            Ok(warnings
+                .iter()
+                .filter(|(g, u, entry)| *g == guild_id && *u == user_id && entry.warned_at >= since)
+                .map(|(_, _, entry)| entry.clone())
+                .collect())
+        })
+    }
+
+    fn count_warnings_in_window(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        window_seconds: i64,
+    ) -> RepositoryFuture<'_, i64> {
+        Box::pin(async move {
+            let since = window_start(window_seconds);
+            let warnings = self.warnings.lock().unwrap_or_else(|e| e.into_inner());
+            let count = warnings
+                .iter()
+                .filter(|(g, u, entry)| {
+                    *g == guild_id && *u == user_id && entry.warned_at as i64 >= since
+                })
+                .count();
+            Ok(count as i64)
+        })
+    }
+
+    fn clear_warnings(&self, guild_id: u64, user_id: u64) -> RepositoryFuture<'_, u64> {
+        Box::pin(async move {
+            let mut warnings = self.warnings.lock().unwrap_or_else(|e| e.into_inner());
+            let before = warnings.len();
+            warnings.retain(|(g, u, _)| !(*g == guild_id && *u == user_id));
+            Ok((before - warnings.len()) as u64)
+        })
+    }
+}
+
+impl EscalationRepository for InMemoryRepository {
+    fn escalation_config_if_enabled(
+        &self,
+        guild_id: u64,
+    ) -> RepositoryFuture<'_, Option<EscalationConfig>> {
+        Box::pin(async move {
+            Ok(self
+                .escalation
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&guild_id)
+                .filter(|config| config.enabled)
+                .cloned())
+        })
+    }
+}
+
+fn window_start(window_seconds: i64) -> i64 {
+    now_unix_secs() as i64 - window_seconds
+}
@@ -0,0 +1,155 @@
+//! Trait views over the tables that moderation logic reads and writes.
+//!
+//! [`Database`] implements every trait by delegating to `impls::*`;
+//! [`InMemoryRepository`] implements them over plain collections so that
+//! logic built on the traits can be tested without Postgres.
+
+mod memory;
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::database::Database;
+use crate::impls::{cases, escalation, warnings};
+use crate::model::cases::CaseSummary;
+use crate::model::escalation::EscalationConfig;
+use crate::model::warnings::{WarningEntry, WarningRecord};
+
+pub use memory::InMemoryRepository;
+
+pub type RepositoryFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+pub trait CaseRepository: Debug + Send + Sync {
+    fn create_case<'a>(&'a self, new_case: cases::NewCase<'a>)
+    -> RepositoryFuture<'a, CaseSummary>;
+    fn list_recent_cases<'a>(
+        &'a self,
+        guild_id: u64,
+        filters: cases::CaseFilters<'a>,
+    ) -> RepositoryFuture<'a, Vec<CaseSummary>>;
+    /// Timeout cases (manual, automatic or from a filter) against `user_id`
+    /// in the last `window_seconds`.
+    fn count_timeouts_in_window(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        window_seconds: i64,
+    ) -> RepositoryFuture<'_, i64>;
+}
+
+pub trait WarningRepository: Debug + Send + Sync {
+    fn record_warning<'a>(
+        &'a self,
+        guild_id: u64,
+        user_id: u64,
+        moderator_id: u64,
+        reason: &'a str,
+    ) -> RepositoryFuture<'a, WarningRecord>;
+    fn warnings_since(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        since: u64,
+    ) -> RepositoryFuture<'_, Vec<WarningEntry>>;
+    fn count_warnings_in_window(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        window_seconds: i64,
+    ) -> RepositoryFuture<'_, i64>;
+    fn clear_warnings(&self, guild_id: u64, user_id: u64) -> RepositoryFuture<'_, u64>;
+}
+
+pub trait EscalationRepository: Debug + Send + Sync {
+    /// The guild's escalation config, or `None` when it's unset or disabled.
+    fn escalation_config_if_enabled(
+        &self,
+        guild_id: u64,
+    ) -> RepositoryFuture<'_, Option<EscalationConfig>>;
+}
+
+impl CaseRepository for Database {
+    fn create_case<'a>(
+        &'a self,
+        new_case: cases::NewCase<'a>,
+    ) -> RepositoryFuture<'a, CaseSummary> {
+        Box::pin(cases::create_case(self, new_case))
+    }
+
+    fn list_recent_cases<'a>(
+        &'a self,
+        guild_id: u64,
+        filters: cases::CaseFilters<'a>,
+    ) -> RepositoryFuture<'a, Vec<CaseSummary>> {
+        Box::pin(cases::list_recent_cases(self, guild_id, filters))
+    }
+
+    fn count_timeouts_in_window(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        window_seconds: i64,
+    ) -> RepositoryFuture<'_, i64> {
+        Box::pin(escalation::count_timeouts_in_window(
+            self,
+            guild_id,
+            user_id,
+            window_seconds,
+        ))
+    }
+}
+
+impl WarningRepository for Database {
+    fn record_warning<'a>(
+        &'a self,
+        guild_id: u64,
+        user_id: u64,
+        moderator_id: u64,
+        reason: &'a str,
+    ) -> RepositoryFuture<'a, WarningRecord> {
+        Box::pin(warnings::record_warning(
+            self,
+            guild_id,
+            user_id,
+            moderator_id,
+            reason,
+        ))
+    }
+
+    fn warnings_since(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        since: u64,
+    ) -> RepositoryFuture<'_, Vec<WarningEntry>> {
+        Box::pin(warnings::warnings_since(self, guild_id, user_id, since))
+    }
+
+    fn count_warnings_in_window(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        window_seconds: i64,
+    ) -> RepositoryFuture<'_, i64> {
+        Box::pin(escalation::count_warnings_in_window(
+            self,
+            guild_id,
+            user_id,
+            window_seconds,
+        ))
+    }
+
+    fn clear_warnings(&self, guild_id: u64, user_id: u64) -> RepositoryFuture<'_, u64> {
+        Box::pin(warnings::clear_warnings(self, guild_id, user_id))
+    }
+}
+
+impl EscalationRepository for Database {
+    fn escalation_config_if_enabled(
+        &self,
+        guild_id: u64,
+    ) -> RepositoryFuture<'_, Option<EscalationConfig>> {
+        Box::pin(escalation::get_escalation_if_enabled(self, guild_id))
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+/// Attempts per call, including the first.
+const RETRY_ATTEMPTS: u32 = 3;
+/// Delay before the first retry; doubled for each later one.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+/// Consecutive transient failures that open the breaker.
+const BREAKER_FAILURE_THRESHOLD: u32 = 5;
+/// How long the breaker stays open before letting a probe call through.
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Returned instead of running the query while the breaker is open.
+#[derive(Clone, Copy, Debug)]
+pub struct CircuitOpenError {
+    pub retry_in: Duration,
+}
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "database circuit breaker is open (retrying in {}s)",
+            self.retry_in.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    /// Calls fail fast until the cooldown ends.
+    Open {
+        retry_in: Duration,
+    },
+    /// The cooldown ended; the next call decides whether to close or reopen.
+    HalfOpen,
+}
+
+/// Point-in-time breaker reading for the `status` command.
+#[derive(Clone, Copy, Debug)]
+pub struct BreakerSnapshot {
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+    pub times_opened: u64,
+}
+
+/// Trips after repeated transient database failures so hot-path handlers stop
+/// queueing behind a dead connection pool, then lets one call through after a
+/// cooldown to probe for recovery.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    started: Instant,
+    threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: AtomicU32,
+    /// Milliseconds since `started` until which the breaker is open; 0 when
+    /// it has never opened or has closed again.
+    open_until_ms: AtomicU64,
+    times_opened: AtomicU64,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(BREAKER_FAILURE_THRESHOLD, BREAKER_COOLDOWN)
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            threshold,
+            cooldown,
+            consecutive_failures: AtomicU32::new(0),
+            open_until_ms: AtomicU64::new(0),
+            times_opened: AtomicU64::new(0),
+        }
+    }
+
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        BreakerSnapshot {
+            state: self.state_at(self.now_ms()),
+            consecutive_failures: self.consecutive_failures.load(Ordering::Relaxed),
+            times_opened: self.times_opened.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Run `op`, retrying transient failures with exponential backoff. Fails
+    /// fast with [`CircuitOpenError`] while the breaker is open.
+    pub async fn call<T, F, Fut>(&self, op_name: &'static str, mut op: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        if let BreakerState::Open { retry_in } = self.state_at(self.now_ms()) {
+            return Err(CircuitOpenError { retry_in }.into());
+        }
+
+        let mut delay = RETRY_BASE_DELAY;
+        let mut attempt = 1;
+        loop {
+            match op().await {
+                Ok(value) => {
+                    self.record_success();
+                    return Ok(value);
+                }
+                Err(source) if !is_transient(&source) => {
+                    // The database answered, so the connection is healthy.
+                    self.record_success();
+                    return Err(source);
+                }
+                Err(source) => {
+                    if self.record_failure(self.now_ms()) {
+                        warn!(
+                            op = op_name,
+                            cooldown_secs = self.cooldown.as_secs(),
+                            ?source,
+                            "database circuit breaker opened"
+                        );
+                        return Err(source);
+                    }
+                    if attempt >= RETRY_ATTEMPTS {
+                        return Err(source);
+                    }
+                    warn!(op = op_name, attempt, ?source, "retrying database call");
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    fn now_ms(&self) -> u64 {
+        u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    fn state_at(&self, now_ms: u64) -> BreakerState {
+        let open_until = self.open_until_ms.load(Ordering::Acquire);
+        if open_until == 0 {
+            BreakerState::Closed
+        } else if now_ms < open_until {
+            BreakerState::Open {
+                retry_in: Duration::from_millis(open_until - now_ms),
+            }
+        } else {
+            BreakerState::HalfOpen
+        }
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        if self.open_until_ms.swap(0, Ordering::AcqRel) != 0 {
+            info!("database circuit breaker closed");
+        }
+    }
+
+    /// Count a transient failure. Returns `true` if this call opened (or,
+    /// from half-open, reopened) the breaker.
+    fn record_failure(&self, now_ms: u64) -> bool {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let half_open = matches!(self.state_at(now_ms), BreakerState::HalfOpen);
+        if failures < self.threshold && !half_open {
+            return false;
+        }
+
+        let cooldown_ms = u64::try_from(self.cooldown.as_millis()).unwrap_or(u64::MAX);
+        self.open_until_ms
+            .store(now_ms.saturating_add(cooldown_ms).max(1), Ordering::Release);
+        self.times_opened.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Whether an error looks like a connection problem that a retry could fix,
+/// as opposed to a query the database rejected.
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    let Some(error) = error.downcast_ref::<sqlx::Error>() else {
+        return false;
+    };
+
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::Tls(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db_error) => db_error.code().is_some_and(|code| {
+            // Class 08: connection exception; 57P01-57P03: server shutting
+            // down or not yet accepting connections.
+            code.starts_with("08") || matches!(code.as_ref(), "57P01" | "57P02" | "57P03")
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_threshold_and_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new(3, Duration::from_millis(1_000));
+
+        assert!(!breaker.record_failure(10));
+        assert!(!breaker.record_failure(20));
+        assert_eq!(breaker.state_at(20), BreakerState::Closed);
+
+        assert!(breaker.record_failure(30));
+        assert_eq!(
+            breaker.state_at(530),
+            BreakerState::Open {
+                retry_in: Duration::from_millis(500)
+            }
+        );
+        assert_eq!(breaker.state_at(1_030), BreakerState::HalfOpen);
+
+        // A failed probe reopens straight away.
+        assert!(breaker.record_failure(1_100));
+        assert!(matches!(breaker.state_at(1_200), BreakerState::Open { .. }));
+
+        breaker.record_success();
+        assert_eq!(breaker.state_at(1_200), BreakerState::Closed);
+        assert_eq!(breaker.snapshot().consecutive_failures, 0);
+        assert_eq!(breaker.snapshot().times_opened, 2);
+    }
+
+    #[test]
+    fn only_connection_errors_are_transient() {
+        assert!(is_transient(&sqlx::Error::PoolTimedOut.into()));
+        assert!(is_transient(
+            &sqlx::Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset)).into()
+        ));
+        assert!(!is_transient(&sqlx::Error::RowNotFound.into()));
+        assert!(!is_transient(&anyhow::anyhow!("guild_id out of i64 range")));
+    }
+
+    #[tokio::test]
+    async fn fails_fast_while_open() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let result: anyhow::Result<()> = breaker
+            .call("test", || async { Err(sqlx::Error::PoolTimedOut.into()) })
+            .await;
+        assert!(result.is_err());
+
+        let mut calls = 0;
+        let result: anyhow::Result<()> = breaker
+            .call("test", || {
+                calls += 1;
+                async { Ok(()) }
+            })
+            .await;
+        assert!(
+            result
+                .unwrap_err()
+                .downcast_ref::<CircuitOpenError>()
+                .is_some()
+        );
+        assert_eq!(calls, 0);
+    }
+}
@@ -0,0 +1,388 @@
+use std::env;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt as _;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::provider::{
+    ChatImage, ChatPrompt, ChatRole, ChatTurn, LlmProvider, ProviderFuture, ReplyDeltas,
+};
+
+const API_URL: &str = "https://api.anthropic.com/v1/messages";
+const API_VERSION: &str = "2023-06-01";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Clone, Debug)]
+pub struct AnthropicProvider {
+    http: reqwest::Client,
+    api_key: String,
+    model: String,
+    /// Used instead of `model` for prompts with images.
+    vision_model: String,
+    max_tokens: u32,
+}
+
+impl AnthropicProvider {
+    pub fn from_env() -> anyhow::Result<Self> {
+        let api_key = env::var("ANTHROPIC_API_KEY")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .context("ANTHROPIC_API_KEY must be set when LLM_PROVIDER=anthropic")?;
+        let model = env::var("ANTHROPIC_MODEL")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "claude-3-5-haiku-latest".to_owned());
+        let vision_model = env::var("ANTHROPIC_VISION_MODEL")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| model.clone());
+        let max_tokens = env::var("ANTHROPIC_MAX_TOKENS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(1024);
+
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("failed to build anthropic http client")?;
+
+        Ok(Self {
+            http,
+            api_key,
+            model,
+            vision_model,
+            max_tokens,
+        })
+    }
+
+    async fn send(&self, prompt: &ChatPrompt, stream: bool) -> anyhow::Result<reqwest::Response> {
+        let model = if prompt.has_images() {
+            &self.vision_model
+        } else {
+            &self.model
+        };
+        let body = json!({
+            "model": model,
+            "max_tokens": self.max_tokens,
+            "system": prompt.system,
+            "messages": anthropic_messages(&prompt.turns),
+            "temperature": 0.75,
+            "stream": stream,
+        });
+
+        let response = self
+            .http
+            .post(API_URL)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&body)?)
+            .send()
+            .await
+            .context("failed to reach the anthropic api")?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        Err(api_error(status, &body))
+    }
+}
+
+impl LlmProvider for AnthropicProvider {
+    fn complete<'a>(&'a self, prompt: &'a ChatPrompt) -> ProviderFuture<'a, String> {
+        Box::pin(async move {
+            let response = self.send(prompt, false).await?;
+            let body: Value = serde_json::from_slice(&response.bytes().await?)
+                .context("failed to decode anthropic response")?;
+
+            let text = body["content"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|block| block["type"] == "text")
+                .filter_map(|block| block["text"].as_str())
+                .collect::<String>();
+            Ok(text)
+        })
+    }
+
+    fn stream<'a>(&'a self, prompt: &'a ChatPrompt) -> ProviderFuture<'a, ReplyDeltas> {
+        Box::pin(async move {
+            let mut bytes = self.send(prompt, true).await?.bytes_stream();
+            let (tx, rx) = mpsc::channel(32);
+
+            tokio::spawn(async move {
+                let mut buffer = Vec::new();
+                while let Some(chunk) = bytes.next().await {
+                    let chunk = match chunk {
+                        Ok(chunk) => chunk,
+                        Err(source) => {
+                            let _ = tx
+                                .send(Err(anyhow::Error::new(source)
+                                    .context("anthropic stream interrupted")))
+                                .await;
+                            return;
+                        }
+                    };
+                    buffer.extend_from_slice(&chunk);
+
+                    while let Some(end) = buffer.windows(2).position(|pair| pair == b"\n\n") {
+                        let event: Vec<u8> = buffer.drain(..end + 2).collect();
+                        let delta = match parse_stream_event(&String::from_utf8_lossy(&event)) {
+                            StreamEvent::Text(text) => Ok(text),
+                            StreamEvent::Error(source) => Err(source),
+                            StreamEvent::Stop => return,
+                            StreamEvent::Other => continue,
+                        };
+                        let failed = delta.is_err();
+                        if tx.send(delta).await.is_err() || failed {
+                            return;
+                        }
+                    }
+                }
+            });
+
+            Ok(Box::pin(ReceiverStream::new(rx)) as ReplyDeltas)
+        })
+    }
+}
+
+enum StreamEvent {
+    Text(String),
+    Error(anyhow::Error),
+    Stop,
+    Other,
+}
+
+/// Read one server-sent event from the streaming messages endpoint.
+fn parse_stream_event(event: &str) -> StreamEvent {
+    let Some(data) = event
+        .lines()
+        .find_map(|line| line.trim_end_matches('\r').strip_prefix("data:"))
+    else {
+        return StreamEvent::Other;
+    };
+    let Ok(data) = serde_json::from_str::<Value>(data.trim()) else {
+        return StreamEvent::Other;
+    };
+
+    match data["type"].as_str() {
+        Some("content_block_delta") => match data["delta"]["text"].as_str() {
+            Some(text) => StreamEvent::Text(text.to_owned()),
+            None => StreamEvent::Other,
+        },
+        Some("message_stop") => StreamEvent::Stop,
+        Some("error") => StreamEvent::Error(anyhow::anyhow!(
+            "anthropic stream error ({}): {}",
+            data["error"]["type"].as_str().unwrap_or("unknown"),
+            data["error"]["message"].as_str().unwrap_or("no message")
+        )),
+        _ => StreamEvent::Other,
+    }
+}
+
+/// Turn an error response into an error carrying the API's own explanation.
+fn api_error(status: reqwest::StatusCode, body: &str) -> anyhow::Error {
+    let parsed = serde_json::from_str::<Value>(body).ok();
+    let error = parsed.as_ref().map(|body| &body["error"]);
+    let kind = error
+        .and_then(|error| error["type"].as_str())
+        .unwrap_or("unknown_error");
+    let message = error
+        .and_then(|error| error["message"].as_str())
+        .unwrap_or(body);
+
+    anyhow::anyhow!("anthropic api error ({status}, {kind}): {message}")
+}
+
+/// Map turns onto the messages API, which wants strictly alternating roles
+/// starting with the user: consecutive turns from the same side are merged
+/// and anything before the first user turn is dropped. Turns with images
+/// become content blocks with the images ahead of the text.
+fn anthropic_messages(turns: &[ChatTurn]) -> Vec<Value> {
+    let mut merged: Vec<(ChatRole, String, Vec<&ChatImage>)> = Vec::new();
+    for turn in turns.iter().skip_while(|turn| turn.role != ChatRole::User) {
+        match merged.last_mut() {
+            Some((role, content, images)) if *role == turn.role => {
+                content.push_str("\n\n");
+                content.push_str(&turn.content);
+                images.extend(&turn.images);
+            }
+            _ => merged.push((
+                turn.role,
+                turn.content.clone(),
+                turn.images.iter().collect(),
+            )),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(role, content, images)| {
+            let role = match role {
+                ChatRole::User => "user",
+                ChatRole::Assistant => "assistant",
+            };
+            if images.is_empty() {
+                return json!({ "role": role, "content": content });
+            }
+
+            let mut blocks: Vec<Value> = images
+                .into_iter()
+                .map(|image| {
+                    json!({
+                        "type": "image",
+                        "source": {
+                            "type": "base64",
+                            "media_type": image.media_type,
+                            "data": STANDARD.encode(&image.data),
+                        },
+                    })
+                })
+                .collect();
+            blocks.push(json!({ "type": "text", "text": content }));
+            json!({ "role": role, "content": blocks })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(role: ChatRole, content: &str) -> ChatTurn {
+        ChatTurn {
+            role,
+            content: content.to_owned(),
+            images: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn consecutive_turns_from_one_side_are_merged() {
+        let messages = anthropic_messages(&[
+            turn(ChatRole::User, "first"),
+            turn(ChatRole::User, "second"),
+            turn(ChatRole::Assistant, "reply"),
+            turn(ChatRole::Assistant, "more"),
+            turn(ChatRole::User, "third"),
+        ]);
+
+        assert_eq!(
+            messages,
+            vec![
+                json!({ "role": "user", "content": "first\n\nsecond" }),
+                json!({ "role": "assistant", "content": "reply\n\nmore" }),
+                json!({ "role": "user", "content": "third" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn leading_assistant_turns_are_dropped() {
+        let messages = anthropic_messages(&[
+            turn(ChatRole::Assistant, "hello"),
+            turn(ChatRole::Assistant, "anyone?"),
+            turn(ChatRole::User, "hi"),
+        ]);
+
+        assert_eq!(messages, vec![json!({ "role": "user", "content": "hi" })]);
+        assert!(anthropic_messages(&[turn(ChatRole::Assistant, "alone")]).is_empty());
+    }
+
+    #[test]
+    fn images_come_before_the_text() {
+        let mut with_image = turn(ChatRole::User, "what is this?");
+        with_image.images.push(ChatImage {
+            media_type: "image/png".to_owned(),
+            data: vec![1, 2, 3],
+        });
+
+        let messages = anthropic_messages(&[with_image]);
+
+        let blocks = messages[0]["content"].as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["type"], "image");
+        assert_eq!(blocks[0]["source"]["media_type"], "image/png");
+        assert_eq!(blocks[0]["source"]["data"], STANDARD.encode([1, 2, 3]));
+        assert_eq!(
+            blocks[1],
+            json!({ "type": "text", "text": "what is this?" })
+        );
+    }
+
+    #[test]
+    fn content_block_delta_yields_text() {
+        let event = "event: content_block_delta\r\n\
+                     data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n";
+
+        assert!(matches!(parse_stream_event(event), StreamEvent::Text(text) if text == "Hi"));
+    }
+
+    #[test]
+    fn message_stop_ends_the_stream() {
+        let event = "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n";
+
+        assert!(matches!(parse_stream_event(event), StreamEvent::Stop));
+    }
+
+    #[test]
+    fn error_event_carries_the_api_message() {
+        let event = "event: error\n\
+                     data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n";
+
+        let StreamEvent::Error(source) = parse_stream_event(event) else {
+            panic!("expected an error event");
+        };
+        let message = source.to_string();
+        assert!(message.contains("overloaded_error"));
+        assert!(message.contains("Overloaded"));
+    }
+
+    #[test]
+    fn other_events_are_skipped() {
+        assert!(matches!(
+            parse_stream_event("event: ping\ndata: {\"type\":\"ping\"}\n\n"),
+            StreamEvent::Other
+        ));
+        assert!(matches!(
+            parse_stream_event(": keep-alive\n\n"),
+            StreamEvent::Other
+        ));
+    }
+
+    #[test]
+    fn api_error_uses_the_error_body() {
+        let error = api_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"type":"error","error":{"type":"invalid_request_error","message":"max_tokens: must be positive"}}"#,
+        );
+
+        assert_eq!(
+            error.to_string(),
+            "anthropic api error (400 Bad Request, invalid_request_error): max_tokens: must be positive"
+        );
+    }
+
+    #[test]
+    fn api_error_falls_back_to_the_raw_body() {
+        let error = api_error(reqwest::StatusCode::BAD_GATEWAY, "upstream went away");
+
+        assert_eq!(
+            error.to_string(),
+            "anthropic api error (502 Bad Gateway, unknown_error): upstream went away"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Keeps channel history inside a token budget so a few long messages can't
+//! push the prompt past the model's context window.
+//!
+//! Token counts are estimated rather than tokenized: roughly four characters
+//! per token plus a small per-message overhead is close enough for both
+//! backends and errs on the generous side.
+
+use crate::provider::{ChatPrompt, ChatRole, ChatTurn};
+
+/// Budget used when `LLM_HISTORY_TOKEN_BUDGET` is unset or invalid.
+pub const DEFAULT_HISTORY_TOKEN_BUDGET: usize = 3000;
+
+const CHARS_PER_TOKEN: usize = 4;
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Cap on how much dropped history is handed to the model for a recap.
+const RECAP_SOURCE_MAX_CHARS: usize = 6000;
+
+/// Cap on the recap itself once it's added to the system prompt.
+const RECAP_MAX_CHARS: usize = 500;
+const RECAP_HEADING: &str = "\n\nSummary of earlier messages in this channel: ";
+
+/// Tokens held back from the history budget for [`append_recap`], so the
+/// prompt still fits once the recap is added.
+pub const RECAP_RESERVED_TOKENS: usize =
+    (RECAP_HEADING.len() + RECAP_MAX_CHARS).div_ceil(CHARS_PER_TOKEN);
+
+/// Turns dropped past what the budget needs when a new recap is made, so the
+/// next few replies can reuse it instead of asking for another.
+const RECAP_EXTRA_TURNS: usize = 6;
+
+const RECAP_INSTRUCTIONS: &str = "Summarize the following Discord conversation excerpt in at most three short sentences. \
+Keep who said what when it matters, skip greetings and filler, and reply with the summary only.";
+
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN) + MESSAGE_OVERHEAD_TOKENS
+}
+
+/// Drop the oldest turns until the system prompt and the remaining turns fit
+/// in `budget`, returning what was dropped, oldest first. The last turn is
+/// the message being replied to and is always kept.
+pub fn trim_to_budget(prompt: &mut ChatPrompt, budget: usize) -> Vec<ChatTurn> {
+    let cut = turns_over_budget(prompt, budget);
+    prompt.turns.drain(..cut).collect()
+}
+
+/// How many of the oldest turns [`trim_to_budget`] would drop.
+pub fn turns_over_budget(prompt: &ChatPrompt, budget: usize) -> usize {
+    let mut used = estimate_tokens(&prompt.system)
+        + prompt
+            .turns
+            .iter()
+            .map(|turn| estimate_tokens(&turn.content))
+            .sum::<usize>();
+
+    let mut cut = 0;
+    while used > budget && cut + 1 < prompt.turns.len() {
+        used -= estimate_tokens(&prompt.turns[cut].content);
+        cut += 1;
+    }
+
+    cut
+}
+
+/// Where to cut for a new recap when `needed` turns must go: a few more, so
+/// the recap stays usable as the channel moves on, but never the last turn.
+pub fn recap_cut(needed: usize, turn_count: usize) -> usize {
+    (needed + RECAP_EXTRA_TURNS).min(turn_count.saturating_sub(1))
+}
+
+/// Add a recap of dropped turns to the system prompt, cut down to the space
+/// [`RECAP_RESERVED_TOKENS`] holds for it.
+pub fn append_recap(prompt: &mut ChatPrompt, recap: &str) {
+    let recap = recap.trim();
+    if recap.is_empty() {
+        return;
+    }
+
+    prompt.system.push_str(RECAP_HEADING);
+    prompt.system.extend(recap.chars().take(RECAP_MAX_CHARS));
+}
+
+/// A one-off prompt asking the model to summarize turns that no longer fit.
+pub fn recap_prompt(dropped: &[ChatTurn]) -> ChatPrompt {
+    let mut transcript = String::new();
+    for turn in dropped {
+        let line = match turn.role {
+            ChatRole::User => turn.content.clone(),
+            ChatRole::Assistant => format!("Autumn: {}", turn.content),
+        };
+        transcript.push_str(&line);
+        transcript.push('\n');
+    }
+
+    // Keep the most recent part of the excerpt if it is still too long.
+    let excess = transcript
+        .chars()
+        .count()
+        .saturating_sub(RECAP_SOURCE_MAX_CHARS);
+    if excess > 0 {
+        transcript = transcript.chars().skip(excess).collect();
+    }
+
+    ChatPrompt {
+        system: RECAP_INSTRUCTIONS.to_owned(),
+        turns: vec![ChatTurn {
+            role: ChatRole::User,
+            content: transcript,
+            images: Vec::new(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(role: ChatRole, content: &str) -> ChatTurn {
+        ChatTurn {
+            role,
+            content: content.to_owned(),
+            images: Vec::new(),
+        }
+    }
+
+    fn prompt(turns: Vec<ChatTurn>) -> ChatPrompt {
+        ChatPrompt {
+            system: "system".to_owned(),
+            turns,
+        }
+    }
+
+    fn prompt_tokens(prompt: &ChatPrompt) -> usize {
+        estimate_tokens(&prompt.system)
+            + prompt
+                .turns
+                .iter()
+                .map(|turn| estimate_tokens(&turn.content))
+                .sum::<usize>()
+    }
+
+    #[test]
+    fn trimming_drops_oldest_turns_first() {
+        let mut prompt = prompt(vec![
+            turn(ChatRole::User, &"a".repeat(400)),
+            turn(ChatRole::Assistant, &"b".repeat(400)),
+            turn(ChatRole::User, &"c".repeat(400)),
+            turn(ChatRole::User, "latest"),
+        ]);
+
+        let dropped = trim_to_budget(&mut prompt, 150);
+
+        assert_eq!(dropped.len(), 2);
+        assert!(dropped[0].content.starts_with('a'));
+        assert!(dropped[1].content.starts_with('b'));
+        assert_eq!(prompt.turns.len(), 2);
+        assert!(prompt.turns[0].content.starts_with('c'));
+        assert!(prompt_tokens(&prompt) <= 150);
+    }
+
+    #[test]
+    fn trimming_always_keeps_the_last_turn() {
+        let mut prompt = prompt(vec![
+            turn(ChatRole::User, "older"),
+            turn(ChatRole::User, &"x".repeat(10_000)),
+        ]);
+
+        let dropped = trim_to_budget(&mut prompt, 10);
+
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(prompt.turns.len(), 1);
+        assert_eq!(prompt.turns[0].content.len(), 10_000);
+    }
+
+    #[test]
+    fn trimming_leaves_prompts_within_budget_alone() {
+        let mut prompt = prompt(vec![turn(ChatRole::User, "hi"), turn(ChatRole::User, "yo")]);
+
+        assert!(trim_to_budget(&mut prompt, 1000).is_empty());
+        assert_eq!(prompt.turns.len(), 2);
+    }
+
+    #[test]
+    fn recap_cut_never_takes_the_last_turn() {
+        assert_eq!(recap_cut(2, 20), 2 + RECAP_EXTRA_TURNS);
+        assert_eq!(recap_cut(3, 5), 4);
+        assert_eq!(recap_cut(0, 0), 0);
+    }
+
+    #[test]
+    fn recap_source_is_capped() {
+        let dropped = vec![
+            turn(ChatRole::User, &"old ".repeat(2000)),
+            turn(ChatRole::Assistant, &"new ".repeat(2000)),
+        ];
+
+        let recap = recap_prompt(&dropped);
+
+        let source = &recap.turns[0].content;
+        assert_eq!(source.chars().count(), RECAP_SOURCE_MAX_CHARS);
+        assert!(source.ends_with("new \n"));
+    }
+
+    #[test]
+    fn appended_recap_fits_in_the_reserved_tokens() {
+        let mut prompt = prompt(vec![turn(ChatRole::User, "latest")]);
+        let before = prompt_tokens(&prompt);
+
+        append_recap(&mut prompt, &"é".repeat(5000));
+
+        assert!(prompt.system.contains("Summary of earlier messages"));
+        assert!(prompt_tokens(&prompt) - before <= RECAP_RESERVED_TOKENS);
+    }
+}
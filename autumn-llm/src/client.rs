@@ -1,70 +1,198 @@
 use std::env;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
-use anyhow::Context as _;
-use autumn_database::{Database, impls::llm_chat::list_recent_llm_chat_messages};
-use ollama_rs::{
-    Ollama,
-    generation::chat::{ChatMessage, request::ChatMessageRequest},
-    models::ModelOptions,
+use autumn_database::cache::llm_recap_key;
+use autumn_database::{
+    CacheService, Database,
+    impls::{
+        ai_config::get_llm_system_prompt,
+        cases::{CaseFilters, list_recent_cases},
+        llm_chat::list_recent_llm_chat_messages,
+        notes::list_user_notes,
+        warnings::{now_unix_secs, warnings_since},
+    },
 };
+use sha2::{Digest, Sha256};
+use tokio_stream::StreamExt as _;
+use tracing::warn;
+
+use crate::anthropic::AnthropicProvider;
+use crate::budget::{self, DEFAULT_HISTORY_TOKEN_BUDGET};
+use crate::ollama::OllamaProvider;
+use crate::provider::{ChatImage, ChatPrompt, ChatRole, ChatTurn, LlmProvider, ReplyDeltas};
+use crate::reasons;
+use crate::reply_cache::{self, DEFAULT_REPLY_CACHE_TTL};
+use crate::summary::{self, ModerationHistory};
+use crate::toxicity::{self, ToxicityVerdict};
+use crate::translate;
+use crate::vision;
 
 #[derive(Clone, Debug)]
 pub struct LlmService {
-    client: Ollama,
-    model: String,
+    provider: Arc<dyn LlmProvider>,
+    stream_replies: bool,
+    history_token_budget: usize,
+    recap_trimmed_history: bool,
+    /// How long channel replies are reused for identical prompts; `None`
+    /// when `LLM_REPLY_CACHE_TTL=0` turns the cache off.
+    reply_cache_ttl: Option<Duration>,
+    stats: Arc<LlmStatsInner>,
 }
 
-impl LlmService {
-    pub fn from_env_optional() -> anyhow::Result<Option<Self>> {
-        let enabled = env::var("OLLAMA_ENABLED")
-            .ok()
-            .map(|value| {
-                matches!(
-                    value.trim().to_ascii_lowercase().as_str(),
-                    "1" | "true" | "yes" | "on"
-                )
-            })
-            .unwrap_or(true);
-
-        if !enabled {
+#[derive(Debug, Default)]
+struct LlmStatsInner {
+    model_calls: AtomicU64,
+    cached_replies: AtomicU64,
+}
+
+/// Counters since startup, shown by `!status`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LlmStatsSnapshot {
+    /// Requests sent to the provider, including classifiers and recaps.
+    pub model_calls: u64,
+    /// Channel replies served from the reply cache; each is a call saved.
+    pub cached_replies: u64,
+}
+
+/// A reply being generated token by token; see
+/// [`LlmService::stream_channel_reply`].
+pub struct ChannelReplyStream {
+    inner: ReplyDeltas,
+    text: String,
+    done: bool,
+    /// Where the finished reply is cached, when it came from the model.
+    cache_entry: Option<(CacheService, String, Duration)>,
+}
+
+/// A generated briefing on a user's moderation history, with how much of the
+/// record it covers.
+#[derive(Clone, Debug)]
+pub struct HistorySummary {
+    pub text: String,
+    pub cases: usize,
+    pub warnings: usize,
+    pub notes: usize,
+}
+
+/// Most recent cases included in a history summary.
+const SUMMARY_MAX_CASES: u32 = 50;
+
+/// How long a channel's history recap is kept for reuse by later replies.
+const RECAP_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+impl ChannelReplyStream {
+    /// Wait for the next chunk and return the reply so far, or `None` once
+    /// the model has finished.
+    pub async fn next_text(&mut self) -> anyhow::Result<Option<&str>> {
+        if self.done {
             return Ok(None);
         }
 
-        let host_raw = env::var("OLLAMA_HOST").ok();
-        let port_raw = env::var("OLLAMA_PORT").ok();
-        let model_raw = env::var("OLLAMA_MODEL").ok();
+        match self.inner.next().await {
+            Some(delta) => {
+                self.text.push_str(&delta?);
+                Ok(Some(&self.text))
+            }
+            None => {
+                self.done = true;
+                if let Some((cache, key, ttl)) = self.cache_entry.take() {
+                    store_reply(&cache, &key, ttl, self.text.trim()).await;
+                }
+                Ok(None)
+            }
+        }
+    }
 
-        let host = host_raw.as_deref().map(str::trim).unwrap_or_default();
-        let port = port_raw.as_deref().map(str::trim).unwrap_or_default();
-        let model = model_raw.as_deref().map(str::trim).unwrap_or_default();
+    /// The complete reply, once [`Self::next_text`] has returned `None`.
+    pub fn into_text(self) -> String {
+        self.text.trim().to_owned()
+    }
+}
 
-        if host.is_empty() && port.is_empty() && model.is_empty() {
-            return Ok(None);
+impl LlmService {
+    /// Build the service from the environment, or `None` if no backend is
+    /// configured. `LLM_PROVIDER` picks the backend: `ollama` (the default)
+    /// needs an `OLLAMA_*` variable and can be switched off with
+    /// `OLLAMA_ENABLED=false`; `anthropic` needs `ANTHROPIC_API_KEY`.
+    pub fn from_env_optional() -> anyhow::Result<Option<Self>> {
+        match llm_provider_name().as_str() {
+            "anthropic" => {
+                let api_key = env::var("ANTHROPIC_API_KEY").unwrap_or_default();
+                if api_key.trim().is_empty() {
+                    return Ok(None);
+                }
+            }
+            _ => {
+                let enabled = env::var("OLLAMA_ENABLED")
+                    .ok()
+                    .map(|value| env_flag(&value))
+                    .unwrap_or(true);
+
+                if !enabled || !OllamaProvider::configured() {
+                    return Ok(None);
+                }
+            }
         }
 
         Ok(Some(Self::from_env()?))
     }
 
     pub fn from_env() -> anyhow::Result<Self> {
-        let host = env::var("OLLAMA_HOST")
+        let provider: Arc<dyn LlmProvider> = match llm_provider_name().as_str() {
+            "ollama" => Arc::new(OllamaProvider::from_env()),
+            "anthropic" => Arc::new(AnthropicProvider::from_env()?),
+            other => anyhow::bail!("unknown LLM_PROVIDER `{other}` (expected ollama or anthropic)"),
+        };
+
+        let stream_replies = env::var("LLM_STREAM_REPLIES")
+            .ok()
+            .map(|value| env_flag(&value))
+            .unwrap_or(false);
+        let history_token_budget = env::var("LLM_HISTORY_TOKEN_BUDGET")
             .ok()
-            .map(|value| value.trim().to_owned())
-            .filter(|value| !value.is_empty())
-            .unwrap_or_else(|| "http://127.0.0.1".to_owned());
-        let port = env::var("OLLAMA_PORT")
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_HISTORY_TOKEN_BUDGET);
+        let recap_trimmed_history = env::var("LLM_HISTORY_RECAP")
             .ok()
-            .and_then(|value| value.parse::<u16>().ok())
-            .unwrap_or(11434);
-        let model = env::var("OLLAMA_MODEL")
+            .map(|value| env_flag(&value))
+            .unwrap_or(false);
+        let reply_cache_ttl = match env::var("LLM_REPLY_CACHE_TTL")
             .ok()
-            .map(|value| value.trim().to_owned())
-            .filter(|value| !value.is_empty())
-            .unwrap_or_else(|| "gpt-oss:20b-cloud".to_owned());
+            .and_then(|value| value.trim().parse::<u64>().ok())
+        {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => Some(DEFAULT_REPLY_CACHE_TTL),
+        };
+
+        Ok(Self {
+            provider,
+            stream_replies,
+            history_token_budget,
+            recap_trimmed_history,
+            reply_cache_ttl,
+            stats: Arc::default(),
+        })
+    }
+
+    /// Whether replies should be streamed into an edited message instead of
+    /// sent once complete (`LLM_STREAM_REPLIES`).
+    pub fn streams_replies(&self) -> bool {
+        self.stream_replies
+    }
 
-        let client = Ollama::new(host, port);
-        Ok(Self { client, model })
+    pub fn stats_snapshot(&self) -> LlmStatsSnapshot {
+        LlmStatsSnapshot {
+            model_calls: self.stats.model_calls.load(Ordering::Relaxed),
+            cached_replies: self.stats.cached_replies.load(Ordering::Relaxed),
+        }
     }
 
+    /// Reply to a mention. An identical prompt answered in the same channel
+    /// within `LLM_REPLY_CACHE_TTL` gets the same reply without a model call.
     pub async fn generate_channel_reply(
         &self,
         db: &Database,
@@ -73,45 +201,328 @@ impl LlmService {
         user_prompt: &str,
         author_display_name: &str,
     ) -> anyhow::Result<String> {
-        let history = list_recent_llm_chat_messages(db, guild_id, channel_id, 20).await?;
+        let cache_key = self.reply_cache_key(db, guild_id, channel_id, user_prompt);
+        if let Some(reply) = self.cached_reply(db, cache_key.as_deref()).await {
+            return Ok(reply);
+        }
+
+        let prompt = self
+            .channel_prompt(db, guild_id, channel_id, user_prompt, author_display_name)
+            .await?;
+        let reply = self.complete(&prompt).await?.trim().to_owned();
+
+        if let (Some(key), Some(ttl)) = (cache_key, self.reply_cache_ttl) {
+            store_reply(db.cache(), &key, ttl, &reply).await;
+        }
+
+        Ok(reply)
+    }
+
+    /// Like [`Self::generate_channel_reply`], but hands back the reply as it
+    /// is generated.
+    pub async fn stream_channel_reply(
+        &self,
+        db: &Database,
+        guild_id: u64,
+        channel_id: u64,
+        user_prompt: &str,
+        author_display_name: &str,
+    ) -> anyhow::Result<ChannelReplyStream> {
+        let cache_key = self.reply_cache_key(db, guild_id, channel_id, user_prompt);
+        if let Some(reply) = self.cached_reply(db, cache_key.as_deref()).await {
+            return Ok(ChannelReplyStream {
+                inner: Box::pin(tokio_stream::once(Ok(reply))),
+                text: String::new(),
+                done: false,
+                cache_entry: None,
+            });
+        }
+
+        let prompt = self
+            .channel_prompt(db, guild_id, channel_id, user_prompt, author_display_name)
+            .await?;
+        let inner = self.stream(&prompt).await?;
+
+        Ok(ChannelReplyStream {
+            inner,
+            text: String::new(),
+            done: false,
+            cache_entry: cache_key
+                .zip(self.reply_cache_ttl)
+                .map(|(key, ttl)| (db.cache().clone(), key, ttl)),
+        })
+    }
+
+    /// Ask the model how likely `content` is to be harassment or a disguised
+    /// slur. Used by the word filter's optional second stage.
+    pub async fn classify_toxicity(&self, content: &str) -> anyhow::Result<ToxicityVerdict> {
+        let reply = self.complete(&toxicity::classifier_prompt(content)).await?;
 
-        let mut messages = Vec::with_capacity(history.len() + 2);
-        messages.push(ChatMessage::system(crate::prompt::system_prompt()));
+        toxicity::parse_verdict(&reply)
+    }
+
+    /// Ask a vision model whether `images` are sexual or gory. Used by the
+    /// image filter; the verdict's category is `nsfw`, `gore` or `none`.
+    pub async fn classify_images(&self, images: Vec<ChatImage>) -> anyhow::Result<ToxicityVerdict> {
+        let reply = self
+            .complete(&vision::image_classifier_prompt(images))
+            .await?;
+
+        toxicity::parse_verdict(&reply)
+    }
+
+    /// Translate `content` into `language`.
+    pub async fn translate(&self, content: &str, language: &str) -> anyhow::Result<String> {
+        let reply = self
+            .complete(&translate::translate_prompt(content, language))
+            .await?;
+
+        Ok(reply.trim().to_owned())
+    }
 
+    /// Suggest a case reason for `action` from the message that prompted it.
+    /// Returns an empty string when the model had nothing usable to say.
+    pub async fn suggest_case_reason(&self, action: &str, content: &str) -> anyhow::Result<String> {
+        let reply = self
+            .complete(&reasons::reason_prompt(action, content))
+            .await?;
+
+        Ok(reasons::clean_reason(&reply))
+    }
+
+    /// Summarize a user's cases, active warnings and notes for moderators.
+    /// Returns `None` when the user has no history to summarize.
+    pub async fn summarize_moderation_history(
+        &self,
+        db: &Database,
+        guild_id: u64,
+        user_id: u64,
+        subject: &str,
+    ) -> anyhow::Result<Option<HistorySummary>> {
+        let cases = list_recent_cases(
+            db,
+            guild_id,
+            CaseFilters {
+                target_user_id: Some(user_id),
+                moderator_user_id: None,
+                action: None,
+                limit: SUMMARY_MAX_CASES,
+            },
+        )
+        .await?;
+        let warnings = warnings_since(db, guild_id, user_id, 0).await?;
+        let notes = list_user_notes(db, guild_id, user_id).await?;
+
+        if cases.is_empty() && warnings.is_empty() && notes.is_empty() {
+            return Ok(None);
+        }
+
+        let prompt = summary::summary_prompt(&ModerationHistory {
+            subject,
+            cases: &cases,
+            warnings: &warnings,
+            notes: &notes,
+            now: now_unix_secs(),
+        });
+        let text = self.complete(&prompt).await?;
+
+        Ok(Some(HistorySummary {
+            text: text.trim().to_owned(),
+            cases: cases.len(),
+            warnings: warnings.len(),
+            notes: notes.len(),
+        }))
+    }
+
+    fn reply_cache_key(
+        &self,
+        db: &Database,
+        guild_id: u64,
+        channel_id: u64,
+        user_prompt: &str,
+    ) -> Option<String> {
+        self.reply_cache_ttl?;
+        Some(reply_cache::reply_key(
+            db.cache(),
+            guild_id,
+            channel_id,
+            user_prompt,
+        ))
+    }
+
+    async fn cached_reply(&self, db: &Database, key: Option<&str>) -> Option<String> {
+        match db.cache().get_json::<String>(key?).await {
+            Ok(Some(reply)) => {
+                self.stats.cached_replies.fetch_add(1, Ordering::Relaxed);
+                Some(reply)
+            }
+            Ok(None) => None,
+            Err(source) => {
+                warn!(?source, "failed to read cached llm reply");
+                None
+            }
+        }
+    }
+
+    async fn complete(&self, prompt: &ChatPrompt) -> anyhow::Result<String> {
+        self.stats.model_calls.fetch_add(1, Ordering::Relaxed);
+        self.provider.complete(prompt).await
+    }
+
+    async fn stream(&self, prompt: &ChatPrompt) -> anyhow::Result<ReplyDeltas> {
+        self.stats.model_calls.fetch_add(1, Ordering::Relaxed);
+        self.provider.stream(prompt).await
+    }
+
+    async fn channel_prompt(
+        &self,
+        db: &Database,
+        guild_id: u64,
+        channel_id: u64,
+        user_prompt: &str,
+        author_display_name: &str,
+    ) -> anyhow::Result<ChatPrompt> {
+        let history = list_recent_llm_chat_messages(db, guild_id, channel_id, 20).await?;
+        let system = get_llm_system_prompt(db, guild_id)
+            .await?
+            .unwrap_or_else(crate::prompt::system_prompt);
+
+        let mut turns = Vec::with_capacity(history.len() + 1);
         for item in history.into_iter().rev() {
-            let mapped = match item.role.as_str() {
-                "user" => ChatMessage::user(format_history_content(
-                    "user",
-                    item.display_name.as_deref(),
-                    &item.content,
-                )),
-                "assistant" => ChatMessage::assistant(item.content.clone()),
+            let turn = match item.role.as_str() {
+                "user" => ChatTurn {
+                    role: ChatRole::User,
+                    content: format_history_content(
+                        "user",
+                        item.display_name.as_deref(),
+                        &item.content,
+                    ),
+                    images: Vec::new(),
+                },
+                "assistant" => ChatTurn {
+                    role: ChatRole::Assistant,
+                    content: item.content,
+                    images: Vec::new(),
+                },
                 _ => continue,
             };
-            messages.push(mapped);
+            turns.push(turn);
         }
 
-        let priority_prompt = format!(
-            "--- LATEST MESSAGE TO REPLY TO ---\n{}: {}",
-            author_display_name, user_prompt
-        );
-        messages.push(ChatMessage::user(priority_prompt));
-
-        let request = ChatMessageRequest::new(self.model.clone(), messages).options(
-            ModelOptions::default()
-                .temperature(0.75)
-                .repeat_penalty(1.2),
-        );
-        let response = self
-            .client
-            .send_chat_messages(request)
-            .await
-            .context("failed to get ollama chat response")?;
-
-        Ok(response.message.content.trim().to_owned())
+        turns.push(ChatTurn {
+            role: ChatRole::User,
+            content: format!(
+                "--- LATEST MESSAGE TO REPLY TO ---\n{}: {}",
+                author_display_name, user_prompt
+            ),
+            images: Vec::new(),
+        });
+
+        let mut prompt = ChatPrompt { system, turns };
+        if !self.recap_trimmed_history {
+            budget::trim_to_budget(&mut prompt, self.history_token_budget);
+            return Ok(prompt);
+        }
+
+        let history_budget = self
+            .history_token_budget
+            .saturating_sub(budget::RECAP_RESERVED_TOKENS);
+        let needed = budget::turns_over_budget(&prompt, history_budget);
+        if needed == 0 {
+            return Ok(prompt);
+        }
+
+        let recap_key = llm_recap_key(db.cache(), guild_id, channel_id);
+        let cached = match db.cache().get_json::<(String, String)>(&recap_key).await {
+            Ok(cached) => cached,
+            Err(source) => {
+                warn!(?source, "failed to read cached llm recap");
+                None
+            }
+        };
+
+        // The last recap still works while the turn it ends on hasn't left
+        // the history window and cutting there drops enough.
+        let last = prompt.turns.len() - 1;
+        let reusable = cached.and_then(|(covers, recap)| {
+            prompt.turns[..last]
+                .iter()
+                .rposition(|turn| turn_digest(turn) == covers)
+                .map(|index| index + 1)
+                .filter(|cut| *cut >= needed)
+                .map(|cut| (cut, recap))
+        });
+
+        let (cut, recap) = match reusable {
+            Some((cut, recap)) => (cut, Some(recap)),
+            None => {
+                let cut = budget::recap_cut(needed, prompt.turns.len());
+                let recap = match self
+                    .complete(&budget::recap_prompt(&prompt.turns[..cut]))
+                    .await
+                {
+                    Ok(recap) => {
+                        let covers = turn_digest(&prompt.turns[cut - 1]);
+                        if let Err(source) = db
+                            .cache()
+                            .set_json(&recap_key, &(covers, recap.trim()), RECAP_CACHE_TTL)
+                            .await
+                        {
+                            warn!(?source, "failed to cache llm recap");
+                        }
+                        Some(recap)
+                    }
+                    Err(source) => {
+                        warn!(
+                            ?source,
+                            guild_id, channel_id, "failed to recap trimmed llm history"
+                        );
+                        None
+                    }
+                };
+                (cut, recap)
+            }
+        };
+
+        prompt.turns.drain(..cut);
+        if let Some(recap) = recap {
+            budget::append_recap(&mut prompt, &recap);
+        }
+
+        Ok(prompt)
     }
 }
 
+/// Identifies the turn a cached recap ends on.
+fn turn_digest(turn: &ChatTurn) -> String {
+    hex::encode(Sha256::digest(turn.content.as_bytes()))
+}
+
+/// Cache a finished reply. Empty replies are not worth keeping.
+async fn store_reply(cache: &CacheService, key: &str, ttl: Duration, reply: &str) {
+    if reply.is_empty() {
+        return;
+    }
+    if let Err(source) = cache.set_json(key, &reply, ttl).await {
+        warn!(?source, "failed to cache llm reply");
+    }
+}
+
+fn llm_provider_name() -> String {
+    env::var("LLM_PROVIDER")
+        .ok()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "ollama".to_owned())
+}
+
+fn env_flag(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
 fn format_history_content(role: &str, display_name: Option<&str>, content: &str) -> String {
     let normalized_name = display_name
         .map(str::trim)
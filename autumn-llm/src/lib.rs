@@ -1,4 +1,16 @@
+mod anthropic;
+mod budget;
 mod client;
+mod ollama;
 mod prompt;
+mod provider;
+mod reasons;
+mod reply_cache;
+mod summary;
+mod toxicity;
+mod translate;
+mod vision;
 
-pub use client::LlmService;
+pub use client::{ChannelReplyStream, HistorySummary, LlmService, LlmStatsSnapshot};
+pub use provider::ChatImage;
+pub use toxicity::ToxicityVerdict;
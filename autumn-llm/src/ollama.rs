@@ -0,0 +1,122 @@
+use std::env;
+
+use anyhow::Context as _;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use ollama_rs::{
+    Ollama,
+    generation::{
+        chat::{ChatMessage, request::ChatMessageRequest},
+        images::Image,
+    },
+    models::ModelOptions,
+};
+use tokio_stream::StreamExt as _;
+
+use crate::provider::{ChatPrompt, ChatRole, LlmProvider, ProviderFuture, ReplyDeltas};
+
+#[derive(Clone, Debug)]
+pub struct OllamaProvider {
+    client: Ollama,
+    model: String,
+    /// Used instead of `model` for prompts with images.
+    vision_model: String,
+}
+
+impl OllamaProvider {
+    /// Whether any `OLLAMA_*` connection variable is set.
+    pub fn configured() -> bool {
+        ["OLLAMA_HOST", "OLLAMA_PORT", "OLLAMA_MODEL"]
+            .iter()
+            .any(|key| env::var(key).is_ok_and(|value| !value.trim().is_empty()))
+    }
+
+    pub fn from_env() -> Self {
+        let host = env::var("OLLAMA_HOST")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "http://127.0.0.1".to_owned());
+        let port = env::var("OLLAMA_PORT")
+            .ok()
+            .and_then(|value| value.parse::<u16>().ok())
+            .unwrap_or(11434);
+        let model = env::var("OLLAMA_MODEL")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "gpt-oss:20b-cloud".to_owned());
+        let vision_model = env::var("OLLAMA_VISION_MODEL")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| model.clone());
+
+        Self {
+            client: Ollama::new(host, port),
+            model,
+            vision_model,
+        }
+    }
+
+    fn request(&self, prompt: &ChatPrompt) -> ChatMessageRequest {
+        let mut messages = Vec::with_capacity(prompt.turns.len() + 1);
+        messages.push(ChatMessage::system(prompt.system.clone()));
+        messages.extend(prompt.turns.iter().map(|turn| {
+            match turn.role {
+                ChatRole::User if !turn.images.is_empty() => {
+                    ChatMessage::user(turn.content.clone()).with_images(
+                        turn.images
+                            .iter()
+                            .map(|image| Image::from_base64(STANDARD.encode(&image.data)))
+                            .collect(),
+                    )
+                }
+                ChatRole::User => ChatMessage::user(turn.content.clone()),
+                ChatRole::Assistant => ChatMessage::assistant(turn.content.clone()),
+            }
+        }));
+
+        let model = if prompt.has_images() {
+            &self.vision_model
+        } else {
+            &self.model
+        };
+        ChatMessageRequest::new(model.clone(), messages).options(
+            ModelOptions::default()
+                .temperature(0.75)
+                .repeat_penalty(1.2),
+        )
+    }
+}
+
+impl LlmProvider for OllamaProvider {
+    fn complete<'a>(&'a self, prompt: &'a ChatPrompt) -> ProviderFuture<'a, String> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .send_chat_messages(self.request(prompt))
+                .await
+                .context("failed to get ollama chat response")?;
+
+            Ok(response.message.content)
+        })
+    }
+
+    fn stream<'a>(&'a self, prompt: &'a ChatPrompt) -> ProviderFuture<'a, ReplyDeltas> {
+        Box::pin(async move {
+            let stream = self
+                .client
+                .send_chat_messages_stream(self.request(prompt))
+                .await
+                .context("failed to start ollama chat stream")?;
+
+            let deltas = stream.map(|chunk| {
+                chunk
+                    .map(|chunk| chunk.message.content)
+                    .map_err(|()| anyhow::anyhow!("ollama chat stream failed"))
+            });
+            Ok(Box::pin(deltas) as ReplyDeltas)
+        })
+    }
+}
@@ -0,0 +1,67 @@
+//! The backends that can generate replies. [`crate::LlmService`] builds a
+//! [`ChatPrompt`] from the channel history and hands it to whichever
+//! [`LlmProvider`] `LLM_PROVIDER` selected.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+use tokio_stream::Stream;
+
+pub type ProviderFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// Pieces of a reply as they are generated, in order.
+pub type ReplyDeltas = Pin<Box<dyn Stream<Item = anyhow::Result<String>> + Send>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChatRole {
+    User,
+    Assistant,
+}
+
+#[derive(Clone, Debug)]
+pub struct ChatTurn {
+    pub role: ChatRole,
+    pub content: String,
+    /// Images shown alongside `content`; only user turns carry any.
+    pub images: Vec<ChatImage>,
+}
+
+/// Raw image bytes, such as a downloaded Discord attachment.
+#[derive(Clone)]
+pub struct ChatImage {
+    /// `image/png`, `image/jpeg`, `image/gif` or `image/webp`.
+    pub media_type: String,
+    pub data: Vec<u8>,
+}
+
+impl Debug for ChatImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatImage")
+            .field("media_type", &self.media_type)
+            .field("bytes", &self.data.len())
+            .finish()
+    }
+}
+
+impl ChatPrompt {
+    /// Whether any turn carries images, so a vision model is needed.
+    pub fn has_images(&self) -> bool {
+        self.turns.iter().any(|turn| !turn.images.is_empty())
+    }
+}
+
+/// A provider-neutral conversation: the persona plus turns, oldest first,
+/// ending with the message to reply to.
+#[derive(Clone, Debug)]
+pub struct ChatPrompt {
+    pub system: String,
+    pub turns: Vec<ChatTurn>,
+}
+
+pub trait LlmProvider: Debug + Send + Sync {
+    /// Generate a complete reply.
+    fn complete<'a>(&'a self, prompt: &'a ChatPrompt) -> ProviderFuture<'a, String>;
+    /// Start generating a reply and hand it back piece by piece.
+    fn stream<'a>(&'a self, prompt: &'a ChatPrompt) -> ProviderFuture<'a, ReplyDeltas>;
+}
@@ -0,0 +1,23 @@
+//! Short-lived cache of channel replies keyed by the prompt text, so the same
+//! mention spammed into a channel is answered without another model call.
+
+use std::time::Duration;
+
+use autumn_database::CacheService;
+use autumn_database::cache::llm_reply_key;
+use sha2::{Digest, Sha256};
+
+pub const DEFAULT_REPLY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cache key for `prompt` in a channel. Case and spacing are ignored, so
+/// trivially varied copies of a prompt share an entry.
+pub fn reply_key(cache: &CacheService, guild_id: u64, channel_id: u64, prompt: &str) -> String {
+    let normalized = prompt
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let digest = hex::encode(Sha256::digest(normalized.as_bytes()));
+
+    llm_reply_key(cache, guild_id, channel_id, &digest)
+}
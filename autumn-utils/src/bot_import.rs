@@ -0,0 +1,342 @@
+//! Parsers for settings exported from other moderation bots.
+//!
+//! None of these bots publish a stable export schema, so each layout lists the
+//! key paths the common dashboard and community exporters use, and the first
+//! one present wins. Ids and timestamps are accepted as strings or numbers.
+
+use anyhow::Context as _;
+use serde_json::Value;
+
+use crate::parse::parse_duration_seconds;
+use crate::time::parse_rfc3339_unix_secs;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceBot {
+    Dyno,
+    Carlbot,
+    Wick,
+}
+
+impl SourceBot {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "dyno" => Some(Self::Dyno),
+            "carl" | "carlbot" | "carl-bot" => Some(Self::Carlbot),
+            "wick" => Some(Self::Wick),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Dyno => "Dyno",
+            Self::Carlbot => "Carl-bot",
+            Self::Wick => "Wick",
+        }
+    }
+
+    fn layout(self) -> &'static Layout {
+        match self {
+            Self::Dyno => &DYNO,
+            Self::Carlbot => &CARLBOT,
+            Self::Wick => &WICK,
+        }
+    }
+}
+
+/// A warning from the source bot's history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportedWarning {
+    pub user_id: u64,
+    /// `None` when the export did not record who issued the warning.
+    pub moderator_id: Option<u64>,
+    pub reason: String,
+    /// Unix seconds; `None` when the export had no usable timestamp.
+    pub warned_at: Option<u64>,
+}
+
+/// The source bot's "mute after N warnings" rule, mapped onto escalation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImportedMuteConfig {
+    pub warn_threshold: Option<u32>,
+    pub warn_window_seconds: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BotImport {
+    /// Lowercased, deduplicated filtered words.
+    pub words: Vec<String>,
+    pub warnings: Vec<ImportedWarning>,
+    pub mute: Option<ImportedMuteConfig>,
+    /// Warning entries dropped because they had no valid user id.
+    pub skipped_warnings: usize,
+}
+
+impl BotImport {
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.warnings.is_empty() && self.mute.is_none()
+    }
+}
+
+struct Layout {
+    word_paths: &'static [&'static str],
+    warning_paths: &'static [&'static str],
+    /// Only entries whose `kind_key` is one of these are warnings, when set.
+    warning_kinds: Option<(&'static str, &'static [&'static str])>,
+    user_keys: &'static [&'static str],
+    moderator_keys: &'static [&'static str],
+    reason_keys: &'static [&'static str],
+    time_keys: &'static [&'static str],
+    mute_paths: &'static [&'static str],
+    threshold_keys: &'static [&'static str],
+    window_keys: &'static [&'static str],
+}
+
+const DYNO: Layout = Layout {
+    word_paths: &["automod.bannedWords", "automod.banned_words", "bannedWords"],
+    warning_paths: &["warnings", "moderation.warnings"],
+    warning_kinds: None,
+    user_keys: &["user", "userid", "userId"],
+    moderator_keys: &["mod", "modid", "modId", "moderator"],
+    reason_keys: &["reason"],
+    time_keys: &["createdAt", "created_at", "date"],
+    mute_paths: &["moderation.autoMute", "automod.autoMute"],
+    threshold_keys: &["warnings", "count", "threshold"],
+    window_keys: &["window", "period"],
+};
+
+const CARLBOT: Layout = Layout {
+    word_paths: &["censor.words", "censor.blacklist", "filtered_words"],
+    warning_paths: &["warnings", "infractions"],
+    warning_kinds: Some(("action", &["warn", "warning"])),
+    user_keys: &["user_id", "target", "target_id"],
+    moderator_keys: &["moderator_id", "moderator", "mod_id"],
+    reason_keys: &["reason"],
+    time_keys: &["timestamp", "created_at", "date"],
+    mute_paths: &["warn_punishments", "punishments"],
+    threshold_keys: &["warns", "count", "threshold"],
+    window_keys: &["within", "window"],
+};
+
+const WICK: Layout = Layout {
+    word_paths: &[
+        "automod.blacklisted_words",
+        "word_blacklist",
+        "blacklistedWords",
+    ],
+    warning_paths: &["warns", "warnings"],
+    warning_kinds: None,
+    user_keys: &["userId", "user", "user_id"],
+    moderator_keys: &["moderatorId", "moderator", "moderator_id"],
+    reason_keys: &["reason"],
+    time_keys: &["date", "timestamp", "createdAt"],
+    mute_paths: &["punishments.warn", "auto_punish"],
+    threshold_keys: &["threshold", "warns", "count"],
+    window_keys: &["window", "within"],
+};
+
+/// Parse an export file from `bot`. Fails only when the file is not JSON;
+/// sections that are missing or malformed are left empty.
+pub fn parse_bot_export(bot: SourceBot, content: &str) -> anyhow::Result<BotImport> {
+    let root: Value = serde_json::from_str(content).context("export is not valid JSON")?;
+    let layout = bot.layout();
+    let mut import = BotImport::default();
+
+    if let Some(words) = first_path(&root, layout.word_paths).and_then(Value::as_array) {
+        for word in words.iter().filter_map(Value::as_str) {
+            let word = word.trim().to_lowercase();
+            if !word.is_empty() && !import.words.contains(&word) {
+                import.words.push(word);
+            }
+        }
+    }
+
+    if let Some(entries) = first_path(&root, layout.warning_paths).and_then(Value::as_array) {
+        for entry in entries {
+            if let Some((kind_key, kinds)) = layout.warning_kinds
+                && let Some(kind) = entry.get(kind_key).and_then(Value::as_str)
+                && !kinds.iter().any(|k| kind.eq_ignore_ascii_case(k))
+            {
+                continue;
+            }
+
+            let Some(user_id) = first_key(entry, layout.user_keys).and_then(as_id) else {
+                import.skipped_warnings += 1;
+                continue;
+            };
+
+            import.warnings.push(ImportedWarning {
+                user_id,
+                moderator_id: first_key(entry, layout.moderator_keys).and_then(as_id),
+                reason: first_key(entry, layout.reason_keys)
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .filter(|reason| !reason.is_empty())
+                    .unwrap_or("No reason provided")
+                    .to_owned(),
+                warned_at: first_key(entry, layout.time_keys).and_then(as_unix_secs),
+            });
+        }
+    }
+
+    if let Some(mute) = first_path(&root, layout.mute_paths) {
+        // Punishment lists hold one rule per action; take the first mute.
+        let rule = match mute {
+            Value::Array(rules) => rules.iter().find(|rule| {
+                rule.get("action")
+                    .and_then(Value::as_str)
+                    .is_some_and(|action| matches!(action, "mute" | "timeout"))
+            }),
+            other => Some(other),
+        };
+
+        if let Some(rule) = rule {
+            let config = ImportedMuteConfig {
+                warn_threshold: first_key(rule, layout.threshold_keys)
+                    .and_then(Value::as_u64)
+                    .and_then(|n| u32::try_from(n).ok())
+                    .filter(|n| *n > 0),
+                warn_window_seconds: first_key(rule, layout.window_keys).and_then(as_seconds),
+            };
+            if config.warn_threshold.is_some() {
+                import.mute = Some(config);
+            }
+        }
+    }
+
+    Ok(import)
+}
+
+fn first_path<'a>(root: &'a Value, paths: &[&str]) -> Option<&'a Value> {
+    paths.iter().find_map(|path| {
+        path.split('.')
+            .try_fold(root, |value, key| value.get(key))
+            .filter(|value| !value.is_null())
+    })
+}
+
+fn first_key<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a Value> {
+    keys.iter()
+        .find_map(|key| value.get(*key).filter(|value| !value.is_null()))
+}
+
+fn as_id(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(raw) => raw.trim().parse().ok(),
+        Value::Number(number) => number.as_u64(),
+        _ => None,
+    }
+    .filter(|id| *id != 0)
+}
+
+/// Numbers above 10^12 are taken as milliseconds.
+fn as_unix_secs(value: &Value) -> Option<u64> {
+    let number = match value {
+        Value::String(raw) => match raw.trim().parse::<u64>() {
+            Ok(number) => number,
+            Err(_) => return parse_rfc3339_unix_secs(raw),
+        },
+        Value::Number(number) => number.as_u64()?,
+        _ => return None,
+    };
+
+    Some(if number > 1_000_000_000_000 {
+        number / 1000
+    } else {
+        number
+    })
+}
+
+fn as_seconds(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(raw) => parse_duration_seconds(raw),
+        Value::Number(number) => number.as_u64().filter(|secs| *secs > 0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dyno_export() {
+        let import = parse_bot_export(
+            SourceBot::Dyno,
+            r#"{
+                "automod": { "bannedWords": ["Foo", "bar", "foo", " "] },
+                "warnings": [
+                    { "user": "123456789012345678", "mod": "223456789012345678",
+                      "reason": "spam", "createdAt": "2024-03-05T12:34:56.000Z" },
+                    { "mod": "223456789012345678", "reason": "no user" }
+                ],
+                "moderation": { "autoMute": { "warnings": 3, "window": "1d" } }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(import.words, vec!["foo".to_owned(), "bar".to_owned()]);
+        assert_eq!(
+            import.warnings,
+            vec![ImportedWarning {
+                user_id: 123_456_789_012_345_678,
+                moderator_id: Some(223_456_789_012_345_678),
+                reason: "spam".to_owned(),
+                warned_at: Some(1_709_642_096),
+            }]
+        );
+        assert_eq!(import.skipped_warnings, 1);
+        assert_eq!(
+            import.mute,
+            Some(ImportedMuteConfig {
+                warn_threshold: Some(3),
+                warn_window_seconds: Some(86_400),
+            })
+        );
+    }
+
+    #[test]
+    fn carlbot_infractions_keep_only_warnings() {
+        let import = parse_bot_export(
+            SourceBot::Carlbot,
+            r#"{
+                "infractions": [
+                    { "action": "warn", "user_id": 5, "moderator_id": 6,
+                      "timestamp": 1709642096000 },
+                    { "action": "ban", "user_id": 7, "moderator_id": 6 }
+                ],
+                "warn_punishments": [
+                    { "warns": 2, "action": "kick" },
+                    { "warns": 4, "action": "mute", "within": 3600 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(import.warnings.len(), 1);
+        assert_eq!(import.warnings[0].user_id, 5);
+        assert_eq!(import.warnings[0].reason, "No reason provided");
+        assert_eq!(import.warnings[0].warned_at, Some(1_709_642_096));
+        assert_eq!(
+            import.mute,
+            Some(ImportedMuteConfig {
+                warn_threshold: Some(4),
+                warn_window_seconds: Some(3_600),
+            })
+        );
+    }
+
+    #[test]
+    fn missing_sections_are_empty() {
+        let import = parse_bot_export(SourceBot::Wick, r#"{ "prefix": "w!" }"#).unwrap();
+        assert!(import.is_empty());
+        assert!(parse_bot_export(SourceBot::Wick, "not json").is_err());
+    }
+
+    #[test]
+    fn parses_bot_names() {
+        assert_eq!(SourceBot::parse("Carl-bot"), Some(SourceBot::Carlbot));
+        assert_eq!(SourceBot::parse(" dyno "), Some(SourceBot::Dyno));
+        assert_eq!(SourceBot::parse("mee6"), None);
+    }
+}
@@ -1,3 +1,7 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
 use poise::serenity_prelude as serenity;
 use tokio::time::{Duration, sleep};
 use tracing::error;
@@ -7,32 +11,77 @@ use crate::time::now_unix_secs;
 const BULK_DELETE_MAX_AGE_SECS: u64 = 14 * 24 * 60 * 60;
 const BULK_DELETE_SAFETY_BUFFER_SECS: u64 = 60 * 60;
 const HISTORY_PAGE_DELAY_MS: u64 = 1100;
+/// Least time between progress reports, so edits stay under Discord's
+/// message edit rate limit.
+const PROGRESS_REPORT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How far [`purge_user_globally`] has got.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeProgress {
+    pub channels_scanned: usize,
+    pub channels_total: usize,
+    pub messages_deleted: u64,
+}
+
+impl PurgeProgress {
+    /// One-line summary, e.g. `Scanned 3/12 channels, deleted 45 messages`.
+    pub fn summary(&self) -> String {
+        format!(
+            "Scanned {}/{} channels, deleted {} message{}",
+            self.channels_scanned,
+            self.channels_total,
+            self.messages_deleted,
+            if self.messages_deleted == 1 { "" } else { "s" }
+        )
+    }
+}
 
-pub async fn purge_user_globally(
+/// Delete the target's messages from every text channel and thread in the
+/// guild, newest first, stopping at `cutoff_secs` when set.
+///
+/// `on_progress` runs after channels finish, at most every few seconds and
+/// once more at the end. Setting `abort` stops the purge after the current
+/// history page; what was deleted so far is still returned.
+pub async fn purge_user_globally<F, Fut>(
     http: &serenity::Http,
     guild_id: serenity::GuildId,
     target_user_id: serenity::UserId,
     cutoff_secs: Option<u64>,
-) -> anyhow::Result<u64> {
-    let channels = guild_id.channels(http).await?;
-    let mut deleted_count = 0_u64;
-    let bulk_delete_cutoff = now_unix_secs()
-        .saturating_sub(BULK_DELETE_MAX_AGE_SECS.saturating_sub(BULK_DELETE_SAFETY_BUFFER_SECS))
-        as i64;
-
-    for channel in channels.values() {
-        if !matches!(
-            channel.kind,
-            serenity::ChannelType::Text
-                | serenity::ChannelType::News
-                | serenity::ChannelType::PublicThread
-                | serenity::ChannelType::PrivateThread
-                | serenity::ChannelType::NewsThread
-        ) {
-            continue;
+    abort: &AtomicBool,
+    mut on_progress: F,
+) -> anyhow::Result<PurgeProgress>
+where
+    F: FnMut(PurgeProgress) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let channels: Vec<serenity::ChannelId> = guild_id
+        .channels(http)
+        .await?
+        .into_values()
+        .filter(|channel| {
+            matches!(
+                channel.kind,
+                serenity::ChannelType::Text
+                    | serenity::ChannelType::News
+                    | serenity::ChannelType::PublicThread
+                    | serenity::ChannelType::PrivateThread
+                    | serenity::ChannelType::NewsThread
+            )
+        })
+        .map(|channel| channel.id)
+        .collect();
+
+    let mut progress = PurgeProgress {
+        channels_total: channels.len(),
+        ..Default::default()
+    };
+    let mut last_report = Instant::now();
+
+    for channel_id in channels {
+        if abort.load(Ordering::Relaxed) {
+            break;
         }
 
-        let channel_id = channel.id;
         let mut before: Option<serenity::MessageId> = None;
 
         loop {
@@ -59,64 +108,123 @@ pub async fn purge_user_globally(
                     .unwrap_or(false)
             });
 
-            let mut bulk_candidate_ids: Vec<serenity::MessageId> = Vec::new();
-            let mut single_delete_ids: Vec<serenity::MessageId> = Vec::new();
-
-            for message in messages {
-                if message.author.id != target_user_id {
-                    continue;
-                }
-
-                if let Some(cutoff) = cutoff_secs
-                    && message.timestamp.unix_timestamp() < cutoff as i64
-                {
-                    continue;
-                }
-
-                if message.timestamp.unix_timestamp() >= bulk_delete_cutoff {
-                    bulk_candidate_ids.push(message.id);
-                } else {
-                    single_delete_ids.push(message.id);
-                }
+            let target_messages: Vec<(serenity::MessageId, i64)> = messages
+                .iter()
+                .filter(|message| message.author.id == target_user_id)
+                .filter(|message| {
+                    cutoff_secs
+                        .is_none_or(|cutoff| message.timestamp.unix_timestamp() >= cutoff as i64)
+                })
+                .map(|message| (message.id, message.timestamp.unix_timestamp()))
+                .collect();
+
+            progress.messages_deleted = progress.messages_deleted.saturating_add(
+                delete_messages_with_fallback(http, channel_id, &target_messages).await,
+            );
+
+            if should_break_for_cutoff || abort.load(Ordering::Relaxed) {
+                break;
             }
 
-            if !bulk_candidate_ids.is_empty() {
-                for chunk in bulk_candidate_ids.chunks(100) {
-                    if chunk.len() < 2 {
-                        single_delete_ids.extend_from_slice(chunk);
-                        continue;
-                    }
-
-                    match channel_id.delete_messages(http, chunk.to_vec()).await {
-                        Ok(_) => {
-                            deleted_count = deleted_count.saturating_add(chunk.len() as u64);
-                        }
-                        Err(source) => {
-                            error!(
-                                ?source,
-                                channel_id = channel_id.get(),
-                                count = chunk.len(),
-                                "bulk delete failed, falling back to single delete"
-                            );
-                            single_delete_ids.extend_from_slice(chunk);
-                        }
-                    }
-                }
-            }
+            sleep(Duration::from_millis(HISTORY_PAGE_DELAY_MS)).await;
+        }
 
-            for message_id in single_delete_ids {
-                if channel_id.delete_message(http, message_id).await.is_ok() {
-                    deleted_count = deleted_count.saturating_add(1);
-                }
-            }
+        progress.channels_scanned += 1;
+        if last_report.elapsed() >= PROGRESS_REPORT_INTERVAL {
+            on_progress(progress).await;
+            last_report = Instant::now();
+        }
+    }
 
-            if should_break_for_cutoff {
-                break;
+    on_progress(progress).await;
+
+    Ok(progress)
+}
+
+/// Delete `(message_id, created_at_unix)` pairs from one channel. Messages
+/// young enough are bulk-deleted in chunks of 100; older ones (Discord refuses
+/// to bulk-delete past 14 days) and failed chunks fall back to single deletes.
+/// Returns how many were deleted.
+pub async fn delete_messages_with_fallback(
+    http: &serenity::Http,
+    channel_id: serenity::ChannelId,
+    messages: &[(serenity::MessageId, i64)],
+) -> u64 {
+    let bulk_delete_cutoff = now_unix_secs()
+        .saturating_sub(BULK_DELETE_MAX_AGE_SECS.saturating_sub(BULK_DELETE_SAFETY_BUFFER_SECS))
+        as i64;
+    let mut deleted_count = 0_u64;
+
+    let mut bulk_candidate_ids: Vec<serenity::MessageId> = Vec::new();
+    let mut single_delete_ids: Vec<serenity::MessageId> = Vec::new();
+    for (message_id, created_at) in messages {
+        if *created_at >= bulk_delete_cutoff {
+            bulk_candidate_ids.push(*message_id);
+        } else {
+            single_delete_ids.push(*message_id);
+        }
+    }
+
+    for chunk in bulk_candidate_ids.chunks(100) {
+        if chunk.len() < 2 {
+            single_delete_ids.extend_from_slice(chunk);
+            continue;
+        }
+
+        match channel_id.delete_messages(http, chunk.to_vec()).await {
+            Ok(_) => {
+                deleted_count = deleted_count.saturating_add(chunk.len() as u64);
+            }
+            Err(source) => {
+                error!(
+                    ?source,
+                    channel_id = channel_id.get(),
+                    count = chunk.len(),
+                    "bulk delete failed, falling back to single delete"
+                );
+                single_delete_ids.extend_from_slice(chunk);
             }
+        }
+    }
 
-            sleep(Duration::from_millis(HISTORY_PAGE_DELAY_MS)).await;
+    for message_id in single_delete_ids {
+        if channel_id.delete_message(http, message_id).await.is_ok() {
+            deleted_count = deleted_count.saturating_add(1);
         }
     }
 
-    Ok(deleted_count)
+    deleted_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_summary_counts_channels_and_messages() {
+        let progress = PurgeProgress {
+            channels_scanned: 3,
+            channels_total: 12,
+            messages_deleted: 45,
+        };
+
+        assert_eq!(
+            progress.summary(),
+            "Scanned 3/12 channels, deleted 45 messages"
+        );
+    }
+
+    #[test]
+    fn progress_summary_uses_singular_for_one_message() {
+        let progress = PurgeProgress {
+            channels_scanned: 1,
+            channels_total: 1,
+            messages_deleted: 1,
+        };
+
+        assert_eq!(
+            progress.summary(),
+            "Scanned 1/1 channels, deleted 1 message"
+        );
+    }
 }
@@ -8,6 +8,38 @@ pub enum ConfirmationResult {
     TimedOut(serenity::Message),
 }
 
+/// Outcome of [`prompt_confirm_with_reason`]. A confirmation carries the
+/// submitted modal, still unanswered, and the reason typed into it.
+pub enum ReasonConfirmationResult {
+    Confirmed {
+        interaction: serenity::ModalInteraction,
+        reason: String,
+    },
+    Declined(serenity::ComponentInteraction),
+    /// The typed confirmation text didn't match; the modal is unanswered.
+    Mismatched(serenity::ModalInteraction),
+    TimedOut(serenity::Message),
+}
+
+/// Text a moderator must type to confirm a high-risk action, e.g. the
+/// target's username.
+#[derive(Debug, Clone, Copy)]
+pub struct TypedConfirmation<'a> {
+    /// Modal field label, at most 45 characters.
+    pub label: &'a str,
+    pub expected: &'a str,
+}
+
+/// Shown when the typed confirmation text doesn't match.
+pub const TYPED_CONFIRMATION_MISMATCH_TEXT: &str =
+    "The confirmation text didn't match, so nothing was done.";
+
+/// How long the reason modal stays open once Confirm is pressed.
+pub const REASON_MODAL_TIMEOUT_SECS: u64 = 120;
+
+/// Longest reason the modal accepts.
+pub const REASON_MAX_CHARS: u16 = 500;
+
 pub async fn resolve_confirmation_result<U, E>(
     ctx: poise::Context<'_, U, E>,
     confirmation: ConfirmationResult,
@@ -112,3 +144,212 @@ where
 
     Ok(ConfirmationResult::Confirmed(interaction))
 }
+
+/// Like [`prompt_confirm_decline`], but Confirm opens a modal asking for the
+/// reason, prefilled with `default_reason`, so destructive actions are
+/// always documented. Closing the modal without submitting counts as a
+/// timeout.
+pub async fn prompt_confirm_with_reason<U, E>(
+    ctx: poise::Context<'_, U, E>,
+    content: impl Into<String>,
+    embed: serenity::CreateEmbed,
+    timeout: Duration,
+    default_reason: Option<&str>,
+) -> Result<ReasonConfirmationResult, serenity::Error>
+where
+    U: Send + Sync,
+    E: Send + Sync,
+{
+    prompt_confirm_modal(ctx, content, embed, timeout, default_reason, None).await
+}
+
+/// The stricter [`prompt_confirm_with_reason`] for high-risk actions: the
+/// modal also asks the moderator to type `typed.expected`, so a misclick
+/// can't go through.
+pub async fn prompt_confirm_typed_with_reason<U, E>(
+    ctx: poise::Context<'_, U, E>,
+    content: impl Into<String>,
+    embed: serenity::CreateEmbed,
+    timeout: Duration,
+    default_reason: Option<&str>,
+    typed: TypedConfirmation<'_>,
+) -> Result<ReasonConfirmationResult, serenity::Error>
+where
+    U: Send + Sync,
+    E: Send + Sync,
+{
+    prompt_confirm_modal(ctx, content, embed, timeout, default_reason, Some(typed)).await
+}
+
+/// Whether `typed` matches the expected confirmation text, ignoring case
+/// and surrounding whitespace.
+pub fn typed_confirmation_matches(typed: &str, expected: &str) -> bool {
+    typed.trim().to_lowercase() == expected.trim().to_lowercase()
+}
+
+async fn prompt_confirm_modal<U, E>(
+    ctx: poise::Context<'_, U, E>,
+    content: impl Into<String>,
+    embed: serenity::CreateEmbed,
+    timeout: Duration,
+    default_reason: Option<&str>,
+    typed: Option<TypedConfirmation<'_>>,
+) -> Result<ReasonConfirmationResult, serenity::Error>
+where
+    U: Send + Sync,
+    E: Send + Sync,
+{
+    let interaction = match prompt_confirm_decline(ctx, content, embed, timeout).await? {
+        ConfirmationResult::Confirmed(interaction) => interaction,
+        ConfirmationResult::Declined(interaction) => {
+            return Ok(ReasonConfirmationResult::Declined(interaction));
+        }
+        ConfirmationResult::TimedOut(message) => {
+            return Ok(ReasonConfirmationResult::TimedOut(message));
+        }
+    };
+
+    let modal_id = format!("{}_reason_modal", ctx.id());
+    let mut reason_input =
+        serenity::CreateInputText::new(serenity::InputTextStyle::Paragraph, "Reason", "reason")
+            .placeholder("Why is this being done?")
+            .max_length(REASON_MAX_CHARS)
+            .required(true);
+    if let Some(default_reason) = default_reason {
+        reason_input = reason_input.value(default_reason);
+    }
+
+    let mut components = vec![serenity::CreateActionRow::InputText(reason_input)];
+    if let Some(typed) = typed {
+        components.push(serenity::CreateActionRow::InputText(
+            serenity::CreateInputText::new(serenity::InputTextStyle::Short, typed.label, "typed")
+                .placeholder(typed.expected)
+                .required(true),
+        ));
+    }
+
+    interaction
+        .create_response(
+            ctx.http(),
+            serenity::CreateInteractionResponse::Modal(
+                serenity::CreateModal::new(&modal_id, "Confirm with Reason").components(components),
+            ),
+        )
+        .await?;
+
+    let submission = serenity::ModalInteractionCollector::new(ctx)
+        .custom_ids(vec![modal_id])
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(REASON_MODAL_TIMEOUT_SECS))
+        .await;
+
+    let reason = submission.as_ref().and_then(|submission| {
+        modal_input_value(submission, "reason")
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+    });
+
+    match (submission, reason) {
+        (Some(interaction), Some(reason)) => Ok(ReasonConfirmationResult::Confirmed {
+            interaction,
+            reason,
+        }),
+        _ => Ok(ReasonConfirmationResult::TimedOut(*interaction.message)),
+    }
+}
+
+/// Answer a [`prompt_confirm_with_reason`] outcome with the matching text,
+/// returning the modal and reason when the moderator confirmed.
+pub async fn resolve_reason_confirmation_result<U, E>(
+    ctx: poise::Context<'_, U, E>,
+    confirmation: ReasonConfirmationResult,
+    timed_out_text: &str,
+    declined_text: &str,
+    processing_text: &str,
+) -> Result<Option<(serenity::ModalInteraction, String)>, serenity::Error>
+where
+    U: Send + Sync,
+    E: Send + Sync,
+{
+    match confirmation {
+        ReasonConfirmationResult::TimedOut(message) => {
+            resolve_confirmation_result(
+                ctx,
+                ConfirmationResult::TimedOut(message),
+                timed_out_text,
+                declined_text,
+                processing_text,
+            )
+            .await?;
+            Ok(None)
+        }
+        ReasonConfirmationResult::Declined(interaction) => {
+            resolve_confirmation_result(
+                ctx,
+                ConfirmationResult::Declined(interaction),
+                timed_out_text,
+                declined_text,
+                processing_text,
+            )
+            .await?;
+            Ok(None)
+        }
+        ReasonConfirmationResult::Mismatched(interaction) => {
+            interaction
+                .create_response(
+                    ctx.http(),
+                    serenity::CreateInteractionResponse::UpdateMessage(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content(TYPED_CONFIRMATION_MISMATCH_TEXT)
+                            .embeds(vec![])
+                            .components(vec![]),
+                    ),
+                )
+                .await?;
+            Ok(None)
+        }
+        ReasonConfirmationResult::Confirmed {
+            interaction,
+            reason,
+        } => {
+            interaction
+                .create_response(
+                    ctx.http(),
+                    serenity::CreateInteractionResponse::UpdateMessage(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content(processing_text)
+                            .embeds(vec![])
+                            .components(vec![]),
+                    ),
+                )
+                .await?;
+            Ok(Some((interaction, reason)))
+        }
+    }
+}
+
+fn modal_input_value(modal: &serenity::ModalInteraction, custom_id: &str) -> Option<String> {
+    modal
+        .data
+        .components
+        .iter()
+        .flat_map(|row| &row.components)
+        .find_map(|component| match component {
+            serenity::ActionRowComponent::InputText(input) if input.custom_id == custom_id => {
+                input.value.clone()
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_confirmation_ignores_case_and_padding() {
+        assert!(typed_confirmation_matches(" Spammer99 ", "spammer99"));
+        assert!(!typed_confirmation_matches("spammer9", "spammer99"));
+        assert!(!typed_confirmation_matches("", "spammer99"));
+    }
+}
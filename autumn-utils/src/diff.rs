@@ -0,0 +1,181 @@
+/// Word-level diffs above this many comparisons fall back to showing the whole
+/// message as replaced, keeping the LCS table small.
+const MAX_DIFF_CELLS: usize = 250_000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Equal,
+    Removed,
+    Added,
+}
+
+/// Render a word-level diff of `before` → `after` as Discord markdown:
+/// removed words are struck through and added words are bold.
+///
+/// Markdown in the input is escaped so it can't break the markers, and the
+/// output stops at a whole segment once it would exceed `max_len` characters.
+pub fn render_word_diff(before: &str, after: &str, max_len: usize) -> String {
+    let old_tokens = tokenize(before);
+    let new_tokens = tokenize(after);
+    let ops = diff_tokens(&old_tokens, &new_tokens);
+
+    let mut output = String::new();
+    let mut output_len = 0;
+    let mut index = 0;
+
+    while index < ops.len() {
+        let (op, _) = ops[index];
+        let run_end = ops[index..]
+            .iter()
+            .position(|(other, _)| *other != op)
+            .map_or(ops.len(), |offset| index + offset);
+
+        let run = &ops[index..run_end];
+        // Removed words don't exist in the new text, so their trailing
+        // whitespace is replaced by a plain separator.
+        let last_gap = match op {
+            DiffOp::Removed if run_end < ops.len() => " ",
+            DiffOp::Removed => "",
+            _ => run.last().map_or("", |(_, token)| token.gap),
+        };
+        let mut inner = String::new();
+        for (position, (_, token)) in run.iter().enumerate() {
+            inner.push_str(&escape_markdown(token.word));
+            if position + 1 < run.len() {
+                inner.push_str(token.gap);
+            }
+        }
+
+        let segment = match op {
+            DiffOp::Equal => format!("{}{}", inner, last_gap),
+            DiffOp::Removed => format!("~~{}~~{}", inner, last_gap),
+            DiffOp::Added => format!("**{}**{}", inner, last_gap),
+        };
+
+        let segment_len = segment.chars().count();
+        if output_len + segment_len > max_len {
+            output.push('…');
+            break;
+        }
+
+        output.push_str(&segment);
+        output_len += segment_len;
+        index = run_end;
+    }
+
+    output.trim_end().to_owned()
+}
+
+struct Token<'a> {
+    word: &'a str,
+    gap: &'a str,
+}
+
+/// Split `value` into words, each carrying the whitespace that follows it so
+/// line breaks survive the diff.
+fn tokenize(value: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = value.trim_start();
+
+    while !rest.is_empty() {
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (word, after_word) = rest.split_at(word_end);
+        let gap_end = after_word
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(after_word.len());
+        let (gap, next) = after_word.split_at(gap_end);
+        tokens.push(Token { word, gap });
+        rest = next;
+    }
+
+    tokens
+}
+
+fn diff_tokens<'t, 'a>(
+    old_tokens: &'t [Token<'a>],
+    new_tokens: &'t [Token<'a>],
+) -> Vec<(DiffOp, &'t Token<'a>)> {
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        return old_tokens
+            .iter()
+            .map(|token| (DiffOp::Removed, token))
+            .chain(new_tokens.iter().map(|token| (DiffOp::Added, token)))
+            .collect();
+    }
+
+    // lcs[i][j] = LCS length of old_tokens[i..] and new_tokens[j..].
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_tokens[i].word == new_tokens[j].word {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_tokens[i].word == new_tokens[j].word {
+            ops.push((DiffOp::Equal, &new_tokens[j]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push((DiffOp::Removed, &old_tokens[i]));
+            i += 1;
+        } else {
+            ops.push((DiffOp::Added, &new_tokens[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old_tokens[i..].iter().map(|token| (DiffOp::Removed, token)));
+    ops.extend(new_tokens[j..].iter().map(|token| (DiffOp::Added, token)));
+
+    ops
+}
+
+fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if matches!(character, '\\' | '*' | '_' | '~' | '`' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_word_diff;
+
+    #[test]
+    fn marks_removed_and_added_words() {
+        assert_eq!(
+            render_word_diff("the quick brown fox", "the slow brown fox jumps", 200),
+            "the ~~quick~~ **slow** brown fox **jumps**"
+        );
+        assert_eq!(render_word_diff("same text", "same text", 200), "same text");
+    }
+
+    #[test]
+    fn groups_runs_and_keeps_line_breaks() {
+        assert_eq!(
+            render_word_diff("a b c\nd", "a x y\nd", 200),
+            "a ~~b c~~ **x y**\nd"
+        );
+    }
+
+    #[test]
+    fn escapes_markdown_and_truncates_on_segments() {
+        assert_eq!(
+            render_word_diff("*hi*", "~~hi~~", 200),
+            "~~\\*hi\\*~~ **\\~\\~hi\\~\\~**"
+        );
+        assert_eq!(render_word_diff("one", "one two three", 6), "one …");
+    }
+}
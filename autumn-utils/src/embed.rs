@@ -3,14 +3,28 @@ use poise::serenity_prelude as serenity;
 /// Default embed color used across the bot UI.
 pub const DEFAULT_EMBED_COLOR: u32 = 0x90_55_30;
 
+/// A blank embed in a guild's accent color, or the default color when the
+/// guild hasn't set one.
+pub fn guild_embed(accent_color: Option<u32>) -> serenity::CreateEmbed {
+    serenity::CreateEmbed::new().color(accent_color.unwrap_or(DEFAULT_EMBED_COLOR))
+}
+
 /// Build a standard paginated embed with consistent styling.
 pub fn build_paginated_embed(
     title: &str,
     description: impl Into<String>,
     page: usize,
     total_pages: usize,
+    accent_color: Option<u32>,
 ) -> serenity::CreateEmbed {
-    build_paginated_embed_with_footer_note(title, description, page, total_pages, None)
+    build_paginated_embed_with_footer_note(
+        title,
+        description,
+        page,
+        total_pages,
+        None,
+        accent_color,
+    )
 }
 
 /// Build a standard paginated embed with an optional footer suffix.
@@ -20,6 +34,7 @@ pub fn build_paginated_embed_with_footer_note(
     page: usize,
     total_pages: usize,
     footer_note: Option<&str>,
+    accent_color: Option<u32>,
 ) -> serenity::CreateEmbed {
     let page = page.max(1);
     let total_pages = total_pages.max(1);
@@ -36,9 +51,8 @@ pub fn build_paginated_embed_with_footer_note(
         }
     };
 
-    let builder = serenity::CreateEmbed::new()
+    let builder = guild_embed(accent_color)
         .title(title.to_owned())
-        .color(DEFAULT_EMBED_COLOR)
         .description(description);
 
     if footer_text.is_empty() {
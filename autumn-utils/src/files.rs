@@ -0,0 +1,43 @@
+/// Lowercased extension of an attachment filename, without the dot.
+///
+/// Only the last segment counts, so `setup.exe.txt` is `txt` and a file
+/// named `.exe` or `archive` has no extension.
+pub fn file_extension(filename: &str) -> Option<String> {
+    let (stem, extension) = filename.rsplit_once('.')?;
+    if stem.is_empty() || extension.is_empty() {
+        return None;
+    }
+
+    Some(extension.to_ascii_lowercase())
+}
+
+/// Normalize user input like `.EXE` or `exe` to `exe`.
+pub fn normalize_extension_input(raw: &str) -> Option<String> {
+    let extension = raw.trim().trim_start_matches('.').to_ascii_lowercase();
+    let valid = !extension.is_empty()
+        && extension.len() <= 16
+        && extension.chars().all(|c| c.is_ascii_alphanumeric());
+    valid.then_some(extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_extension, normalize_extension_input};
+
+    #[test]
+    fn extracts_last_extension() {
+        assert_eq!(file_extension("Setup.EXE"), Some("exe".to_owned()));
+        assert_eq!(file_extension("setup.exe.txt"), Some("txt".to_owned()));
+        assert_eq!(file_extension(".exe"), None);
+        assert_eq!(file_extension("archive"), None);
+        assert_eq!(file_extension("trailing."), None);
+    }
+
+    #[test]
+    fn normalizes_extension_input() {
+        assert_eq!(normalize_extension_input(" .SCR "), Some("scr".to_owned()));
+        assert_eq!(normalize_extension_input("exe"), Some("exe".to_owned()));
+        assert_eq!(normalize_extension_input("."), None);
+        assert_eq!(normalize_extension_input("ex e"), None);
+    }
+}
@@ -3,6 +3,15 @@ pub fn format_case_label(case_code: &str, action_case_number: u64) -> String {
     format!("{}{}", case_code.to_ascii_uppercase(), action_case_number)
 }
 
+/// Whether a case action was produced by an automod filter (e.g.
+/// `word_filter_delete`, `spam_repeat_warn`), whose reason is the matched
+/// violation.
+pub fn is_filter_action(action: &str) -> bool {
+    action
+        .rsplit_once('_')
+        .is_some_and(|(prefix, _)| prefix.ends_with("_filter") || prefix == "spam_repeat")
+}
+
 /// Convert internal action identifiers to user-facing names.
 pub fn action_display_name(action: &str) -> String {
     match action {
@@ -17,6 +26,12 @@ pub fn action_display_name(action: &str) -> String {
         "purge" => "Purge".to_owned(),
         "terminate" => "Terminate".to_owned(),
         "auto_timeout" => "Auto Timeout".to_owned(),
+        "temprole" => "Temporary Role".to_owned(),
+        "temprole_remove" => "Temporary Role Removed".to_owned(),
+        "report" => "Member Report".to_owned(),
+        "bansync_ban" => "Synced Ban".to_owned(),
+        "shadowmute" => "Shadow Mute".to_owned(),
+        "unshadowmute" => "Shadow Mute Lifted".to_owned(),
         other => {
             let normalized = other.trim();
             if normalized.is_empty() {
@@ -60,6 +75,16 @@ pub fn action_past_tense(action: &str) -> String {
         "purge" => "purged".to_owned(),
         "terminate" => "terminated".to_owned(),
         "auto_timeout" => "automatically timed out".to_owned(),
+        "temprole" => "given a temporary role".to_owned(),
+        "temprole_remove" => "removed from a temporary role".to_owned(),
+        "report" => "reported".to_owned(),
+        "quarantine" => "quarantined".to_owned(),
+        "release" => "released from quarantine".to_owned(),
+        "shadowmute" => "shadow muted".to_owned(),
+        "unshadowmute" => "no longer shadow muted".to_owned(),
+        "global_blacklist_ban" => "banned (global blacklist)".to_owned(),
+        "global_blacklist_quarantine" => "quarantined (global blacklist)".to_owned(),
+        "bansync_ban" => "banned (ban sync)".to_owned(),
         other => format!("{}ed", other),
     }
 }
@@ -132,6 +157,7 @@ pub fn event_display_name(event_type: &str) -> &'static str {
         "created" => "Created",
         "reason_updated" => "Reason Updated",
         "note_added" => "Note Added",
+        "status_changed" => "Status Changed",
         _ => "Updated",
     }
 }
@@ -140,7 +166,7 @@ pub fn event_display_name(event_type: &str) -> &'static str {
 mod tests {
     use super::{
         action_display_name, action_past_tense, event_display_name, format_case_label,
-        format_compact_duration, parse_case_label,
+        format_compact_duration, is_filter_action, parse_case_label,
     };
 
     #[test]
@@ -166,11 +192,21 @@ mod tests {
         assert_eq!(action_display_name("custom_action"), "Custom Action");
     }
 
+    #[test]
+    fn filter_actions_are_detected() {
+        assert!(is_filter_action("word_filter_delete"));
+        assert!(is_filter_action("link_filter_timeout"));
+        assert!(is_filter_action("spam_repeat_delete"));
+        assert!(!is_filter_action("auto_timeout"));
+        assert!(!is_filter_action("warn"));
+    }
+
     #[test]
     fn action_past_tense_phrases_fit_embed_titles() {
         assert_eq!(action_past_tense("untimeout"), "untimed out");
         assert_eq!(action_past_tense("unwarn"), "unwarned");
         assert_eq!(action_past_tense("unwarn_all"), "unwarned all");
+        assert_eq!(action_past_tense("temprole"), "given a temporary role");
     }
 
     #[test]
@@ -178,6 +214,7 @@ mod tests {
         assert_eq!(event_display_name("created"), "Created");
         assert_eq!(event_display_name("reason_updated"), "Reason Updated");
         assert_eq!(event_display_name("note_added"), "Note Added");
+        assert_eq!(event_display_name("status_changed"), "Status Changed");
         assert_eq!(event_display_name("other"), "Updated");
     }
 
@@ -1,18 +1,40 @@
+/// Parsers for settings exported from other moderation bots.
+pub mod bot_import;
 /// Shared cleanup helpers for moderation operations.
 pub mod cleanup;
 /// Shared confirmation prompt helpers.
 pub mod confirmation;
+/// Word-level message diff rendering.
+pub mod diff;
 /// Generic embed builders shared across commands.
 pub mod embed;
+/// Attachment filename helpers.
+pub mod files;
 /// Shared formatting helpers (case labels, action names, parsing).
 pub mod formatting;
+/// Link and domain parsing helpers.
+pub mod links;
 /// Single source of truth for the message-command prefix.
 pub const COMMAND_PREFIX: char = '!';
+/// Per-guild modlog embed template rendering.
+pub mod modlog_template;
+/// Text normalization for filter matching (confusables, leet-speak).
+pub mod normalize;
+/// Splits structured entries into embed-sized pages.
+pub mod page_builder;
 /// Shared pagination helper utilities.
 pub mod pagination;
 /// Pure parser helpers.
 pub mod parse;
 /// Permission helper utilities.
 pub mod permissions;
+/// Reaction polls: parsing, payloads and result tallies.
+pub mod poll;
+/// Process resource readings for status reporting.
+pub mod process;
+/// Cron-like schedules for recurring tasks.
+pub mod recurrence;
 /// Shared time helpers.
 pub mod time;
+/// Plain-text transcripts of deleted messages.
+pub mod transcript;
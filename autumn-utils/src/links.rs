@@ -0,0 +1,150 @@
+/// Extract the lowercased host of every `http://` or `https://` link in `content`.
+///
+/// Links wrapped in `<...>` (embed suppression) and trailing punctuation are
+/// handled; a leading `www.` is kept so callers can match on domain suffixes.
+pub fn extract_link_domains(content: &str) -> Vec<String> {
+    let lower = content.to_lowercase();
+    let mut domains = Vec::new();
+
+    for (start, _) in lower.match_indices("http") {
+        let rest = &lower[start..];
+        let Some(after_scheme) = rest
+            .strip_prefix("https://")
+            .or_else(|| rest.strip_prefix("http://"))
+        else {
+            continue;
+        };
+
+        let authority = after_scheme
+            .split(|c: char| matches!(c, '/' | '?' | '#' | '>' | ')' | ']') || c.is_whitespace())
+            .next()
+            .unwrap_or_default();
+
+        // Drop userinfo and port.
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        let host = host.split(':').next().unwrap_or_default();
+        let host = host.trim_end_matches(['.', ',', '!', '*', '_', '~', '|', '"', '\'']);
+
+        if host.contains('.') && !domains.iter().any(|d| d == host) {
+            domains.push(host.to_owned());
+        }
+    }
+
+    domains
+}
+
+/// Extract Discord invite codes (`discord.gg/<code>`, `discord.com/invite/<code>`)
+/// from `content`, preserving the code's original case.
+pub fn extract_invite_codes(content: &str) -> Vec<String> {
+    const INVITE_PREFIXES: &[&str] = &[
+        "discord.gg/",
+        "discord.com/invite/",
+        "discordapp.com/invite/",
+    ];
+
+    // ASCII lowercasing keeps byte offsets aligned with `content`.
+    let lower = content.to_ascii_lowercase();
+    let mut codes = Vec::new();
+
+    for prefix in INVITE_PREFIXES {
+        for (start, _) in lower.match_indices(prefix) {
+            let code: String = content[start + prefix.len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+                .collect();
+
+            if !code.is_empty() && !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+    }
+
+    codes
+}
+
+/// Check whether `host` is `domain` or one of its subdomains.
+pub fn domain_matches(host: &str, domain: &str) -> bool {
+    let host = host.trim_end_matches('.');
+    let domain = domain.trim_start_matches("*.").trim_end_matches('.');
+    if domain.is_empty() {
+        return false;
+    }
+
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Normalize user input like `https://www.Example.com/path` to `www.example.com`.
+pub fn normalize_domain_input(raw: &str) -> Option<String> {
+    let lower = raw.trim().to_lowercase();
+    let without_scheme = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))
+        .unwrap_or(&lower);
+    let host = without_scheme
+        .split(['/', '?', '#', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("*.")
+        .trim_end_matches('.');
+
+    let valid = host.contains('.')
+        && host
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '.');
+    valid.then(|| host.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        domain_matches, extract_invite_codes, extract_link_domains, normalize_domain_input,
+    };
+
+    #[test]
+    fn extracts_hosts_from_links() {
+        assert_eq!(
+            extract_link_domains("see https://Example.com/a?b and <http://www.test.org>"),
+            vec!["example.com".to_owned(), "www.test.org".to_owned()]
+        );
+        assert_eq!(
+            extract_link_domains("(https://user@host.io:8080/x), https://host.io."),
+            vec!["host.io".to_owned()]
+        );
+        assert!(extract_link_domains("no links, just http talk").is_empty());
+        assert!(extract_link_domains("http://localhost/").is_empty());
+    }
+
+    #[test]
+    fn extracts_invite_codes() {
+        assert_eq!(
+            extract_invite_codes("join discord.gg/AbC123 or https://Discord.com/invite/xyz-9!"),
+            vec!["AbC123".to_owned(), "xyz-9".to_owned()]
+        );
+        assert!(extract_invite_codes("discord.gg/ is empty").is_empty());
+    }
+
+    #[test]
+    fn matches_domains_and_subdomains() {
+        assert!(domain_matches("example.com", "example.com"));
+        assert!(domain_matches("cdn.example.com", "example.com"));
+        assert!(domain_matches("cdn.example.com", "*.example.com"));
+        assert!(!domain_matches("badexample.com", "example.com"));
+        assert!(!domain_matches("example.com", ""));
+    }
+
+    #[test]
+    fn normalizes_domain_input() {
+        assert_eq!(
+            normalize_domain_input("https://WWW.Example.com/path"),
+            Some("www.example.com".to_owned())
+        );
+        assert_eq!(
+            normalize_domain_input("*.example.com"),
+            Some("example.com".to_owned())
+        );
+        assert_eq!(normalize_domain_input("not a domain"), None);
+    }
+}
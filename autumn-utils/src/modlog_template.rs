@@ -0,0 +1,214 @@
+use poise::serenity_prelude as serenity;
+
+use crate::embed::guild_embed;
+
+/// Field keys a modlog template can reference.
+pub const MODLOG_TEMPLATE_FIELDS: &[&str] = &[
+    "target",
+    "reason",
+    "action",
+    "duration",
+    "moderator",
+    "when",
+];
+
+/// Template entry that inserts a blank line.
+pub const MODLOG_TEMPLATE_SEPARATOR: &str = "-";
+
+/// Footer placeholders replaced when rendering.
+pub const MODLOG_FOOTER_PLACEHOLDERS: &[&str] = &["{case}", "{action}"];
+
+/// One rendered `**Label :** value` line of a modlog embed, tagged with its
+/// template key. Separators use [`MODLOG_TEMPLATE_SEPARATOR`] and an empty line.
+#[derive(Clone, Debug)]
+pub struct ModlogField {
+    pub key: &'static str,
+    pub line: String,
+}
+
+impl ModlogField {
+    pub fn new(key: &'static str, line: String) -> Self {
+        Self { key, line }
+    }
+
+    pub fn separator() -> Self {
+        Self {
+            key: MODLOG_TEMPLATE_SEPARATOR,
+            line: String::new(),
+        }
+    }
+}
+
+/// A guild's modlog template, resolved from storage.
+#[derive(Clone, Debug, Default)]
+pub struct ModlogLayout {
+    pub field_order: Option<Vec<String>>,
+    pub color: Option<u32>,
+    pub footer: Option<String>,
+}
+
+/// Build a modlog embed from a publisher's fields, applying the guild's
+/// layout (field order, color, footer) when one is set.
+pub fn build_modlog_embed(
+    title: String,
+    fields: &[ModlogField],
+    layout: Option<&ModlogLayout>,
+    placeholders: &[(&str, &str)],
+) -> serenity::CreateEmbed {
+    let order = layout.and_then(|layout| layout.field_order.as_deref());
+    let color = layout.and_then(|layout| layout.color);
+
+    let mut embed = guild_embed(color)
+        .title(title)
+        .description(render_modlog_fields(fields, order));
+
+    if let Some(footer) = layout.and_then(|layout| layout.footer.as_deref()) {
+        embed = embed.footer(serenity::CreateEmbedFooter::new(render_placeholders(
+            footer,
+            placeholders,
+        )));
+    }
+
+    embed
+}
+
+/// Join `fields` into an embed description. Without a template `order` the
+/// publisher's default layout is kept; with one, only the listed keys are
+/// shown, in that order. Fields a publisher doesn't provide are skipped.
+pub fn render_modlog_fields(fields: &[ModlogField], order: Option<&[String]>) -> String {
+    let lines: Vec<&str> = match order {
+        None => fields.iter().map(|field| field.line.as_str()).collect(),
+        Some(order) => order
+            .iter()
+            .filter_map(|key| {
+                if key == MODLOG_TEMPLATE_SEPARATOR {
+                    return Some("");
+                }
+                fields
+                    .iter()
+                    .find(|field| field.key == key)
+                    .map(|field| field.line.as_str())
+            })
+            .collect(),
+    };
+
+    // Drop separators that would end up leading, trailing or doubled.
+    let mut output: Vec<&str> = Vec::with_capacity(lines.len());
+    for line in lines {
+        if line.is_empty() && output.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        output.push(line);
+    }
+    while output.last().is_some_and(|last| last.is_empty()) {
+        output.pop();
+    }
+
+    output.join("\n")
+}
+
+/// Parse a comma- or space-separated field list like `target, reason, -, when`.
+/// Returns the offending entry on error.
+pub fn parse_template_fields(input: &str) -> Result<Vec<String>, String> {
+    let fields: Vec<String> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|entry| entry.trim().to_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+
+    if let Some(unknown) = fields.iter().find(|entry| {
+        entry.as_str() != MODLOG_TEMPLATE_SEPARATOR
+            && !MODLOG_TEMPLATE_FIELDS.contains(&entry.as_str())
+    }) {
+        return Err(unknown.clone());
+    }
+
+    if fields.is_empty() {
+        return Err(String::new());
+    }
+
+    Ok(fields)
+}
+
+/// Parse `#rrggbb` / `rrggbb` into a color value.
+pub fn parse_hex_color(input: &str) -> Option<u32> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// Replace `{name}` placeholders with their values.
+pub fn render_placeholders(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_owned(), |output, (placeholder, value)| {
+            output.replace(placeholder, value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ModlogField, parse_hex_color, parse_template_fields, render_modlog_fields,
+        render_placeholders,
+    };
+
+    fn sample_fields() -> Vec<ModlogField> {
+        vec![
+            ModlogField::new("target", "**Target :** <@1>".to_owned()),
+            ModlogField::new("reason", "**Reason :** spam".to_owned()),
+            ModlogField::separator(),
+            ModlogField::new("moderator", "**Moderator :** <@2>".to_owned()),
+            ModlogField::new("when", "**When :** <t:0:R>".to_owned()),
+        ]
+    }
+
+    #[test]
+    fn renders_default_and_templated_layouts() {
+        let fields = sample_fields();
+        assert_eq!(
+            render_modlog_fields(&fields, None),
+            "**Target :** <@1>\n**Reason :** spam\n\n**Moderator :** <@2>\n**When :** <t:0:R>"
+        );
+
+        let order = ["-", "when", "-", "-", "duration", "target", "-"].map(str::to_owned);
+        assert_eq!(
+            render_modlog_fields(&fields, Some(&order)),
+            "**When :** <t:0:R>\n\n**Target :** <@1>"
+        );
+    }
+
+    #[test]
+    fn parses_template_fields() {
+        assert_eq!(
+            parse_template_fields("Target, reason - when"),
+            Ok(vec![
+                "target".to_owned(),
+                "reason".to_owned(),
+                "-".to_owned(),
+                "when".to_owned()
+            ])
+        );
+        assert_eq!(
+            parse_template_fields("target, avatar"),
+            Err("avatar".to_owned())
+        );
+        assert_eq!(parse_template_fields(" , "), Err(String::new()));
+    }
+
+    #[test]
+    fn parses_colors_and_placeholders() {
+        assert_eq!(parse_hex_color("#FF8800"), Some(0xFF8800));
+        assert_eq!(parse_hex_color("ff8800"), Some(0xFF8800));
+        assert_eq!(parse_hex_color("#fff"), None);
+        assert_eq!(
+            render_placeholders(
+                "Case {case} ({action})",
+                &[("{case}", "B3"), ("{action}", "Ban")]
+            ),
+            "Case B3 (Ban)"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Fold message text into a canonical lowercase form for filter matching.
+///
+/// Strips zero-width characters and diacritics, folds compatibility forms
+/// (fullwidth, mathematical letters) and common Cyrillic/Greek look-alikes to
+/// ASCII, and maps basic leet-speak (`1` → `i`, `@` → `a`). Digits are only
+/// mapped inside tokens that also contain letters, and symbols only when
+/// followed by an alphanumeric character, so plain numbers and punctuation
+/// at the end of a word are left alone.
+pub fn normalize_for_matching(text: &str) -> String {
+    let folded: String = text
+        .chars()
+        .filter(|ch| !is_zero_width(*ch))
+        .nfkd()
+        .filter(|ch| !is_combining_mark(*ch))
+        .flat_map(char::to_lowercase)
+        .map(fold_confusable)
+        .collect();
+
+    let mut out = String::with_capacity(folded.len());
+    for (index, token) in folded.split(char::is_whitespace).enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        push_leet_token(&mut out, token);
+    }
+
+    out
+}
+
+/// Stable fingerprint of message content for duplicate detection.
+///
+/// Content is normalized and whitespace-collapsed first so trivial variations
+/// (case, spacing, leet-speak) hash the same. Uses 64-bit FNV-1a so the value
+/// is stable across processes and can be shared through Redis.
+pub fn content_fingerprint(text: &str) -> String {
+    let normalized = normalize_for_matching(text);
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (index, word) in normalized.split_whitespace().enumerate() {
+        if index > 0 {
+            hash ^= u64::from(b' ');
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        for byte in word.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    format!("{hash:016x}")
+}
+
+/// Count `(letters, uppercase letters)` in `text` for caps detection.
+///
+/// Links and `<...>` tokens (mentions, channels, custom emoji) are skipped so
+/// they neither inflate nor dilute the ratio.
+pub fn letter_case_counts(text: &str) -> (usize, usize) {
+    let mut letters = 0;
+    let mut uppercase = 0;
+
+    for word in text.split_whitespace() {
+        let lower = word.to_ascii_lowercase();
+        if lower.starts_with("http://")
+            || lower.starts_with("https://")
+            || (word.starts_with('<') && word.ends_with('>'))
+        {
+            continue;
+        }
+
+        for ch in word.chars().filter(|ch| ch.is_alphabetic()) {
+            letters += 1;
+            if ch.is_uppercase() {
+                uppercase += 1;
+            }
+        }
+    }
+
+    (letters, uppercase)
+}
+
+fn push_leet_token(out: &mut String, token: &str) {
+    let chars: Vec<char> = token.chars().collect();
+    let has_letter = chars.iter().any(|ch| ch.is_alphabetic());
+
+    for (index, ch) in chars.iter().copied().enumerate() {
+        let followed_by_alnum = chars
+            .get(index + 1)
+            .is_some_and(|next| next.is_alphanumeric());
+
+        let mapped = if ch.is_ascii_digit() {
+            if has_letter { leet_digit(ch) } else { ch }
+        } else if followed_by_alnum {
+            leet_symbol(ch).unwrap_or(ch)
+        } else {
+            ch
+        };
+
+        out.push(mapped);
+    }
+}
+
+fn is_zero_width(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}' | '\u{034F}' | '\u{180E}' | '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{2064}'
+            | '\u{FEFF}'
+    )
+}
+
+fn fold_confusable(ch: char) -> char {
+    match ch {
+        // Cyrillic
+        'а' => 'a',
+        'в' => 'b',
+        'с' => 'c',
+        'ԁ' => 'd',
+        'е' | 'ё' | 'є' => 'e',
+        'һ' | 'н' => 'h',
+        'і' | 'ї' => 'i',
+        'ј' => 'j',
+        'к' => 'k',
+        'м' => 'm',
+        'о' => 'o',
+        'р' => 'p',
+        'ԛ' => 'q',
+        'ѕ' => 's',
+        'т' => 't',
+        'у' => 'y',
+        'ԝ' => 'w',
+        'х' => 'x',
+        // Greek
+        'α' => 'a',
+        'β' => 'b',
+        'ε' => 'e',
+        'η' => 'n',
+        'ι' => 'i',
+        'κ' => 'k',
+        'ν' => 'v',
+        'ο' => 'o',
+        'ρ' => 'p',
+        'τ' => 't',
+        'υ' => 'u',
+        'χ' => 'x',
+        // Latin look-alikes not covered by compatibility decomposition
+        'ı' => 'i',
+        'ł' => 'l',
+        'ø' => 'o',
+        'đ' => 'd',
+        'ß' => 's',
+        _ => ch,
+    }
+}
+
+fn leet_digit(ch: char) -> char {
+    match ch {
+        '0' => 'o',
+        '1' => 'i',
+        '2' => 'z',
+        '3' => 'e',
+        '4' => 'a',
+        '5' => 's',
+        '6' => 'g',
+        '7' => 't',
+        '8' => 'b',
+        '9' => 'g',
+        _ => ch,
+    }
+}
+
+fn leet_symbol(ch: char) -> Option<char> {
+    match ch {
+        '@' => Some('a'),
+        '$' => Some('s'),
+        '!' | '|' => Some('i'),
+        '+' => Some('t'),
+        '€' => Some('e'),
+        '£' => Some('l'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_fingerprint, letter_case_counts, normalize_for_matching};
+
+    #[test]
+    fn leaves_plain_ascii_lowercased() {
+        assert_eq!(normalize_for_matching("Hello World"), "hello world");
+        assert_eq!(normalize_for_matching(""), "");
+    }
+
+    #[test]
+    fn maps_leet_digits_inside_words() {
+        assert_eq!(normalize_for_matching("n1gg3r"), "nigger");
+        assert_eq!(normalize_for_matching("h4x0r"), "haxor");
+        assert_eq!(normalize_for_matching("l33t 5p34k"), "leet speak");
+    }
+
+    #[test]
+    fn leaves_plain_numbers_alone() {
+        assert_eq!(normalize_for_matching("I have 3 cats"), "i have 3 cats");
+        assert_eq!(normalize_for_matching("call 911"), "call 911");
+    }
+
+    #[test]
+    fn maps_leet_symbols_before_letters() {
+        assert_eq!(normalize_for_matching("@ss"), "ass");
+        assert_eq!(normalize_for_matching("$hit"), "shit");
+        assert_eq!(normalize_for_matching("n!gga"), "nigga");
+        assert_eq!(normalize_for_matching("b|tch"), "bitch");
+    }
+
+    #[test]
+    fn keeps_trailing_punctuation() {
+        assert_eq!(normalize_for_matching("hello!"), "hello!");
+        assert_eq!(normalize_for_matching("wow! ok"), "wow! ok");
+        assert_eq!(normalize_for_matching("price: 5$"), "price: 5$");
+    }
+
+    #[test]
+    fn removes_zero_width_characters() {
+        assert_eq!(normalize_for_matching("ta\u{200B}rd"), "tard");
+        assert_eq!(normalize_for_matching("f\u{200D}a\u{FEFF}g"), "fag");
+        assert_eq!(normalize_for_matching("sof\u{00AD}t"), "soft");
+    }
+
+    #[test]
+    fn strips_diacritics() {
+        assert_eq!(normalize_for_matching("fág"), "fag");
+        assert_eq!(normalize_for_matching("ñìggêr"), "nigger");
+        assert_eq!(normalize_for_matching("Zalgo: t̷̢a̸r̵d"), "zalgo: tard");
+    }
+
+    #[test]
+    fn folds_compatibility_forms() {
+        assert_eq!(normalize_for_matching("ｔａｒｄ"), "tard");
+        assert_eq!(normalize_for_matching("𝐭𝐚𝐫𝐝"), "tard");
+        assert_eq!(normalize_for_matching("ⓣⓐⓡⓓ"), "tard");
+    }
+
+    #[test]
+    fn folds_cyrillic_and_greek_lookalikes() {
+        assert_eq!(normalize_for_matching("fаg"), "fag");
+        assert_eq!(normalize_for_matching("сооn"), "coon");
+        assert_eq!(normalize_for_matching("ΚΙΚΕ"), "kike");
+    }
+
+    #[test]
+    fn combines_evasion_techniques() {
+        assert_eq!(normalize_for_matching("N\u{200B}1ĞĞ3Ŕ"), "nigger");
+        assert_eq!(normalize_for_matching("ｎ１ｇｇ４"), "nigga");
+    }
+
+    #[test]
+    fn preserves_token_boundaries() {
+        assert_eq!(normalize_for_matching("a  b\tc"), "a  b c");
+    }
+
+    #[test]
+    fn fingerprint_ignores_trivial_variations() {
+        assert_eq!(
+            content_fingerprint("Buy  Cheap NITRO"),
+            content_fingerprint("buy cheap n1tro")
+        );
+        assert_ne!(
+            content_fingerprint("hello"),
+            content_fingerprint("hello there")
+        );
+        assert_eq!(content_fingerprint("abc").len(), 16);
+    }
+
+    #[test]
+    fn counts_letter_case_outside_links_and_mentions() {
+        assert_eq!(letter_case_counts("HELLO world!"), (10, 5));
+        assert_eq!(
+            letter_case_counts("<@123> hi https://EXAMPLE.COM <:KEK:456>"),
+            (2, 0)
+        );
+        assert_eq!(letter_case_counts("1234 !!!"), (0, 0));
+    }
+}
@@ -0,0 +1,225 @@
+/// Most characters Discord shows in an embed description.
+pub const EMBED_DESCRIPTION_MAX_CHARS: usize = 4096;
+
+/// Most fields Discord allows on one embed, and so the most entries a page
+/// can hold if it's ever rendered as fields.
+pub const EMBED_MAX_FIELDS: usize = 25;
+
+/// One item on a page: a heading line, then `**Label :** value` fields and
+/// free text in the order they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageEntry {
+    heading: String,
+    lines: Vec<String>,
+}
+
+impl PageEntry {
+    pub fn new(heading: impl Into<String>) -> Self {
+        Self {
+            heading: heading.into(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Add a `**label :** value` line.
+    pub fn field(mut self, label: &str, value: impl AsRef<str>) -> Self {
+        self.lines
+            .push(format!("**{} :** {}", label, value.as_ref()));
+        self
+    }
+
+    /// Add a line of free text.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.lines.push(text.into());
+        self
+    }
+
+    fn render(&self) -> String {
+        let mut out = self.heading.clone();
+        for line in &self.lines {
+            out.push('\n');
+            out.push_str(line);
+        }
+        out
+    }
+}
+
+/// Splits entries into embed description pages, starting a new page when
+/// the entry count or Discord's description limit would be exceeded.
+#[derive(Debug, Clone)]
+pub struct PageBuilder {
+    per_page: usize,
+    header: Option<String>,
+    separator: &'static str,
+    entries: Vec<String>,
+}
+
+impl PageBuilder {
+    /// At most `per_page` entries per page, capped at [`EMBED_MAX_FIELDS`].
+    pub fn new(per_page: usize) -> Self {
+        Self {
+            per_page: per_page.clamp(1, EMBED_MAX_FIELDS),
+            header: None,
+            separator: "\n\n",
+            entries: Vec::new(),
+        }
+    }
+
+    /// Text repeated at the top of every page, e.g. a total count.
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// Put entries on consecutive lines instead of separating them with a
+    /// blank line.
+    pub fn compact(mut self) -> Self {
+        self.separator = "\n";
+        self
+    }
+
+    pub fn push(&mut self, entry: PageEntry) {
+        self.entries.push(entry.render());
+    }
+
+    /// The finished pages; none if no entries were pushed.
+    pub fn build(self) -> Vec<String> {
+        self.build_with_entry_pages().0
+    }
+
+    /// The finished pages, plus the 1-indexed page each entry landed on in
+    /// the order they were pushed.
+    pub fn build_with_entry_pages(self) -> (Vec<String>, Vec<usize>) {
+        let header = self
+            .header
+            .map(|header| truncate_chars(&header, EMBED_DESCRIPTION_MAX_CHARS / 2));
+        let header_chars = header.as_ref().map_or(0, |header| {
+            header.chars().count() + self.separator.chars().count()
+        });
+        let entry_budget = EMBED_DESCRIPTION_MAX_CHARS - header_chars;
+        let separator_chars = self.separator.chars().count();
+
+        let mut pages: Vec<Vec<String>> = Vec::new();
+        let mut current: Vec<String> = Vec::new();
+        let mut current_chars = 0;
+        let mut entry_pages = Vec::with_capacity(self.entries.len());
+
+        for entry in self.entries {
+            let entry = truncate_chars(&entry, entry_budget);
+            let entry_chars = entry.chars().count();
+
+            if !current.is_empty()
+                && (current.len() >= self.per_page
+                    || current_chars + separator_chars + entry_chars > entry_budget)
+            {
+                pages.push(std::mem::take(&mut current));
+                current_chars = 0;
+            }
+
+            if !current.is_empty() {
+                current_chars += separator_chars;
+            }
+            current_chars += entry_chars;
+            current.push(entry);
+            entry_pages.push(pages.len() + 1);
+        }
+        if !current.is_empty() {
+            pages.push(current);
+        }
+
+        let pages = pages
+            .into_iter()
+            .map(|entries| {
+                let body = entries.join(self.separator);
+                match &header {
+                    Some(header) => format!("{}{}{}", header, self.separator, body),
+                    None => body,
+                }
+            })
+            .collect();
+
+        (pages, entry_pages)
+    }
+}
+
+/// `value` cut to `max_chars` characters, ending in `…` when shortened.
+fn truncate_chars(value: &str, max_chars: usize) -> String {
+    if value.chars().count() <= max_chars {
+        return value.to_owned();
+    }
+
+    let mut out: String = value.chars().take(max_chars.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_render_heading_fields_and_text_in_order() {
+        let mut builder = PageBuilder::new(5).header("Total: **1**");
+        builder.push(PageEntry::new("#1").field("Reason", "spam").text("extra"));
+
+        assert_eq!(
+            builder.build(),
+            vec!["Total: **1**\n\n#1\n**Reason :** spam\nextra".to_owned()]
+        );
+    }
+
+    #[test]
+    fn pages_split_on_entry_count() {
+        let mut builder = PageBuilder::new(2).compact();
+        for index in 1..=5 {
+            builder.push(PageEntry::new(index.to_string()));
+        }
+
+        assert_eq!(builder.build(), vec!["1\n2", "3\n4", "5"]);
+    }
+
+    #[test]
+    fn pages_split_before_the_description_limit() {
+        let mut builder = PageBuilder::new(EMBED_MAX_FIELDS).header("header");
+        for _ in 0..3 {
+            builder.push(PageEntry::new("x".repeat(1500)));
+        }
+
+        let (pages, entry_pages) = builder.build_with_entry_pages();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(entry_pages, vec![1, 1, 2]);
+        assert!(
+            pages
+                .iter()
+                .all(|page| page.chars().count() <= EMBED_DESCRIPTION_MAX_CHARS)
+        );
+        assert!(pages.iter().all(|page| page.starts_with("header\n\n")));
+    }
+
+    #[test]
+    fn oversized_entries_are_truncated() {
+        let mut builder = PageBuilder::new(5);
+        builder.push(PageEntry::new("y".repeat(5000)));
+
+        let pages = builder.build();
+
+        assert_eq!(pages[0].chars().count(), EMBED_DESCRIPTION_MAX_CHARS);
+        assert!(pages[0].ends_with('…'));
+    }
+
+    #[test]
+    fn per_page_is_capped_at_the_field_limit() {
+        let mut builder = PageBuilder::new(100).compact();
+        for _ in 0..30 {
+            builder.push(PageEntry::new("z"));
+        }
+
+        assert_eq!(builder.build().len(), 2);
+    }
+
+    #[test]
+    fn no_entries_means_no_pages() {
+        assert!(PageBuilder::new(5).header("header").build().is_empty());
+    }
+}
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use poise::serenity_prelude as serenity;
 
-use crate::embed::DEFAULT_EMBED_COLOR;
+use crate::embed::guild_embed;
 
 pub const PAGINATION_TIMEOUT_SECS: u64 = 60 * 3;
 
@@ -22,17 +22,63 @@ pub fn page_window(total_items: usize, per_page: usize, page: usize) -> (usize,
     (start, end)
 }
 
+/// Most options a Discord select menu can hold.
+pub const MAX_PAGE_SECTIONS: usize = 25;
+
+/// A named page the select menu can jump straight to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageSection {
+    pub label: String,
+    /// 1-indexed page the section starts on.
+    pub page: usize,
+}
+
+/// One section per distinct label, pointing at the page holding its first
+/// item. `labels` gives each item's label in display order.
+pub fn first_page_sections<'a>(
+    labels: impl IntoIterator<Item = &'a str>,
+    per_page: usize,
+) -> Vec<PageSection> {
+    let per_page = per_page.max(1);
+    labeled_page_sections(
+        labels
+            .into_iter()
+            .enumerate()
+            .map(|(index, label)| (label, index / per_page + 1)),
+    )
+}
+
+/// Like [`first_page_sections`] for items that already know their page, as
+/// from [`crate::page_builder::PageBuilder::build_with_entry_pages`].
+pub fn labeled_page_sections<'a>(
+    items: impl IntoIterator<Item = (&'a str, usize)>,
+) -> Vec<PageSection> {
+    let mut sections: Vec<PageSection> = Vec::new();
+
+    for (label, page) in items {
+        if sections.iter().any(|section| section.label == label) {
+            continue;
+        }
+        sections.push(PageSection {
+            label: label.to_owned(),
+            page,
+        });
+    }
+
+    sections.truncate(MAX_PAGE_SECTIONS);
+    sections
+}
+
 fn build_page_embed(
     title: &str,
     description: &str,
     page: usize,
     total_pages: usize,
     author_icon_url: Option<&str>,
+    accent_color: Option<u32>,
     show_footer: bool,
 ) -> serenity::CreateEmbed {
-    let mut embed = serenity::CreateEmbed::new()
-        .color(DEFAULT_EMBED_COLOR)
-        .description(description.to_owned());
+    let mut embed = guild_embed(accent_color).description(description.to_owned());
 
     if show_footer {
         embed = embed.footer(serenity::CreateEmbedFooter::new(format!(
@@ -55,13 +101,15 @@ fn pagination_components(
     prev_id: &str,
     jump_id: &str,
     next_id: &str,
+    section_id: &str,
+    sections: &[PageSection],
     current_page: usize,
     total_pages: usize,
 ) -> Vec<serenity::CreateActionRow> {
     let is_first_page = current_page == 0;
     let is_last_page = current_page + 1 >= total_pages;
 
-    vec![serenity::CreateActionRow::Buttons(vec![
+    let mut rows = vec![serenity::CreateActionRow::Buttons(vec![
         serenity::CreateButton::new(prev_id)
             .label("Prev")
             .disabled(is_first_page)
@@ -73,7 +121,32 @@ fn pagination_components(
             .label("Next")
             .disabled(is_last_page)
             .style(serenity::ButtonStyle::Secondary),
-    ])]
+    ])];
+
+    if !sections.is_empty() {
+        // Highlight the last section starting at or before the current page.
+        let current_section = sections
+            .iter()
+            .rposition(|section| section.page <= current_page + 1);
+        let options = sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                serenity::CreateSelectMenuOption::new(&section.label, index.to_string())
+                    .description(format!("Page {}", section.page))
+                    .default_selection(Some(index) == current_section)
+            })
+            .collect();
+        rows.push(serenity::CreateActionRow::SelectMenu(
+            serenity::CreateSelectMenu::new(
+                section_id,
+                serenity::CreateSelectMenuKind::String { options },
+            )
+            .placeholder("Jump to section"),
+        ));
+    }
+
+    rows
 }
 
 pub async fn paginate_embed_pages<U, E>(
@@ -89,6 +162,26 @@ where
     paginate_embed_pages_with_icon(ctx, title, pages, start_page, None).await
 }
 
+/// Like [`paginate_embed_pages`], with a select menu that jumps straight to
+/// one of `sections`.
+pub async fn paginate_embed_pages_with_sections<U, E>(
+    ctx: poise::Context<'_, U, E>,
+    title: &str,
+    pages: &[String],
+    start_page: usize,
+    sections: &[PageSection],
+) -> Result<(), serenity::Error>
+where
+    U: Send + Sync,
+    E: Send + Sync,
+{
+    let options = PaginationOptions {
+        sections,
+        ..Default::default()
+    };
+    paginate_embed_pages_with_options(ctx, title, pages, start_page, options).await
+}
+
 pub async fn paginate_embed_pages_with_icon<U, E>(
     ctx: poise::Context<'_, U, E>,
     title: &str,
@@ -100,6 +193,63 @@ where
     U: Send + Sync,
     E: Send + Sync,
 {
+    let options = PaginationOptions {
+        author_icon_url,
+        ..Default::default()
+    };
+    paginate_embed_pages_with_options(ctx, title, pages, start_page, options).await
+}
+
+/// Optional extras for [`paginate_embed_pages_with_options`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PaginationOptions<'a> {
+    /// Shown next to the title.
+    pub author_icon_url: Option<&'a str>,
+    /// Named pages offered in a select menu.
+    pub sections: &'a [PageSection],
+    /// Let anyone with these permissions in the channel flip pages too, not
+    /// only the invoker.
+    pub shared_with: Option<serenity::Permissions>,
+    /// The guild's embed color, if it set one.
+    pub accent_color: Option<u32>,
+}
+
+/// Whether `user_id`, holding `permissions` in the channel, may drive a
+/// paginator started by `author_id`.
+pub fn can_flip_pages(
+    author_id: serenity::UserId,
+    user_id: serenity::UserId,
+    permissions: Option<serenity::Permissions>,
+    shared_with: Option<serenity::Permissions>,
+) -> bool {
+    if user_id == author_id {
+        return true;
+    }
+
+    match (shared_with, permissions) {
+        (Some(required), Some(granted)) => granted.administrator() || granted.contains(required),
+        _ => false,
+    }
+}
+
+pub async fn paginate_embed_pages_with_options<U, E>(
+    ctx: poise::Context<'_, U, E>,
+    title: &str,
+    pages: &[String],
+    start_page: usize,
+    options: PaginationOptions<'_>,
+) -> Result<(), serenity::Error>
+where
+    U: Send + Sync,
+    E: Send + Sync,
+{
+    let PaginationOptions {
+        author_icon_url,
+        sections,
+        shared_with,
+        accent_color,
+    } = options;
+
     if pages.is_empty() {
         return Ok(());
     }
@@ -114,6 +264,7 @@ where
             current_page + 1,
             total_pages,
             author_icon_url,
+            accent_color,
             false,
         )))
         .await?;
@@ -127,6 +278,18 @@ where
     let next_button_id = format!("{}_next", ctx_id);
     let jump_modal_id = format!("{}_jump_modal", ctx_id);
     let jump_input_id = format!("{}_jump_input", ctx_id);
+    let section_menu_id = format!("{}_section", ctx_id);
+    // Sections pointing past the last page would only ever clamp to it, and a
+    // menu with a single entry has nowhere to jump.
+    let mut sections: Vec<PageSection> = sections
+        .iter()
+        .filter(|section| (1..=total_pages).contains(&section.page))
+        .take(MAX_PAGE_SECTIONS)
+        .cloned()
+        .collect();
+    if sections.len() < 2 {
+        sections.clear();
+    }
 
     let reply = ctx
         .send(
@@ -137,12 +300,15 @@ where
                     current_page + 1,
                     total_pages,
                     author_icon_url,
+                    accent_color,
                     true,
                 ))
                 .components(pagination_components(
                     &prev_button_id,
                     &jump_button_id,
                     &next_button_id,
+                    &section_menu_id,
+                    &sections,
                     current_page,
                     total_pages,
                 )),
@@ -159,8 +325,16 @@ where
             let author_id = ctx.author().id;
             move |interaction| {
                 interaction.data.custom_id.starts_with(&prefix)
-                    && interaction.user.id == author_id
                     && interaction.message.id == message_id
+                    && can_flip_pages(
+                        author_id,
+                        interaction.user.id,
+                        interaction
+                            .member
+                            .as_ref()
+                            .and_then(|member| member.permissions),
+                        shared_with,
+                    )
             }
         })
         .timeout(Duration::from_secs(PAGINATION_TIMEOUT_SECS))
@@ -182,12 +356,15 @@ where
                                 current_page + 1,
                                 total_pages,
                                 author_icon_url,
+                                accent_color,
                                 true,
                             ))
                             .components(pagination_components(
                                 &prev_button_id,
                                 &jump_button_id,
                                 &next_button_id,
+                                &section_menu_id,
+                                &sections,
                                 current_page,
                                 total_pages,
                             )),
@@ -211,12 +388,55 @@ where
                                 current_page + 1,
                                 total_pages,
                                 author_icon_url,
+                                accent_color,
                                 true,
                             ))
                             .components(pagination_components(
                                 &prev_button_id,
                                 &jump_button_id,
                                 &next_button_id,
+                                &section_menu_id,
+                                &sections,
+                                current_page,
+                                total_pages,
+                            )),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        if press.data.custom_id == section_menu_id {
+            if let serenity::ComponentInteractionDataKind::StringSelect { values } =
+                &press.data.kind
+                && let Some(section) = values
+                    .first()
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .and_then(|index| sections.get(index))
+            {
+                current_page = section.page - 1;
+            }
+
+            press
+                .create_response(
+                    ctx.http(),
+                    serenity::CreateInteractionResponse::UpdateMessage(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .embed(build_page_embed(
+                                title,
+                                &pages[current_page],
+                                current_page + 1,
+                                total_pages,
+                                author_icon_url,
+                                accent_color,
+                                true,
+                            ))
+                            .components(pagination_components(
+                                &prev_button_id,
+                                &jump_button_id,
+                                &next_button_id,
+                                &section_menu_id,
+                                &sections,
                                 current_page,
                                 total_pages,
                             )),
@@ -250,7 +470,7 @@ where
             .await?;
 
         let maybe_modal = serenity::collector::ModalInteractionCollector::new(ctx)
-            .author_id(ctx.author().id)
+            .author_id(press.user.id)
             .channel_id(ctx.channel_id())
             .custom_ids(vec![jump_modal_id.clone()])
             .timeout(Duration::from_secs(PAGINATION_TIMEOUT_SECS))
@@ -293,12 +513,15 @@ where
                                 current_page + 1,
                                 total_pages,
                                 author_icon_url,
+                                accent_color,
                                 true,
                             ))
                             .components(pagination_components(
                                 &prev_button_id,
                                 &jump_button_id,
                                 &next_button_id,
+                                &section_menu_id,
+                                &sections,
                                 current_page,
                                 total_pages,
                             )),
@@ -318,6 +541,7 @@ where
                 current_page + 1,
                 total_pages,
                 author_icon_url,
+                accent_color,
                 true,
             )),
         )
@@ -325,3 +549,250 @@ where
 
     Ok(())
 }
+
+/// Longest component custom id Discord accepts.
+pub const MAX_CUSTOM_ID_CHARS: usize = 100;
+
+const STATELESS_PREV: &str = "prev";
+const STATELESS_NEXT: &str = "next";
+const STATELESS_MENU: &str = "menu";
+
+/// Custom id for a stateless paginator control. `kind` routes presses to a
+/// handler and `state` is that handler's encoded query, so the page can be
+/// rebuilt from the id alone after the collector or the bot is gone.
+fn stateless_page_id(kind: &str, control: &str, page: usize, state: &str) -> String {
+    format!("{}:{}:{}:{}", kind, control, page, state)
+}
+
+/// Whether a stateless paginator's ids for `kind` and `state` fit in a custom id.
+pub fn fits_stateless_page_id(kind: &str, state: &str) -> bool {
+    stateless_page_id(kind, STATELESS_MENU, usize::MAX, state).len() <= MAX_CUSTOM_ID_CHARS
+}
+
+/// A press on a stateless paginator, decoded from its custom id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatelessPagePress<'a> {
+    pub kind: &'a str,
+    pub state: &'a str,
+    /// 1-indexed page to show next.
+    pub page: usize,
+}
+
+/// Decode a stateless paginator press. `selected` is the chosen option for
+/// the section menu and ignored for buttons. Ids from other components
+/// decode to `None`.
+pub fn parse_stateless_page_press<'a>(
+    custom_id: &'a str,
+    selected: Option<&str>,
+) -> Option<StatelessPagePress<'a>> {
+    let mut parts = custom_id.splitn(4, ':');
+    let kind = parts.next()?;
+    let control = parts.next()?;
+    let page = parts.next()?.parse::<usize>().ok()?;
+    let state = parts.next()?;
+
+    let page = match control {
+        STATELESS_PREV | STATELESS_NEXT => page,
+        STATELESS_MENU => selected?.parse::<usize>().ok()?,
+        _ => return None,
+    };
+
+    Some(StatelessPagePress {
+        kind,
+        state,
+        page: page.max(1),
+    })
+}
+
+fn stateless_pagination_components(
+    kind: &str,
+    state: &str,
+    sections: &[PageSection],
+    current_page: usize,
+    total_pages: usize,
+) -> Vec<serenity::CreateActionRow> {
+    let mut rows = vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(stateless_page_id(
+            kind,
+            STATELESS_PREV,
+            current_page.saturating_sub(1),
+            state,
+        ))
+        .label("Prev")
+        .disabled(current_page <= 1)
+        .style(serenity::ButtonStyle::Secondary),
+        serenity::CreateButton::new(stateless_page_id(
+            kind,
+            STATELESS_NEXT,
+            current_page + 1,
+            state,
+        ))
+        .label("Next")
+        .disabled(current_page >= total_pages)
+        .style(serenity::ButtonStyle::Secondary),
+    ])];
+
+    if sections.len() >= 2 {
+        let current_section = sections
+            .iter()
+            .rposition(|section| section.page <= current_page);
+        let options = sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                serenity::CreateSelectMenuOption::new(&section.label, section.page.to_string())
+                    .description(format!("Page {}", section.page))
+                    .default_selection(Some(index) == current_section)
+            })
+            .collect();
+        rows.push(serenity::CreateActionRow::SelectMenu(
+            serenity::CreateSelectMenu::new(
+                stateless_page_id(kind, STATELESS_MENU, 0, state),
+                serenity::CreateSelectMenuKind::String { options },
+            )
+            .placeholder("Jump to section"),
+        ));
+    }
+
+    rows
+}
+
+/// The embed and controls for `page` (1-indexed, clamped) of a stateless
+/// paginator. Send it once, then answer each press decoded by
+/// [`parse_stateless_page_press`] with the same call for the new page.
+pub fn stateless_page_message(
+    title: &str,
+    pages: &[String],
+    page: usize,
+    kind: &str,
+    state: &str,
+    sections: &[PageSection],
+    accent_color: Option<u32>,
+) -> (serenity::CreateEmbed, Vec<serenity::CreateActionRow>) {
+    let total_pages = pages.len().max(1);
+    let page = page.clamp(1, total_pages);
+    let description = pages.get(page - 1).map(String::as_str).unwrap_or_default();
+    let sections: Vec<PageSection> = sections
+        .iter()
+        .filter(|section| (1..=total_pages).contains(&section.page))
+        .take(MAX_PAGE_SECTIONS)
+        .cloned()
+        .collect();
+
+    let embed = build_page_embed(
+        title,
+        description,
+        page,
+        total_pages,
+        None,
+        accent_color,
+        total_pages > 1,
+    );
+    let components = if total_pages > 1 {
+        stateless_pagination_components(kind, state, &sections, page, total_pages)
+    } else {
+        Vec::new()
+    };
+
+    (embed, components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_point_at_the_first_page_of_each_label() {
+        let labels = [
+            "moderation",
+            "moderation",
+            "moderation",
+            "utility",
+            "moderation",
+        ];
+
+        assert_eq!(
+            first_page_sections(labels, 2),
+            vec![
+                PageSection {
+                    label: "moderation".to_owned(),
+                    page: 1,
+                },
+                PageSection {
+                    label: "utility".to_owned(),
+                    page: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_invoker_flips_unshared_pages() {
+        let author = serenity::UserId::new(1);
+        let other = serenity::UserId::new(2);
+        let moderator = Some(serenity::Permissions::MANAGE_MESSAGES);
+
+        assert!(can_flip_pages(author, author, None, None));
+        assert!(!can_flip_pages(author, other, moderator, None));
+    }
+
+    #[test]
+    fn shared_pages_need_the_permission() {
+        let author = serenity::UserId::new(1);
+        let other = serenity::UserId::new(2);
+        let shared = Some(serenity::Permissions::MANAGE_MESSAGES);
+
+        assert!(can_flip_pages(
+            author,
+            other,
+            Some(serenity::Permissions::MANAGE_MESSAGES | serenity::Permissions::SEND_MESSAGES),
+            shared
+        ));
+        assert!(can_flip_pages(
+            author,
+            other,
+            Some(serenity::Permissions::ADMINISTRATOR),
+            shared
+        ));
+        assert!(!can_flip_pages(
+            author,
+            other,
+            Some(serenity::Permissions::SEND_MESSAGES),
+            shared
+        ));
+        assert!(!can_flip_pages(author, other, None, shared));
+    }
+
+    #[test]
+    fn stateless_presses_round_trip_through_custom_ids() {
+        let next = stateless_page_id("modlogs", STATELESS_NEXT, 3, "1:-:ban");
+        let menu = stateless_page_id("modlogs", STATELESS_MENU, 0, "1:-:ban");
+
+        assert_eq!(
+            parse_stateless_page_press(&next, None),
+            Some(StatelessPagePress {
+                kind: "modlogs",
+                state: "1:-:ban",
+                page: 3,
+            })
+        );
+        assert_eq!(
+            parse_stateless_page_press(&menu, Some("5")).map(|press| press.page),
+            Some(5)
+        );
+        assert_eq!(parse_stateless_page_press(&menu, None), None);
+        assert_eq!(parse_stateless_page_press("123_next", None), None);
+        assert!(fits_stateless_page_id("modlogs", "1:-:ban"));
+        assert!(!fits_stateless_page_id("modlogs", &"x".repeat(90)));
+    }
+
+    #[test]
+    fn sections_are_capped_at_the_select_menu_limit() {
+        let labels: Vec<String> = (0..40).map(|index| index.to_string()).collect();
+
+        let sections = first_page_sections(labels.iter().map(String::as_str), 10);
+
+        assert_eq!(sections.len(), MAX_PAGE_SECTIONS);
+        assert_eq!(sections[24].page, 3);
+    }
+}
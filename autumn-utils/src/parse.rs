@@ -1,66 +1,180 @@
-/// Parse a compact duration token like `30s`, `10m`, `2h`, `1d`, or plain seconds.
+use crate::time::now_unix_secs;
+
+const MINUTE_SECS: u64 = 60;
+const HOUR_SECS: u64 = 60 * MINUTE_SECS;
+const DAY_SECS: u64 = 24 * HOUR_SECS;
+const WEEK_SECS: u64 = 7 * DAY_SECS;
+
+/// Most words a leading duration may span, e.g. `until friday 9am`.
+const MAX_DURATION_WORDS: usize = 6;
+
+/// Parse a duration: compact (`30s`, `10m`, `1h30m`), spelled out (`2 hours`,
+/// `1 week 2 days`), plain seconds, or `until <day> <time>` counted from now.
 pub fn parse_duration_seconds(raw: &str) -> Option<u64> {
+    parse_duration_seconds_at(raw, now_unix_secs())
+}
+
+/// [`parse_duration_seconds`] with `until` measured from `now`. Days and
+/// clock times after `until` are read in UTC, like `--at`: `today`,
+/// `tomorrow` or a weekday, and/or `9am`, `9:30pm`, `21:00`, `noon` or
+/// `midnight`. A day alone means its start; a time alone its next occurrence.
+pub fn parse_duration_seconds_at(raw: &str, now: u64) -> Option<u64> {
     let value = raw.trim();
-    if value.is_empty() {
-        return None;
+    let mut words = value.split_whitespace();
+    if words.next()?.eq_ignore_ascii_case("until") {
+        let target = parse_until(words, now)?;
+        return target.checked_sub(now).filter(|seconds| *seconds > 0);
     }
 
-    let compact: String = value.chars().filter(|ch| !ch.is_whitespace()).collect();
-    if compact.is_empty() {
-        return None;
-    }
+    parse_unit_duration(value)
+}
 
-    let bytes = compact.as_bytes();
-    let mut cursor = 0;
+/// Sum `<number><unit>` segments, where the unit may follow after a space. A
+/// bare number is seconds, but only on its own.
+fn parse_unit_duration(value: &str) -> Option<u64> {
+    let lower = value.to_ascii_lowercase();
+    let mut chars = lower.chars().peekable();
     let mut total_seconds = 0_u64;
-    let mut saw_unit_segment = false;
 
-    while cursor < bytes.len() {
-        let number_start = cursor;
-        while cursor < bytes.len() && bytes[cursor].is_ascii_digit() {
-            cursor += 1;
+    loop {
+        while chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
         }
 
-        if number_start == cursor {
-            return None;
+        let mut digits = String::new();
+        while let Some(ch) = chars.next_if(char::is_ascii_digit) {
+            digits.push(ch);
         }
+        let number = digits.parse::<u64>().ok().filter(|number| *number > 0)?;
 
-        let number = compact[number_start..cursor].parse::<u64>().ok()?;
-        if number == 0 {
-            return None;
+        while chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+        let mut unit = String::new();
+        while let Some(ch) = chars.next_if(char::is_ascii_alphabetic) {
+            unit.push(ch);
         }
 
-        let saw_unit = cursor < bytes.len();
-        let multiplier = if saw_unit {
-            let unit = bytes[cursor] as char;
-            cursor += 1;
-
-            match unit {
-                's' | 'S' => 1_u64,
-                'm' | 'M' => 60_u64,
-                'h' | 'H' => 60_u64 * 60,
-                'd' | 'D' => 60_u64 * 60 * 24,
-                _ => return None,
+        let multiplier = if unit.is_empty() {
+            if total_seconds > 0 || chars.peek().is_some() {
+                return None;
             }
+            1
         } else {
-            1_u64
+            unit_seconds(&unit)?
         };
 
-        if !saw_unit && saw_unit_segment {
+        total_seconds = total_seconds.checked_add(number.checked_mul(multiplier)?)?;
+    }
+
+    (total_seconds > 0).then_some(total_seconds)
+}
+
+fn unit_seconds(unit: &str) -> Option<u64> {
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(MINUTE_SECS),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(HOUR_SECS),
+        "d" | "day" | "days" => Some(DAY_SECS),
+        "w" | "wk" | "wks" | "week" | "weeks" => Some(WEEK_SECS),
+        _ => None,
+    }
+}
+
+/// The unix second `until <words>` points at, if it is after `now`.
+fn parse_until<'a>(words: impl Iterator<Item = &'a str>, now: u64) -> Option<u64> {
+    let mut day_start = None;
+    let mut time_of_day = None;
+
+    for word in words {
+        let word = word.to_ascii_lowercase();
+        if day_start.is_none()
+            && let Some(start) = parse_day(&word, now)
+        {
+            day_start = Some(start);
+        } else if time_of_day.is_none()
+            && let Some(seconds) = parse_clock_time(&word)
+        {
+            time_of_day = Some(seconds);
+        } else {
             return None;
         }
+    }
+
+    let today = now - now % DAY_SECS;
+    let target = match (day_start, time_of_day) {
+        (Some(start), Some(seconds)) => start + seconds,
+        (Some(start), None) => start,
+        (None, Some(seconds)) if today + seconds > now => today + seconds,
+        (None, Some(seconds)) => today + DAY_SECS + seconds,
+        (None, None) => return None,
+    };
+
+    (target > now).then_some(target)
+}
+
+/// Start of `today`, `tomorrow`, or the next given weekday (a week out when
+/// it is today).
+fn parse_day(word: &str, now: u64) -> Option<u64> {
+    let today = now - now % DAY_SECS;
+    let weekday = match word {
+        "today" => return Some(today),
+        "tomorrow" => return Some(today + DAY_SECS),
+        "mon" | "monday" => 0,
+        "tue" | "tues" | "tuesday" => 1,
+        "wed" | "wednesday" => 2,
+        "thu" | "thur" | "thurs" | "thursday" => 3,
+        "fri" | "friday" => 4,
+        "sat" | "saturday" => 5,
+        "sun" | "sunday" => 6,
+        _ => return None,
+    };
 
-        saw_unit_segment = saw_unit_segment || saw_unit;
+    // The unix epoch fell on a Thursday.
+    let current = (now / DAY_SECS + 3) % 7;
+    let days_ahead = match (weekday + 7 - current) % 7 {
+        0 => 7,
+        days => days,
+    };
+    Some(today + days_ahead * DAY_SECS)
+}
 
-        let part_seconds = number.checked_mul(multiplier)?;
-        total_seconds = total_seconds.checked_add(part_seconds)?;
+/// Seconds after midnight for `9am`, `9:30pm`, `21:00`, `noon` or `midnight`.
+fn parse_clock_time(word: &str) -> Option<u64> {
+    match word {
+        "noon" => return Some(12 * HOUR_SECS),
+        "midnight" => return Some(0),
+        _ => {}
     }
 
-    if total_seconds == 0 {
-        None
-    } else {
-        Some(total_seconds)
+    let (clock, pm) = match word.strip_suffix("am") {
+        Some(clock) => (clock, Some(false)),
+        None => match word.strip_suffix("pm") {
+            Some(clock) => (clock, Some(true)),
+            None => (word, None),
+        },
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => {
+            (hour.parse::<u64>().ok()?, minute.parse().ok()?)
+        }
+        Some(_) => return None,
+        // A bare hour needs am/pm to read as a time.
+        None if pm.is_some() => (clock.parse::<u64>().ok()?, 0),
+        None => return None,
+    };
+    if minute > 59 {
+        return None;
     }
+
+    let hour = match pm {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None if hour <= 23 => hour,
+        None => return None,
+    };
+
+    Some(hour * HOUR_SECS + minute * MINUTE_SECS)
 }
 
 pub fn has_duration_unit(raw: &str) -> bool {
@@ -69,5 +183,257 @@ pub fn has_duration_unit(raw: &str) -> bool {
         return false;
     };
 
-    matches!(last, 's' | 'S' | 'm' | 'M' | 'h' | 'H' | 'd' | 'D')
+    matches!(
+        last,
+        's' | 'S' | 'm' | 'M' | 'h' | 'H' | 'd' | 'D' | 'w' | 'W'
+    )
+}
+
+/// Split the longest leading duration with units (`7d`, `1d 12h`, `2 hours`,
+/// `until tomorrow 9am`) off `raw`. Returns the total seconds, if any, and
+/// the remaining text.
+pub fn split_leading_duration(raw: &str) -> (Option<u64>, Option<String>) {
+    let raw = raw.trim();
+    let now = now_unix_secs();
+    match split_duration_prefix_at(raw, now) {
+        Some((duration, rest)) => (
+            parse_duration_seconds_at(duration, now),
+            (!rest.is_empty()).then(|| rest.to_owned()),
+        ),
+        None => (None, (!raw.is_empty()).then(|| raw.to_owned())),
+    }
+}
+
+/// The longest run of leading words in `raw` that reads as a duration, and
+/// the trimmed text after it. A trailing bare number doesn't count, so `5
+/// spam` isn't five seconds.
+pub fn split_duration_prefix_at(raw: &str, now: u64) -> Option<(&str, &str)> {
+    duration_prefix_at(raw, now, true)
+}
+
+/// Like [`split_duration_prefix_at`], but the shortest run, so another
+/// duration can follow: `2h 1d spam` splits after `2h` and `2 hours 1d`
+/// after `2 hours`.
+pub fn split_first_duration_at(raw: &str, now: u64) -> Option<(&str, &str)> {
+    duration_prefix_at(raw, now, false)
+}
+
+fn duration_prefix_at(raw: &str, now: u64, longest: bool) -> Option<(&str, &str)> {
+    let raw = raw.trim_start();
+    let mut word_ends = Vec::new();
+    let mut offset = 0;
+    for word in raw.split_whitespace().take(MAX_DURATION_WORDS) {
+        offset += raw[offset..].find(word)? + word.len();
+        word_ends.push(offset);
+    }
+
+    let split = |&end: &usize| {
+        let candidate = &raw[..end];
+        let last_word = candidate.split_whitespace().last()?;
+        if last_word.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        parse_duration_seconds_at(candidate, now)?;
+        Some((candidate, raw[end..].trim()))
+    };
+
+    if longest {
+        word_ends.iter().rev().find_map(split)
+    } else {
+        word_ends.iter().find_map(split)
+    }
+}
+
+/// Parse a user mention (`<@123>`, `<@!123>`) or a raw user ID.
+pub fn parse_user_mention(raw: &str) -> Option<u64> {
+    let value = raw.trim();
+    let id = value
+        .strip_prefix("<@")
+        .and_then(|rest| rest.strip_suffix('>'))
+        .map(|rest| rest.trim_start_matches('!'))
+        .unwrap_or(value);
+
+    // Snowflakes are at least 17 digits; shorter numbers are keywords.
+    if id.len() < 17 || !id.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    id.parse::<u64>().ok().filter(|id| *id != 0)
+}
+
+/// Parse a message link like `https://discord.com/channels/<guild>/<channel>/<message>`
+/// into `(guild_id, channel_id, message_id)`. Also accepts the `ptb`/`canary`
+/// and legacy `discordapp.com` hosts.
+pub fn parse_message_link(raw: &str) -> Option<(u64, u64, u64)> {
+    let value = raw.trim().trim_start_matches('<').trim_end_matches('>');
+    let path = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))?;
+    let (host, path) = path.split_once('/')?;
+
+    let host = host
+        .strip_prefix("ptb.")
+        .or_else(|| host.strip_prefix("canary."))
+        .unwrap_or(host);
+    if host != "discord.com" && host != "discordapp.com" {
+        return None;
+    }
+
+    let mut segments = path.strip_prefix("channels/")?.split('/');
+    let guild_id = segments.next()?.parse::<u64>().ok()?;
+    let channel_id = segments.next()?.parse::<u64>().ok()?;
+    let message_id = segments.next()?.parse::<u64>().ok()?;
+    if segments.next().is_some_and(|segment| !segment.is_empty()) {
+        return None;
+    }
+
+    Some((guild_id, channel_id, message_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_duration_seconds_at, split_duration_prefix_at, split_first_duration_at,
+        split_leading_duration,
+    };
+
+    // Thursday 1970-01-08, 12:00 UTC.
+    const NOW: u64 = 7 * 86_400 + 12 * 3600;
+
+    fn parse(raw: &str) -> Option<u64> {
+        parse_duration_seconds_at(raw, NOW)
+    }
+
+    #[test]
+    fn parses_compact_durations() {
+        assert_eq!(parse("30s"), Some(30));
+        assert_eq!(parse("10m"), Some(600));
+        assert_eq!(parse("2H"), Some(7200));
+        assert_eq!(parse("1d"), Some(86_400));
+        assert_eq!(parse("2w"), Some(1_209_600));
+        assert_eq!(parse("1h30m"), Some(5400));
+        assert_eq!(parse("1d12h"), Some(129_600));
+        assert_eq!(parse(" 1d 12h "), Some(129_600));
+        assert_eq!(parse("10 m"), Some(600));
+        assert_eq!(parse("45"), Some(45));
+    }
+
+    #[test]
+    fn parses_spelled_out_durations() {
+        assert_eq!(parse("2 hours"), Some(7200));
+        assert_eq!(parse("1 hour"), Some(3600));
+        assert_eq!(parse("1 week"), Some(604_800));
+        assert_eq!(parse("1 week 2 days"), Some(777_600));
+        assert_eq!(parse("90 seconds"), Some(90));
+        assert_eq!(parse("5 mins"), Some(300));
+        assert_eq!(parse("3 Days"), Some(259_200));
+        assert_eq!(parse("1 hr 15 min"), Some(4500));
+        assert_eq!(parse("2 wks"), Some(1_209_600));
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("   "), None);
+        assert_eq!(parse("hours"), None);
+        assert_eq!(parse("0m"), None);
+        assert_eq!(parse("1h0m"), None);
+        assert_eq!(parse("1h30"), None);
+        assert_eq!(parse("30 10m"), None);
+        assert_eq!(parse("1 fortnight"), None);
+        assert_eq!(parse("1st"), None);
+        assert_eq!(parse("-5m"), None);
+        assert_eq!(parse("1.5h"), None);
+        assert_eq!(parse("99999999999999999999s"), None);
+        assert_eq!(parse("999999999999999999w"), None);
+    }
+
+    #[test]
+    fn parses_until_clock_times() {
+        assert_eq!(parse("until 9pm"), Some(9 * 3600));
+        assert_eq!(parse("until 21:30"), Some(9 * 3600 + 1800));
+        assert_eq!(parse("until 9:15am"), Some(21 * 3600 + 900));
+        assert_eq!(parse("until 9am"), Some(21 * 3600));
+        assert_eq!(parse("until noon"), Some(86_400));
+        assert_eq!(parse("until midnight"), Some(12 * 3600));
+        assert_eq!(parse("until 12am"), Some(12 * 3600));
+        assert_eq!(parse("until 12pm"), Some(86_400));
+        assert_eq!(parse("UNTIL 1PM"), Some(3600));
+    }
+
+    #[test]
+    fn parses_until_days() {
+        assert_eq!(parse("until tomorrow"), Some(12 * 3600));
+        assert_eq!(parse("until tomorrow 9am"), Some(21 * 3600));
+        assert_eq!(parse("until 9am tomorrow"), Some(21 * 3600));
+        assert_eq!(parse("until today 6pm"), Some(6 * 3600));
+        assert_eq!(parse("until friday"), Some(12 * 3600));
+        assert_eq!(parse("until sat noon"), Some(2 * 86_400));
+        // A weekday that is today means next week's.
+        assert_eq!(parse("until thursday"), Some(6 * 86_400 + 12 * 3600));
+        assert_eq!(parse("until monday 8:00"), Some(3 * 86_400 + 20 * 3600));
+    }
+
+    #[test]
+    fn rejects_invalid_until_forms() {
+        assert_eq!(parse("until"), None);
+        assert_eq!(parse("until today"), None);
+        assert_eq!(parse("until today 9am"), None);
+        assert_eq!(parse("until 9"), None);
+        assert_eq!(parse("until 13pm"), None);
+        assert_eq!(parse("until 24:00"), None);
+        assert_eq!(parse("until 9:5pm"), None);
+        assert_eq!(parse("until tomorrow tomorrow"), None);
+        assert_eq!(parse("until 9am 10am"), None);
+        assert_eq!(parse("until later"), None);
+        assert_eq!(parse("until 2h"), None);
+    }
+
+    #[test]
+    fn splits_multi_word_duration_prefixes() {
+        assert_eq!(
+            split_duration_prefix_at("2 hours spamming", NOW),
+            Some(("2 hours", "spamming"))
+        );
+        assert_eq!(
+            split_duration_prefix_at("until tomorrow 9am  raiding", NOW),
+            Some(("until tomorrow 9am", "raiding"))
+        );
+        assert_eq!(
+            split_duration_prefix_at("1h 30m", NOW),
+            Some(("1h 30m", ""))
+        );
+        assert_eq!(split_duration_prefix_at("5 spam", NOW), None);
+        assert_eq!(split_duration_prefix_at("30", NOW), None);
+        assert_eq!(split_duration_prefix_at("until further notice", NOW), None);
+    }
+
+    #[test]
+    fn splits_first_duration_only() {
+        assert_eq!(
+            split_first_duration_at("2 hours 1d spam", NOW),
+            Some(("2 hours", "1d spam"))
+        );
+        assert_eq!(split_first_duration_at("2h 1d", NOW), Some(("2h", "1d")));
+        assert_eq!(split_first_duration_at("1 week", NOW), Some(("1 week", "")));
+        assert_eq!(split_first_duration_at("5 spam", NOW), None);
+    }
+
+    #[test]
+    fn splits_leading_duration_tokens() {
+        assert_eq!(
+            split_leading_duration("1d 12h spamming  invites"),
+            (Some(129_600), Some("spamming  invites".to_owned()))
+        );
+        assert_eq!(
+            split_leading_duration("1st offense"),
+            (None, Some("1st offense".to_owned()))
+        );
+        assert_eq!(split_leading_duration(" 7d "), (Some(604_800), None));
+        assert_eq!(
+            split_leading_duration("1 week raiding"),
+            (Some(604_800), Some("raiding".to_owned()))
+        );
+        assert_eq!(split_leading_duration(""), (None, None));
+    }
 }
@@ -55,3 +55,228 @@ pub async fn has_user_permission(
 
     Ok(perms.contains(serenity::Permissions::ADMINISTRATOR) || perms.contains(required))
 }
+
+/// Whether `user_id` sits above `role_id` in the role hierarchy, so letting
+/// them hand the role out through the bot doesn't escalate their privileges.
+/// The server owner can manage every role.
+pub async fn can_manage_role(
+    http: &serenity::Http,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+    role_id: serenity::RoleId,
+) -> anyhow::Result<bool> {
+    let guild = guild_id.to_partial_guild(http).await?;
+    if guild.owner_id == user_id {
+        return Ok(true);
+    }
+
+    let member = guild_id.member(http, user_id).await?;
+    let Some(target) = guild.roles.get(&role_id) else {
+        return Ok(false);
+    };
+
+    let highest = member
+        .roles
+        .iter()
+        .filter_map(|id| guild.roles.get(id))
+        .map(|role| role.position)
+        .max()
+        .unwrap_or(0);
+
+    Ok(highest > target.position)
+}
+
+/// Where a moderation target stands against whoever is acting on them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoleHierarchy {
+    /// The actor's highest role is above the target's, the actor owns the
+    /// server, or the target isn't in the server.
+    Allowed,
+    /// The target owns the server, which no role outranks.
+    TargetIsOwner,
+    /// The target's highest role is level with or above the actor's.
+    TargetAbove,
+}
+
+impl RoleHierarchy {
+    /// A reply explaining why the bot can't `action` the target, if it can't.
+    pub fn bot_blocked_message(self, action: &str) -> Option<String> {
+        match self {
+            Self::Allowed => None,
+            Self::TargetIsOwner => Some(format!("I can't {action} the server owner.")),
+            Self::TargetAbove => Some(format!(
+                "That user's highest role is above mine, so I can't {action} them. \
+                 Move my role above theirs first."
+            )),
+        }
+    }
+
+    /// A reply explaining why a moderator may not `action` the target, if
+    /// they may not.
+    pub fn moderator_blocked_message(self, action: &str) -> Option<String> {
+        match self {
+            Self::Allowed => None,
+            Self::TargetIsOwner => Some(format!("You can't {action} the server owner.")),
+            Self::TargetAbove => Some(format!(
+                "You can't {action} someone whose highest role is equal to or above yours."
+            )),
+        }
+    }
+}
+
+/// Compare `actor_id`'s highest role with `target_id`'s before acting on
+/// them, so commands can explain a hierarchy refusal instead of surfacing
+/// Discord's generic missing-permissions error.
+pub async fn compare_role_hierarchy(
+    http: &serenity::Http,
+    guild_id: serenity::GuildId,
+    actor_id: serenity::UserId,
+    target_id: serenity::UserId,
+) -> anyhow::Result<RoleHierarchy> {
+    let guild = guild_id.to_partial_guild(http).await?;
+    if guild.owner_id == actor_id {
+        return Ok(RoleHierarchy::Allowed);
+    }
+    if guild.owner_id == target_id {
+        return Ok(RoleHierarchy::TargetIsOwner);
+    }
+
+    let target = match guild_id.member(http, target_id).await {
+        Ok(member) => member,
+        // Bans also work on users who already left.
+        Err(serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response)))
+            if response.status_code.as_u16() == 404 =>
+        {
+            return Ok(RoleHierarchy::Allowed);
+        }
+        Err(source) => return Err(source.into()),
+    };
+    let actor = guild_id.member(http, actor_id).await?;
+
+    let highest_position = |member: &serenity::Member| {
+        member
+            .roles
+            .iter()
+            .filter_map(|id| guild.roles.get(id))
+            .map(|role| role.position)
+            .max()
+            .unwrap_or(0)
+    };
+
+    Ok(compare_role_positions(
+        highest_position(&actor),
+        highest_position(&target),
+    ))
+}
+
+/// A bot feature and the Discord permissions it fails without.
+pub struct BotFeature {
+    pub name: &'static str,
+    pub required: serenity::Permissions,
+}
+
+/// Server-wide permissions behind each moderation feature, checked by
+/// `!permcheck`.
+pub const BOT_FEATURES: &[BotFeature] = &[
+    BotFeature {
+        name: "Bans (`!ban`, `!unban`, `!terminate`)",
+        required: serenity::Permissions::BAN_MEMBERS,
+    },
+    BotFeature {
+        name: "Kicks (`!kick`)",
+        required: serenity::Permissions::KICK_MEMBERS,
+    },
+    BotFeature {
+        name: "Timeouts (`!timeout`, escalation, automod)",
+        required: serenity::Permissions::MODERATE_MEMBERS,
+    },
+    BotFeature {
+        name: "Message deletion (`!purge`, filters, antispam)",
+        required: serenity::Permissions::MANAGE_MESSAGES
+            .union(serenity::Permissions::READ_MESSAGE_HISTORY),
+    },
+    BotFeature {
+        name: "Channel creation and locking (`!setup`, `!archive`)",
+        required: serenity::Permissions::MANAGE_CHANNELS,
+    },
+    BotFeature {
+        name: "Role changes (`!temprole`)",
+        required: serenity::Permissions::MANAGE_ROLES,
+    },
+    BotFeature {
+        name: "Logging actions taken outside the bot",
+        required: serenity::Permissions::VIEW_AUDIT_LOG,
+    },
+];
+
+/// What `granted` lacks of `required`. `ADMINISTRATOR` covers everything.
+pub fn missing_permissions(
+    granted: serenity::Permissions,
+    required: serenity::Permissions,
+) -> serenity::Permissions {
+    if granted.contains(serenity::Permissions::ADMINISTRATOR) {
+        return serenity::Permissions::empty();
+    }
+
+    required.difference(granted)
+}
+
+/// Discord only lets a member act on members whose highest role is strictly
+/// lower than theirs.
+fn compare_role_positions(actor_highest: u16, target_highest: u16) -> RoleHierarchy {
+    if actor_highest > target_highest {
+        RoleHierarchy::Allowed
+    } else {
+        RoleHierarchy::TargetAbove
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actor_needs_a_strictly_higher_role() {
+        assert_eq!(compare_role_positions(5, 3), RoleHierarchy::Allowed);
+        assert_eq!(compare_role_positions(5, 5), RoleHierarchy::TargetAbove);
+        assert_eq!(compare_role_positions(2, 7), RoleHierarchy::TargetAbove);
+        assert_eq!(compare_role_positions(1, 0), RoleHierarchy::Allowed);
+    }
+
+    #[test]
+    fn missing_permissions_are_the_difference_unless_admin() {
+        let required = serenity::Permissions::BAN_MEMBERS | serenity::Permissions::KICK_MEMBERS;
+
+        assert_eq!(
+            missing_permissions(serenity::Permissions::BAN_MEMBERS, required),
+            serenity::Permissions::KICK_MEMBERS
+        );
+        assert!(missing_permissions(required, required).is_empty());
+        assert!(missing_permissions(serenity::Permissions::ADMINISTRATOR, required).is_empty());
+    }
+
+    #[test]
+    fn only_blocked_outcomes_have_a_message() {
+        assert_eq!(RoleHierarchy::Allowed.bot_blocked_message("ban"), None);
+        assert_eq!(
+            RoleHierarchy::Allowed.moderator_blocked_message("warn"),
+            None
+        );
+        assert_eq!(
+            RoleHierarchy::TargetIsOwner
+                .bot_blocked_message("kick")
+                .as_deref(),
+            Some("I can't kick the server owner.")
+        );
+        assert!(
+            RoleHierarchy::TargetAbove
+                .bot_blocked_message("timeout")
+                .is_some_and(|message| message.contains("can't timeout them"))
+        );
+        assert!(
+            RoleHierarchy::TargetAbove
+                .moderator_blocked_message("warn")
+                .is_some_and(|message| message.starts_with("You can't warn"))
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Polls voted on with numbered reactions: `question | option | option`.
+
+use serde_json::{Value, json};
+
+/// Most options a poll can offer, one per number emoji.
+pub const MAX_POLL_OPTIONS: usize = 10;
+pub const MIN_POLL_OPTIONS: usize = 2;
+/// Discord's limit on embed titles.
+pub const POLL_QUESTION_MAX_CHARS: usize = 256;
+pub const POLL_OPTION_MAX_CHARS: usize = 100;
+
+/// The reaction members vote with for each option, in order.
+pub const POLL_OPTION_EMOJIS: [&str; MAX_POLL_OPTIONS] = [
+    "1\u{fe0f}\u{20e3}",
+    "2\u{fe0f}\u{20e3}",
+    "3\u{fe0f}\u{20e3}",
+    "4\u{fe0f}\u{20e3}",
+    "5\u{fe0f}\u{20e3}",
+    "6\u{fe0f}\u{20e3}",
+    "7\u{fe0f}\u{20e3}",
+    "8\u{fe0f}\u{20e3}",
+    "9\u{fe0f}\u{20e3}",
+    "\u{1f51f}",
+];
+
+/// Width of the result bars, in blocks.
+const RESULT_BAR_WIDTH: u64 = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poll {
+    pub question: String,
+    pub options: Vec<String>,
+}
+
+impl Poll {
+    /// Parse `question | option | option ...`, with 2 to 10 options.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut parts = raw
+            .split('|')
+            .map(str::trim)
+            .filter(|part| !part.is_empty());
+
+        let question = parts
+            .next()
+            .ok_or_else(|| "A poll needs a question.".to_owned())?;
+        let options: Vec<String> = parts.map(str::to_owned).collect();
+
+        if !(MIN_POLL_OPTIONS..=MAX_POLL_OPTIONS).contains(&options.len()) {
+            return Err(format!(
+                "A poll needs {} to {} options, separated by `|`.",
+                MIN_POLL_OPTIONS, MAX_POLL_OPTIONS
+            ));
+        }
+        if question.chars().count() > POLL_QUESTION_MAX_CHARS {
+            return Err(format!(
+                "The question can be at most {} characters.",
+                POLL_QUESTION_MAX_CHARS
+            ));
+        }
+        if options
+            .iter()
+            .any(|option| option.chars().count() > POLL_OPTION_MAX_CHARS)
+        {
+            return Err(format!(
+                "Options can be at most {} characters each.",
+                POLL_OPTION_MAX_CHARS
+            ));
+        }
+
+        Ok(Self {
+            question: question.to_owned(),
+            options,
+        })
+    }
+
+    /// Numbered option lines, as shown while the poll is open.
+    pub fn option_lines(&self) -> String {
+        self.options
+            .iter()
+            .zip(POLL_OPTION_EMOJIS)
+            .map(|(option, emoji)| format!("{} {}", emoji, option))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Option lines with a bar, vote count and share for each, given the
+    /// votes per option in order.
+    pub fn result_lines(&self, votes: &[u64]) -> String {
+        let total: u64 = votes.iter().sum();
+        let mut lines: Vec<String> = self
+            .options
+            .iter()
+            .zip(POLL_OPTION_EMOJIS)
+            .enumerate()
+            .map(|(index, (option, emoji))| {
+                let count = votes.get(index).copied().unwrap_or(0);
+                let filled = (count * RESULT_BAR_WIDTH).checked_div(total).unwrap_or(0);
+                let percent = (count * 100).checked_div(total).unwrap_or(0);
+                format!(
+                    "{} {}\n`{}{}` {} vote{} ({}%)",
+                    emoji,
+                    option,
+                    "█".repeat(filled as usize),
+                    "░".repeat((RESULT_BAR_WIDTH - filled) as usize),
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    percent
+                )
+            })
+            .collect();
+        lines.push(format!("\n**Total votes :** {}", total));
+        lines.join("\n")
+    }
+
+    /// The scheduled close job's payload: this poll and the message it was
+    /// posted as.
+    pub fn to_payload(&self, message_id: u64) -> String {
+        json!({
+            "message_id": message_id,
+            "question": self.question,
+            "options": self.options,
+        })
+        .to_string()
+    }
+
+    /// Reverse of [`Poll::to_payload`].
+    pub fn from_payload(payload: &str) -> Option<(u64, Self)> {
+        let value: Value = serde_json::from_str(payload).ok()?;
+        let message_id = value.get("message_id")?.as_u64()?;
+        let question = value.get("question")?.as_str()?.to_owned();
+        let options = value
+            .get("options")?
+            .as_array()?
+            .iter()
+            .map(|option| option.as_str().map(str::to_owned))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some((message_id, Self { question, options }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_question_and_options() {
+        let poll = Poll::parse("Best snack? | chips |  | cookies ").unwrap();
+
+        assert_eq!(poll.question, "Best snack?");
+        assert_eq!(poll.options, vec!["chips", "cookies"]);
+    }
+
+    #[test]
+    fn rejects_too_few_or_too_many_options() {
+        assert!(Poll::parse("Question? | only one").is_err());
+        assert!(Poll::parse("").is_err());
+
+        let eleven = (1..=11).map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(Poll::parse(&format!("Question? | {}", eleven.join(" | "))).is_err());
+    }
+
+    #[test]
+    fn rejects_long_options() {
+        let long = "x".repeat(POLL_OPTION_MAX_CHARS + 1);
+        assert!(Poll::parse(&format!("Question? | a | {}", long)).is_err());
+    }
+
+    #[test]
+    fn payload_round_trips() {
+        let poll = Poll::parse("Pick | a | b | c").unwrap();
+
+        assert_eq!(
+            Poll::from_payload(&poll.to_payload(42)),
+            Some((42, poll.clone()))
+        );
+        assert_eq!(Poll::from_payload("not json"), None);
+    }
+
+    #[test]
+    fn results_show_counts_and_shares() {
+        let poll = Poll::parse("Pick | a | b").unwrap();
+
+        let lines = poll.result_lines(&[3, 1]);
+
+        assert!(lines.contains("`███████░░░` 3 votes (75%)"));
+        assert!(lines.contains("`██░░░░░░░░` 1 vote (25%)"));
+        assert!(lines.ends_with("**Total votes :** 4"));
+    }
+
+    #[test]
+    fn results_with_no_votes_are_empty_bars() {
+        let poll = Poll::parse("Pick | a | b").unwrap();
+
+        assert!(poll.result_lines(&[]).contains("`░░░░░░░░░░` 0 votes (0%)"));
+    }
+}
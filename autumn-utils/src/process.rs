@@ -0,0 +1,55 @@
+/// Resident memory of the current process in bytes. Only available on Linux.
+pub fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss_bytes(&status)
+}
+
+/// Read the `VmRSS:` line of a `/proc/<pid>/status` file.
+pub fn parse_vm_rss_bytes(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib = line
+        .trim_start_matches("VmRSS:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// Format a byte count like `12.3 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, parse_vm_rss_bytes};
+
+    #[test]
+    fn parses_vm_rss() {
+        let status = "Name:\tautumn\nVmPeak:\t  200000 kB\nVmRSS:\t   51234 kB\nThreads:\t8\n";
+        assert_eq!(parse_vm_rss_bytes(status), Some(51234 * 1024));
+        assert_eq!(parse_vm_rss_bytes("Name:\tautumn\n"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(50 * 1024 * 1024), "50.0 MiB");
+    }
+}
@@ -0,0 +1,146 @@
+//! Cron-like schedules for recurring tasks: `daily HH:MM` or
+//! `weekly <day> HH:MM`, always in UTC.
+
+use std::fmt;
+
+const DAY_SECS: u64 = 86_400;
+const WEEK_SECS: u64 = 7 * DAY_SECS;
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recurrence {
+    Daily {
+        minute_of_day: u32,
+    },
+    /// `weekday` counts from Monday = 0.
+    Weekly {
+        weekday: u32,
+        minute_of_day: u32,
+    },
+}
+
+impl Recurrence {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut tokens = raw.split_whitespace();
+        let recurrence = match tokens.next()?.to_ascii_lowercase().as_str() {
+            "daily" => Self::Daily {
+                minute_of_day: parse_clock(tokens.next()?)?,
+            },
+            "weekly" => {
+                let day = tokens.next()?.to_ascii_lowercase();
+                let weekday = WEEKDAYS
+                    .iter()
+                    .position(|name| day.starts_with(name))
+                    .and_then(|index| u32::try_from(index).ok())?;
+                Self::Weekly {
+                    weekday,
+                    minute_of_day: parse_clock(tokens.next()?)?,
+                }
+            }
+            _ => return None,
+        };
+
+        tokens.next().is_none().then_some(recurrence)
+    }
+
+    /// Seconds between runs.
+    pub fn period_secs(self) -> u64 {
+        match self {
+            Self::Daily { .. } => DAY_SECS,
+            Self::Weekly { .. } => WEEK_SECS,
+        }
+    }
+
+    /// The first run strictly after `now` (unix seconds).
+    pub fn next_after(self, now: u64) -> u64 {
+        let (offset, period) = match self {
+            Self::Daily { minute_of_day } => (u64::from(minute_of_day) * 60, DAY_SECS),
+            // The unix epoch fell on a Thursday, three days after a Monday.
+            Self::Weekly {
+                weekday,
+                minute_of_day,
+            } => (
+                ((u64::from(weekday) + 4) % 7) * DAY_SECS + u64::from(minute_of_day) * 60,
+                WEEK_SECS,
+            ),
+        };
+
+        let start = now - now % period + offset;
+        if start > now { start } else { start + period }
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Daily { minute_of_day } => {
+                write!(
+                    f,
+                    "daily {:02}:{:02}",
+                    minute_of_day / 60,
+                    minute_of_day % 60
+                )
+            }
+            Self::Weekly {
+                weekday,
+                minute_of_day,
+            } => write!(
+                f,
+                "weekly {} {:02}:{:02}",
+                WEEKDAYS[weekday as usize % 7],
+                minute_of_day / 60,
+                minute_of_day % 60
+            ),
+        }
+    }
+}
+
+fn parse_clock(raw: &str) -> Option<u32> {
+    let (hour, minute) = raw.split_once(':')?;
+    let hour: u32 = hour.parse().ok()?;
+    let minute: u32 = minute.parse().ok()?;
+    (hour < 24 && minute < 60).then_some(hour * 60 + minute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recurrence;
+
+    #[test]
+    fn parses_and_round_trips() {
+        let daily = Recurrence::parse("Daily 3:05").unwrap();
+        assert_eq!(daily, Recurrence::Daily { minute_of_day: 185 });
+        assert_eq!(daily.to_string(), "daily 03:05");
+
+        let weekly = Recurrence::parse("weekly monday 09:00").unwrap();
+        assert_eq!(weekly.to_string(), "weekly mon 09:00");
+        assert_eq!(Recurrence::parse(&weekly.to_string()), Some(weekly));
+
+        assert_eq!(Recurrence::parse("daily 24:00"), None);
+        assert_eq!(Recurrence::parse("weekly someday 09:00"), None);
+        assert_eq!(Recurrence::parse("daily 09:00 extra"), None);
+    }
+
+    #[test]
+    fn finds_next_run() {
+        // Thursday 1970-01-01 12:00 UTC.
+        let now = 12 * 3600;
+        let daily = Recurrence::Daily { minute_of_day: 0 };
+        assert_eq!(daily.next_after(now), 86_400);
+        assert_eq!(daily.next_after(86_400), 2 * 86_400);
+
+        // Next Monday 09:00 is 1970-01-05.
+        let monday = Recurrence::Weekly {
+            weekday: 0,
+            minute_of_day: 9 * 60,
+        };
+        assert_eq!(monday.next_after(now), 4 * 86_400 + 9 * 3600);
+
+        // Thursday 13:00 is later the same day.
+        let thursday = Recurrence::Weekly {
+            weekday: 3,
+            minute_of_day: 13 * 60,
+        };
+        assert_eq!(thursday.next_after(now), 13 * 3600);
+    }
+}
@@ -1,8 +1,180 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::parse::{parse_duration_seconds_at, split_first_duration_at};
+
 /// Return the current unix timestamp in seconds.
 pub fn now_unix_secs() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map_or(0, |duration| duration.as_secs())
 }
+
+/// Milliseconds between the unix epoch and the Discord epoch (2015-01-01).
+const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+
+/// The smallest snowflake created at or after `unix_secs`, for id range queries.
+pub fn snowflake_at_unix_secs(unix_secs: u64) -> u64 {
+    unix_secs
+        .saturating_mul(1000)
+        .saturating_sub(DISCORD_EPOCH_MS)
+        << 22
+}
+
+/// Parse an RFC 3339 timestamp like `2024-03-05T12:34:56.789Z` or
+/// `2024-03-05 12:34:56+02:00` into unix seconds. Fractional seconds are
+/// dropped; a missing offset is read as UTC.
+pub fn parse_rfc3339_unix_secs(raw: &str) -> Option<u64> {
+    let value = raw.trim();
+    let (date, rest) = value.split_at_checked(10)?;
+    let rest = rest.strip_prefix(['T', 't', ' '])?;
+
+    let mut date_parts = date.split('-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: i64 = date_parts.next()?.parse().ok()?;
+    let day: i64 = date_parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let (time, offset_secs) = if let Some(time) = rest.strip_suffix(['Z', 'z']) {
+        (time, 0)
+    } else if let Some(index) = rest.rfind(['+', '-']) {
+        let (time, offset) = rest.split_at(index);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = offset[1..].split_once(':')?;
+        let hours: i64 = hours.parse().ok()?;
+        let minutes: i64 = minutes.parse().ok()?;
+        (time, sign * (hours * 3600 + minutes * 60))
+    } else {
+        (rest, 0)
+    };
+
+    let time = time.split_once('.').map_or(time, |(whole, _)| whole);
+    let mut time_parts = time.split(':');
+    let hour: i64 = time_parts.next()?.parse().ok()?;
+    let minute: i64 = time_parts.next()?.parse().ok()?;
+    let second: i64 = time_parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    if hour > 23 || minute > 59 || second > 60 || time_parts.next().is_some() {
+        return None;
+    }
+
+    // Days since the epoch for a proleptic Gregorian date (Howard Hinnant's
+    // days_from_civil).
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    u64::try_from(secs).ok()
+}
+
+/// Split a leading start time off `raw`: `--at HH:MM` (the next time that UTC
+/// clock time comes round) or `in <delay>` (the first duration, e.g. `2h` or
+/// `2 hours`, so the action's own duration can follow). Returns the start as
+/// unix seconds and the remaining text, or `None` when `raw` does not start
+/// with either form.
+pub fn split_deferred_start(raw: &str, now: u64) -> Option<(u64, Option<String>)> {
+    let raw = raw.trim_start();
+    let keyword = raw.split_whitespace().next()?;
+    let after = &raw[keyword.len()..];
+
+    let (run_at, rest) = if keyword.eq_ignore_ascii_case("--at") {
+        let value = after.split_whitespace().next()?;
+        let rest = after.trim_start()[value.len()..].trim();
+        (next_utc_clock_time(value, now)?, rest)
+    } else if keyword.eq_ignore_ascii_case("in") {
+        let (delay, rest) = split_first_duration_at(after, now)?;
+        (
+            now.checked_add(parse_duration_seconds_at(delay, now)?)?,
+            rest,
+        )
+    } else {
+        return None;
+    };
+
+    let rest = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some((run_at, (!rest.is_empty()).then_some(rest)))
+}
+
+/// The first unix second after `now` whose UTC clock reads `HH:MM`.
+fn next_utc_clock_time(raw: &str, now: u64) -> Option<u64> {
+    let (hour, minute) = raw.split_once(':')?;
+    let hour: u64 = hour.parse().ok()?;
+    let minute: u64 = minute.parse().ok()?;
+    if hour > 23 || minute > 59 {
+        return None;
+    }
+
+    let candidate = now - now % 86_400 + hour * 3600 + minute * 60;
+    Some(if candidate > now {
+        candidate
+    } else {
+        candidate + 86_400
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_rfc3339_unix_secs, split_deferred_start};
+
+    #[test]
+    fn splits_deferred_start() {
+        // 1970-01-02 12:00:00 UTC.
+        let now = 86_400 + 12 * 3600;
+        assert_eq!(
+            split_deferred_start("--at 22:00 raiding", now),
+            Some((86_400 + 22 * 3600, Some("raiding".to_owned())))
+        );
+        assert_eq!(
+            split_deferred_start("--at 09:30", now),
+            Some((2 * 86_400 + 9 * 3600 + 30 * 60, None))
+        );
+        assert_eq!(
+            split_deferred_start("in 2h 1d spam", now),
+            Some((now + 7200, Some("1d spam".to_owned())))
+        );
+        assert_eq!(split_deferred_start("--at 25:00", now), None);
+        assert_eq!(split_deferred_start("in the morning", now), None);
+        assert_eq!(split_deferred_start("1d spam", now), None);
+    }
+
+    #[test]
+    fn splits_spelled_out_deferred_delays() {
+        let now = 86_400 + 12 * 3600;
+        assert_eq!(
+            split_deferred_start("in 2 hours", now),
+            Some((now + 7200, None))
+        );
+        assert_eq!(
+            split_deferred_start("in 1 week 1d spam", now),
+            Some((now + 7 * 86_400, Some("1d spam".to_owned())))
+        );
+        assert_eq!(
+            split_deferred_start("in 30 minutes 2 hours raiding", now),
+            Some((now + 1800, Some("2 hours raiding".to_owned())))
+        );
+    }
+
+    #[test]
+    fn parses_rfc3339_timestamps() {
+        assert_eq!(parse_rfc3339_unix_secs("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_rfc3339_unix_secs("2024-03-05T12:34:56.789Z"),
+            Some(1_709_642_096)
+        );
+        assert_eq!(
+            parse_rfc3339_unix_secs("2024-03-05 14:34:56+02:00"),
+            Some(1_709_642_096)
+        );
+        assert_eq!(
+            parse_rfc3339_unix_secs("2024-03-05T12:34:56"),
+            Some(1_709_642_096)
+        );
+        assert_eq!(parse_rfc3339_unix_secs("2024-13-05T12:34:56Z"), None);
+        assert_eq!(parse_rfc3339_unix_secs("yesterday"), None);
+    }
+}
@@ -0,0 +1,86 @@
+/// One deleted message captured for a transcript.
+#[derive(Clone, Debug)]
+pub struct TranscriptMessage {
+    pub author_id: u64,
+    pub author_name: String,
+    /// Set when a transcript spans several channels.
+    pub channel_id: Option<u64>,
+    /// Pre-formatted timestamp, e.g. RFC 3339.
+    pub timestamp: String,
+    pub content: String,
+    pub attachments: Vec<String>,
+}
+
+/// Render messages as a plain-text transcript, one block per message:
+///
+/// ```text
+/// [2026-03-11T10:00:00Z] name (123) in channel 456
+///   line one
+///   line two
+///   [attachment] https://...
+/// ```
+pub fn render_transcript(header: &str, messages: &[TranscriptMessage]) -> String {
+    let mut output = String::new();
+    output.push_str(header);
+    output.push_str(&format!("\nMessages: {}\n", messages.len()));
+
+    for message in messages {
+        output.push_str(&format!(
+            "\n[{}] {} ({})",
+            message.timestamp, message.author_name, message.author_id
+        ));
+        if let Some(channel_id) = message.channel_id {
+            output.push_str(&format!(" in channel {}", channel_id));
+        }
+        output.push('\n');
+
+        let content = message.content.trim();
+        if content.is_empty() && message.attachments.is_empty() {
+            output.push_str("  (no text content)\n");
+        }
+        for line in content.lines() {
+            output.push_str("  ");
+            output.push_str(line);
+            output.push('\n');
+        }
+        for attachment in &message.attachments {
+            output.push_str(&format!("  [attachment] {}\n", attachment));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TranscriptMessage, render_transcript};
+
+    #[test]
+    fn renders_messages_in_order() {
+        let messages = vec![
+            TranscriptMessage {
+                author_id: 1,
+                author_name: "alice".to_owned(),
+                channel_id: None,
+                timestamp: "2026-03-11T10:00:00Z".to_owned(),
+                content: "hello\nworld".to_owned(),
+                attachments: Vec::new(),
+            },
+            TranscriptMessage {
+                author_id: 2,
+                author_name: "bob".to_owned(),
+                channel_id: Some(9),
+                timestamp: "2026-03-11T10:01:00Z".to_owned(),
+                content: String::new(),
+                attachments: vec!["cat.png (https://cdn/cat.png)".to_owned()],
+            },
+        ];
+
+        assert_eq!(
+            render_transcript("Purge in #general", &messages),
+            "Purge in #general\nMessages: 2\n\
+             \n[2026-03-11T10:00:00Z] alice (1)\n  hello\n  world\n\
+             \n[2026-03-11T10:01:00Z] bob (2) in channel 9\n  [attachment] cat.png (https://cdn/cat.png)\n"
+        );
+    }
+}